@@ -0,0 +1,315 @@
+use fyrox::{
+    core::{
+        algebra::Vector2,
+        color::Color,
+        math::{round_to_step, triangulator, Rect},
+        pool::Handle,
+    },
+    gui::{
+        brush::Brush,
+        define_constructor, define_widget_deref,
+        draw::{CommandTexture, Draw, DrawingContext},
+        message::{MessageDirection, MouseButton, UiMessage},
+        widget::{Widget, WidgetBuilder, WidgetMessage},
+        BuildContext, Control, UiNode, UserInterface,
+    },
+};
+use std::{
+    any::{Any, TypeId},
+    ops::{Deref, DerefMut},
+};
+
+const POINT_RADIUS: f32 = 5.0;
+const GRID_STEP: f32 = 0.25;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlendSpaceFieldMessage {
+    /// Sets new positions of the points of the field.
+    Points(Vec<Vector2<f32>>),
+    /// Sets new sampling point, it is used only for visualization.
+    SamplingPoint(Option<Vector2<f32>>),
+    /// Occurs when user has finished dragging a point.
+    MovePoint {
+        index: usize,
+        position: Vector2<f32>,
+    },
+}
+
+impl BlendSpaceFieldMessage {
+    define_constructor!(BlendSpaceFieldMessage:Points => fn points(Vec<Vector2<f32>>), layout: false);
+    define_constructor!(BlendSpaceFieldMessage:SamplingPoint => fn sampling_point(Option<Vector2<f32>>), layout: false);
+    define_constructor!(BlendSpaceFieldMessage:MovePoint => fn move_point(index: usize, position: Vector2<f32>), layout: false);
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct DragContext {
+    index: usize,
+    position: Vector2<f32>,
+}
+
+/// A widget that shows points of a blend space with its triangulation and allows to move the points
+/// by dragging them with the mouse.
+#[derive(Clone)]
+pub struct BlendSpaceField {
+    widget: Widget,
+    points: Vec<Vector2<f32>>,
+    sampling_point: Option<Vector2<f32>>,
+    triangles: Vec<[usize; 3]>,
+    drag_context: Option<DragContext>,
+}
+
+define_widget_deref!(BlendSpaceField);
+
+impl BlendSpaceField {
+    fn point_position(&self, index: usize) -> Vector2<f32> {
+        match self.drag_context {
+            Some(ref drag_context) if drag_context.index == index => drag_context.position,
+            _ => self.points[index],
+        }
+    }
+
+    /// Returns bounds of the field in blend space units. Bounds are calculated using initial
+    /// positions of the points, so the field won't "jump" when a point is being dragged.
+    fn field_bounds(&self) -> Rect<f32> {
+        let mut min = Vector2::new(-1.0, -1.0);
+        let mut max = Vector2::new(1.0, 1.0);
+        for point in self.points.iter() {
+            min = min.inf(point);
+            max = max.sup(point);
+        }
+        let margin = (max - min).scale(0.1);
+        min -= margin;
+        max += margin;
+        Rect::new(min.x, min.y, max.x - min.x, max.y - min.y)
+    }
+
+    fn field_to_local(&self, point: Vector2<f32>) -> Vector2<f32> {
+        let bounds = self.field_bounds();
+        let size = self.actual_local_size();
+        Vector2::new(
+            (point.x - bounds.x()) / bounds.w() * size.x,
+            // Y axis of the field points up.
+            size.y - (point.y - bounds.y()) / bounds.h() * size.y,
+        )
+    }
+
+    fn local_to_field(&self, point: Vector2<f32>) -> Vector2<f32> {
+        let bounds = self.field_bounds();
+        let size = self.actual_local_size();
+        Vector2::new(
+            bounds.x() + point.x / size.x.max(f32::EPSILON) * bounds.w(),
+            bounds.y() + (size.y - point.y) / size.y.max(f32::EPSILON) * bounds.h(),
+        )
+    }
+
+    fn pick_point(&self, local_position: Vector2<f32>) -> Option<usize> {
+        self.points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (i, (self.field_to_local(*p) - local_position).norm()))
+            .filter(|(_, distance)| *distance <= POINT_RADIUS * 2.0)
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+            .map(|(i, _)| i)
+    }
+}
+
+impl Control for BlendSpaceField {
+    fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn draw(&self, ctx: &mut DrawingContext) {
+        let bounds = self.bounding_rect();
+        ctx.push_rect_filled(&bounds, None);
+        ctx.commit(
+            self.clip_bounds(),
+            self.widget.background(),
+            CommandTexture::None,
+            None,
+        );
+
+        // Grid.
+        let field_bounds = self.field_bounds();
+        let mut x = round_to_step(field_bounds.x(), GRID_STEP);
+        while x <= field_bounds.x() + field_bounds.w() {
+            ctx.push_line(
+                self.field_to_local(Vector2::new(x, field_bounds.y())),
+                self.field_to_local(Vector2::new(x, field_bounds.y() + field_bounds.h())),
+                1.0,
+            );
+            x += GRID_STEP;
+        }
+        let mut y = round_to_step(field_bounds.y(), GRID_STEP);
+        while y <= field_bounds.y() + field_bounds.h() {
+            ctx.push_line(
+                self.field_to_local(Vector2::new(field_bounds.x(), y)),
+                self.field_to_local(Vector2::new(field_bounds.x() + field_bounds.w(), y)),
+                1.0,
+            );
+            y += GRID_STEP;
+        }
+        ctx.commit(
+            self.clip_bounds(),
+            Brush::Solid(Color::opaque(50, 50, 50)),
+            CommandTexture::None,
+            None,
+        );
+
+        // Triangles.
+        for triangle in self.triangles.iter() {
+            for (a, b) in [
+                (triangle[0], triangle[1]),
+                (triangle[1], triangle[2]),
+                (triangle[2], triangle[0]),
+            ] {
+                ctx.push_line(
+                    self.field_to_local(self.point_position(a)),
+                    self.field_to_local(self.point_position(b)),
+                    2.0,
+                );
+            }
+        }
+        ctx.commit(
+            self.clip_bounds(),
+            Brush::Solid(Color::opaque(100, 100, 100)),
+            CommandTexture::None,
+            None,
+        );
+
+        // Points.
+        for index in 0..self.points.len() {
+            ctx.push_circle(
+                self.field_to_local(self.point_position(index)),
+                POINT_RADIUS,
+                16,
+                Color::WHITE,
+            );
+        }
+        ctx.commit(
+            self.clip_bounds(),
+            self.widget.foreground(),
+            CommandTexture::None,
+            None,
+        );
+
+        if let Some(sampling_point) = self.sampling_point {
+            let center = self.field_to_local(sampling_point);
+            let size = POINT_RADIUS;
+            ctx.push_line(
+                center - Vector2::new(size, size),
+                center + Vector2::new(size, size),
+                2.0,
+            );
+            ctx.push_line(
+                center - Vector2::new(size, -size),
+                center + Vector2::new(size, -size),
+                2.0,
+            );
+            ctx.commit(
+                self.clip_bounds(),
+                Brush::Solid(Color::opaque(255, 140, 0)),
+                CommandTexture::None,
+                None,
+            );
+        }
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let Some(msg) = message.data::<WidgetMessage>() {
+            if message.destination() != self.handle() {
+                return;
+            }
+
+            match msg {
+                WidgetMessage::MouseDown { pos, button } => {
+                    if *button == MouseButton::Left {
+                        if let Some(index) = self.pick_point(self.screen_to_local(*pos)) {
+                            self.drag_context = Some(DragContext {
+                                index,
+                                position: self.points[index],
+                            });
+
+                            ui.capture_mouse(self.handle());
+
+                            message.set_handled(true);
+                        }
+                    }
+                }
+                WidgetMessage::MouseMove { pos, .. } => {
+                    let position = self.local_to_field(self.screen_to_local(*pos));
+                    if let Some(drag_context) = self.drag_context.as_mut() {
+                        drag_context.position = position;
+                    }
+                }
+                WidgetMessage::MouseUp { button, .. } => {
+                    if *button == MouseButton::Left {
+                        if let Some(drag_context) = self.drag_context.take() {
+                            ui.release_mouse_capture();
+
+                            if drag_context.position != self.points[drag_context.index] {
+                                ui.send_message(BlendSpaceFieldMessage::move_point(
+                                    self.handle(),
+                                    MessageDirection::FromWidget,
+                                    drag_context.index,
+                                    drag_context.position,
+                                ));
+                            }
+
+                            message.set_handled(true);
+                        }
+                    }
+                }
+                _ => (),
+            }
+        } else if let Some(msg) = message.data::<BlendSpaceFieldMessage>() {
+            if message.destination() == self.handle()
+                && message.direction() == MessageDirection::ToWidget
+            {
+                match msg {
+                    BlendSpaceFieldMessage::Points(points) => {
+                        if &self.points != points {
+                            self.points = points.clone();
+                            self.triangles = triangulator::triangulate_points(&self.points);
+                        }
+                    }
+                    BlendSpaceFieldMessage::SamplingPoint(sampling_point) => {
+                        self.sampling_point = *sampling_point;
+                    }
+                    BlendSpaceFieldMessage::MovePoint { .. } => {}
+                }
+            }
+        }
+    }
+}
+
+pub struct BlendSpaceFieldBuilder {
+    widget_builder: WidgetBuilder,
+}
+
+impl BlendSpaceFieldBuilder {
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self { widget_builder }
+    }
+
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let field = BlendSpaceField {
+            widget: self
+                .widget_builder
+                .with_background(Brush::Solid(Color::opaque(30, 30, 30)))
+                .with_foreground(Brush::Solid(Color::opaque(220, 220, 220)))
+                .build(),
+            points: Default::default(),
+            sampling_point: None,
+            triangles: Default::default(),
+            drag_context: None,
+        };
+
+        ctx.add_node(UiNode::new(field))
+    }
+}
@@ -5,28 +5,36 @@ use crate::{
 use fyrox::{
     animation::machine::node::{
         blend::{BlendPoseDefinition, IndexedBlendInputDefinition},
+        blend_space::BlendSpacePointDefinition,
         PoseNodeDefinition,
     },
-    core::pool::Handle,
+    core::{algebra::Vector2, pool::Handle},
 };
 
 define_push_element_to_collection_command!(AddInputCommand<Handle<PoseNodeDefinition>, IndexedBlendInputDefinition>(self, context) {
-    match &mut context.resource.absm_definition.nodes[self.handle] {
+    match &mut context.layer_mut().nodes[self.handle] {
         PoseNodeDefinition::BlendAnimationsByIndex(definition) => &mut definition.inputs,
         _ => unreachable!(),
     }
 });
 
 define_push_element_to_collection_command!(AddPoseSourceCommand<Handle<PoseNodeDefinition>, BlendPoseDefinition>(self, context) {
-    match &mut context.resource.absm_definition.nodes[self.handle] {
+    match &mut context.layer_mut().nodes[self.handle] {
         PoseNodeDefinition::BlendAnimations(definition) => &mut definition.pose_sources,
         _ => unreachable!(),
     }
 });
 
+define_push_element_to_collection_command!(AddBlendSpacePointCommand<Handle<PoseNodeDefinition>, BlendSpacePointDefinition>(self, context) {
+    match &mut context.layer_mut().nodes[self.handle] {
+        PoseNodeDefinition::BlendSpace(definition) => &mut definition.points,
+        _ => unreachable!(),
+    }
+});
+
 define_set_collection_element_command!(
     SetBlendAnimationByIndexInputPoseSourceCommand<Handle<PoseNodeDefinition>, Handle<PoseNodeDefinition>>(self, context) {
-        match context.resource.absm_definition.nodes[self.handle] {
+        match context.layer_mut().nodes[self.handle] {
             PoseNodeDefinition::BlendAnimationsByIndex(ref mut definition) => {
                 &mut definition.inputs[self.index].pose_source
             }
@@ -37,7 +45,7 @@ define_set_collection_element_command!(
 
 define_set_collection_element_command!(
     SetBlendAnimationsPoseSourceCommand<Handle<PoseNodeDefinition>, Handle<PoseNodeDefinition>>(self, context) {
-        match context.resource.absm_definition.nodes[self.handle] {
+        match context.layer_mut().nodes[self.handle] {
             PoseNodeDefinition::BlendAnimations(ref mut definition) => {
                 &mut definition.pose_sources[self.index].pose_source
             }
@@ -45,3 +53,25 @@ define_set_collection_element_command!(
         }
     }
 );
+
+define_set_collection_element_command!(
+    SetBlendSpacePointPoseSourceCommand<Handle<PoseNodeDefinition>, Handle<PoseNodeDefinition>>(self, context) {
+        match context.layer_mut().nodes[self.handle] {
+            PoseNodeDefinition::BlendSpace(ref mut definition) => {
+                &mut definition.points[self.index].pose_source
+            }
+            _ => unreachable!(),
+        }
+    }
+);
+
+define_set_collection_element_command!(
+    SetBlendSpacePointPositionCommand<Handle<PoseNodeDefinition>, Vector2<f32>>(self, context) {
+        match context.layer_mut().nodes[self.handle] {
+            PoseNodeDefinition::BlendSpace(ref mut definition) => {
+                &mut definition.points[self.index].position
+            }
+            _ => unreachable!(),
+        }
+    }
+);
@@ -0,0 +1,108 @@
+use crate::{
+    absm::{
+        command::{AbsmCommand, AbsmCommandTrait, AbsmEditorContext},
+        SelectedEntity,
+    },
+    define_universal_commands,
+};
+use fyrox::{animation::machine::MachineLayerDefinition, core::reflect::ResolvePath};
+
+define_universal_commands!(
+    make_set_layer_property_command,
+    AbsmCommandTrait,
+    AbsmCommand,
+    AbsmEditorContext,
+    usize,
+    ctx,
+    handle,
+    self,
+    { &mut ctx.resource.absm_definition.layers[self.handle] }
+);
+
+/// Switches the layer that is being edited. Selection is swapped too, because selected entities
+/// belong to a particular layer.
+#[derive(Debug)]
+pub struct SetCurrentLayerCommand {
+    pub layer: usize,
+    pub selection: Vec<SelectedEntity>,
+}
+
+impl SetCurrentLayerCommand {
+    fn swap(&mut self, context: &mut AbsmEditorContext) {
+        std::mem::swap(&mut self.layer, context.current_layer);
+        std::mem::swap(&mut self.selection, context.selection);
+    }
+}
+
+impl AbsmCommandTrait for SetCurrentLayerCommand {
+    fn name(&mut self, _context: &AbsmEditorContext) -> String {
+        "Set Current Layer".to_string()
+    }
+
+    fn execute(&mut self, context: &mut AbsmEditorContext) {
+        self.swap(context)
+    }
+
+    fn revert(&mut self, context: &mut AbsmEditorContext) {
+        self.swap(context)
+    }
+}
+
+#[derive(Debug)]
+pub struct AddLayerCommand {
+    pub layer: Option<MachineLayerDefinition>,
+}
+
+impl AddLayerCommand {
+    pub fn new(layer: MachineLayerDefinition) -> Self {
+        Self { layer: Some(layer) }
+    }
+}
+
+impl AbsmCommandTrait for AddLayerCommand {
+    fn name(&mut self, _context: &AbsmEditorContext) -> String {
+        "Add Layer".to_string()
+    }
+
+    fn execute(&mut self, context: &mut AbsmEditorContext) {
+        context
+            .resource
+            .absm_definition
+            .layers
+            .push(self.layer.take().unwrap());
+    }
+
+    fn revert(&mut self, context: &mut AbsmEditorContext) {
+        self.layer = context.resource.absm_definition.layers.pop();
+    }
+}
+
+#[derive(Debug)]
+pub struct RemoveLayerCommand {
+    pub index: usize,
+    pub layer: Option<MachineLayerDefinition>,
+}
+
+impl RemoveLayerCommand {
+    pub fn new(index: usize) -> Self {
+        Self { index, layer: None }
+    }
+}
+
+impl AbsmCommandTrait for RemoveLayerCommand {
+    fn name(&mut self, _context: &AbsmEditorContext) -> String {
+        "Remove Layer".to_string()
+    }
+
+    fn execute(&mut self, context: &mut AbsmEditorContext) {
+        self.layer = Some(context.resource.absm_definition.layers.remove(self.index));
+    }
+
+    fn revert(&mut self, context: &mut AbsmEditorContext) {
+        context
+            .resource
+            .absm_definition
+            .layers
+            .insert(self.index, self.layer.take().unwrap());
+    }
+}
@@ -2,7 +2,7 @@ use crate::{absm::SelectedEntity, define_command_stack};
 use fyrox::{
     animation::machine::{
         node::PoseNodeDefinition, state::StateDefinition, transition::TransitionDefinition,
-        MachineDefinition, MachineInstantiationError,
+        MachineInstantiationError, MachineLayerDefinition,
     },
     asset::ResourceDataRef,
     core::{
@@ -16,6 +16,7 @@ use std::{
     ops::{Deref, DerefMut},
 };
 
+pub mod layer;
 pub mod parameter;
 pub mod pose;
 pub mod state;
@@ -25,6 +26,15 @@ pub mod transition;
 pub struct AbsmEditorContext<'a> {
     pub selection: &'a mut Vec<SelectedEntity>,
     pub resource: ResourceDataRef<'a, AbsmResourceState, MachineInstantiationError>,
+    pub current_layer: &'a mut usize,
+}
+
+impl<'a> AbsmEditorContext<'a> {
+    /// Returns a layer that is currently being edited. Every command that modifies states,
+    /// transitions or pose nodes works with this layer.
+    pub fn layer_mut(&mut self) -> &mut MachineLayerDefinition {
+        &mut self.resource.absm_definition.layers[*self.current_layer]
+    }
 }
 
 pub mod blend;
@@ -138,16 +148,12 @@ macro_rules! define_spawn_command {
                 match std::mem::replace(self, $name::Unknown) {
                     $name::NonExecuted { state } => {
                         *self = $name::Executed {
-                            handle: context.resource.absm_definition.$container.spawn(state),
+                            handle: context.layer_mut().$container.spawn(state),
                         };
                     }
                     $name::Reverted { ticket, state } => {
                         *self = $name::Executed {
-                            handle: context
-                                .resource
-                                .absm_definition
-                                .$container
-                                .put_back(ticket, state),
+                            handle: context.layer_mut().$container.put_back(ticket, state),
                         }
                     }
                     _ => unreachable!(),
@@ -157,11 +163,7 @@ macro_rules! define_spawn_command {
             fn revert(&mut self, context: &mut AbsmEditorContext) {
                 match std::mem::replace(self, $name::Unknown) {
                     $name::Executed { handle } => {
-                        let (ticket, state) = context
-                            .resource
-                            .absm_definition
-                            .$container
-                            .take_reserve(handle);
+                        let (ticket, state) = context.layer_mut().$container.take_reserve(handle);
                         *self = $name::Reverted { ticket, state }
                     }
                     _ => unreachable!(),
@@ -170,11 +172,7 @@ macro_rules! define_spawn_command {
 
             fn finalize(&mut self, context: &mut AbsmEditorContext) {
                 if let $name::Reverted { ticket, .. } = std::mem::replace(self, $name::Unknown) {
-                    context
-                        .resource
-                        .absm_definition
-                        .$container
-                        .forget_ticket(ticket)
+                    context.layer_mut().$container.forget_ticket(ticket)
                 }
             }
         }
@@ -213,13 +211,13 @@ impl AbsmCommandTrait for AddStateCommand {
     fn execute(&mut self, context: &mut AbsmEditorContext) {
         match std::mem::replace(self, AddStateCommand::Unknown) {
             AddStateCommand::NonExecuted { state } => {
-                let handle = context.resource.absm_definition.states.spawn(state);
+                let handle = context.layer_mut().states.spawn(state);
 
-                let prev_entry_state = context.resource.absm_definition.entry_state;
+                let prev_entry_state = context.layer_mut().entry_state;
 
                 // Set entry state if it wasn't set yet.
-                if context.resource.absm_definition.entry_state.is_none() {
-                    context.resource.absm_definition.entry_state = handle;
+                if context.layer_mut().entry_state.is_none() {
+                    context.layer_mut().entry_state = handle;
                 }
 
                 *self = AddStateCommand::Executed {
@@ -228,17 +226,13 @@ impl AbsmCommandTrait for AddStateCommand {
                 };
             }
             AddStateCommand::Reverted { ticket, state } => {
-                let handle = context
-                    .resource
-                    .absm_definition
-                    .states
-                    .put_back(ticket, state);
+                let handle = context.layer_mut().states.put_back(ticket, state);
 
-                let prev_entry_state = context.resource.absm_definition.entry_state;
+                let prev_entry_state = context.layer_mut().entry_state;
 
                 // Set entry state if it wasn't set yet.
-                if context.resource.absm_definition.entry_state.is_none() {
-                    context.resource.absm_definition.entry_state = handle;
+                if context.layer_mut().entry_state.is_none() {
+                    context.layer_mut().entry_state = handle;
                 }
 
                 *self = AddStateCommand::Executed {
@@ -256,9 +250,9 @@ impl AbsmCommandTrait for AddStateCommand {
                 handle,
                 prev_entry_state,
             } => {
-                context.resource.absm_definition.entry_state = prev_entry_state;
+                context.layer_mut().entry_state = prev_entry_state;
 
-                let (ticket, state) = context.resource.absm_definition.states.take_reserve(handle);
+                let (ticket, state) = context.layer_mut().states.take_reserve(handle);
                 *self = AddStateCommand::Reverted { ticket, state }
             }
             _ => unreachable!(),
@@ -269,11 +263,7 @@ impl AbsmCommandTrait for AddStateCommand {
         if let AddStateCommand::Reverted { ticket, .. } =
             std::mem::replace(self, AddStateCommand::Unknown)
         {
-            context
-                .resource
-                .absm_definition
-                .states
-                .forget_ticket(ticket)
+            context.layer_mut().states.forget_ticket(ticket)
         }
     }
 }
@@ -310,9 +300,9 @@ impl AbsmCommandTrait for AddPoseNodeCommand {
             AddPoseNodeCommand::NonExecuted { node } => {
                 let parent_state = node.parent_state;
 
-                let handle = context.resource.absm_definition.nodes.spawn(node);
+                let handle = context.layer_mut().nodes.spawn(node);
 
-                let parent_state_ref = &mut context.resource.absm_definition.states[parent_state];
+                let parent_state_ref = &mut context.layer_mut().states[parent_state];
                 let prev_root_node = parent_state_ref.root;
                 if parent_state_ref.root.is_none() {
                     parent_state_ref.root = handle;
@@ -326,13 +316,9 @@ impl AbsmCommandTrait for AddPoseNodeCommand {
             AddPoseNodeCommand::Reverted { ticket, node } => {
                 let parent_state = node.parent_state;
 
-                let handle = context
-                    .resource
-                    .absm_definition
-                    .nodes
-                    .put_back(ticket, node);
+                let handle = context.layer_mut().nodes.put_back(ticket, node);
 
-                let parent_state_ref = &mut context.resource.absm_definition.states[parent_state];
+                let parent_state_ref = &mut context.layer_mut().states[parent_state];
                 let prev_root_node = parent_state_ref.root;
                 if parent_state_ref.root.is_none() {
                     parent_state_ref.root = handle;
@@ -353,9 +339,9 @@ impl AbsmCommandTrait for AddPoseNodeCommand {
                 handle,
                 prev_root_node,
             } => {
-                let (ticket, node) = context.resource.absm_definition.nodes.take_reserve(handle);
+                let (ticket, node) = context.layer_mut().nodes.take_reserve(handle);
 
-                context.resource.absm_definition.states[node.parent_state].root = prev_root_node;
+                context.layer_mut().states[node.parent_state].root = prev_root_node;
 
                 *self = AddPoseNodeCommand::Reverted { ticket, node }
             }
@@ -367,7 +353,7 @@ impl AbsmCommandTrait for AddPoseNodeCommand {
         if let AddPoseNodeCommand::Reverted { ticket, .. } =
             std::mem::replace(self, AddPoseNodeCommand::Unknown)
         {
-            context.resource.absm_definition.nodes.forget_ticket(ticket)
+            context.layer_mut().nodes.forget_ticket(ticket)
         }
     }
 }
@@ -400,7 +386,11 @@ macro_rules! define_move_command {
                 position
             }
 
-            fn set_position(&self, definition: &mut MachineDefinition, position: Vector2<f32>) {
+            fn set_position(
+                &self,
+                definition: &mut MachineLayerDefinition,
+                position: Vector2<f32>,
+            ) {
                 definition.$container[self.node].position = position;
             }
         }
@@ -412,12 +402,12 @@ macro_rules! define_move_command {
 
             fn execute(&mut self, context: &mut AbsmEditorContext) {
                 let position = self.swap();
-                self.set_position(&mut context.resource.absm_definition, position);
+                self.set_position(context.layer_mut(), position);
             }
 
             fn revert(&mut self, context: &mut AbsmEditorContext) {
                 let position = self.swap();
-                self.set_position(&mut context.resource.absm_definition, position);
+                self.set_position(context.layer_mut(), position);
             }
         }
     };
@@ -478,11 +468,7 @@ macro_rules! define_free_command {
             fn execute(&mut self, context: &mut AbsmEditorContext) {
                 match std::mem::replace(self, Self::Unknown) {
                     Self::NonExecuted(state) | Self::Reverted(state) => {
-                        let (ticket, state) = context
-                            .resource
-                            .absm_definition
-                            .$container
-                            .take_reserve(state);
+                        let (ticket, state) = context.layer_mut().$container.take_reserve(state);
                         *self = Self::Executed { state, ticket }
                     }
                     _ => unreachable!(),
@@ -492,13 +478,8 @@ macro_rules! define_free_command {
             fn revert(&mut self, context: &mut AbsmEditorContext) {
                 match std::mem::replace(self, Self::Unknown) {
                     Self::Executed { state, ticket } => {
-                        *self = Self::Reverted(
-                            context
-                                .resource
-                                .absm_definition
-                                .$container
-                                .put_back(ticket, state),
-                        );
+                        *self =
+                            Self::Reverted(context.layer_mut().$container.put_back(ticket, state));
                     }
                     _ => unreachable!(),
                 }
@@ -507,11 +488,7 @@ macro_rules! define_free_command {
             fn finalize(&mut self, context: &mut AbsmEditorContext) {
                 match std::mem::replace(self, Self::Unknown) {
                     Self::Executed { ticket, .. } => {
-                        context
-                            .resource
-                            .absm_definition
-                            .$container
-                            .forget_ticket(ticket);
+                        context.layer_mut().$container.forget_ticket(ticket);
                     }
                     _ => (),
                 }
@@ -642,10 +619,7 @@ pub struct SetMachineEntryStateCommand {
 
 impl SetMachineEntryStateCommand {
     fn swap(&mut self, context: &mut AbsmEditorContext) {
-        std::mem::swap(
-            &mut context.resource.absm_definition.entry_state,
-            &mut self.entry,
-        );
+        std::mem::swap(&mut context.layer_mut().entry_state, &mut self.entry);
     }
 }
 
@@ -700,5 +674,5 @@ macro_rules! define_absm_swap_command {
 }
 
 define_absm_swap_command!(SetStateRootPoseCommand<Handle<StateDefinition>, Handle<PoseNodeDefinition>>[](self, context) {
-    &mut context.resource.absm_definition.states[self.handle].root
+    &mut context.layer_mut().states[self.handle].root
 });
@@ -16,5 +16,5 @@ define_universal_commands!(
     ctx,
     handle,
     self,
-    { &mut ctx.resource.absm_definition.nodes[self.handle] }
+    { &mut ctx.layer_mut().nodes[self.handle] }
 );
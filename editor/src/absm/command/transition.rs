@@ -16,5 +16,5 @@ define_universal_commands!(
     ctx,
     handle,
     self,
-    { &mut ctx.resource.absm_definition.transitions[self.handle] }
+    { &mut ctx.layer_mut().transitions[self.handle] }
 );
@@ -1,14 +1,16 @@
 use crate::{
     absm::{
+        blend_space::{BlendSpaceFieldBuilder, BlendSpaceFieldMessage},
         command::{
-            pose::make_set_pose_property_command, state::make_set_state_property_command,
+            blend::SetBlendSpacePointPositionCommand, pose::make_set_pose_property_command,
+            state::make_set_state_property_command,
             transition::make_set_transition_property_command, CommandGroup,
         },
         message::MessageSender,
         AbsmDataModel, SelectedEntity,
     },
     inspector::editors::make_property_editors_container,
-    Message, MessageDirection, MSG_SYNC_FLAG,
+    send_sync_message, Message, MessageDirection, MSG_SYNC_FLAG,
 };
 use fyrox::{
     animation::machine::{
@@ -17,13 +19,15 @@ use fyrox::{
                 BlendAnimationsByIndexDefinition, BlendAnimationsDefinition, BlendPoseDefinition,
                 IndexedBlendInputDefinition,
             },
-            play::{PlayAnimationDefinition, TimeSlice},
-            BasePoseNodeDefinition,
+            blend_space::{BlendSpaceDefinition, BlendSpacePointDefinition},
+            play::{PlayAnimationDefinition, RootMotionSettingsDefinition, TimeSlice},
+            BasePoseNodeDefinition, PoseNodeDefinition,
         },
-        MachineDefinition, PoseWeight,
+        MachineLayerDefinition, Parameter, PoseWeight,
     },
     core::{inspect::Inspect, pool::Handle},
     gui::{
+        grid::{Column, GridBuilder, Row},
         inspector::{
             editors::{
                 collection::VecCollectionPropertyEditorDefinition,
@@ -34,7 +38,7 @@ use fyrox::{
             InspectorBuilder, InspectorContext, InspectorMessage,
         },
         message::UiMessage,
-        widget::WidgetBuilder,
+        widget::{WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowTitle},
         BuildContext, UiNode, UserInterface,
     },
@@ -45,18 +49,36 @@ use std::{rc::Rc, sync::mpsc::Sender};
 pub struct Inspector {
     pub window: Handle<UiNode>,
     inspector: Handle<UiNode>,
+    blend_space_field: Handle<UiNode>,
     selection: Vec<SelectedEntity>,
     property_editors: Rc<PropertyEditorDefinitionContainer>,
 }
 
 impl Inspector {
     pub fn new(ctx: &mut BuildContext, sender: Sender<Message>) -> Self {
-        let inspector = InspectorBuilder::new(WidgetBuilder::new()).build(ctx);
+        let inspector = InspectorBuilder::new(WidgetBuilder::new().on_row(1)).build(ctx);
+        let blend_space_field = BlendSpaceFieldBuilder::new(
+            WidgetBuilder::new()
+                .on_row(0)
+                .with_height(200.0)
+                .with_visibility(false),
+        )
+        .build(ctx);
         let window = WindowBuilder::new(WidgetBuilder::new())
             .can_close(false)
             .can_minimize(false)
             .with_title(WindowTitle::text("Inspector"))
-            .with_content(inspector)
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(blend_space_field)
+                        .with_child(inspector),
+                )
+                .add_row(Row::auto())
+                .add_row(Row::stretch())
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
             .build(ctx);
 
         let property_editors = make_property_editors_container(sender);
@@ -75,6 +97,11 @@ impl Inspector {
         property_editors.insert(InspectablePropertyEditorDefinition::<PoseWeight>::new());
         property_editors.insert(InspectablePropertyEditorDefinition::<TimeSlice>::new());
         property_editors.insert(EnumPropertyEditorDefinition::<TimeSlice>::new_optional());
+        property_editors.insert(InspectablePropertyEditorDefinition::<
+            RootMotionSettingsDefinition,
+        >::new());
+        property_editors
+            .insert(EnumPropertyEditorDefinition::<RootMotionSettingsDefinition>::new_optional());
         property_editors.insert(InspectablePropertyEditorDefinition::<TimeSlice>::new());
         property_editors.insert(InspectablePropertyEditorDefinition::<
             BlendAnimationsByIndexDefinition,
@@ -84,10 +111,18 @@ impl Inspector {
         >::new());
         property_editors
             .insert(InspectablePropertyEditorDefinition::<PlayAnimationDefinition>::new());
+        property_editors.insert(InspectablePropertyEditorDefinition::<BlendSpaceDefinition>::new());
+        property_editors.insert(InspectablePropertyEditorDefinition::<
+            BlendSpacePointDefinition,
+        >::new());
+        property_editors.insert(VecCollectionPropertyEditorDefinition::<
+            BlendSpacePointDefinition,
+        >::new());
 
         Self {
             window,
             inspector,
+            blend_space_field,
             selection: Default::default(),
             property_editors: Rc::new(property_editors),
         }
@@ -95,7 +130,7 @@ impl Inspector {
 
     fn first_selected_entity<'a>(
         &self,
-        definition: &'a MachineDefinition,
+        definition: &'a MachineLayerDefinition,
     ) -> Option<&'a dyn Inspect> {
         self.selection.first().map(|first| match first {
             SelectedEntity::Transition(transition) => {
@@ -114,6 +149,68 @@ impl Inspector {
             MessageDirection::ToWidget,
             Default::default(),
         ));
+        ui.send_message(WidgetMessage::visibility(
+            self.blend_space_field,
+            MessageDirection::ToWidget,
+            false,
+        ));
+    }
+
+    fn sync_blend_space_field(&self, ui: &mut UserInterface, data_model: &AbsmDataModel) {
+        let guard = data_model.resource.data_ref();
+        let definition = &guard.absm_definition;
+
+        let blend_space = match self.selection.first() {
+            Some(SelectedEntity::PoseNode(pose_node)) => {
+                match definition.layers[data_model.current_layer]
+                    .nodes
+                    .try_borrow(*pose_node)
+                {
+                    Some(PoseNodeDefinition::BlendSpace(blend_space)) => Some(blend_space),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+
+        send_sync_message(
+            ui,
+            WidgetMessage::visibility(
+                self.blend_space_field,
+                MessageDirection::ToWidget,
+                blend_space.is_some(),
+            ),
+        );
+
+        if let Some(blend_space) = blend_space {
+            send_sync_message(
+                ui,
+                BlendSpaceFieldMessage::points(
+                    self.blend_space_field,
+                    MessageDirection::ToWidget,
+                    blend_space.points.iter().map(|p| p.position).collect(),
+                ),
+            );
+
+            let sampling_point = definition
+                .parameters
+                .container
+                .iter()
+                .find(|p| p.name == blend_space.sampling_parameter)
+                .and_then(|p| match p.value {
+                    Parameter::SamplingPoint(point) => Some(point),
+                    _ => None,
+                });
+
+            send_sync_message(
+                ui,
+                BlendSpaceFieldMessage::sampling_point(
+                    self.blend_space_field,
+                    MessageDirection::ToWidget,
+                    sampling_point,
+                ),
+            );
+        }
     }
 
     pub fn sync_to_model(&mut self, ui: &mut UserInterface, data_model: &AbsmDataModel) {
@@ -122,7 +219,9 @@ impl Inspector {
         if self.selection != data_model.selection {
             self.selection = data_model.selection.clone();
 
-            if let Some(obj_ref) = self.first_selected_entity(&guard.absm_definition) {
+            if let Some(obj_ref) =
+                self.first_selected_entity(&guard.absm_definition.layers[data_model.current_layer])
+            {
                 let ctx = InspectorContext::from_object(
                     obj_ref,
                     &mut ui.build_ctx(),
@@ -138,7 +237,9 @@ impl Inspector {
                     ctx,
                 ));
             }
-        } else if let Some(obj_ref) = self.first_selected_entity(&guard.absm_definition) {
+        } else if let Some(obj_ref) =
+            self.first_selected_entity(&guard.absm_definition.layers[data_model.current_layer])
+        {
             let ctx = ui
                 .node(self.inspector)
                 .cast::<fyrox::gui::inspector::Inspector>()
@@ -152,6 +253,9 @@ impl Inspector {
                 }
             }
         }
+
+        drop(guard);
+        self.sync_blend_space_field(ui, data_model);
     }
 
     pub fn handle_ui_message(
@@ -160,7 +264,19 @@ impl Inspector {
         data_model: &AbsmDataModel,
         sender: &MessageSender,
     ) {
-        if message.destination() == self.inspector
+        if let Some(BlendSpaceFieldMessage::MovePoint { index, position }) = message.data() {
+            if message.destination() == self.blend_space_field
+                && message.direction() == MessageDirection::FromWidget
+            {
+                if let Some(SelectedEntity::PoseNode(pose_node)) = data_model.selection.first() {
+                    sender.do_command(SetBlendSpacePointPositionCommand {
+                        handle: *pose_node,
+                        index: *index,
+                        value: *position,
+                    });
+                }
+            }
+        } else if message.destination() == self.inspector
             && message.direction() == MessageDirection::FromWidget
         {
             if let Some(InspectorMessage::PropertyChanged(args)) =
@@ -0,0 +1,306 @@
+use crate::{
+    absm::{
+        command::{
+            layer::{
+                make_set_layer_property_command, AddLayerCommand, RemoveLayerCommand,
+                SetCurrentLayerCommand,
+            },
+            AbsmCommand, CommandGroup,
+        },
+        message::MessageSender,
+        AbsmDataModel,
+    },
+    gui::make_dropdown_list_option,
+    inspector::editors::make_property_editors_container,
+    send_sync_message, Message, MessageDirection, MSG_SYNC_FLAG,
+};
+use fyrox::{
+    animation::machine::{LayerBlendMode, LayerMaskDefinition, MachineLayerDefinition, PoseWeight},
+    core::pool::Handle,
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        dropdown_list::{DropdownListBuilder, DropdownListMessage},
+        grid::{Column, GridBuilder, Row},
+        inspector::{
+            editors::{
+                collection::VecCollectionPropertyEditorDefinition,
+                enumeration::EnumPropertyEditorDefinition,
+                inspectable::InspectablePropertyEditorDefinition,
+                PropertyEditorDefinitionContainer,
+            },
+            InspectorBuilder, InspectorContext, InspectorMessage,
+        },
+        message::UiMessage,
+        scroll_viewer::ScrollViewerBuilder,
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowTitle},
+        BuildContext, Thickness, UiNode, UserInterface,
+    },
+    utils::log::Log,
+};
+use std::{rc::Rc, sync::mpsc::Sender};
+
+pub struct LayerPanel {
+    pub window: Handle<UiNode>,
+    layers: Handle<UiNode>,
+    add: Handle<UiNode>,
+    remove: Handle<UiNode>,
+    inspector: Handle<UiNode>,
+    property_editors: Rc<PropertyEditorDefinitionContainer>,
+    layer_names: Vec<String>,
+    current_layer: Option<usize>,
+}
+
+impl LayerPanel {
+    pub fn new(ctx: &mut BuildContext, sender: Sender<Message>) -> Self {
+        let property_editors = make_property_editors_container(sender);
+        property_editors.insert(EnumPropertyEditorDefinition::<PoseWeight>::new());
+        property_editors.insert(EnumPropertyEditorDefinition::<LayerBlendMode>::new());
+        property_editors.insert(InspectablePropertyEditorDefinition::<LayerMaskDefinition>::new());
+        property_editors.insert(VecCollectionPropertyEditorDefinition::<String>::new());
+
+        let layers;
+        let add;
+        let remove;
+        let inspector;
+        let window = WindowBuilder::new(WidgetBuilder::new())
+            .can_close(false)
+            .can_minimize(false)
+            .with_title(WindowTitle::text("Layers"))
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            GridBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(0)
+                                    .with_child({
+                                        layers = DropdownListBuilder::new(
+                                            WidgetBuilder::new()
+                                                .on_column(0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_close_on_selection(true)
+                                        .build(ctx);
+                                        layers
+                                    })
+                                    .with_child({
+                                        add = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .on_column(1)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("+")
+                                        .build(ctx);
+                                        add
+                                    })
+                                    .with_child({
+                                        remove = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .on_column(2)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("-")
+                                        .build(ctx);
+                                        remove
+                                    }),
+                            )
+                            .add_column(Column::stretch())
+                            .add_column(Column::strict(22.0))
+                            .add_column(Column::strict(22.0))
+                            .add_row(Row::strict(24.0))
+                            .build(ctx),
+                        )
+                        .with_child(
+                            ScrollViewerBuilder::new(WidgetBuilder::new().on_row(1))
+                                .with_content({
+                                    inspector =
+                                        InspectorBuilder::new(WidgetBuilder::new()).build(ctx);
+                                    inspector
+                                })
+                                .build(ctx),
+                        ),
+                )
+                .add_row(Row::strict(26.0))
+                .add_row(Row::stretch())
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            window,
+            layers,
+            add,
+            remove,
+            inspector,
+            property_editors: Rc::new(property_editors),
+            layer_names: Default::default(),
+            current_layer: None,
+        }
+    }
+
+    pub fn reset(&mut self, ui: &mut UserInterface, data_model: Option<&AbsmDataModel>) {
+        self.layer_names.clear();
+        self.current_layer = None;
+
+        send_sync_message(
+            ui,
+            DropdownListMessage::items(self.layers, MessageDirection::ToWidget, vec![]),
+        );
+        send_sync_message(
+            ui,
+            DropdownListMessage::selection(self.layers, MessageDirection::ToWidget, None),
+        );
+        ui.send_message(InspectorMessage::context(
+            self.inspector,
+            MessageDirection::ToWidget,
+            Default::default(),
+        ));
+
+        if let Some(data_model) = data_model {
+            self.sync_to_model(ui, data_model);
+        }
+    }
+
+    pub fn sync_to_model(&mut self, ui: &mut UserInterface, data_model: &AbsmDataModel) {
+        let guard = data_model.resource.data_ref();
+        let definition = &guard.absm_definition;
+
+        let layer_names = definition
+            .layers
+            .iter()
+            .map(|layer| layer.name.clone())
+            .collect::<Vec<_>>();
+
+        if self.layer_names != layer_names {
+            let items = layer_names
+                .iter()
+                .map(|name| make_dropdown_list_option(&mut ui.build_ctx(), name))
+                .collect::<Vec<_>>();
+
+            send_sync_message(
+                ui,
+                DropdownListMessage::items(self.layers, MessageDirection::ToWidget, items),
+            );
+
+            // Force the list to refresh the selected item, it will be stale otherwise.
+            send_sync_message(
+                ui,
+                DropdownListMessage::selection(self.layers, MessageDirection::ToWidget, None),
+            );
+            send_sync_message(
+                ui,
+                DropdownListMessage::selection(
+                    self.layers,
+                    MessageDirection::ToWidget,
+                    Some(data_model.current_layer),
+                ),
+            );
+
+            // Indices of layers are shifted when a layer is added or removed, so the inspector
+            // must be re-created.
+            if self.layer_names.len() != layer_names.len() {
+                self.current_layer = None;
+            }
+            self.layer_names = layer_names;
+        }
+
+        let layer = &definition.layers[data_model.current_layer];
+
+        if self.current_layer != Some(data_model.current_layer) {
+            self.current_layer = Some(data_model.current_layer);
+
+            send_sync_message(
+                ui,
+                DropdownListMessage::selection(
+                    self.layers,
+                    MessageDirection::ToWidget,
+                    Some(data_model.current_layer),
+                ),
+            );
+
+            let inspector_context = InspectorContext::from_object(
+                layer,
+                &mut ui.build_ctx(),
+                self.property_editors.clone(),
+                None,
+                MSG_SYNC_FLAG,
+                0,
+            );
+
+            ui.send_message(InspectorMessage::context(
+                self.inspector,
+                MessageDirection::ToWidget,
+                inspector_context,
+            ));
+        } else {
+            let ctx = ui
+                .node(self.inspector)
+                .cast::<fyrox::gui::inspector::Inspector>()
+                .unwrap()
+                .context()
+                .clone();
+
+            if let Err(sync_errors) = ctx.sync(layer, ui, 0) {
+                for error in sync_errors {
+                    Log::err(format!("Failed to sync property. Reason: {:?}", error))
+                }
+            }
+        }
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        sender: &MessageSender,
+        data_model: &AbsmDataModel,
+    ) {
+        if message.direction() != MessageDirection::FromWidget {
+            return;
+        }
+
+        if let Some(ButtonMessage::Click) = message.data() {
+            let layer_count = data_model.resource.data_ref().absm_definition.layers.len();
+
+            if message.destination() == self.add {
+                let layer = MachineLayerDefinition {
+                    name: format!("Layer {}", layer_count),
+                    ..Default::default()
+                };
+
+                sender.do_command(CommandGroup::from(vec![
+                    AbsmCommand::new(AddLayerCommand::new(layer)),
+                    AbsmCommand::new(SetCurrentLayerCommand {
+                        layer: layer_count,
+                        selection: vec![],
+                    }),
+                ]));
+            } else if message.destination() == self.remove && layer_count > 1 {
+                let current_layer = data_model.current_layer;
+
+                // Switch to a neighbour layer first, the removed layer cannot be current.
+                sender.do_command(CommandGroup::from(vec![
+                    AbsmCommand::new(SetCurrentLayerCommand {
+                        layer: current_layer.saturating_sub(1),
+                        selection: vec![],
+                    }),
+                    AbsmCommand::new(RemoveLayerCommand::new(current_layer)),
+                ]));
+            }
+        } else if let Some(DropdownListMessage::SelectionChanged(Some(layer))) = message.data() {
+            if message.destination() == self.layers && *layer != data_model.current_layer {
+                sender.do_command(SetCurrentLayerCommand {
+                    layer: *layer,
+                    selection: vec![],
+                });
+            }
+        } else if let Some(InspectorMessage::PropertyChanged(args)) = message.data() {
+            if message.destination() == self.inspector {
+                sender.do_command_value(
+                    make_set_layer_property_command(data_model.current_layer, args).unwrap(),
+                );
+            }
+        }
+    }
+}
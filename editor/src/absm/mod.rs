@@ -1,10 +1,11 @@
 use crate::{
     absm::{
         command::{
-            blend::{AddInputCommand, AddPoseSourceCommand},
+            blend::{AddBlendSpacePointCommand, AddInputCommand, AddPoseSourceCommand},
             AbsmCommand, AbsmCommandStack, AbsmEditorContext,
         },
         inspector::Inspector,
+        layer::LayerPanel,
         menu::Menu,
         message::{AbsmMessage, MessageSender},
         node::{AbsmNode, AbsmNodeMessage},
@@ -20,6 +21,7 @@ use fyrox::{
     animation::machine::{
         node::{
             blend::{BlendPoseDefinition, IndexedBlendInputDefinition},
+            blend_space::BlendSpacePointDefinition,
             PoseNodeDefinition,
         },
         state::StateDefinition,
@@ -51,10 +53,12 @@ use std::{
     sync::mpsc::{channel, Receiver, Sender},
 };
 
+mod blend_space;
 mod canvas;
 mod command;
 mod connection;
 mod inspector;
+mod layer;
 mod menu;
 mod message;
 mod node;
@@ -84,6 +88,8 @@ pub struct AbsmDataModel {
     path: PathBuf,
     preview_model_path: PathBuf,
     selection: Vec<SelectedEntity>,
+    // Index of a layer that is currently being edited.
+    current_layer: usize,
     resource: AbsmResource,
 }
 
@@ -93,6 +99,7 @@ impl AbsmDataModel {
             path: Default::default(),
             preview_model_path: Default::default(),
             selection: Default::default(),
+            current_layer: 0,
             resource: AbsmResource::from(Resource::new(ResourceState::Ok(AbsmResourceState {
                 path: Default::default(),
                 absm_definition: Default::default(),
@@ -104,6 +111,7 @@ impl AbsmDataModel {
         AbsmEditorContext {
             selection: &mut self.selection,
             resource: self.resource.data_ref(),
+            current_layer: &mut self.current_layer,
         }
     }
 
@@ -147,6 +155,7 @@ pub struct AbsmEditor {
     state_viewer: StateViewer,
     menu: Menu,
     parameter_panel: ParameterPanel,
+    layer_panel: LayerPanel,
 }
 
 impl AbsmEditor {
@@ -163,7 +172,8 @@ impl AbsmEditor {
         let inspector = Inspector::new(ctx, sender.clone());
         let state_graph_viewer = StateGraphViewer::new(ctx);
         let state_viewer = StateViewer::new(ctx);
-        let parameter_panel = ParameterPanel::new(ctx, sender);
+        let parameter_panel = ParameterPanel::new(ctx, sender.clone());
+        let layer_panel = LayerPanel::new(ctx, sender);
 
         let docking_manager = DockingManagerBuilder::new(
             WidgetBuilder::new().on_row(1).with_child(
@@ -185,9 +195,25 @@ impl AbsmEditor {
                                                         ))
                                                         .build(ctx),
                                                     TileBuilder::new(WidgetBuilder::new())
-                                                        .with_content(TileContent::Window(
-                                                            parameter_panel.window,
-                                                        ))
+                                                        .with_content(TileContent::VerticalTiles {
+                                                            splitter: 0.5,
+                                                            tiles: [
+                                                                TileBuilder::new(
+                                                                    WidgetBuilder::new(),
+                                                                )
+                                                                .with_content(TileContent::Window(
+                                                                    parameter_panel.window,
+                                                                ))
+                                                                .build(ctx),
+                                                                TileBuilder::new(
+                                                                    WidgetBuilder::new(),
+                                                                )
+                                                                .with_content(TileContent::Window(
+                                                                    layer_panel.window,
+                                                                ))
+                                                                .build(ctx),
+                                                            ],
+                                                        })
                                                         .build(ctx),
                                                 ],
                                             })
@@ -261,6 +287,7 @@ impl AbsmEditor {
             previewer,
             state_viewer,
             parameter_panel,
+            layer_panel,
         }
     }
 
@@ -268,6 +295,7 @@ impl AbsmEditor {
         if let Some(data_model) = self.data_model.as_ref() {
             let ui = &mut engine.user_interface;
             self.parameter_panel.sync_to_model(ui, data_model);
+            self.layer_panel.sync_to_model(ui, data_model);
             self.state_graph_viewer.sync_to_model(data_model, ui);
             self.state_viewer.sync_to_model(ui, data_model);
             self.inspector.sync_to_model(ui, data_model);
@@ -321,6 +349,8 @@ impl AbsmEditor {
         if let Some(data_model) = self.data_model.as_ref() {
             self.parameter_panel
                 .reset(&mut engine.user_interface, Some(data_model));
+            self.layer_panel
+                .reset(&mut engine.user_interface, Some(data_model));
             self.previewer.set_preview_model(
                 engine,
                 &data_model.preview_model_path,
@@ -331,6 +361,7 @@ impl AbsmEditor {
             self.state_graph_viewer.clear(&engine.user_interface);
             self.previewer.clear(engine);
             self.parameter_panel.reset(&mut engine.user_interface, None);
+            self.layer_panel.reset(&mut engine.user_interface, None);
             self.inspector.clear(&engine.user_interface);
         }
     }
@@ -447,20 +478,26 @@ impl AbsmEditor {
     pub fn handle_machine_events(&self, engine: &mut Engine) {
         let scene = &mut engine.scenes[self.previewer.scene()];
 
-        if let Some(machine) = scene
+        let current_layer = match self.data_model.as_ref() {
+            Some(data_model) => data_model.current_layer,
+            None => return,
+        };
+
+        if let Some(layer) = scene
             .animation_machines
             .try_get_mut(self.previewer.current_absm())
+            .and_then(|machine| machine.layers_mut().get_mut(current_layer))
         {
-            while let Some(event) = machine.pop_event() {
+            while let Some(event) = layer.pop_event() {
                 match event {
                     Event::ActiveStateChanged(state) => {
-                        if let Some(state_ref) = machine.states().try_borrow(state) {
+                        if let Some(state_ref) = layer.states().try_borrow(state) {
                             self.state_graph_viewer
                                 .activate_state(&engine.user_interface, state_ref.definition);
                         }
                     }
                     Event::ActiveTransitionChanged(transition) => {
-                        if let Some(transition_ref) = machine.transitions().try_borrow(transition) {
+                        if let Some(transition_ref) = layer.transitions().try_borrow(transition) {
                             self.state_graph_viewer.activate_transition(
                                 &engine.user_interface,
                                 transition_ref.definition,
@@ -493,6 +530,8 @@ impl AbsmEditor {
                 .handle_ui_message(message, data_model, &self.message_sender);
             self.parameter_panel
                 .handle_ui_message(message, &self.message_sender);
+            self.layer_panel
+                .handle_ui_message(message, &self.message_sender, data_model);
         }
 
         if let Some(FileSelectorMessage::Commit(path)) = message.data() {
@@ -524,8 +563,9 @@ impl AbsmEditor {
                             .node(message.destination())
                             .query_component::<AbsmNode<PoseNodeDefinition>>()
                         {
-                            let model_ref = &data_model.resource.data_ref().absm_definition.nodes
-                                [node.model_handle];
+                            let model_ref = &data_model.resource.data_ref().absm_definition.layers
+                                [data_model.current_layer]
+                                .nodes[node.model_handle];
 
                             match model_ref {
                                 PoseNodeDefinition::PlayAnimation(_) => {
@@ -543,6 +583,13 @@ impl AbsmEditor {
                                         IndexedBlendInputDefinition::default(),
                                     ));
                                 }
+                                PoseNodeDefinition::BlendSpace(_) => {
+                                    self.message_sender
+                                        .do_command(AddBlendSpacePointCommand::new(
+                                            node.model_handle,
+                                            BlendSpacePointDefinition::default(),
+                                        ));
+                                }
                             }
                         }
                     }
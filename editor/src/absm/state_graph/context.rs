@@ -152,10 +152,8 @@ impl NodeContextMenu {
                     .collect::<Vec<_>>();
 
                 // Gather every transition that leads from/to any of states to remove.
-                let transitions_to_remove = data_model
-                    .resource
-                    .data_ref()
-                    .absm_definition
+                let transitions_to_remove = data_model.resource.data_ref().absm_definition.layers
+                    [data_model.current_layer]
                     .transitions
                     .pair_iter()
                     .filter_map(|(handle, transition)| {
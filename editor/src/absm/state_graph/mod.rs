@@ -35,6 +35,8 @@ pub struct StateGraphViewer {
     canvas_context_menu: CanvasContextMenu,
     node_context_menu: NodeContextMenu,
     transition_context_menu: TransitionContextMenu,
+    // Index of a layer which state graph is shown.
+    layer: usize,
 }
 
 fn fetch_state_node_model_handle(
@@ -82,6 +84,7 @@ impl StateGraphViewer {
             node_context_menu,
             canvas_context_menu,
             transition_context_menu,
+            layer: 0,
         }
     }
 
@@ -213,7 +216,15 @@ impl StateGraphViewer {
     }
 
     pub fn sync_to_model(&mut self, data_model: &AbsmDataModel, ui: &mut UserInterface) {
-        let definition = &data_model.resource.data_ref().absm_definition;
+        let definition =
+            &data_model.resource.data_ref().absm_definition.layers[data_model.current_layer];
+
+        // Views of other layer cannot be reused, so they must be re-created from scratch.
+        let layer_changed = self.layer != data_model.current_layer;
+        if layer_changed {
+            self.layer = data_model.current_layer;
+            self.clear(ui);
+        }
 
         let canvas = ui
             .node(self.canvas)
@@ -224,14 +235,14 @@ impl StateGraphViewer {
             .children()
             .iter()
             .cloned()
-            .filter(|c| ui.node(*c).has_component::<AbsmNode<StateDefinition>>())
+            .filter(|c| !layer_changed && ui.node(*c).has_component::<AbsmNode<StateDefinition>>())
             .collect::<Vec<_>>();
 
         let mut transitions = canvas
             .children()
             .iter()
             .cloned()
-            .filter(|c| ui.node(*c).has_component::<Transition>())
+            .filter(|c| !layer_changed && ui.node(*c).has_component::<Transition>())
             .collect::<Vec<_>>();
 
         match states
@@ -2,7 +2,8 @@ use crate::{
     absm::{
         command::{
             blend::{
-                SetBlendAnimationByIndexInputPoseSourceCommand, SetBlendAnimationsPoseSourceCommand,
+                SetBlendAnimationByIndexInputPoseSourceCommand,
+                SetBlendAnimationsPoseSourceCommand, SetBlendSpacePointPoseSourceCommand,
             },
             AbsmCommand, AddPoseNodeCommand, ChangeSelectionCommand, CommandGroup,
             DeletePoseNodeCommand, SetStateRootPoseCommand,
@@ -18,11 +19,12 @@ use fyrox::{
     animation::machine::{
         node::{
             blend::{BlendAnimationsByIndexDefinition, BlendAnimationsDefinition},
+            blend_space::BlendSpaceDefinition,
             play::PlayAnimationDefinition,
             BasePoseNodeDefinition, PoseNodeDefinition,
         },
         state::StateDefinition,
-        MachineDefinition,
+        MachineLayerDefinition,
     },
     core::pool::Handle,
     gui::{
@@ -39,6 +41,7 @@ pub struct CanvasContextMenu {
     create_play_animation: Handle<UiNode>,
     create_blend_animations: Handle<UiNode>,
     create_blend_by_index: Handle<UiNode>,
+    create_blend_space: Handle<UiNode>,
     pub menu: Handle<UiNode>,
     pub canvas: Handle<UiNode>,
     pub node_context_menu: Handle<UiNode>,
@@ -49,6 +52,7 @@ impl CanvasContextMenu {
         let create_play_animation;
         let create_blend_animations;
         let create_blend_by_index;
+        let create_blend_space;
         let menu = PopupBuilder::new(
             WidgetBuilder::new()
                 .with_enabled(false) // Disabled by default.
@@ -68,6 +72,10 @@ impl CanvasContextMenu {
                     .with_child({
                         create_blend_by_index = create_menu_item("Blend By Index", vec![], ctx);
                         create_blend_by_index
+                    })
+                    .with_child({
+                        create_blend_space = create_menu_item("Blend Space", vec![], ctx);
+                        create_blend_space
                     }),
             )
             .build(ctx),
@@ -78,6 +86,7 @@ impl CanvasContextMenu {
             create_play_animation,
             create_blend_animations,
             create_blend_by_index,
+            create_blend_space,
             menu,
             canvas: Default::default(),
             node_context_menu: Default::default(),
@@ -105,6 +114,7 @@ impl CanvasContextMenu {
                     animation: Default::default(),
                     speed: 1.0,
                     time_slice: None,
+                    root_motion: None,
                 }))
             } else if message.destination() == self.create_blend_animations {
                 Some(PoseNodeDefinition::BlendAnimations(
@@ -127,6 +137,15 @@ impl CanvasContextMenu {
                         inputs: Default::default(),
                     },
                 ))
+            } else if message.destination() == self.create_blend_space {
+                Some(PoseNodeDefinition::BlendSpace(BlendSpaceDefinition {
+                    base: BasePoseNodeDefinition {
+                        position,
+                        parent_state: current_state,
+                    },
+                    sampling_parameter: "".to_string(),
+                    points: Default::default(),
+                }))
             } else {
                 None
             };
@@ -180,7 +199,7 @@ impl NodeContextMenu {
         &mut self,
         message: &UiMessage,
         selection: &[SelectedEntity],
-        definition: &MachineDefinition,
+        definition: &MachineLayerDefinition,
         sender: &MessageSender,
         ui: &UserInterface,
     ) {
@@ -250,7 +269,7 @@ impl ConnectionContextMenu {
         message: &UiMessage,
         ui: &mut UserInterface,
         sender: &MessageSender,
-        definition: &MachineDefinition,
+        definition: &MachineLayerDefinition,
     ) {
         if let Some(MenuItemMessage::Click) = message.data() {
             if message.destination == self.remove {
@@ -290,6 +309,13 @@ impl ConnectionContextMenu {
                             value: Default::default(),
                         })
                     }
+                    PoseNodeDefinition::BlendSpace(_) => {
+                        sender.do_command(SetBlendSpacePointPoseSourceCommand {
+                            handle: model_handle,
+                            index,
+                            value: Default::default(),
+                        })
+                    }
                 }
             }
         } else if let Some(PopupMessage::Placement(Placement::Cursor(target))) = message.data() {
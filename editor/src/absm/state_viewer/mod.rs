@@ -3,8 +3,10 @@ use crate::{
     absm::{
         canvas::{AbsmCanvasBuilder, AbsmCanvasMessage},
         command::{
-            blend::SetBlendAnimationByIndexInputPoseSourceCommand, AbsmCommand,
-            ChangeSelectionCommand, CommandGroup, MovePoseNodeCommand,
+            blend::{
+                SetBlendAnimationByIndexInputPoseSourceCommand, SetBlendSpacePointPoseSourceCommand,
+            },
+            AbsmCommand, ChangeSelectionCommand, CommandGroup, MovePoseNodeCommand,
         },
         connection::{Connection, ConnectionBuilder},
         message::MessageSender,
@@ -35,6 +37,8 @@ pub struct StateViewer {
     pub window: Handle<UiNode>,
     canvas: Handle<UiNode>,
     state: Handle<StateDefinition>,
+    // Index of a layer to which the state belongs.
+    layer: usize,
     canvas_context_menu: CanvasContextMenu,
     node_context_menu: NodeContextMenu,
     connection_context_menu: ConnectionContextMenu,
@@ -116,6 +120,7 @@ impl StateViewer {
             window,
             canvas,
             state: Default::default(),
+            layer: 0,
             canvas_context_menu,
             node_context_menu,
             connection_context_menu,
@@ -131,11 +136,10 @@ impl StateViewer {
         assert!(state.is_some());
 
         self.state = state;
+        self.layer = data_model.current_layer;
 
-        let (state_name, exists) = data_model
-            .resource
-            .data_ref()
-            .absm_definition
+        let (state_name, exists) = data_model.resource.data_ref().absm_definition.layers
+            [data_model.current_layer]
             .states
             .try_borrow(self.state)
             .map(|state| {
@@ -191,7 +195,8 @@ impl StateViewer {
         sender: &MessageSender,
         data_model: &AbsmDataModel,
     ) {
-        let definition = &data_model.resource.data_ref().absm_definition;
+        let definition =
+            &data_model.resource.data_ref().absm_definition.layers[data_model.current_layer];
 
         if message.destination() == self.canvas {
             if let Some(msg) = message.data::<AbsmCanvasMessage>() {
@@ -260,6 +265,13 @@ impl StateViewer {
                                     value: source_node,
                                 });
                             }
+                            PoseNodeDefinition::BlendSpace(_) => {
+                                sender.do_command(SetBlendSpacePointPoseSourceCommand {
+                                    handle: dest_node,
+                                    index: dest_socket_ref.index,
+                                    value: source_node,
+                                });
+                            }
                         }
                     }
                     _ => (),
@@ -285,7 +297,14 @@ impl StateViewer {
             return;
         }
 
-        let definition = &data_model.resource.data_ref().absm_definition;
+        if self.layer != data_model.current_layer {
+            // The state belongs to other layer, there is nothing to show anymore.
+            self.clear(ui);
+            return;
+        }
+
+        let definition =
+            &data_model.resource.data_ref().absm_definition.layers[data_model.current_layer];
 
         let parent_state_ref = &definition.states[self.state];
 
@@ -358,6 +377,9 @@ impl StateViewer {
                                 "Blend Animations By Index",
                                 true,
                             ),
+                            PoseNodeDefinition::BlendSpace(blend_space) => {
+                                (blend_space.points.len(), "Blend Space", true)
+                            }
                         };
 
                         let node_view = AbsmNodeBuilder::new(
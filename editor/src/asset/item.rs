@@ -147,7 +147,7 @@ impl AssetItemBuilder {
                         kind = AssetKind::Texture;
                         Some(into_gui_texture(resource_manager.request_texture(&path)))
                     }
-                    "fbx" | "rgs" => {
+                    "fbx" | "gltf" | "glb" | "rgs" => {
                         kind = AssetKind::Model;
                         load_image(include_bytes!("../../resources/embed/model.png"))
                     }
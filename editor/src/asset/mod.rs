@@ -397,6 +397,8 @@ impl AssetBrowser {
                                 ext.as_str(),
                                 "rgs"
                                     | "fbx"
+                                    | "gltf"
+                                    | "glb"
                                     | "jpg"
                                     | "tga"
                                     | "png"
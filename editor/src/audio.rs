@@ -1,7 +1,8 @@
 use crate::utils::window_content;
 use crate::{
-    scene::commands::effect::AddEffectCommand, ChangeSelectionCommand, EditorScene, GridBuilder,
-    Message, MessageDirection, Mode, SceneCommand, Selection, UserInterface,
+    gui::make_dropdown_list_option, scene::commands::effect::AddEffectCommand,
+    ChangeSelectionCommand, EditorScene, GridBuilder, Message, MessageDirection, Mode,
+    SceneCommand, Selection, UserInterface,
 };
 use fyrox::gui::widget::WidgetMessage;
 use fyrox::{
@@ -11,6 +12,7 @@ use fyrox::{
         border::BorderBuilder,
         button::{ButtonBuilder, ButtonMessage},
         decorator::DecoratorBuilder,
+        dropdown_list::{DropdownList, DropdownListBuilder},
         grid::{Column, Row},
         list_view::{ListView, ListViewBuilder, ListViewMessage},
         message::UiMessage,
@@ -20,7 +22,10 @@ use fyrox::{
         window::{WindowBuilder, WindowTitle},
         Orientation, Thickness, UiNode,
     },
-    scene::sound::effect::{BaseEffectBuilder, Effect, ReverbEffectBuilder},
+    scene::sound::effect::{
+        BaseEffectBuilder, ChorusEffectBuilder, CompressorEffectBuilder, DelayEffectBuilder,
+        Effect, EqualizerEffectBuilder, ReverbEffectBuilder,
+    },
 };
 use std::{cmp::Ordering, rc::Rc, sync::mpsc::Sender};
 
@@ -43,9 +48,25 @@ pub struct AudioPanel {
     pub window: Handle<UiNode>,
     edit_context: Handle<UiNode>,
     add_effect: Handle<UiNode>,
+    effect_kind: Handle<UiNode>,
     effects: Handle<UiNode>,
 }
 
+/// Names of effects that can be added from the panel, order must match [`make_effect`].
+const EFFECT_KINDS: [&str; 5] = ["Reverb", "Delay", "Chorus", "Compressor", "Equalizer"];
+
+fn make_effect(kind: usize) -> Effect {
+    let base = BaseEffectBuilder::new().with_name(EFFECT_KINDS[kind].to_owned());
+    match kind {
+        0 => ReverbEffectBuilder::new(base).build_effect(),
+        1 => DelayEffectBuilder::new(base).build_effect(),
+        2 => ChorusEffectBuilder::new(base).build_effect(),
+        3 => CompressorEffectBuilder::new(base).build_effect(),
+        4 => EqualizerEffectBuilder::new(base).build_effect(),
+        _ => unreachable!(),
+    }
+}
+
 fn item_effect(item: Handle<UiNode>, ui: &UserInterface) -> Handle<Effect> {
     *ui.node(item)
         .user_data_ref::<Handle<Effect>>()
@@ -58,6 +79,7 @@ impl AudioPanel {
 
         let edit_context;
         let add_effect;
+        let effect_kind;
         let effects;
         let window = WindowBuilder::new(WidgetBuilder::new())
             .with_content(
@@ -72,13 +94,28 @@ impl AudioPanel {
                             StackPanelBuilder::new(
                                 WidgetBuilder::new()
                                     .on_row(1)
+                                    .with_child({
+                                        effect_kind = DropdownListBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_margin(Thickness::uniform(1.0))
+                                                .with_width(100.0),
+                                        )
+                                        .with_items(
+                                            EFFECT_KINDS
+                                                .iter()
+                                                .map(|name| make_dropdown_list_option(ctx, name))
+                                                .collect(),
+                                        )
+                                        .with_selected(0)
+                                        .build(ctx);
+                                        effect_kind
+                                    })
                                     .with_child({
                                         add_effect = ButtonBuilder::new(
                                             WidgetBuilder::new()
                                                 .with_margin(Thickness::uniform(1.0)),
                                         )
-                                        // TODO: Add selector when there's more effects.
-                                        .with_text("Add Reverb")
+                                        .with_text("Add Effect")
                                         .build(ctx);
                                         add_effect
                                     })
@@ -108,6 +145,7 @@ impl AudioPanel {
             window,
             effects,
             add_effect,
+            effect_kind,
             edit_context,
         }
     }
@@ -130,14 +168,17 @@ impl AudioPanel {
                     )))
                     .unwrap();
             } else if message.destination() == self.add_effect {
+                let kind = engine
+                    .user_interface
+                    .node(self.effect_kind)
+                    .cast::<DropdownList>()
+                    .expect("Must be DropdownList")
+                    .selection()
+                    .unwrap_or_default();
+
                 sender
                     .send(Message::DoSceneCommand(SceneCommand::new(
-                        AddEffectCommand::new(
-                            ReverbEffectBuilder::new(
-                                BaseEffectBuilder::new().with_name("Reverb".to_owned()),
-                            )
-                            .build_effect(),
-                        ),
+                        AddEffectCommand::new(make_effect(kind)),
                     )))
                     .unwrap()
             }
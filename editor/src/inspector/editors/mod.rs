@@ -47,6 +47,7 @@ use fyrox::{
         graph::physics::CoefficientCombineRule,
         joint::*,
         light::{
+            area::AreaLightShape,
             directional::{CsmOptions, FrustumSplitOptions},
             BaseLight,
         },
@@ -59,10 +60,15 @@ use fyrox::{
             },
             EmitterWrapper,
         },
+        ragdoll::Limb,
+        reflection_probe::ReflectionProbeUpdateMode,
         rigidbody::RigidBodyType,
         sound::{
             self,
-            effect::{BaseEffect, Effect, EffectInput, ReverbEffect},
+            effect::{
+                BaseEffect, ChorusEffect, CompressorEffect, DelayEffect, Effect, EffectInput,
+                EqualizerBand, EqualizerBandKind, EqualizerEffect, ReverbEffect,
+            },
             Biquad, DistanceModel, SoundBufferResource, SoundBufferResourceLoadError,
             SoundBufferState, Status,
         },
@@ -115,6 +121,7 @@ pub fn make_property_editors_container(
     container.register_inheritable_vec_collection::<NodeHandle>();
 
     container.register_inheritable_vec_collection::<Surface>();
+    container.register_inheritable_vec_collection::<Limb>();
     container.register_inheritable_vec_collection::<Layer>();
     container.register_inheritable_vec_collection::<EmitterWrapper>();
     container.register_inheritable_vec_collection::<LevelOfDetail>();
@@ -123,6 +130,7 @@ pub fn make_property_editors_container(
     container.register_inheritable_vec_collection::<LodControlledObject>();
     container.register_inheritable_vec_collection::<GeometrySource>();
     container.register_inheritable_vec_collection::<EffectInput>();
+    container.register_inheritable_vec_collection::<EqualizerBand>();
 
     container.insert(make_status_enum_editor_definition());
 
@@ -203,6 +211,15 @@ pub fn make_property_editors_container(
     container.register_inheritable_inspectable::<RevoluteJoint>();
     container.register_inheritable_inspectable::<PrismaticJoint>();
     container.register_inheritable_inspectable::<dim2::joint::PrismaticJoint>();
+    container.register_inheritable_inspectable::<RopeJoint>();
+    container.register_inheritable_inspectable::<dim2::joint::RopeJoint>();
+    container.register_inheritable_inspectable::<SpringJoint>();
+    container.register_inheritable_inspectable::<dim2::joint::SpringJoint>();
+    container.register_inheritable_inspectable::<GenericJoint>();
+    container.register_inheritable_inspectable::<dim2::joint::GenericJoint>();
+    container.register_inheritable_inspectable::<JointMotor>();
+    container.register_inheritable_inspectable::<JointAxisParams>();
+    container.register_inheritable_enum::<JointMotorModel, _>();
 
     container.register_inheritable_inspectable::<Base>();
     container.register_inheritable_inspectable::<BaseEffect>();
@@ -210,8 +227,16 @@ pub fn make_property_editors_container(
 
     container.register_inheritable_enum::<Effect, _>();
     container.register_inheritable_enum::<Emitter, _>();
+    container.register_inheritable_enum::<ReflectionProbeUpdateMode, _>();
+    container.register_inheritable_enum::<AreaLightShape, _>();
 
     container.register_inheritable_inspectable::<ReverbEffect>();
+    container.register_inheritable_inspectable::<DelayEffect>();
+    container.register_inheritable_inspectable::<ChorusEffect>();
+    container.register_inheritable_inspectable::<CompressorEffect>();
+    container.register_inheritable_inspectable::<EqualizerEffect>();
+    container.register_inheritable_inspectable::<EqualizerBand>();
+    container.register_inheritable_enum::<EqualizerBandKind, _>();
     container.register_inheritable_inspectable::<Biquad>();
     container.register_inheritable_inspectable::<BaseEmitter>();
     container.register_inheritable_inspectable::<SphereEmitter>();
@@ -1843,6 +1843,12 @@ impl Editor {
             }
         }
 
+        self.scene_viewer.update_statistics(
+            &self.engine.user_interface,
+            &self.engine.renderer.get_statistics(),
+            self.settings.debugging.show_statistics,
+        );
+
         self.material_editor.update(&mut self.engine);
         self.asset_browser.update(&mut self.engine);
     }
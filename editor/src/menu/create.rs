@@ -18,8 +18,8 @@ use fyrox::{
         camera::CameraBuilder,
         decal::DecalBuilder,
         light::{
-            directional::DirectionalLightBuilder, point::PointLightBuilder, spot::SpotLightBuilder,
-            BaseLightBuilder,
+            area::AreaLightBuilder, directional::DirectionalLightBuilder, point::PointLightBuilder,
+            spot::SpotLightBuilder, BaseLightBuilder,
         },
         mesh::{
             surface::{Surface, SurfaceData},
@@ -31,6 +31,7 @@ use fyrox::{
             ParticleSystemBuilder,
         },
         pivot::PivotBuilder,
+        reflection_probe::ReflectionProbeBuilder,
         sound::{listener::ListenerBuilder, SoundBuilder},
         sprite::SpriteBuilder,
         terrain::{LayerDefinition, TerrainBuilder},
@@ -82,9 +83,11 @@ pub struct CreateEntityMenu {
     create_cylinder: Handle<UiNode>,
     create_quad: Handle<UiNode>,
     create_decal: Handle<UiNode>,
+    create_reflection_probe: Handle<UiNode>,
     create_point_light: Handle<UiNode>,
     create_spot_light: Handle<UiNode>,
     create_directional_light: Handle<UiNode>,
+    create_area_light: Handle<UiNode>,
     create_terrain: Handle<UiNode>,
     create_camera: Handle<UiNode>,
     create_sprite: Handle<UiNode>,
@@ -106,9 +109,11 @@ impl CreateEntityMenu {
         let create_point_light;
         let create_spot_light;
         let create_directional_light;
+        let create_area_light;
         let create_camera;
         let create_sprite;
         let create_decal;
+        let create_reflection_probe;
         let create_particle_system;
         let create_terrain;
         let create_pivot;
@@ -179,6 +184,10 @@ impl CreateEntityMenu {
                         create_point_light = create_menu_item("Point Light", vec![], ctx);
                         create_point_light
                     },
+                    {
+                        create_area_light = create_menu_item("Area Light", vec![], ctx);
+                        create_area_light
+                    },
                 ],
                 ctx,
             ),
@@ -205,6 +214,10 @@ impl CreateEntityMenu {
                 create_decal = create_menu_item("Decal", vec![], ctx);
                 create_decal
             },
+            {
+                create_reflection_probe = create_menu_item("Reflection Probe", vec![], ctx);
+                create_reflection_probe
+            },
         ];
 
         (
@@ -217,6 +230,7 @@ impl CreateEntityMenu {
                 create_point_light,
                 create_spot_light,
                 create_directional_light,
+                create_area_light,
                 create_camera,
                 create_sprite,
                 create_particle_system,
@@ -225,6 +239,7 @@ impl CreateEntityMenu {
                 create_sound_source,
                 create_listener,
                 create_decal,
+                create_reflection_probe,
                 physics_menu,
                 physics2d_menu,
                 dim2_menu,
@@ -275,6 +290,14 @@ impl CreateEntityMenu {
                             ))
                             .build_node(),
                         )
+                    } else if message.destination() == self.create_area_light {
+                        Some(
+                            AreaLightBuilder::new(BaseLightBuilder::new(
+                                BaseBuilder::new().with_name("AreaLight"),
+                            ))
+                            .with_radius(10.0)
+                            .build_node(),
+                        )
                     } else if message.destination() == self.create_cone {
                         Some(
                             MeshBuilder::new(BaseBuilder::new().with_name("Cone"))
@@ -349,6 +372,13 @@ impl CreateEntityMenu {
                         )
                     } else if message.destination() == self.create_decal {
                         Some(DecalBuilder::new(BaseBuilder::new().with_name("Decal")).build_node())
+                    } else if message.destination() == self.create_reflection_probe {
+                        Some(
+                            ReflectionProbeBuilder::new(
+                                BaseBuilder::new().with_name("Reflection Probe"),
+                            )
+                            .build_node(),
+                        )
                     } else if message.destination() == self.create_listener {
                         Some(
                             ListenerBuilder::new(BaseBuilder::new().with_name("Listener"))
@@ -2,7 +2,10 @@ use crate::menu::create_menu_item;
 use fyrox::{
     core::pool::Handle,
     gui::{menu::MenuItemMessage, message::UiMessage, BuildContext, UiNode},
-    scene::{base::BaseBuilder, collider::*, joint::*, node::Node, rigidbody::RigidBodyBuilder},
+    scene::{
+        base::BaseBuilder, character_controller::CharacterControllerBuilder, collider::*, joint::*,
+        node::Node, rigidbody::RigidBodyBuilder,
+    },
 };
 
 pub struct PhysicsMenu {
@@ -12,17 +15,25 @@ pub struct PhysicsMenu {
     create_ball_joint: Handle<UiNode>,
     create_prismatic_joint: Handle<UiNode>,
     create_fixed_joint: Handle<UiNode>,
+    create_rope_joint: Handle<UiNode>,
+    create_spring_joint: Handle<UiNode>,
+    create_generic_joint: Handle<UiNode>,
     create_collider: Handle<UiNode>,
+    create_character_controller: Handle<UiNode>,
 }
 
 impl PhysicsMenu {
     pub fn new(ctx: &mut BuildContext) -> Self {
         let create_rigid_body;
         let create_collider;
+        let create_character_controller;
         let create_revolute_joint;
         let create_ball_joint;
         let create_prismatic_joint;
         let create_fixed_joint;
+        let create_rope_joint;
+        let create_spring_joint;
+        let create_generic_joint;
         let menu = create_menu_item(
             "Physics",
             vec![
@@ -50,6 +61,23 @@ impl PhysicsMenu {
                     create_fixed_joint = create_menu_item("Fixed Joint", vec![], ctx);
                     create_fixed_joint
                 },
+                {
+                    create_rope_joint = create_menu_item("Rope Joint", vec![], ctx);
+                    create_rope_joint
+                },
+                {
+                    create_spring_joint = create_menu_item("Spring Joint", vec![], ctx);
+                    create_spring_joint
+                },
+                {
+                    create_generic_joint = create_menu_item("Generic Joint", vec![], ctx);
+                    create_generic_joint
+                },
+                {
+                    create_character_controller =
+                        create_menu_item("Character Controller", vec![], ctx);
+                    create_character_controller
+                },
             ],
             ctx,
         );
@@ -61,7 +89,11 @@ impl PhysicsMenu {
             create_ball_joint,
             create_prismatic_joint,
             create_fixed_joint,
+            create_rope_joint,
+            create_spring_joint,
+            create_generic_joint,
             create_collider,
+            create_character_controller,
         }
     }
 
@@ -93,12 +125,37 @@ impl PhysicsMenu {
                         .with_params(JointParams::FixedJoint(Default::default()))
                         .build_node(),
                 )
+            } else if message.destination() == self.create_rope_joint {
+                Some(
+                    JointBuilder::new(BaseBuilder::new().with_name("Rope Joint"))
+                        .with_params(JointParams::RopeJoint(Default::default()))
+                        .build_node(),
+                )
+            } else if message.destination() == self.create_spring_joint {
+                Some(
+                    JointBuilder::new(BaseBuilder::new().with_name("Spring Joint"))
+                        .with_params(JointParams::SpringJoint(Default::default()))
+                        .build_node(),
+                )
+            } else if message.destination() == self.create_generic_joint {
+                Some(
+                    JointBuilder::new(BaseBuilder::new().with_name("Generic Joint"))
+                        .with_params(JointParams::GenericJoint(Default::default()))
+                        .build_node(),
+                )
             } else if message.destination == self.create_collider {
                 Some(
                     ColliderBuilder::new(BaseBuilder::new().with_name("Collider"))
                         .with_shape(ColliderShape::Cuboid(Default::default()))
                         .build_node(),
                 )
+            } else if message.destination() == self.create_character_controller {
+                Some(
+                    CharacterControllerBuilder::new(
+                        BaseBuilder::new().with_name("Character Controller"),
+                    )
+                    .build_node(),
+                )
             } else {
                 None
             }
@@ -4,6 +4,7 @@ use fyrox::{
     gui::{menu::MenuItemMessage, message::UiMessage, BuildContext, UiNode},
     scene::{
         base::BaseBuilder,
+        dim2::character_controller::CharacterControllerBuilder,
         dim2::{collider::*, joint::*, rigidbody::RigidBodyBuilder},
         node::Node,
     },
@@ -15,16 +16,24 @@ pub struct Physics2dMenu {
     create_ball_joint: Handle<UiNode>,
     create_prismatic_joint: Handle<UiNode>,
     create_fixed_joint: Handle<UiNode>,
+    create_rope_joint: Handle<UiNode>,
+    create_spring_joint: Handle<UiNode>,
+    create_generic_joint: Handle<UiNode>,
     create_collider: Handle<UiNode>,
+    create_character_controller: Handle<UiNode>,
 }
 
 impl Physics2dMenu {
     pub fn new(ctx: &mut BuildContext) -> Self {
         let create_rigid_body;
         let create_collider;
+        let create_character_controller;
         let create_ball_joint;
         let create_prismatic_joint;
         let create_fixed_joint;
+        let create_rope_joint;
+        let create_spring_joint;
+        let create_generic_joint;
         let menu = create_menu_item(
             "Physics 2D",
             vec![
@@ -48,6 +57,23 @@ impl Physics2dMenu {
                     create_fixed_joint = create_menu_item("Fixed Joint", vec![], ctx);
                     create_fixed_joint
                 },
+                {
+                    create_rope_joint = create_menu_item("Rope Joint", vec![], ctx);
+                    create_rope_joint
+                },
+                {
+                    create_spring_joint = create_menu_item("Spring Joint", vec![], ctx);
+                    create_spring_joint
+                },
+                {
+                    create_generic_joint = create_menu_item("Generic Joint", vec![], ctx);
+                    create_generic_joint
+                },
+                {
+                    create_character_controller =
+                        create_menu_item("Character Controller", vec![], ctx);
+                    create_character_controller
+                },
             ],
             ctx,
         );
@@ -58,7 +84,11 @@ impl Physics2dMenu {
             create_ball_joint,
             create_prismatic_joint,
             create_fixed_joint,
+            create_rope_joint,
+            create_spring_joint,
+            create_generic_joint,
             create_collider,
+            create_character_controller,
         }
     }
 
@@ -87,12 +117,37 @@ impl Physics2dMenu {
                         .with_params(JointParams::FixedJoint(Default::default()))
                         .build_node(),
                 )
+            } else if message.destination() == self.create_rope_joint {
+                Some(
+                    JointBuilder::new(BaseBuilder::new().with_name("Rope Joint 2D"))
+                        .with_params(JointParams::RopeJoint(Default::default()))
+                        .build_node(),
+                )
+            } else if message.destination() == self.create_spring_joint {
+                Some(
+                    JointBuilder::new(BaseBuilder::new().with_name("Spring Joint 2D"))
+                        .with_params(JointParams::SpringJoint(Default::default()))
+                        .build_node(),
+                )
+            } else if message.destination() == self.create_generic_joint {
+                Some(
+                    JointBuilder::new(BaseBuilder::new().with_name("Generic Joint 2D"))
+                        .with_params(JointParams::GenericJoint(Default::default()))
+                        .build_node(),
+                )
             } else if message.destination == self.create_collider {
                 Some(
                     ColliderBuilder::new(BaseBuilder::new().with_name("Collider 2D"))
                         .with_shape(ColliderShape::Cuboid(Default::default()))
                         .build_node(),
                 )
+            } else if message.destination() == self.create_character_controller {
+                Some(
+                    CharacterControllerBuilder::new(
+                        BaseBuilder::new().with_name("Character Controller 2D"),
+                    )
+                    .build_node(),
+                )
             } else {
                 None
             }
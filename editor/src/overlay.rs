@@ -103,14 +103,17 @@ impl SceneRenderPass for OverlayRenderPass {
             .unwrap();
 
         for node in ctx.scene.graph.linear_iter() {
-            let icon =
-                if node.is_directional_light() || node.is_spot_light() || node.is_point_light() {
-                    light_icon.clone()
-                } else if node.is_sound() {
-                    sound_icon.clone()
-                } else {
-                    continue;
-                };
+            let icon = if node.is_directional_light()
+                || node.is_spot_light()
+                || node.is_point_light()
+                || node.is_area_light()
+            {
+                light_icon.clone()
+            } else if node.is_sound() {
+                sound_icon.clone()
+            } else {
+                continue;
+            };
 
             let position = node.global_position();
             let world_matrix = Matrix4::new_translation(&position);
@@ -25,7 +25,11 @@ use fyrox::{
         camera::Camera,
         debug::{Line, SceneDrawingContext},
         graph::Graph,
-        light::{point::PointLight, spot::SpotLight},
+        light::{
+            area::{AreaLight, AreaLightShape},
+            point::PointLight,
+            spot::SpotLight,
+        },
         mesh::{
             buffer::{VertexAttributeUsage, VertexReadTrait},
             Mesh,
@@ -319,6 +323,39 @@ impl EditorScene {
                     Color::GREEN,
                     false,
                 );
+            } else if let Some(light) = node.query_component_ref::<AreaLight>() {
+                // Shape of the light lies in local XZ plane, while the shapes below are drawn
+                // in XY plane.
+                let transform = light.global_transform()
+                    * Matrix4::from_axis_angle(&Vector3::x_axis(), std::f32::consts::FRAC_PI_2);
+                let half_size = light.size() * 0.5;
+                match light.shape() {
+                    AreaLightShape::Rectangle => {
+                        ctx.draw_rectangle(half_size.x, half_size.y, transform, Color::GREEN)
+                    }
+                    AreaLightShape::Disc => ctx.draw_circle(
+                        Default::default(),
+                        1.0,
+                        32,
+                        transform
+                            * Matrix4::new_nonuniform_scaling(&Vector3::new(
+                                half_size.x,
+                                half_size.y,
+                                1.0,
+                            )),
+                        Color::GREEN,
+                    ),
+                }
+                ctx.add_line(Line {
+                    begin: light.global_position(),
+                    end: light.global_position()
+                        - light
+                            .up_vector()
+                            .try_normalize(f32::EPSILON)
+                            .unwrap_or_default()
+                            .scale(half_size.x.max(half_size.y).max(0.5)),
+                    color: Color::GREEN,
+                });
             }
 
             for &child in node.children() {
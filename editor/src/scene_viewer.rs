@@ -27,7 +27,7 @@ use fyrox::{
         image::{ImageBuilder, ImageMessage},
         message::{KeyCode, MessageDirection, MouseButton, UiMessage},
         stack_panel::StackPanelBuilder,
-        text::TextBuilder,
+        text::{TextBuilder, TextMessage},
         utils::make_simple_tooltip,
         vec::vec3::{Vec3EditorBuilder, Vec3EditorMessage},
         widget::{WidgetBuilder, WidgetMessage},
@@ -36,6 +36,7 @@ use fyrox::{
         BRUSH_BRIGHT_BLUE, BRUSH_LIGHT, BRUSH_LIGHTER, BRUSH_LIGHTEST, COLOR_DARKEST,
         COLOR_LIGHTEST,
     },
+    renderer::Statistics,
     resource::texture::{Texture, TextureState},
     scene::camera::Projection,
     utils::into_gui_texture,
@@ -62,6 +63,7 @@ pub struct SceneViewer {
     interaction_mode_panel: Handle<UiNode>,
     contextual_actions: Handle<UiNode>,
     global_position_display: Handle<UiNode>,
+    statistics_display: Handle<UiNode>,
 }
 
 fn make_interaction_mode_button(
@@ -323,6 +325,7 @@ impl SceneViewer {
         .with_orientation(Orientation::Horizontal)
         .build(ctx);
 
+        let statistics_display;
         let window = WindowBuilder::new(WidgetBuilder::new())
             .can_close(false)
             .can_minimize(false)
@@ -346,6 +349,19 @@ impl SceneViewer {
                                         .build(ctx);
                                         frame
                                     })
+                                    .with_child({
+                                        statistics_display = TextBuilder::new(
+                                            WidgetBuilder::new()
+                                                .on_row(0)
+                                                .on_column(1)
+                                                .with_visibility(false)
+                                                .with_hit_test_visibility(false)
+                                                .with_margin(Thickness::uniform(4.0))
+                                                .with_foreground(Brush::Solid(Color::WHITE)),
+                                        )
+                                        .build(ctx);
+                                        statistics_display
+                                    })
                                     .with_child(
                                         CanvasBuilder::new(
                                             WidgetBuilder::new().on_column(1).with_child({
@@ -402,6 +418,7 @@ impl SceneViewer {
             interaction_mode_panel,
             contextual_actions,
             global_position_display,
+            statistics_display,
             build_profile,
         }
     }
@@ -593,6 +610,27 @@ impl SceneViewer {
         }
     }
 
+    pub fn update_statistics(&self, ui: &UserInterface, statistics: &Statistics, visible: bool) {
+        if ui.node(self.statistics_display).visibility() != visible {
+            ui.send_message(WidgetMessage::visibility(
+                self.statistics_display,
+                MessageDirection::ToWidget,
+                visible,
+            ));
+        }
+
+        if visible {
+            ui.send_message(TextMessage::text(
+                self.statistics_display,
+                MessageDirection::ToWidget,
+                format!(
+                    "{}\n{}\n{}",
+                    statistics.geometry, statistics.lighting, statistics.gpu_time
+                ),
+            ));
+        }
+    }
+
     pub fn on_mode_changed(&self, ui: &UserInterface, mode: &Mode) {
         let enabled = mode.is_edit();
         ui.send_message(ButtonMessage::content(
@@ -9,6 +9,8 @@ pub struct DebuggingSettings {
     pub show_physics: bool,
     pub show_bounds: bool,
     pub show_tbn: bool,
+    #[serde(default)]
+    pub show_statistics: bool,
 }
 
 impl Default for DebuggingSettings {
@@ -17,6 +19,7 @@ impl Default for DebuggingSettings {
             show_physics: true,
             show_bounds: true,
             show_tbn: false,
+            show_statistics: false,
         }
     }
 }
@@ -33,7 +33,7 @@ use fyrox::{
         window::{WindowBuilder, WindowMessage, WindowTitle},
         HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
     },
-    renderer::{CsmSettings, QualitySettings, ShadowMapPrecision},
+    renderer::{AntialiasingMode, CsmSettings, QualitySettings, ShadowMapPrecision, SsrSettings},
     utils::log::Log,
 };
 use ron::ser::PrettyConfig;
@@ -123,8 +123,10 @@ impl Settings {
         container.insert(InspectablePropertyEditorDefinition::<GraphicsSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<SelectionSettings>::new());
         container.insert(EnumPropertyEditorDefinition::<ShadowMapPrecision>::new());
+        container.insert(EnumPropertyEditorDefinition::<AntialiasingMode>::new());
         container.insert(InspectablePropertyEditorDefinition::<DebuggingSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<CsmSettings>::new());
+        container.insert(InspectablePropertyEditorDefinition::<SsrSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<QualitySettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<CameraSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<
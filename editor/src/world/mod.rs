@@ -80,7 +80,11 @@ fn make_graph_node_item(
     ctx: &mut BuildContext,
     context_menu: Handle<UiNode>,
 ) -> Handle<UiNode> {
-    let icon = if node.is_point_light() || node.is_directional_light() || node.is_spot_light() {
+    let icon = if node.is_point_light()
+        || node.is_directional_light()
+        || node.is_spot_light()
+        || node.is_area_light()
+    {
         load_image(include_bytes!("../../resources/embed/light.png"))
     } else if node.is_joint() || node.is_joint2d() {
         load_image(include_bytes!("../../resources/embed/joint.png"))
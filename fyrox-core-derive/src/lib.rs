@@ -8,7 +8,14 @@ use syn::{parse_macro_input, DeriveInput};
 
 /// Implements `Visit` trait
 ///
-/// User has to import `Visit`, `Visitor` and `VisitResult` to use this macro.
+/// User has to import `Visit`, `Visitor` and `VisitResult` to use this macro. `VisitError` must
+/// be imported as well if `version` or `rename_from` attributes are used.
+///
+/// Type layout can be versioned with `#[visit(version = N, migrate = "path::to::fn")]`. The version
+/// is stored alongside the data and data without it is treated as version `0`. Fields that are
+/// missing in data of older version keep their values, after that the migration function (if any)
+/// is called with the stored version: `fn(&mut self, version: u32, visitor: &mut Visitor) -> VisitResult`.
+/// Renamed fields can be loaded from old data with `#[visit(rename_from = "OldName")]`.
 #[proc_macro_derive(Visit, attributes(visit))]
 pub fn visit(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
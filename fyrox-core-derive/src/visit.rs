@@ -9,6 +9,7 @@ use syn::*;
 // impl `#[derive(Visit)]` for `struct` or `enum`
 pub fn impl_visit(ast: DeriveInput) -> TokenStream2 {
     let ty_args = args::TypeArgs::from_derive_input(&ast).unwrap();
+    ty_args.validate();
     match &ty_args.data {
         ast::Data::Struct(ref field_args) => self::impl_visit_struct(&ty_args, field_args),
         ast::Data::Enum(ref variants) => self::impl_visit_enum(&ty_args, variants),
@@ -24,15 +25,23 @@ fn impl_visit_struct(
         quote! { Ok(()) }
     } else {
         // `field.visit(..)?;` parts
-        let field_visits =
-            utils::create_field_visits(true, field_args.fields.iter(), field_args.style);
+        let field_visits = utils::create_field_visits(
+            true,
+            ty_args.version,
+            field_args.fields.iter(),
+            field_args.style,
+        );
+        let version_visit = utils::create_version_visit(ty_args);
+        let migrate_call = utils::create_migrate_call(ty_args);
 
         quote! {
             let mut region = match visitor.enter_region(name) {
                 Ok(x) => x,
                 Err(err) => return Err(err),
             };
+            #version_visit
             #(#field_visits)*
+            #migrate_call
             Ok(())
         }
     };
@@ -155,7 +164,8 @@ fn impl_visit_enum(ty_args: &args::TypeArgs, variant_args: &[args::VariantArgs])
 
         match style {
             ast::Style::Struct => {
-                let field_visits = utils::create_field_visits(false, fields.iter(), style);
+                let field_visits =
+                    utils::create_field_visits(false, ty_args.version, fields.iter(), style);
 
                 let idents = fields.iter().map(|field| {
                     let ident = &field.ident;
@@ -169,7 +179,8 @@ fn impl_visit_enum(ty_args: &args::TypeArgs, variant_args: &[args::VariantArgs])
                 }
             }
             ast::Style::Tuple => {
-                let field_visits = utils::create_field_visits(false, fields.iter(), style);
+                let field_visits =
+                    utils::create_field_visits(false, ty_args.version, fields.iter(), style);
 
                 let idents = (0..fields.len()).map(|i| format_ident!("f{}", Index::from(i)));
 
@@ -185,6 +196,9 @@ fn impl_visit_enum(ty_args: &args::TypeArgs, variant_args: &[args::VariantArgs])
         }
     });
 
+    let version_visit = utils::create_version_visit(ty_args);
+    let migrate_call = utils::create_migrate_call(ty_args);
+
     utils::create_impl(
         ty_args,
         variant_args.iter().flat_map(|v| v.fields.iter()).cloned(),
@@ -194,6 +208,8 @@ fn impl_visit_enum(ty_args: &args::TypeArgs, variant_args: &[args::VariantArgs])
                  Err(err) => return Err(err),
              };
 
+             #version_visit
+
              let mut id = id(self);
              if let Err(err) = id.visit("Id", &mut region) {
                  return Err(err);
@@ -210,6 +226,8 @@ fn impl_visit_enum(ty_args: &args::TypeArgs, variant_args: &[args::VariantArgs])
                  #(#variant_visits)*
              }
 
+             #migrate_call
+
              return Ok(());
 
              #fn_id
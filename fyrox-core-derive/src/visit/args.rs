@@ -9,6 +9,29 @@ pub struct TypeArgs {
     pub generics: Generics,
     pub data: ast::Data<VariantArgs, FieldArgs>,
     // attrs: Vec<Attribute>
+    // ---
+    /// `#[visit(version = N)]`
+    ///
+    /// Current version of the type layout, it is written to the stream and used to detect
+    /// old data on read
+    #[darling(default)]
+    pub version: Option<u32>,
+
+    /// `#[visit(migrate = "<function path>")]`
+    ///
+    /// Function that is called on read when stored version is older than the current one.
+    /// Expected signature: `fn(&mut self, version: u32, visitor: &mut Visitor) -> VisitResult`
+    #[darling(default)]
+    pub migrate: Option<Path>,
+}
+
+impl TypeArgs {
+    pub fn validate(&self) {
+        assert!(
+            self.migrate.is_none() || self.version.is_some(),
+            "`migrate` requires `version` to be set!"
+        );
+    }
 }
 
 /// Parsed from struct's or enum variant's field
@@ -37,6 +60,12 @@ pub struct FieldArgs {
     /// Ignore missing field
     #[darling(default)]
     pub optional: bool,
+
+    /// `#[visit(rename_from = "..")]`
+    ///
+    /// Old name of the field, it is used on read if there's no field with the actual name
+    #[darling(default)]
+    pub rename_from: Option<String>,
 }
 
 #[derive(FromVariant)]
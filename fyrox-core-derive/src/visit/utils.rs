@@ -48,10 +48,62 @@ fn create_impl_generics(
     generics
 }
 
+/// Name of the field that stores type version in the visitor stream.
+const VERSION_FIELD_NAME: &str = "__Version";
+
+/// Writes current version of the type or reads the stored one into `__version` local variable.
+/// Data without version field is treated as version `0`.
+pub fn create_version_visit(ty_args: &args::TypeArgs) -> TokenStream2 {
+    let version = match ty_args.version {
+        Some(version) => version,
+        None => return quote!(),
+    };
+    let ty_name = format!("{}", ty_args.ident);
+
+    quote! {
+        let mut __version: u32 = #version;
+        if region.is_reading() {
+            match __version.visit(#VERSION_FIELD_NAME, &mut region) {
+                Ok(_) => {
+                    if __version > #version {
+                        return Err(VisitError::User(format!(
+                            "Unsupported version {} of type `{}`, the latest supported version is {}",
+                            __version, #ty_name, #version
+                        )));
+                    }
+                }
+                Err(VisitError::FieldDoesNotExist(_)) => __version = 0,
+                Err(err) => return Err(err),
+            }
+        } else if let Err(err) = __version.visit(#VERSION_FIELD_NAME, &mut region) {
+            return Err(err);
+        }
+    }
+}
+
+/// Calls the migration function if stored data has older version than the current one.
+pub fn create_migrate_call(ty_args: &args::TypeArgs) -> TokenStream2 {
+    match (ty_args.version, &ty_args.migrate) {
+        (Some(version), Some(migrate)) => quote! {
+            if region.is_reading() && __version < #version {
+                if let Err(err) = #migrate(self, __version, &mut region) {
+                    return Err(err);
+                }
+            }
+        },
+        _ => quote!(),
+    }
+}
+
 /// `<prefix>field.visit("name", visitor)?;`
+///
+/// If `version` is set, fields that are missing in data of older version are left untouched
+/// so the migration function can fill them.
 pub fn create_field_visits<'a>(
     // false if enum variant
     is_struct: bool,
+    // current version of the type, if any
+    version: Option<u32>,
     fields: impl Iterator<Item = &'a args::FieldArgs>,
     field_style: ast::Style,
 ) -> Vec<TokenStream2> {
@@ -104,12 +156,12 @@ pub fn create_field_visits<'a>(
                 None => name,
             };
 
-            (ident, name, field.optional)
+            (ident, name, field.optional, field.rename_from.clone())
         })
         .collect::<Vec<_>>();
 
     let mut no_dup = FxHashSet::default();
-    for name in visit_args.iter().map(|(_, name, _)| name) {
+    for name in visit_args.iter().map(|(_, name, _, _)| name) {
         if !no_dup.insert(name) {
             panic!("duplicate visiting names detected!");
         }
@@ -119,18 +171,70 @@ pub fn create_field_visits<'a>(
 
     visit_args
         .iter()
-        .map(|(ident, name, optional)| {
+        .map(|(ident, name, optional, rename_from)| {
             if *optional {
-                quote! {
+                return quote! {
                     #prefix #ident.visit(#name, &mut region).ok();
-                }
-            } else {
-                quote! {
+                };
+            }
+
+            if version.is_none() && rename_from.is_none() {
+                return quote! {
                     if let Err(err) = #prefix #ident.visit(#name, &mut region) {
                         return Err(err);
                     }
+                };
+            }
+
+            // Data of older version is allowed to not have the field.
+            let on_missing = match version {
+                Some(version) => quote! {
+                    if __version >= #version {
+                        return Err(err);
+                    }
+                },
+                None => quote! {
+                    return Err(err);
+                },
+            };
+
+            let on_missing = match rename_from {
+                Some(old_name) => {
+                    let is_old_missing = is_missing_error(old_name);
+                    quote! {
+                        if let Err(err) = #prefix #ident.visit(#old_name, &mut region) {
+                            if !(#is_old_missing) {
+                                return Err(err);
+                            }
+                            #on_missing
+                        }
+                    }
+                }
+                None => on_missing,
+            };
+
+            let is_missing = is_missing_error(name);
+            quote! {
+                if let Err(err) = #prefix #ident.visit(#name, &mut region) {
+                    if !(region.is_reading() && #is_missing) {
+                        return Err(err);
+                    }
+                    #on_missing
                 }
             }
         })
         .collect::<Vec<_>>()
 }
+
+/// Expression that checks whether `err` is caused by absence of a field or region with the
+/// given name.
+fn is_missing_error(name: &str) -> TokenStream2 {
+    quote! {
+        match &err {
+            VisitError::FieldDoesNotExist(missing) | VisitError::RegionDoesNotExist(missing) => {
+                missing == #name
+            }
+            _ => false,
+        }
+    }
+}
@@ -25,3 +25,85 @@ fn rename() {
 
     assert_eq!(data, data_default);
 }
+
+#[derive(Debug, Clone, PartialEq, Visit)]
+pub struct OldLayout {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Visit)]
+#[visit(version = 1, migrate = "NewLayout::migrate")]
+pub struct NewLayout {
+    #[visit(rename_from = "X")]
+    pub horizontal: f32,
+    pub sum: f32,
+}
+
+impl NewLayout {
+    fn migrate(&mut self, version: u32, visitor: &mut Visitor) -> VisitResult {
+        if version == 0 {
+            let mut y = 0.0f32;
+            y.visit("Y", visitor)?;
+            self.sum = self.horizontal + y;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Visit)]
+#[visit(version = 2)]
+pub struct NewerLayout {
+    pub horizontal: f32,
+    pub sum: f32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Visit)]
+#[visit(version = 1)]
+pub struct PartialLayout {
+    pub horizontal: f32,
+}
+
+fn save_as<T: Visit, U: Visit>(data: &mut T, loaded: &mut U) -> VisitResult {
+    let mut visitor = Visitor::new();
+    data.visit("Data", &mut visitor)?;
+    let mut visitor = Visitor::load_from_memory(visitor.save_binary_to_vec()?)?;
+    loaded.visit("Data", &mut visitor)
+}
+
+#[test]
+fn migrate() {
+    let mut old = OldLayout { x: 1.0, y: 2.0 };
+    let mut new = NewLayout::default();
+    save_as(&mut old, &mut new).unwrap();
+    assert_eq!(
+        new,
+        NewLayout {
+            horizontal: 1.0,
+            sum: 3.0
+        }
+    );
+
+    // Current version must be loaded without migration.
+    let mut data = NewLayout {
+        horizontal: 2.0,
+        sum: 5.0,
+    };
+    let mut data_default = NewLayout::default();
+    super::save_load("migrate", &mut data, &mut data_default);
+    assert_eq!(data, data_default);
+
+    // Data of a newer version can't be loaded.
+    let mut newer = NewerLayout::default();
+    let mut new = NewLayout::default();
+    assert!(save_as(&mut newer, &mut new).is_err());
+
+    // Fields are allowed to be missing only in data of older versions.
+    let mut old = OldLayout { x: 1.0, y: 2.0 };
+    let mut newer = NewerLayout::default();
+    save_as(&mut old, &mut newer).unwrap();
+    assert_eq!(newer.sum, 0.0);
+    let mut partial = PartialLayout { horizontal: 1.0 };
+    let mut new = NewLayout::default();
+    assert!(save_as(&mut partial, &mut new).is_err());
+}
@@ -323,6 +323,12 @@ impl<T> VecExtensions<T> for Vec<T> {
     }
 }
 
+/// A trait for an entity that has unique type identifier.
+pub trait TypeUuidProvider: Sized {
+    /// Return type UUID.
+    fn type_uuid() -> uuid::Uuid;
+}
+
 #[inline]
 pub fn hash_combine(lhs: u64, rhs: u64) -> u64 {
     lhs ^ (rhs
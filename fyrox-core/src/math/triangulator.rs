@@ -168,10 +168,135 @@ pub fn triangulate(vertices: &[Vector3<f32>], out_triangles: &mut Vec<[usize; 3]
     }
 }
 
+/// Triangle of a point set triangulation with precomputed circumcircle.
+struct DelaunayTriangle {
+    indices: [usize; 3],
+    circumcenter: Vector2<f32>,
+    circumradius_sqr: f32,
+}
+
+impl DelaunayTriangle {
+    fn new(indices: [usize; 3], points: &[Vector2<f32>]) -> Self {
+        let a = points[indices[0]];
+        let b = points[indices[1]];
+        let c = points[indices[2]];
+
+        let d = 2.0 * (a.x * (b.y - c.y) + b.x * (c.y - a.y) + c.x * (a.y - b.y));
+        if d.abs() <= f32::EPSILON {
+            // Degenerate triangle, its circumcircle is infinitely large.
+            return Self {
+                indices,
+                circumcenter: (a + b + c).scale(1.0 / 3.0),
+                circumradius_sqr: f32::INFINITY,
+            };
+        }
+
+        let (a_sqr, b_sqr, c_sqr) = (a.norm_squared(), b.norm_squared(), c.norm_squared());
+        let circumcenter = Vector2::new(
+            (a_sqr * (b.y - c.y) + b_sqr * (c.y - a.y) + c_sqr * (a.y - b.y)) / d,
+            (a_sqr * (c.x - b.x) + b_sqr * (a.x - c.x) + c_sqr * (b.x - a.x)) / d,
+        );
+
+        Self {
+            indices,
+            circumcenter,
+            circumradius_sqr: (a - circumcenter).norm_squared(),
+        }
+    }
+
+    fn circumcircle_contains(&self, point: Vector2<f32>) -> bool {
+        (point - self.circumcenter).norm_squared() < self.circumradius_sqr
+    }
+
+    fn edges(&self) -> [[usize; 2]; 3] {
+        let [a, b, c] = self.indices;
+        [[a, b], [b, c], [c, a]]
+    }
+}
+
+///
+/// Triangulates specified set of points using Bowyer-Watson algorithm. The result is Delaunay
+/// triangulation - circumcircle of every triangle does not contain any other point, which means
+/// that triangles are as "fat" as possible. Returns empty set of triangles if there are less
+/// than three points or all points are collinear.
+///
+pub fn triangulate_points(points: &[Vector2<f32>]) -> Vec<[usize; 3]> {
+    if points.len() < 3 {
+        return Vec::new();
+    }
+
+    let mut min = points[0];
+    let mut max = points[0];
+    for point in points.iter() {
+        min = min.inf(point);
+        max = max.sup(point);
+    }
+
+    let size = (max - min).max();
+    if size <= f32::EPSILON {
+        return Vec::new();
+    }
+
+    // Create "super" triangle that contains every point of the set, it will be removed at the end.
+    let center = (min + max).scale(0.5);
+    let mut vertices = points.to_vec();
+    vertices.push(center + Vector2::new(-20.0 * size, -size));
+    vertices.push(center + Vector2::new(0.0, 20.0 * size));
+    vertices.push(center + Vector2::new(20.0 * size, -size));
+
+    let n = points.len();
+    let mut triangles = vec![DelaunayTriangle::new([n, n + 1, n + 2], &vertices)];
+
+    for (i, point) in points.iter().enumerate() {
+        // Find every triangle which circumcircle contains the point and gather a boundary of the
+        // polygonal hole formed by these triangles.
+        let mut boundary = Vec::<[usize; 2]>::new();
+        triangles.retain(|triangle| {
+            if triangle.circumcircle_contains(*point) {
+                for edge in triangle.edges() {
+                    if let Some(position) = boundary
+                        .iter()
+                        .position(|e| e[0] == edge[1] && e[1] == edge[0] || *e == edge)
+                    {
+                        // Shared edge is not a part of the boundary.
+                        boundary.remove(position);
+                    } else {
+                        boundary.push(edge);
+                    }
+                }
+                false
+            } else {
+                true
+            }
+        });
+
+        // Re-triangulate the hole.
+        for edge in boundary {
+            triangles.push(DelaunayTriangle::new([edge[0], edge[1], i], &vertices));
+        }
+    }
+
+    let min_area = size * size * f32::EPSILON;
+    triangles
+        .into_iter()
+        .filter(|triangle| {
+            triangle.indices.iter().all(|i| *i < n)
+                && math::get_signed_triangle_area(
+                    points[triangle.indices[0]],
+                    points[triangle.indices[1]],
+                    points[triangle.indices[2]],
+                )
+                .abs()
+                    > min_area
+        })
+        .map(|triangle| triangle.indices)
+        .collect()
+}
+
 #[cfg(test)]
 mod test {
-    use crate::algebra::{Point3, Unit, UnitQuaternion, Vector3};
-    use crate::math::triangulator::triangulate;
+    use crate::algebra::{Point3, Unit, UnitQuaternion, Vector2, Vector3};
+    use crate::math::triangulator::{triangulate, triangulate_points, DelaunayTriangle};
 
     #[test]
     fn quadrilaterals_triangulation_non_concave() {
@@ -239,4 +364,63 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn point_set_triangulation() {
+        let square = [
+            Vector2::new(0.0, 0.0),
+            Vector2::new(1.0, 0.0),
+            Vector2::new(1.0, 1.0),
+            Vector2::new(0.0, 1.0),
+        ];
+        assert_eq!(triangulate_points(&square).len(), 2);
+
+        let mut square_with_center = square.to_vec();
+        square_with_center.push(Vector2::new(0.5, 0.5));
+        let triangles = triangulate_points(&square_with_center);
+        assert_eq!(triangles.len(), 4);
+        assert!(triangles.iter().all(|t| t.contains(&4)));
+    }
+
+    #[test]
+    fn point_set_triangulation_degenerate() {
+        assert!(triangulate_points(&[]).is_empty());
+        assert!(triangulate_points(&[Vector2::new(0.0, 0.0), Vector2::new(1.0, 0.0)]).is_empty());
+        assert!(triangulate_points(&[
+            Vector2::new(0.0, 0.0),
+            Vector2::new(1.0, 0.0),
+            Vector2::new(2.0, 0.0),
+            Vector2::new(3.0, 0.0),
+        ])
+        .is_empty());
+    }
+
+    #[test]
+    fn point_set_triangulation_is_delaunay() {
+        let points = [
+            Vector2::new(0.0, 0.0),
+            Vector2::new(4.0, 0.3),
+            Vector2::new(-3.0, 1.0),
+            Vector2::new(1.5, 2.5),
+            Vector2::new(0.2, -2.7),
+            Vector2::new(-2.2, -1.9),
+            Vector2::new(3.1, -2.2),
+            Vector2::new(-0.7, 3.9),
+        ];
+
+        let triangles = triangulate_points(&points);
+        assert!(!triangles.is_empty());
+
+        for triangle in triangles.iter() {
+            let circle = DelaunayTriangle::new(*triangle, &points);
+            for (i, point) in points.iter().enumerate() {
+                if !triangle.contains(&i) {
+                    assert!(
+                        (point - circle.circumcenter).norm_squared()
+                            >= circle.circumradius_sqr - 0.0001
+                    );
+                }
+            }
+        }
+    }
 }
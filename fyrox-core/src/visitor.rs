@@ -207,72 +207,267 @@ impl<'a, T: Pod> Visit for PodVecView<'a, T> {
 }
 
 impl FieldKind {
-    fn as_string(&self) -> String {
+    fn write_text(&self, out: &mut String) {
+        fn write_floats<'a>(out: &mut String, kind: &str, values: impl Iterator<Item = &'a f32>) {
+            *out += kind;
+            *out += "(";
+            for (i, value) in values.enumerate() {
+                if i != 0 {
+                    *out += ", ";
+                }
+                *out += &value.to_string();
+            }
+            *out += ")";
+        }
+
         match self {
-            Self::Bool(data) => format!("<bool = {}>, ", data),
-            Self::U8(data) => format!("<u8 = {}>, ", data),
-            Self::I8(data) => format!("<i8 = {}>, ", data),
-            Self::U16(data) => format!("<u16 = {}>, ", data),
-            Self::I16(data) => format!("<i16 = {}>, ", data),
-            Self::U32(data) => format!("<u32 = {}>, ", data),
-            Self::I32(data) => format!("<i32 = {}>, ", data),
-            Self::U64(data) => format!("<u64 = {}>, ", data),
-            Self::I64(data) => format!("<i64 = {}>, ", data),
-            Self::F32(data) => format!("<f32 = {}>, ", data),
-            Self::F64(data) => format!("<f64 = {}>, ", data),
-            Self::Vector3(data) => format!("<vec3 = {}; {}; {}>, ", data.x, data.y, data.z),
+            Self::Bool(data) => *out += &format!("bool({})", data),
+            Self::U8(data) => *out += &format!("u8({})", data),
+            Self::I8(data) => *out += &format!("i8({})", data),
+            Self::U16(data) => *out += &format!("u16({})", data),
+            Self::I16(data) => *out += &format!("i16({})", data),
+            Self::U32(data) => *out += &format!("u32({})", data),
+            Self::I32(data) => *out += &format!("i32({})", data),
+            Self::U64(data) => *out += &format!("u64({})", data),
+            Self::I64(data) => *out += &format!("i64({})", data),
+            Self::F32(data) => *out += &format!("f32({})", data),
+            Self::F64(data) => *out += &format!("f64({})", data),
+            Self::Vector2(data) => write_floats(out, "vec2", data.iter()),
+            Self::Vector3(data) => write_floats(out, "vec3", data.iter()),
+            Self::Vector4(data) => write_floats(out, "vec4", data.iter()),
             Self::UnitQuaternion(data) => {
-                format!("<quat = {}; {}; {}; {}>, ", data.i, data.j, data.k, data.w)
+                write_floats(out, "quat", [data.i, data.j, data.k, data.w].iter())
             }
-            Self::Matrix4(data) => {
-                let mut out = String::from("<mat4 = ");
-                for f in data.iter() {
-                    out += format!("{}; ", f).as_str();
+            Self::UnitComplex(data) => write_floats(out, "complex", [data.re, data.im].iter()),
+            // Matrices are written in column-major order, the same order they're stored in memory.
+            Self::Matrix2(data) => write_floats(out, "mat2", data.iter()),
+            Self::Matrix3(data) => write_floats(out, "mat3", data.iter()),
+            Self::Matrix4(data) => write_floats(out, "mat4", data.iter()),
+            // Strings and paths are stored as raw bytes, keep them readable when possible.
+            Self::Data(data) => match std::str::from_utf8(data) {
+                Ok(string) => {
+                    *out += "str(";
+                    write_quoted(out, string);
+                    *out += ")";
                 }
-                out
-            }
-            Self::Data(data) => {
-                let out = match String::from_utf8(data.clone()) {
-                    Ok(s) => s,
-                    Err(_) => base64::encode(data),
-                };
-                format!("<data = {}>, ", out)
-            }
-            Self::Matrix3(data) => {
-                let mut out = String::from("<mat3 = ");
-                for f in data.iter() {
-                    out += format!("{}; ", f).as_str();
+                Err(_) => {
+                    *out += "data(";
+                    write_quoted(out, &base64::encode(data));
+                    *out += ")";
                 }
-                out
-            }
-            Self::Vector2(data) => format!("<vec2 = {}; {}>, ", data.x, data.y),
-            Self::Vector4(data) => {
-                format!("<vec4 = {}; {}; {}; {}>, ", data.x, data.y, data.z, data.w)
-            }
-            Self::Uuid(uuid) => uuid.to_string(),
-            Self::UnitComplex(data) => {
-                format!("<complex = {}; {}>, ", data.re, data.im)
+            },
+            Self::Uuid(uuid) => {
+                *out += "uuid(";
+                write_quoted(out, &uuid.to_string());
+                *out += ")";
             }
-            FieldKind::PodArray {
+            Self::PodArray {
                 type_id,
                 element_size,
                 bytes,
             } => {
-                let base64_encoded = base64::encode(bytes);
-                format!(
-                    "<podarray = {}; {}; [{}]>",
-                    type_id, element_size, base64_encoded
+                *out += &format!("pod({}, {}, ", type_id, element_size);
+                write_quoted(out, &base64::encode(bytes));
+                *out += ")";
+            }
+        }
+    }
+
+    fn read_text(reader: &mut TextReader) -> Result<Self, VisitError> {
+        let kind = reader.read_identifier()?;
+        reader.expect('(')?;
+        let field = match kind.as_str() {
+            "bool" => match reader.read_identifier()?.as_str() {
+                "true" => Self::Bool(true),
+                "false" => Self::Bool(false),
+                other => return Err(reader.error(format!("invalid bool value {}", other))),
+            },
+            "u8" => Self::U8(reader.read_number()?),
+            "i8" => Self::I8(reader.read_number()?),
+            "u16" => Self::U16(reader.read_number()?),
+            "i16" => Self::I16(reader.read_number()?),
+            "u32" => Self::U32(reader.read_number()?),
+            "i32" => Self::I32(reader.read_number()?),
+            "u64" => Self::U64(reader.read_number()?),
+            "i64" => Self::I64(reader.read_number()?),
+            "f32" => Self::F32(reader.read_number()?),
+            "f64" => Self::F64(reader.read_number()?),
+            "vec2" => Self::Vector2(Vector2::from_column_slice(&reader.read_floats::<2>()?)),
+            "vec3" => Self::Vector3(Vector3::from_column_slice(&reader.read_floats::<3>()?)),
+            "vec4" => Self::Vector4(Vector4::from_column_slice(&reader.read_floats::<4>()?)),
+            "quat" => {
+                let [x, y, z, w] = reader.read_floats::<4>()?;
+                Self::UnitQuaternion(UnitQuaternion::new_normalize(Quaternion::new(w, x, y, z)))
+            }
+            "complex" => {
+                let [re, im] = reader.read_floats::<2>()?;
+                Self::UnitComplex(UnitComplex::from_complex(Complex::new(re, im)))
+            }
+            "mat2" => Self::Matrix2(Matrix2::from_column_slice(&reader.read_floats::<4>()?)),
+            "mat3" => Self::Matrix3(Matrix3::from_column_slice(&reader.read_floats::<9>()?)),
+            "mat4" => Self::Matrix4(Matrix4::from_column_slice(&reader.read_floats::<16>()?)),
+            "str" => Self::Data(reader.read_string()?.into_bytes()),
+            "data" => Self::Data(reader.read_base64()?),
+            "uuid" => {
+                let string = reader.read_string()?;
+                Self::Uuid(
+                    Uuid::parse_str(&string)
+                        .map_err(|e| reader.error(format!("invalid uuid {}: {}", string, e)))?,
                 )
             }
-            Self::Matrix2(data) => {
-                let mut out = String::from("<mat2 = ");
-                for f in data.iter() {
-                    out += format!("{}; ", f).as_str();
+            "pod" => {
+                let type_id = reader.read_number()?;
+                reader.expect(',')?;
+                let element_size = reader.read_number()?;
+                reader.expect(',')?;
+                let bytes = reader.read_base64()?;
+                Self::PodArray {
+                    type_id,
+                    element_size,
+                    bytes,
                 }
-                out
             }
+            _ => return Err(reader.error(format!("unknown field type {}", kind))),
+        };
+        reader.expect(')')?;
+        Ok(field)
+    }
+}
+
+fn write_quoted(out: &mut String, string: &str) {
+    out.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => *out += "\\\"",
+            '\\' => *out += "\\\\",
+            '\n' => *out += "\\n",
+            '\r' => *out += "\\r",
+            '\t' => *out += "\\t",
+            _ => out.push(c),
         }
     }
+    out.push('"');
+}
+
+/// A simple reader of the text format produced by [`Visitor::save_text`]. It tracks current line
+/// to produce meaningful error messages.
+struct TextReader<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+}
+
+impl<'a> TextReader<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            chars: text.chars().peekable(),
+            line: 1,
+        }
+    }
+
+    fn error<S: AsRef<str>>(&self, message: S) -> VisitError {
+        VisitError::InvalidTextFormat(format!("line {}: {}", self.line, message.as_ref()))
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.chars.peek() {
+            if !c.is_whitespace() {
+                break;
+            }
+            if *c == '\n' {
+                self.line += 1;
+            }
+            self.chars.next();
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.chars.peek().cloned()
+    }
+
+    fn expect(&mut self, expected: char) -> VisitResult {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.chars.next();
+                Ok(())
+            }
+            Some(c) => Err(self.error(format!("expected '{}', got '{}'", expected, c))),
+            None => Err(self.error(format!("expected '{}', got end of file", expected))),
+        }
+    }
+
+    fn read_while<F: Fn(char) -> bool>(&mut self, predicate: F) -> String {
+        self.skip_whitespace();
+        let mut out = String::new();
+        while let Some(c) = self.chars.peek() {
+            if !predicate(*c) {
+                break;
+            }
+            out.push(*c);
+            self.chars.next();
+        }
+        out
+    }
+
+    fn read_identifier(&mut self) -> Result<String, VisitError> {
+        let identifier = self.read_while(|c| c.is_ascii_alphanumeric() || c == '_');
+        if identifier.is_empty() {
+            Err(self.error("expected identifier"))
+        } else {
+            Ok(identifier)
+        }
+    }
+
+    fn read_number<T: std::str::FromStr>(&mut self) -> Result<T, VisitError> {
+        let string =
+            self.read_while(|c| c.is_ascii_alphanumeric() || c == '-' || c == '+' || c == '.');
+        string
+            .parse()
+            .map_err(|_| self.error(format!("invalid number {}", string)))
+    }
+
+    fn read_floats<const N: usize>(&mut self) -> Result<[f32; N], VisitError> {
+        let mut values = [0.0; N];
+        for (i, value) in values.iter_mut().enumerate() {
+            if i != 0 {
+                self.expect(',')?;
+            }
+            *value = self.read_number()?;
+        }
+        Ok(values)
+    }
+
+    fn read_string(&mut self) -> Result<String, VisitError> {
+        self.expect('"')?;
+        let mut out = String::new();
+        loop {
+            match self.chars.next() {
+                Some('"') => return Ok(out),
+                Some('\\') => match self.chars.next() {
+                    Some('"') => out.push('"'),
+                    Some('\\') => out.push('\\'),
+                    Some('n') => out.push('\n'),
+                    Some('r') => out.push('\r'),
+                    Some('t') => out.push('\t'),
+                    Some(c) => return Err(self.error(format!("unknown escape sequence \\{}", c))),
+                    None => break,
+                },
+                Some(c) => {
+                    if c == '\n' {
+                        self.line += 1;
+                    }
+                    out.push(c)
+                }
+                None => break,
+            }
+        }
+        Err(self.error("unterminated string"))
+    }
+
+    fn read_base64(&mut self) -> Result<Vec<u8>, VisitError> {
+        let string = self.read_string()?;
+        base64::decode(&string).map_err(|e| self.error(format!("invalid base64 data: {}", e)))
+    }
 }
 
 macro_rules! impl_field_data {
@@ -379,6 +574,7 @@ pub enum VisitError {
     UnexpectedRcNullIndex,
     PoisonedMutex,
     FileLoadError(FileLoadError),
+    InvalidTextFormat(String),
 }
 
 impl Display for VisitError {
@@ -401,6 +597,7 @@ impl Display for VisitError {
             Self::UnexpectedRcNullIndex => write!(f, "unexpected rc null index"),
             Self::PoisonedMutex => write!(f, "attempt to lock poisoned mutex"),
             Self::FileLoadError(e) => write!(f, "file load error: {:?}", e),
+            Self::InvalidTextFormat(msg) => write!(f, "invalid text format: {}", msg),
         }
     }
 }
@@ -676,8 +873,10 @@ impl Field {
         ))
     }
 
-    fn as_string(&self) -> String {
-        format!("{}{}", self.name, self.kind.as_string())
+    fn save_text(field: &Field, out: &mut String) {
+        write_quoted(out, &field.name);
+        *out += ": ";
+        field.kind.write_text(out);
     }
 }
 
@@ -757,6 +956,7 @@ impl Default for Visitor {
 
 impl Visitor {
     const MAGIC: &'static str = "RG3D";
+    const TEXT_MAGIC: &'static str = "RG3DTXT";
 
     pub fn new() -> Self {
         let mut nodes = Pool::new();
@@ -840,32 +1040,41 @@ impl Visitor {
         }
     }
 
-    fn print_node(&self, node_handle: Handle<Node>, nesting: usize, out_string: &mut String) {
-        let offset = (0..nesting).map(|_| "\t").collect::<String>();
+    fn save_node_text(&self, node_handle: Handle<Node>, nesting: usize, out: &mut String) {
         let node = self.nodes.borrow(node_handle);
-        *out_string += format!(
-            "{}{}[Fields={}, Children={}]: ",
-            offset,
-            node.name,
-            node.fields.len(),
-            node.children.len()
-        )
-        .as_str();
+        let offset = "\t".repeat(nesting);
+        *out += &offset;
+        write_quoted(out, &node.name);
+        *out += " {\n";
         for field in node.fields.iter() {
-            *out_string += field.as_string().as_str();
+            *out += &offset;
+            *out += "\t";
+            Field::save_text(field, out);
+            *out += "\n";
         }
-
-        *out_string += "\n";
-
         for child_handle in node.children.iter() {
-            self.print_node(*child_handle, nesting + 1, out_string);
+            self.save_node_text(*child_handle, nesting + 1, out);
         }
+        *out += &offset;
+        *out += "}\n";
     }
 
+    /// Writes the visitor's content in human-readable text format. Unlike binary format, the
+    /// text one can be easily compared or merged by version control systems. The output can be
+    /// loaded back using [`Self::load_text`] or [`Self::load_from_text`].
     pub fn save_text(&self) -> String {
-        let mut out_string = String::new();
-        self.print_node(self.root, 0, &mut out_string);
-        out_string
+        let mut out = String::new();
+        out += Self::TEXT_MAGIC;
+        out += "\n";
+        self.save_node_text(self.root, 0, &mut out);
+        out
+    }
+
+    /// Writes the visitor's content in text format to a file at the given path.
+    pub fn save_text_to_file<P: AsRef<Path>>(&self, path: P) -> VisitResult {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(self.save_text().as_bytes())?;
+        Ok(())
     }
 
     pub fn save_binary_to_memory<W: Write>(&self, mut writer: W) -> VisitResult {
@@ -932,11 +1141,95 @@ impl Visitor {
         Ok(handle)
     }
 
+    fn load_node_text(
+        &mut self,
+        name: String,
+        reader: &mut TextReader,
+    ) -> Result<Handle<Node>, VisitError> {
+        let mut node = Node {
+            name,
+            ..Node::default()
+        };
+
+        reader.expect('{')?;
+        let mut children = Vec::new();
+        loop {
+            match reader.peek() {
+                Some('}') => {
+                    reader.expect('}')?;
+                    break;
+                }
+                Some('"') => {
+                    // Both fields and child nodes start with a name, they can be distinguished
+                    // only by a character after the name.
+                    let name = reader.read_string()?;
+                    if reader.peek() == Some(':') {
+                        reader.expect(':')?;
+                        node.fields
+                            .push(Field::new(&name, FieldKind::read_text(reader)?));
+                    } else {
+                        children.push(self.load_node_text(name, reader)?);
+                    }
+                }
+                Some(c) => return Err(reader.error(format!("unexpected character '{}'", c))),
+                None => return Err(reader.error("unexpected end of file")),
+            }
+        }
+
+        node.children = children.clone();
+
+        let handle = self.nodes.spawn(node);
+        for child_handle in children.iter() {
+            let child = self.nodes.borrow_mut(*child_handle);
+            child.parent = handle;
+        }
+
+        Ok(handle)
+    }
+
+    /// Loads a file that was previously written in any of supported formats (binary or text).
+    /// The format is detected automatically.
     pub async fn load_binary<P: AsRef<Path>>(path: P) -> Result<Self, VisitError> {
         Self::load_from_memory(io::load_file(path).await?)
     }
 
+    /// Loads a text file that was previously written by [`Self::save_text_to_file`].
+    pub async fn load_text<P: AsRef<Path>>(path: P) -> Result<Self, VisitError> {
+        let data = io::load_file(path).await?;
+        Self::load_from_text(&String::from_utf8(data)?)
+    }
+
+    /// Loads visitor from a string, that was previously produced by [`Self::save_text`].
+    pub fn load_from_text(text: &str) -> Result<Self, VisitError> {
+        let mut reader = TextReader::new(text);
+        if reader.read_while(|c| !c.is_whitespace()) != Self::TEXT_MAGIC {
+            return Err(VisitError::NotSupportedFormat);
+        }
+        let mut visitor = Self {
+            nodes: Pool::new(),
+            rc_map: Default::default(),
+            arc_map: Default::default(),
+            reading: true,
+            current_node: Handle::NONE,
+            root: Handle::NONE,
+            environment: None,
+        };
+        let root_name = reader.read_string()?;
+        visitor.root = visitor.load_node_text(root_name, &mut reader)?;
+        if let Some(c) = reader.peek() {
+            return Err(reader.error(format!("unexpected character '{}'", c)));
+        }
+        visitor.current_node = visitor.root;
+        Ok(visitor)
+    }
+
+    /// Loads visitor from a memory buffer. The buffer could contain data in either binary or
+    /// text format, the format is detected automatically.
     pub fn load_from_memory(data: Vec<u8>) -> Result<Self, VisitError> {
+        if data.starts_with(Self::TEXT_MAGIC.as_bytes()) {
+            return Self::load_from_text(&String::from_utf8(data)?);
+        }
+
         let mut reader = Cursor::new(data);
         let mut magic: [u8; 4] = Default::default();
         reader.read_exact(&mut magic)?;
@@ -1446,6 +1739,30 @@ impl<T: Default + Visit, const SIZE: usize> Visit for [T; SIZE] {
     }
 }
 
+/// `usize` is platform-dependent, so it is always saved as `u64`.
+impl Visit for usize {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut value = *self as u64;
+        value.visit(name, visitor)?;
+        if visitor.is_reading() {
+            *self = value as usize;
+        }
+        Ok(())
+    }
+}
+
+/// `isize` is platform-dependent, so it is always saved as `i64`.
+impl Visit for isize {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut value = *self as i64;
+        value.visit(name, visitor)?;
+        if visitor.is_reading() {
+            *self = value as isize;
+        }
+        Ok(())
+    }
+}
+
 impl Visit for Duration {
     fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
         let mut region = visitor.enter_region(name)?;
@@ -1477,7 +1794,10 @@ impl<T: Visit> Visit for Range<T> {
 
 #[cfg(test)]
 mod test {
-    use crate::visitor::{Data, Visit, VisitResult, Visitor};
+    use crate::{
+        algebra::{Matrix4, UnitQuaternion, Vector3},
+        visitor::{Data, Visit, VisitResult, Visitor},
+    };
     use std::{fs::File, io::Write, path::Path, rc::Rc};
 
     #[derive(Visit, Default)]
@@ -1581,4 +1901,60 @@ mod test {
             objects.visit("Objects", &mut visitor).unwrap();
         }
     }
+
+    #[test]
+    fn visitor_text_test() {
+        let mut resource = Rc::new(Resource::new(ResourceKind::Texture(Texture {
+            data: vec![0, 1, 2, 255],
+        })));
+        let mut name = String::from("Name with \"quotes\"\nand a new line");
+        let mut position = Vector3::new(1.0f32, -2.5, 1.0e-7);
+        let mut rotation = UnitQuaternion::from_euler_angles(0.1f32, 0.2, 0.3);
+        let mut matrix = Matrix4::new_translation(&position);
+        let mut values = vec![f64::MAX, f64::MIN_POSITIVE];
+
+        let text = {
+            let mut visitor = Visitor::new();
+            resource.visit("Resource", &mut visitor).unwrap();
+            name.visit("Name", &mut visitor).unwrap();
+            position.visit("Position", &mut visitor).unwrap();
+            rotation.visit("Rotation", &mut visitor).unwrap();
+            matrix.visit("Matrix", &mut visitor).unwrap();
+            values.visit("Values", &mut visitor).unwrap();
+            visitor.save_text()
+        };
+
+        let mut visitor = Visitor::load_from_memory(text.clone().into_bytes()).unwrap();
+        let mut loaded_resource: Rc<Resource> = Rc::new(Default::default());
+        loaded_resource.visit("Resource", &mut visitor).unwrap();
+        let mut loaded_name = String::new();
+        loaded_name.visit("Name", &mut visitor).unwrap();
+        let mut loaded_position = Vector3::default();
+        loaded_position.visit("Position", &mut visitor).unwrap();
+        let mut loaded_rotation = UnitQuaternion::default();
+        loaded_rotation.visit("Rotation", &mut visitor).unwrap();
+        let mut loaded_matrix = Matrix4::default();
+        loaded_matrix.visit("Matrix", &mut visitor).unwrap();
+        let mut loaded_values = Vec::<f64>::new();
+        loaded_values.visit("Values", &mut visitor).unwrap();
+
+        match &loaded_resource.kind {
+            ResourceKind::Texture(texture) => assert_eq!(texture.data, vec![0, 1, 2, 255]),
+            _ => panic!("resource kind mismatch"),
+        }
+        assert_eq!(loaded_name, name);
+        assert_eq!(loaded_position, position);
+        assert!(loaded_rotation.angle_to(&rotation) < 1.0e-6);
+        assert_eq!(loaded_matrix, matrix);
+        assert_eq!(loaded_values, values);
+
+        // Strings must be readable, binary data is encoded.
+        assert!(text.contains(r#"str("Name with \"quotes\"\nand a new line")"#));
+        assert!(text.contains("data("));
+
+        // Saving loaded visitor again must produce exactly the same text.
+        assert_eq!(Visitor::load_from_text(&text).unwrap().save_text(), text);
+
+        assert!(Visitor::load_from_text("RG3DTXT\n\"__ROOT__\" { \"Foo\": u8(1) ").is_err());
+    }
 }
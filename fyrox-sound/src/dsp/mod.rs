@@ -50,6 +50,31 @@ impl DelayLine {
     pub fn last(&self) -> f32 {
         self.last
     }
+
+    /// Returns a sample that was fed `delay` samples ago, fractional delays are linearly
+    /// interpolated. Delay is clamped to `[1; len]` range. This method allows you to create
+    /// modulated delays that are used in effects like chorus or flanger.
+    pub fn tap(&self, delay: f32) -> f32 {
+        let len = self.samples.len();
+        let delay = delay.max(1.0).min(len as f32);
+        let whole = delay as usize;
+        let fraction = delay - whole as f32;
+        let sample_at = |offset: usize| {
+            let offset = offset.min(len);
+            self.samples[(self.pos as usize + len - offset) % len]
+        };
+        let a = sample_at(whole);
+        let b = sample_at(whole + 1);
+        a + (b - a) * fraction
+    }
+
+    /// Clears the delay line.
+    pub fn clear(&mut self) {
+        for sample in self.samples.iter_mut() {
+            *sample = 0.0;
+        }
+        self.last = 0.0;
+    }
 }
 
 impl Default for DelayLine {
@@ -0,0 +1,216 @@
+//! Chorus and flanger module
+//!
+//! # Overview
+//!
+//! Chorus mixes input signal with its copies delayed by a slowly changing amount of time, which
+//! gives an impression of multiple sources playing the same sound. Flanger is the same effect, but
+//! with much shorter delays and strong feedback, which gives a distinctive "swooshing" sound. Both
+//! effects are covered by [`Chorus`], use [`Chorus::flanger`] to create one with flanger settings.
+//!
+//! # Usage
+//!
+//! ```
+//! use fyrox_sound::context::SoundContext;
+//! use fyrox_sound::effects::chorus::Chorus;
+//! use fyrox_sound::effects::{Effect, BaseEffect};
+//!
+//! fn add_chorus(context: &mut SoundContext) {
+//!     let mut chorus = Chorus::new(BaseEffect::default());
+//!     chorus.set_rate(0.8);
+//!     context.state().add_effect(Effect::Chorus(chorus));
+//! }
+//! ```
+
+use crate::{
+    context::{DistanceModel, SAMPLE_RATE},
+    dsp::DelayLine,
+    effects::{BaseEffect, EffectRenderTrait},
+    listener::Listener,
+    source::SoundSource,
+};
+use fyrox_core::{
+    pool::Pool,
+    visitor::{Visit, VisitResult, Visitor},
+};
+use std::{
+    f32::consts::PI,
+    ops::{Deref, DerefMut},
+    time::Duration,
+};
+
+/// Max total delay (base delay + depth) that can be set, it defines the size of internal delay
+/// lines.
+pub const MAX_CHORUS_DELAY: Duration = Duration::from_millis(100);
+
+/// Phase shift between left and right channels modulation, it makes the effect wider.
+const STEREO_PHASE_SHIFT: f32 = PI * 0.5;
+
+/// See module docs.
+#[derive(Debug, Clone, Visit)]
+pub struct Chorus {
+    base: BaseEffect,
+    delay: Duration,
+    depth: Duration,
+    rate: f32,
+    feedback: f32,
+    dry: f32,
+    wet: f32,
+    #[visit(skip)]
+    phase: f32,
+    #[visit(skip)]
+    left: DelayLine,
+    #[visit(skip)]
+    right: DelayLine,
+}
+
+impl Default for Chorus {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+impl Chorus {
+    /// Creates new chorus effect with 20 ms base delay, 5 ms modulation depth and 0.5 Hz
+    /// modulation rate.
+    pub fn new(base: BaseEffect) -> Self {
+        let len = (MAX_CHORUS_DELAY.as_secs_f32() * SAMPLE_RATE as f32) as usize + 2;
+        Self {
+            base,
+            delay: Duration::from_millis(20),
+            depth: Duration::from_millis(5),
+            rate: 0.5,
+            feedback: 0.0,
+            dry: 1.0,
+            wet: 0.5,
+            phase: 0.0,
+            left: DelayLine::new(len),
+            right: DelayLine::new(len),
+        }
+    }
+
+    /// Creates new effect with flanger settings - 3 ms base delay, 2 ms modulation depth,
+    /// 0.25 Hz modulation rate and strong feedback.
+    pub fn flanger(base: BaseEffect) -> Self {
+        let mut flanger = Self::new(base);
+        flanger.set_delay(Duration::from_millis(3));
+        flanger.set_depth(Duration::from_millis(2));
+        flanger.set_rate(0.25);
+        flanger.set_feedback(0.7);
+        flanger
+    }
+
+    /// Sets base delay of the copy of the signal. Chorus usually uses 15-30 ms, flanger 1-5 ms.
+    /// Sum of delay and depth is clamped to [`MAX_CHORUS_DELAY`].
+    pub fn set_delay(&mut self, delay: Duration) {
+        self.delay = delay.min(MAX_CHORUS_DELAY);
+        self.depth = self.depth.min(MAX_CHORUS_DELAY - self.delay);
+    }
+
+    /// Returns base delay of the copy of the signal.
+    pub fn delay(&self) -> Duration {
+        self.delay
+    }
+
+    /// Sets max deviation of the delay from the base delay.
+    pub fn set_depth(&mut self, depth: Duration) {
+        self.depth = depth.min(MAX_CHORUS_DELAY - self.delay);
+    }
+
+    /// Returns max deviation of the delay from the base delay.
+    pub fn depth(&self) -> Duration {
+        self.depth
+    }
+
+    /// Sets frequency (in Hz) of delay modulation.
+    pub fn set_rate(&mut self, rate: f32) {
+        self.rate = rate.max(0.0);
+    }
+
+    /// Returns frequency (in Hz) of delay modulation.
+    pub fn rate(&self) -> f32 {
+        self.rate
+    }
+
+    /// Sets how much of delayed signal will be fed back to the input. Flanger usually uses strong
+    /// feedback. For numeric stability the value is clamped to `[-0.99; 0.99]`.
+    pub fn set_feedback(&mut self, feedback: f32) {
+        self.feedback = feedback.clamp(-0.99, 0.99);
+    }
+
+    /// Returns current feedback factor.
+    pub fn feedback(&self) -> f32 {
+        self.feedback
+    }
+
+    /// Sets how much of input signal should be passed to output without any processing.
+    /// Default value is 1.0.
+    pub fn set_dry(&mut self, dry: f32) {
+        self.dry = dry.clamp(0.0, 1.0);
+    }
+
+    /// Returns dry part.
+    pub fn dry(&self) -> f32 {
+        self.dry
+    }
+
+    /// Sets how much of modulated signal should be passed to output. Default value is 0.5.
+    pub fn set_wet(&mut self, wet: f32) {
+        self.wet = wet.clamp(0.0, 1.0);
+    }
+
+    /// Returns wet part.
+    pub fn wet(&self) -> f32 {
+        self.wet
+    }
+}
+
+impl EffectRenderTrait for Chorus {
+    fn render(
+        &mut self,
+        sources: &Pool<SoundSource>,
+        listener: &Listener,
+        distance_model: DistanceModel,
+        mix_buf: &mut [(f32, f32)],
+    ) {
+        self.base
+            .render(sources, listener, distance_model, mix_buf.len());
+
+        let delay = self.delay.as_secs_f32() * SAMPLE_RATE as f32;
+        let depth = self.depth.as_secs_f32() * SAMPLE_RATE as f32;
+        let phase_step = 2.0 * PI * self.rate / SAMPLE_RATE as f32;
+
+        for ((out_left, out_right), &(left, right)) in
+            mix_buf.iter_mut().zip(self.base.frame_samples.iter())
+        {
+            let delayed_left = self.left.tap(delay + depth * self.phase.sin());
+            let delayed_right = self
+                .right
+                .tap(delay + depth * (self.phase + STEREO_PHASE_SHIFT).sin());
+
+            self.left.feed(left + delayed_left * self.feedback);
+            self.right.feed(right + delayed_right * self.feedback);
+
+            *out_left += self.base.gain * (self.dry * left + self.wet * delayed_left);
+            *out_right += self.base.gain * (self.dry * right + self.wet * delayed_right);
+
+            self.phase += phase_step;
+            if self.phase > 2.0 * PI {
+                self.phase -= 2.0 * PI;
+            }
+        }
+    }
+}
+
+impl Deref for Chorus {
+    type Target = BaseEffect;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Chorus {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
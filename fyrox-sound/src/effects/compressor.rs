@@ -0,0 +1,268 @@
+//! Dynamic range compressor module
+//!
+//! # Overview
+//!
+//! Compressor reduces volume of loud sounds, narrowing dynamic range of the signal. When the level
+//! of the signal goes above threshold, it is reduced according to the ratio - with ratio 4:1 every
+//! 4 dB above threshold become 1 dB. Limiter is a compressor with very high ratio and fast attack,
+//! it does not allow the signal to go above threshold. Use [`Compressor::limiter`] to create one.
+//! Both left and right channels are compressed by the same amount to keep stereo image stable.
+//!
+//! # Usage
+//!
+//! ```
+//! use fyrox_sound::context::SoundContext;
+//! use fyrox_sound::effects::compressor::Compressor;
+//! use fyrox_sound::effects::{Effect, BaseEffect};
+//!
+//! fn add_compressor(context: &mut SoundContext) {
+//!     let mut compressor = Compressor::new(BaseEffect::default());
+//!     compressor.set_threshold(-12.0);
+//!     compressor.set_ratio(4.0);
+//!     context.state().add_effect(Effect::Compressor(compressor));
+//! }
+//! ```
+
+use crate::{
+    context::{DistanceModel, SAMPLE_RATE},
+    effects::{BaseEffect, EffectRenderTrait},
+    listener::Listener,
+    source::SoundSource,
+};
+use fyrox_core::{
+    pool::Pool,
+    visitor::{Visit, VisitResult, Visitor},
+};
+use std::{
+    ops::{Deref, DerefMut},
+    time::Duration,
+};
+
+/// Converts linear amplitude to decibels.
+pub fn linear_to_db(linear: f32) -> f32 {
+    20.0 * linear.max(1.0e-6).log10()
+}
+
+/// Converts decibels to linear amplitude.
+pub fn db_to_linear(db: f32) -> f32 {
+    10.0f32.powf(db / 20.0)
+}
+
+/// Calculates smoothing coefficient of one-pole envelope follower for the given time.
+fn time_coefficient(time: Duration) -> f32 {
+    let samples = time.as_secs_f32() * SAMPLE_RATE as f32;
+    if samples <= 0.0 {
+        0.0
+    } else {
+        (-1.0 / samples).exp()
+    }
+}
+
+/// See module docs.
+#[derive(Debug, Clone, Visit)]
+pub struct Compressor {
+    base: BaseEffect,
+    threshold: f32,
+    ratio: f32,
+    attack: Duration,
+    release: Duration,
+    makeup_gain: f32,
+    #[visit(skip)]
+    envelope: f32,
+}
+
+impl Default for Compressor {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+impl Compressor {
+    /// Creates new compressor with -18 dB threshold, 4:1 ratio, 10 ms attack and 100 ms release.
+    pub fn new(base: BaseEffect) -> Self {
+        Self {
+            base,
+            threshold: -18.0,
+            ratio: 4.0,
+            attack: Duration::from_millis(10),
+            release: Duration::from_millis(100),
+            makeup_gain: 0.0,
+            envelope: 0.0,
+        }
+    }
+
+    /// Creates new compressor with limiter settings - -1 dB threshold, 100:1 ratio, almost
+    /// immediate attack and 50 ms release.
+    pub fn limiter(base: BaseEffect) -> Self {
+        let mut limiter = Self::new(base);
+        limiter.set_threshold(-1.0);
+        limiter.set_ratio(100.0);
+        limiter.set_attack(Duration::from_micros(100));
+        limiter.set_release(Duration::from_millis(50));
+        limiter
+    }
+
+    /// Sets level (in dB) above which the signal will be compressed. It should be negative,
+    /// `0 dB` is the max level of the signal.
+    pub fn set_threshold(&mut self, threshold: f32) {
+        self.threshold = threshold.min(0.0);
+    }
+
+    /// Returns level (in dB) above which the signal will be compressed.
+    pub fn threshold(&self) -> f32 {
+        self.threshold
+    }
+
+    /// Sets compression ratio, for example with ratio 4.0 every 4 dB above threshold become 1 dB.
+    /// The value is clamped to be at least 1.0 (no compression).
+    pub fn set_ratio(&mut self, ratio: f32) {
+        self.ratio = ratio.max(1.0);
+    }
+
+    /// Returns compression ratio.
+    pub fn ratio(&self) -> f32 {
+        self.ratio
+    }
+
+    /// Sets how fast the compressor reacts on signal that goes above threshold.
+    pub fn set_attack(&mut self, attack: Duration) {
+        self.attack = attack;
+    }
+
+    /// Returns attack time.
+    pub fn attack(&self) -> Duration {
+        self.attack
+    }
+
+    /// Sets how fast the compressor stops to reduce the signal after it went below threshold.
+    pub fn set_release(&mut self, release: Duration) {
+        self.release = release;
+    }
+
+    /// Returns release time.
+    pub fn release(&self) -> Duration {
+        self.release
+    }
+
+    /// Sets gain (in dB) that is applied after compression, it is used to compensate volume
+    /// loss caused by compression.
+    pub fn set_makeup_gain(&mut self, makeup_gain: f32) {
+        self.makeup_gain = makeup_gain;
+    }
+
+    /// Returns gain (in dB) that is applied after compression.
+    pub fn makeup_gain(&self) -> f32 {
+        self.makeup_gain
+    }
+
+    /// Processes single frame of the signal using smoothing coefficients of attack and release.
+    /// Gain of the effect is not applied.
+    fn process(&mut self, attack: f32, release: f32, left: f32, right: f32) -> (f32, f32) {
+        // Peak envelope follower, left and right channels are linked.
+        let peak = left.abs().max(right.abs());
+        let k = if peak > self.envelope {
+            attack
+        } else {
+            release
+        };
+        self.envelope = peak + k * (self.envelope - peak);
+
+        let gain =
+            db_to_linear(self.gain_reduction(linear_to_db(self.envelope)) + self.makeup_gain);
+
+        (left * gain, right * gain)
+    }
+
+    /// Calculates gain reduction (in dB) for the given level of the signal (in dB).
+    fn gain_reduction(&self, level: f32) -> f32 {
+        let overshoot = level - self.threshold;
+        if overshoot > 0.0 {
+            overshoot * (1.0 / self.ratio - 1.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+impl EffectRenderTrait for Compressor {
+    fn render(
+        &mut self,
+        sources: &Pool<SoundSource>,
+        listener: &Listener,
+        distance_model: DistanceModel,
+        mix_buf: &mut [(f32, f32)],
+    ) {
+        self.base
+            .render(sources, listener, distance_model, mix_buf.len());
+
+        let attack = time_coefficient(self.attack);
+        let release = time_coefficient(self.release);
+
+        for (i, (out_left, out_right)) in mix_buf.iter_mut().enumerate() {
+            let (left, right) = self.base.frame_samples[i];
+            let (left, right) = self.process(attack, release, left, right);
+
+            *out_left += self.base.gain * left;
+            *out_right += self.base.gain * right;
+        }
+    }
+}
+
+impl Deref for Compressor {
+    type Target = BaseEffect;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Compressor {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::effects::{
+        compressor::{db_to_linear, time_coefficient, Compressor},
+        BaseEffect,
+    };
+    use std::time::Duration;
+
+    fn run(compressor: &mut Compressor, level: f32, frames: usize) -> (f32, f32) {
+        let attack = time_coefficient(compressor.attack());
+        let release = time_coefficient(compressor.release());
+        let mut output = (0.0, 0.0);
+        for _ in 0..frames {
+            output = compressor.process(attack, release, level, -level);
+        }
+        output
+    }
+
+    #[test]
+    fn test_compressor_gain() {
+        let mut compressor = Compressor::new(BaseEffect::default());
+        compressor.set_threshold(-20.0);
+        compressor.set_ratio(4.0);
+        compressor.set_attack(Duration::from_millis(1));
+        compressor.set_release(Duration::from_millis(1));
+
+        // -26 dB signal is below threshold and must not be changed.
+        let quiet = db_to_linear(-26.0);
+        let (left, right) = run(&mut compressor, quiet, 4410);
+        assert!((left - quiet).abs() < 1.0e-5);
+        assert!((right + quiet).abs() < 1.0e-5);
+
+        // 0 dB signal is 20 dB above threshold, with 4:1 ratio it must become 5 dB above
+        // threshold (-15 dB). Envelope follower settles in a few attack periods.
+        let (left, right) = run(&mut compressor, 1.0, 4410);
+        let expected = db_to_linear(-15.0);
+        assert!((left - expected).abs() < 1.0e-3, "{}", left);
+        assert!((right + expected).abs() < 1.0e-3, "{}", right);
+
+        // Gain reduction must go away when the signal goes below threshold again.
+        let (left, _) = run(&mut compressor, quiet, 4410);
+        assert!((left - quiet).abs() < 1.0e-5, "{}", left);
+    }
+}
@@ -0,0 +1,225 @@
+//! Delay (echo) module
+//!
+//! # Overview
+//!
+//! Delay effect repeats input signal after some period of time, each repetition is attenuated by
+//! feedback factor. It is useful to simulate echoes in large open spaces like canyons or mountains.
+//!
+//! # Usage
+//!
+//! ```
+//! use std::time::Duration;
+//! use fyrox_sound::context::SoundContext;
+//! use fyrox_sound::effects::delay::Delay;
+//! use fyrox_sound::effects::{Effect, BaseEffect};
+//!
+//! fn add_echo(context: &mut SoundContext) {
+//!     let mut delay = Delay::new(BaseEffect::default());
+//!     delay.set_delay_time(Duration::from_millis(350));
+//!     delay.set_feedback(0.4);
+//!     context.state().add_effect(Effect::Delay(delay));
+//! }
+//! ```
+
+use crate::{
+    context::{DistanceModel, SAMPLE_RATE},
+    dsp::DelayLine,
+    effects::{BaseEffect, EffectRenderTrait},
+    listener::Listener,
+    source::SoundSource,
+};
+use fyrox_core::{
+    pool::Pool,
+    visitor::{Visit, VisitResult, Visitor},
+};
+use std::{
+    ops::{Deref, DerefMut},
+    time::Duration,
+};
+
+/// Max delay time that can be set, it defines the size of internal delay lines.
+pub const MAX_DELAY_TIME: Duration = Duration::from_secs(5);
+
+fn duration_to_samples(duration: Duration) -> f32 {
+    duration.as_secs_f32() * SAMPLE_RATE as f32
+}
+
+/// See module docs.
+#[derive(Debug, Clone, Visit)]
+pub struct Delay {
+    base: BaseEffect,
+    delay_time: Duration,
+    feedback: f32,
+    dry: f32,
+    wet: f32,
+    #[visit(skip)]
+    left: DelayLine,
+    #[visit(skip)]
+    right: DelayLine,
+}
+
+impl Default for Delay {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+impl Delay {
+    /// Creates new instance of delay effect with 250 ms delay time and 0.5 feedback.
+    pub fn new(base: BaseEffect) -> Self {
+        let len = duration_to_samples(MAX_DELAY_TIME) as usize + 1;
+        Self {
+            base,
+            delay_time: Duration::from_millis(250),
+            feedback: 0.5,
+            dry: 1.0,
+            wet: 0.5,
+            left: DelayLine::new(len),
+            right: DelayLine::new(len),
+        }
+    }
+
+    /// Sets time between repetitions of the signal. It is clamped to [`MAX_DELAY_TIME`].
+    pub fn set_delay_time(&mut self, delay_time: Duration) {
+        self.delay_time = delay_time.min(MAX_DELAY_TIME);
+    }
+
+    /// Returns time between repetitions of the signal.
+    pub fn delay_time(&self) -> Duration {
+        self.delay_time
+    }
+
+    /// Sets how much of delayed signal will be fed back to the input. Each repetition is
+    /// attenuated by this factor. For numeric stability the value is clamped to `[0; 0.99]`.
+    pub fn set_feedback(&mut self, feedback: f32) {
+        self.feedback = feedback.clamp(0.0, 0.99);
+    }
+
+    /// Returns current feedback factor.
+    pub fn feedback(&self) -> f32 {
+        self.feedback
+    }
+
+    /// Sets how much of input signal should be passed to output without any processing.
+    /// Default value is 1.0.
+    pub fn set_dry(&mut self, dry: f32) {
+        self.dry = dry.clamp(0.0, 1.0);
+    }
+
+    /// Returns dry part.
+    pub fn dry(&self) -> f32 {
+        self.dry
+    }
+
+    /// Sets how much of delayed signal should be passed to output. Default value is 0.5.
+    pub fn set_wet(&mut self, wet: f32) {
+        self.wet = wet.clamp(0.0, 1.0);
+    }
+
+    /// Returns wet part.
+    pub fn wet(&self) -> f32 {
+        self.wet
+    }
+
+    /// Processes single frame of the signal, `delay` is the delay time in samples. Gain of the
+    /// effect is not applied.
+    fn process(&mut self, delay: f32, left: f32, right: f32) -> (f32, f32) {
+        let delayed_left = self.left.tap(delay);
+        let delayed_right = self.right.tap(delay);
+
+        self.left.feed(left + delayed_left * self.feedback);
+        self.right.feed(right + delayed_right * self.feedback);
+
+        (
+            self.dry * left + self.wet * delayed_left,
+            self.dry * right + self.wet * delayed_right,
+        )
+    }
+}
+
+impl EffectRenderTrait for Delay {
+    fn render(
+        &mut self,
+        sources: &Pool<SoundSource>,
+        listener: &Listener,
+        distance_model: DistanceModel,
+        mix_buf: &mut [(f32, f32)],
+    ) {
+        self.base
+            .render(sources, listener, distance_model, mix_buf.len());
+
+        let delay = duration_to_samples(self.delay_time);
+
+        for (i, (out_left, out_right)) in mix_buf.iter_mut().enumerate() {
+            let (left, right) = self.base.frame_samples[i];
+            let (left, right) = self.process(delay, left, right);
+
+            *out_left += self.base.gain * left;
+            *out_right += self.base.gain * right;
+        }
+    }
+}
+
+impl Deref for Delay {
+    type Target = BaseEffect;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Delay {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        context::SAMPLE_RATE,
+        effects::{
+            delay::{duration_to_samples, Delay},
+            BaseEffect,
+        },
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn test_delay_impulse_response() {
+        let mut delay = Delay::new(BaseEffect::default());
+        delay.set_delay_time(Duration::from_millis(10));
+        delay.set_feedback(0.5);
+        delay.set_dry(1.0);
+        delay.set_wet(0.5);
+
+        let samples = duration_to_samples(delay.delay_time());
+        let offset = (SAMPLE_RATE / 100) as usize;
+
+        let output = (0..offset * 3 + 1)
+            .map(|i| {
+                let input = if i == 0 { 1.0 } else { 0.0 };
+                delay.process(samples, input, -input)
+            })
+            .collect::<Vec<_>>();
+
+        for (i, &(left, right)) in output.iter().enumerate() {
+            let expected = if i == 0 {
+                // Dry signal.
+                1.0
+            } else if i == offset {
+                // First echo.
+                0.5
+            } else if i == offset * 2 {
+                // Second echo, attenuated by feedback.
+                0.25
+            } else if i == offset * 3 {
+                0.125
+            } else {
+                0.0
+            };
+            assert!((left - expected).abs() < 1.0e-3, "{}: {}", i, left);
+            assert!((right + expected).abs() < 1.0e-3, "{}: {}", i, right);
+        }
+    }
+}
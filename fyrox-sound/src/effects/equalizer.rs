@@ -0,0 +1,252 @@
+//! Parametric equalizer module
+//!
+//! # Overview
+//!
+//! Equalizer changes the balance of frequencies in the signal. It consists of any number of bands,
+//! each band boosts or cuts frequencies around (or below/above for shelves) its frequency. Every
+//! band is implemented as a biquad filter, bands are applied one after another.
+//!
+//! # Usage
+//!
+//! ```
+//! use fyrox_sound::context::SoundContext;
+//! use fyrox_sound::effects::equalizer::{Equalizer, EqualizerBand, EqualizerBandKind};
+//! use fyrox_sound::effects::{Effect, BaseEffect};
+//!
+//! fn add_equalizer(context: &mut SoundContext) {
+//!     let mut equalizer = Equalizer::new(BaseEffect::default());
+//!     let fc = context.state().normalize_frequency(250.0);
+//!     equalizer.set_bands(vec![EqualizerBand {
+//!         kind: EqualizerBandKind::LowShelf,
+//!         fc,
+//!         gain: 6.0,
+//!         quality: 0.707,
+//!     }]);
+//!     context.state().add_effect(Effect::Equalizer(equalizer));
+//! }
+//! ```
+
+use crate::{
+    context::{DistanceModel, SAMPLE_RATE},
+    dsp::filters::{Biquad, BiquadKind},
+    effects::{BaseEffect, EffectRenderTrait},
+    listener::Listener,
+    source::SoundSource,
+};
+use fyrox_core::{
+    inspect::{Inspect, PropertyInfo},
+    pool::Pool,
+    reflect::Reflect,
+    visitor::{Visit, VisitResult, Visitor},
+};
+use std::ops::{Deref, DerefMut};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Kind of equalizer band, it defines which frequencies will be affected by the band.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Visit,
+    Inspect,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum EqualizerBandKind {
+    /// Affects all frequencies below band's frequency.
+    LowShelf,
+    /// Affects frequencies around band's frequency.
+    Peak,
+    /// Affects all frequencies above band's frequency.
+    HighShelf,
+}
+
+impl Default for EqualizerBandKind {
+    fn default() -> Self {
+        Self::Peak
+    }
+}
+
+/// A single band of the equalizer.
+#[derive(Debug, Clone, PartialEq, Visit, Inspect, Reflect)]
+pub struct EqualizerBand {
+    /// Kind of the band.
+    pub kind: EqualizerBandKind,
+    /// Normalized frequency of the band. Use `SoundContext::normalize_frequency` to convert
+    /// frequency in hertz.
+    pub fc: f32,
+    /// Gain (in dB) of the band. Positive values boost frequencies, negative - cut.
+    pub gain: f32,
+    /// Defines band width, the lower it is, the wider the band will be.
+    pub quality: f32,
+}
+
+impl Default for EqualizerBand {
+    fn default() -> Self {
+        Self {
+            kind: EqualizerBandKind::Peak,
+            fc: 1000.0 / SAMPLE_RATE as f32,
+            gain: 0.0,
+            quality: std::f32::consts::FRAC_1_SQRT_2,
+        }
+    }
+}
+
+impl EqualizerBand {
+    fn make_filter(&self) -> Biquad {
+        let kind = match self.kind {
+            EqualizerBandKind::LowShelf => BiquadKind::LowShelf,
+            EqualizerBandKind::Peak => BiquadKind::Peak,
+            EqualizerBandKind::HighShelf => BiquadKind::HighShelf,
+        };
+        // Biquad filters expect amplitude factor in `sqrt(linear gain)` form.
+        Biquad::new(
+            kind,
+            self.fc.clamp(0.0, 0.49),
+            10.0f32.powf(self.gain / 40.0),
+            self.quality.max(0.01),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+struct BandFilter {
+    band: EqualizerBand,
+    left: Biquad,
+    right: Biquad,
+}
+
+impl BandFilter {
+    fn new(band: &EqualizerBand) -> Self {
+        let filter = band.make_filter();
+        Self {
+            band: band.clone(),
+            left: filter.clone(),
+            right: filter,
+        }
+    }
+}
+
+/// See module docs.
+#[derive(Debug, Clone, Visit)]
+pub struct Equalizer {
+    base: BaseEffect,
+    bands: Vec<EqualizerBand>,
+    #[visit(skip)]
+    filters: Vec<BandFilter>,
+}
+
+impl Default for Equalizer {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+impl Equalizer {
+    /// Creates new equalizer with three neutral bands: low shelf at 100 Hz, peak at 1 kHz and
+    /// high shelf at 8 kHz.
+    pub fn new(base: BaseEffect) -> Self {
+        Self {
+            base,
+            bands: vec![
+                EqualizerBand {
+                    kind: EqualizerBandKind::LowShelf,
+                    fc: 100.0 / SAMPLE_RATE as f32,
+                    ..Default::default()
+                },
+                EqualizerBand::default(),
+                EqualizerBand {
+                    kind: EqualizerBandKind::HighShelf,
+                    fc: 8000.0 / SAMPLE_RATE as f32,
+                    ..Default::default()
+                },
+            ],
+            filters: Default::default(),
+        }
+    }
+
+    /// Sets new set of bands.
+    pub fn set_bands(&mut self, bands: Vec<EqualizerBand>) {
+        self.bands = bands;
+    }
+
+    /// Returns current set of bands.
+    pub fn bands(&self) -> &[EqualizerBand] {
+        &self.bands
+    }
+
+    /// Returns mutable reference to current set of bands.
+    pub fn bands_mut(&mut self) -> &mut Vec<EqualizerBand> {
+        &mut self.bands
+    }
+
+    fn sync_filters(&mut self) {
+        self.filters.truncate(self.bands.len());
+        for (i, band) in self.bands.iter().enumerate() {
+            match self.filters.get_mut(i) {
+                Some(filter) => {
+                    if filter.band != *band {
+                        // Keep filter state to prevent clicks when parameters are changing.
+                        let new = band.make_filter();
+                        filter.band = band.clone();
+                        for biquad in [&mut filter.left, &mut filter.right] {
+                            biquad.b0 = new.b0;
+                            biquad.b1 = new.b1;
+                            biquad.b2 = new.b2;
+                            biquad.a1 = new.a1;
+                            biquad.a2 = new.a2;
+                        }
+                    }
+                }
+                None => self.filters.push(BandFilter::new(band)),
+            }
+        }
+    }
+}
+
+impl EffectRenderTrait for Equalizer {
+    fn render(
+        &mut self,
+        sources: &Pool<SoundSource>,
+        listener: &Listener,
+        distance_model: DistanceModel,
+        mix_buf: &mut [(f32, f32)],
+    ) {
+        self.base
+            .render(sources, listener, distance_model, mix_buf.len());
+
+        self.sync_filters();
+
+        for ((out_left, out_right), &(left, right)) in
+            mix_buf.iter_mut().zip(self.base.frame_samples.iter())
+        {
+            let mut left = left;
+            let mut right = right;
+            for filter in self.filters.iter_mut() {
+                left = filter.left.feed(left);
+                right = filter.right.feed(right);
+            }
+
+            *out_left += self.base.gain * left;
+            *out_right += self.base.gain * right;
+        }
+    }
+}
+
+impl Deref for Equalizer {
+    type Target = BaseEffect;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Equalizer {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
@@ -7,7 +7,9 @@
 use crate::{
     context::DistanceModel,
     dsp::filters::Biquad,
-    effects::reverb::Reverb,
+    effects::{
+        chorus::Chorus, compressor::Compressor, delay::Delay, equalizer::Equalizer, reverb::Reverb,
+    },
     listener::Listener,
     source::{SoundSource, Status},
 };
@@ -18,6 +20,10 @@ use fyrox_core::{
 };
 use std::ops::{Deref, DerefMut};
 
+pub mod chorus;
+pub mod compressor;
+pub mod delay;
+pub mod equalizer;
 pub mod reverb;
 
 /// Stub effect that does nothing.
@@ -58,6 +64,14 @@ pub enum Effect {
     Stub(StubEffect),
     /// Reverberation effect. See corresponding module for more info.
     Reverb(Reverb),
+    /// Delay (echo) effect. See corresponding module for more info.
+    Delay(Delay),
+    /// Chorus and flanger effect. See corresponding module for more info.
+    Chorus(Chorus),
+    /// Dynamic range compressor and limiter. See corresponding module for more info.
+    Compressor(Compressor),
+    /// Parametric equalizer. See corresponding module for more info.
+    Equalizer(Equalizer),
 }
 
 impl Default for Effect {
@@ -258,6 +272,10 @@ macro_rules! static_dispatch {
         match $self {
             Effect::Stub(v) => v.$func($($args),*),
             Effect::Reverb(v) => v.$func($($args),*),
+            Effect::Delay(v) => v.$func($($args),*),
+            Effect::Chorus(v) => v.$func($($args),*),
+            Effect::Compressor(v) => v.$func($($args),*),
+            Effect::Equalizer(v) => v.$func($($args),*),
         }
     };
 }
@@ -281,6 +299,10 @@ impl Deref for Effect {
         match self {
             Effect::Stub(v) => v,
             Effect::Reverb(v) => v,
+            Effect::Delay(v) => v,
+            Effect::Chorus(v) => v,
+            Effect::Compressor(v) => v,
+            Effect::Equalizer(v) => v,
         }
     }
 }
@@ -290,6 +312,10 @@ impl DerefMut for Effect {
         match self {
             Effect::Stub(v) => v,
             Effect::Reverb(v) => v,
+            Effect::Delay(v) => v,
+            Effect::Chorus(v) => v,
+            Effect::Compressor(v) => v,
+            Effect::Equalizer(v) => v,
         }
     }
 }
@@ -6,7 +6,7 @@
 //! - WAV and OGG/Vorbis formats support.
 //! - Streaming.
 //! - Head-related transfer function support ([HRTF](https://en.wikipedia.org/wiki/Head-related_transfer_function)).
-//! - Reverb effect.
+//! - Effects: reverb, delay, chorus/flanger, compressor/limiter, parametric equalizer.
 //!
 //! ## Examples
 //!
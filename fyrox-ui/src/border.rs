@@ -1,5 +1,13 @@
 use crate::{
-    core::{algebra::Vector2, math::Rect, pool::Handle, scope_profile},
+    core::{
+        algebra::Vector2,
+        math::Rect,
+        pool::Handle,
+        scope_profile,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     define_constructor,
     draw::{CommandTexture, Draw, DrawingContext},
     message::UiMessage,
@@ -11,7 +19,7 @@ use std::{
     ops::{Deref, DerefMut},
 };
 
-#[derive(Clone)]
+#[derive(Clone, Visit)]
 pub struct Border {
     pub widget: Widget,
     pub stroke_thickness: Thickness,
@@ -19,6 +27,12 @@ pub struct Border {
 
 crate::define_widget_deref!(Border);
 
+impl Default for Border {
+    fn default() -> Self {
+        Self::new(Widget::default())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum BorderMessage {
     StrokeThickness(Thickness),
@@ -28,6 +42,12 @@ impl BorderMessage {
     define_constructor!(BorderMessage:StrokeThickness => fn stroke_thickness(Thickness), layout: false);
 }
 
+impl TypeUuidProvider for Border {
+    fn type_uuid() -> Uuid {
+        uuid!("1605f8b8-0cd3-4833-89c3-0fd4cedd66b1")
+    }
+}
+
 impl Control for Border {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
@@ -1,7 +1,13 @@
 use crate::{
     border::BorderBuilder,
     brush::{Brush, GradientPoint},
-    core::{algebra::Vector2, pool::Handle},
+    core::{
+        algebra::Vector2,
+        pool::Handle,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     decorator::DecoratorBuilder,
     define_constructor,
     message::{MessageDirection, UiMessage},
@@ -28,7 +34,7 @@ impl ButtonMessage {
     define_constructor!(ButtonMessage:Content => fn content(ButtonContent), layout: false);
 }
 
-#[derive(Clone)]
+#[derive(Default, Clone, Visit)]
 pub struct Button {
     pub widget: Widget,
     pub decorator: Handle<UiNode>,
@@ -37,6 +43,12 @@ pub struct Button {
 
 crate::define_widget_deref!(Button);
 
+impl TypeUuidProvider for Button {
+    fn type_uuid() -> Uuid {
+        uuid!("5d36fba9-3794-4d84-ae25-d66774e6e22f")
+    }
+}
+
 impl Control for Button {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
@@ -1,5 +1,13 @@
 use crate::{
-    core::{algebra::Vector2, math::Rect, pool::Handle, scope_profile},
+    core::{
+        algebra::Vector2,
+        math::Rect,
+        pool::Handle,
+        scope_profile,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     message::UiMessage,
     widget::{Widget, WidgetBuilder},
     BuildContext, Control, UiNode, UserInterface,
@@ -10,13 +18,19 @@ use std::{
 };
 
 /// Allows user to directly set position and size of a node
-#[derive(Clone)]
+#[derive(Default, Clone, Visit)]
 pub struct Canvas {
     pub widget: Widget,
 }
 
 crate::define_widget_deref!(Canvas);
 
+impl TypeUuidProvider for Canvas {
+    fn type_uuid() -> Uuid {
+        uuid!("a6ceb860-5867-47ef-a361-02f6f191fac7")
+    }
+}
+
 impl Control for Canvas {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
@@ -1,7 +1,13 @@
 use crate::{
     border::BorderBuilder,
     brush::Brush,
-    core::{color::Color, pool::Handle},
+    core::{
+        color::Color,
+        pool::Handle,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     define_constructor,
     grid::{Column, GridBuilder, Row},
     message::{MessageDirection, UiMessage},
@@ -25,7 +31,7 @@ impl CheckBoxMessage {
     define_constructor!(CheckBoxMessage:Check => fn checked(Option<bool>), layout: false);
 }
 
-#[derive(Clone)]
+#[derive(Default, Clone, Visit)]
 pub struct CheckBox {
     pub widget: Widget,
     pub checked: Option<bool>,
@@ -36,6 +42,12 @@ pub struct CheckBox {
 
 crate::define_widget_deref!(CheckBox);
 
+impl TypeUuidProvider for CheckBox {
+    fn type_uuid() -> Uuid {
+        uuid!("61502301-f051-41db-a16e-e0c09bcc5600")
+    }
+}
+
 impl Control for CheckBox {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
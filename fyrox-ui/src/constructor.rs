@@ -0,0 +1,184 @@
+//! A special container that is able to create widgets by their type UUID. It is used to save and
+//! load widgets, see [`crate::scene`] module docs for more info.
+
+use crate::{
+    border::Border,
+    button::Button,
+    canvas::Canvas,
+    check_box::CheckBox,
+    core::{parking_lot::Mutex, uuid::Uuid, visitor::prelude::*, TypeUuidProvider},
+    decorator::Decorator,
+    dropdown_list::DropdownList,
+    expander::Expander,
+    grid::Grid,
+    image::Image,
+    list_view::{ListView, ListViewItem},
+    menu::{Menu, MenuItem},
+    popup::Popup,
+    progress_bar::ProgressBar,
+    scroll_bar::ScrollBar,
+    scroll_panel::ScrollPanel,
+    scroll_viewer::ScrollViewer,
+    stack_panel::StackPanel,
+    tab_control::TabControl,
+    text::Text,
+    text_box::TextBox,
+    tree::{Tree, TreeRoot},
+    vector_image::VectorImage,
+    window::Window,
+    wrap_panel::WrapPanel,
+    Control, UiNode,
+};
+use fxhash::FxHashMap;
+use std::any::{Any, TypeId};
+
+/// A simple type alias for boxed widget constructor.
+pub type WidgetConstructor = Box<dyn FnMut() -> UiNode + Send>;
+
+type WidgetVisitor = fn(&mut UiNode, &str, &mut Visitor) -> VisitResult;
+
+struct WidgetTypeEntry {
+    constructor: WidgetConstructor,
+    visitor: WidgetVisitor,
+}
+
+fn visit_widget<T: Control + Visit>(
+    node: &mut UiNode,
+    name: &str,
+    visitor: &mut Visitor,
+) -> VisitResult {
+    node.as_any_mut()
+        .downcast_mut::<T>()
+        .ok_or_else(|| VisitError::User("Widget type mismatch!".to_owned()))?
+        .visit(name, visitor)
+}
+
+/// A special container that is able to create widgets by their type UUID.
+///
+/// Only registered widgets can be saved and loaded, every registered widget type must implement
+/// [`Visit`] trait and must have unique type UUID. Widgets that are never saved do not need to be
+/// registered.
+#[derive(Default)]
+pub struct WidgetConstructorContainer {
+    map: Mutex<FxHashMap<Uuid, WidgetTypeEntry>>,
+    type_uuids: Mutex<FxHashMap<TypeId, Uuid>>,
+}
+
+impl WidgetConstructorContainer {
+    /// Creates default widget constructor container with constructors for built-in widgets.
+    pub fn new() -> Self {
+        let container = WidgetConstructorContainer::default();
+
+        container.add::<Border>();
+        container.add::<Button>();
+        container.add::<Canvas>();
+        container.add::<CheckBox>();
+        container.add::<Decorator>();
+        container.add::<DropdownList>();
+        container.add::<Expander>();
+        container.add::<Grid>();
+        container.add::<Image>();
+        container.add::<ListView>();
+        container.add::<ListViewItem>();
+        container.add::<Menu>();
+        container.add::<MenuItem>();
+        container.add::<Popup>();
+        container.add::<ProgressBar>();
+        container.add::<ScrollBar>();
+        container.add::<ScrollPanel>();
+        container.add::<ScrollViewer>();
+        container.add::<StackPanel>();
+        container.add::<TabControl>();
+        container.add::<Text>();
+        container.add::<TextBox>();
+        container.add::<Tree>();
+        container.add::<TreeRoot>();
+        container.add::<VectorImage>();
+        container.add::<Window>();
+        container.add::<WrapPanel>();
+
+        container
+    }
+
+    /// Adds new type constructor for a given type.
+    pub fn add<T>(&self)
+    where
+        T: TypeUuidProvider + Control + Default + Visit,
+    {
+        let previous = self.map.lock().insert(
+            T::type_uuid(),
+            Self::make_entry::<T>(Box::new(|| UiNode::new(T::default()))),
+        );
+
+        assert!(previous.is_none());
+
+        self.type_uuids
+            .lock()
+            .insert(TypeId::of::<T>(), T::type_uuid());
+    }
+
+    /// Adds custom type constructor. The constructor must create widgets of `T` type.
+    pub fn add_custom<T>(&self, type_uuid: Uuid, constructor: WidgetConstructor)
+    where
+        T: Control + Visit,
+    {
+        self.map
+            .lock()
+            .insert(type_uuid, Self::make_entry::<T>(constructor));
+        self.type_uuids.lock().insert(TypeId::of::<T>(), type_uuid);
+    }
+
+    fn make_entry<T: Control + Visit>(constructor: WidgetConstructor) -> WidgetTypeEntry {
+        WidgetTypeEntry {
+            constructor,
+            visitor: visit_widget::<T>,
+        }
+    }
+
+    /// Unregisters type constructor.
+    pub fn remove(&self, type_uuid: Uuid) {
+        self.map.lock().remove(&type_uuid);
+        self.type_uuids.lock().retain(|_, uuid| *uuid != type_uuid);
+    }
+
+    /// Makes an attempt to create a widget using provided type UUID. It may fail if there is no
+    /// widget constructor for specified type UUID.
+    pub fn try_create(&self, type_uuid: &Uuid) -> Option<UiNode> {
+        self.map
+            .lock()
+            .get_mut(type_uuid)
+            .map(|entry| (entry.constructor)())
+    }
+
+    /// Returns type UUID of the given widget, or `None` if the widget type is not registered.
+    pub fn type_uuid_of(&self, node: &UiNode) -> Option<Uuid> {
+        self.type_uuids
+            .lock()
+            .get(&Any::type_id(node.as_any()))
+            .cloned()
+    }
+
+    /// Saves or loads data of the given widget, the widget type must be registered.
+    pub fn visit(&self, node: &mut UiNode, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let type_uuid = self.type_uuid_of(node).ok_or_else(|| {
+            VisitError::User("Widget type is not registered, unable to visit it!".to_owned())
+        })?;
+        let widget_visitor = self
+            .map
+            .lock()
+            .get(&type_uuid)
+            .map(|entry| entry.visitor)
+            .unwrap();
+        widget_visitor(node, name, visitor)
+    }
+
+    /// Returns total amount of constructors.
+    pub fn len(&self) -> usize {
+        self.map.lock().len()
+    }
+
+    /// Returns true if the container is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
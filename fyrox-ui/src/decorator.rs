@@ -1,7 +1,14 @@
 use crate::{
     border::{Border, BorderBuilder},
     brush::{Brush, GradientPoint},
-    core::{algebra::Vector2, color::Color, pool::Handle},
+    core::{
+        algebra::Vector2,
+        color::Color,
+        pool::Handle,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     define_constructor,
     draw::DrawingContext,
     message::{MessageDirection, UiMessage},
@@ -42,7 +49,7 @@ impl DecoratorMessage {
 ///
 /// This element is widely used to provide some generic visual behaviour for various
 /// widgets. For example it used to decorate button, items in items control.
-#[derive(Clone)]
+#[derive(Clone, Visit)]
 pub struct Decorator {
     pub border: Border,
     pub normal_brush: Brush,
@@ -54,6 +61,21 @@ pub struct Decorator {
     pub is_pressable: bool,
 }
 
+impl Default for Decorator {
+    fn default() -> Self {
+        Self {
+            border: Default::default(),
+            normal_brush: BRUSH_LIGHT,
+            hover_brush: BRUSH_LIGHTER,
+            pressed_brush: BRUSH_LIGHTEST,
+            selected_brush: BRUSH_BRIGHT,
+            disabled_brush: Brush::Solid(Color::opaque(50, 50, 50)),
+            is_selected: false,
+            is_pressable: true,
+        }
+    }
+}
+
 impl Deref for Decorator {
     type Target = Widget;
 
@@ -68,6 +90,12 @@ impl DerefMut for Decorator {
     }
 }
 
+impl TypeUuidProvider for Decorator {
+    fn type_uuid() -> Uuid {
+        uuid!("64675597-608b-4225-877d-be034a3b96fd")
+    }
+}
+
 impl Control for Decorator {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         self.border.query_component(type_id).or_else(|| {
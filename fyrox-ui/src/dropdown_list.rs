@@ -3,7 +3,13 @@
 
 use crate::{
     border::BorderBuilder,
-    core::{algebra::Vector2, pool::Handle},
+    core::{
+        algebra::Vector2,
+        pool::Handle,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     define_constructor,
     grid::{Column, GridBuilder, Row},
     list_view::{ListViewBuilder, ListViewMessage},
@@ -36,7 +42,7 @@ impl DropdownListMessage {
     define_constructor!(DropdownListMessage:Close => fn close(), layout: false);
 }
 
-#[derive(Clone)]
+#[derive(Default, Clone, Visit)]
 pub struct DropdownList {
     pub widget: Widget,
     pub popup: Handle<UiNode>,
@@ -50,6 +56,12 @@ pub struct DropdownList {
 
 crate::define_widget_deref!(DropdownList);
 
+impl TypeUuidProvider for DropdownList {
+    fn type_uuid() -> Uuid {
+        uuid!("5edf0eb1-3d70-44c0-bccd-bc8ebbf76a98")
+    }
+}
+
 impl Control for DropdownList {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
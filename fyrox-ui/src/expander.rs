@@ -1,6 +1,11 @@
 use crate::{
     check_box::{CheckBoxBuilder, CheckBoxMessage},
-    core::pool::Handle,
+    core::{
+        pool::Handle,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     define_constructor,
     grid::{Column, GridBuilder, Row},
     message::{MessageDirection, UiMessage},
@@ -22,7 +27,7 @@ impl ExpanderMessage {
     define_constructor!(ExpanderMessage:Expand => fn expand(bool), layout: false);
 }
 
-#[derive(Clone)]
+#[derive(Default, Clone, Visit)]
 pub struct Expander {
     pub widget: Widget,
     pub content: Handle<UiNode>,
@@ -32,6 +37,12 @@ pub struct Expander {
 
 crate::define_widget_deref!(Expander);
 
+impl TypeUuidProvider for Expander {
+    fn type_uuid() -> Uuid {
+        uuid!("49fed56f-933f-4859-ae8b-caf3fc942188")
+    }
+}
+
 impl Control for Expander {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
@@ -1,6 +1,6 @@
 use crate::{
     brush::Brush,
-    core::{algebra::Vector2, color::Color, math::Rect},
+    core::{algebra::Vector2, color::Color, math::Rect, visitor::prelude::*},
     ttf::SharedFont,
     Font, HorizontalAlignment, VerticalAlignment,
 };
@@ -60,7 +60,7 @@ impl TextLine {
 }
 
 /// Wrapping mode for formatted text.
-#[derive(Copy, Clone, PartialOrd, PartialEq, Hash, Debug, Eq)]
+#[derive(Copy, Clone, PartialOrd, PartialEq, Hash, Debug, Eq, Visit)]
 pub enum WrapMode {
     /// No wrapping needed.
     NoWrap,
@@ -116,6 +116,46 @@ pub struct FormattedText {
     pub shadow_offset: Vector2<f32>,
 }
 
+impl Visit for FormattedText {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        self.font.visit("Font", &mut region)?;
+
+        // Glyph indices depend on the font, so only character codes are saved.
+        let mut text = self.text();
+        text.visit("Text", &mut region)?;
+        let mut mask_char = self.mask_char.map(|c| c.char_code);
+        mask_char.visit("MaskChar", &mut region)?;
+
+        self.vertical_alignment
+            .visit("VerticalAlignment", &mut region)?;
+        self.horizontal_alignment
+            .visit("HorizontalAlignment", &mut region)?;
+        self.brush.visit("Brush", &mut region)?;
+        self.constraint.visit("Constraint", &mut region)?;
+        self.wrap.visit("Wrap", &mut region)?;
+        self.shadow.visit("Shadow", &mut region)?;
+        self.shadow_brush.visit("ShadowBrush", &mut region)?;
+        self.shadow_dilation.visit("ShadowDilation", &mut region)?;
+        self.shadow_offset.visit("ShadowOffset", &mut region)?;
+
+        if region.is_reading() {
+            self.set_text(text);
+            let font = self.font.0.lock();
+            self.mask_char = mask_char.map(|code| Character::from_char_with_font(code, &font));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for FormattedText {
+    fn default() -> Self {
+        FormattedTextBuilder::new(SharedFont::default()).build()
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 struct Word {
     width: f32,
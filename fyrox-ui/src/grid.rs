@@ -1,5 +1,13 @@
 use crate::{
-    core::{algebra::Vector2, math::Rect, pool::Handle, scope_profile},
+    core::{
+        algebra::Vector2,
+        math::Rect,
+        pool::Handle,
+        scope_profile,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     draw::{CommandTexture, Draw, DrawingContext},
     message::UiMessage,
     widget::{Widget, WidgetBuilder},
@@ -11,21 +19,29 @@ use std::{
     ops::{Deref, DerefMut},
 };
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Visit)]
 pub enum SizeMode {
     Strict,
     Auto,
     Stretch,
 }
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Visit)]
 pub struct GridDimension {
     pub size_mode: SizeMode,
     pub desired_size: f32,
+    #[visit(skip)]
     pub actual_size: f32,
+    #[visit(skip)]
     pub location: f32,
 }
 
+impl Default for GridDimension {
+    fn default() -> Self {
+        Self::auto()
+    }
+}
+
 impl GridDimension {
     pub fn generic(size_mode: SizeMode, desired_size: f32) -> Self {
         Self {
@@ -53,19 +69,35 @@ pub type Column = GridDimension;
 pub type Row = GridDimension;
 
 /// Automatically arranges children by rows and columns
-#[derive(Clone)]
+#[derive(Clone, Visit)]
 pub struct Grid {
     pub widget: Widget,
     pub rows: RefCell<Vec<Row>>,
     pub columns: RefCell<Vec<Column>>,
     pub draw_border: bool,
     pub border_thickness: f32,
+    #[visit(skip)]
     pub cells: RefCell<Vec<Cell>>,
+    #[visit(skip)]
     pub groups: RefCell<[Vec<usize>; 4]>,
 }
 
 crate::define_widget_deref!(Grid);
 
+impl Default for Grid {
+    fn default() -> Self {
+        Self {
+            widget: Default::default(),
+            rows: Default::default(),
+            columns: Default::default(),
+            draw_border: false,
+            border_thickness: 1.0,
+            cells: Default::default(),
+            groups: Default::default(),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct Cell {
     pub nodes: Vec<Handle<UiNode>>,
@@ -209,6 +241,12 @@ fn arrange_dims(dims: &mut [GridDimension], final_size: f32) {
     }
 }
 
+impl TypeUuidProvider for Grid {
+    fn type_uuid() -> Uuid {
+        uuid!("b2084dcf-5a35-4263-b864-b8f9be74dcd5")
+    }
+}
+
 impl Control for Grid {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
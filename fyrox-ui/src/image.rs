@@ -1,9 +1,17 @@
 use crate::{
     brush::Brush,
-    core::{algebra::Vector2, color::Color, pool::Handle},
+    core::{
+        algebra::Vector2,
+        color::Color,
+        pool::Handle,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     define_constructor,
     draw::{CommandTexture, Draw, DrawingContext, SharedTexture},
     message::{MessageDirection, UiMessage},
+    scene::visit_texture,
     widget::{Widget, WidgetBuilder},
     BuildContext, Control, UiNode, UserInterface,
 };
@@ -23,7 +31,7 @@ impl ImageMessage {
     define_constructor!(ImageMessage:Flip => fn flip(bool), layout: false);
 }
 
-#[derive(Clone)]
+#[derive(Default, Clone)]
 pub struct Image {
     pub widget: Widget,
     pub texture: Option<SharedTexture>,
@@ -32,6 +40,18 @@ pub struct Image {
 
 crate::define_widget_deref!(Image);
 
+impl Visit for Image {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        self.widget.visit("Widget", &mut region)?;
+        visit_texture(&mut self.texture, "Texture", &mut region)?;
+        self.flip.visit("Flip", &mut region)?;
+
+        Ok(())
+    }
+}
+
 impl Image {
     pub fn new(widget: Widget) -> Self {
         Self {
@@ -50,6 +70,12 @@ impl Image {
     }
 }
 
+impl TypeUuidProvider for Image {
+    fn type_uuid() -> Uuid {
+        uuid!("97b66371-734e-4a94-a617-087e97760f6c")
+    }
+}
+
 impl Control for Image {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
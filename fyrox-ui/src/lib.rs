@@ -19,6 +19,7 @@ pub mod button;
 pub mod canvas;
 pub mod check_box;
 pub mod color;
+pub mod constructor;
 pub mod curve;
 pub mod decorator;
 pub mod dock;
@@ -39,6 +40,7 @@ pub mod popup;
 pub mod progress_bar;
 pub mod range;
 pub mod rect;
+pub mod scene;
 pub mod scroll_bar;
 pub mod scroll_panel;
 pub mod scroll_viewer;
@@ -64,6 +66,7 @@ use crate::{
         math::Rect,
         pool::{Handle, Pool},
         scope_profile,
+        visitor::prelude::*,
     },
     draw::{CommandTexture, Draw, DrawingContext},
     message::{
@@ -112,7 +115,7 @@ pub const BRUSH_BRIGHT_BLUE: Brush = Brush::Solid(COLOR_BRIGHT_BLUE);
 pub const BRUSH_TEXT: Brush = Brush::Solid(COLOR_TEXT);
 pub const BRUSH_FOREGROUND: Brush = Brush::Solid(COLOR_FOREGROUND);
 
-#[derive(Copy, Clone, PartialEq, Debug, Eq)]
+#[derive(Copy, Clone, PartialEq, Debug, Eq, Visit)]
 pub enum HorizontalAlignment {
     Stretch,
     Left,
@@ -120,7 +123,7 @@ pub enum HorizontalAlignment {
     Right,
 }
 
-#[derive(Copy, Clone, PartialEq, Debug, Eq)]
+#[derive(Copy, Clone, PartialEq, Debug, Eq, Visit)]
 pub enum VerticalAlignment {
     Stretch,
     Top,
@@ -128,7 +131,7 @@ pub enum VerticalAlignment {
     Bottom,
 }
 
-#[derive(Copy, Clone, PartialEq, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug, Visit)]
 pub struct Thickness {
     pub left: f32,
     pub top: f32,
@@ -142,7 +145,7 @@ impl Default for Thickness {
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Visit)]
 pub enum Orientation {
     Vertical,
     Horizontal,
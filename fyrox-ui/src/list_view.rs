@@ -1,7 +1,13 @@
 use crate::{
     border::BorderBuilder,
     brush::Brush,
-    core::{color::Color, pool::Handle},
+    core::{
+        color::Color,
+        pool::Handle,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     decorator::{Decorator, DecoratorMessage},
     define_constructor,
     draw::{CommandTexture, Draw, DrawingContext},
@@ -34,7 +40,7 @@ impl ListViewMessage {
     define_constructor!(ListViewMessage:BringItemIntoView => fn bring_item_into_view(Handle<UiNode>), layout: false);
 }
 
-#[derive(Clone)]
+#[derive(Default, Clone, Visit)]
 pub struct ListView {
     pub widget: Widget,
     pub selected_index: Option<usize>,
@@ -121,13 +127,19 @@ impl ListView {
     }
 }
 
-#[derive(Clone)]
+#[derive(Default, Clone, Visit)]
 pub struct ListViewItem {
     pub widget: Widget,
 }
 
 crate::define_widget_deref!(ListViewItem);
 
+impl TypeUuidProvider for ListViewItem {
+    fn type_uuid() -> Uuid {
+        uuid!("e6ac6a4d-dae4-4f27-a8b7-ed715bac7717")
+    }
+}
+
 impl Control for ListViewItem {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
@@ -178,6 +190,12 @@ impl Control for ListViewItem {
     }
 }
 
+impl TypeUuidProvider for ListView {
+    fn type_uuid() -> Uuid {
+        uuid!("4f484b1c-a847-4d2b-a803-0bc27f44198b")
+    }
+}
+
 impl Control for ListView {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
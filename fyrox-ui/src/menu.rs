@@ -1,7 +1,14 @@
 use crate::{
     border::BorderBuilder,
     brush::Brush,
-    core::{algebra::Vector2, color::Color, pool::Handle},
+    core::{
+        algebra::Vector2,
+        color::Color,
+        pool::Handle,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     decorator::DecoratorBuilder,
     define_constructor,
     grid::{Column, GridBuilder, Row},
@@ -53,14 +60,21 @@ impl MenuItemMessage {
     define_constructor!(MenuItemMessage:Items => fn items(Vec<Handle<UiNode>>), layout: false);
 }
 
-#[derive(Clone)]
+#[derive(Default, Clone, Visit)]
 pub struct Menu {
     widget: Widget,
+    #[visit(skip)]
     active: bool,
 }
 
 crate::define_widget_deref!(Menu);
 
+impl TypeUuidProvider for Menu {
+    fn type_uuid() -> Uuid {
+        uuid!("ade5660c-5672-4736-81a8-e81e378d8e61")
+    }
+}
+
 impl Control for Menu {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
@@ -161,13 +175,13 @@ impl Control for Menu {
     }
 }
 
-#[derive(Copy, Clone, PartialOrd, PartialEq, Eq, Hash)]
+#[derive(Copy, Clone, PartialOrd, PartialEq, Eq, Hash, Visit)]
 pub enum MenuItemPlacement {
     Bottom,
     Right,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Visit)]
 pub struct MenuItem {
     pub widget: Widget,
     pub items: Vec<Handle<UiNode>>,
@@ -178,6 +192,18 @@ pub struct MenuItem {
 
 crate::define_widget_deref!(MenuItem);
 
+impl Default for MenuItem {
+    fn default() -> Self {
+        Self {
+            widget: Default::default(),
+            items: Default::default(),
+            popup: Default::default(),
+            panel: Default::default(),
+            placement: MenuItemPlacement::Right,
+        }
+    }
+}
+
 // MenuItem uses popup to show its content, popup can be top-most only if it is
 // direct child of root canvas of UI. This fact adds some complications to search
 // of parent menu - we can't just traverse the tree because popup is not a child
@@ -234,6 +260,12 @@ fn close_menu_chain(from: Handle<UiNode>, ui: &UserInterface) {
     }
 }
 
+impl TypeUuidProvider for MenuItem {
+    fn type_uuid() -> Uuid {
+        uuid!("42dfc923-bc50-4be6-9979-ba32047fd8ea")
+    }
+}
+
 impl Control for MenuItem {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
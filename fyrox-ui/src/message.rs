@@ -14,7 +14,7 @@
 //! internal library code can send such messages without a risk of breaking anything.
 
 use crate::{
-    core::{algebra::Vector2, pool::Handle},
+    core::{algebra::Vector2, pool::Handle, visitor::prelude::*},
     UiNode,
 };
 use std::{any::Any, cell::Cell, fmt::Debug, rc::Rc};
@@ -478,7 +478,7 @@ pub enum KeyCode {
     Plus,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Visit)]
 pub enum CursorIcon {
     Default,
     Crosshair,
@@ -1,6 +1,13 @@
 use crate::{
     border::BorderBuilder,
-    core::{algebra::Vector2, math::Rect, pool::Handle},
+    core::{
+        algebra::Vector2,
+        math::Rect,
+        pool::Handle,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     define_constructor,
     message::{ButtonState, MessageDirection, OsEvent, UiMessage},
     widget::{Widget, WidgetBuilder, WidgetMessage},
@@ -29,7 +36,7 @@ impl PopupMessage {
     define_constructor!(PopupMessage:AdjustPosition => fn adjust_position(), layout: true);
 }
 
-#[derive(Copy, Clone, PartialEq, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug, Visit)]
 pub enum Placement {
     /// A popup should be placed relative to given widget at the left top corner of the widget screen bounds.
     /// Widget handle could be `NONE`, in this case the popup will be placed at the left top corner of the screen.
@@ -66,11 +73,12 @@ pub enum Placement {
     },
 }
 
-#[derive(Clone)]
+#[derive(Clone, Visit)]
 pub struct Popup {
     pub widget: Widget,
     pub placement: Placement,
     pub stays_open: bool,
+    #[visit(skip)]
     pub is_open: bool,
     pub content: Handle<UiNode>,
     pub body: Handle<UiNode>,
@@ -79,6 +87,20 @@ pub struct Popup {
 
 crate::define_widget_deref!(Popup);
 
+impl Default for Popup {
+    fn default() -> Self {
+        Self {
+            widget: Default::default(),
+            placement: Placement::Cursor(Default::default()),
+            stays_open: false,
+            is_open: false,
+            content: Default::default(),
+            body: Default::default(),
+            smart_placement: true,
+        }
+    }
+}
+
 fn adjust_placement_position(
     node_screen_bounds: Rect<f32>,
     screen_size: Vector2<f32>,
@@ -130,6 +152,12 @@ impl Popup {
     }
 }
 
+impl TypeUuidProvider for Popup {
+    fn type_uuid() -> Uuid {
+        uuid!("35a50ba8-7ff6-4724-a0b5-3ba657a92154")
+    }
+}
+
 impl Control for Popup {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
@@ -0,0 +1,539 @@
+//! UI scene is a serializable tree of widgets, it allows you to save a part of user interface (or
+//! the whole interface) to a file and instantiate it later in any [`UserInterface`].
+//!
+//! # Serialization
+//!
+//! Every widget is saved together with its type UUID, widget constructors from
+//! [`SerializationContext::widget_constructors`] are used to find the UUID of a widget on save and
+//! to create widgets of correct types on load. Custom widgets must implement [`Visit`] trait and
+//! must be registered in the container to be saveable.
+//!
+//! Fonts and textures are saved as references. Fonts are re-created from their source files with
+//! the same height and char set, fonts that were created from memory (including the built-in font)
+//! are replaced with [`SerializationContext::default_font`]. Textures are opaque for the library,
+//! so it uses [`SerializationContext::texture_resolver`] to convert a texture to a path and back,
+//! textures are dropped if there is no resolver.
+//!
+//! # Loading
+//!
+//! Widgets cannot be sent to other threads, so loading is split in two parts: [`UiSceneSource`]
+//! reads a file and loads all fonts it needs (this could be done in a background thread), then
+//! [`UiSceneSource::to_scene`] creates actual widgets. [`UiScene::from_file`] does both steps.
+//!
+//! # Instantiation
+//!
+//! Loaded widgets keep their original handles, [`UiScene::instantiate`] adds copies of the widgets
+//! to a user interface and remaps every handle using [`NodeHandleMapping`]. Handles to widgets
+//! that are not part of the scene are left untouched, so a scene should contain every widget that
+//! is referenced by its widgets.
+//!
+//! # Example
+//!
+//! ```rust
+//! use fyrox_ui::{
+//!     core::pool::Handle,
+//!     scene::{SerializationContext, UiScene},
+//!     UiNode, UserInterface,
+//! };
+//! use std::sync::Arc;
+//!
+//! async fn load_menu(ui: &mut UserInterface) -> Handle<UiNode> {
+//!     let context = Arc::new(SerializationContext::new(ui.default_font.clone()));
+//!     let scene = UiScene::from_file("data/menu.uis", context).await.unwrap();
+//!     scene.instantiate(ui)
+//! }
+//! ```
+
+use crate::{
+    constructor::WidgetConstructorContainer,
+    core::{io, parking_lot::Mutex, pool::Handle, uuid::Uuid, visitor::prelude::*},
+    draw::SharedTexture,
+    ttf::{Font, SharedFont},
+    NodeHandleMapping, UiNode, UserInterface,
+};
+use fxhash::FxHashMap;
+use std::{
+    any::Any,
+    fmt::{Display, Formatter},
+    ops::{Deref, Range},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Converts textures to paths and back. UI library does not know anything about actual textures,
+/// so the host (usually the engine) must provide a way to save and restore texture references.
+pub trait TextureResolver: Send + Sync {
+    /// Returns a path of the given texture or `None` if the texture cannot be saved as a reference
+    /// (for example if it was created procedurally).
+    fn texture_path(&self, texture: &SharedTexture) -> Option<PathBuf>;
+
+    /// Returns a texture for the given path, the texture may be loaded asynchronously.
+    fn load_texture(&self, path: &Path) -> Option<SharedTexture>;
+}
+
+/// A set of data needed to save and load UI scenes.
+pub struct SerializationContext {
+    /// A container with constructors for every widget type that can be loaded.
+    pub widget_constructors: Arc<WidgetConstructorContainer>,
+    /// A font that will be used instead of fonts that have no source file.
+    pub default_font: SharedFont,
+    /// An optional texture resolver, see [`TextureResolver`] docs.
+    pub texture_resolver: Option<Arc<dyn TextureResolver>>,
+}
+
+impl SerializationContext {
+    /// Creates new serialization context with constructors for built-in widgets and without a
+    /// texture resolver.
+    pub fn new(default_font: SharedFont) -> Self {
+        Self {
+            widget_constructors: Arc::new(WidgetConstructorContainer::new()),
+            default_font,
+            texture_resolver: None,
+        }
+    }
+
+    /// Sets widget constructors, it allows to share the same container between multiple contexts.
+    pub fn with_widget_constructors(
+        mut self,
+        widget_constructors: Arc<WidgetConstructorContainer>,
+    ) -> Self {
+        self.widget_constructors = widget_constructors;
+        self
+    }
+
+    /// Sets texture resolver.
+    pub fn with_texture_resolver<R: TextureResolver + 'static>(mut self, resolver: R) -> Self {
+        self.texture_resolver = Some(Arc::new(resolver));
+        self
+    }
+}
+
+/// Visitor environment that is used while a scene is saved or loaded. It holds a table of fonts,
+/// fonts are saved as indices in the table.
+pub(crate) struct VisitEnvironment {
+    context: Arc<SerializationContext>,
+    fonts: Mutex<Vec<SharedFont>>,
+}
+
+impl VisitEnvironment {
+    pub(crate) fn from_visitor(visitor: &Visitor) -> Result<EnvironmentRef, VisitError> {
+        match visitor.environment {
+            Some(ref environment) if environment.is::<VisitEnvironment>() => {
+                Ok(EnvironmentRef(environment.clone()))
+            }
+            _ => Err(VisitError::User(
+                "Visitor environment must contain UI serialization context!".to_owned(),
+            )),
+        }
+    }
+
+    pub(crate) fn register_font(&self, font: &SharedFont) -> u32 {
+        let mut fonts = self.fonts.lock();
+        match fonts.iter().position(|f| f == font) {
+            Some(index) => index as u32,
+            None => {
+                fonts.push(font.clone());
+                fonts.len() as u32 - 1
+            }
+        }
+    }
+
+    pub(crate) fn font(&self, id: u32) -> Option<SharedFont> {
+        self.fonts.lock().get(id as usize).cloned()
+    }
+}
+
+/// A shared reference to the environment of a visitor, it allows to use the environment while
+/// the visitor is borrowed mutably.
+pub(crate) struct EnvironmentRef(Arc<dyn Any>);
+
+impl Deref for EnvironmentRef {
+    type Target = VisitEnvironment;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.downcast_ref().unwrap()
+    }
+}
+
+/// Saves texture reference using texture resolver of the serialization context. It could be used
+/// to implement `Visit` trait for custom widgets with textures.
+pub fn visit_texture(
+    texture: &mut Option<SharedTexture>,
+    name: &str,
+    visitor: &mut Visitor,
+) -> VisitResult {
+    let environment = VisitEnvironment::from_visitor(visitor)?;
+    let resolver = environment.context.texture_resolver.as_ref();
+
+    let mut path = if visitor.is_reading() {
+        None
+    } else {
+        texture
+            .as_ref()
+            .and_then(|texture| resolver.and_then(|r| r.texture_path(texture)))
+    };
+    path.visit(name, visitor)?;
+
+    if visitor.is_reading() {
+        *texture = path.and_then(|path| resolver.and_then(|r| r.load_texture(&path)));
+    }
+
+    Ok(())
+}
+
+#[derive(Default, Visit)]
+struct FontDefinition {
+    path: Option<PathBuf>,
+    height: f32,
+    char_set: Vec<Range<u32>>,
+}
+
+/// An error that may occur during UI scene loading.
+#[derive(Debug)]
+pub enum UiSceneError {
+    /// The file is corrupted or has incompatible version.
+    Visit(VisitError),
+    /// Unable to load a font.
+    Font {
+        /// Path of the font.
+        path: PathBuf,
+        /// Reason of the error.
+        reason: &'static str,
+    },
+}
+
+impl Display for UiSceneError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UiSceneError::Visit(err) => write!(f, "Unable to read UI scene: {}", err),
+            UiSceneError::Font { path, reason } => {
+                write!(f, "Unable to load font {}: {}", path.display(), reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for UiSceneError {}
+
+impl From<VisitError> for UiSceneError {
+    fn from(err: VisitError) -> Self {
+        Self::Visit(err)
+    }
+}
+
+/// See module docs.
+#[derive(Default)]
+pub struct UiScene {
+    nodes: Vec<UiNode>,
+    root: Handle<UiNode>,
+}
+
+fn read_node(name: &str, visitor: &mut Visitor) -> Result<UiNode, VisitError> {
+    let environment = VisitEnvironment::from_visitor(visitor)?;
+
+    let mut region = visitor.enter_region(name)?;
+
+    let mut id = Uuid::default();
+    id.visit("TypeUuid", &mut region)?;
+
+    let mut node = environment
+        .context
+        .widget_constructors
+        .try_create(&id)
+        .ok_or_else(|| VisitError::User(format!("Unknown widget type uuid {}!", id)))?;
+
+    environment
+        .context
+        .widget_constructors
+        .visit(&mut node, "WidgetData", &mut region)?;
+
+    Ok(node)
+}
+
+fn write_node(name: &str, node: &mut UiNode, visitor: &mut Visitor) -> VisitResult {
+    let environment = VisitEnvironment::from_visitor(visitor)?;
+    let widget_constructors = &environment.context.widget_constructors;
+
+    let mut region = visitor.enter_region(name)?;
+
+    let mut id = widget_constructors.type_uuid_of(node).ok_or_else(|| {
+        VisitError::User(format!(
+            "Widget {} has unregistered type and cannot be saved!",
+            node.name()
+        ))
+    })?;
+    id.visit("TypeUuid", &mut region)?;
+
+    widget_constructors.visit(node, "WidgetData", &mut region)?;
+
+    Ok(())
+}
+
+impl UiScene {
+    /// Creates new scene from a widget and all its descendants.
+    pub fn from_ui(ui: &UserInterface, root: Handle<UiNode>) -> Self {
+        let mut nodes = Vec::new();
+        let mut stack = vec![root];
+        while let Some(handle) = stack.pop() {
+            let node = ui.node(handle);
+            stack.extend_from_slice(node.children());
+            nodes.push(UiNode(node.clone_boxed()));
+        }
+        Self { nodes, root }
+    }
+
+    /// Returns original handle of the root widget of the scene.
+    pub fn root(&self) -> Handle<UiNode> {
+        self.root
+    }
+
+    /// Returns widgets of the scene, widgets keep their original handles.
+    pub fn nodes(&self) -> &[UiNode] {
+        &self.nodes
+    }
+
+    /// Adds copies of the widgets of the scene to the given user interface and returns a handle
+    /// of the new root widget. The root widget will be attached to the root canvas of the UI.
+    /// The method can be called multiple times to create multiple instances of the scene.
+    pub fn instantiate(&self, ui: &mut UserInterface) -> Handle<UiNode> {
+        let nodes = self
+            .nodes
+            .iter()
+            .map(|node| (node.handle(), node))
+            .collect::<FxHashMap<_, _>>();
+
+        let mut map = NodeHandleMapping::default();
+
+        let root = instantiate_recursive(ui, &nodes, self.root, &mut map);
+
+        for &node_handle in map.hash_map.values() {
+            ui.nodes[node_handle].resolve(&map);
+        }
+
+        root
+    }
+
+    /// Saves the scene to a file using binary format.
+    pub fn save<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        context: Arc<SerializationContext>,
+    ) -> VisitResult {
+        let environment = Arc::new(VisitEnvironment {
+            context,
+            fonts: Default::default(),
+        });
+
+        let mut visitor = Visitor::new();
+        visitor.environment = Some(environment.clone());
+
+        // Fonts are registered while widgets are written, so the table must be written last.
+        self.visit_nodes("Scene", &mut visitor)?;
+
+        let mut definitions = environment
+            .fonts
+            .lock()
+            .iter()
+            .map(|font| {
+                let font = font.0.lock();
+                FontDefinition {
+                    path: font.path().map(|p| p.to_owned()),
+                    height: font.height(),
+                    char_set: font.char_set().to_vec(),
+                }
+            })
+            .collect::<Vec<_>>();
+        definitions.visit("Fonts", &mut visitor)?;
+
+        visitor.save_binary(path)
+    }
+
+    /// Loads a scene from a file. Fonts are loaded first, so the method is asynchronous. See
+    /// [`UiSceneSource`] if you need to load a scene in a background thread.
+    pub async fn from_file<P: AsRef<Path>>(
+        path: P,
+        context: Arc<SerializationContext>,
+    ) -> Result<Self, UiSceneError> {
+        UiSceneSource::from_file(path).await?.to_scene(context)
+    }
+
+    fn visit_nodes(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        self.root.visit("Root", &mut region)?;
+
+        let mut count = self.nodes.len() as u32;
+        count.visit("NodeCount", &mut region)?;
+
+        if region.is_reading() {
+            self.nodes.clear();
+            for i in 0..count {
+                self.nodes
+                    .push(read_node(&format!("Node{}", i), &mut region)?);
+            }
+        } else {
+            for (i, node) in self.nodes.iter_mut().enumerate() {
+                write_node(&format!("Node{}", i), node, &mut region)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Contents of a UI scene file together with fonts the scene uses. Unlike [`UiScene`], the source
+/// can be sent to other threads, so it can be loaded in background and turned into a scene later
+/// using [`Self::to_scene`].
+#[derive(Default, Debug, Clone)]
+pub struct UiSceneSource {
+    data: Vec<u8>,
+    // `None` means that the font has no source file and will be replaced with default font.
+    fonts: Vec<Option<SharedFont>>,
+}
+
+impl UiSceneSource {
+    /// Reads a scene file and loads every font that is used by the scene.
+    pub async fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, UiSceneError> {
+        let data = io::load_file(path).await.map_err(VisitError::from)?;
+
+        // Visitor is not `Send`, so it must not live across await points.
+        let definitions = {
+            let mut visitor = Visitor::load_from_memory(data.clone())?;
+            let mut definitions = Vec::<FontDefinition>::new();
+            definitions.visit("Fonts", &mut visitor)?;
+            definitions
+        };
+
+        let mut fonts = Vec::with_capacity(definitions.len());
+        for definition in definitions {
+            let font = match definition.path {
+                Some(path) => Some(SharedFont::new(
+                    Font::from_file(&path, definition.height, &definition.char_set)
+                        .await
+                        .map_err(|reason| UiSceneError::Font { path, reason })?,
+                )),
+                None => None,
+            };
+            fonts.push(font);
+        }
+
+        Ok(Self { data, fonts })
+    }
+
+    /// Creates widgets of the scene. Every call creates a new set of widgets, fonts are shared.
+    pub fn to_scene(&self, context: Arc<SerializationContext>) -> Result<UiScene, UiSceneError> {
+        let mut visitor = Visitor::load_from_memory(self.data.clone())?;
+
+        let fonts = self
+            .fonts
+            .iter()
+            .map(|font| font.clone().unwrap_or_else(|| context.default_font.clone()))
+            .collect();
+
+        visitor.environment = Some(Arc::new(VisitEnvironment {
+            context,
+            fonts: Mutex::new(fonts),
+        }));
+
+        let mut scene = UiScene::default();
+        scene.visit_nodes("Scene", &mut visitor)?;
+        Ok(scene)
+    }
+}
+
+fn instantiate_recursive(
+    ui: &mut UserInterface,
+    nodes: &FxHashMap<Handle<UiNode>, &UiNode>,
+    node_handle: Handle<UiNode>,
+    map: &mut NodeHandleMapping,
+) -> Handle<UiNode> {
+    let node = match nodes.get(&node_handle) {
+        Some(node) => *node,
+        None => return Handle::NONE,
+    };
+
+    let mut copy = UiNode(node.clone_boxed());
+
+    let mut children = Vec::new();
+    for &child in node.children() {
+        let child_copy = instantiate_recursive(ui, nodes, child, map);
+        if child_copy.is_some() {
+            children.push(child_copy);
+        }
+    }
+
+    copy.set_children(children);
+    // Parent handle is from other user interface, it will be set on linking.
+    copy.parent = Handle::NONE;
+    let copy_handle = ui.add_node(copy);
+    map.add_mapping(node_handle, copy_handle);
+    copy_handle
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{algebra::Vector2, futures::executor::block_on, TypeUuidProvider},
+        scene::{SerializationContext, UiScene},
+        stack_panel::{StackPanel, StackPanelBuilder},
+        text::{Text, TextBuilder},
+        widget::WidgetBuilder,
+        Orientation, UserInterface,
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn save_load_instantiate() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+
+        let ctx = &mut ui.build_ctx();
+        let text = TextBuilder::new(WidgetBuilder::new().with_name("Text"))
+            .with_text("Hello")
+            .build(ctx);
+        let panel = StackPanelBuilder::new(WidgetBuilder::new().with_child(text))
+            .with_orientation(Orientation::Horizontal)
+            .build(ctx);
+
+        let context = Arc::new(SerializationContext::new(ui.default_font.clone()));
+
+        let path = std::env::temp_dir().join("fyrox_ui_scene_test.uis");
+        let mut scene = UiScene::from_ui(&ui, panel);
+        scene.save(&path, context.clone()).unwrap();
+
+        let loaded = block_on(UiScene::from_file(&path, context)).unwrap();
+        assert_eq!(loaded.nodes().len(), 2);
+
+        let mut other_ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        let root = loaded.instantiate(&mut other_ui);
+
+        let panel = other_ui
+            .node(root)
+            .cast::<StackPanel>()
+            .expect("Root must be a stack panel!");
+        assert_eq!(panel.orientation, Orientation::Horizontal);
+        assert_eq!(panel.children().len(), 1);
+
+        let text_handle = panel.children()[0];
+        let text = other_ui
+            .node(text_handle)
+            .cast::<Text>()
+            .expect("Child must be a text!");
+        assert_eq!(text.text(), "Hello");
+        assert_eq!(text.name(), "Text");
+        assert_eq!(text.parent(), root);
+    }
+
+    #[test]
+    fn unregistered_widget_cannot_be_saved() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+
+        let text = TextBuilder::new(WidgetBuilder::new()).build(&mut ui.build_ctx());
+
+        let context = SerializationContext::new(ui.default_font.clone());
+        context.widget_constructors.remove(Text::type_uuid());
+
+        let path = std::env::temp_dir().join("fyrox_ui_scene_unregistered_test.uis");
+        let mut scene = UiScene::from_ui(&ui, text);
+        assert!(scene.save(&path, Arc::new(context)).is_err());
+    }
+}
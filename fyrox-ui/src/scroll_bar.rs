@@ -3,7 +3,14 @@ use crate::{
     brush::{Brush, GradientPoint},
     button::{ButtonBuilder, ButtonMessage},
     canvas::CanvasBuilder,
-    core::{algebra::Vector2, color::Color, pool::Handle},
+    core::{
+        algebra::Vector2,
+        color::Color,
+        pool::Handle,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     decorator::DecoratorBuilder,
     define_constructor,
     grid::{Column, GridBuilder, Row},
@@ -33,7 +40,7 @@ impl ScrollBarMessage {
     define_constructor!(ScrollBarMessage:MinValue => fn min_value(f32), layout: false);
 }
 
-#[derive(Clone)]
+#[derive(Clone, Visit)]
 pub struct ScrollBar {
     pub widget: Widget,
     pub min: f32,
@@ -41,7 +48,9 @@ pub struct ScrollBar {
     pub value: f32,
     pub step: f32,
     pub orientation: Orientation,
+    #[visit(skip)]
     pub is_dragging: bool,
+    #[visit(skip)]
     pub offset: Vector2<f32>,
     pub increase: Handle<UiNode>,
     pub decrease: Handle<UiNode>,
@@ -53,6 +62,33 @@ pub struct ScrollBar {
 
 crate::define_widget_deref!(ScrollBar);
 
+impl Default for ScrollBar {
+    fn default() -> Self {
+        Self {
+            widget: Default::default(),
+            min: 0.0,
+            max: 100.0,
+            value: 0.0,
+            step: 1.0,
+            orientation: Orientation::Horizontal,
+            is_dragging: false,
+            offset: Default::default(),
+            increase: Default::default(),
+            decrease: Default::default(),
+            indicator: Default::default(),
+            field: Default::default(),
+            value_text: Default::default(),
+            value_precision: 3,
+        }
+    }
+}
+
+impl TypeUuidProvider for ScrollBar {
+    fn type_uuid() -> Uuid {
+        uuid!("6f91006e-621a-467f-a897-1c2b159c7abc")
+    }
+}
+
 impl Control for ScrollBar {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
@@ -1,6 +1,15 @@
 use crate::{
     brush::Brush,
-    core::{algebra::Vector2, color::Color, math::Rect, pool::Handle, scope_profile},
+    core::{
+        algebra::Vector2,
+        color::Color,
+        math::Rect,
+        pool::Handle,
+        scope_profile,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     define_constructor,
     draw::{CommandTexture, Draw, DrawingContext},
     message::{MessageDirection, UiMessage},
@@ -28,7 +37,7 @@ impl ScrollPanelMessage {
 }
 
 /// Allows user to scroll content
-#[derive(Clone)]
+#[derive(Clone, Visit)]
 pub struct ScrollPanel {
     pub widget: Widget,
     pub scroll: Vector2<f32>,
@@ -38,6 +47,23 @@ pub struct ScrollPanel {
 
 crate::define_widget_deref!(ScrollPanel);
 
+impl Default for ScrollPanel {
+    fn default() -> Self {
+        Self {
+            widget: Default::default(),
+            scroll: Default::default(),
+            vertical_scroll_allowed: true,
+            horizontal_scroll_allowed: false,
+        }
+    }
+}
+
+impl TypeUuidProvider for ScrollPanel {
+    fn type_uuid() -> Uuid {
+        uuid!("ea1abce2-6b51-412f-8416-c8d1c12570ad")
+    }
+}
+
 impl Control for ScrollPanel {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
@@ -1,5 +1,11 @@
 use crate::{
-    core::{algebra::Vector2, pool::Handle},
+    core::{
+        algebra::Vector2,
+        pool::Handle,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     define_constructor,
     grid::{Column, GridBuilder, Row},
     message::{MessageDirection, UiMessage},
@@ -26,7 +32,7 @@ impl ScrollViewerMessage {
     define_constructor!(ScrollViewerMessage:BringIntoView=> fn bring_into_view(Handle<UiNode>), layout: true);
 }
 
-#[derive(Clone)]
+#[derive(Default, Clone, Visit)]
 pub struct ScrollViewer {
     pub widget: Widget,
     pub content: Handle<UiNode>,
@@ -67,6 +73,12 @@ impl ScrollViewer {
     }
 }
 
+impl TypeUuidProvider for ScrollViewer {
+    fn type_uuid() -> Uuid {
+        uuid!("3685088d-2feb-47a4-9b05-f683f381da37")
+    }
+}
+
 impl Control for ScrollViewer {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
@@ -2,7 +2,13 @@ use crate::{
     border::BorderBuilder,
     brush::Brush,
     button::{ButtonBuilder, ButtonMessage},
-    core::{color::Color, pool::Handle},
+    core::{
+        color::Color,
+        pool::Handle,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     grid::{Column, GridBuilder, Row},
     message::{MessageDirection, UiMessage},
     widget::{Widget, WidgetBuilder, WidgetMessage},
@@ -13,13 +19,13 @@ use std::{
     ops::{Deref, DerefMut},
 };
 
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Default, Clone, PartialEq, Eq, Visit)]
 pub struct Tab {
     pub header_button: Handle<UiNode>,
     pub content: Handle<UiNode>,
 }
 
-#[derive(Clone)]
+#[derive(Default, Clone, Visit)]
 pub struct TabControl {
     pub widget: Widget,
     pub tabs: Vec<Tab>,
@@ -27,6 +33,12 @@ pub struct TabControl {
 
 crate::define_widget_deref!(TabControl);
 
+impl TypeUuidProvider for TabControl {
+    fn type_uuid() -> Uuid {
+        uuid!("99b55e20-ebe3-4356-a5cb-d77e217b8196")
+    }
+}
+
 impl Control for TabControl {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
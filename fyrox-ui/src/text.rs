@@ -1,6 +1,13 @@
 use crate::{
     brush::Brush,
-    core::{algebra::Vector2, color::Color, pool::Handle},
+    core::{
+        algebra::Vector2,
+        color::Color,
+        pool::Handle,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     define_constructor,
     draw::DrawingContext,
     formatted_text::{FormattedText, FormattedTextBuilder, WrapMode},
@@ -40,7 +47,7 @@ impl TextMessage {
     define_constructor!(TextMessage:ShadowOffset => fn shadow_offset(Vector2<f32>), layout: false);
 }
 
-#[derive(Clone)]
+#[derive(Default, Clone, Visit)]
 pub struct Text {
     pub widget: Widget,
     pub formatted_text: RefCell<FormattedText>,
@@ -48,6 +55,12 @@ pub struct Text {
 
 crate::define_widget_deref!(Text);
 
+impl TypeUuidProvider for Text {
+    fn type_uuid() -> Uuid {
+        uuid!("c6732702-c033-414e-8cc2-f53f03e6e89b")
+    }
+}
+
 impl Control for Text {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
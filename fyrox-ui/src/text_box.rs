@@ -5,6 +5,9 @@ use crate::{
         color::Color,
         math::Rect,
         pool::Handle,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
     },
     define_constructor,
     draw::{CommandTexture, Draw, DrawingContext},
@@ -70,7 +73,7 @@ pub struct Position {
     pub offset: usize,
 }
 
-#[derive(Copy, Clone, PartialOrd, PartialEq, Eq, Ord, Hash, Debug)]
+#[derive(Copy, Clone, PartialOrd, PartialEq, Eq, Ord, Hash, Debug, Visit)]
 #[repr(u32)]
 pub enum TextCommitMode {
     /// Text box will immediately send Text message after any change.
@@ -119,23 +122,31 @@ impl SelectionRange {
 
 pub type FilterCallback = dyn FnMut(char) -> bool;
 
-#[derive(Clone)]
+#[derive(Clone, Visit)]
 pub struct TextBox {
     pub widget: Widget,
+    #[visit(skip)]
     pub caret_position: Position,
+    #[visit(skip)]
     pub caret_visible: bool,
+    #[visit(skip)]
     pub blink_timer: f32,
     pub blink_interval: f32,
     pub formatted_text: RefCell<FormattedText>,
+    #[visit(skip)]
     pub selection_range: Option<SelectionRange>,
+    #[visit(skip)]
     pub selecting: bool,
+    #[visit(skip)]
     pub has_focus: bool,
     pub caret_brush: Brush,
     pub selection_brush: Brush,
+    #[visit(skip)]
     pub filter: Option<Rc<RefCell<FilterCallback>>>,
     pub commit_mode: TextCommitMode,
     pub multiline: bool,
     pub editable: bool,
+    #[visit(skip)]
     pub view_position: Vector2<f32>,
     pub skip_chars: Vec<u32>,
 }
@@ -148,6 +159,30 @@ impl Debug for TextBox {
 
 crate::define_widget_deref!(TextBox);
 
+impl Default for TextBox {
+    fn default() -> Self {
+        Self {
+            widget: Default::default(),
+            caret_position: Position::default(),
+            caret_visible: false,
+            blink_timer: 0.0,
+            blink_interval: 0.5,
+            formatted_text: Default::default(),
+            selection_range: None,
+            selecting: false,
+            has_focus: false,
+            caret_brush: Brush::Solid(Color::WHITE),
+            selection_brush: Brush::Solid(Color::opaque(80, 118, 178)),
+            filter: None,
+            commit_mode: TextCommitMode::LostFocusPlusEnter,
+            multiline: false,
+            editable: true,
+            view_position: Default::default(),
+            skip_chars: Default::default(),
+        }
+    }
+}
+
 impl TextBox {
     fn reset_blink(&mut self) {
         self.caret_visible = true;
@@ -683,6 +718,12 @@ impl TextBox {
     }
 }
 
+impl TypeUuidProvider for TextBox {
+    fn type_uuid() -> Uuid {
+        uuid!("00279014-d6ea-4c5c-873e-5bf7ac8ac80f")
+    }
+}
+
 impl Control for TextBox {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
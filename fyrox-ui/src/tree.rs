@@ -11,7 +11,14 @@ use crate::{
     border::BorderBuilder,
     brush::Brush,
     check_box::{CheckBoxBuilder, CheckBoxMessage},
-    core::{algebra::Vector2, color::Color, pool::Handle},
+    core::{
+        algebra::Vector2,
+        color::Color,
+        pool::Handle,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     decorator::{DecoratorBuilder, DecoratorMessage},
     define_constructor,
     grid::{Column, GridBuilder, Row},
@@ -82,7 +89,7 @@ impl TreeRootMessage {
     define_constructor!(TreeRootMessage:CollapseAll => fn collapse_all(), layout: false);
 }
 
-#[derive(Debug, Clone)]
+#[derive(Default, Debug, Clone, Visit)]
 pub struct Tree {
     pub widget: Widget,
     pub expander: Handle<UiNode>,
@@ -97,6 +104,12 @@ pub struct Tree {
 
 crate::define_widget_deref!(Tree);
 
+impl TypeUuidProvider for Tree {
+    fn type_uuid() -> Uuid {
+        uuid!("7c0ebf81-f7bc-493b-b90d-5fc29db1237a")
+    }
+}
+
 impl Control for Tree {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
@@ -464,7 +477,7 @@ fn build_expander(
     .build(ctx)
 }
 
-#[derive(Debug, Clone)]
+#[derive(Default, Debug, Clone, Visit)]
 pub struct TreeRoot {
     widget: Widget,
     panel: Handle<UiNode>,
@@ -474,6 +487,12 @@ pub struct TreeRoot {
 
 crate::define_widget_deref!(TreeRoot);
 
+impl TypeUuidProvider for TreeRoot {
+    fn type_uuid() -> Uuid {
+        uuid!("02f108a0-aa9a-4c8a-98dc-094beb09e404")
+    }
+}
+
 impl Control for TreeRoot {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
@@ -1,13 +1,14 @@
 use crate::{
-    core::{algebra::Vector2, io, parking_lot::Mutex, rectpack::RectPacker},
+    core::{algebra::Vector2, io, parking_lot::Mutex, rectpack::RectPacker, visitor::prelude::*},
     draw::SharedTexture,
+    scene::VisitEnvironment,
 };
 use fxhash::FxHashMap;
 use std::{
     borrow::Cow,
     fmt::{Debug, Formatter},
     ops::{Deref, Range},
-    path::Path,
+    path::{Path, PathBuf},
     sync::Arc,
 };
 
@@ -22,8 +23,11 @@ pub struct FontGlyph {
     pub pixels: Vec<u8>,
 }
 
+#[derive(Default)]
 pub struct Font {
     height: f32,
+    path: Option<PathBuf>,
+    char_set: Vec<Range<u32>>,
     glyphs: Vec<FontGlyph>,
     ascender: f32,
     descender: f32,
@@ -46,6 +50,39 @@ impl SharedFont {
     }
 }
 
+impl Default for SharedFont {
+    fn default() -> Self {
+        Self::new(Font::default())
+    }
+}
+
+/// Fonts are saved as references - only the path, the height and the char set are written. Fonts
+/// are loaded before widgets, so the actual fonts are taken from the visitor environment. Fonts
+/// without a path (for example the built-in font) are replaced with the default font of the
+/// serialization context on load. See [`crate::scene::UiScene`] for more info.
+impl Visit for SharedFont {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let environment = VisitEnvironment::from_visitor(visitor)?;
+
+        let mut region = visitor.enter_region(name)?;
+
+        let mut id = if region.is_reading() {
+            0
+        } else {
+            environment.register_font(self)
+        };
+        id.visit("Id", &mut region)?;
+
+        if region.is_reading() {
+            *self = environment
+                .font(id)
+                .ok_or_else(|| VisitError::User(format!("Font with id {} does not exist!", id)))?;
+        }
+
+        Ok(())
+    }
+}
+
 impl From<Arc<Mutex<Font>>> for SharedFont {
     fn from(arc: Arc<Mutex<Font>>) -> Self {
         SharedFont(arc)
@@ -153,6 +190,8 @@ impl Font {
 
         let mut font = Font {
             height,
+            path: None,
+            char_set: char_set.to_vec(),
             glyphs: Vec::new(),
             ascender: font_metrics.ascent,
             descender: font_metrics.descent,
@@ -194,8 +233,10 @@ impl Font {
         height: f32,
         char_set: &[Range<u32>],
     ) -> Result<Self, &'static str> {
-        if let Ok(file_content) = io::load_file(path).await {
-            Self::from_memory(file_content, height, char_set)
+        if let Ok(file_content) = io::load_file(path.as_ref()).await {
+            let mut font = Self::from_memory(file_content, height, char_set)?;
+            font.path = Some(path.as_ref().to_owned());
+            Ok(font)
         } else {
             Err("Unable to read file")
         }
@@ -224,6 +265,19 @@ impl Font {
         self.height
     }
 
+    /// Returns path of the file from which the font was loaded, `None` if the font was created
+    /// from memory.
+    #[inline]
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Returns ranges of characters that were rasterized to the atlas of the font.
+    #[inline]
+    pub fn char_set(&self) -> &[Range<u32>] {
+        &self.char_set
+    }
+
     #[inline]
     pub fn ascender(&self) -> f32 {
         self.ascender
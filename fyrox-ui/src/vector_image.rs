@@ -1,5 +1,13 @@
 use crate::{
-    core::{algebra::Vector2, color::Color, math::Vector2Ext, pool::Handle},
+    core::{
+        algebra::Vector2,
+        color::Color,
+        math::Vector2Ext,
+        pool::Handle,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     draw::{CommandTexture, Draw, DrawingContext},
     message::UiMessage,
     widget::{Widget, WidgetBuilder},
@@ -10,7 +18,7 @@ use std::{
     ops::{Deref, DerefMut},
 };
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Visit)]
 pub enum Primitive {
     Triangle {
         points: [Vector2<f32>; 3],
@@ -27,6 +35,14 @@ pub enum Primitive {
     },
 }
 
+impl Default for Primitive {
+    fn default() -> Self {
+        Self::Triangle {
+            points: Default::default(),
+        }
+    }
+}
+
 fn line_thickness_vector(a: Vector2<f32>, b: Vector2<f32>, thickness: f32) -> Vector2<f32> {
     if let Some(dir) = (b - a).try_normalize(f32::EPSILON) {
         Vector2::new(dir.y, -dir.x).scale(thickness * 0.5)
@@ -69,7 +85,7 @@ impl Primitive {
     }
 }
 
-#[derive(Clone)]
+#[derive(Default, Clone, Visit)]
 pub struct VectorImage {
     pub widget: Widget,
     pub primitives: Vec<Primitive>,
@@ -77,6 +93,12 @@ pub struct VectorImage {
 
 crate::define_widget_deref!(VectorImage);
 
+impl TypeUuidProvider for VectorImage {
+    fn type_uuid() -> Uuid {
+        uuid!("a883cac2-9856-401f-a734-3c254ab54915")
+    }
+}
+
 impl Control for VectorImage {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
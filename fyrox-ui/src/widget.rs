@@ -1,6 +1,6 @@
 use crate::{
     brush::Brush,
-    core::{algebra::Vector2, math::Rect, pool::Handle},
+    core::{algebra::Vector2, math::Rect, pool::Handle, visitor::prelude::*},
     define_constructor,
     message::{CursorIcon, KeyCode, MessageDirection, UiMessage},
     HorizontalAlignment, LayoutEvent, MouseButton, MouseState, Thickness, UiNode, UserInterface,
@@ -331,7 +331,7 @@ impl WidgetMessage {
     define_constructor!(WidgetMessage:DoubleClick => fn double_click(button: MouseButton), layout: false);
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Visit)]
 pub struct Widget {
     pub handle: Handle<UiNode>,
     pub name: String,
@@ -359,16 +359,20 @@ pub struct Widget {
     pub margin: Thickness,
     /// Current visibility state
     pub visibility: bool,
+    #[visit(skip)]
     pub global_visibility: bool,
     pub children: Vec<Handle<UiNode>>,
     pub parent: Handle<UiNode>,
     /// Indices of commands in command buffer emitted by the node.
+    #[visit(skip)]
     pub command_indices: RefCell<Vec<usize>>,
+    #[visit(skip)]
     pub is_mouse_directly_over: bool,
     pub hit_test_visibility: bool,
     pub z_index: usize,
     pub allow_drag: bool,
     pub allow_drop: bool,
+    #[visit(skip)]
     pub user_data: Option<Rc<dyn Any>>,
     pub draw_on_top: bool,
     pub enabled: bool,
@@ -380,27 +384,44 @@ pub struct Widget {
     pub clip_to_bounds: bool,
     pub layout_transform: Matrix3<f32>,
     pub render_transform: Matrix3<f32>,
+    #[visit(skip)]
     pub visual_transform: Matrix3<f32>,
     pub preview_messages: bool,
     pub handle_os_events: bool,
+    #[visit(skip)]
     pub layout_events_sender: Option<Sender<LayoutEvent>>,
 
     /// Layout. Interior mutability is a must here because layout performed in
     /// a series of recursive calls.
+    #[visit(skip)]
     pub measure_valid: Cell<bool>,
+    #[visit(skip)]
     pub arrange_valid: Cell<bool>,
+    #[visit(skip)]
     pub prev_measure: Cell<Vector2<f32>>,
+    #[visit(skip)]
     pub prev_arrange: Cell<Rect<f32>>,
     /// Desired size of the node after Measure pass.
+    #[visit(skip)]
     pub desired_size: Cell<Vector2<f32>>,
     /// Actual local position of the widget after Arrange pass.
+    #[visit(skip)]
     pub actual_local_position: Cell<Vector2<f32>>,
     /// Actual local size of the widget after Arrange pass.
+    #[visit(skip)]
     pub actual_local_size: Cell<Vector2<f32>>,
+    #[visit(skip)]
     pub prev_global_visibility: bool,
+    #[visit(skip)]
     pub clip_bounds: Cell<Rect<f32>>,
 }
 
+impl Default for Widget {
+    fn default() -> Self {
+        WidgetBuilder::new().build()
+    }
+}
+
 impl Widget {
     #[inline]
     pub fn handle(&self) -> Handle<UiNode> {
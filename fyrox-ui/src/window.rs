@@ -2,7 +2,15 @@ use crate::{
     border::BorderBuilder,
     brush::{Brush, GradientPoint},
     button::{ButtonBuilder, ButtonMessage},
-    core::{algebra::Vector2, color::Color, math::Rect, pool::Handle},
+    core::{
+        algebra::Vector2,
+        color::Color,
+        math::Rect,
+        pool::Handle,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     decorator::DecoratorBuilder,
     define_constructor,
     grid::{Column, GridBuilder, Row},
@@ -76,12 +84,16 @@ impl WindowMessage {
 /// Represents a widget looking as window in Windows - with title, minimize and close buttons.
 /// It has scrollable region for content, content can be any desired node or even other window.
 /// Window can be dragged by its title.
-#[derive(Clone)]
+#[derive(Clone, Visit)]
 pub struct Window {
     pub widget: Widget,
+    #[visit(skip)]
     pub mouse_click_pos: Vector2<f32>,
+    #[visit(skip)]
     pub initial_position: Vector2<f32>,
+    #[visit(skip)]
     pub initial_size: Vector2<f32>,
+    #[visit(skip)]
     pub is_dragging: bool,
     pub minimized: bool,
     pub can_minimize: bool,
@@ -90,8 +102,10 @@ pub struct Window {
     pub header: Handle<UiNode>,
     pub minimize_button: Handle<UiNode>,
     pub close_button: Handle<UiNode>,
+    #[visit(skip)]
     pub drag_delta: Vector2<f32>,
     pub content: Handle<UiNode>,
+    #[visit(skip)]
     pub grips: RefCell<[Grip; 8]>,
     pub title: Handle<UiNode>,
     pub title_grid: Handle<UiNode>,
@@ -131,8 +145,52 @@ impl Grip {
     }
 }
 
+fn default_grips() -> [Grip; 8] {
+    [
+        // Corners have priority
+        Grip::new(GripKind::LeftTopCorner, CursorIcon::NwResize),
+        Grip::new(GripKind::RightTopCorner, CursorIcon::NeResize),
+        Grip::new(GripKind::RightBottomCorner, CursorIcon::SeResize),
+        Grip::new(GripKind::LeftBottomCorner, CursorIcon::SwResize),
+        Grip::new(GripKind::Left, CursorIcon::WResize),
+        Grip::new(GripKind::Top, CursorIcon::NResize),
+        Grip::new(GripKind::Right, CursorIcon::EResize),
+        Grip::new(GripKind::Bottom, CursorIcon::SResize),
+    ]
+}
+
 crate::define_widget_deref!(Window);
 
+impl Default for Window {
+    fn default() -> Self {
+        Self {
+            widget: Default::default(),
+            mouse_click_pos: Default::default(),
+            initial_position: Default::default(),
+            initial_size: Default::default(),
+            is_dragging: false,
+            minimized: false,
+            can_minimize: true,
+            can_close: true,
+            can_resize: true,
+            header: Default::default(),
+            minimize_button: Default::default(),
+            close_button: Default::default(),
+            drag_delta: Default::default(),
+            content: Default::default(),
+            grips: RefCell::new(default_grips()),
+            title: Default::default(),
+            title_grid: Default::default(),
+        }
+    }
+}
+
+impl TypeUuidProvider for Window {
+    fn type_uuid() -> Uuid {
+        uuid!("c22da580-d4fe-494f-8ab2-bf529ebbb23d")
+    }
+}
+
 impl Control for Window {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
@@ -844,17 +902,7 @@ impl WindowBuilder {
             close_button,
             drag_delta: Default::default(),
             content: self.content,
-            grips: RefCell::new([
-                // Corners have priority
-                Grip::new(GripKind::LeftTopCorner, CursorIcon::NwResize),
-                Grip::new(GripKind::RightTopCorner, CursorIcon::NeResize),
-                Grip::new(GripKind::RightBottomCorner, CursorIcon::SeResize),
-                Grip::new(GripKind::LeftBottomCorner, CursorIcon::SwResize),
-                Grip::new(GripKind::Left, CursorIcon::WResize),
-                Grip::new(GripKind::Top, CursorIcon::NResize),
-                Grip::new(GripKind::Right, CursorIcon::EResize),
-                Grip::new(GripKind::Bottom, CursorIcon::SResize),
-            ]),
+            grips: RefCell::new(default_grips()),
             title,
             title_grid,
         }
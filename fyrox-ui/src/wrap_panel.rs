@@ -1,7 +1,14 @@
 #![allow(clippy::reversed_empty_ranges)]
 
 use crate::{
-    core::{algebra::Vector2, math::Rect, pool::Handle},
+    core::{
+        algebra::Vector2,
+        math::Rect,
+        pool::Handle,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
     message::UiMessage,
     widget::{Widget, WidgetBuilder},
     BuildContext, Control, Orientation, UiNode, UserInterface,
@@ -12,15 +19,26 @@ use std::{
     ops::{Deref, DerefMut, Range},
 };
 
-#[derive(Clone)]
+#[derive(Clone, Visit)]
 pub struct WrapPanel {
     pub widget: Widget,
     pub orientation: Orientation,
+    #[visit(skip)]
     pub lines: RefCell<Vec<Line>>,
 }
 
 crate::define_widget_deref!(WrapPanel);
 
+impl Default for WrapPanel {
+    fn default() -> Self {
+        Self {
+            widget: Default::default(),
+            orientation: Orientation::Vertical,
+            lines: Default::default(),
+        }
+    }
+}
+
 impl WrapPanel {
     pub fn new(widget: Widget) -> Self {
         Self {
@@ -57,6 +75,12 @@ impl Default for Line {
     }
 }
 
+impl TypeUuidProvider for WrapPanel {
+    fn type_uuid() -> Uuid {
+        uuid!("017d8f59-6ac1-4541-8224-d8487ed6f585")
+    }
+}
+
 impl Control for WrapPanel {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
@@ -0,0 +1,588 @@
+//! Inverse kinematics (IK) allows you to calculate rotations of bones of a chain, so the last bone
+//! of the chain (effector) will reach a target. Typical use cases are foot placement on uneven
+//! terrain and hands placement on a weapon. See [`IkChain`] docs for more info.
+//!
+//! IK is applied as a post-process step on an animation pose, before it is applied to a graph:
+//!
+//! ```no_run
+//! use fyrox::{
+//!     animation::{ik::IkChain, Animation},
+//!     core::algebra::Vector3,
+//!     scene::graph::Graph,
+//! };
+//!
+//! fn apply_animation(animation: &Animation, foot_ik: &mut IkChain, graph: &mut Graph) {
+//!     // A point on the ground, usually obtained by a ray cast.
+//!     foot_ik.set_target(Vector3::new(0.0, 0.1, 1.0));
+//!
+//!     let mut pose = animation.get_pose().clone();
+//!     foot_ik.apply(&mut pose, graph);
+//!     pose.apply(graph);
+//! }
+//! ```
+//!
+//! Animation blending state machines have built-in support for IK chains, see
+//! [`crate::animation::machine::Machine::add_ik_chain`].
+
+use crate::{
+    animation::{AnimationPose, LocalPose},
+    core::{
+        algebra::{Matrix3, Matrix4, Rotation3, UnitQuaternion, Vector3},
+        math::Matrix4Ext,
+        pool::Handle,
+        visitor::prelude::*,
+    },
+    scene::{graph::Graph, node::Node},
+};
+
+/// An algorithm that is used to solve an IK chain.
+#[derive(Debug, Clone, PartialEq, Visit)]
+pub enum IkSolver {
+    /// Analytical solver for chains of exactly three joints (for example thigh, shin and foot or
+    /// upper arm, forearm and hand). It is the fastest and the most stable solver, use it when
+    /// possible.
+    TwoBone,
+
+    /// Forward And Backward Reaching Inverse Kinematics. Iterative solver for chains of any
+    /// length, produces natural looking results for long chains (tails, tentacles, spines).
+    Fabrik {
+        /// Maximum amount of iterations.
+        iterations: u32,
+        /// Maximum distance between the effector and the target at which the chain is considered
+        /// solved.
+        tolerance: f32,
+    },
+
+    /// Cyclic Coordinate Descent. Iterative solver for chains of any length, it tends to bend
+    /// the joints closest to the effector more.
+    Ccd {
+        /// Maximum amount of iterations.
+        iterations: u32,
+        /// Maximum distance between the effector and the target at which the chain is considered
+        /// solved.
+        tolerance: f32,
+    },
+}
+
+impl Default for IkSolver {
+    fn default() -> Self {
+        Self::TwoBone
+    }
+}
+
+/// A chain of joints (bones) that should be rotated so the last joint of the chain (effector)
+/// reaches the target.
+///
+/// # Joints
+///
+/// Joints must be specified from the root of the chain to the effector, and every joint must be a
+/// descendant of the previous one. Only rotations of every joint except the effector are changed
+/// by a solver.
+///
+/// # Targets
+///
+/// Target and pole target are defined in world coordinates. Pole target defines a direction in
+/// which the chain will bend, for example a point in front of a knee of a character.
+///
+/// # Weight
+///
+/// Weight defines how much the solution affects the pose - zero weight leaves the pose untouched,
+/// one means that the effector will reach the target (if possible). Weight can be used to smoothly
+/// enable or disable IK.
+#[derive(Debug, Clone, PartialEq, Visit)]
+pub struct IkChain {
+    joints: Vec<Handle<Node>>,
+    solver: IkSolver,
+    target: Vector3<f32>,
+    pole_target: Option<Vector3<f32>>,
+    weight: f32,
+    enabled: bool,
+}
+
+impl Default for IkChain {
+    fn default() -> Self {
+        Self {
+            joints: Default::default(),
+            solver: Default::default(),
+            target: Default::default(),
+            pole_target: None,
+            weight: 1.0,
+            enabled: true,
+        }
+    }
+}
+
+impl IkChain {
+    /// Creates new IK chain with given solver and joints.
+    pub fn new(solver: IkSolver, joints: Vec<Handle<Node>>) -> Self {
+        Self {
+            joints,
+            solver,
+            ..Default::default()
+        }
+    }
+
+    /// Creates new IK chain with two-bone solver.
+    pub fn two_bone(upper: Handle<Node>, middle: Handle<Node>, end: Handle<Node>) -> Self {
+        Self::new(IkSolver::TwoBone, vec![upper, middle, end])
+    }
+
+    /// Creates new IK chain with FABRIK solver and default settings.
+    pub fn fabrik(joints: Vec<Handle<Node>>) -> Self {
+        Self::new(
+            IkSolver::Fabrik {
+                iterations: 10,
+                tolerance: 0.001,
+            },
+            joints,
+        )
+    }
+
+    /// Creates new IK chain with CCD solver and default settings.
+    pub fn ccd(joints: Vec<Handle<Node>>) -> Self {
+        Self::new(
+            IkSolver::Ccd {
+                iterations: 10,
+                tolerance: 0.001,
+            },
+            joints,
+        )
+    }
+
+    /// Returns joints of the chain.
+    pub fn joints(&self) -> &[Handle<Node>] {
+        &self.joints
+    }
+
+    /// Returns a mutable reference to joints of the chain.
+    pub fn joints_mut(&mut self) -> &mut Vec<Handle<Node>> {
+        &mut self.joints
+    }
+
+    /// Returns solver of the chain.
+    pub fn solver(&self) -> &IkSolver {
+        &self.solver
+    }
+
+    /// Sets new solver of the chain.
+    pub fn set_solver(&mut self, solver: IkSolver) {
+        self.solver = solver;
+    }
+
+    /// Sets new target position (in world coordinates) of the chain.
+    pub fn set_target(&mut self, target: Vector3<f32>) {
+        self.target = target;
+    }
+
+    /// Returns target position of the chain.
+    pub fn target(&self) -> Vector3<f32> {
+        self.target
+    }
+
+    /// Sets new pole target position (in world coordinates) of the chain.
+    pub fn set_pole_target(&mut self, pole_target: Option<Vector3<f32>>) {
+        self.pole_target = pole_target;
+    }
+
+    /// Returns pole target position of the chain.
+    pub fn pole_target(&self) -> Option<Vector3<f32>> {
+        self.pole_target
+    }
+
+    /// Sets new weight of the chain, it will be clamped to `[0; 1]` range.
+    pub fn set_weight(&mut self, weight: f32) {
+        self.weight = weight.clamp(0.0, 1.0);
+    }
+
+    /// Returns weight of the chain.
+    pub fn weight(&self) -> f32 {
+        self.weight
+    }
+
+    /// Enables or disables the chain.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Returns `true` if the chain is enabled, `false` - otherwise.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Solves the chain and modifies rotations of its joints in the given pose. Transforms of
+    /// nodes that are not in the pose are taken from the graph.
+    pub fn apply(&self, pose: &mut AnimationPose, graph: &Graph) {
+        if !self.enabled
+            || self.weight <= 0.0
+            || self.joints.len() < 2
+            || (self.solver == IkSolver::TwoBone && self.joints.len() != 3)
+            || self.joints.iter().any(|j| !graph.is_valid_handle(*j))
+        {
+            return;
+        }
+
+        // Joints must be in the pose, otherwise the solution will be lost.
+        for &joint in self.joints.iter() {
+            pose.local_poses.entry(joint).or_insert_with(|| {
+                let transform = graph[joint].local_transform();
+                LocalPose {
+                    node: joint,
+                    position: **transform.position(),
+                    scale: **transform.scale(),
+                    rotation: **transform.rotation(),
+                }
+            });
+        }
+
+        let original_rotations = self
+            .joints
+            .iter()
+            .map(|j| pose.local_poses[j].rotation)
+            .collect::<Vec<_>>();
+
+        let mut positions = self
+            .joints
+            .iter()
+            .map(|j| world_matrix(*j, pose, graph).position())
+            .collect::<Vec<_>>();
+
+        match self.solver {
+            IkSolver::TwoBone => solve_two_bone(&mut positions, self.target, self.pole_target),
+            IkSolver::Fabrik {
+                iterations,
+                tolerance,
+            } => {
+                solve_fabrik(&mut positions, self.target, iterations, tolerance);
+                if let Some(pole_target) = self.pole_target {
+                    apply_pole_target(&mut positions, pole_target);
+                }
+            }
+            IkSolver::Ccd {
+                iterations,
+                tolerance,
+            } => {
+                solve_ccd(&mut positions, self.target, iterations, tolerance);
+                if let Some(pole_target) = self.pole_target {
+                    apply_pole_target(&mut positions, pole_target);
+                }
+            }
+        }
+
+        // Convert new positions of the joints to local rotations, starting from the root of the
+        // chain, because rotation of a joint moves every joint after it.
+        for i in 0..self.joints.len() - 1 {
+            let joint = self.joints[i];
+            let parent_world = world_matrix(graph[joint].parent(), pose, graph);
+            let joint_position = (parent_world * local_matrix(joint, pose, graph)).position();
+            let child_position = world_matrix(self.joints[i + 1], pose, graph).position();
+
+            if let Some(delta) = UnitQuaternion::rotation_between(
+                &(child_position - joint_position),
+                &(positions[i + 1] - joint_position),
+            ) {
+                // Delta rotation is in world space, bring it to the space in which local rotation
+                // of the joint is applied.
+                let frame =
+                    rotation_of(&parent_world) * **graph[joint].local_transform().pre_rotation();
+                let local_pose = pose.local_poses.get_mut(&joint).unwrap();
+                local_pose.rotation = frame.inverse() * delta * frame * local_pose.rotation;
+            }
+        }
+
+        if self.weight < 1.0 {
+            for (joint, original_rotation) in self.joints.iter().zip(original_rotations) {
+                let local_pose = pose.local_poses.get_mut(joint).unwrap();
+                local_pose.rotation = original_rotation.nlerp(&local_pose.rotation, self.weight);
+            }
+        }
+    }
+}
+
+fn local_matrix(node: Handle<Node>, pose: &AnimationPose, graph: &Graph) -> Matrix4<f32> {
+    let transform = graph[node].local_transform();
+    match pose.local_poses.get(&node) {
+        Some(local_pose) => {
+            let mut transform = transform.clone();
+            transform
+                .set_position(local_pose.position)
+                .set_rotation(local_pose.rotation)
+                .set_scale(local_pose.scale);
+            transform.matrix()
+        }
+        None => transform.matrix(),
+    }
+}
+
+/// Calculates world matrix of a node using local poses from the pose (if any) and local transforms
+/// of nodes from the graph.
+fn world_matrix(node: Handle<Node>, pose: &AnimationPose, graph: &Graph) -> Matrix4<f32> {
+    let mut matrix = Matrix4::identity();
+    let mut current = node;
+    while let Some(current_ref) = graph.try_get(current) {
+        matrix = local_matrix(current, pose, graph) * matrix;
+        current = current_ref.parent();
+    }
+    matrix
+}
+
+fn rotation_of(matrix: &Matrix4<f32>) -> UnitQuaternion<f32> {
+    let basis = matrix.basis();
+    let normalize = |v: Vector3<f32>| v.try_normalize(f32::EPSILON).unwrap_or_default();
+    UnitQuaternion::from_rotation_matrix(&Rotation3::from_matrix_unchecked(Matrix3::from_columns(
+        &[
+            normalize(basis.column(0).into()),
+            normalize(basis.column(1).into()),
+            normalize(basis.column(2).into()),
+        ],
+    )))
+}
+
+fn any_perpendicular(v: Vector3<f32>) -> Vector3<f32> {
+    let other = if v.x.abs() < 0.9 {
+        Vector3::x()
+    } else {
+        Vector3::y()
+    };
+    v.cross(&other).normalize()
+}
+
+fn solve_two_bone(
+    positions: &mut [Vector3<f32>],
+    target: Vector3<f32>,
+    pole_target: Option<Vector3<f32>>,
+) {
+    let (a, b, c) = (positions[0], positions[1], positions[2]);
+
+    let upper_length = (b - a).norm();
+    let lower_length = (c - b).norm();
+    if upper_length <= f32::EPSILON || lower_length <= f32::EPSILON {
+        return;
+    }
+
+    let to_target = target - a;
+    let direction = match to_target.try_normalize(f32::EPSILON) {
+        Some(direction) => direction,
+        None => return,
+    };
+
+    // Keep the chain slightly bent (or stretched) to prevent snapping of the middle joint.
+    let distance = to_target.norm().clamp(
+        (upper_length - lower_length).abs() + 0.0001,
+        upper_length + lower_length - 0.0001,
+    );
+
+    // Find a direction of the bend, it is perpendicular to the direction to the target.
+    let project = |v: Vector3<f32>| v - direction.scale(v.dot(&direction));
+    let bend = project(pole_target.map_or(b - a, |p| p - a))
+        .try_normalize(f32::EPSILON)
+        .or_else(|| project(b - a).try_normalize(f32::EPSILON))
+        .unwrap_or_else(|| any_perpendicular(direction));
+
+    let cos_angle = ((upper_length * upper_length + distance * distance
+        - lower_length * lower_length)
+        / (2.0 * upper_length * distance))
+        .clamp(-1.0, 1.0);
+    let sin_angle = (1.0 - cos_angle * cos_angle).sqrt();
+
+    positions[1] =
+        a + direction.scale(upper_length * cos_angle) + bend.scale(upper_length * sin_angle);
+    positions[2] = a + direction.scale(distance);
+}
+
+fn bone_lengths(positions: &[Vector3<f32>]) -> Vec<f32> {
+    positions.windows(2).map(|p| (p[1] - p[0]).norm()).collect()
+}
+
+fn solve_fabrik(
+    positions: &mut [Vector3<f32>],
+    target: Vector3<f32>,
+    iterations: u32,
+    tolerance: f32,
+) {
+    let lengths = bone_lengths(positions);
+    let count = positions.len();
+    let root = positions[0];
+
+    let direction_or_up =
+        |v: Vector3<f32>| v.try_normalize(f32::EPSILON).unwrap_or_else(Vector3::y);
+
+    if (target - root).norm() >= lengths.iter().sum::<f32>() {
+        // Target is unreachable, stretch the chain towards it.
+        let direction = direction_or_up(target - root);
+        for i in 1..count {
+            positions[i] = positions[i - 1] + direction.scale(lengths[i - 1]);
+        }
+        return;
+    }
+
+    for _ in 0..iterations {
+        if (positions[count - 1] - target).norm() <= tolerance {
+            break;
+        }
+
+        // Backward pass - from the effector to the root.
+        positions[count - 1] = target;
+        for i in (0..count - 1).rev() {
+            positions[i] = positions[i + 1]
+                + direction_or_up(positions[i] - positions[i + 1]).scale(lengths[i]);
+        }
+
+        // Forward pass - from the root to the effector.
+        positions[0] = root;
+        for i in 1..count {
+            positions[i] = positions[i - 1]
+                + direction_or_up(positions[i] - positions[i - 1]).scale(lengths[i - 1]);
+        }
+    }
+}
+
+fn solve_ccd(
+    positions: &mut [Vector3<f32>],
+    target: Vector3<f32>,
+    iterations: u32,
+    tolerance: f32,
+) {
+    let count = positions.len();
+
+    for _ in 0..iterations {
+        if (positions[count - 1] - target).norm() <= tolerance {
+            break;
+        }
+
+        for i in (0..count - 1).rev() {
+            let joint = positions[i];
+            if let Some(rotation) =
+                UnitQuaternion::rotation_between(&(positions[count - 1] - joint), &(target - joint))
+            {
+                for position in positions[(i + 1)..].iter_mut() {
+                    *position = joint + rotation * (*position - joint);
+                }
+            }
+        }
+    }
+}
+
+/// Rotates every intermediate joint around the line between its neighbours, so the joint will be
+/// as close to the pole target as possible. Lengths of bones are preserved.
+fn apply_pole_target(positions: &mut [Vector3<f32>], pole_target: Vector3<f32>) {
+    for i in 1..positions.len() - 1 {
+        let (prev, next) = (positions[i - 1], positions[i + 1]);
+        let axis = match (next - prev).try_normalize(f32::EPSILON) {
+            Some(axis) => axis,
+            None => continue,
+        };
+
+        let project = |v: Vector3<f32>| v - axis.scale(v.dot(&axis));
+        let joint_offset = project(positions[i] - prev);
+        let pole_offset = project(pole_target - prev);
+
+        if let Some(rotation) = UnitQuaternion::rotation_between(&joint_offset, &pole_offset) {
+            positions[i] = prev + rotation * (positions[i] - prev);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::{
+            ik::{
+                apply_pole_target, bone_lengths, solve_ccd, solve_fabrik, solve_two_bone, IkChain,
+            },
+            AnimationPose,
+        },
+        core::algebra::Vector3,
+        scene::{
+            base::BaseBuilder, graph::Graph, pivot::PivotBuilder, transform::TransformBuilder,
+        },
+    };
+
+    fn make_chain(count: usize) -> Vec<Vector3<f32>> {
+        (0..count)
+            .map(|i| Vector3::new(0.0, i as f32, 0.0))
+            .collect()
+    }
+
+    fn assert_lengths_preserved(before: &[Vector3<f32>], after: &[Vector3<f32>]) {
+        for (a, b) in bone_lengths(before).iter().zip(bone_lengths(after)) {
+            assert!((a - b).abs() < 0.001);
+        }
+    }
+
+    #[test]
+    fn test_two_bone() {
+        let initial = make_chain(3);
+        let target = Vector3::new(1.0, 1.0, 0.0);
+        let pole_target = Vector3::new(0.0, 0.0, 5.0);
+
+        let mut positions = initial.clone();
+        solve_two_bone(&mut positions, target, Some(pole_target));
+
+        assert!((positions[2] - target).norm() < 0.001);
+        assert_lengths_preserved(&initial, &positions);
+        // Middle joint must bend towards the pole target.
+        assert!(positions[1].z > 0.0);
+    }
+
+    #[test]
+    fn test_iterative_solvers() {
+        let initial = make_chain(5);
+        let target = Vector3::new(1.5, 2.0, 0.5);
+
+        let mut positions = initial.clone();
+        solve_fabrik(&mut positions, target, 20, 0.0001);
+        assert!((positions[4] - target).norm() < 0.01);
+        assert!((positions[0] - initial[0]).norm() < 0.001);
+        assert_lengths_preserved(&initial, &positions);
+
+        let mut positions = initial.clone();
+        solve_ccd(&mut positions, target, 20, 0.0001);
+        assert!((positions[4] - target).norm() < 0.01);
+        assert_lengths_preserved(&initial, &positions);
+
+        apply_pole_target(&mut positions, Vector3::new(0.0, 0.0, -5.0));
+        assert_lengths_preserved(&initial, &positions);
+    }
+
+    #[test]
+    fn test_unreachable_target() {
+        let initial = make_chain(4);
+        let mut positions = initial.clone();
+        solve_fabrik(&mut positions, Vector3::new(10.0, 0.0, 0.0), 10, 0.001);
+        assert!((positions[3] - Vector3::new(3.0, 0.0, 0.0)).norm() < 0.001);
+    }
+
+    #[test]
+    fn test_chain_applied_to_pose() {
+        let mut graph = Graph::new();
+
+        let make_joint = |graph: &mut Graph, y: f32| {
+            PivotBuilder::new(
+                BaseBuilder::new().with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, y, 0.0))
+                        .build(),
+                ),
+            )
+            .build(graph)
+        };
+
+        let end = make_joint(&mut graph, 1.0);
+        let middle = make_joint(&mut graph, 1.0);
+        let upper = make_joint(&mut graph, 0.0);
+        graph.link_nodes(end, middle);
+        graph.link_nodes(middle, upper);
+
+        let target = Vector3::new(1.0, 1.0, 0.0);
+        let mut chain = IkChain::two_bone(upper, middle, end);
+        chain.set_target(target);
+        chain.set_pole_target(Some(Vector3::new(0.0, 0.0, 5.0)));
+
+        let mut pose = AnimationPose::default();
+        chain.apply(&mut pose, &graph);
+        pose.apply(&mut graph);
+        graph.update_hierarchical_data();
+
+        assert!((graph[end].global_position() - target).norm() < 0.001);
+    }
+}
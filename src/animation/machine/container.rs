@@ -47,7 +47,7 @@ impl AnimationMachineContainer {
         let machine = self.remove(handle);
 
         let mut removed_animations = Vec::new();
-        for node in machine.nodes.iter() {
+        for node in machine.layers().iter().flat_map(|layer| layer.nodes()) {
             if let PoseNode::PlayAnimation(play_animation) = node {
                 if animations.try_get(play_animation.animation).is_some() {
                     if let Some(animation) = animations.remove(play_animation.animation) {
@@ -0,0 +1,1157 @@
+//! Layer is a separate state graph of a machine, that produces its own animation pose. Poses of
+//! all layers are combined together (from the first to the last) to get the final pose of the
+//! machine. See [`MachineLayer`] docs for more info.
+
+use crate::{
+    animation::{
+        machine::{
+            event::LimitedEventQueue,
+            find_node_by_definition, find_state_by_definition, instantiate_node,
+            node::{BasePoseNode, PoseNodeDefinition},
+            state::StateDefinition,
+            transition::TransitionDefinition,
+            AnimationsPack, BlendAnimations, BlendAnimationsByIndex, BlendPose, BlendSpacePoint,
+            Event, IndexedBlendInput, MachineInstantiationError, Parameter, ParameterContainer,
+            PlayAnimation, PoseNode, PoseWeight, State, Transition,
+        },
+        Animation, AnimationContainer, AnimationPose,
+    },
+    core::{
+        inspect::{Inspect, PropertyInfo},
+        pool::{Handle, Pool},
+        reflect::Reflect,
+        visitor::prelude::*,
+    },
+    scene::{graph::Graph, node::Node},
+    utils::log::{Log, MessageKind},
+};
+use fxhash::FxHashMap;
+use fyrox_resource::ResourceState;
+use std::cmp::Ordering;
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Defines how the pose of a layer is combined with the poses of the previous layers.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Visit,
+    Inspect,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum LayerBlendMode {
+    /// Pose of the layer replaces the pose of the previous layers, the weight of the layer
+    /// defines how much of the layer pose will be used (0 - none, 1 - entire pose).
+    Override,
+
+    /// Difference between the pose of the layer and its reference pose is added on top of the
+    /// pose of the previous layers: positions are added, rotations and scales are multiplied.
+    /// The reference pose is built from the first frames of the animations of the layer, it
+    /// means that the first frame of an additive animation must be the pose that means "no
+    /// changes". The weight of the layer scales the difference.
+    Additive,
+}
+
+impl Default for LayerBlendMode {
+    fn default() -> Self {
+        Self::Override
+    }
+}
+
+/// A set of scene nodes that will be animated by a layer. Empty mask means that the layer
+/// animates every node.
+#[derive(Default, Debug, Visit, Clone, PartialEq)]
+pub struct LayerMask {
+    nodes: Vec<Handle<Node>>,
+}
+
+impl LayerMask {
+    /// Creates a mask from the given set of nodes.
+    pub fn new(nodes: Vec<Handle<Node>>) -> Self {
+        Self { nodes }
+    }
+
+    /// Creates a mask that contains given nodes and all their descendants. It is the easiest
+    /// way of creating masks for body parts, for example upper body mask could be created from
+    /// spine bone.
+    pub fn from_hierarchy(graph: &Graph, roots: &[Handle<Node>]) -> Self {
+        let mut nodes = Vec::new();
+        for root in roots {
+            for node in graph.traverse_handle_iter(*root) {
+                if !nodes.contains(&node) {
+                    nodes.push(node);
+                }
+            }
+        }
+        Self { nodes }
+    }
+
+    /// Returns a set of nodes in the mask.
+    pub fn nodes(&self) -> &[Handle<Node>] {
+        &self.nodes
+    }
+
+    /// Returns `true` if the mask is empty, `false` - otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns `true` if a layer with this mask should animate the given node.
+    pub fn should_animate(&self, node: Handle<Node>) -> bool {
+        self.nodes.is_empty() || self.nodes.contains(&node)
+    }
+}
+
+/// Definition of a layer mask. Unlike [`LayerMask`] it uses names of bones, which makes it
+/// independent of a particular model instance.
+#[derive(Default, Debug, Visit, Clone, Inspect, Reflect)]
+pub struct LayerMaskDefinition {
+    #[inspect(
+        description = "Names of bones that will be animated by the layer. Every \
+    descendant of a bone will be animated too. Empty list means that the layer animates \
+    every bone."
+    )]
+    pub bones: Vec<String>,
+}
+
+impl LayerMaskDefinition {
+    /// Creates an instance of the mask for a model hierarchy starting from the given root.
+    pub fn instantiate(&self, root: Handle<Node>, graph: &Graph) -> LayerMask {
+        let roots = self
+            .bones
+            .iter()
+            .filter_map(|name| {
+                let bone = graph.find_by_name(root, name);
+                if bone.is_none() {
+                    Log::warn(format!(
+                        "Unable to find bone {} for layer mask, it will be ignored!",
+                        name
+                    ));
+                    None
+                } else {
+                    Some(bone)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        LayerMask::from_hierarchy(graph, &roots)
+    }
+}
+
+/// Layer is a separate state graph (with its own states, transitions and pose nodes) that
+/// produces an animation pose. Each layer has a weight, a mask and a blend mode, that define how
+/// the pose of the layer will be combined with the poses of the previous layers. Typical example
+/// of layers is a character that can shoot while running: the first layer handles locomotion for
+/// the entire body and the second layer handles shooting for the upper body only (using a mask
+/// with spine bone).
+///
+/// Keep in mind that in override mode nodes that are not animated by previous layers take the
+/// pose of a layer as is, ignoring the weight. Additive layers, on the other hand, affect only
+/// the nodes that are animated by previous layers. It also means that the first layer should
+/// always be in override mode.
+#[derive(Debug, Visit, Clone)]
+pub struct MachineLayer {
+    name: String,
+    weight: PoseWeight,
+    blend_mode: LayerBlendMode,
+    mask: LayerMask,
+    nodes: Pool<PoseNode>,
+    transitions: Pool<Transition>,
+    states: Pool<State>,
+    active_state: Handle<State>,
+    entry_state: Handle<State>,
+    active_transition: Handle<Transition>,
+
+    #[visit(skip)]
+    final_pose: AnimationPose,
+    #[visit(skip)]
+    reference_pose: AnimationPose,
+    // Animations that were used to build the reference pose.
+    #[visit(skip)]
+    reference_animations: Vec<Handle<Animation>>,
+    #[visit(skip)]
+    events: LimitedEventQueue,
+}
+
+impl Default for MachineLayer {
+    fn default() -> Self {
+        Self::new("Base Layer")
+    }
+}
+
+/// Definition of a machine layer. See [`MachineLayer`] docs for more info.
+#[derive(Debug, Visit, Clone, Inspect, Reflect)]
+pub struct MachineLayerDefinition {
+    #[inspect(description = "The name of the layer.")]
+    pub name: String,
+    #[inspect(
+        description = "Weight of the layer, it defines how much of the layer pose \
+    will be used."
+    )]
+    pub weight: PoseWeight,
+    #[inspect(
+        description = "Defines how the pose of the layer is combined with the poses \
+    of the previous layers."
+    )]
+    pub blend_mode: LayerBlendMode,
+    #[inspect(description = "A set of bones that will be animated by the layer.")]
+    pub mask: LayerMaskDefinition,
+    #[inspect(skip)]
+    #[reflect(hidden)]
+    pub nodes: Pool<PoseNodeDefinition>,
+    #[inspect(skip)]
+    #[reflect(hidden)]
+    pub transitions: Pool<TransitionDefinition>,
+    #[inspect(skip)]
+    #[reflect(hidden)]
+    pub states: Pool<StateDefinition>,
+    #[inspect(skip)]
+    #[reflect(hidden)]
+    pub entry_state: Handle<StateDefinition>,
+}
+
+impl Default for MachineLayerDefinition {
+    fn default() -> Self {
+        Self {
+            name: "Base Layer".to_string(),
+            weight: PoseWeight::Constant(1.0),
+            blend_mode: Default::default(),
+            mask: Default::default(),
+            nodes: Default::default(),
+            transitions: Default::default(),
+            states: Default::default(),
+            entry_state: Default::default(),
+        }
+    }
+}
+
+impl MachineLayerDefinition {
+    pub(super) fn instantiate(
+        &self,
+        root: Handle<Node>,
+        graph: &mut Graph,
+        animations: &mut AnimationContainer,
+        animations_pack: &AnimationsPack,
+    ) -> Result<MachineLayer, MachineInstantiationError> {
+        let mut layer = MachineLayer::new(&self.name);
+        layer.weight = self.weight.clone();
+        layer.blend_mode = self.blend_mode;
+        layer.mask = self.mask.instantiate(root, graph);
+
+        // Instantiate nodes.
+        let mut node_map = FxHashMap::default();
+        for (definition_handle, node_definition) in self.nodes.pair_iter() {
+            let instance_handle = layer.add_node(instantiate_node(
+                node_definition,
+                definition_handle,
+                animations_pack,
+                root,
+                graph,
+                animations,
+            )?);
+
+            node_map.insert(definition_handle, instance_handle);
+        }
+
+        // Link nodes.
+        for (definition_handle, instance_handle) in node_map.iter() {
+            let definition = &self.nodes[*definition_handle];
+            let instance = layer.node_mut(*instance_handle);
+
+            match instance {
+                PoseNode::PlayAnimation(_) => {
+                    // Do nothing, has no links to other nodes.
+                }
+                PoseNode::BlendAnimations(blend_animations) => {
+                    if let PoseNodeDefinition::BlendAnimations(blend_animations_definition) =
+                        definition
+                    {
+                        for (blend_pose, blend_pose_definition) in blend_animations
+                            .pose_sources
+                            .iter_mut()
+                            .zip(blend_animations_definition.pose_sources.iter())
+                        {
+                            blend_pose.pose_source = node_map
+                                .get(&blend_pose_definition.pose_source)
+                                .cloned()
+                                .unwrap_or_default();
+                        }
+                    } else {
+                        unreachable!()
+                    }
+                }
+                PoseNode::BlendAnimationsByIndex(blend_animations) => {
+                    if let PoseNodeDefinition::BlendAnimationsByIndex(blend_animations_definition) =
+                        definition
+                    {
+                        for (input, input_definition) in blend_animations
+                            .inputs
+                            .iter_mut()
+                            .zip(blend_animations_definition.inputs.iter())
+                        {
+                            input.pose_source = node_map
+                                .get(&input_definition.pose_source)
+                                .cloned()
+                                .unwrap_or_default();
+                        }
+                    } else {
+                        unreachable!()
+                    }
+                }
+                PoseNode::BlendSpace(blend_space) => {
+                    if let PoseNodeDefinition::BlendSpace(blend_space_definition) = definition {
+                        for (point, point_definition) in blend_space
+                            .points
+                            .iter_mut()
+                            .zip(blend_space_definition.points.iter())
+                        {
+                            point.pose_source = node_map
+                                .get(&point_definition.pose_source)
+                                .cloned()
+                                .unwrap_or_default();
+                        }
+                    } else {
+                        unreachable!()
+                    }
+                }
+            }
+        }
+
+        // Instantiate states.
+        let mut state_map = FxHashMap::default();
+        for (definition_handle, state_definition) in self.states.pair_iter() {
+            let mut state = State::new(
+                state_definition.name.as_ref(),
+                node_map
+                    .get(&state_definition.root)
+                    .cloned()
+                    .unwrap_or_default(),
+            );
+
+            state.definition = definition_handle;
+
+            let instance_handle = layer.add_state(state);
+
+            state_map.insert(definition_handle, instance_handle);
+        }
+
+        // Instantiate transitions.
+        for (transition_definition_handle, transition_definition) in self.transitions.pair_iter() {
+            layer.add_transition(Transition {
+                definition: transition_definition_handle,
+                name: transition_definition.name.clone(),
+                transition_time: transition_definition.transition_time,
+                elapsed_time: 0.0,
+                source: state_map
+                    .get(&transition_definition.source)
+                    .cloned()
+                    .expect("There must be a respective source state!"),
+                dest: state_map
+                    .get(&transition_definition.dest)
+                    .cloned()
+                    .expect("There must be a respective dest state!"),
+                rule: transition_definition.rule.clone(),
+                invert_rule: transition_definition.invert_rule,
+                blend_factor: 0.0,
+            });
+        }
+
+        layer.set_entry_state(
+            state_map
+                .get(&self.entry_state)
+                .cloned()
+                .unwrap_or_default(),
+        );
+
+        Ok(layer)
+    }
+}
+
+impl MachineLayer {
+    /// Creates new empty layer with the given name.
+    #[inline]
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            weight: PoseWeight::Constant(1.0),
+            blend_mode: Default::default(),
+            mask: Default::default(),
+            nodes: Default::default(),
+            transitions: Default::default(),
+            states: Default::default(),
+            active_state: Default::default(),
+            entry_state: Default::default(),
+            active_transition: Default::default(),
+            final_pose: Default::default(),
+            reference_pose: Default::default(),
+            reference_animations: Default::default(),
+            events: LimitedEventQueue::new(2048),
+        }
+    }
+
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[inline]
+    pub fn set_name(&mut self, name: &str) {
+        self.name = name.to_owned();
+    }
+
+    #[inline]
+    pub fn weight(&self) -> &PoseWeight {
+        &self.weight
+    }
+
+    /// Sets new weight of the layer. Use [`PoseWeight::Parameter`] to control the weight in
+    /// runtime using machine parameters.
+    #[inline]
+    pub fn set_weight(&mut self, weight: PoseWeight) {
+        self.weight = weight;
+    }
+
+    #[inline]
+    pub fn blend_mode(&self) -> LayerBlendMode {
+        self.blend_mode
+    }
+
+    #[inline]
+    pub fn set_blend_mode(&mut self, blend_mode: LayerBlendMode) {
+        self.blend_mode = blend_mode;
+    }
+
+    #[inline]
+    pub fn mask(&self) -> &LayerMask {
+        &self.mask
+    }
+
+    #[inline]
+    pub fn set_mask(&mut self, mask: LayerMask) {
+        self.mask = mask;
+    }
+
+    #[inline]
+    pub fn add_node(&mut self, node: PoseNode) -> Handle<PoseNode> {
+        self.nodes.spawn(node)
+    }
+
+    #[inline]
+    pub fn set_entry_state(&mut self, entry_state: Handle<State>) {
+        self.active_state = entry_state;
+        self.entry_state = entry_state;
+    }
+
+    #[inline]
+    pub fn entry_state(&self) -> Handle<State> {
+        self.entry_state
+    }
+
+    #[inline]
+    pub fn add_state(&mut self, state: State) -> Handle<State> {
+        let state = self.states.spawn(state);
+        if self.active_state.is_none() {
+            self.active_state = state;
+        }
+        state
+    }
+
+    #[inline]
+    pub fn add_transition(&mut self, transition: Transition) -> Handle<Transition> {
+        self.transitions.spawn(transition)
+    }
+
+    #[inline]
+    pub fn get_state(&self, state: Handle<State>) -> &State {
+        &self.states[state]
+    }
+
+    #[inline]
+    pub fn get_transition(&self, transition: Handle<Transition>) -> &Transition {
+        &self.transitions[transition]
+    }
+
+    #[inline]
+    pub fn pop_event(&mut self) -> Option<Event> {
+        self.events.pop()
+    }
+
+    #[inline]
+    pub fn reset(&mut self) {
+        for transition in self.transitions.iter_mut() {
+            transition.reset();
+        }
+
+        self.active_state = self.entry_state;
+    }
+
+    #[inline]
+    pub fn nodes(&self) -> impl Iterator<Item = &PoseNode> {
+        self.nodes.iter()
+    }
+
+    #[inline]
+    pub fn node_mut(&mut self, handle: Handle<PoseNode>) -> &mut PoseNode {
+        &mut self.nodes[handle]
+    }
+
+    #[inline]
+    pub fn active_state(&self) -> Handle<State> {
+        self.active_state
+    }
+
+    #[inline]
+    pub fn active_transition(&self) -> Handle<Transition> {
+        self.active_transition
+    }
+
+    #[inline]
+    pub fn transitions(&self) -> &Pool<Transition> {
+        &self.transitions
+    }
+
+    #[inline]
+    pub fn states(&self) -> &Pool<State> {
+        &self.states
+    }
+
+    /// Returns the pose of the layer, calculated on the last update of the machine.
+    #[inline]
+    pub fn pose(&self) -> &AnimationPose {
+        &self.final_pose
+    }
+
+    /// Returns the reference pose of the layer, it is used only in additive blend mode. See
+    /// [`LayerBlendMode::Additive`] docs for more info.
+    #[inline]
+    pub fn reference_pose(&self) -> &AnimationPose {
+        &self.reference_pose
+    }
+
+    /// Rebuilds the reference pose if the set of animations of the layer has changed. For every
+    /// node the first frame of the first animation that animates the node is used.
+    fn update_reference_pose(&mut self, animations: &AnimationContainer) {
+        let layer_animations = self
+            .nodes
+            .iter()
+            .filter_map(|node| match node {
+                PoseNode::PlayAnimation(play_animation) => Some(play_animation.animation),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        if layer_animations == self.reference_animations {
+            return;
+        }
+
+        self.reference_pose.reset();
+        let mut first_frame = AnimationPose::default();
+        for animation in layer_animations.iter() {
+            if let Some(animation) = animations.try_get(*animation) {
+                animation.sample_first_frame(&mut first_frame);
+                self.reference_pose.extend_missing(&first_frame);
+            }
+        }
+
+        self.reference_animations = layer_animations;
+    }
+
+    /// Visits the state graph of the layer using the field names of a machine that was saved
+    /// before layers were added.
+    pub(super) fn visit_legacy(&mut self, visitor: &mut Visitor) -> VisitResult {
+        self.nodes.visit("Nodes", visitor)?;
+        self.transitions.visit("Transitions", visitor)?;
+        self.states.visit("States", visitor)?;
+        self.active_state.visit("ActiveState", visitor)?;
+        self.entry_state.visit("EntryState", visitor)?;
+        self.active_transition.visit("ActiveTransition", visitor)
+    }
+
+    pub(super) fn resolve(
+        &mut self,
+        definition: &MachineLayerDefinition,
+        root: Handle<Node>,
+        animations_pack: &AnimationsPack,
+        graph: &mut Graph,
+        animations: &mut AnimationContainer,
+    ) {
+        // Step 1. Restore integrity - add missing entities, remove nonexistent from instance.
+        match definition
+            .nodes
+            .alive_count()
+            .cmp(&self.nodes.alive_count())
+        {
+            Ordering::Less => {
+                // Some nodes were deleted in definition, remove respective instances.
+                let mut nodes_to_remove = Vec::new();
+                for (handle, node) in self.nodes.pair_iter() {
+                    if !definition.nodes.is_valid_handle(node.definition) {
+                        nodes_to_remove.push(handle);
+                    }
+                }
+
+                for node_to_remove in nodes_to_remove {
+                    self.nodes.free(node_to_remove);
+                }
+            }
+            Ordering::Equal => {
+                // Do nothing
+            }
+            Ordering::Greater => {
+                // Some nodes were added in definition, create respective instances.
+                for (node_definition_handle, node_definition) in definition.nodes.pair_iter() {
+                    if self
+                        .nodes
+                        .iter()
+                        .all(|n| n.definition != node_definition_handle)
+                    {
+                        let pose_node = instantiate_node(
+                            node_definition,
+                            node_definition_handle,
+                            animations_pack,
+                            root,
+                            graph,
+                            animations,
+                        )
+                        .unwrap();
+
+                        let _ = self.nodes.spawn(pose_node);
+                    }
+                }
+            }
+        }
+        let definition_to_node_map = self
+            .nodes
+            .pair_iter()
+            .map(|(h, n)| (n.definition, h))
+            .collect::<FxHashMap<_, _>>();
+        let fetch_node_by_definition = |definition: Handle<PoseNodeDefinition>| {
+            definition_to_node_map
+                .get(&definition)
+                .cloned()
+                .unwrap_or_default()
+        };
+
+        match definition
+            .states
+            .alive_count()
+            .cmp(&self.states.alive_count())
+        {
+            Ordering::Less => {
+                // Some states were deleted in definition, remove respective instances.
+                let mut states_to_remove = Vec::new();
+                for (handle, state) in self.states.pair_iter() {
+                    if !definition.states.is_valid_handle(state.definition) {
+                        states_to_remove.push(handle);
+                    }
+                }
+
+                for node_to_remove in states_to_remove {
+                    self.states.free(node_to_remove);
+                }
+            }
+            Ordering::Equal => {
+                // Do nothing.
+            }
+            Ordering::Greater => {
+                // Some states were added in definition, create respective instances.
+                for (state_definition_handle, state_definition) in definition.states.pair_iter() {
+                    if self
+                        .states
+                        .iter()
+                        .all(|s| s.definition != state_definition_handle)
+                    {
+                        let root = find_node_by_definition(&self.nodes, state_definition.root);
+
+                        let mut state = State::new(state_definition.name.as_ref(), root);
+
+                        state.definition = state_definition_handle;
+
+                        let _ = self.states.spawn(state);
+                    }
+                }
+            }
+        }
+
+        match definition
+            .transitions
+            .alive_count()
+            .cmp(&self.transitions.alive_count())
+        {
+            Ordering::Less => {
+                // Some transitions were deleted in definition, remove respective instances.
+                let mut transitions_to_remove = Vec::new();
+                for (handle, transition) in self.transitions.pair_iter() {
+                    if !definition
+                        .transitions
+                        .is_valid_handle(transition.definition)
+                    {
+                        transitions_to_remove.push(handle);
+                    }
+                }
+
+                for node_to_remove in transitions_to_remove {
+                    self.transitions.free(node_to_remove);
+                }
+            }
+            Ordering::Equal => {
+                // Do nothing.
+            }
+            Ordering::Greater => {
+                // Some transitions were added in definition, create respective instances.
+                for (transition_definition_handle, transition_definition) in
+                    definition.transitions.pair_iter()
+                {
+                    if self
+                        .transitions
+                        .iter()
+                        .all(|t| t.definition != transition_definition_handle)
+                    {
+                        let mut transition = Transition::new(
+                            transition_definition.name.as_ref(),
+                            find_state_by_definition(&self.states, transition_definition.source),
+                            find_state_by_definition(&self.states, transition_definition.dest),
+                            transition_definition.transition_time,
+                            transition_definition.rule.as_str(),
+                        );
+
+                        transition.definition = transition_definition_handle;
+
+                        let _ = self.transitions.spawn(transition);
+                    }
+                }
+            }
+        }
+
+        // Step 2. Sync data of instance entities with respective definitions.
+        for node in self.nodes.iter_mut() {
+            let node_definition = &definition.nodes[node.definition];
+
+            match node {
+                PoseNode::PlayAnimation(play_animation) => {
+                    if let PoseNodeDefinition::PlayAnimation(play_animation_definition) =
+                        node_definition
+                    {
+                        let definition_animation = animations_pack
+                            .animations()
+                            .get(&play_animation_definition.animation);
+
+                        if animations.try_get(play_animation.animation).map_or(
+                            true,
+                            |current_animation| {
+                                definition_animation != current_animation.resource.as_ref()
+                            },
+                        ) {
+                            animations.remove(play_animation.animation);
+
+                            let new_animation = if let Some(definition_animation) =
+                                definition_animation
+                            {
+                                if matches!(*definition_animation.state(), ResourceState::Ok(_)) {
+                                    definition_animation
+                                        .retarget_animations_internal(root, graph, animations)
+                                        .first()
+                                        .cloned()
+                                        .unwrap_or_default()
+                                } else {
+                                    Handle::NONE
+                                }
+                            } else {
+                                Handle::NONE
+                            };
+
+                            *play_animation = PlayAnimation {
+                                base: BasePoseNode {
+                                    definition: play_animation.definition,
+                                },
+                                animation: new_animation,
+                                output_pose: Default::default(),
+                            };
+                        }
+
+                        // Apply definition properties to instance.
+                        if let Some(animation) = animations.try_get_mut(play_animation.animation) {
+                            animation
+                                .set_speed(play_animation_definition.speed)
+                                .set_time_slice(
+                                    play_animation_definition.time_slice.clone().map(|s| s.0),
+                                );
+                            animation.set_root_motion_settings(
+                                play_animation_definition
+                                    .root_motion
+                                    .as_ref()
+                                    .map(|s| s.instantiate(root, graph)),
+                            );
+                        }
+                    } else {
+                        unreachable!()
+                    }
+                }
+                PoseNode::BlendAnimations(blend_animations) => {
+                    if let PoseNodeDefinition::BlendAnimations(blend_animations_definition) =
+                        node_definition
+                    {
+                        *blend_animations = BlendAnimations {
+                            base: BasePoseNode {
+                                definition: blend_animations.definition,
+                            },
+                            pose_sources: blend_animations_definition
+                                .pose_sources
+                                .iter()
+                                .map(|s| BlendPose {
+                                    weight: s.weight.clone(),
+                                    pose_source: fetch_node_by_definition(s.pose_source),
+                                })
+                                .collect(),
+                            output_pose: std::mem::take(&mut blend_animations.output_pose),
+                        }
+                    }
+                }
+                PoseNode::BlendAnimationsByIndex(blend_animations) => {
+                    if let PoseNodeDefinition::BlendAnimationsByIndex(blend_animations_definition) =
+                        node_definition
+                    {
+                        *blend_animations = BlendAnimationsByIndex {
+                            base: BasePoseNode {
+                                definition: blend_animations.definition,
+                            },
+                            index_parameter: blend_animations_definition.index_parameter.clone(),
+                            inputs: blend_animations_definition
+                                .inputs
+                                .iter()
+                                .map(|i| IndexedBlendInput {
+                                    blend_time: i.blend_time,
+                                    pose_source: fetch_node_by_definition(i.pose_source),
+                                })
+                                .collect(),
+                            prev_index: blend_animations.prev_index.clone(),
+                            blend_time: blend_animations.blend_time.clone(),
+                            output_pose: std::mem::take(&mut blend_animations.output_pose),
+                        }
+                    }
+                }
+                PoseNode::BlendSpace(blend_space) => {
+                    if let PoseNodeDefinition::BlendSpace(blend_space_definition) = node_definition
+                    {
+                        blend_space.set_sampling_parameter(
+                            blend_space_definition.sampling_parameter.clone(),
+                        );
+                        blend_space.set_points(
+                            blend_space_definition
+                                .points
+                                .iter()
+                                .map(|p| BlendSpacePoint {
+                                    position: p.position,
+                                    pose_source: fetch_node_by_definition(p.pose_source),
+                                })
+                                .collect(),
+                        );
+                    }
+                }
+            }
+        }
+
+        for state in self.states.iter_mut() {
+            let state_definition = &definition.states[state.definition];
+
+            // Reassign the entire state to trigger compiler error if there's a new field.
+            *state = State {
+                definition: state.definition,
+                name: state_definition.name.clone(),
+                root: find_node_by_definition(&self.nodes, state_definition.root),
+            };
+        }
+
+        for transition in self.transitions.iter_mut() {
+            let transition_definition = &definition.transitions[transition.definition];
+
+            *transition = Transition {
+                definition: transition.definition,
+                name: transition_definition.name.clone(),
+                transition_time: transition_definition.transition_time,
+                elapsed_time: transition.elapsed_time,
+                source: find_state_by_definition(&self.states, transition_definition.source),
+                dest: find_state_by_definition(&self.states, transition_definition.dest),
+                rule: transition_definition.rule.clone(),
+                invert_rule: transition_definition.invert_rule,
+                blend_factor: transition.blend_factor,
+            };
+        }
+
+        // Step 3. Sync layer properties.
+        self.name = definition.name.clone();
+        self.weight = definition.weight.clone();
+        self.blend_mode = definition.blend_mode;
+        self.mask = definition.mask.instantiate(root, graph);
+        // Animations could be changed without changing their handles, force reference pose update.
+        self.reference_animations.clear();
+
+        let entry_state = find_state_by_definition(&self.states, definition.entry_state);
+        if self.entry_state != entry_state {
+            self.set_entry_state(entry_state);
+        }
+    }
+
+    pub(super) fn evaluate_pose(
+        &mut self,
+        parameters: &ParameterContainer,
+        animations: &AnimationContainer,
+        dt: f32,
+        debug: bool,
+    ) {
+        self.final_pose.reset();
+
+        if self.blend_mode == LayerBlendMode::Additive {
+            self.update_reference_pose(animations);
+        }
+
+        if self.active_state.is_some() || self.active_transition.is_some() {
+            // Gather actual poses for each state.
+            for state in self.states.iter_mut() {
+                state.update(&self.nodes, parameters, animations, dt);
+            }
+
+            if self.active_transition.is_none() {
+                // Find transition.
+                for (handle, transition) in self.transitions.pair_iter_mut() {
+                    if transition.dest() == self.active_state
+                        || transition.source() != self.active_state
+                    {
+                        continue;
+                    }
+                    if let Some(Parameter::Rule(mut active)) =
+                        parameters.get(transition.rule()).cloned()
+                    {
+                        if transition.invert_rule {
+                            active = !active;
+                        }
+
+                        if active {
+                            self.events.push(Event::StateLeave(self.active_state));
+                            if debug {
+                                Log::writeln(
+                                    MessageKind::Information,
+                                    format!(
+                                        "Leaving state: {}",
+                                        self.states[self.active_state].name()
+                                    ),
+                                );
+                            }
+
+                            self.events.push(Event::StateEnter(transition.source()));
+                            if debug {
+                                Log::writeln(
+                                    MessageKind::Information,
+                                    format!(
+                                        "Entering state: {}",
+                                        self.states[transition.source()].name()
+                                    ),
+                                );
+                            }
+
+                            self.active_state = Handle::NONE;
+
+                            self.active_transition = handle;
+                            self.events
+                                .push(Event::ActiveTransitionChanged(self.active_transition));
+
+                            break;
+                        }
+                    }
+                }
+            }
+
+            // Double check for active transition because we can have empty machine.
+            if self.active_transition.is_some() {
+                let transition = &mut self.transitions[self.active_transition];
+
+                // Blend between source and dest states.
+                if let Some(source_pose) = self.states[transition.source()].pose(&self.nodes) {
+                    self.final_pose
+                        .blend_with(&source_pose, 1.0 - transition.blend_factor());
+                }
+                if let Some(dest_pose) = self.states[transition.dest()].pose(&self.nodes) {
+                    self.final_pose
+                        .blend_with(&dest_pose, transition.blend_factor());
+                }
+
+                transition.update(dt);
+
+                if transition.is_done() {
+                    transition.reset();
+
+                    self.active_transition = Handle::NONE;
+                    self.events
+                        .push(Event::ActiveTransitionChanged(self.active_transition));
+
+                    self.active_state = transition.dest();
+                    self.events
+                        .push(Event::ActiveStateChanged(self.active_state));
+
+                    if debug {
+                        Log::writeln(
+                            MessageKind::Information,
+                            format!(
+                                "Active state changed: {}",
+                                self.states[self.active_state].name()
+                            ),
+                        );
+                    }
+                }
+            } else {
+                // We must have active state all the time when we do not have any active transition.
+                // Just get pose from active state.
+                if let Some(active_state_pose) = self.states[self.active_state].pose(&self.nodes) {
+                    active_state_pose.clone_into(&mut self.final_pose);
+                }
+            }
+        }
+    }
+
+    /// Combines the pose of the layer with the given pose (which is usually the result of the
+    /// previous layers) using the weight, the mask and the blend mode of the layer.
+    pub(super) fn blend_into(&self, parameters: &ParameterContainer, dest: &mut AnimationPose) {
+        let weight = self.weight.value(parameters);
+        let mask = &self.mask;
+        match self.blend_mode {
+            LayerBlendMode::Override => {
+                dest.override_with(&self.final_pose, weight, |node| mask.should_animate(node))
+            }
+            LayerBlendMode::Additive => {
+                dest.add_additive(&self.final_pose, &self.reference_pose, weight, |node| {
+                    mask.should_animate(node)
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::{
+            machine::{
+                layer::{LayerBlendMode, LayerMask, MachineLayer},
+                Parameter, ParameterContainer, PlayAnimation, PoseNode, PoseWeight,
+            },
+            Animation, AnimationContainer, AnimationPose, KeyFrame, LocalPose, Track,
+        },
+        core::{
+            algebra::{UnitQuaternion, Vector3},
+            pool::Handle,
+        },
+        scene::node::Node,
+    };
+
+    fn make_pose(poses: &[(Handle<Node>, LocalPose)]) -> AnimationPose {
+        let mut pose = AnimationPose::default();
+        for (node, local_pose) in poses {
+            pose.add_local_pose(LocalPose {
+                node: *node,
+                ..local_pose.clone()
+            });
+        }
+        pose
+    }
+
+    #[test]
+    fn test_override_layer_with_mask() {
+        let a = Handle::new(1, 1);
+        let b = Handle::new(2, 1);
+
+        let base = LocalPose {
+            position: Vector3::new(1.0, 0.0, 0.0),
+            ..Default::default()
+        };
+        let mut dest = make_pose(&[(a, base.clone()), (b, base)]);
+
+        let layer_pose = LocalPose {
+            position: Vector3::new(3.0, 0.0, 0.0),
+            ..Default::default()
+        };
+        let mut layer = MachineLayer::new("Upper Body");
+        layer.set_mask(LayerMask::new(vec![a]));
+        layer.set_weight(PoseWeight::Parameter("Weight".to_owned()));
+        layer.final_pose = make_pose(&[(a, layer_pose.clone()), (b, layer_pose)]);
+
+        let mut parameters = ParameterContainer::default();
+        parameters.insert("Weight".to_owned(), Parameter::Weight(0.5));
+
+        layer.blend_into(&parameters, &mut dest);
+
+        assert_eq!(dest.local_poses[&a].position, Vector3::new(2.0, 0.0, 0.0));
+        assert_eq!(dest.local_poses[&b].position, Vector3::new(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_additive_layer() {
+        let a = Handle::new(1, 1);
+        let c = Handle::new(3, 1);
+
+        let mut dest = make_pose(&[(
+            a,
+            LocalPose {
+                position: Vector3::new(1.0, 0.0, 0.0),
+                ..Default::default()
+            },
+        )]);
+
+        // Non-identity reference, only the difference between it and the layer pose must be added.
+        let reference_rotation =
+            UnitQuaternion::from_axis_angle(&Vector3::x_axis(), 30.0f32.to_radians());
+        let reference_position = Vector3::new(0.0, 0.0, 5.0);
+        let reference_scale = Vector3::new(3.0, 3.0, 3.0);
+
+        let mut track = Track::new();
+        track.set_node(a);
+        track.add_key_frame(KeyFrame::new(
+            0.0,
+            reference_position,
+            reference_scale,
+            reference_rotation,
+        ));
+        track.add_key_frame(KeyFrame::new(
+            1.0,
+            Vector3::default(),
+            Vector3::repeat(1.0),
+            UnitQuaternion::identity(),
+        ));
+        let mut animation = Animation::default();
+        animation.add_track(track);
+        let mut animations = AnimationContainer::new();
+        let animation = animations.add(animation);
+
+        let mut layer = MachineLayer::new("Breathing");
+        layer.set_blend_mode(LayerBlendMode::Additive);
+        layer.set_weight(PoseWeight::Constant(0.5));
+        layer.add_node(PoseNode::PlayAnimation(PlayAnimation::new(animation)));
+        layer.update_reference_pose(&animations);
+
+        let reference = &layer.reference_pose().local_poses[&a];
+        assert_eq!(reference.position, reference_position);
+        assert_eq!(reference.scale, reference_scale);
+
+        let pose = LocalPose {
+            position: reference_position + Vector3::new(0.0, 2.0, 0.0),
+            scale: reference_scale * 2.0,
+            rotation: reference_rotation
+                * UnitQuaternion::from_axis_angle(&Vector3::y_axis(), 90.0f32.to_radians()),
+            ..Default::default()
+        };
+        layer.final_pose = make_pose(&[(a, pose.clone()), (c, pose)]);
+
+        layer.blend_into(&ParameterContainer::default(), &mut dest);
+
+        let pose = &dest.local_poses[&a];
+        assert!((pose.position - Vector3::new(1.0, 1.0, 0.0)).norm() < 0.001);
+        assert!((pose.scale - Vector3::new(1.5, 1.5, 1.5)).norm() < 0.001);
+        assert!((pose.rotation.angle() - 45.0f32.to_radians()).abs() < 0.001);
+        // Additive layers must not animate nodes that are not animated by previous layers.
+        assert!(!dest.local_poses.contains_key(&c));
+    }
+}
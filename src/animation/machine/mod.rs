@@ -84,23 +84,22 @@
 //!
 //! ```
 //!
-//! You can use multiple machines to animation single model - for example one machine can be for
-//! locomotion and other is for combat. This means that locomotion machine will take control over
-//! lower body and combat machine will control upper body.
+//! A machine can have multiple layers, each with its own state graph - for example one layer can be
+//! for locomotion and other is for combat. Combat layer can have a mask with the upper body bones,
+//! so locomotion layer will take control over lower body and combat layer will control upper body.
+//! See [`MachineLayer`] docs for more info.
 
 use fxhash::FxHashMap;
-use std::cmp::Ordering;
 use std::sync::Arc;
 
 use crate::{
     animation::{
+        ik::IkChain,
         machine::{
-            event::LimitedEventQueue,
-            node::{BasePoseNode, PoseNodeDefinition},
-            parameter::ParameterContainerDefinition,
+            node::PoseNodeDefinition, parameter::ParameterContainerDefinition,
             state::StateDefinition,
-            transition::TransitionDefinition,
         },
+        root_motion::RootMotion,
         AnimationContainer, AnimationPose,
     },
     core::futures::future::join_all,
@@ -116,12 +115,15 @@ use crate::{
         model::{Model, ModelLoadError},
     },
     scene::{graph::Graph, node::Node, Scene},
-    utils::log::{Log, MessageKind},
 };
 pub use event::Event;
 use fyrox_resource::ResourceState;
+pub use layer::{
+    LayerBlendMode, LayerMask, LayerMaskDefinition, MachineLayer, MachineLayerDefinition,
+};
 pub use node::{
     blend::{BlendAnimations, BlendAnimationsByIndex, BlendPose, IndexedBlendInput},
+    blend_space::{BlendSpace, BlendSpacePoint},
     play::PlayAnimation,
     EvaluatePose, PoseNode,
 };
@@ -131,38 +133,107 @@ pub use transition::Transition;
 
 pub mod container;
 pub mod event;
+pub mod layer;
 pub mod node;
 pub mod parameter;
 pub mod state;
 pub mod transition;
 
-#[derive(Default, Debug, Visit, Clone)]
+/// Animation blending state machine, see module docs for more info. The machine consists of one
+/// or more layers, each layer has its own state graph and produces its own pose. Poses of the
+/// layers are combined from the first to the last one, see [`MachineLayer`] docs for more info.
+/// A machine always has at least one layer (base layer), every method that works with states,
+/// transitions and pose nodes without taking a layer works with the base layer.
+#[derive(Debug, Clone)]
 pub struct Machine {
     pub(crate) root: Handle<Node>,
     pub(crate) resource: Option<AbsmResource>,
     parameters: ParameterContainer,
-    nodes: Pool<PoseNode>,
-    transitions: Pool<Transition>,
-    states: Pool<State>,
-    active_state: Handle<State>,
-    entry_state: Handle<State>,
-    active_transition: Handle<Transition>,
-
-    #[visit(skip)]
+    layers: Vec<MachineLayer>,
     final_pose: AnimationPose,
-    #[visit(skip)]
-    events: LimitedEventQueue,
-    #[visit(skip)]
+    ik_chains: Vec<IkChain>,
     debug: bool,
 }
 
-#[derive(Default, Debug, Visit, Clone)]
+impl Default for Machine {
+    fn default() -> Self {
+        Self::new(Handle::NONE)
+    }
+}
+
+impl Visit for Machine {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        self.root.visit("Root", &mut region)?;
+        self.resource.visit("Resource", &mut region)?;
+        self.parameters.visit("Parameters", &mut region)?;
+
+        if region.is_reading() {
+            self.layers.clear();
+            if self.layers.visit("Layers", &mut region).is_err() {
+                // Backward compatibility - machines without layers store the state graph directly.
+                let mut base_layer = MachineLayer::default();
+                base_layer.visit_legacy(&mut region)?;
+                self.layers = vec![base_layer];
+            }
+            if self.layers.is_empty() {
+                self.layers.push(MachineLayer::default());
+            }
+        } else {
+            self.layers.visit("Layers", &mut region)?;
+        }
+
+        // Backward compatibility - machines without IK chains.
+        let _ = self.ik_chains.visit("IkChains", &mut region);
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct MachineDefinition {
     pub parameters: ParameterContainerDefinition,
-    pub nodes: Pool<PoseNodeDefinition>,
-    pub transitions: Pool<TransitionDefinition>,
-    pub states: Pool<StateDefinition>,
-    pub entry_state: Handle<StateDefinition>,
+    /// A set of layers of the machine, there must be at least one layer.
+    pub layers: Vec<MachineLayerDefinition>,
+}
+
+impl Default for MachineDefinition {
+    fn default() -> Self {
+        Self {
+            parameters: Default::default(),
+            layers: vec![MachineLayerDefinition::default()],
+        }
+    }
+}
+
+impl Visit for MachineDefinition {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        self.parameters.visit("Parameters", &mut region)?;
+
+        if region.is_reading() {
+            self.layers.clear();
+            if self.layers.visit("Layers", &mut region).is_err() {
+                // Backward compatibility - definitions without layers store the state graph
+                // directly.
+                let mut base_layer = MachineLayerDefinition::default();
+                base_layer.nodes.visit("Nodes", &mut region)?;
+                base_layer.transitions.visit("Transitions", &mut region)?;
+                base_layer.states.visit("States", &mut region)?;
+                base_layer.entry_state.visit("EntryState", &mut region)?;
+                self.layers = vec![base_layer];
+            }
+            if self.layers.is_empty() {
+                self.layers.push(MachineLayerDefinition::default());
+            }
+        } else {
+            self.layers.visit("Layers", &mut region)?;
+        }
+
+        Ok(())
+    }
 }
 
 /// An error that may occur during ABSM resource loading.
@@ -204,7 +275,7 @@ impl From<Option<Arc<ModelLoadError>>> for MachineInstantiationError {
     }
 }
 
-fn instantiate_node(
+pub(super) fn instantiate_node(
     node_definition: &PoseNodeDefinition,
     definition_handle: Handle<PoseNodeDefinition>,
     animations_pack: &AnimationsPack,
@@ -232,6 +303,12 @@ fn instantiate_node(
                 animation
                     .set_speed(play_animation.speed)
                     .set_time_slice(play_animation.time_slice.clone().map(|s| s.0));
+                animation.set_root_motion_settings(
+                    play_animation
+                        .root_motion
+                        .as_ref()
+                        .map(|s| s.instantiate(root, graph)),
+                );
             }
 
             PoseNode::make_play_animation(animation)
@@ -263,6 +340,18 @@ fn instantiate_node(
                     .collect(),
             )
         }
+        PoseNodeDefinition::BlendSpace(blend_space) => PoseNode::make_blend_space(
+            blend_space.sampling_parameter.clone(),
+            blend_space
+                .points
+                .iter()
+                .map(|p| BlendSpacePoint {
+                    position: p.position,
+                    // Will be assigned on the next stage.
+                    pose_source: Default::default(),
+                })
+                .collect(),
+        ),
     };
 
     node.definition = definition_handle;
@@ -293,8 +382,9 @@ impl AnimationsPack {
 
 impl MachineDefinition {
     pub(crate) fn collect_animation_paths(&self) -> Vec<String> {
-        self.nodes
+        self.layers
             .iter()
+            .flat_map(|layer| layer.nodes.iter())
             .filter_map(|node| {
                 if let PoseNodeDefinition::PlayAnimation(play_animation) = node {
                     Some(play_animation.animation.clone())
@@ -342,120 +432,25 @@ impl MachineDefinition {
             machine.set_parameter(&definition.name, definition.value);
         }
 
-        // Instantiate nodes.
-        let mut node_map = FxHashMap::default();
-        for (definition_handle, node_definition) in self.nodes.pair_iter() {
-            let instance_handle = machine.add_node(instantiate_node(
-                node_definition,
-                definition_handle,
-                &animations,
+        // Instantiate layers.
+        machine.layers.clear();
+        for layer_definition in self.layers.iter() {
+            machine.layers.push(layer_definition.instantiate(
                 root,
                 &mut scene.graph,
                 &mut scene.animations,
+                &animations,
             )?);
-
-            node_map.insert(definition_handle, instance_handle);
         }
-
-        // Link nodes.
-        for (definition_handle, instance_handle) in node_map.iter() {
-            let definition = &self.nodes[*definition_handle];
-            let instance = machine.node_mut(*instance_handle);
-
-            match instance {
-                PoseNode::PlayAnimation(_) => {
-                    // Do nothing, has no links to other nodes.
-                }
-                PoseNode::BlendAnimations(blend_animations) => {
-                    if let PoseNodeDefinition::BlendAnimations(blend_animations_definition) =
-                        definition
-                    {
-                        for (blend_pose, blend_pose_definition) in blend_animations
-                            .pose_sources
-                            .iter_mut()
-                            .zip(blend_animations_definition.pose_sources.iter())
-                        {
-                            blend_pose.pose_source = node_map
-                                .get(&blend_pose_definition.pose_source)
-                                .cloned()
-                                .unwrap_or_default();
-                        }
-                    } else {
-                        unreachable!()
-                    }
-                }
-                PoseNode::BlendAnimationsByIndex(blend_animations) => {
-                    if let PoseNodeDefinition::BlendAnimationsByIndex(blend_animations_definition) =
-                        definition
-                    {
-                        for (input, input_definition) in blend_animations
-                            .inputs
-                            .iter_mut()
-                            .zip(blend_animations_definition.inputs.iter())
-                        {
-                            input.pose_source = node_map
-                                .get(&input_definition.pose_source)
-                                .cloned()
-                                .unwrap_or_default();
-                        }
-                    } else {
-                        unreachable!()
-                    }
-                }
-            }
-        }
-
-        // Instantiate states.
-        let mut state_map = FxHashMap::default();
-        for (definition_handle, state_definition) in self.states.pair_iter() {
-            let mut state = State::new(
-                state_definition.name.as_ref(),
-                node_map
-                    .get(&state_definition.root)
-                    .cloned()
-                    .unwrap_or_default(),
-            );
-
-            state.definition = definition_handle;
-
-            let instance_handle = machine.add_state(state);
-
-            state_map.insert(definition_handle, instance_handle);
-        }
-
-        // Instantiate transitions.
-        for (transition_definition_handle, transition_definition) in self.transitions.pair_iter() {
-            machine.add_transition(Transition {
-                definition: transition_definition_handle,
-                name: transition_definition.name.clone(),
-                transition_time: transition_definition.transition_time,
-                elapsed_time: 0.0,
-                source: state_map
-                    .get(&transition_definition.source)
-                    .cloned()
-                    .expect("There must be a respective source state!"),
-                dest: state_map
-                    .get(&transition_definition.dest)
-                    .cloned()
-                    .expect("There must be a respective dest state!"),
-                rule: transition_definition.rule.clone(),
-                invert_rule: transition_definition.invert_rule,
-                blend_factor: 0.0,
-            });
+        if machine.layers.is_empty() {
+            machine.layers.push(MachineLayer::default());
         }
 
-        machine.set_entry_state(
-            state_map
-                .get(&self.entry_state)
-                .cloned()
-                .unwrap_or_default(),
-        );
-
         Ok(scene.animation_machines.add(machine))
     }
 }
 
-fn find_state_by_definition(
+pub(super) fn find_state_by_definition(
     states: &Pool<State>,
     definition: Handle<StateDefinition>,
 ) -> Handle<State> {
@@ -471,7 +466,7 @@ fn find_state_by_definition(
         .unwrap_or_default()
 }
 
-fn find_node_by_definition(
+pub(super) fn find_node_by_definition(
     nodes: &Pool<PoseNode>,
     definition: Handle<PoseNodeDefinition>,
 ) -> Handle<PoseNode> {
@@ -493,22 +488,17 @@ impl Machine {
         Self {
             root,
             resource: None,
-            nodes: Default::default(),
-            states: Default::default(),
-            transitions: Default::default(),
-            final_pose: Default::default(),
-            active_state: Default::default(),
-            entry_state: Default::default(),
-            active_transition: Default::default(),
             parameters: Default::default(),
-            events: LimitedEventQueue::new(2048),
+            layers: vec![MachineLayer::default()],
+            final_pose: Default::default(),
+            ik_chains: Default::default(),
             debug: false,
         }
     }
 
     #[inline]
     pub fn add_node(&mut self, node: PoseNode) -> Handle<PoseNode> {
-        self.nodes.spawn(node)
+        self.base_layer_mut().add_node(node)
     }
 
     #[inline]
@@ -532,8 +522,7 @@ impl Machine {
 
     #[inline]
     pub fn set_entry_state(&mut self, entry_state: Handle<State>) {
-        self.active_state = entry_state;
-        self.entry_state = entry_state;
+        self.base_layer_mut().set_entry_state(entry_state)
     }
 
     #[inline]
@@ -543,31 +532,27 @@ impl Machine {
 
     #[inline]
     pub fn add_state(&mut self, state: State) -> Handle<State> {
-        let state = self.states.spawn(state);
-        if self.active_state.is_none() {
-            self.active_state = state;
-        }
-        state
+        self.base_layer_mut().add_state(state)
     }
 
     #[inline]
     pub fn add_transition(&mut self, transition: Transition) -> Handle<Transition> {
-        self.transitions.spawn(transition)
+        self.base_layer_mut().add_transition(transition)
     }
 
     #[inline]
     pub fn get_state(&self, state: Handle<State>) -> &State {
-        &self.states[state]
+        self.base_layer().get_state(state)
     }
 
     #[inline]
     pub fn get_transition(&self, transition: Handle<Transition>) -> &Transition {
-        &self.transitions[transition]
+        self.base_layer().get_transition(transition)
     }
 
     #[inline]
     pub fn pop_event(&mut self) -> Option<Event> {
-        self.events.pop()
+        self.base_layer_mut().pop_event()
     }
 
     #[inline]
@@ -577,41 +562,79 @@ impl Machine {
 
     #[inline]
     pub fn reset(&mut self) {
-        for transition in self.transitions.iter_mut() {
-            transition.reset();
+        for layer in self.layers.iter_mut() {
+            layer.reset();
         }
-
-        self.active_state = self.entry_state;
     }
 
     #[inline]
     pub fn nodes(&self) -> impl Iterator<Item = &PoseNode> {
-        self.nodes.iter()
+        self.base_layer().nodes()
     }
 
     #[inline]
     pub fn node_mut(&mut self, handle: Handle<PoseNode>) -> &mut PoseNode {
-        &mut self.nodes[handle]
+        self.base_layer_mut().node_mut(handle)
     }
 
     #[inline]
     pub fn active_state(&self) -> Handle<State> {
-        self.active_state
+        self.base_layer().active_state()
     }
 
     #[inline]
     pub fn active_transition(&self) -> Handle<Transition> {
-        self.active_transition
+        self.base_layer().active_transition()
     }
 
     #[inline]
     pub fn transitions(&self) -> &Pool<Transition> {
-        &self.transitions
+        self.base_layer().transitions()
     }
 
     #[inline]
     pub fn states(&self) -> &Pool<State> {
-        &self.states
+        self.base_layer().states()
+    }
+
+    /// Returns a reference to the first layer of the machine.
+    #[inline]
+    pub fn base_layer(&self) -> &MachineLayer {
+        &self.layers[0]
+    }
+
+    /// Returns a reference to the first layer of the machine.
+    #[inline]
+    pub fn base_layer_mut(&mut self) -> &mut MachineLayer {
+        &mut self.layers[0]
+    }
+
+    #[inline]
+    pub fn layers(&self) -> &[MachineLayer] {
+        &self.layers
+    }
+
+    #[inline]
+    pub fn layers_mut(&mut self) -> &mut [MachineLayer] {
+        &mut self.layers
+    }
+
+    /// Adds new layer on top of existing layers and returns its index.
+    #[inline]
+    pub fn add_layer(&mut self, layer: MachineLayer) -> usize {
+        self.layers.push(layer);
+        self.layers.len() - 1
+    }
+
+    /// Removes a layer at the given index. The last remaining layer cannot be removed, the method
+    /// returns `None` in this case.
+    #[inline]
+    pub fn remove_layer(&mut self, index: usize) -> Option<MachineLayer> {
+        if self.layers.len() > 1 && index < self.layers.len() {
+            Some(self.layers.remove(index))
+        } else {
+            None
+        }
     }
 
     pub fn restore_resources(&mut self, resource_manager: ResourceManager) {
@@ -632,295 +655,30 @@ impl Machine {
         if let Some(resource) = self.resource.clone() {
             let definition = &resource.data_ref().absm_definition;
 
-            // Step 1. Restore integrity - add missing entities, remove nonexistent from instance.
-            match definition
-                .nodes
-                .alive_count()
-                .cmp(&self.nodes.alive_count())
-            {
-                Ordering::Less => {
-                    // Some nodes were deleted in definition, remove respective instances.
-                    let mut nodes_to_remove = Vec::new();
-                    for (handle, node) in self.nodes.pair_iter() {
-                        if !definition.nodes.is_valid_handle(node.definition) {
-                            nodes_to_remove.push(handle);
-                        }
-                    }
-
-                    for node_to_remove in nodes_to_remove {
-                        self.nodes.free(node_to_remove);
-                    }
-                }
-                Ordering::Equal => {
-                    // Do nothing
-                }
-                Ordering::Greater => {
-                    // Some nodes were added in definition, create respective instances.
-                    for (node_definition_handle, node_definition) in definition.nodes.pair_iter() {
-                        if self
-                            .nodes
-                            .iter()
-                            .all(|n| n.definition != node_definition_handle)
-                        {
-                            let pose_node = instantiate_node(
-                                node_definition,
-                                node_definition_handle,
-                                animations_pack,
-                                self.root,
-                                graph,
-                                animations,
-                            )
-                            .unwrap();
-
-                            let _ = self.nodes.spawn(pose_node);
-                        }
-                    }
-                }
-            }
-            let definition_to_node_map = self
-                .nodes
-                .pair_iter()
-                .map(|(h, n)| (n.definition, h))
-                .collect::<FxHashMap<_, _>>();
-            let fetch_node_by_definition = |definition: Handle<PoseNodeDefinition>| {
-                definition_to_node_map
-                    .get(&definition)
-                    .cloned()
-                    .unwrap_or_default()
-            };
-
-            match definition
-                .states
-                .alive_count()
-                .cmp(&self.states.alive_count())
-            {
-                Ordering::Less => {
-                    // Some states were deleted in definition, remove respective instances.
-                    let mut states_to_remove = Vec::new();
-                    for (handle, state) in self.states.pair_iter() {
-                        if !definition.states.is_valid_handle(state.definition) {
-                            states_to_remove.push(handle);
-                        }
-                    }
-
-                    for node_to_remove in states_to_remove {
-                        self.states.free(node_to_remove);
-                    }
-                }
-                Ordering::Equal => {
-                    // Do nothing.
-                }
-                Ordering::Greater => {
-                    // Some states were added in definition, create respective instances.
-                    for (state_definition_handle, state_definition) in definition.states.pair_iter()
-                    {
-                        if self
-                            .states
-                            .iter()
-                            .all(|s| s.definition != state_definition_handle)
-                        {
-                            let root = find_node_by_definition(&self.nodes, state_definition.root);
-
-                            let mut state = State::new(state_definition.name.as_ref(), root);
-
-                            state.definition = state_definition_handle;
-
-                            let _ = self.states.spawn(state);
-                        }
-                    }
-                }
-            }
-
-            match definition
-                .transitions
-                .alive_count()
-                .cmp(&self.transitions.alive_count())
-            {
-                Ordering::Less => {
-                    // Some transitions were deleted in definition, remove respective instances.
-                    let mut transitions_to_remove = Vec::new();
-                    for (handle, transition) in self.transitions.pair_iter() {
-                        if !definition
-                            .transitions
-                            .is_valid_handle(transition.definition)
-                        {
-                            transitions_to_remove.push(handle);
-                        }
-                    }
-
-                    for node_to_remove in transitions_to_remove {
-                        self.transitions.free(node_to_remove);
-                    }
-                }
-                Ordering::Equal => {
-                    // Do nothing.
-                }
-                Ordering::Greater => {
-                    // Some transitions were added in definition, create respective instances.
-                    for (transition_definition_handle, transition_definition) in
-                        definition.transitions.pair_iter()
-                    {
-                        if self
-                            .transitions
-                            .iter()
-                            .all(|t| t.definition != transition_definition_handle)
-                        {
-                            let mut transition = Transition::new(
-                                transition_definition.name.as_ref(),
-                                find_state_by_definition(
-                                    &self.states,
-                                    transition_definition.source,
-                                ),
-                                find_state_by_definition(&self.states, transition_definition.dest),
-                                transition_definition.transition_time,
-                                transition_definition.rule.as_str(),
-                            );
-
-                            transition.definition = transition_definition_handle;
-
-                            let _ = self.transitions.spawn(transition);
-                        }
-                    }
-                }
-            }
-
-            // Step 2. Sync data of instance entities with respective definitions.
-            for node in self.nodes.iter_mut() {
-                let node_definition = &definition.nodes[node.definition];
-
-                match node {
-                    PoseNode::PlayAnimation(play_animation) => {
-                        if let PoseNodeDefinition::PlayAnimation(play_animation_definition) =
-                            node_definition
-                        {
-                            let definition_animation = animations_pack
-                                .animations()
-                                .get(&play_animation_definition.animation);
-
-                            if animations.try_get(play_animation.animation).map_or(
-                                true,
-                                |current_animation| {
-                                    definition_animation != current_animation.resource.as_ref()
-                                },
-                            ) {
-                                animations.remove(play_animation.animation);
-
-                                let new_animation = if let Some(definition_animation) =
-                                    definition_animation
-                                {
-                                    if matches!(*definition_animation.state(), ResourceState::Ok(_))
-                                    {
-                                        definition_animation
-                                            .retarget_animations_internal(
-                                                self.root, graph, animations,
-                                            )
-                                            .first()
-                                            .cloned()
-                                            .unwrap_or_default()
-                                    } else {
-                                        Handle::NONE
-                                    }
-                                } else {
-                                    Handle::NONE
-                                };
-
-                                *play_animation = PlayAnimation {
-                                    base: BasePoseNode {
-                                        definition: play_animation.definition,
-                                    },
-                                    animation: new_animation,
-                                    output_pose: Default::default(),
-                                };
-                            }
-
-                            // Apply definition properties to instance.
-                            if let Some(animation) =
-                                animations.try_get_mut(play_animation.animation)
-                            {
-                                animation
-                                    .set_speed(play_animation_definition.speed)
-                                    .set_time_slice(
-                                        play_animation_definition.time_slice.clone().map(|s| s.0),
-                                    );
-                            }
-                        } else {
-                            unreachable!()
-                        }
-                    }
-                    PoseNode::BlendAnimations(blend_animations) => {
-                        if let PoseNodeDefinition::BlendAnimations(blend_animations_definition) =
-                            node_definition
-                        {
-                            *blend_animations = BlendAnimations {
-                                base: BasePoseNode {
-                                    definition: blend_animations.definition,
-                                },
-                                pose_sources: blend_animations_definition
-                                    .pose_sources
-                                    .iter()
-                                    .map(|s| BlendPose {
-                                        weight: s.weight.clone(),
-                                        pose_source: fetch_node_by_definition(s.pose_source),
-                                    })
-                                    .collect(),
-                                output_pose: std::mem::take(&mut blend_animations.output_pose),
-                            }
-                        }
-                    }
-                    PoseNode::BlendAnimationsByIndex(blend_animations) => {
-                        if let PoseNodeDefinition::BlendAnimationsByIndex(
-                            blend_animations_definition,
-                        ) = node_definition
-                        {
-                            *blend_animations = BlendAnimationsByIndex {
-                                base: BasePoseNode {
-                                    definition: blend_animations.definition,
-                                },
-                                index_parameter: blend_animations_definition
-                                    .index_parameter
-                                    .clone(),
-                                inputs: blend_animations_definition
-                                    .inputs
-                                    .iter()
-                                    .map(|i| IndexedBlendInput {
-                                        blend_time: i.blend_time,
-                                        pose_source: fetch_node_by_definition(i.pose_source),
-                                    })
-                                    .collect(),
-                                prev_index: blend_animations.prev_index.clone(),
-                                blend_time: blend_animations.blend_time.clone(),
-                                output_pose: std::mem::take(&mut blend_animations.output_pose),
-                            }
+            // Step 1. Sync layers count. Layers have no identity, so the only reliable way of
+            // syncing them after addition or removal is to re-create all of them.
+            let layer_count = definition.layers.len().max(1);
+            if layer_count != self.layers.len() {
+                for layer in self.layers.drain(..) {
+                    for node in layer.nodes() {
+                        if let PoseNode::PlayAnimation(play_animation) = node {
+                            animations.remove(play_animation.animation);
                         }
                     }
                 }
+                self.layers
+                    .extend((0..layer_count).map(|_| MachineLayer::default()));
             }
 
-            for state in self.states.iter_mut() {
-                let state_definition = &definition.states[state.definition];
-
-                // Reassign the entire state to trigger compiler error if there's a new field.
-                *state = State {
-                    definition: state.definition,
-                    name: state_definition.name.clone(),
-                    root: find_node_by_definition(&self.nodes, state_definition.root),
-                };
-            }
-
-            for transition in self.transitions.iter_mut() {
-                let transition_definition = &definition.transitions[transition.definition];
-
-                *transition = Transition {
-                    definition: transition.definition,
-                    name: transition_definition.name.clone(),
-                    transition_time: transition_definition.transition_time,
-                    elapsed_time: transition.elapsed_time,
-                    source: find_state_by_definition(&self.states, transition_definition.source),
-                    dest: find_state_by_definition(&self.states, transition_definition.dest),
-                    rule: transition_definition.rule.clone(),
-                    invert_rule: transition_definition.invert_rule,
-                    blend_factor: transition.blend_factor,
-                };
+            // Step 2. Sync each layer with its definition.
+            for (layer, layer_definition) in self.layers.iter_mut().zip(definition.layers.iter()) {
+                layer.resolve(
+                    layer_definition,
+                    self.root,
+                    animations_pack,
+                    graph,
+                    animations,
+                );
             }
 
             // Step 3. Sync parameters.
@@ -931,111 +689,57 @@ impl Machine {
         }
     }
 
+    /// Returns root motion of the final pose of the machine. Root motion of a machine is a blend
+    /// of root motions of the animations that are used by active states and transitions. See
+    /// [`crate::animation::root_motion::RootMotionSettings`] docs for more info.
+    pub fn root_motion(&self) -> Option<&RootMotion> {
+        self.final_pose.root_motion()
+    }
+
     pub fn evaluate_pose(&mut self, animations: &AnimationContainer, dt: f32) -> &AnimationPose {
         self.final_pose.reset();
 
-        if self.active_state.is_some() || self.active_transition.is_some() {
-            // Gather actual poses for each state.
-            for state in self.states.iter_mut() {
-                state.update(&self.nodes, &self.parameters, animations, dt);
-            }
-
-            if self.active_transition.is_none() {
-                // Find transition.
-                for (handle, transition) in self.transitions.pair_iter_mut() {
-                    if transition.dest() == self.active_state
-                        || transition.source() != self.active_state
-                    {
-                        continue;
-                    }
-                    if let Some(Parameter::Rule(mut active)) =
-                        self.parameters.get(transition.rule()).cloned()
-                    {
-                        if transition.invert_rule {
-                            active = !active;
-                        }
-
-                        if active {
-                            self.events.push(Event::StateLeave(self.active_state));
-                            if self.debug {
-                                Log::writeln(
-                                    MessageKind::Information,
-                                    format!(
-                                        "Leaving state: {}",
-                                        self.states[self.active_state].name()
-                                    ),
-                                );
-                            }
-
-                            self.events.push(Event::StateEnter(transition.source()));
-                            if self.debug {
-                                Log::writeln(
-                                    MessageKind::Information,
-                                    format!(
-                                        "Entering state: {}",
-                                        self.states[transition.source()].name()
-                                    ),
-                                );
-                            }
-
-                            self.active_state = Handle::NONE;
-
-                            self.active_transition = handle;
-                            self.events
-                                .push(Event::ActiveTransitionChanged(self.active_transition));
-
-                            break;
-                        }
-                    }
-                }
-            }
+        for layer in self.layers.iter_mut() {
+            layer.evaluate_pose(&self.parameters, animations, dt, self.debug);
+            layer.blend_into(&self.parameters, &mut self.final_pose);
+        }
 
-            // Double check for active transition because we can have empty machine.
-            if self.active_transition.is_some() {
-                let transition = &mut self.transitions[self.active_transition];
+        &self.final_pose
+    }
 
-                // Blend between source and dest states.
-                if let Some(source_pose) = self.states[transition.source()].pose(&self.nodes) {
-                    self.final_pose
-                        .blend_with(&source_pose, 1.0 - transition.blend_factor());
-                }
-                if let Some(dest_pose) = self.states[transition.dest()].pose(&self.nodes) {
-                    self.final_pose
-                        .blend_with(&dest_pose, transition.blend_factor());
-                }
+    /// Returns final pose of the machine, that was calculated by the last call of
+    /// [`Self::evaluate_pose`] (and possibly modified by [`Self::solve_ik`]).
+    pub fn pose(&self) -> &AnimationPose {
+        &self.final_pose
+    }
 
-                transition.update(dt);
+    /// Adds new IK chain to the machine and returns its index. IK chains are solved after pose
+    /// evaluation, in the order of addition. See [`IkChain`] docs for more info.
+    pub fn add_ik_chain(&mut self, chain: IkChain) -> usize {
+        self.ik_chains.push(chain);
+        self.ik_chains.len() - 1
+    }
 
-                if transition.is_done() {
-                    transition.reset();
+    /// Removes an IK chain at the given index and returns it.
+    pub fn remove_ik_chain(&mut self, index: usize) -> IkChain {
+        self.ik_chains.remove(index)
+    }
 
-                    self.active_transition = Handle::NONE;
-                    self.events
-                        .push(Event::ActiveTransitionChanged(self.active_transition));
+    #[inline]
+    pub fn ik_chains(&self) -> &[IkChain] {
+        &self.ik_chains
+    }
 
-                    self.active_state = transition.dest();
-                    self.events
-                        .push(Event::ActiveStateChanged(self.active_state));
+    #[inline]
+    pub fn ik_chains_mut(&mut self) -> &mut [IkChain] {
+        &mut self.ik_chains
+    }
 
-                    if self.debug {
-                        Log::writeln(
-                            MessageKind::Information,
-                            format!(
-                                "Active state changed: {}",
-                                self.states[self.active_state].name()
-                            ),
-                        );
-                    }
-                }
-            } else {
-                // We must have active state all the time when we do not have any active transition.
-                // Just get pose from active state.
-                if let Some(active_state_pose) = self.states[self.active_state].pose(&self.nodes) {
-                    active_state_pose.clone_into(&mut self.final_pose);
-                }
-            }
+    /// Applies IK chains of the machine to the final pose. It is called automatically by the
+    /// scene after [`Self::evaluate_pose`].
+    pub fn solve_ik(&mut self, graph: &Graph) {
+        for chain in self.ik_chains.iter() {
+            chain.apply(&mut self.final_pose, graph);
         }
-
-        &self.final_pose
     }
 }
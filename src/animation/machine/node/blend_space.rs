@@ -0,0 +1,402 @@
+//! Blend space is a pose node that blends animations placed at points of a 2D parameter space. See
+//! [`BlendSpace`] docs for more info.
+
+use crate::{
+    animation::{
+        machine::{
+            node::{BasePoseNode, BasePoseNodeDefinition, PoseNodeDefinition},
+            EvaluatePose, Parameter, ParameterContainer, PoseNode,
+        },
+        AnimationContainer, AnimationPose,
+    },
+    core::{
+        algebra::Vector2,
+        inspect::{Inspect, PropertyInfo},
+        math::{self, triangulator},
+        pool::{Handle, Pool},
+        reflect::Reflect,
+        visitor::prelude::*,
+    },
+};
+use std::{
+    cell::{Ref, RefCell},
+    cmp::Ordering,
+    ops::{Deref, DerefMut},
+};
+
+/// An animation pose placed at some point of a blend space.
+#[derive(Default, Debug, Visit, Clone)]
+pub struct BlendSpacePoint {
+    pub position: Vector2<f32>,
+    pub pose_source: Handle<PoseNode>,
+}
+
+#[derive(Default, Debug, Visit, Clone, Inspect, Reflect)]
+pub struct BlendSpacePointDefinition {
+    pub position: Vector2<f32>,
+    #[inspect(skip)]
+    pub pose_source: Handle<PoseNodeDefinition>,
+}
+
+/// Blend space node. It takes a set of poses placed at points of a 2D space and blends them using
+/// a sampling point taken from a [`Parameter::SamplingPoint`] parameter. Typical usage is
+/// locomotion - place walk and run animations at points that correspond to their velocity
+/// (forward, backward, strafe left, etc.) and set the sampling point to the current velocity of
+/// a character, the node will produce smooth pose for any velocity in between.
+///
+/// The points are triangulated, and the weights of the poses are barycentric coordinates of the
+/// sampling point in a triangle that contains it. If the sampling point lies outside of the
+/// triangulated area, the closest point on its boundary is used instead. If all the points lie
+/// on a single line, the node works as 1D blend space - it interpolates between two nearest
+/// points on the line.
+#[derive(Default, Debug, Clone)]
+pub struct BlendSpace {
+    pub base: BasePoseNode,
+    pub(crate) points: Vec<BlendSpacePoint>,
+    pub(crate) sampling_parameter: String,
+    pub(crate) triangles: Vec<[usize; 3]>,
+    pub(crate) output_pose: RefCell<AnimationPose>,
+}
+
+impl Visit for BlendSpace {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        self.base.visit("Base", &mut region)?;
+        self.points.visit("Points", &mut region)?;
+        self.sampling_parameter
+            .visit("SamplingParameter", &mut region)?;
+
+        if region.is_reading() {
+            self.triangulate();
+        }
+
+        Ok(())
+    }
+}
+
+impl Deref for BlendSpace {
+    type Target = BasePoseNode;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for BlendSpace {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl BlendSpace {
+    /// Creates new blend space node with given points. `sampling_parameter` must be name of
+    /// SamplingPoint parameter in machine.
+    pub fn new(sampling_parameter: String, points: Vec<BlendSpacePoint>) -> Self {
+        let mut blend_space = Self {
+            base: Default::default(),
+            points,
+            sampling_parameter,
+            triangles: Default::default(),
+            output_pose: Default::default(),
+        };
+        blend_space.triangulate();
+        blend_space
+    }
+
+    /// Returns a reference to the points of the blend space.
+    pub fn points(&self) -> &[BlendSpacePoint] {
+        &self.points
+    }
+
+    /// Sets new points of the blend space and re-triangulates it.
+    pub fn set_points(&mut self, points: Vec<BlendSpacePoint>) {
+        self.points = points;
+        self.triangulate();
+    }
+
+    /// Returns triangles (triples of point indices) of the blend space. The list is empty if
+    /// there are less than three points or all of them are collinear.
+    pub fn triangles(&self) -> &[[usize; 3]] {
+        &self.triangles
+    }
+
+    /// Returns name of the parameter that is used as sampling point.
+    pub fn sampling_parameter(&self) -> &str {
+        &self.sampling_parameter
+    }
+
+    /// Sets name of the parameter that is used as sampling point.
+    pub fn set_sampling_parameter(&mut self, parameter: String) {
+        self.sampling_parameter = parameter;
+    }
+
+    fn triangulate(&mut self) {
+        self.triangles = triangulator::triangulate_points(
+            &self.points.iter().map(|p| p.position).collect::<Vec<_>>(),
+        );
+    }
+
+    /// Calculates weights of the points for the given sampling point. Returns a set of pairs
+    /// `(point index, weight)`, the sum of weights is always one. The set is empty if the blend
+    /// space has no points.
+    pub fn fetch_weights(&self, sampling_point: Vector2<f32>) -> Vec<(usize, f32)> {
+        let positions = self.points.iter().map(|p| p.position).collect::<Vec<_>>();
+        if self.triangles.is_empty() {
+            fetch_weights_1d(&positions, sampling_point)
+        } else {
+            fetch_weights_2d(&positions, &self.triangles, sampling_point)
+        }
+    }
+}
+
+fn fetch_weights_2d(
+    positions: &[Vector2<f32>],
+    triangles: &[[usize; 3]],
+    sampling_point: Vector2<f32>,
+) -> Vec<(usize, f32)> {
+    // Check if the sampling point is inside of any triangle first.
+    for triangle in triangles.iter() {
+        let (u, v, w) = math::get_barycentric_coords_2d(
+            sampling_point,
+            positions[triangle[0]],
+            positions[triangle[1]],
+            positions[triangle[2]],
+        );
+
+        if u >= -f32::EPSILON && v >= -f32::EPSILON && w >= -f32::EPSILON {
+            return vec![(triangle[0], u), (triangle[1], v), (triangle[2], w)];
+        }
+    }
+
+    // The point is outside, find closest point on the edges of the triangles and interpolate
+    // between the ends of that edge.
+    let mut closest = None;
+    let mut closest_sqr_distance = f32::MAX;
+    for triangle in triangles.iter() {
+        for (a, b) in [
+            (triangle[0], triangle[1]),
+            (triangle[1], triangle[2]),
+            (triangle[2], triangle[0]),
+        ] {
+            let t = project_on_segment(positions[a], positions[b], sampling_point);
+            let sqr_distance =
+                (positions[a].lerp(&positions[b], t) - sampling_point).norm_squared();
+            if sqr_distance < closest_sqr_distance {
+                closest_sqr_distance = sqr_distance;
+                closest = Some((a, b, t));
+            }
+        }
+    }
+
+    closest
+        .map(|(a, b, t)| vec![(a, 1.0 - t), (b, t)])
+        .unwrap_or_default()
+}
+
+fn fetch_weights_1d(positions: &[Vector2<f32>], sampling_point: Vector2<f32>) -> Vec<(usize, f32)> {
+    let origin = match positions.first() {
+        Some(origin) => *origin,
+        None => return vec![],
+    };
+
+    // All points lie on a single line, find its direction using the most distant point.
+    let direction = positions
+        .iter()
+        .map(|p| p - origin)
+        .max_by(|a, b| {
+            a.norm_squared()
+                .partial_cmp(&b.norm_squared())
+                .unwrap_or(Ordering::Equal)
+        })
+        .and_then(|d| d.try_normalize(f32::EPSILON));
+
+    let direction = match direction {
+        Some(direction) => direction,
+        // All points are at the same location.
+        None => return vec![(0, 1.0)],
+    };
+
+    let mut projections = positions
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (i, (p - origin).dot(&direction)))
+        .collect::<Vec<_>>();
+    projections.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal));
+
+    let t = (sampling_point - origin).dot(&direction);
+
+    let (first, last) = (projections[0], projections[projections.len() - 1]);
+    if t <= first.1 {
+        return vec![(first.0, 1.0)];
+    } else if t >= last.1 {
+        return vec![(last.0, 1.0)];
+    }
+
+    for pair in projections.windows(2) {
+        let (left, right) = (pair[0], pair[1]);
+        if t >= left.1 && t <= right.1 {
+            let length = right.1 - left.1;
+            let k = if length > f32::EPSILON {
+                (t - left.1) / length
+            } else {
+                0.0
+            };
+            return vec![(left.0, 1.0 - k), (right.0, k)];
+        }
+    }
+
+    vec![(last.0, 1.0)]
+}
+
+fn project_on_segment(a: Vector2<f32>, b: Vector2<f32>, point: Vector2<f32>) -> f32 {
+    let ab = b - a;
+    let sqr_length = ab.norm_squared();
+    if sqr_length > f32::EPSILON {
+        ((point - a).dot(&ab) / sqr_length).clamp(0.0, 1.0)
+    } else {
+        0.0
+    }
+}
+
+impl EvaluatePose for BlendSpace {
+    fn eval_pose(
+        &self,
+        nodes: &Pool<PoseNode>,
+        params: &ParameterContainer,
+        animations: &AnimationContainer,
+        dt: f32,
+    ) -> Ref<AnimationPose> {
+        self.output_pose.borrow_mut().reset();
+
+        if let Some(&Parameter::SamplingPoint(sampling_point)) =
+            params.get(&self.sampling_parameter)
+        {
+            for (index, weight) in self.fetch_weights(sampling_point) {
+                if let Some(pose_source) = nodes
+                    .try_borrow(self.points[index].pose_source)
+                    .map(|pose_source| pose_source.eval_pose(nodes, params, animations, dt))
+                {
+                    self.output_pose
+                        .borrow_mut()
+                        .blend_with(&pose_source, weight);
+                }
+            }
+        }
+
+        self.output_pose.borrow()
+    }
+
+    fn pose(&self) -> Ref<AnimationPose> {
+        self.output_pose.borrow()
+    }
+}
+
+#[derive(Default, Debug, Visit, Clone, Inspect, Reflect)]
+pub struct BlendSpaceDefinition {
+    pub base: BasePoseNodeDefinition,
+    pub sampling_parameter: String,
+    pub points: Vec<BlendSpacePointDefinition>,
+}
+
+impl BlendSpaceDefinition {
+    pub fn children(&self) -> Vec<Handle<PoseNodeDefinition>> {
+        self.points.iter().map(|p| p.pose_source).collect()
+    }
+}
+
+impl Deref for BlendSpaceDefinition {
+    type Target = BasePoseNodeDefinition;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for BlendSpaceDefinition {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::machine::node::blend_space::{BlendSpace, BlendSpacePoint},
+        core::algebra::Vector2,
+    };
+
+    fn make_blend_space(positions: &[Vector2<f32>]) -> BlendSpace {
+        BlendSpace::new(
+            "Velocity".to_owned(),
+            positions
+                .iter()
+                .map(|p| BlendSpacePoint {
+                    position: *p,
+                    pose_source: Default::default(),
+                })
+                .collect(),
+        )
+    }
+
+    fn weight_of(weights: &[(usize, f32)], index: usize) -> f32 {
+        weights
+            .iter()
+            .filter(|(i, _)| *i == index)
+            .map(|(_, w)| *w)
+            .sum()
+    }
+
+    #[test]
+    fn test_blend_space_2d_weights() {
+        let blend_space = make_blend_space(&[
+            Vector2::new(0.0, 0.0),
+            Vector2::new(1.0, 0.0),
+            Vector2::new(0.0, 1.0),
+            Vector2::new(-1.0, 0.0),
+            Vector2::new(0.0, -1.0),
+        ]);
+        assert_eq!(blend_space.triangles().len(), 4);
+
+        // Exactly at a point.
+        let weights = blend_space.fetch_weights(Vector2::new(1.0, 0.0));
+        assert!((weight_of(&weights, 1) - 1.0).abs() < 0.001);
+
+        // Between center and right points.
+        let weights = blend_space.fetch_weights(Vector2::new(0.5, 0.0));
+        assert!((weight_of(&weights, 0) - 0.5).abs() < 0.001);
+        assert!((weight_of(&weights, 1) - 0.5).abs() < 0.001);
+
+        // Outside - clamped to the boundary.
+        let weights = blend_space.fetch_weights(Vector2::new(5.0, 0.0));
+        assert!((weight_of(&weights, 1) - 1.0).abs() < 0.001);
+
+        let sum = blend_space
+            .fetch_weights(Vector2::new(0.2, 0.3))
+            .iter()
+            .map(|(_, w)| *w)
+            .sum::<f32>();
+        assert!((sum - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_blend_space_1d_weights() {
+        let blend_space = make_blend_space(&[
+            Vector2::new(2.0, 0.0),
+            Vector2::new(0.0, 0.0),
+            Vector2::new(1.0, 0.0),
+        ]);
+        assert!(blend_space.triangles().is_empty());
+
+        let weights = blend_space.fetch_weights(Vector2::new(1.5, 3.0));
+        assert!((weight_of(&weights, 2) - 0.5).abs() < 0.001);
+        assert!((weight_of(&weights, 0) - 0.5).abs() < 0.001);
+
+        let weights = blend_space.fetch_weights(Vector2::new(-1.0, 0.0));
+        assert!((weight_of(&weights, 1) - 1.0).abs() < 0.001);
+
+        assert!(make_blend_space(&[])
+            .fetch_weights(Vector2::default())
+            .is_empty());
+    }
+}
@@ -5,6 +5,7 @@ use crate::{
                 blend::{
                     BlendAnimations, BlendAnimationsByIndexDefinition, BlendAnimationsDefinition,
                 },
+                blend_space::{BlendSpace, BlendSpaceDefinition, BlendSpacePoint},
                 play::{PlayAnimation, PlayAnimationDefinition},
             },
             state::StateDefinition,
@@ -26,6 +27,7 @@ use std::{
 };
 
 pub mod blend;
+pub mod blend_space;
 pub mod play;
 
 #[derive(Debug, Visit, Clone, Default)]
@@ -44,6 +46,9 @@ pub enum PoseNode {
 
     /// See docs for `BlendAnimationsByIndex`.
     BlendAnimationsByIndex(BlendAnimationsByIndex),
+
+    /// See docs for `BlendSpace`.
+    BlendSpace(BlendSpace),
 }
 
 impl Default for PoseNode {
@@ -70,6 +75,11 @@ impl PoseNode {
     ) -> Self {
         Self::BlendAnimationsByIndex(BlendAnimationsByIndex::new(index_parameter, inputs))
     }
+
+    /// Creates new node that blends poses placed at points of 2D space.
+    pub fn make_blend_space(sampling_parameter: String, points: Vec<BlendSpacePoint>) -> Self {
+        Self::BlendSpace(BlendSpace::new(sampling_parameter, points))
+    }
 }
 
 macro_rules! static_dispatch {
@@ -78,6 +88,7 @@ macro_rules! static_dispatch {
             PoseNode::PlayAnimation(v) => v.$func($($args),*),
             PoseNode::BlendAnimations(v) => v.$func($($args),*),
             PoseNode::BlendAnimationsByIndex(v) => v.$func($($args),*),
+            PoseNode::BlendSpace(v) => v.$func($($args),*),
         }
     };
 }
@@ -136,6 +147,7 @@ pub enum PoseNodeDefinition {
     PlayAnimation(PlayAnimationDefinition),
     BlendAnimations(BlendAnimationsDefinition),
     BlendAnimationsByIndex(BlendAnimationsByIndexDefinition),
+    BlendSpace(BlendSpaceDefinition),
 }
 
 impl PoseNodeDefinition {
@@ -147,6 +159,7 @@ impl PoseNodeDefinition {
             }
             PoseNodeDefinition::BlendAnimations(definition) => definition.children(),
             PoseNodeDefinition::BlendAnimationsByIndex(definition) => definition.children(),
+            PoseNodeDefinition::BlendSpace(definition) => definition.children(),
         }
     }
 }
@@ -165,6 +178,7 @@ impl Deref for PoseNodeDefinition {
             PoseNodeDefinition::PlayAnimation(v) => v,
             PoseNodeDefinition::BlendAnimations(v) => v,
             PoseNodeDefinition::BlendAnimationsByIndex(v) => v,
+            PoseNodeDefinition::BlendSpace(v) => v,
         }
     }
 }
@@ -175,6 +189,7 @@ impl DerefMut for PoseNodeDefinition {
             PoseNodeDefinition::PlayAnimation(v) => v,
             PoseNodeDefinition::BlendAnimations(v) => v,
             PoseNodeDefinition::BlendAnimationsByIndex(v) => v,
+            PoseNodeDefinition::BlendSpace(v) => v,
         }
     }
 }
@@ -4,6 +4,7 @@ use crate::{
             node::{BasePoseNode, BasePoseNodeDefinition, EvaluatePose},
             ParameterContainer, PoseNode,
         },
+        root_motion::RootMotionSettings,
         Animation, AnimationContainer, AnimationPose,
     },
     core::{
@@ -12,6 +13,8 @@ use crate::{
         reflect::Reflect,
         visitor::prelude::*,
     },
+    scene::{graph::Graph, node::Node},
+    utils::log::Log,
 };
 use std::ops::Range;
 use std::{
@@ -45,6 +48,39 @@ impl DerefMut for PlayAnimation {
 #[derive(Default, Debug, Visit, Clone, Inspect, Reflect)]
 pub struct TimeSlice(pub Range<f32>);
 
+/// Definition of root motion settings. Unlike [`RootMotionSettings`] it uses a name of the root
+/// bone, which makes it independent of a particular model instance.
+#[derive(Default, Debug, Visit, Clone, Inspect, Reflect)]
+pub struct RootMotionSettingsDefinition {
+    #[inspect(description = "Name of a bone which movement will be extracted into root motion.")]
+    pub bone: String,
+    pub ignore_x_movement: bool,
+    pub ignore_y_movement: bool,
+    pub ignore_z_movement: bool,
+    pub ignore_rotations: bool,
+}
+
+impl RootMotionSettingsDefinition {
+    /// Creates root motion settings for a model hierarchy starting from the given root.
+    pub fn instantiate(&self, root: Handle<Node>, graph: &Graph) -> RootMotionSettings {
+        let node = graph.find_by_name(root, &self.bone);
+        if node.is_none() {
+            Log::warn(format!(
+                "Unable to find bone {} for root motion, root motion won't be extracted!",
+                self.bone
+            ));
+        }
+
+        RootMotionSettings {
+            node,
+            ignore_x_movement: self.ignore_x_movement,
+            ignore_y_movement: self.ignore_y_movement,
+            ignore_z_movement: self.ignore_z_movement,
+            ignore_rotations: self.ignore_rotations,
+        }
+    }
+}
+
 #[derive(Debug, Visit, Clone, Inspect, Reflect)]
 pub struct PlayAnimationDefinition {
     pub base: BasePoseNodeDefinition,
@@ -53,6 +89,8 @@ pub struct PlayAnimationDefinition {
     pub speed: f32,
     #[visit(optional)] // Backward compatibility
     pub time_slice: Option<TimeSlice>,
+    #[visit(optional)] // Backward compatibility
+    pub root_motion: Option<RootMotionSettingsDefinition>,
 }
 
 impl Default for PlayAnimationDefinition {
@@ -62,6 +100,7 @@ impl Default for PlayAnimationDefinition {
             animation: "".to_string(),
             speed: 1.0,
             time_slice: None,
+            root_motion: None,
         }
     }
 }
@@ -1,4 +1,5 @@
 use crate::core::{
+    algebra::Vector2,
     inspect::{Inspect, PropertyInfo},
     reflect::Reflect,
     visitor::prelude::*,
@@ -19,6 +20,10 @@ pub enum Parameter {
 
     /// An index of pose.
     Index(u32),
+
+    /// A point in 2D space, it is used to sample poses in BlendSpace node. For example it could be
+    /// a velocity of a character in its local space.
+    SamplingPoint(Vector2<f32>),
 }
 
 impl Default for Parameter {
@@ -44,6 +49,23 @@ impl Default for PoseWeight {
     }
 }
 
+impl PoseWeight {
+    /// Calculates actual value of the weight. Parametrized weight will be zero if there is no
+    /// Weight parameter with the given name.
+    pub fn value(&self, parameters: &ParameterContainer) -> f32 {
+        match self {
+            PoseWeight::Constant(value) => *value,
+            PoseWeight::Parameter(param_id) => {
+                if let Some(Parameter::Weight(weight)) = parameters.get(param_id) {
+                    *weight
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
 pub type ParameterContainer = FxHashMap<String, Parameter>;
 
 #[derive(Debug, Default, Visit, Clone, Inspect, Reflect)]
@@ -1,4 +1,8 @@
 use crate::{
+    animation::{
+        property::{PropertyPose, PropertyTrack},
+        root_motion::{RootMotion, RootMotionSettings},
+    },
     asset::ResourceState,
     core::{
         algebra::{UnitQuaternion, Vector3},
@@ -17,7 +21,10 @@ use std::{
     ops::{Index, IndexMut, Range},
 };
 
+pub mod ik;
 pub mod machine;
+pub mod property;
+pub mod root_motion;
 pub mod spritesheet;
 
 #[derive(Copy, Clone, Debug, Visit)]
@@ -271,10 +278,14 @@ impl Default for AnimationSignal {
 pub struct Animation {
     // TODO: Extract into separate struct AnimationTimeline
     tracks: Vec<Track>,
+    #[visit(optional)] // Backward compatibility
+    property_tracks: Vec<PropertyTrack>,
     length: f32,
     time_position: f32,
     #[visit(optional)] // Backward compatibility
     time_slice: Option<Range<f32>>,
+    #[visit(optional)] // Backward compatibility
+    root_motion_settings: Option<RootMotionSettings>,
     ///////////////////////////////////////////////////////
     speed: f32,
     looped: bool,
@@ -323,6 +334,41 @@ impl LocalPose {
         self.scale += other.scale.scale(weight);
     }
 
+    fn interpolate(&mut self, other: &LocalPose, weight: f32) {
+        self.position = self.position.lerp(&other.position, weight);
+        self.rotation = self.rotation.nlerp(&other.rotation, weight);
+        self.scale = self.scale.lerp(&other.scale, weight);
+    }
+
+    fn add_difference(&mut self, pose: &LocalPose, reference: Option<&LocalPose>, weight: f32) {
+        let (position, rotation, scale) = match reference {
+            Some(reference) => (
+                pose.position - reference.position,
+                reference.rotation.inverse() * pose.rotation,
+                pose.scale.zip_map(&reference.scale, |s, r| {
+                    if r.abs() > f32::EPSILON {
+                        s / r
+                    } else {
+                        1.0
+                    }
+                }),
+            ),
+            None => (pose.position, pose.rotation, pose.scale),
+        };
+
+        // Pick the shortest arc, otherwise partial weights will rotate the long way around.
+        let rotation = if rotation.w < 0.0 {
+            UnitQuaternion::new_unchecked(-rotation.into_inner())
+        } else {
+            rotation
+        };
+
+        self.position += position.scale(weight);
+        self.rotation *= UnitQuaternion::identity().nlerp(&rotation, weight);
+        self.scale
+            .component_mul_assign(&Vector3::repeat(1.0).lerp(&scale, weight));
+    }
+
     pub fn position(&self) -> Vector3<f32> {
         self.position
     }
@@ -339,6 +385,8 @@ impl LocalPose {
 #[derive(Default, Debug, Clone)]
 pub struct AnimationPose {
     local_poses: FxHashMap<Handle<Node>, LocalPose>,
+    property_poses: Vec<PropertyPose>,
+    root_motion: Option<RootMotion>,
 }
 
 impl AnimationPose {
@@ -347,6 +395,9 @@ impl AnimationPose {
         for (handle, local_pose) in self.local_poses.iter() {
             dest.local_poses.insert(*handle, local_pose.clone());
         }
+        dest.property_poses
+            .extend(self.property_poses.iter().cloned());
+        dest.root_motion = self.root_motion.clone();
     }
 
     pub fn blend_with(&mut self, other: &AnimationPose, weight: f32) {
@@ -359,14 +410,150 @@ impl AnimationPose {
                 self.add_local_pose(other_pose.weighted_clone(weight));
             }
         }
+
+        for other_pose in other.property_poses.iter() {
+            if let Some(current_pose) = self
+                .property_poses
+                .iter_mut()
+                .find(|p| p.is_same_property(other_pose))
+            {
+                current_pose.blend_with(other_pose, weight);
+            } else {
+                self.property_poses.push(other_pose.weighted_clone(weight));
+            }
+        }
+
+        if let Some(other_root_motion) = other.root_motion.as_ref() {
+            self.root_motion
+                .get_or_insert_with(Default::default)
+                .blend_with(other_root_motion, weight);
+        }
+    }
+
+    /// Interpolates the pose of every node that passes the filter towards the respective pose
+    /// of the other animation pose. Nodes that are not in this pose yet take the pose from the
+    /// other animation pose as is. Root motion is interpolated regardless of the filter.
+    pub fn override_with<F>(&mut self, other: &AnimationPose, weight: f32, mut filter: F)
+    where
+        F: FnMut(Handle<Node>) -> bool,
+    {
+        for (handle, other_pose) in other.local_poses.iter() {
+            if !filter(*handle) {
+                continue;
+            }
+
+            if let Some(current_pose) = self.local_poses.get_mut(handle) {
+                current_pose.interpolate(other_pose, weight);
+            } else {
+                self.add_local_pose(other_pose.clone());
+            }
+        }
+
+        for other_pose in other.property_poses.iter() {
+            if !filter(other_pose.node()) {
+                continue;
+            }
+
+            if let Some(current_pose) = self
+                .property_poses
+                .iter_mut()
+                .find(|p| p.is_same_property(other_pose))
+            {
+                current_pose.interpolate(other_pose, weight);
+            } else {
+                self.property_poses.push(other_pose.clone());
+            }
+        }
+
+        if let Some(other_root_motion) = other.root_motion.as_ref() {
+            if let Some(root_motion) = self.root_motion.as_mut() {
+                root_motion.interpolate(other_root_motion, weight);
+            } else {
+                self.root_motion = Some(other_root_motion.clone());
+            }
+        }
+    }
+
+    /// Adds the difference between the other animation pose and the reference pose on top of
+    /// this pose for every node that passes the filter: positions are added, rotations and scales
+    /// are multiplied, property values are added. Nodes and properties that are missing in the
+    /// reference pose use identity as the reference. Nodes that are not in this pose are ignored.
+    /// Root motion of the other pose is ignored too.
+    pub fn add_additive<F>(
+        &mut self,
+        other: &AnimationPose,
+        reference: &AnimationPose,
+        weight: f32,
+        mut filter: F,
+    ) where
+        F: FnMut(Handle<Node>) -> bool,
+    {
+        for (handle, other_pose) in other.local_poses.iter() {
+            if !filter(*handle) {
+                continue;
+            }
+
+            if let Some(current_pose) = self.local_poses.get_mut(handle) {
+                current_pose.add_difference(other_pose, reference.local_poses.get(handle), weight);
+            }
+        }
+
+        for other_pose in other.property_poses.iter() {
+            if !filter(other_pose.node()) {
+                continue;
+            }
+
+            if let Some(current_pose) = self
+                .property_poses
+                .iter_mut()
+                .find(|p| p.is_same_property(other_pose))
+            {
+                let reference_pose = reference
+                    .property_poses
+                    .iter()
+                    .find(|p| p.is_same_property(other_pose));
+                current_pose.add_difference(other_pose, reference_pose, weight);
+            }
+        }
+    }
+
+    /// Adds poses of the nodes and the properties of the other pose that are not in this pose.
+    fn extend_missing(&mut self, other: &AnimationPose) {
+        for (handle, other_pose) in other.local_poses.iter() {
+            self.local_poses
+                .entry(*handle)
+                .or_insert_with(|| other_pose.clone());
+        }
+
+        for other_pose in other.property_poses.iter() {
+            if !self
+                .property_poses
+                .iter()
+                .any(|p| p.is_same_property(other_pose))
+            {
+                self.property_poses.push(other_pose.clone());
+            }
+        }
     }
 
     fn add_local_pose(&mut self, local_pose: LocalPose) {
         self.local_poses.insert(local_pose.node, local_pose);
     }
 
+    /// Returns animated property values of the pose, see [`PropertyTrack`] docs for more info.
+    pub fn property_poses(&self) -> &[PropertyPose] {
+        &self.property_poses
+    }
+
+    /// Returns root motion of the pose (if any), see [`RootMotionSettings`] docs for more info.
+    pub fn root_motion(&self) -> Option<&RootMotion> {
+        self.root_motion.as_ref()
+    }
+
     pub fn reset(&mut self) {
         self.local_poses.clear();
+        self.property_poses.clear();
+        self.root_motion = None;
     }
 
     pub fn apply(&self, graph: &mut Graph) {
@@ -381,10 +568,18 @@ impl AnimationPose {
                     .set_scale(local_pose.scale);
             }
         }
+
+        for property_pose in self.property_poses.iter() {
+            if let Some(node) = graph.try_get_mut(property_pose.node()) {
+                property_pose.apply(node);
+            }
+        }
     }
 
     /// Calls given callback function for each node and allows you to apply pose with your own
     /// rules. This could be useful if you need to ignore transform some part of pose for a node.
+    /// Keep in mind that animated properties are not applied by this method, use
+    /// [`Self::property_poses`] to apply them manually.
     pub fn apply_with<C>(&self, graph: &mut Graph, mut callback: C)
     where
         C: FnMut(&mut Node, Handle<Node>, &LocalPose),
@@ -403,6 +598,7 @@ impl Clone for Animation {
     fn clone(&self) -> Self {
         Self {
             tracks: self.tracks.clone(),
+            property_tracks: self.property_tracks.clone(),
             speed: self.speed,
             length: self.length,
             time_position: self.time_position,
@@ -413,6 +609,7 @@ impl Clone for Animation {
             signals: self.signals.clone(),
             events: Default::default(),
             time_slice: self.time_slice.clone(),
+            root_motion_settings: self.root_motion_settings.clone(),
         }
     }
 }
@@ -432,11 +629,32 @@ impl Animation {
         &self.tracks
     }
 
+    /// Adds new property track to the animation, see [`PropertyTrack`] docs for more info.
+    pub fn add_property_track(&mut self, track: PropertyTrack) {
+        self.length = self.length.max(track.max_time());
+        self.property_tracks.push(track);
+    }
+
+    /// Returns a reference to property tracks of the animation.
+    pub fn property_tracks(&self) -> &[PropertyTrack] {
+        &self.property_tracks
+    }
+
+    /// Returns a mutable reference to property tracks of the animation.
+    pub fn property_tracks_mut(&mut self) -> &mut [PropertyTrack] {
+        &mut self.property_tracks
+    }
+
+    /// Removes every property track that does not satisfy given predicate.
+    pub fn retain_property_tracks<F>(&mut self, filter: F)
+    where
+        F: FnMut(&PropertyTrack) -> bool,
+    {
+        self.property_tracks.retain(filter)
+    }
+
     pub fn set_time_position(&mut self, time: f32) -> &mut Self {
-        let time_slice = self.time_slice.clone().unwrap_or(Range {
-            start: 0.0,
-            end: self.length,
-        });
+        let time_slice = self.time_slice_or_full();
 
         if self.looped {
             self.time_position = wrapf(time, time_slice.start, time_slice.end);
@@ -458,6 +676,35 @@ impl Animation {
         self.set_time_position(self.time_position);
     }
 
+    /// Sets new root motion settings of the animation, `None` disables root motion extraction.
+    /// See [`RootMotionSettings`] docs for more info.
+    pub fn set_root_motion_settings(&mut self, settings: Option<RootMotionSettings>) {
+        self.root_motion_settings = settings;
+    }
+
+    /// Returns a reference to root motion settings of the animation.
+    pub fn root_motion_settings(&self) -> Option<&RootMotionSettings> {
+        self.root_motion_settings.as_ref()
+    }
+
+    /// Returns a mutable reference to root motion settings of the animation.
+    pub fn root_motion_settings_mut(&mut self) -> Option<&mut RootMotionSettings> {
+        self.root_motion_settings.as_mut()
+    }
+
+    /// Returns root motion extracted on the last update of the animation. It is `None` if the
+    /// animation has no root motion settings.
+    pub fn root_motion(&self) -> Option<&RootMotion> {
+        self.pose.root_motion()
+    }
+
+    fn time_slice_or_full(&self) -> Range<f32> {
+        self.time_slice.clone().unwrap_or(Range {
+            start: 0.0,
+            end: self.length,
+        })
+    }
+
     pub fn rewind(&mut self) -> &mut Self {
         self.set_time_position(0.0)
     }
@@ -487,9 +734,81 @@ impl Animation {
             }
         }
 
+        self.update_root_motion(current_time_position, new_time_position);
+
         self.set_time_position(new_time_position);
     }
 
+    fn update_root_motion(&mut self, current_time_position: f32, new_time_position: f32) {
+        let settings = match self.root_motion_settings.as_ref() {
+            Some(settings) => settings,
+            None => return,
+        };
+
+        let track = match self.tracks.iter().find(|t| t.node == settings.node) {
+            Some(track) if track.is_enabled() => track,
+            _ => return,
+        };
+
+        let time_slice = self.time_slice_or_full();
+
+        let sample = |time: f32| {
+            track
+                .get_local_pose(time)
+                .map(|pose| (pose.position, pose.rotation))
+                .unwrap_or_else(|| (Vector3::default(), UnitQuaternion::identity()))
+        };
+
+        let motion_between = |from: f32, to: f32| {
+            let (from_position, from_rotation) = sample(from);
+            let (to_position, to_rotation) = sample(to);
+            RootMotion {
+                delta_position: settings.mask_position(to_position - from_position),
+                delta_rotation: if settings.ignore_rotations {
+                    UnitQuaternion::identity()
+                } else {
+                    from_rotation.inverse() * to_rotation
+                },
+            }
+        };
+
+        let root_motion = if self.looped && new_time_position > time_slice.end {
+            // Wrapped forward, take the motion till the end and then from the beginning.
+            let wrapped = wrapf(new_time_position, time_slice.start, time_slice.end);
+            motion_between(current_time_position, time_slice.end)
+                .chain(&motion_between(time_slice.start, wrapped))
+        } else if self.looped && new_time_position < time_slice.start {
+            // Wrapped backward.
+            let wrapped = wrapf(new_time_position, time_slice.start, time_slice.end);
+            motion_between(current_time_position, time_slice.start)
+                .chain(&motion_between(time_slice.end, wrapped))
+        } else {
+            motion_between(
+                current_time_position,
+                new_time_position.clamp(time_slice.start, time_slice.end),
+            )
+        };
+
+        // Remove extracted motion from the pose, the root node must stay at its initial location.
+        let (start_position, start_rotation) = sample(time_slice.start);
+        if let Some(local_pose) = self.pose.local_poses.get_mut(&settings.node) {
+            if !settings.ignore_x_movement {
+                local_pose.position.x = start_position.x;
+            }
+            if !settings.ignore_y_movement {
+                local_pose.position.y = start_position.y;
+            }
+            if !settings.ignore_z_movement {
+                local_pose.position.z = start_position.z;
+            }
+            if !settings.ignore_rotations {
+                local_pose.rotation = start_rotation;
+            }
+        }
+
+        self.pose.root_motion = Some(root_motion);
+    }
+
     pub fn pop_event(&mut self) -> Option<AnimationEvent> {
         self.events.pop_front()
     }
@@ -676,14 +995,35 @@ impl Animation {
     }
 
     fn update_pose(&mut self) {
-        self.pose.reset();
+        let mut pose = std::mem::take(&mut self.pose);
+        self.sample_pose(self.time_position, &mut pose);
+        self.pose = pose;
+    }
+
+    /// Writes the pose of the animation at the given time to `pose`, the state of the animation
+    /// is not changed. Root motion is not extracted.
+    pub fn sample_pose(&self, time: f32, pose: &mut AnimationPose) {
+        pose.reset();
         for track in self.tracks.iter() {
             if track.is_enabled() {
-                if let Some(local_pose) = track.get_local_pose(self.time_position) {
-                    self.pose.add_local_pose(local_pose);
+                if let Some(local_pose) = track.get_local_pose(time) {
+                    pose.add_local_pose(local_pose);
                 }
             }
         }
+        for track in self.property_tracks.iter() {
+            if track.is_enabled() {
+                if let Some(property_pose) = track.fetch_pose(time) {
+                    pose.property_poses.push(property_pose);
+                }
+            }
+        }
+    }
+
+    /// Writes the pose of the first frame of the animation (the start of its time slice) to
+    /// `pose`.
+    pub fn sample_first_frame(&self, pose: &mut AnimationPose) {
+        self.sample_pose(self.time_slice_or_full().start, pose)
     }
 
     pub fn get_pose(&self) -> &AnimationPose {
@@ -695,6 +1035,7 @@ impl Default for Animation {
     fn default() -> Self {
         Self {
             tracks: Vec::new(),
+            property_tracks: Vec::new(),
             speed: 1.0,
             length: 0.0,
             time_position: 0.0,
@@ -705,6 +1046,7 @@ impl Default for Animation {
             signals: Default::default(),
             events: Default::default(),
             time_slice: Default::default(),
+            root_motion_settings: Default::default(),
         }
     }
 }
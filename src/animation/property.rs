@@ -0,0 +1,436 @@
+//! Property tracks allow you to animate any property of a scene node that can be reached using
+//! reflection, not only its local transform. For example it could be a color of a light, a field
+//! of view of a camera, a property of a script, etc.
+//!
+//! # Property paths
+//!
+//! Property of a node is defined by its path in terms of [`Reflect`] API, see
+//! [`crate::core::reflect::ResolvePath`] for more info. Some examples of valid paths:
+//!
+//! - `base_light.color` - color of a point, spot or directional light.
+//! - `base_light.intensity` - intensity of a point, spot or directional light.
+//! - `projection.Perspective@0.fov` - field of view of a camera with perspective projection.
+//!
+//! # Key frames
+//!
+//! Each track stores a set of curves, one per each component of the animated value, for example
+//! `Vector3` values have three curves (X, Y, Z) and colors have four (R, G, B, A). This allows you
+//! to use every interpolation mode supported by [`Curve`] (constant, linear, cubic) and to edit
+//! each component separately if needed. Use [`PropertyTrack::add_key_frame`] to add key frames
+//! in a type-safe manner.
+//!
+//! # Example
+//!
+//! ```rust
+//! use fyrox::{
+//!     animation::{
+//!         property::{PropertyTrack, PropertyValue, PropertyValueKind},
+//!         Animation,
+//!     },
+//!     core::{color::Color, curve::CurveKeyKind, pool::Handle},
+//!     scene::node::Node,
+//! };
+//!
+//! fn create_blinking_light(light: Handle<Node>) -> Animation {
+//!     let mut track = PropertyTrack::new(light, "base_light.color", PropertyValueKind::Color);
+//!     track.add_key_frame(0.0, PropertyValue::Color(Color::WHITE), CurveKeyKind::Linear);
+//!     track.add_key_frame(0.5, PropertyValue::Color(Color::RED), CurveKeyKind::Linear);
+//!     track.add_key_frame(1.0, PropertyValue::Color(Color::WHITE), CurveKeyKind::Linear);
+//!
+//!     let mut animation = Animation::default();
+//!     animation.add_property_track(track);
+//!     animation
+//! }
+//! ```
+
+#![warn(missing_docs)]
+
+use crate::{
+    core::{
+        algebra::{Vector2, Vector3, Vector4},
+        color::Color,
+        curve::{Curve, CurveKey, CurveKeyKind},
+        pool::Handle,
+        reflect::{Reflect, ResolvePath},
+        visitor::prelude::*,
+    },
+    scene::node::Node,
+    utils::log::Log,
+};
+
+/// Type of a value that is animated by a property track.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Visit)]
+pub enum PropertyValueKind {
+    /// Single `f32` value.
+    Real,
+    /// `Vector2<f32>` value.
+    Vector2,
+    /// `Vector3<f32>` value.
+    Vector3,
+    /// `Vector4<f32>` value.
+    Vector4,
+    /// [`Color`] value.
+    Color,
+}
+
+impl Default for PropertyValueKind {
+    fn default() -> Self {
+        Self::Real
+    }
+}
+
+impl PropertyValueKind {
+    /// Returns total amount of components (and therefore curves) of a value of this kind.
+    pub fn components_count(self) -> usize {
+        match self {
+            PropertyValueKind::Real => 1,
+            PropertyValueKind::Vector2 => 2,
+            PropertyValueKind::Vector3 => 3,
+            PropertyValueKind::Vector4 | PropertyValueKind::Color => 4,
+        }
+    }
+}
+
+/// Typed value of an animated property.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PropertyValue {
+    /// Single `f32` value.
+    Real(f32),
+    /// `Vector2<f32>` value.
+    Vector2(Vector2<f32>),
+    /// `Vector3<f32>` value.
+    Vector3(Vector3<f32>),
+    /// `Vector4<f32>` value.
+    Vector4(Vector4<f32>),
+    /// [`Color`] value.
+    Color(Color),
+}
+
+impl PropertyValue {
+    /// Returns kind of the value.
+    pub fn kind(&self) -> PropertyValueKind {
+        match self {
+            PropertyValue::Real(_) => PropertyValueKind::Real,
+            PropertyValue::Vector2(_) => PropertyValueKind::Vector2,
+            PropertyValue::Vector3(_) => PropertyValueKind::Vector3,
+            PropertyValue::Vector4(_) => PropertyValueKind::Vector4,
+            PropertyValue::Color(_) => PropertyValueKind::Color,
+        }
+    }
+
+    /// Returns components of the value packed in a 4D vector, unused components are zero. Colors
+    /// are converted to `[0; 1]` range.
+    pub fn components(&self) -> Vector4<f32> {
+        match *self {
+            PropertyValue::Real(v) => Vector4::new(v, 0.0, 0.0, 0.0),
+            PropertyValue::Vector2(v) => Vector4::new(v.x, v.y, 0.0, 0.0),
+            PropertyValue::Vector3(v) => Vector4::new(v.x, v.y, v.z, 0.0),
+            PropertyValue::Vector4(v) => v,
+            PropertyValue::Color(v) => v.as_frgba(),
+        }
+    }
+
+    /// Creates a value of given kind from its components. It is the inverse of
+    /// [`Self::components`].
+    pub fn from_components(kind: PropertyValueKind, components: Vector4<f32>) -> Self {
+        match kind {
+            PropertyValueKind::Real => PropertyValue::Real(components.x),
+            PropertyValueKind::Vector2 => PropertyValue::Vector2(components.xy()),
+            PropertyValueKind::Vector3 => PropertyValue::Vector3(components.xyz()),
+            PropertyValueKind::Vector4 => PropertyValue::Vector4(components),
+            PropertyValueKind::Color => {
+                let to_u8 = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+                PropertyValue::Color(Color::from_rgba(
+                    to_u8(components.x),
+                    to_u8(components.y),
+                    to_u8(components.z),
+                    to_u8(components.w),
+                ))
+            }
+        }
+    }
+
+    fn into_boxed_reflect(self) -> Box<dyn Reflect> {
+        match self {
+            PropertyValue::Real(v) => Box::new(v),
+            PropertyValue::Vector2(v) => Box::new(v),
+            PropertyValue::Vector3(v) => Box::new(v),
+            PropertyValue::Vector4(v) => Box::new(v),
+            PropertyValue::Color(v) => Box::new(v),
+        }
+    }
+}
+
+/// A track that animates a property of a node, defined by its reflection path. See module docs
+/// for more info.
+#[derive(Clone, Debug, Visit)]
+pub struct PropertyTrack {
+    node: Handle<Node>,
+    property: String,
+    kind: PropertyValueKind,
+    curves: Vec<Curve>,
+    enabled: bool,
+}
+
+impl Default for PropertyTrack {
+    fn default() -> Self {
+        Self {
+            node: Default::default(),
+            property: Default::default(),
+            kind: Default::default(),
+            curves: Default::default(),
+            enabled: true,
+        }
+    }
+}
+
+impl PropertyTrack {
+    /// Creates new empty track that animates a property with given path of a given node.
+    pub fn new<P: Into<String>>(node: Handle<Node>, property: P, kind: PropertyValueKind) -> Self {
+        Self {
+            node,
+            property: property.into(),
+            kind,
+            curves: vec![Curve::default(); kind.components_count()],
+            enabled: true,
+        }
+    }
+
+    /// Sets new node which property will be animated by the track.
+    pub fn set_node(&mut self, node: Handle<Node>) {
+        self.node = node;
+    }
+
+    /// Returns a handle of node which property is animated by the track.
+    pub fn node(&self) -> Handle<Node> {
+        self.node
+    }
+
+    /// Returns reflection path of the animated property.
+    pub fn property(&self) -> &str {
+        &self.property
+    }
+
+    /// Returns kind of the animated value.
+    pub fn kind(&self) -> PropertyValueKind {
+        self.kind
+    }
+
+    /// Enables or disables the track. Disabled tracks won't be evaluated.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Returns `true` if the track is enabled, `false` - otherwise.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Adds new key frame at given time, `interpolation` defines how the value will be
+    /// interpolated between the new key and the next one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the kind of the value does not match the kind of the track.
+    pub fn add_key_frame(&mut self, time: f32, value: PropertyValue, interpolation: CurveKeyKind) {
+        assert_eq!(value.kind(), self.kind);
+
+        let components = value.components();
+        for (i, curve) in self.curves.iter_mut().enumerate() {
+            curve.add_key(CurveKey::new(time, components[i], interpolation.clone()));
+        }
+    }
+
+    /// Returns a curve per each component of the animated value.
+    pub fn curves(&self) -> &[Curve] {
+        &self.curves
+    }
+
+    /// Returns a curve per each component of the animated value. Could be used to edit each
+    /// component separately.
+    pub fn curves_mut(&mut self) -> &mut [Curve] {
+        &mut self.curves
+    }
+
+    /// Returns time of the last key frame of the track.
+    pub fn max_time(&self) -> f32 {
+        self.curves
+            .iter()
+            .filter_map(|c| c.keys().last())
+            .map(|k| k.location())
+            .fold(0.0, f32::max)
+    }
+
+    /// Calculates value of the property at given time. Returns `None` if the track has no keys.
+    pub fn fetch(&self, time: f32) -> Option<PropertyValue> {
+        if self.curves.iter().all(|c| c.is_empty()) {
+            return None;
+        }
+
+        let mut components = Vector4::default();
+        for (i, curve) in self.curves.iter().enumerate().take(4) {
+            components[i] = curve.value_at(time);
+        }
+
+        Some(PropertyValue::from_components(self.kind, components))
+    }
+
+    pub(super) fn fetch_pose(&self, time: f32) -> Option<PropertyPose> {
+        self.fetch(time).map(|value| PropertyPose {
+            node: self.node,
+            property: self.property.clone(),
+            kind: self.kind,
+            components: value.components(),
+        })
+    }
+}
+
+/// Snapshot of a value of an animated property.
+#[derive(Clone, Debug)]
+pub struct PropertyPose {
+    node: Handle<Node>,
+    property: String,
+    kind: PropertyValueKind,
+    components: Vector4<f32>,
+}
+
+impl PropertyPose {
+    pub(super) fn weighted_clone(&self, weight: f32) -> Self {
+        Self {
+            node: self.node,
+            property: self.property.clone(),
+            kind: self.kind,
+            components: self.components.scale(weight),
+        }
+    }
+
+    pub(super) fn blend_with(&mut self, other: &PropertyPose, weight: f32) {
+        if self.kind == other.kind {
+            self.components += other.components.scale(weight);
+        }
+    }
+
+    pub(super) fn add_difference(
+        &mut self,
+        other: &PropertyPose,
+        reference: Option<&PropertyPose>,
+        weight: f32,
+    ) {
+        if self.kind == other.kind {
+            let difference = match reference {
+                Some(reference) if reference.kind == other.kind => {
+                    other.components - reference.components
+                }
+                _ => other.components,
+            };
+            self.components += difference.scale(weight);
+        }
+    }
+
+    pub(super) fn interpolate(&mut self, other: &PropertyPose, weight: f32) {
+        if self.kind == other.kind {
+            self.components = self.components.lerp(&other.components, weight);
+        }
+    }
+
+    pub(super) fn is_same_property(&self, other: &PropertyPose) -> bool {
+        self.node == other.node && self.property == other.property
+    }
+
+    /// Returns a handle of node which property is animated.
+    pub fn node(&self) -> Handle<Node> {
+        self.node
+    }
+
+    /// Returns reflection path of the animated property.
+    pub fn property(&self) -> &str {
+        &self.property
+    }
+
+    /// Returns value of the property.
+    pub fn value(&self) -> PropertyValue {
+        PropertyValue::from_components(self.kind, self.components)
+    }
+
+    /// Sets the value of the property to the given node.
+    pub fn apply(&self, node: &mut Node) {
+        match node.as_reflect_mut().resolve_path_mut(&self.property) {
+            Ok(field) => {
+                if field.set(self.value().into_boxed_reflect()).is_err() {
+                    Log::err(format!(
+                        "Unable to animate property {} of node {}: type mismatch, expected {:?}!",
+                        self.property,
+                        node.name(),
+                        self.kind
+                    ));
+                }
+            }
+            Err(err) => Log::err(format!(
+                "Unable to animate property {} of node {}: {:?}",
+                self.property,
+                node.name(),
+                err
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::property::{PropertyTrack, PropertyValue, PropertyValueKind},
+        core::{algebra::Vector3, color::Color, curve::CurveKeyKind, pool::Handle},
+        scene::{
+            base::BaseBuilder,
+            light::{point::PointLightBuilder, BaseLight, BaseLightBuilder},
+        },
+    };
+
+    #[test]
+    fn test_property_track_fetch() {
+        let mut track = PropertyTrack::new(Handle::NONE, "position", PropertyValueKind::Vector3);
+        assert_eq!(track.fetch(0.0), None);
+
+        track.add_key_frame(
+            0.0,
+            PropertyValue::Vector3(Vector3::new(0.0, 0.0, 0.0)),
+            CurveKeyKind::Linear,
+        );
+        track.add_key_frame(
+            2.0,
+            PropertyValue::Vector3(Vector3::new(2.0, 4.0, -2.0)),
+            CurveKeyKind::Linear,
+        );
+
+        assert_eq!(track.max_time(), 2.0);
+        assert_eq!(
+            track.fetch(1.0),
+            Some(PropertyValue::Vector3(Vector3::new(1.0, 2.0, -1.0)))
+        );
+        assert_eq!(
+            track.fetch(3.0),
+            Some(PropertyValue::Vector3(Vector3::new(2.0, 4.0, -2.0)))
+        );
+    }
+
+    #[test]
+    fn test_property_pose_apply() {
+        let mut node =
+            PointLightBuilder::new(BaseLightBuilder::new(BaseBuilder::new())).build_node();
+
+        let mut track =
+            PropertyTrack::new(Handle::NONE, "base_light.color", PropertyValueKind::Color);
+        track.add_key_frame(
+            0.0,
+            PropertyValue::Color(Color::RED),
+            CurveKeyKind::Constant,
+        );
+
+        track.fetch_pose(0.0).unwrap().apply(&mut node);
+
+        assert_eq!(
+            node.query_component_ref::<BaseLight>().unwrap().color(),
+            Color::RED
+        );
+    }
+}
@@ -0,0 +1,198 @@
+//! Root motion is a movement of a root node of a character (usually hips bone) extracted from an
+//! animation. See [`RootMotionSettings`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{UnitQuaternion, Vector3},
+        pool::Handle,
+        visitor::prelude::*,
+    },
+    scene::node::Node,
+};
+
+/// Defines which node of an animation is the root node and which parts of its movement should be
+/// extracted into root motion.
+///
+/// Usually animations of a character move its root node (hips bone for example), which means that
+/// the character's mesh will "run away" from its physical body while playing walk animation. Root
+/// motion solves this issue - movement of the root node is removed from the animation pose and
+/// exposed as [`RootMotion`], so a game can apply it to a rigid body or a character controller.
+/// This way movement of the character will perfectly match its animation (no foot sliding).
+///
+/// Extracted movement is removed from the pose of the root node, the node stays at the position
+/// and rotation of the first frame of the animation. Ignored parts of the movement are kept in
+/// the pose as is. Typical setup for walking characters is to ignore Y movement, because vertical
+/// movement is usually handled by physics.
+#[derive(Default, Debug, Clone, PartialEq, Visit)]
+pub struct RootMotionSettings {
+    /// A handle to a node which movement will be extracted.
+    pub node: Handle<Node>,
+    /// Keep movement along X axis in the pose.
+    pub ignore_x_movement: bool,
+    /// Keep movement along Y axis in the pose.
+    pub ignore_y_movement: bool,
+    /// Keep movement along Z axis in the pose.
+    pub ignore_z_movement: bool,
+    /// Keep rotations in the pose.
+    pub ignore_rotations: bool,
+}
+
+impl RootMotionSettings {
+    pub(super) fn mask_position(&self, mut position: Vector3<f32>) -> Vector3<f32> {
+        if self.ignore_x_movement {
+            position.x = 0.0;
+        }
+        if self.ignore_y_movement {
+            position.y = 0.0;
+        }
+        if self.ignore_z_movement {
+            position.z = 0.0;
+        }
+        position
+    }
+}
+
+/// Movement of a root node of an animation between two successive updates. Position delta is
+/// defined in local coordinates of the parent of the root node, so usually it should be transformed
+/// by world rotation of a character before it is applied to its rigid body.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RootMotion {
+    /// Position offset of the root node.
+    pub delta_position: Vector3<f32>,
+    /// Rotation offset of the root node.
+    pub delta_rotation: UnitQuaternion<f32>,
+}
+
+impl Default for RootMotion {
+    fn default() -> Self {
+        Self {
+            delta_position: Default::default(),
+            delta_rotation: UnitQuaternion::identity(),
+        }
+    }
+}
+
+impl RootMotion {
+    /// Combines two successive root motions into one.
+    pub(super) fn chain(&self, next: &RootMotion) -> RootMotion {
+        RootMotion {
+            delta_position: self.delta_position + next.delta_position,
+            delta_rotation: self.delta_rotation * next.delta_rotation,
+        }
+    }
+
+    /// Adds weighted root motion to this one.
+    pub fn blend_with(&mut self, other: &RootMotion, weight: f32) {
+        self.delta_position += other.delta_position.scale(weight);
+        self.delta_rotation *= UnitQuaternion::identity().nlerp(&other.shortest_rotation(), weight);
+    }
+
+    /// Interpolates this root motion towards the other one.
+    pub fn interpolate(&mut self, other: &RootMotion, weight: f32) {
+        self.delta_position = self.delta_position.lerp(&other.delta_position, weight);
+        self.delta_rotation = self
+            .delta_rotation
+            .nlerp(&other.shortest_rotation(), weight);
+    }
+
+    fn shortest_rotation(&self) -> UnitQuaternion<f32> {
+        if self.delta_rotation.w < 0.0 {
+            UnitQuaternion::new_unchecked(-self.delta_rotation.into_inner())
+        } else {
+            self.delta_rotation
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::{
+            root_motion::{RootMotion, RootMotionSettings},
+            Animation, KeyFrame, Track,
+        },
+        core::{
+            algebra::{UnitQuaternion, Vector3},
+            pool::Handle,
+        },
+    };
+
+    fn make_animation() -> Animation {
+        let node = Handle::new(1, 1);
+
+        let mut track = Track::new();
+        track.set_node(node);
+        track.add_key_frame(KeyFrame::new(
+            0.0,
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 1.0, 1.0),
+            UnitQuaternion::identity(),
+        ));
+        track.add_key_frame(KeyFrame::new(
+            1.0,
+            Vector3::new(0.0, 1.0, 2.0),
+            Vector3::new(1.0, 1.0, 1.0),
+            UnitQuaternion::identity(),
+        ));
+
+        let mut animation = Animation::default();
+        animation.add_track(track);
+        animation.set_root_motion_settings(Some(RootMotionSettings {
+            node,
+            ignore_y_movement: true,
+            ..Default::default()
+        }));
+        animation
+    }
+
+    #[test]
+    fn test_root_motion_extraction() {
+        let mut animation = make_animation();
+
+        animation.tick(0.5);
+
+        let root_motion = animation.root_motion().unwrap();
+        assert!((root_motion.delta_position - Vector3::new(0.0, 0.0, 1.0)).norm() < 0.001);
+
+        // Extracted movement must be removed from the pose, ignored must be kept.
+        let local_pose = &animation.get_pose().local_poses[&Handle::new(1, 1)];
+        assert!((local_pose.position() - Vector3::new(0.0, 0.0, 0.0)).norm() < 0.001);
+
+        animation.tick(0.5);
+        let local_pose = &animation.get_pose().local_poses[&Handle::new(1, 1)];
+        assert!((local_pose.position() - Vector3::new(0.0, 0.5, 0.0)).norm() < 0.001);
+    }
+
+    #[test]
+    fn test_root_motion_loop_wrap() {
+        let mut animation = make_animation();
+        animation.set_loop(true);
+        animation.set_time_position(0.75);
+
+        // Goes from 0.75 to 1.0 and then from 0.0 to 0.25.
+        animation.tick(0.5);
+
+        let root_motion = animation.root_motion().unwrap();
+        assert!((root_motion.delta_position - Vector3::new(0.0, 0.0, 1.0)).norm() < 0.001);
+    }
+
+    #[test]
+    fn test_root_motion_blending() {
+        let mut a = RootMotion {
+            delta_position: Vector3::new(1.0, 0.0, 0.0),
+            ..Default::default()
+        };
+        let b = RootMotion {
+            delta_position: Vector3::new(0.0, 0.0, 1.0),
+            ..Default::default()
+        };
+
+        let mut blended = RootMotion::default();
+        blended.blend_with(&a, 0.5);
+        blended.blend_with(&b, 0.5);
+        assert!((blended.delta_position - Vector3::new(0.5, 0.0, 0.5)).norm() < 0.001);
+
+        a.interpolate(&b, 1.0);
+        assert_eq!(a, b);
+    }
+}
@@ -95,7 +95,6 @@ impl Executor {
         let event_loop = self.event_loop;
 
         let clock = Instant::now();
-        let fixed_timestep = 1.0 / 60.0;
         let mut elapsed_time = 0.0;
 
         let args = Args::parse();
@@ -121,6 +120,8 @@ impl Executor {
         engine.enable_plugins(override_scene, true);
 
         event_loop.run(move |event, _, control_flow| {
+            let fixed_timestep = engine.fixed_timestep();
+
             engine.handle_os_event_by_plugins(&event, fixed_timestep, control_flow);
 
             let scenes = engine
@@ -139,13 +140,13 @@ impl Executor {
 
             match event {
                 Event::MainEventsCleared => {
-                    let mut dt = clock.elapsed().as_secs_f32() - elapsed_time;
-                    while dt >= fixed_timestep {
-                        dt -= fixed_timestep;
-                        elapsed_time += fixed_timestep;
+                    // Fixed updates are performed by the engine, it catches up with real time
+                    // using an accumulator.
+                    let time = clock.elapsed().as_secs_f32();
+                    let dt = time - elapsed_time;
+                    elapsed_time = time;
 
-                        engine.update(fixed_timestep, control_flow);
-                    }
+                    engine.update(dt, control_flow);
 
                     while let Some(_ui_event) = engine.user_interface.poll_message() {}
 
@@ -0,0 +1,145 @@
+//! Headless renderer allows you to render scenes without a window, which is useful for automated
+//! visual tests (golden-image tests) on machines without a display, offline rendering, etc.
+//!
+//! # Contexts
+//!
+//! Headless renderer can be created using one of two kinds of OpenGL contexts:
+//!
+//! - Software OSMesa context (Linux and BSD only) - does not need an event loop and a display
+//!   server and works on machines without a GPU, but requires `libOSMesa` to be installed. See
+//!   [`HeadlessRenderer::new`].
+//! - Hidden context created from an event loop - uses the GPU (or whatever OpenGL implementation
+//!   is installed) and works on every desktop platform, but requires a display server. See
+//!   [`HeadlessRenderer::from_event_loop`].
+//!
+//! In both cases the scenes are rendered into an offscreen frame buffer and then read back into
+//! an image, the context itself is never presented.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use fyrox::{
+//!     core::algebra::Vector2,
+//!     engine::{
+//!         headless::HeadlessRenderer, resource_manager::ResourceManager, SerializationContext,
+//!     },
+//!     scene::Scene,
+//! };
+//! use std::sync::Arc;
+//!
+//! let resource_manager = ResourceManager::new(Arc::new(SerializationContext::new()));
+//! let mut renderer = HeadlessRenderer::new(&resource_manager, (320, 240)).unwrap();
+//!
+//! let mut scene = Scene::new();
+//! // Add a camera and some objects to the scene here.
+//! scene.update(Vector2::new(320.0, 240.0), 0.0);
+//!
+//! let image = renderer.render_scene_to_image(&scene).unwrap();
+//! image.save("scene.png").unwrap();
+//! ```
+
+use crate::{
+    engine::{error::EngineError, resource_manager::ResourceManager},
+    event_loop::EventLoopWindowTarget,
+    renderer::{framework::error::FrameworkError, Renderer},
+};
+use std::ops::{Deref, DerefMut};
+
+/// See module docs.
+pub struct HeadlessRenderer {
+    // Renderer must be destroyed before the context, so it must be declared first.
+    renderer: Renderer,
+    #[allow(dead_code)] // Only keeps the context alive.
+    context: glutin::Context<glutin::PossiblyCurrent>,
+}
+
+impl Deref for HeadlessRenderer {
+    type Target = Renderer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.renderer
+    }
+}
+
+impl DerefMut for HeadlessRenderer {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.renderer
+    }
+}
+
+fn context_builder<'a>() -> glutin::ContextBuilder<'a, glutin::NotCurrent> {
+    glutin::ContextBuilder::new()
+        .with_gl_profile(glutin::GlProfile::Core)
+        .with_gl(glutin::GlRequest::GlThenGles {
+            opengl_version: (3, 3),
+            opengles_version: (3, 0),
+        })
+}
+
+impl HeadlessRenderer {
+    /// Creates new headless renderer using software OSMesa context, it requires neither an
+    /// event loop nor a display server, so it can be used on CI machines without a GPU.
+    /// `frame_size` defines size of images produced by the renderer.
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+    ))]
+    pub fn new(
+        resource_manager: &ResourceManager,
+        frame_size: (u32, u32),
+    ) -> Result<Self, EngineError> {
+        use glutin::platform::unix::HeadlessContextExt;
+
+        let context = context_builder().build_osmesa(frame_size.into())?;
+
+        Self::from_context(context, resource_manager, frame_size)
+    }
+
+    /// Creates new headless renderer using a hidden OpenGL context created from the given event
+    /// loop. `frame_size` defines size of images produced by the renderer.
+    pub fn from_event_loop<T>(
+        event_loop: &EventLoopWindowTarget<T>,
+        resource_manager: &ResourceManager,
+        frame_size: (u32, u32),
+    ) -> Result<Self, EngineError> {
+        let context = context_builder().build_headless(event_loop, frame_size.into())?;
+
+        Self::from_context(context, resource_manager, frame_size)
+    }
+
+    fn from_context(
+        context: glutin::Context<glutin::NotCurrent>,
+        resource_manager: &ResourceManager,
+        frame_size: (u32, u32),
+    ) -> Result<Self, EngineError> {
+        let context = match unsafe { context.make_current() } {
+            Ok(context) => context,
+            Err((_, e)) => return Err(EngineError::from(e)),
+        };
+
+        let glow_context =
+            unsafe { glow::Context::from_loader_function(|s| context.get_proc_address(s)) };
+
+        let renderer = Renderer::new(glow_context, frame_size, resource_manager)?;
+
+        Ok(Self { renderer, context })
+    }
+
+    /// Sets new size of images produced by the renderer.
+    pub fn set_frame_size(&mut self, new_size: (u32, u32)) -> Result<(), FrameworkError> {
+        self.renderer.set_frame_size(new_size)
+    }
+
+    /// Returns a reference to the inner renderer.
+    pub fn renderer(&self) -> &Renderer {
+        &self.renderer
+    }
+
+    /// Returns a reference to the inner renderer.
+    pub fn renderer_mut(&mut self) -> &mut Renderer {
+        &mut self.renderer
+    }
+}
@@ -5,6 +5,8 @@
 
 pub mod error;
 pub mod executor;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod headless;
 pub mod resource_manager;
 
 use crate::{
@@ -16,21 +18,29 @@ use crate::{
     },
     event::Event,
     event_loop::{ControlFlow, EventLoop},
-    gui::UserInterface,
+    gui::{constructor::WidgetConstructorContainer, scene, UserInterface},
     plugin::{
         Plugin, PluginConstructor, PluginContext, PluginRegistrationContext, SoundEngineHelper,
     },
     renderer::{framework::error::FrameworkError, Renderer},
     resource::{model::Model, texture::TextureKind},
     scene::{
-        base::ScriptMessage, node::constructor::NodeConstructorContainer, sound::SoundEngine,
+        base::ScriptMessage,
+        camera::Camera,
+        graph::Graph,
+        node::{constructor::NodeConstructorContainer, Node},
+        sound::SoundEngine,
         Scene, SceneContainer,
     },
-    script::{constructor::ScriptConstructorContainer, Script, ScriptContext, ScriptDeinitContext},
-    utils::log::Log,
+    script::{
+        constructor::ScriptConstructorContainer,
+        message::{self, RoutingStrategy, ScriptMessageQueue, ScriptMessageSender},
+        Script, ScriptContext, ScriptDeinitContext,
+    },
+    utils::{log::Log, UiTextureResolver},
     window::{Window, WindowBuilder},
 };
-use fxhash::FxHashSet;
+use fxhash::FxHashMap;
 use std::{
     collections::{HashSet, VecDeque},
     sync::{
@@ -47,6 +57,8 @@ pub struct SerializationContext {
     pub node_constructors: NodeConstructorContainer,
     /// A script constructor container.
     pub script_constructors: ScriptConstructorContainer,
+    /// A widget constructor container, it is used to save and load UI scenes.
+    pub widget_constructors: Arc<WidgetConstructorContainer>,
 }
 
 impl Default for SerializationContext {
@@ -61,6 +73,7 @@ impl SerializationContext {
         Self {
             node_constructors: NodeConstructorContainer::new(),
             script_constructors: ScriptConstructorContainer::new(),
+            widget_constructors: Arc::new(WidgetConstructorContainer::new()),
         }
     }
 }
@@ -106,6 +119,15 @@ pub struct Engine {
     // Amount of time (in seconds) that passed from creation of the engine.
     elapsed_time: f32,
 
+    // Time step (in seconds) of fixed updates.
+    fixed_timestep: f32,
+
+    // Maximum amount of fixed updates per single update.
+    max_fixed_steps: u32,
+
+    // Amount of time (in seconds) that was not yet simulated by fixed updates.
+    fixed_time_accumulator: f32,
+
     /// A special container that is able to create nodes by their type UUID. Use a copy of this
     /// value whenever you need it as a parameter in other parts of the engine.
     pub serialization_context: Arc<SerializationContext>,
@@ -115,12 +137,13 @@ pub struct Engine {
 
 #[derive(Default)]
 struct ScriptProcessor {
-    scripted_scenes: FxHashSet<Handle<Scene>>,
+    // Every scripted scene has its own message queue.
+    scripted_scenes: FxHashMap<Handle<Scene>, ScriptMessageQueue>,
 }
 
 impl ScriptProcessor {
     fn has_scripted_scene(&self, scene: Handle<Scene>) -> bool {
-        self.scripted_scenes.contains(&scene)
+        self.scripted_scenes.contains_key(&scene)
     }
 
     fn register_scripted_scene(
@@ -130,7 +153,10 @@ impl ScriptProcessor {
         resource_manager: &ResourceManager,
     ) {
         // Register the scene and ensure that it wasn't registered previously.
-        let added = self.scripted_scenes.insert(scene);
+        let added = self
+            .scripted_scenes
+            .insert(scene, Default::default())
+            .is_none();
 
         assert!(added);
 
@@ -152,6 +178,44 @@ impl ScriptProcessor {
         block_on(wait_context.wait_concurrent());
     }
 
+    fn handle_fixed_update(
+        &mut self,
+        scenes: &mut SceneContainer,
+        plugins: &mut Vec<Box<dyn Plugin>>,
+        resource_manager: &ResourceManager,
+        dt: f32,
+        elapsed_time: f32,
+    ) {
+        for (&scene_handle, message_queue) in self.scripted_scenes.iter() {
+            let scene = match scenes.try_get_mut(scene_handle) {
+                Some(scene) if scene.enabled => scene,
+                _ => continue,
+            };
+
+            let mut context = ScriptContext {
+                dt,
+                elapsed_time,
+                plugins,
+                handle: Default::default(),
+                scene,
+                resource_manager,
+                message_sender: &message_queue.sender,
+            };
+
+            dispatch_collision_events(&mut context);
+
+            for index in 0..context.scene.graph.capacity() {
+                context.handle = context.scene.graph.handle_from_index(index);
+
+                process_node(&mut context, &mut |script, context| {
+                    if script.initialized && script.started {
+                        script.on_fixed_update(context);
+                    }
+                });
+            }
+        }
+    }
+
     fn handle_scripts(
         &mut self,
         scenes: &mut SceneContainer,
@@ -161,9 +225,9 @@ impl ScriptProcessor {
         elapsed_time: f32,
     ) {
         self.scripted_scenes
-            .retain(|handle| scenes.is_valid_handle(*handle));
+            .retain(|handle, _| scenes.is_valid_handle(*handle));
 
-        'scene_loop: for &scene_handle in self.scripted_scenes.iter() {
+        'scene_loop: for (&scene_handle, message_queue) in self.scripted_scenes.iter() {
             let scene = &mut scenes[scene_handle];
 
             // Disabled scenes should not update their scripts.
@@ -195,6 +259,7 @@ impl ScriptProcessor {
                     handle: Default::default(),
                     scene,
                     resource_manager,
+                    message_sender: &message_queue.sender,
                 };
 
                 'init_loop: for init_loop_iteration in 0..max_iterations {
@@ -274,6 +339,19 @@ impl ScriptProcessor {
                 }
             }
 
+            // Deliver messages after the update pass, so there is no chance that two scripts will
+            // be borrowed at once.
+            let mut context = ScriptContext {
+                dt,
+                elapsed_time,
+                plugins,
+                handle: Default::default(),
+                scene,
+                resource_manager,
+                message_sender: &message_queue.sender,
+            };
+            dispatch_script_messages(&mut context, &message_queue.receiver);
+
             // As the last step, destroy queued scripts.
             let mut context = ScriptDeinitContext {
                 elapsed_time,
@@ -293,7 +371,7 @@ impl ScriptProcessor {
 
         // Process scripts from destroyed scenes.
         for (handle, mut detached_scene) in scenes.destruction_list.drain(..) {
-            if self.scripted_scenes.contains(&handle) {
+            if self.scripted_scenes.contains_key(&handle) {
                 let mut context = ScriptDeinitContext {
                     elapsed_time,
                     plugins,
@@ -445,10 +523,84 @@ where
     }
 }
 
+fn dispatch_collision_events(context: &mut ScriptContext) {
+    // Events are delivered to scripts of colliders and their parents (usually rigid bodies).
+    fn receivers(graph: &Graph, collider: Handle<Node>) -> [Handle<Node>; 2] {
+        [
+            collider,
+            graph
+                .try_get(collider)
+                .map(|c| c.parent())
+                .unwrap_or_default(),
+        ]
+    }
+
+    let events = context.scene.graph.physics.collision_events().to_vec();
+    for event in events.iter() {
+        for receiver in receivers(&context.scene.graph, event.collider) {
+            context.handle = receiver;
+            process_node(context, &mut |script, context| {
+                if script.initialized && script.started {
+                    script.on_collision(event, context);
+                }
+            });
+        }
+    }
+
+    let events = context.scene.graph.physics2d.collision_events().to_vec();
+    for event in events.iter() {
+        for receiver in receivers(&context.scene.graph, event.collider) {
+            context.handle = receiver;
+            process_node(context, &mut |script, context| {
+                if script.initialized && script.started {
+                    script.on_collision_2d(event, context);
+                }
+            });
+        }
+    }
+}
+
+fn dispatch_script_messages(
+    context: &mut ScriptContext,
+    receiver: &Receiver<message::ScriptMessageEnvelope>,
+) {
+    // Messages sent from `on_message` will be delivered on next frame.
+    let messages = receiver.try_iter().collect::<Vec<_>>();
+
+    for message in messages.iter() {
+        let receivers = match message.routing {
+            RoutingStrategy::Node(handle) => vec![handle],
+            RoutingStrategy::Hierarchy(root) => {
+                if context.scene.graph.is_valid_handle(root) {
+                    context.scene.graph.traverse_handle_iter(root).collect()
+                } else {
+                    Vec::new()
+                }
+            }
+            RoutingStrategy::Global => context
+                .scene
+                .graph
+                .pair_iter()
+                .filter_map(|(handle, node)| node.script.as_ref().map(|_| handle))
+                .collect(),
+        };
+
+        for receiver in receivers {
+            context.handle = receiver;
+            process_node(context, &mut |script, context| {
+                if script.initialized && script.started {
+                    script.on_message(message, context);
+                }
+            });
+        }
+    }
+}
+
 pub(crate) fn process_scripts<T>(
     scene: &mut Scene,
     plugins: &mut [Box<dyn Plugin>],
     resource_manager: &ResourceManager,
+    message_sender: &ScriptMessageSender,
     dt: f32,
     elapsed_time: f32,
     mut func: T,
@@ -462,6 +614,7 @@ pub(crate) fn process_scripts<T>(
         handle: Default::default(),
         scene,
         resource_manager,
+        message_sender,
     };
 
     for node_index in 0..context.scene.graph.capacity() {
@@ -617,6 +770,9 @@ impl Engine {
             plugins_enabled: false,
             plugin_constructors: Default::default(),
             elapsed_time: 0.0,
+            fixed_timestep: 1.0 / 60.0,
+            max_fixed_steps: 8,
+            fixed_time_accumulator: 0.0,
         })
     }
 
@@ -638,6 +794,59 @@ impl Engine {
         self.elapsed_time
     }
 
+    /// Sets new time step (in seconds) of fixed updates, default is `1.0 / 60.0`. Physics of
+    /// every enabled scene is stepped with this time step, the engine overwrites
+    /// [`crate::scene::graph::physics::IntegrationParameters::dt`] (both 3D and 2D) on every
+    /// fixed update.
+    pub fn set_fixed_timestep(&mut self, timestep: f32) {
+        self.fixed_timestep = timestep.max(f32::EPSILON);
+    }
+
+    /// Creates serialization context for UI scenes of the main user interface. It uses widget
+    /// constructors of [`Self::serialization_context`] and loads textures using the resource
+    /// manager of the engine. See [`crate::resource::ui_scene::UiSceneResource`] for more info.
+    pub fn ui_serialization_context(&self) -> Arc<scene::SerializationContext> {
+        Arc::new(
+            scene::SerializationContext::new(self.user_interface.default_font.clone())
+                .with_widget_constructors(self.serialization_context.widget_constructors.clone())
+                .with_texture_resolver(UiTextureResolver::new(self.resource_manager.clone())),
+        )
+    }
+
+    /// Returns current time step (in seconds) of fixed updates.
+    pub fn fixed_timestep(&self) -> f32 {
+        self.fixed_timestep
+    }
+
+    /// Sets maximum amount of fixed updates that can be performed during single [`Engine::update`]
+    /// call, default is 8. The rest of the time is dropped, it prevents the engine from falling
+    /// behind forever when a frame takes more time than the fixed updates can simulate.
+    pub fn set_max_fixed_steps(&mut self, max_steps: u32) {
+        self.max_fixed_steps = max_steps.max(1);
+    }
+
+    /// Returns maximum amount of fixed updates per single update.
+    pub fn max_fixed_steps(&self) -> u32 {
+        self.max_fixed_steps
+    }
+
+    /// Returns a fraction of the fixed timestep that is not yet simulated, it is in `[0; 1]`
+    /// range. It can be used to interpolate state of objects between two last fixed updates, so
+    /// the rendering will be smooth even if the frame rate does not match the fixed update rate.
+    ///
+    /// # Important notes
+    ///
+    /// The engine does not interpolate anything by itself, scene nodes (including rigid bodies)
+    /// are rendered in the state of the last fixed update. If you need smooth movement, store
+    /// previous and current state of your objects in [`Plugin::on_fixed_update`] (or
+    /// [`crate::script::ScriptTrait::on_fixed_update`]) and blend them in [`Plugin::update`]
+    /// using the value from [`PluginContext::interpolation_alpha`]. Keep in mind that such
+    /// blending should be applied to visual nodes only (for example to a child mesh of a rigid
+    /// body), changing the transform of a rigid body teleports it in the physics world.
+    pub fn interpolation_alpha(&self) -> f32 {
+        self.fixed_time_accumulator / self.fixed_timestep
+    }
+
     /// Returns reference to main window. Could be useful to set fullscreen mode, change
     /// size of window, its title, etc.
     #[inline]
@@ -665,6 +874,10 @@ impl Engine {
         self.renderer.update_caches(dt);
         self.handle_model_events();
 
+        self.fixed_update(dt, control_flow);
+
+        let camera_jitter = self.renderer.camera_jitter();
+
         for scene in self.scenes.iter_mut().filter(|s| s.enabled) {
             let frame_size = scene.render_target.as_ref().map_or(window_size, |rt| {
                 if let TextureKind::Rectangle { width, height } = rt.data_ref().kind() {
@@ -674,13 +887,58 @@ impl Engine {
                 }
             });
 
-            scene.update(frame_size, dt);
+            for camera in scene
+                .graph
+                .linear_iter_mut()
+                .filter_map(|n| n.cast_mut::<Camera>())
+            {
+                camera.set_jitter(camera_jitter);
+            }
+
+            // Physics is stepped by fixed updates.
+            scene.update_internal(frame_size, dt, false);
         }
 
         self.update_plugins(dt, control_flow);
         self.handle_scripts(dt);
     }
 
+    /// Performs as many fixed updates as needed to simulate the given amount of time. Every fixed
+    /// update steps physics of enabled scenes and then calls [`Plugin::on_fixed_update`] and
+    /// [`crate::script::ScriptTrait::on_fixed_update`].
+    ///
+    /// Normally, this is called from `Engine::update()`.
+    /// You should only call this manually if you don't use that method.
+    pub fn fixed_update(&mut self, dt: f32, control_flow: &mut ControlFlow) {
+        self.fixed_time_accumulator += dt;
+
+        let mut steps = 0;
+        while self.fixed_time_accumulator >= self.fixed_timestep {
+            if steps == self.max_fixed_steps {
+                // Drop the time that cannot be simulated in a reasonable amount of steps.
+                self.fixed_time_accumulator %= self.fixed_timestep;
+                break;
+            }
+
+            self.fixed_time_accumulator -= self.fixed_timestep;
+            steps += 1;
+
+            for scene in self.scenes.iter_mut().filter(|s| s.enabled) {
+                fixed_update_scene(scene, self.fixed_timestep);
+            }
+
+            let fixed_timestep = self.fixed_timestep;
+            self.fixed_update_plugins(fixed_timestep, control_flow);
+            self.script_processor.handle_fixed_update(
+                &mut self.scenes,
+                &mut self.plugins,
+                &self.resource_manager,
+                fixed_timestep,
+                self.elapsed_time,
+            );
+        }
+    }
+
     /// Performs post update for the engine.
     ///
     /// Normally, this is called from `Engine::update()`.
@@ -719,13 +977,38 @@ impl Engine {
         );
     }
 
+    fn fixed_update_plugins(&mut self, dt: f32, control_flow: &mut ControlFlow) {
+        if self.plugins_enabled {
+            let interpolation_alpha = self.interpolation_alpha();
+            let mut context = PluginContext {
+                scenes: &mut self.scenes,
+                resource_manager: &self.resource_manager,
+                renderer: &mut self.renderer,
+                dt,
+                interpolation_alpha,
+                user_interface: &mut self.user_interface,
+                serialization_context: &self.serialization_context,
+                window: get_window!(self),
+                sound_engine: SoundEngineHelper {
+                    engine: &self.sound_engine,
+                },
+            };
+
+            for plugin in self.plugins.iter_mut() {
+                plugin.on_fixed_update(&mut context, control_flow);
+            }
+        }
+    }
+
     fn update_plugins(&mut self, dt: f32, control_flow: &mut ControlFlow) {
         if self.plugins_enabled {
+            let interpolation_alpha = self.interpolation_alpha();
             let mut context = PluginContext {
                 scenes: &mut self.scenes,
                 resource_manager: &self.resource_manager,
                 renderer: &mut self.renderer,
                 dt,
+                interpolation_alpha,
                 user_interface: &mut self.user_interface,
                 serialization_context: &self.serialization_context,
                 window: get_window!(self),
@@ -744,6 +1027,7 @@ impl Engine {
                     resource_manager: &self.resource_manager,
                     renderer: &mut self.renderer,
                     dt,
+                    interpolation_alpha,
                     user_interface: &mut self.user_interface,
                     serialization_context: &self.serialization_context,
                     window: get_window!(self),
@@ -766,6 +1050,7 @@ impl Engine {
         control_flow: &mut ControlFlow,
     ) {
         if self.plugins_enabled {
+            let interpolation_alpha = self.interpolation_alpha();
             for plugin in self.plugins.iter_mut() {
                 plugin.on_os_event(
                     event,
@@ -774,6 +1059,7 @@ impl Engine {
                         resource_manager: &self.resource_manager,
                         renderer: &mut self.renderer,
                         dt,
+                        interpolation_alpha,
                         user_interface: &mut self.user_interface,
                         serialization_context: &self.serialization_context,
                         window: get_window!(self),
@@ -797,15 +1083,17 @@ impl Engine {
     pub(crate) fn handle_os_event_by_scripts(
         &mut self,
         event: &Event<()>,
-        scene: Handle<Scene>,
+        scene_handle: Handle<Scene>,
         dt: f32,
     ) {
-        let scene = &mut self.scenes[scene];
-        if scene.enabled {
+        let scene = &mut self.scenes[scene_handle];
+        let message_queue = self.script_processor.scripted_scenes.get(&scene_handle);
+        if let (true, Some(message_queue)) = (scene.enabled, message_queue) {
             process_scripts(
                 scene,
                 &mut self.plugins,
                 &self.resource_manager,
+                &message_queue.sender,
                 dt,
                 self.elapsed_time,
                 |script, context| {
@@ -880,6 +1168,7 @@ impl Engine {
     pub(crate) fn enable_plugins(&mut self, override_scene: Handle<Scene>, enabled: bool) {
         if self.plugins_enabled != enabled {
             self.plugins_enabled = enabled;
+            let interpolation_alpha = self.interpolation_alpha();
 
             if self.plugins_enabled {
                 // Create and initialize instances.
@@ -891,6 +1180,7 @@ impl Engine {
                             resource_manager: &self.resource_manager,
                             renderer: &mut self.renderer,
                             dt: 0.0,
+                            interpolation_alpha,
                             user_interface: &mut self.user_interface,
                             serialization_context: &self.serialization_context,
                             window: get_window!(self),
@@ -910,6 +1200,7 @@ impl Engine {
                         resource_manager: &self.resource_manager,
                         renderer: &mut self.renderer,
                         dt: 0.0,
+                        interpolation_alpha,
                         user_interface: &mut self.user_interface,
                         serialization_context: &self.serialization_context,
                         window: get_window!(self),
@@ -955,16 +1246,34 @@ impl Drop for Engine {
     }
 }
 
+/// Performs single fixed update of the scene, physics is stepped exactly by the fixed time step
+/// so the simulation runs in real time regardless of the rate of fixed updates.
+fn fixed_update_scene(scene: &mut Scene, fixed_timestep: f32) {
+    scene.graph.physics.integration_parameters.dt = fixed_timestep;
+    scene.graph.physics2d.integration_parameters.dt = fixed_timestep;
+    scene.fixed_update();
+}
+
 #[cfg(test)]
 mod test {
     use crate::{
+        core::algebra::Vector2,
         core::{
             inspect::prelude::*, pool::Handle, reflect::Reflect, uuid::Uuid, visitor::prelude::*,
         },
-        engine::{resource_manager::ResourceManager, ScriptProcessor},
+        engine::{fixed_update_scene, resource_manager::ResourceManager, ScriptProcessor},
         impl_component_provider,
-        scene::{base::BaseBuilder, node::Node, pivot::PivotBuilder, Scene, SceneContainer},
-        script::{Script, ScriptContext, ScriptDeinitContext, ScriptTrait},
+        scene::{
+            base::BaseBuilder,
+            collider::{ColliderBuilder, ColliderShape},
+            node::Node,
+            pivot::PivotBuilder,
+            rigidbody::RigidBodyBuilder,
+            Scene, SceneContainer,
+        },
+        script::{
+            message::ScriptMessageEnvelope, Script, ScriptContext, ScriptDeinitContext, ScriptTrait,
+        },
     };
     use std::sync::mpsc::{self, Sender, TryRecvError};
 
@@ -973,6 +1282,8 @@ mod test {
         Initialized(Handle<Node>),
         Started(Handle<Node>),
         Updated(Handle<Node>),
+        FixedUpdated(Handle<Node>),
+        Message(Handle<Node>, u32),
         Destroyed(Handle<Node>),
     }
 
@@ -1062,6 +1373,10 @@ mod test {
             self.sender.send(Event::Updated(ctx.handle)).unwrap();
         }
 
+        fn on_fixed_update(&mut self, ctx: &mut ScriptContext) {
+            self.sender.send(Event::FixedUpdated(ctx.handle)).unwrap();
+        }
+
         fn id(&self) -> Uuid {
             Uuid::new_v4()
         }
@@ -1154,4 +1469,171 @@ mod test {
             }
         }
     }
+
+    #[derive(Debug, Clone, Reflect, Inspect, Visit)]
+    struct MessageScript {
+        #[reflect(hidden)]
+        #[inspect(skip)]
+        #[visit(skip)]
+        sender: Sender<Event>,
+        target: Handle<Node>,
+    }
+
+    impl_component_provider!(MessageScript);
+
+    impl ScriptTrait for MessageScript {
+        fn on_update(&mut self, ctx: &mut ScriptContext) {
+            if self.target.is_some() {
+                ctx.message_sender.send_to_target(self.target, 1u32);
+                ctx.message_sender
+                    .send_hierarchical(ctx.scene.graph.get_root(), 2u32);
+                ctx.message_sender.send_global(3u32);
+            }
+        }
+
+        fn on_message(&mut self, message: &ScriptMessageEnvelope, ctx: &mut ScriptContext) {
+            if let Some(value) = message.data::<u32>() {
+                self.sender
+                    .send(Event::Message(ctx.handle, *value))
+                    .unwrap();
+            }
+        }
+
+        fn id(&self) -> Uuid {
+            Uuid::new_v4()
+        }
+    }
+
+    #[test]
+    fn test_messages() {
+        let resource_manager = ResourceManager::new(Default::default());
+        let mut scene = Scene::new();
+
+        let (tx, rx) = mpsc::channel();
+
+        let receiver =
+            PivotBuilder::new(BaseBuilder::new().with_script(Script::new(MessageScript {
+                sender: tx.clone(),
+                target: Handle::NONE,
+            })))
+            .build(&mut scene.graph);
+        let sender =
+            PivotBuilder::new(BaseBuilder::new().with_script(Script::new(MessageScript {
+                sender: tx,
+                target: receiver,
+            })))
+            .build(&mut scene.graph);
+
+        let mut scene_container = SceneContainer::new(Default::default());
+        let scene_handle = scene_container.add(scene);
+
+        let mut script_processor = ScriptProcessor::default();
+        script_processor.register_scripted_scene(
+            scene_handle,
+            &mut scene_container,
+            &resource_manager,
+        );
+
+        script_processor.handle_scripts(
+            &mut scene_container,
+            &mut Default::default(),
+            &resource_manager,
+            0.0,
+            0.0,
+        );
+
+        assert_eq!(rx.try_recv(), Ok(Event::Message(receiver, 1)));
+        assert_eq!(rx.try_recv(), Ok(Event::Message(sender, 2)));
+        assert_eq!(rx.try_recv(), Ok(Event::Message(receiver, 2)));
+        assert_eq!(rx.try_recv(), Ok(Event::Message(receiver, 3)));
+        assert_eq!(rx.try_recv(), Ok(Event::Message(sender, 3)));
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+    }
+
+    #[test]
+    fn test_fixed_update() {
+        let resource_manager = ResourceManager::new(Default::default());
+        let mut scene = Scene::new();
+
+        let (tx, rx) = mpsc::channel();
+
+        let node_handle = PivotBuilder::new(
+            BaseBuilder::new().with_script(Script::new(MySubScript { sender: tx })),
+        )
+        .build(&mut scene.graph);
+
+        let mut scene_container = SceneContainer::new(Default::default());
+        let scene_handle = scene_container.add(scene);
+
+        let mut script_processor = ScriptProcessor::default();
+        script_processor.register_scripted_scene(
+            scene_handle,
+            &mut scene_container,
+            &resource_manager,
+        );
+
+        // Scripts that are not initialized yet must not receive fixed updates.
+        script_processor.handle_fixed_update(
+            &mut scene_container,
+            &mut Default::default(),
+            &resource_manager,
+            1.0 / 60.0,
+            0.0,
+        );
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+
+        script_processor.handle_scripts(
+            &mut scene_container,
+            &mut Default::default(),
+            &resource_manager,
+            0.0,
+            0.0,
+        );
+        assert_eq!(rx.try_recv(), Ok(Event::Initialized(node_handle)));
+        assert_eq!(rx.try_recv(), Ok(Event::Started(node_handle)));
+        assert_eq!(rx.try_recv(), Ok(Event::Updated(node_handle)));
+
+        for _ in 0..2 {
+            script_processor.handle_fixed_update(
+                &mut scene_container,
+                &mut Default::default(),
+                &resource_manager,
+                1.0 / 60.0,
+                0.0,
+            );
+        }
+        assert_eq!(rx.try_recv(), Ok(Event::FixedUpdated(node_handle)));
+        assert_eq!(rx.try_recv(), Ok(Event::FixedUpdated(node_handle)));
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+    }
+
+    fn fall_for_one_second(fixed_timestep: f32) -> f32 {
+        let mut scene = Scene::new();
+
+        let body = RigidBodyBuilder::new(
+            BaseBuilder::new().with_children(&[ColliderBuilder::new(BaseBuilder::new())
+                .with_shape(ColliderShape::ball(0.5))
+                .build(&mut scene.graph)]),
+        )
+        .build(&mut scene.graph);
+
+        for _ in 0..(1.0 / fixed_timestep).round() as usize {
+            fixed_update_scene(&mut scene, fixed_timestep);
+        }
+
+        // Pull the simulated position back to the node.
+        scene.update_internal(Vector2::new(1.0, 1.0), 0.0, false);
+
+        -scene.graph[body].local_transform().position().y
+    }
+
+    #[test]
+    fn test_physics_time_step_matches_fixed_timestep() {
+        let distance_60 = fall_for_one_second(1.0 / 60.0);
+        let distance_30 = fall_for_one_second(1.0 / 30.0);
+
+        // Free fall for one second is ~4.9m, integration error is small for both rates.
+        assert!((distance_60 - 4.9).abs() < 0.2, "{}", distance_60);
+        assert!((distance_30 - 4.9).abs() < 0.2, "{}", distance_30);
+    }
 }
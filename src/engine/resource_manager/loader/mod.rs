@@ -11,6 +11,7 @@ pub mod model;
 pub mod shader;
 pub mod sound;
 pub mod texture;
+pub mod ui_scene;
 
 /// Future type for resource loading. See 'ResourceLoader'.
 #[cfg(target_arch = "wasm32")]
@@ -0,0 +1,45 @@
+//! UI scene loader.
+
+use crate::{
+    engine::resource_manager::{
+        container::event::ResourceEventBroadcaster,
+        loader::{BoxedLoaderFuture, ResourceLoader},
+    },
+    resource::ui_scene::{UiSceneImportOptions, UiSceneResource, UiSceneResourceState},
+    utils::log::Log,
+};
+
+/// Default implementation for UI scene loading.
+pub struct UiSceneLoader;
+
+impl ResourceLoader<UiSceneResource, UiSceneImportOptions> for UiSceneLoader {
+    fn load(
+        &self,
+        ui_scene: UiSceneResource,
+        _default_import_options: UiSceneImportOptions,
+        event_broadcaster: ResourceEventBroadcaster<UiSceneResource>,
+        reload: bool,
+    ) -> BoxedLoaderFuture {
+        Box::pin(async move {
+            let path = ui_scene.state().path().to_path_buf();
+
+            match UiSceneResourceState::from_file(&path).await {
+                Ok(ui_scene_state) => {
+                    Log::info(format!("UI scene {:?} is loaded!", path));
+
+                    ui_scene.state().commit_ok(ui_scene_state);
+
+                    event_broadcaster.broadcast_loaded_or_reloaded(ui_scene, reload);
+                }
+                Err(error) => {
+                    Log::err(format!(
+                        "Unable to load UI scene from {:?}! Reason {:?}",
+                        path, error
+                    ));
+
+                    ui_scene.state().commit_error(path, error);
+                }
+            }
+        })
+    }
+}
@@ -16,6 +16,7 @@ use crate::{
                 shader::ShaderLoader,
                 sound::{SoundBufferImportOptions, SoundBufferLoader},
                 texture::TextureLoader,
+                ui_scene::UiSceneLoader,
                 ResourceLoader,
             },
             task::TaskPool,
@@ -28,6 +29,7 @@ use crate::{
         curve::{CurveImportOptions, CurveResource},
         model::{Model, ModelImportOptions},
         texture::{Texture, TextureError, TextureImportOptions, TextureState},
+        ui_scene::{UiSceneImportOptions, UiSceneResource},
     },
     utils::{log::Log, watcher::FileSystemWatcher},
 };
@@ -59,6 +61,9 @@ pub struct ContainersStorage {
 
     /// Container for ABSM resources.
     pub absm: ResourceContainer<AbsmResource, AbsmImportOptions>,
+
+    /// Container for UI scene resources.
+    pub ui_scenes: ResourceContainer<UiSceneResource, UiSceneImportOptions>,
 }
 
 impl ContainersStorage {
@@ -110,6 +115,14 @@ impl ContainersStorage {
         self.absm.set_loader(loader);
     }
 
+    /// Sets a custom UI scene loader.
+    pub fn set_ui_scene_loader<L>(&mut self, loader: L)
+    where
+        L: 'static + ResourceLoader<UiSceneResource, UiSceneImportOptions>,
+    {
+        self.ui_scenes.set_loader(loader);
+    }
+
     /// Wait until all resources are loaded (or failed to load).
     pub fn wait_concurrent(&self) -> ResourceWaitContext {
         ResourceWaitContext {
@@ -119,6 +132,7 @@ impl ContainersStorage {
             shaders: self.shaders.resources(),
             textures: self.textures.resources(),
             sound_buffers: self.sound_buffers.resources(),
+            ui_scenes: self.ui_scenes.resources(),
         }
     }
 }
@@ -132,6 +146,7 @@ pub struct ResourceWaitContext {
     shaders: Vec<Shader>,
     textures: Vec<Texture>,
     sound_buffers: Vec<SoundBufferResource>,
+    ui_scenes: Vec<UiSceneResource>,
 }
 
 impl ResourceWaitContext {
@@ -143,6 +158,7 @@ impl ResourceWaitContext {
         join_all(self.shaders).await;
         join_all(self.textures).await;
         join_all(self.sound_buffers).await;
+        join_all(self.ui_scenes).await;
     }
 }
 
@@ -199,7 +215,8 @@ impl ResourceManager {
             sound_buffers: ResourceContainer::new(task_pool.clone(), Box::new(SoundBufferLoader)),
             shaders: ResourceContainer::new(task_pool.clone(), Box::new(ShaderLoader)),
             curves: ResourceContainer::new(task_pool.clone(), Box::new(CurveLoader)),
-            absm: ResourceContainer::new(task_pool, Box::new(AbsmLoader)),
+            absm: ResourceContainer::new(task_pool.clone(), Box::new(AbsmLoader)),
+            ui_scenes: ResourceContainer::new(task_pool, Box::new(UiSceneLoader)),
         });
 
         resource_manager
@@ -340,6 +357,17 @@ impl ResourceManager {
         self.state().containers_mut().absm.request(path)
     }
 
+    /// Tries to load a new UI scene resource from given path or get instance of existing, if any.
+    /// This method is asynchronous, it immediately returns a UI scene which can be shared across
+    /// multiple places, the loading may fail, but it is internal state of the UI scene resource.
+    ///
+    /// # Async/.await
+    ///
+    /// Each UI scene implements Future trait and can be used in async contexts.
+    pub fn request_ui_scene<P: AsRef<Path>>(&self, path: P) -> UiSceneResource {
+        self.state().containers_mut().ui_scenes.request(path)
+    }
+
     /// Reloads every loaded texture. This method is asynchronous, internally it uses thread pool
     /// to run reload on separate thread per texture.
     pub async fn reload_textures(&self) {
@@ -375,6 +403,13 @@ impl ResourceManager {
         join_all(resources).await;
     }
 
+    /// Reloads every loaded UI scene resource. This method is asynchronous, internally it uses thread pool
+    /// to run reload on separate thread per resource.
+    pub async fn reload_ui_scenes(&self) {
+        let resources = self.state().containers_mut().ui_scenes.reload_resources();
+        join_all(resources).await;
+    }
+
     /// Reloads every loaded sound buffer. This method is asynchronous, internally it uses thread pool
     /// to run reload on separate thread per sound buffer.
     pub async fn reload_sound_buffers(&self) {
@@ -397,6 +432,7 @@ impl ResourceManager {
             self.reload_shaders(),
             self.reload_curve_resources(),
             self.reload_absm_resources(),
+            self.reload_ui_scenes(),
         );
     }
 }
@@ -440,6 +476,7 @@ impl ResourceManagerState {
             + containers.shaders.count_pending_resources()
             + containers.curves.count_pending_resources()
             + containers.absm.count_pending_resources()
+            + containers.ui_scenes.count_pending_resources()
     }
 
     /// Returns total amount of loaded resources.
@@ -451,6 +488,7 @@ impl ResourceManagerState {
             + containers.shaders.count_loaded_resources()
             + containers.curves.count_loaded_resources()
             + containers.absm.count_loaded_resources()
+            + containers.ui_scenes.count_loaded_resources()
     }
 
     /// Returns total amount of registered resources.
@@ -462,6 +500,7 @@ impl ResourceManagerState {
             + containers.shaders.len()
             + containers.curves.len()
             + containers.absm.len()
+            + containers.ui_scenes.len()
     }
 
     /// Returns percentage of loading progress. This method is useful to show progress on
@@ -486,6 +525,7 @@ impl ResourceManagerState {
         containers.shaders.destroy_unused();
         containers.curves.destroy_unused();
         containers.absm.destroy_unused();
+        containers.ui_scenes.destroy_unused();
     }
 
     /// Update resource containers and do hot-reloading.
@@ -503,6 +543,7 @@ impl ResourceManagerState {
         containers.shaders.update(dt);
         containers.curves.update(dt);
         containers.absm.update(dt);
+        containers.ui_scenes.update(dt);
 
         if let Some(watcher) = self.watcher.as_ref() {
             if let Some(DebouncedEvent::Write(path)) = watcher.try_get_event() {
@@ -515,6 +556,7 @@ impl ResourceManagerState {
                         &mut containers.shaders as &mut dyn Container,
                         &mut containers.curves as &mut dyn Container,
                         &mut containers.absm as &mut dyn Container,
+                        &mut containers.ui_scenes as &mut dyn Container,
                     ] {
                         if container.try_reload_resource_from_path(&relative_path) {
                             Log::info(format!(
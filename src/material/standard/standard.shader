@@ -258,19 +258,28 @@
                r#"
                 layout(location = 0) in vec3 vertexPosition;
                 layout(location = 1) in vec2 vertexTexCoord;
-                layout(location = 5) in vec4 boneWeights;
-                layout(location = 6) in vec4 boneIndices;
+                layout(location = 2) in vec3 vertexNormal;
+                layout(location = 3) in vec4 vertexTangent;
+                layout(location = 4) in vec4 boneWeights;
+                layout(location = 5) in vec4 boneIndices;
 
+                uniform mat4 fyrox_worldMatrix;
                 uniform mat4 fyrox_worldViewProjection;
                 uniform bool fyrox_useSkeletalAnimation;
                 uniform mat4 fyrox_boneMatrices[60];
 
                 out vec3 position;
+                out vec3 normal;
                 out vec2 texCoord;
+                out vec3 tangent;
+                out vec3 binormal;
 
                 void main()
                 {
                     vec4 localPosition = vec4(0);
+                    vec3 localNormal = vec3(0);
+                    vec3 localTangent = vec3(0);
+
                     if (fyrox_useSkeletalAnimation)
                     {
                         vec4 vertex = vec4(vertexPosition, 1.0);
@@ -280,32 +289,106 @@
                         int i2 = int(boneIndices.z);
                         int i3 = int(boneIndices.w);
 
-                        localPosition += fyrox_boneMatrices[i0] * vertex * boneWeights.x;
-                        localPosition += fyrox_boneMatrices[i1] * vertex * boneWeights.y;
-                        localPosition += fyrox_boneMatrices[i2] * vertex * boneWeights.z;
-                        localPosition += fyrox_boneMatrices[i3] * vertex * boneWeights.w;
+                        mat4 m0 = fyrox_boneMatrices[i0];
+                        mat4 m1 = fyrox_boneMatrices[i1];
+                        mat4 m2 = fyrox_boneMatrices[i2];
+                        mat4 m3 = fyrox_boneMatrices[i3];
+
+                        localPosition += m0 * vertex * boneWeights.x;
+                        localPosition += m1 * vertex * boneWeights.y;
+                        localPosition += m2 * vertex * boneWeights.z;
+                        localPosition += m3 * vertex * boneWeights.w;
+
+                        localNormal += mat3(m0) * vertexNormal * boneWeights.x;
+                        localNormal += mat3(m1) * vertexNormal * boneWeights.y;
+                        localNormal += mat3(m2) * vertexNormal * boneWeights.z;
+                        localNormal += mat3(m3) * vertexNormal * boneWeights.w;
+
+                        localTangent += mat3(m0) * vertexTangent.xyz * boneWeights.x;
+                        localTangent += mat3(m1) * vertexTangent.xyz * boneWeights.y;
+                        localTangent += mat3(m2) * vertexTangent.xyz * boneWeights.z;
+                        localTangent += mat3(m3) * vertexTangent.xyz * boneWeights.w;
                     }
                     else
                     {
                         localPosition = vec4(vertexPosition, 1.0);
+                        localNormal = vertexNormal;
+                        localTangent = vertexTangent.xyz;
                     }
-                    gl_Position = fyrox_worldViewProjection * localPosition;
+
+                    mat3 nm = mat3(fyrox_worldMatrix);
+                    normal = normalize(nm * localNormal);
+                    tangent = normalize(nm * localTangent);
+                    binormal = normalize(vertexTangent.w * cross(tangent, normal));
+                    position = vec3(fyrox_worldMatrix * localPosition);
                     texCoord = vertexTexCoord;
+
+                    gl_Position = fyrox_worldViewProjection * localPosition;
                 }
                "#,
 
            fragment_shader:
                r#"
                 uniform sampler2D diffuseTexture;
+                uniform sampler2D normalTexture;
+                uniform sampler2D metallicTexture;
+                uniform sampler2D roughnessTexture;
+                uniform sampler2D emissionTexture;
+                uniform sampler2D aoTexture;
+                uniform vec2 texCoordScale;
+                uniform vec3 emissionStrength;
                 uniform vec4 diffuseColor;
 
+                // Define uniforms with reserved names. Fyrox will automatically provide
+                // required data to these uniforms.
+                uniform vec3 fyrox_cameraPosition;
+                uniform vec4 fyrox_ambientLightColor;
+                uniform sampler2D fyrox_lightData;
+                uniform sampler2D fyrox_lightClusters;
+                uniform sampler2D fyrox_lightIndices;
+                uniform mat4 fyrox_viewMatrix;
+                uniform vec4 fyrox_clusterParameters;
+                uniform vec4 fyrox_viewport;
+
                 out vec4 FragColor;
 
+                in vec3 position;
+                in vec3 normal;
                 in vec2 texCoord;
+                in vec3 tangent;
+                in vec3 binormal;
 
                 void main()
                 {
-                    FragColor = diffuseColor * texture(diffuseTexture, texCoord);
+                    vec2 tc = texCoord * texCoordScale;
+
+                    vec4 diffuse = diffuseColor * texture(diffuseTexture, tc);
+
+                    mat3 tangentSpace = mat3(tangent, binormal, normal);
+                    vec3 n = normalize(texture(normalTexture, tc).xyz * 2.0 - 1.0);
+
+                    TClusteredLightingContext ctx;
+                    ctx.fragmentPosition = position;
+                    ctx.fragmentCoord = gl_FragCoord.xy;
+                    ctx.fragmentNormal = normalize(tangentSpace * n);
+                    ctx.viewVector = normalize(fyrox_cameraPosition - position);
+                    ctx.metallic = texture(metallicTexture, tc).r;
+                    ctx.roughness = texture(roughnessTexture, tc).r;
+                    ctx.albedo = diffuse.rgb;
+
+                    vec3 lighting = S_ClusteredLighting(
+                        ctx,
+                        fyrox_lightData,
+                        fyrox_lightClusters,
+                        fyrox_lightIndices,
+                        fyrox_viewMatrix,
+                        fyrox_clusterParameters,
+                        fyrox_viewport);
+
+                    vec3 ambient = fyrox_ambientLightColor.rgb * diffuse.rgb * texture(aoTexture, tc).r;
+                    vec3 emission = emissionStrength * texture(emissionTexture, tc).rgb;
+
+                    FragColor = vec4(ambient + lighting + emission, diffuse.a);
                 }
                "#,
         ),
@@ -89,6 +89,11 @@ pub struct PluginContext<'a> {
     /// passed.
     pub dt: f32,
 
+    /// A fraction of the fixed timestep that is not yet simulated, it is in `[0; 1]` range. It can
+    /// be used to interpolate state of objects between two last fixed updates for smooth rendering.
+    /// See [`crate::engine::Engine::interpolation_alpha`] for more info.
+    pub interpolation_alpha: f32,
+
     /// A reference to serialization context of the engine. See [`SerializationContext`] for more
     /// info.
     pub serialization_context: &'a Arc<SerializationContext>,
@@ -172,7 +177,12 @@ impl dyn Plugin {
 ///     }
 ///
 ///     fn update(&mut self, context: &mut PluginContext, control_flow: &mut ControlFlow) {
-///         // The method is called on every frame, it is guaranteed to have fixed update rate.
+///         // The method is called on every frame.
+///         // The implementation is optional.
+///     }
+///
+///     fn on_fixed_update(&mut self, context: &mut PluginContext, control_flow: &mut ControlFlow) {
+///         // The method is called at fixed rate, it is the place for deterministic simulation.
 ///         // The implementation is optional.
 ///     }
 ///
@@ -186,8 +196,8 @@ pub trait Plugin: BasePlugin {
     /// additional actions.
     fn on_deinit(&mut self, #[allow(unused_variables)] context: PluginContext) {}
 
-    /// Updates the plugin internals at fixed rate (see [`PluginContext::dt`] parameter for more
-    /// info).
+    /// Updates the plugin internals once per frame, [`PluginContext::dt`] contains the time that
+    /// passed since the last frame.
     fn update(
         &mut self,
         #[allow(unused_variables)] context: &mut PluginContext,
@@ -195,6 +205,16 @@ pub trait Plugin: BasePlugin {
     ) {
     }
 
+    /// Updates the plugin internals at fixed rate, [`PluginContext::dt`] is always equal to
+    /// [`crate::engine::Engine::fixed_timestep`]. The method is called zero or more times per
+    /// frame, right after a physics step and before [`Plugin::update`].
+    fn on_fixed_update(
+        &mut self,
+        #[allow(unused_variables)] context: &mut PluginContext,
+        #[allow(unused_variables)] control_flow: &mut ControlFlow,
+    ) {
+    }
+
     /// The method is called when the main window receives an event from the OS. The main use of
     /// the method is to respond to some external events, for example an event from keyboard or
     /// gamepad. See [`Event`] docs for more info.
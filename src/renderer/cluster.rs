@@ -0,0 +1,540 @@
+//! Clustered light culling for forward rendering (also known as Forward+).
+//!
+//! View frustum of a camera is split into a grid of clusters (froxels) - the screen is split into
+//! tiles and each tile is split into slices along the view direction with exponential distribution
+//! (close slices are thinner than distant ones). Each frame every light source is tested against
+//! every cluster and the list of lights affecting a cluster is stored on GPU. Forward shaders then
+//! find the cluster of a fragment and shade it against every light from the list of the cluster.
+//!
+//! Lights are stored in three textures:
+//!
+//! - light data - every row contains parameters of a light: `(position, radius)`,
+//!   `(color * intensity, kind)`, `(direction, cos of half of outer cone angle)` and
+//!   `(cos of half of inner cone angle, two-sided flag, 0, 0)`. Directional lights are stored
+//!   first.
+//! - clusters - every texel contains offset and count of light indices of a cluster. Texture
+//!   has `CLUSTER_GRID_WIDTH * CLUSTER_GRID_HEIGHT` columns and `CLUSTER_GRID_DEPTH` rows.
+//! - light indices - flat list of light indices of every cluster.
+//!
+//! See `S_ClusteredLighting` in shared shader code for GPU side.
+//!
+//! # Limitations
+//!
+//! Lights do not cast shadows in forward rendering. Area lights are approximated by one-sided
+//! (unless they're two-sided) point lights placed at the center of the light.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Vector3, Vector4},
+        color::Color,
+        math::{aabb::AxisAlignedBoundingBox, frustum::Frustum, Rect},
+        scope_profile,
+    },
+    renderer::framework::{
+        error::FrameworkError,
+        gpu_texture::{
+            Coordinate, GpuTexture, GpuTextureKind, MagnificationFilter, MinificationFilter,
+            PixelKind, WrapMode,
+        },
+        state::PipelineState,
+    },
+    scene::{
+        camera::Camera,
+        graph::Graph,
+        light::{
+            area::AreaLight, directional::DirectionalLight, point::PointLight, spot::SpotLight,
+            BaseLight,
+        },
+    },
+};
+use std::{cell::RefCell, rc::Rc};
+
+/// Amount of tiles along X axis of the screen. Keep in sync with shared shader code.
+pub const CLUSTER_GRID_WIDTH: usize = 16;
+/// Amount of tiles along Y axis of the screen. Keep in sync with shared shader code.
+pub const CLUSTER_GRID_HEIGHT: usize = 9;
+/// Amount of slices along view direction. Keep in sync with shared shader code.
+pub const CLUSTER_GRID_DEPTH: usize = 24;
+/// Max amount of lights that could be rendered by forward renderer at once.
+pub const MAX_CLUSTERED_LIGHTS: usize = 256;
+
+const CLUSTER_COUNT: usize = CLUSTER_GRID_WIDTH * CLUSTER_GRID_HEIGHT * CLUSTER_GRID_DEPTH;
+const LIGHT_DATA_TEXELS: usize = 4;
+// Keep in sync with shared shader code.
+const LIGHT_INDICES_WIDTH: usize = 1024;
+const LIGHT_INDICES_HEIGHT: usize = 64;
+const MAX_LIGHT_INDICES: usize = LIGHT_INDICES_WIDTH * LIGHT_INDICES_HEIGHT;
+
+#[derive(Copy, Clone, PartialEq)]
+#[repr(u32)]
+enum ClusteredLightKind {
+    Directional = 0,
+    Point = 1,
+    Spot = 2,
+    Area = 3,
+}
+
+#[derive(Copy, Clone)]
+struct ClusteredLight {
+    kind: ClusteredLightKind,
+    position: Vector3<f32>,
+    radius: f32,
+    color: Vector3<f32>,
+    direction: Vector3<f32>,
+    cos_outer: f32,
+    cos_inner: f32,
+    two_sided: bool,
+}
+
+impl ClusteredLight {
+    fn new(kind: ClusteredLightKind, base_light: &BaseLight, radius: f32) -> Self {
+        Self {
+            kind,
+            position: base_light.global_position(),
+            radius,
+            color: base_light.color().srgb_to_linear_f32().xyz() * base_light.intensity(),
+            direction: base_light
+                .up_vector()
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_else(Vector3::y),
+            cos_outer: 0.0,
+            cos_inner: 0.0,
+            two_sided: false,
+        }
+    }
+
+    fn texels(&self) -> [[f32; 4]; LIGHT_DATA_TEXELS] {
+        [
+            [
+                self.position.x,
+                self.position.y,
+                self.position.z,
+                self.radius,
+            ],
+            [
+                self.color.x,
+                self.color.y,
+                self.color.z,
+                self.kind as u32 as f32,
+            ],
+            [
+                self.direction.x,
+                self.direction.y,
+                self.direction.z,
+                self.cos_outer,
+            ],
+            [
+                self.cos_inner,
+                if self.two_sided { 1.0 } else { 0.0 },
+                0.0,
+                0.0,
+            ],
+        ]
+    }
+}
+
+fn make_texture(
+    state: &mut PipelineState,
+    width: usize,
+    height: usize,
+    pixel_kind: PixelKind,
+) -> Result<Rc<RefCell<GpuTexture>>, FrameworkError> {
+    let mut texture = GpuTexture::new(
+        state,
+        GpuTextureKind::Rectangle { width, height },
+        pixel_kind,
+        MinificationFilter::Nearest,
+        MagnificationFilter::Nearest,
+        1,
+        None,
+    )?;
+    texture
+        .bind_mut(state, 0)
+        .set_wrap(Coordinate::S, WrapMode::ClampToEdge)
+        .set_wrap(Coordinate::T, WrapMode::ClampToEdge);
+    Ok(Rc::new(RefCell::new(texture)))
+}
+
+fn upload(
+    state: &mut PipelineState,
+    texture: &Rc<RefCell<GpuTexture>>,
+    width: usize,
+    height: usize,
+    pixel_kind: PixelKind,
+    data: &[f32],
+) -> Result<(), FrameworkError> {
+    let mut bytes = Vec::with_capacity(std::mem::size_of_val(data));
+    for v in data {
+        bytes.extend_from_slice(&v.to_ne_bytes());
+    }
+    texture.borrow_mut().bind_mut(state, 0).set_data(
+        GpuTextureKind::Rectangle { width, height },
+        pixel_kind,
+        1,
+        Some(&bytes),
+    )?;
+    Ok(())
+}
+
+/// Calculates view-space bounds of every cluster of the given projection.
+fn calculate_cluster_bounds(
+    projection: &Matrix4<f32>,
+    z_near: f32,
+    z_far: f32,
+    bounds: &mut Vec<AxisAlignedBoundingBox>,
+) {
+    bounds.clear();
+
+    let inv_projection = projection.try_inverse().unwrap_or_default();
+    let unproject = |x: f32, y: f32, z: f32| {
+        let p = inv_projection * Vector4::new(x, y, z, 1.0);
+        p.xyz().scale(1.0 / p.w)
+    };
+
+    // Corner rays of every tile, from near to far clipping plane.
+    let mut rays = Vec::with_capacity((CLUSTER_GRID_WIDTH + 1) * (CLUSTER_GRID_HEIGHT + 1));
+    for y in 0..=CLUSTER_GRID_HEIGHT {
+        for x in 0..=CLUSTER_GRID_WIDTH {
+            let ndc_x = -1.0 + 2.0 * x as f32 / CLUSTER_GRID_WIDTH as f32;
+            let ndc_y = -1.0 + 2.0 * y as f32 / CLUSTER_GRID_HEIGHT as f32;
+            rays.push((unproject(ndc_x, ndc_y, -1.0), unproject(ndc_x, ndc_y, 1.0)));
+        }
+    }
+
+    let point_at_depth = |ray: &(Vector3<f32>, Vector3<f32>), depth: f32| {
+        let (begin, end) = ray;
+        let length = begin.z - end.z;
+        let t = if length.abs() > f32::EPSILON {
+            (depth + begin.z) / length
+        } else {
+            0.0
+        };
+        begin + (end - begin).scale(t)
+    };
+
+    for slice in 0..CLUSTER_GRID_DEPTH {
+        let slice_near = slice_depth(z_near, z_far, slice);
+        let slice_far = slice_depth(z_near, z_far, slice + 1);
+
+        for y in 0..CLUSTER_GRID_HEIGHT {
+            for x in 0..CLUSTER_GRID_WIDTH {
+                let mut aabb = AxisAlignedBoundingBox::default();
+                for (dx, dy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+                    let ray = &rays[(y + dy) * (CLUSTER_GRID_WIDTH + 1) + x + dx];
+                    aabb.add_point(point_at_depth(ray, slice_near));
+                    aabb.add_point(point_at_depth(ray, slice_far));
+                }
+                bounds.push(aabb);
+            }
+        }
+    }
+}
+
+/// Returns view-space depth (positive) of the near plane of the given slice.
+fn slice_depth(z_near: f32, z_far: f32, slice: usize) -> f32 {
+    z_near * (z_far / z_near).powf(slice as f32 / CLUSTER_GRID_DEPTH as f32)
+}
+
+/// Returns index of the slice that contains the given view-space depth (positive).
+fn depth_slice(depth: f32, scale: f32, bias: f32) -> usize {
+    ((depth.max(f32::EPSILON).ln() * scale + bias)
+        .floor()
+        .max(0.0) as usize)
+        .min(CLUSTER_GRID_DEPTH - 1)
+}
+
+pub(crate) struct LightClusters {
+    light_data: Rc<RefCell<GpuTexture>>,
+    clusters: Rc<RefCell<GpuTexture>>,
+    light_indices: Rc<RefCell<GpuTexture>>,
+    view_matrix: Matrix4<f32>,
+    parameters: Vector4<f32>,
+    viewport: Vector4<f32>,
+    ambient_color: Color,
+    // Scratch buffers, they're kept between frames to prevent redundant memory allocations.
+    lights: Vec<ClusteredLight>,
+    cluster_bounds: Vec<AxisAlignedBoundingBox>,
+    cluster_lights: Vec<Vec<u32>>,
+}
+
+impl LightClusters {
+    pub fn new(state: &mut PipelineState) -> Result<Self, FrameworkError> {
+        Ok(Self {
+            light_data: make_texture(
+                state,
+                LIGHT_DATA_TEXELS,
+                MAX_CLUSTERED_LIGHTS,
+                PixelKind::RGBA32F,
+            )?,
+            clusters: make_texture(
+                state,
+                CLUSTER_GRID_WIDTH * CLUSTER_GRID_HEIGHT,
+                CLUSTER_GRID_DEPTH,
+                PixelKind::RGBA32F,
+            )?,
+            light_indices: make_texture(
+                state,
+                LIGHT_INDICES_WIDTH,
+                LIGHT_INDICES_HEIGHT,
+                PixelKind::F32,
+            )?,
+            view_matrix: Matrix4::identity(),
+            parameters: Default::default(),
+            viewport: Default::default(),
+            ambient_color: Default::default(),
+            lights: Default::default(),
+            cluster_bounds: Default::default(),
+            cluster_lights: vec![Default::default(); CLUSTER_COUNT],
+        })
+    }
+
+    /// Builds clusters for the given camera and uploads them to GPU.
+    pub fn update(
+        &mut self,
+        state: &mut PipelineState,
+        graph: &Graph,
+        camera: &Camera,
+        viewport: Rect<i32>,
+        ambient_color: Color,
+    ) -> Result<(), FrameworkError> {
+        scope_profile!();
+
+        let frustum = Frustum::from(camera.view_projection_matrix()).unwrap_or_default();
+
+        self.lights.clear();
+        let mut directional_light_count = 0;
+        for node in graph.linear_iter() {
+            if !node.global_visibility() {
+                continue;
+            }
+
+            let light = if let Some(directional) = node.cast::<DirectionalLight>() {
+                directional_light_count += 1;
+                ClusteredLight::new(
+                    ClusteredLightKind::Directional,
+                    directional.base_light_ref(),
+                    f32::MAX,
+                )
+            } else if let Some(point) = node.cast::<PointLight>() {
+                ClusteredLight::new(
+                    ClusteredLightKind::Point,
+                    point.base_light_ref(),
+                    point.radius(),
+                )
+            } else if let Some(spot) = node.cast::<SpotLight>() {
+                ClusteredLight {
+                    cos_outer: (spot.full_cone_angle() * 0.5).cos(),
+                    cos_inner: (spot.hotspot_cone_angle() * 0.5).cos(),
+                    ..ClusteredLight::new(
+                        ClusteredLightKind::Spot,
+                        spot.base_light_ref(),
+                        spot.distance(),
+                    )
+                }
+            } else if let Some(area) = node.cast::<AreaLight>() {
+                ClusteredLight {
+                    two_sided: area.is_two_sided(),
+                    ..ClusteredLight::new(
+                        ClusteredLightKind::Area,
+                        area.base_light_ref(),
+                        area.radius(),
+                    )
+                }
+            } else {
+                continue;
+            };
+
+            if light.kind == ClusteredLightKind::Directional
+                || frustum.is_intersects_sphere(light.position, light.radius)
+            {
+                self.lights.push(light);
+            }
+        }
+
+        // Directional lights affect every cluster, so they're stored first.
+        self.lights
+            .sort_by_key(|light| light.kind != ClusteredLightKind::Directional);
+        self.lights.truncate(MAX_CLUSTERED_LIGHTS);
+        let directional_light_count = directional_light_count.min(self.lights.len());
+
+        let z_near = camera.projection().z_near().max(f32::EPSILON);
+        let z_far = camera.projection().z_far().max(z_near + f32::EPSILON);
+        let depth_scale = CLUSTER_GRID_DEPTH as f32 / (z_far / z_near).ln();
+        let depth_bias = -z_near.ln() * depth_scale;
+
+        calculate_cluster_bounds(
+            &camera.projection_matrix(),
+            z_near,
+            z_far,
+            &mut self.cluster_bounds,
+        );
+
+        for cluster in self.cluster_lights.iter_mut() {
+            cluster.clear();
+        }
+
+        let view_matrix = camera.view_matrix();
+        let tiles_per_slice = CLUSTER_GRID_WIDTH * CLUSTER_GRID_HEIGHT;
+        for (index, light) in self.lights.iter().enumerate().skip(directional_light_count) {
+            let center = view_matrix.transform_point(&light.position.into()).coords;
+            let depth = -center.z;
+            if depth + light.radius < z_near || depth - light.radius > z_far {
+                continue;
+            }
+
+            let first_slice = depth_slice(depth - light.radius, depth_scale, depth_bias);
+            let last_slice = depth_slice(depth + light.radius, depth_scale, depth_bias);
+            for slice in first_slice..=last_slice {
+                let offset = slice * tiles_per_slice;
+                for cluster in offset..(offset + tiles_per_slice) {
+                    if self.cluster_bounds[cluster].is_intersects_sphere(center, light.radius) {
+                        self.cluster_lights[cluster].push(index as u32);
+                    }
+                }
+            }
+        }
+
+        // Upload everything to GPU.
+        let mut light_data = vec![0.0; LIGHT_DATA_TEXELS * MAX_CLUSTERED_LIGHTS * 4];
+        for (light, data) in self
+            .lights
+            .iter()
+            .zip(light_data.chunks_exact_mut(LIGHT_DATA_TEXELS * 4))
+        {
+            for (texel, dest) in light.texels().iter().zip(data.chunks_exact_mut(4)) {
+                dest.copy_from_slice(texel);
+            }
+        }
+
+        let mut clusters = Vec::with_capacity(CLUSTER_COUNT * 4);
+        let mut light_indices = vec![0.0; MAX_LIGHT_INDICES];
+        let mut index_count = 0;
+        for cluster in self.cluster_lights.iter() {
+            let count = cluster.len().min(MAX_LIGHT_INDICES - index_count);
+            for (dest, &index) in light_indices[index_count..(index_count + count)]
+                .iter_mut()
+                .zip(cluster.iter())
+            {
+                *dest = index as f32;
+            }
+            clusters.extend_from_slice(&[index_count as f32, count as f32, 0.0, 0.0]);
+            index_count += count;
+        }
+
+        upload(
+            state,
+            &self.light_data,
+            LIGHT_DATA_TEXELS,
+            MAX_CLUSTERED_LIGHTS,
+            PixelKind::RGBA32F,
+            &light_data,
+        )?;
+        upload(
+            state,
+            &self.clusters,
+            tiles_per_slice,
+            CLUSTER_GRID_DEPTH,
+            PixelKind::RGBA32F,
+            &clusters,
+        )?;
+        upload(
+            state,
+            &self.light_indices,
+            LIGHT_INDICES_WIDTH,
+            LIGHT_INDICES_HEIGHT,
+            PixelKind::F32,
+            &light_indices,
+        )?;
+
+        self.view_matrix = view_matrix;
+        self.parameters =
+            Vector4::new(depth_scale, depth_bias, directional_light_count as f32, 0.0);
+        self.viewport = Vector4::new(
+            viewport.x() as f32,
+            viewport.y() as f32,
+            viewport.w() as f32,
+            viewport.h() as f32,
+        );
+        self.ambient_color = ambient_color;
+
+        Ok(())
+    }
+
+    pub fn light_data(&self) -> &Rc<RefCell<GpuTexture>> {
+        &self.light_data
+    }
+
+    pub fn clusters(&self) -> &Rc<RefCell<GpuTexture>> {
+        &self.clusters
+    }
+
+    pub fn light_indices(&self) -> &Rc<RefCell<GpuTexture>> {
+        &self.light_indices
+    }
+
+    pub fn view_matrix(&self) -> &Matrix4<f32> {
+        &self.view_matrix
+    }
+
+    /// Returns `(depth scale, depth bias, directional light count, 0)`.
+    pub fn parameters(&self) -> &Vector4<f32> {
+        &self.parameters
+    }
+
+    pub fn viewport(&self) -> &Vector4<f32> {
+        &self.viewport
+    }
+
+    pub fn ambient_color(&self) -> &Color {
+        &self.ambient_color
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::{Matrix4, Vector3},
+        renderer::cluster::{
+            calculate_cluster_bounds, depth_slice, slice_depth, CLUSTER_GRID_DEPTH,
+            CLUSTER_GRID_HEIGHT, CLUSTER_GRID_WIDTH,
+        },
+    };
+
+    #[test]
+    fn test_depth_slices() {
+        let (z_near, z_far) = (0.1, 100.0);
+        let scale = CLUSTER_GRID_DEPTH as f32 / (z_far / z_near as f32).ln();
+        let bias = -(z_near as f32).ln() * scale;
+
+        for slice in 0..CLUSTER_GRID_DEPTH {
+            let middle =
+                0.5 * (slice_depth(z_near, z_far, slice) + slice_depth(z_near, z_far, slice + 1));
+            assert_eq!(depth_slice(middle, scale, bias), slice);
+        }
+        assert_eq!(depth_slice(0.0, scale, bias), 0);
+        assert_eq!(depth_slice(1000.0, scale, bias), CLUSTER_GRID_DEPTH - 1);
+    }
+
+    #[test]
+    fn test_cluster_bounds() {
+        let (z_near, z_far) = (0.1, 100.0);
+        let projection = Matrix4::new_perspective(16.0 / 9.0, 1.0, z_near, z_far);
+
+        let mut bounds = Vec::new();
+        calculate_cluster_bounds(&projection, z_near, z_far, &mut bounds);
+        assert_eq!(
+            bounds.len(),
+            CLUSTER_GRID_WIDTH * CLUSTER_GRID_HEIGHT * CLUSTER_GRID_DEPTH
+        );
+
+        // Every point on the view axis must be in one of central clusters.
+        for depth in [0.5, 1.0, 10.0, 50.0] {
+            let point = Vector3::new(0.0, 0.0, -depth);
+            assert!(bounds.iter().any(|b| b.is_contains_point(point)));
+        }
+
+        // First and last slices must touch clipping planes.
+        assert!((bounds[0].max.z + z_near).abs() < 1.0e-4);
+        assert!((bounds.last().unwrap().min.z + z_far).abs() < 1.0e-2);
+    }
+}
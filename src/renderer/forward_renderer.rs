@@ -3,30 +3,40 @@
 //! # Notes
 //!
 //! This renderer eventually will replace deferred renderer, because deferred renderer is too restrictive.
-//! For now it is used **only** to render transparent meshes (or any other mesh that has Forward render
-//! path).
+//! For now it is used **only** to render meshes with [`RenderPath::ForwardPlus`] render path (opaque
+//! meshes) and meshes with [`RenderPath::Forward`] render path (transparent meshes), in that order.
+//!
+//! Lighting in forward shaders is done using clustered light culling, see [`super::cluster`]
+//! module docs for more info.
 
 use crate::core::sstorage::ImmutableString;
 use crate::{
-    core::{math::Rect, scope_profile},
+    core::{color::Color, math::Rect, scope_profile},
     renderer::{
         apply_material,
         batch::BatchStorage,
         cache::{shader::ShaderCache, texture::TextureCache},
-        framework::{framebuffer::FrameBuffer, gpu_texture::GpuTexture, state::PipelineState},
+        cluster::LightClusters,
+        framework::{
+            error::FrameworkError, framebuffer::FrameBuffer, gpu_texture::GpuTexture,
+            state::PipelineState,
+        },
         GeometryCache, MaterialContext, QualitySettings, RenderPassStatistics,
     },
-    scene::{camera::Camera, mesh::RenderPath},
+    scene::{camera::Camera, graph::Graph, mesh::RenderPath},
 };
 use std::{cell::RefCell, rc::Rc};
 
 pub(crate) struct ForwardRenderer {
     render_pass_name: ImmutableString,
+    light_clusters: LightClusters,
 }
 
 pub(crate) struct ForwardRenderContext<'a, 'b> {
     pub state: &'a mut PipelineState,
     pub camera: &'b Camera,
+    pub graph: &'b Graph,
+    pub ambient_color: Color,
     pub geom_cache: &'a mut GeometryCache,
     pub texture_cache: &'a mut TextureCache,
     pub shader_cache: &'a mut ShaderCache,
@@ -40,13 +50,17 @@ pub(crate) struct ForwardRenderContext<'a, 'b> {
 }
 
 impl ForwardRenderer {
-    pub(crate) fn new() -> Self {
-        Self {
+    pub(crate) fn new(state: &mut PipelineState) -> Result<Self, FrameworkError> {
+        Ok(Self {
             render_pass_name: ImmutableString::new("Forward"),
-        }
+            light_clusters: LightClusters::new(state)?,
+        })
     }
 
-    pub(crate) fn render(&self, args: ForwardRenderContext) -> RenderPassStatistics {
+    pub(crate) fn render(
+        &mut self,
+        args: ForwardRenderContext,
+    ) -> Result<RenderPassStatistics, FrameworkError> {
         scope_profile!();
 
         let mut statistics = RenderPassStatistics::default();
@@ -54,6 +68,8 @@ impl ForwardRenderer {
         let ForwardRenderContext {
             state,
             camera,
+            graph,
+            ambient_color,
             geom_cache,
             texture_cache,
             shader_cache,
@@ -66,13 +82,37 @@ impl ForwardRenderer {
             black_dummy,
         } = args;
 
-        let initial_view_projection = camera.view_projection_matrix();
+        let is_forward = |render_path: RenderPath| {
+            render_path == RenderPath::ForwardPlus || render_path == RenderPath::Forward
+        };
 
-        for batch in batch_storage
+        // Do not waste time on light culling if there's nothing to render.
+        if !batch_storage
             .batches
             .iter()
-            .filter(|b| b.render_path == RenderPath::Forward)
+            .any(|b| is_forward(b.render_path))
         {
+            return Ok(statistics);
+        }
+
+        self.light_clusters
+            .update(state, graph, camera, viewport, ambient_color)?;
+
+        let initial_view_projection = camera.view_projection_matrix();
+
+        // Opaque meshes must be rendered first.
+        let batches = batch_storage
+            .batches
+            .iter()
+            .filter(|b| b.render_path == RenderPath::ForwardPlus)
+            .chain(
+                batch_storage
+                    .batches
+                    .iter()
+                    .filter(|b| b.render_path == RenderPath::Forward),
+            );
+
+        for batch in batches {
             let material = batch.material.lock();
             let geometry = geom_cache.get(state, &batch.data);
 
@@ -108,6 +148,7 @@ impl ForwardRenderer {
                                     camera_position: &camera.global_position(),
                                     use_pom: quality_settings.use_parallax_mapping,
                                     light_position: &Default::default(),
+                                    light_clusters: Some(&self.light_clusters),
                                     normal_dummy: normal_dummy.clone(),
                                     white_dummy: white_dummy.clone(),
                                     black_dummy: black_dummy.clone(),
@@ -119,6 +160,6 @@ impl ForwardRenderer {
             }
         }
 
-        statistics
+        Ok(statistics)
     }
 }
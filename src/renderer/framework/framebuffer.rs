@@ -215,6 +215,45 @@ impl FrameBuffer {
         self
     }
 
+    /// Reads pixels of the first color attachment from the given rectangle. Pixels are returned
+    /// as raw bytes of tightly packed RGBA `f32` values, rows go from bottom to top.
+    pub fn read_rgba32f_pixels(&self, state: &mut PipelineState, rect: Rect<i32>) -> Vec<u8> {
+        let mut bytes = vec![0u8; rect.w() as usize * rect.h() as usize * 4 * 4];
+        unsafe {
+            state.set_framebuffer(self.fbo);
+            state.gl.read_pixels(
+                rect.x(),
+                rect.y(),
+                rect.w(),
+                rect.h(),
+                glow::RGBA,
+                glow::FLOAT,
+                glow::PixelPackData::Slice(&mut bytes),
+            );
+        }
+        bytes
+    }
+
+    /// Reads pixels of the first color attachment from the given rectangle. Pixels are returned
+    /// as raw bytes of tightly packed RGBA8 values, rows go from bottom to top.
+    pub fn read_rgba8_pixels(&self, state: &mut PipelineState, rect: Rect<i32>) -> Vec<u8> {
+        let mut bytes = vec![0u8; rect.w() as usize * rect.h() as usize * 4];
+        unsafe {
+            state.set_framebuffer(self.fbo);
+            state.gl.pixel_store_i32(glow::PACK_ALIGNMENT, 1);
+            state.gl.read_pixels(
+                rect.x(),
+                rect.y(),
+                rect.w(),
+                rect.h(),
+                glow::RGBA,
+                glow::UNSIGNED_BYTE,
+                glow::PixelPackData::Slice(&mut bytes),
+            );
+        }
+        bytes
+    }
+
     /// None is possible only for back buffer.
     pub fn id(&self) -> Option<glow::Framebuffer> {
         self.fbo
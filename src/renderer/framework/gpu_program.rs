@@ -32,6 +32,13 @@ pub enum BuiltInUniform {
     CameraPosition,
     UsePOM,
     LightPosition,
+    ViewMatrix,
+    AmbientLightColor,
+    LightData,
+    LightClusters,
+    LightIndices,
+    ClusterParameters,
+    Viewport,
     // Must be last.
     Count,
 }
@@ -431,6 +438,20 @@ fn fetch_built_in_uniform_locations(
         fetch_uniform_location(state, program, "fyrox_usePOM");
     locations[BuiltInUniform::LightPosition as usize] =
         fetch_uniform_location(state, program, "fyrox_lightPosition");
+    locations[BuiltInUniform::ViewMatrix as usize] =
+        fetch_uniform_location(state, program, "fyrox_viewMatrix");
+    locations[BuiltInUniform::AmbientLightColor as usize] =
+        fetch_uniform_location(state, program, "fyrox_ambientLightColor");
+    locations[BuiltInUniform::LightData as usize] =
+        fetch_uniform_location(state, program, "fyrox_lightData");
+    locations[BuiltInUniform::LightClusters as usize] =
+        fetch_uniform_location(state, program, "fyrox_lightClusters");
+    locations[BuiltInUniform::LightIndices as usize] =
+        fetch_uniform_location(state, program, "fyrox_lightIndices");
+    locations[BuiltInUniform::ClusterParameters as usize] =
+        fetch_uniform_location(state, program, "fyrox_clusterParameters");
+    locations[BuiltInUniform::Viewport as usize] =
+        fetch_uniform_location(state, program, "fyrox_viewport");
 
     locations
 }
@@ -67,7 +67,7 @@ impl GpuTextureKind {
     }
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum PixelKind {
     F32,
     F16,
@@ -0,0 +1,100 @@
+use crate::renderer::framework::{error::FrameworkError, state::PipelineState};
+use glow::HasContext;
+use std::marker::PhantomData;
+
+/// Kind of a query.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum QueryKind {
+    /// Measures time (in nanoseconds) that GPU spent to execute commands between beginning and
+    /// end of the query.
+    TimeElapsed,
+}
+
+impl QueryKind {
+    fn into_gl_value(self) -> u32 {
+        match self {
+            Self::TimeElapsed => glow::TIME_ELAPSED,
+        }
+    }
+}
+
+/// Asynchronous GPU query. Results of a query are available after some time (usually a few
+/// frames) after the query was ended, it is possible to check if result is available without
+/// stalling the pipeline by using [`Query::try_get_result`]. Only one query of each kind can be
+/// active at a time, queries cannot be nested.
+pub struct Query {
+    state: *mut PipelineState,
+    id: glow::Query,
+    kind: QueryKind,
+    // Force compiler to not implement Send and Sync, because OpenGL is not thread-safe.
+    thread_mark: PhantomData<*const u8>,
+}
+
+impl Query {
+    pub fn new(state: &mut PipelineState, kind: QueryKind) -> Result<Self, FrameworkError> {
+        let id = unsafe { state.gl.create_query()? };
+
+        Ok(Self {
+            state,
+            id,
+            kind,
+            thread_mark: PhantomData,
+        })
+    }
+
+    pub fn kind(&self) -> QueryKind {
+        self.kind
+    }
+
+    /// Starts the query, every GPU command after this call will be measured by the query.
+    pub fn begin(&self, state: &mut PipelineState) {
+        unsafe {
+            state.gl.begin_query(self.kind.into_gl_value(), self.id);
+        }
+    }
+
+    /// Ends the query. Keep in mind, that it ends currently active query of the same kind.
+    pub fn end(&self, state: &mut PipelineState) {
+        unsafe {
+            state.gl.end_query(self.kind.into_gl_value());
+        }
+    }
+
+    /// Returns result of the query if it is available. This method never stalls the pipeline.
+    pub fn try_get_result(&self, state: &mut PipelineState) -> Option<u32> {
+        unsafe {
+            if state
+                .gl
+                .get_query_parameter_u32(self.id, glow::QUERY_RESULT_AVAILABLE)
+                != 0
+            {
+                Some(
+                    state
+                        .gl
+                        .get_query_parameter_u32(self.id, glow::QUERY_RESULT),
+                )
+            } else {
+                None
+            }
+        }
+    }
+}
+
+impl Drop for Query {
+    fn drop(&mut self) {
+        unsafe {
+            (*self.state).gl.delete_query(self.id);
+        }
+    }
+}
+
+/// Checks whether the current context supports time elapsed queries. They're part of desktop
+/// OpenGL 3.3, but OpenGL ES and WebGL 2 support them only via an extension.
+pub fn is_timer_query_supported(state: &PipelineState) -> bool {
+    let version = unsafe { state.gl.get_parameter_string(glow::VERSION) };
+    let extensions = state.gl.supported_extensions();
+
+    (!cfg!(target_arch = "wasm32") && !version.contains("OpenGL ES"))
+        || extensions.contains("GL_EXT_disjoint_timer_query")
+        || extensions.contains("EXT_disjoint_timer_query_webgl2")
+}
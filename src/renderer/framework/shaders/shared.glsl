@@ -332,4 +332,119 @@ vec4 S_SRGBToLinear(vec4 color) {
 
 float S_Luminance(vec3 x) {
     return dot(x, vec3(0.299, 0.587, 0.114));
-}
\ No newline at end of file
+}
+// Clustered lighting. Keep in sync with renderer/cluster.rs.
+const int S_CLUSTER_GRID_WIDTH = 16;
+const int S_CLUSTER_GRID_HEIGHT = 9;
+const int S_CLUSTER_GRID_DEPTH = 24;
+const int S_LIGHT_INDICES_WIDTH = 1024;
+
+struct TClusteredLightingContext {
+    // World-space position of the fragment.
+    vec3 fragmentPosition;
+    // Window-space coordinates of the fragment (gl_FragCoord.xy).
+    vec2 fragmentCoord;
+    vec3 fragmentNormal;
+    vec3 viewVector;
+    float metallic;
+    float roughness;
+    vec3 albedo;
+};
+
+// Calculates lighting from a light with given index in the light data texture.
+vec3 Internal_ClusteredLight(TClusteredLightingContext ctx, int index, in sampler2D lightData)
+{
+    vec4 positionRadius = texelFetch(lightData, ivec2(0, index), 0);
+    vec4 colorKind = texelFetch(lightData, ivec2(1, index), 0);
+    vec4 directionCone = texelFetch(lightData, ivec2(2, index), 0);
+    vec4 parameters = texelFetch(lightData, ivec2(3, index), 0);
+
+    int kind = int(colorKind.w);
+
+    TPBRContext pbr;
+    pbr.lightColor = colorKind.rgb;
+    pbr.viewVector = ctx.viewVector;
+    pbr.fragmentNormal = ctx.fragmentNormal;
+    pbr.metallic = ctx.metallic;
+    pbr.roughness = ctx.roughness;
+    pbr.albedo = ctx.albedo;
+
+    float attenuation = 1.0;
+
+    if (kind == 0)
+    {
+        // Directional light.
+        pbr.fragmentToLight = directionCone.xyz;
+    }
+    else
+    {
+        vec3 fragmentToLight = positionRadius.xyz - ctx.fragmentPosition;
+        float distance = max(length(fragmentToLight), 0.0001);
+        pbr.fragmentToLight = fragmentToLight / distance;
+
+        attenuation = S_LightDistanceAttenuation(distance, positionRadius.w);
+
+        float directionCos = dot(directionCone.xyz, pbr.fragmentToLight);
+        if (kind == 2)
+        {
+            // Spot light.
+            attenuation *= smoothstep(directionCone.w, parameters.x, directionCos);
+        }
+        else if (kind == 3 && parameters.y < 0.5)
+        {
+            // One-sided area light.
+            attenuation *= step(0.0, directionCos);
+        }
+    }
+
+    return attenuation * S_PBR_CalculateLight(pbr);
+}
+
+// Calculates lighting of a fragment from every light that affects its cluster. Every other
+// parameter should be taken from respective built-in uniforms:
+//
+// uniform sampler2D fyrox_lightData;
+// uniform sampler2D fyrox_lightClusters;
+// uniform sampler2D fyrox_lightIndices;
+// uniform mat4 fyrox_viewMatrix;
+// uniform vec4 fyrox_clusterParameters;
+// uniform vec4 fyrox_viewport;
+vec3 S_ClusteredLighting(
+    TClusteredLightingContext ctx,
+    in sampler2D lightData,
+    in sampler2D lightClusters,
+    in sampler2D lightIndices,
+    mat4 viewMatrix,
+    vec4 clusterParameters,
+    vec4 viewport)
+{
+    vec3 lighting = vec3(0.0);
+
+    // Directional lights affect every cluster and they're stored first.
+    int directionalLightCount = int(clusterParameters.z);
+    for (int i = 0; i < directionalLightCount; ++i)
+    {
+        lighting += Internal_ClusteredLight(ctx, i, lightData);
+    }
+
+    float viewDepth = -(viewMatrix * vec4(ctx.fragmentPosition, 1.0)).z;
+    int slice = int(floor(log(max(viewDepth, 0.0001)) * clusterParameters.x + clusterParameters.y));
+    slice = clamp(slice, 0, S_CLUSTER_GRID_DEPTH - 1);
+
+    vec2 screenPosition = clamp((ctx.fragmentCoord - viewport.xy) / viewport.zw, vec2(0.0), vec2(0.9999));
+    ivec2 tile = ivec2(screenPosition * vec2(S_CLUSTER_GRID_WIDTH, S_CLUSTER_GRID_HEIGHT));
+
+    vec2 cluster = texelFetch(lightClusters, ivec2(tile.x + tile.y * S_CLUSTER_GRID_WIDTH, slice), 0).xy;
+    int offset = int(cluster.x);
+    int count = int(cluster.y);
+
+    for (int i = 0; i < count; ++i)
+    {
+        int linearIndex = offset + i;
+        ivec2 indexCoord = ivec2(linearIndex % S_LIGHT_INDICES_WIDTH, linearIndex / S_LIGHT_INDICES_WIDTH);
+        int lightIndex = int(texelFetch(lightIndices, indexCoord, 0).r);
+        lighting += Internal_ClusteredLight(ctx, lightIndex, lightData);
+    }
+
+    return lighting;
+}
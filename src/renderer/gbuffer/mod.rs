@@ -6,6 +6,11 @@
 //! RT3: RGBA8 - Metallic (x) + Roughness (y) + Ambient Occlusion (z)
 //! RT4: R8UI - Decal mask (x)
 //!
+//! Motion vectors are stored in a separate frame buffer with its own depth, they're rendered
+//! only when temporal anti-aliasing is enabled:
+//!
+//! RT0: RGBA16F - Screen-space motion (xy) + Motion mask (w)
+//!
 //! Every alpha channel is used for layer blending for terrains. This is inefficient, but for
 //! now I don't know better solution.
 
@@ -18,6 +23,7 @@ use crate::{
         algebra::{Matrix4, Vector2},
         color::Color,
         math::Rect,
+        pool::Handle,
         scope_profile,
     },
     renderer::{
@@ -34,14 +40,21 @@ use crate::{
             },
             state::{BlendFactor, BlendFunc, PipelineState},
         },
-        gbuffer::decal::DecalShader,
+        gbuffer::{
+            decal::DecalShader,
+            motion::{InstanceTransform, MotionVectorShader},
+        },
         GeometryCache, MaterialContext, RenderPassStatistics, TextureCache,
     },
-    scene::{camera::Camera, graph::Graph, mesh::surface::SurfaceData, mesh::RenderPath},
+    scene::{
+        camera::Camera, graph::Graph, mesh::surface::SurfaceData, mesh::RenderPath, node::Node,
+    },
 };
-use std::{cell::RefCell, rc::Rc};
+use fxhash::FxHashMap;
+use std::{cell::RefCell, rc::Rc, sync::Arc};
 
 mod decal;
+mod motion;
 
 pub struct GBuffer {
     framebuffer: FrameBuffer,
@@ -51,6 +64,11 @@ pub struct GBuffer {
     cube: GeometryBuffer,
     decal_shader: DecalShader,
     render_pass_name: ImmutableString,
+    velocity_framebuffer: FrameBuffer,
+    motion_vector_shader: MotionVectorShader,
+    // Transforms of surface instances from previous frame. Every surface instance is identified
+    // by its owner node and the surface data it uses.
+    motion_history: FxHashMap<(Handle<Node>, usize), InstanceTransform>,
 }
 
 pub(crate) struct GBufferRenderContext<'a, 'b> {
@@ -69,6 +87,15 @@ pub(crate) struct GBufferRenderContext<'a, 'b> {
     pub graph: &'b Graph,
 }
 
+pub(crate) struct MotionVectorRenderContext<'a, 'b> {
+    pub state: &'a mut PipelineState,
+    pub camera: &'b Camera,
+    pub geom_cache: &'a mut GeometryCache,
+    pub batch_storage: &'a BatchStorage,
+    /// Unjittered view-projection matrix of previous frame.
+    pub previous_view_projection: Option<Matrix4<f32>>,
+}
+
 impl GBuffer {
     pub fn new(
         state: &mut PipelineState,
@@ -195,6 +222,46 @@ impl GBuffer {
             ],
         )?;
 
+        let mut velocity_depth_texture = GpuTexture::new(
+            state,
+            GpuTextureKind::Rectangle { width, height },
+            PixelKind::D24S8,
+            MinificationFilter::Nearest,
+            MagnificationFilter::Nearest,
+            1,
+            None,
+        )?;
+        velocity_depth_texture
+            .bind_mut(state, 0)
+            .set_wrap(Coordinate::S, WrapMode::ClampToEdge)
+            .set_wrap(Coordinate::T, WrapMode::ClampToEdge);
+
+        let mut velocity_texture = GpuTexture::new(
+            state,
+            GpuTextureKind::Rectangle { width, height },
+            PixelKind::RGBA16F,
+            MinificationFilter::Nearest,
+            MagnificationFilter::Nearest,
+            1,
+            None,
+        )?;
+        velocity_texture
+            .bind_mut(state, 0)
+            .set_wrap(Coordinate::S, WrapMode::ClampToEdge)
+            .set_wrap(Coordinate::T, WrapMode::ClampToEdge);
+
+        let velocity_framebuffer = FrameBuffer::new(
+            state,
+            Some(Attachment {
+                kind: AttachmentKind::DepthStencil,
+                texture: Rc::new(RefCell::new(velocity_depth_texture)),
+            }),
+            vec![Attachment {
+                kind: AttachmentKind::Color,
+                texture: Rc::new(RefCell::new(velocity_texture)),
+            }],
+        )?;
+
         let decal_framebuffer = FrameBuffer::new(
             state,
             None,
@@ -222,6 +289,9 @@ impl GBuffer {
             ),
             decal_framebuffer,
             render_pass_name: ImmutableString::new("GBuffer"),
+            velocity_framebuffer,
+            motion_vector_shader: MotionVectorShader::new(state)?,
+            motion_history: Default::default(),
         })
     }
 
@@ -253,6 +323,140 @@ impl GBuffer {
         self.framebuffer.color_attachments()[4].texture.clone()
     }
 
+    /// Screen-space motion of every pixel between previous and current frames. The texture is
+    /// filled only when temporal anti-aliasing is enabled.
+    pub fn velocity_texture(&self) -> Rc<RefCell<GpuTexture>> {
+        self.velocity_framebuffer.color_attachments()[0]
+            .texture
+            .clone()
+    }
+
+    /// Forgets transforms of surface instances from previous frame, so motion of every surface
+    /// will be calculated using only the motion of camera on next frame.
+    pub(crate) fn clear_motion_history(&mut self) {
+        self.motion_history.clear();
+    }
+
+    /// Renders per-object motion vectors of deferred surfaces. Previous transforms (world and
+    /// bone matrices) of every surface instance are taken from the history, which is then
+    /// replaced with the current transforms.
+    #[must_use]
+    pub(crate) fn render_motion_vectors(
+        &mut self,
+        args: MotionVectorRenderContext,
+    ) -> RenderPassStatistics {
+        scope_profile!();
+
+        let mut statistics = RenderPassStatistics::default();
+
+        let MotionVectorRenderContext {
+            state,
+            camera,
+            geom_cache,
+            batch_storage,
+            previous_view_projection,
+        } = args;
+
+        let viewport = Rect::new(0, 0, self.width, self.height);
+        self.velocity_framebuffer.clear(
+            state,
+            viewport,
+            Some(Color::from_rgba(0, 0, 0, 0)),
+            Some(1.0),
+            Some(0),
+        );
+
+        let initial_view_projection = camera.view_projection_matrix();
+        let current_view_projection = camera.unjittered_view_projection_matrix();
+        let previous_view_projection = previous_view_projection.unwrap_or(current_view_projection);
+
+        let shader = &self.motion_vector_shader;
+        let mut history = FxHashMap::default();
+
+        for batch in batch_storage
+            .batches
+            .iter()
+            .filter(|b| b.render_path == RenderPath::Deferred)
+        {
+            let geometry = geom_cache.get(state, &batch.data);
+            let data_id = Arc::as_ptr(&batch.data) as usize;
+
+            for instance in batch.instances.iter() {
+                if !camera.visibility_cache.is_visible(instance.owner) {
+                    continue;
+                }
+
+                let key = (instance.owner, data_id);
+                let previous = self.motion_history.get(&key);
+
+                let previous_world_transform = previous
+                    .map(|p| p.world_transform)
+                    .unwrap_or(instance.world_transform);
+                let previous_bone_matrices = previous
+                    .map(|p| &p.bone_matrices)
+                    .filter(|bones| bones.len() == instance.bone_matrices.len())
+                    .unwrap_or(&instance.bone_matrices);
+
+                let view_projection = if instance.depth_offset != 0.0 {
+                    let mut projection = camera.projection_matrix();
+                    projection[14] -= instance.depth_offset;
+                    projection * camera.view_matrix()
+                } else {
+                    initial_view_projection
+                };
+
+                statistics += self.velocity_framebuffer.draw(
+                    geometry,
+                    state,
+                    viewport,
+                    &shader.program,
+                    &DrawParameters {
+                        cull_face: None,
+                        color_write: Default::default(),
+                        depth_write: true,
+                        stencil_test: None,
+                        depth_test: true,
+                        blend: None,
+                        stencil_op: Default::default(),
+                    },
+                    |mut program_binding| {
+                        program_binding
+                            .set_matrix4(
+                                &shader.world_view_projection,
+                                &(view_projection * instance.world_transform),
+                            )
+                            .set_matrix4(
+                                &shader.current_world_view_projection,
+                                &(current_view_projection * instance.world_transform),
+                            )
+                            .set_matrix4(
+                                &shader.previous_world_view_projection,
+                                &(previous_view_projection * previous_world_transform),
+                            )
+                            .set_bool(&shader.use_skeletal_animation, batch.is_skinned)
+                            .set_matrix4_array(&shader.bone_matrices, &instance.bone_matrices)
+                            .set_matrix4_array(
+                                &shader.previous_bone_matrices,
+                                previous_bone_matrices,
+                            );
+                    },
+                );
+
+                history.insert(
+                    key,
+                    InstanceTransform {
+                        world_transform: instance.world_transform,
+                        bone_matrices: instance.bone_matrices.clone(),
+                    },
+                );
+            }
+        }
+
+        self.motion_history = history;
+
+        statistics
+    }
+
     #[must_use]
     pub(crate) fn fill(&mut self, args: GBufferRenderContext) -> RenderPassStatistics {
         scope_profile!();
@@ -319,6 +523,7 @@ impl GBuffer {
                                 camera_position: &camera.global_position(),
                                 use_pom: use_parallax_mapping,
                                 light_position: &Default::default(),
+                                light_clusters: None,
                                 normal_dummy: normal_dummy.clone(),
                                 white_dummy: white_dummy.clone(),
                                 black_dummy: black_dummy.clone(),
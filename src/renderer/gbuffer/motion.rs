@@ -0,0 +1,50 @@
+use crate::core::{algebra::Matrix4, arrayvec::ArrayVec, sstorage::ImmutableString};
+use crate::renderer::{
+    batch::BONE_MATRICES_COUNT,
+    framework::{
+        error::FrameworkError,
+        gpu_program::{GpuProgram, UniformLocation},
+        state::PipelineState,
+    },
+};
+
+pub struct MotionVectorShader {
+    pub world_view_projection: UniformLocation,
+    pub current_world_view_projection: UniformLocation,
+    pub previous_world_view_projection: UniformLocation,
+    pub use_skeletal_animation: UniformLocation,
+    pub bone_matrices: UniformLocation,
+    pub previous_bone_matrices: UniformLocation,
+    pub program: GpuProgram,
+}
+
+impl MotionVectorShader {
+    pub fn new(state: &mut PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("../shaders/motion_vectors_fs.glsl");
+        let vertex_source = include_str!("../shaders/motion_vectors_vs.glsl");
+
+        let program =
+            GpuProgram::from_source(state, "MotionVectorShader", vertex_source, fragment_source)?;
+        Ok(Self {
+            world_view_projection: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            current_world_view_projection: program
+                .uniform_location(state, &ImmutableString::new("currentWorldViewProjection"))?,
+            previous_world_view_projection: program
+                .uniform_location(state, &ImmutableString::new("previousWorldViewProjection"))?,
+            use_skeletal_animation: program
+                .uniform_location(state, &ImmutableString::new("useSkeletalAnimation"))?,
+            bone_matrices: program
+                .uniform_location(state, &ImmutableString::new("boneMatrices"))?,
+            previous_bone_matrices: program
+                .uniform_location(state, &ImmutableString::new("previousBoneMatrices"))?,
+            program,
+        })
+    }
+}
+
+/// Transform of a surface instance at the moment of rendering.
+pub struct InstanceTransform {
+    pub world_transform: Matrix4<f32>,
+    pub bone_matrices: ArrayVec<Matrix4<f32>, BONE_MATRICES_COUNT>,
+}
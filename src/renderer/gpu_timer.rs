@@ -0,0 +1,216 @@
+//! GPU timer measures time that GPU spent to execute each pass of a frame. It uses asynchronous
+//! time elapsed queries, so results are available with a latency of a few frames, but the
+//! pipeline is never stalled.
+//!
+//! Time elapsed queries cannot be nested, so instead of begin/end pairs the timer uses "current
+//! pass" approach - every switch to another pass ends currently active query and starts a new
+//! one. Time of a pass is a sum of all its queries in a frame, which allows passes to interleave
+//! (for example shadow maps are rendered in the middle of lighting pass).
+//!
+//! Some passes render other passes as their part (for example reflection probes are captured
+//! using G-Buffer and lighting passes), such nested passes must not switch the timer, this is
+//! done by suppressing switches for the duration of the outer pass.
+//!
+//! Every custom pass is timed separately, custom passes are identified by their names.
+
+use crate::renderer::{
+    framework::{
+        query::{is_timer_query_supported, Query, QueryKind},
+        state::PipelineState,
+    },
+    GpuTimeStatistics,
+};
+use std::collections::VecDeque;
+
+/// Maximum amount of frames which queries can wait for results, queries of older frames will
+/// be discarded.
+const MAX_PENDING_FRAMES: usize = 4;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum GpuPass {
+    GBuffer,
+    Shadows,
+    Ssao,
+    Lighting,
+    Reflections,
+    Forward,
+    /// Index of the name of the pass, see [`GpuTimer::custom_pass`].
+    Custom(usize),
+    Bloom,
+    Hdr,
+    AntiAliasing,
+    Ui,
+    Other,
+}
+
+impl GpuTimeStatistics {
+    fn pass_time_mut(&mut self, pass: GpuPass) -> &mut f32 {
+        match pass {
+            GpuPass::GBuffer => &mut self.gbuffer,
+            GpuPass::Shadows => &mut self.shadows,
+            GpuPass::Ssao => &mut self.ssao,
+            GpuPass::Lighting => &mut self.lighting,
+            GpuPass::Reflections => &mut self.reflections,
+            GpuPass::Forward => &mut self.forward,
+            GpuPass::Custom(_) => &mut self.custom,
+            GpuPass::Bloom => &mut self.bloom,
+            GpuPass::Hdr => &mut self.hdr,
+            GpuPass::AntiAliasing => &mut self.anti_aliasing,
+            GpuPass::Ui => &mut self.ui,
+            GpuPass::Other => &mut self.other,
+        }
+    }
+
+    fn add_custom_pass_time(&mut self, name: &str, time: f32) {
+        match self.custom_passes.iter_mut().find(|(n, _)| n == name) {
+            Some((_, total)) => *total += time,
+            None => self.custom_passes.push((name.to_owned(), time)),
+        }
+    }
+}
+
+pub(crate) struct GpuTimer {
+    supported: bool,
+    active: bool,
+    suppressed: usize,
+    custom_pass_names: Vec<String>,
+    free_queries: Vec<Query>,
+    current_frame: Vec<(GpuPass, Query)>,
+    pending_frames: VecDeque<Vec<(GpuPass, Query)>>,
+    timings: GpuTimeStatistics,
+}
+
+impl GpuTimer {
+    pub fn new(state: &PipelineState) -> Self {
+        Self {
+            supported: is_timer_query_supported(state),
+            active: false,
+            suppressed: 0,
+            custom_pass_names: Default::default(),
+            free_queries: Default::default(),
+            current_frame: Default::default(),
+            pending_frames: Default::default(),
+            timings: Default::default(),
+        }
+    }
+
+    /// Collects results of previous frames, must be called before any other GPU work in a frame.
+    pub fn begin_frame(&mut self, state: &mut PipelineState) {
+        if !self.supported {
+            return;
+        }
+
+        while let Some(frame) = self.pending_frames.front() {
+            let mut timings = GpuTimeStatistics {
+                available: true,
+                ..Default::default()
+            };
+
+            let mut ready = true;
+            for (pass, query) in frame.iter() {
+                if let Some(nanoseconds) = query.try_get_result(state) {
+                    let time = nanoseconds as f32 / 1_000_000.0;
+                    *timings.pass_time_mut(*pass) += time;
+                    if let GpuPass::Custom(index) = *pass {
+                        timings.add_custom_pass_time(&self.custom_pass_names[index], time);
+                    }
+                } else {
+                    ready = false;
+                    break;
+                }
+            }
+
+            if ready {
+                self.timings = timings;
+            } else if self.pending_frames.len() <= MAX_PENDING_FRAMES {
+                break;
+            }
+
+            if let Some(frame) = self.pending_frames.pop_front() {
+                self.free_queries
+                    .extend(frame.into_iter().map(|(_, query)| query));
+            }
+        }
+    }
+
+    /// Returns a pass that is used to measure time of a custom pass with the given name.
+    pub fn custom_pass(&mut self, name: &str) -> GpuPass {
+        let index = match self.custom_pass_names.iter().position(|n| n == name) {
+            Some(index) => index,
+            None => {
+                self.custom_pass_names.push(name.to_owned());
+                self.custom_pass_names.len() - 1
+            }
+        };
+        GpuPass::Custom(index)
+    }
+
+    /// Suppresses all switches until [`Self::resume`] is called, time of nested passes will be
+    /// accounted to the current pass. Calls can be nested.
+    pub fn suppress(&mut self) {
+        self.suppressed += 1;
+    }
+
+    /// Resumes switches suppressed by [`Self::suppress`].
+    pub fn resume(&mut self) {
+        self.suppressed = self.suppressed.saturating_sub(1);
+    }
+
+    /// Ends measurement of current pass (if any) and starts measurement of the given pass. Does
+    /// nothing if switches are suppressed.
+    pub fn switch(&mut self, state: &mut PipelineState, pass: GpuPass) {
+        if !self.supported || self.suppressed > 0 {
+            return;
+        }
+
+        self.end_active(state);
+
+        let query = match self.free_queries.pop() {
+            Some(query) => query,
+            None => match Query::new(state, QueryKind::TimeElapsed) {
+                Ok(query) => query,
+                Err(_) => return,
+            },
+        };
+
+        query.begin(state);
+        self.active = true;
+        self.current_frame.push((pass, query));
+    }
+
+    /// Ends measurement of current pass (if any) and submits queries of the frame.
+    pub fn end_frame(&mut self, state: &mut PipelineState) {
+        if !self.supported {
+            return;
+        }
+
+        self.end_active(state);
+
+        if !self.current_frame.is_empty() {
+            self.pending_frames
+                .push_back(std::mem::take(&mut self.current_frame));
+        }
+    }
+
+    /// Ends measurement of current pass (if any) and discards all queries of the frame.
+    pub fn discard_frame(&mut self, state: &mut PipelineState) {
+        self.end_active(state);
+
+        self.free_queries
+            .extend(self.current_frame.drain(..).map(|(_, query)| query));
+    }
+
+    fn end_active(&mut self, state: &mut PipelineState) {
+        if self.active {
+            if let Some((_, query)) = self.current_frame.last() {
+                query.end(state);
+            }
+            self.active = false;
+        }
+    }
+
+    /// Returns the latest available timings.
+    pub fn timings(&self) -> GpuTimeStatistics {
+        self.timings.clone()
+    }
+}
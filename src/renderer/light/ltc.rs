@@ -0,0 +1,762 @@
+//! Look-up tables of linearly transformed cosines (LTC) that are used to shade area lights.
+//!
+//! Each LTC is a clamped cosine distribution transformed by a 3x3 matrix, which approximates GGX
+//! BRDF (multiplied by cosine) for some roughness and view angle. Integral of such distribution
+//! over a polygon (or an ellipse) has closed form, which makes it possible to calculate lighting
+//! from area lights in real-time.
+//!
+//! The tables were obtained by fitting LTC to GGX BRDF (with Smith shadowing-masking function)
+//! using the method from "Real-Time Polygonal-Light Shading with Linearly Transformed Cosines"
+//! by Eric Heitz et al. Both tables are indexed by roughness (columns) and by `sqrt(1 - NdotV)`
+//! (rows).
+
+use crate::renderer::framework::{
+    error::FrameworkError,
+    gpu_texture::{
+        Coordinate, GpuTexture, GpuTextureKind, MagnificationFilter, MinificationFilter, PixelKind,
+        WrapMode,
+    },
+    state::PipelineState,
+};
+use std::{cell::RefCell, rc::Rc};
+
+/// Size of each side of the tables.
+pub const LTC_TABLE_SIZE: usize = 32;
+
+/// Non-zero elements of inverse LTC matrices, normalized by the center element of each matrix
+/// (which is always 1.0 after normalization). Elements are stored in `m00, m02, m20, m22` order.
+const LTC_INV_MATRICES: [f32; LTC_TABLE_SIZE * LTC_TABLE_SIZE * 4] = [
+    1.0, 0.0, 0.0, 0.00002, 1.0, 0.0, 0.0, 0.002081, 1.0, 0.0, 0.0, 0.008325, 1.0, 0.0, 0.0,
+    0.018731, 1.0, 0.0, 0.0, 0.033301, 1.0, 0.0, 0.0, 0.05204, 1.0, 0.0, 0.0, 0.074953, 1.0, 0.0,
+    0.0, 0.102053, 1.0, 0.0, 0.0, 0.133348, 1.0, 0.0, 0.0, 0.168368, 1.0, 0.0, 0.0, 0.206579, 1.0,
+    0.0, 0.0, 0.250073, 1.0, 0.0, 0.0, 0.296138, 1.0, 0.0, 0.0, 0.345932, 1.0, 0.0, 0.0, 0.397628,
+    1.0, 0.0, 0.0, 0.452862, 1.0, 0.0, 0.0, 0.50943, 1.0, 0.0, 0.0, 0.566859, 1.0, 0.0, 0.0,
+    0.624428, 1.0, 0.0, 0.0, 0.682028, 1.0, 0.0, 0.0, 0.737697, 1.0, 0.0, 0.0, 0.791624, 1.0, 0.0,
+    0.0, 0.842501, 1.0, 0.0, 0.0, 0.889936, 1.0, 0.0, 0.0, 0.93359, 1.0, 0.0, 0.0, 0.973197, 1.0,
+    0.0, 0.0, 1.008664, 1.0, 0.0, 0.0, 1.039996, 1.0, 0.0, 0.0, 1.067405, 1.0, 0.0, 0.0, 1.091113,
+    1.0, 0.0, 0.0, 1.111257, 1.0, 0.0, 0.0, 1.128113, 0.99792, 0.04556, -0.000001, 0.00002,
+    0.99792, 0.04556, -0.000095, 0.002077, 0.99792, 0.04556, -0.000379, 0.008307, 0.99792, 0.04556,
+    -0.000853, 0.018692, 0.997921, 0.04556, -0.001517, 0.033232, 0.997922, 0.045561, -0.002371,
+    0.051931, 0.997924, 0.045561, -0.003415, 0.074797, 0.997929, 0.04556, -0.004649, 0.101841,
+    0.997933, 0.04556, -0.00607, 0.133071, 0.997946, 0.045513, -0.007186, 0.167895, 0.997952,
+    0.045556, -0.0094, 0.206151, 0.99797, 0.04554, -0.011307, 0.249551, 0.997998, 0.045375,
+    -0.012489, 0.295481, 0.99802, 0.045432, -0.015294, 0.345193, 0.998059, 0.045245, -0.01703,
+    0.397161, 0.998125, 0.04491, -0.018547, 0.452009, 0.998169, 0.044525, -0.020323, 0.508431,
+    0.998265, 0.043948, -0.021875, 0.565926, 0.998359, 0.043106, -0.023104, 0.623726, 0.998368,
+    0.041863, -0.023756, 0.680898, 0.998442, 0.040209, -0.023947, 0.736922, 0.998486, 0.038094,
+    -0.023659, 0.790683, 0.998543, 0.035438, -0.022683, 0.841694, 0.998619, 0.03235, -0.021278,
+    0.889287, 0.998754, 0.02883, -0.019432, 0.933078, 0.998942, 0.02496, -0.017174, 0.97283,
+    0.999161, 0.020829, -0.014554, 1.008412, 0.999401, 0.016586, -0.011785, 1.039907, 0.999622,
+    0.012322, -0.008912, 1.067405, 0.999801, 0.008079, -0.005954, 1.091102, 0.999931, 0.003941,
+    -0.002962, 1.111273, 1.000004, -0.000025, 0.000008, 1.128154, 0.991692, 0.090765, -0.000002,
+    0.00002, 0.991693, 0.090765, -0.000189, 0.002064, 0.991693, 0.090765, -0.000756, 0.008256,
+    0.991694, 0.090765, -0.0017, 0.018575, 0.991696, 0.090765, -0.003023, 0.033025, 0.991701,
+    0.090766, -0.004723, 0.051608, 0.99171, 0.090766, -0.006803, 0.074331, 0.991726, 0.090767,
+    -0.009261, 0.101207, 0.991749, 0.090767, -0.01209, 0.132241, 0.99179, 0.090697, -0.014521,
+    0.166785, 0.991828, 0.090762, -0.018722, 0.204872, 0.991893, 0.090731, -0.022499, 0.247985,
+    0.991992, 0.090513, -0.025512, 0.293856, 0.992095, 0.090514, -0.030366, 0.34298, 0.992272,
+    0.090136, -0.033832, 0.395158, 0.992471, 0.089648, -0.037549, 0.449566, 0.992745, 0.08892,
+    -0.041116, 0.505686, 0.993013, 0.08774, -0.044026, 0.56298, 0.993264, 0.085937, -0.046051,
+    0.62068, 0.993833, 0.083477, -0.047348, 0.67808, 0.994208, 0.080167, -0.047704, 0.73413,
+    0.994228, 0.075911, -0.047043, 0.788104, 0.994547, 0.070681, -0.04527, 0.839419, 0.995289,
+    0.064516, -0.042475, 0.887471, 0.995839, 0.057514, -0.038778, 0.93166, 0.99628, 0.049813,
+    -0.03426, 0.971776, 0.996839, 0.041637, -0.029166, 1.007717, 0.997648, 0.033157, -0.023605,
+    1.0395, 0.998506, 0.024559, -0.017721, 1.067239, 0.999203, 0.016063, -0.011726, 1.091105,
+    0.999711, 0.007828, -0.005789, 1.111411, 0.999994, -0.000034, 0.000024, 1.128424, 0.981359,
+    0.13526, -0.000003, 0.00002, 0.981357, 0.13526, -0.000281, 0.002042, 0.981358, 0.13526,
+    -0.001126, 0.00817, 0.98136, 0.13526, -0.002534, 0.018382, 0.981365, 0.135261, -0.004504,
+    0.032681, 0.981376, 0.135262, -0.007038, 0.05107, 0.981396, 0.135264, -0.010137, 0.073557,
+    0.98143, 0.135267, -0.0138, 0.100154, 0.981484, 0.135269, -0.01801, 0.130864, 0.981566,
+    0.135198, -0.021948, 0.165101, 0.981659, 0.135271, -0.02789, 0.202748, 0.981805, 0.135223,
+    -0.033447, 0.245378, 0.982024, 0.134989, -0.038439, 0.29102, 0.982273, 0.134875, -0.044919,
+    0.339353, 0.982645, 0.134378, -0.050377, 0.391434, 0.983084, 0.133738, -0.056188, 0.44526,
+    0.983713, 0.132567, -0.06108, 0.501162, 0.984402, 0.130784, -0.065277, 0.558305, 0.984989,
+    0.128174, -0.068555, 0.615886, 0.986092, 0.124559, -0.070584, 0.673188, 0.987318, 0.119676,
+    -0.071169, 0.72942, 0.988437, 0.113303, -0.070075, 0.783894, 0.989545, 0.10551, -0.067577,
+    0.835672, 0.990602, 0.096277, -0.063421, 0.884281, 0.991634, 0.085805, -0.057903, 0.929059,
+    0.99276, 0.074376, -0.051275, 0.969941, 0.994537, 0.062227, -0.043675, 1.006512, 0.995518,
+    0.049593, -0.035324, 1.038777, 0.996744, 0.036809, -0.026596, 1.066927, 0.998232, 0.024096,
+    -0.01765, 1.091086, 0.999339, 0.011753, -0.008709, 1.111648, 0.999989, -0.000054, 0.000032,
+    1.128888, 0.966979, 0.178693, -0.000004, 0.000019, 0.966978, 0.178693, -0.000372, 0.002012,
+    0.966979, 0.178693, -0.001488, 0.00805, 0.966982, 0.178693, -0.003347, 0.018112, 0.966992,
+    0.178694, -0.00595, 0.032202, 0.967011, 0.178697, -0.009298, 0.050322, 0.967048, 0.178702,
+    -0.013391, 0.07248, 0.967107, 0.178709, -0.018228, 0.098689, 0.967198, 0.178717, -0.023777,
+    0.128948, 0.967342, 0.178655, -0.029227, 0.162753, 0.96751, 0.178717, -0.03668, 0.199932,
+    0.967773, 0.178668, -0.043995, 0.241735, 0.968141, 0.178446, -0.051069, 0.286973, 0.968608,
+    0.178193, -0.058943, 0.334848, 0.969235, 0.177635, -0.06648, 0.386073, 0.97006, 0.176783,
+    -0.073996, 0.439424, 0.971109, 0.175272, -0.080515, 0.495027, 0.972986, 0.173093, -0.086197,
+    0.551814, 0.974485, 0.169709, -0.090552, 0.609236, 0.976319, 0.164903, -0.093175, 0.666522,
+    0.97871, 0.158442, -0.093937, 0.723107, 0.98126, 0.15008, -0.092731, 0.778018, 0.984631,
+    0.139782, -0.089422, 0.830632, 0.987328, 0.127517, -0.084016, 0.880078, 0.988828, 0.113598,
+    -0.076785, 0.925635, 0.98994, 0.098449, -0.068032, 0.967203, 0.990968, 0.082387, -0.057934,
+    1.004683, 0.993149, 0.065789, -0.046943, 1.037857, 0.995043, 0.048889, -0.03531, 1.066476,
+    0.996973, 0.032043, -0.023424, 1.091115, 0.998859, 0.015625, -0.011573, 1.111982, 1.000013,
+    -0.000064, 0.000043, 1.129553, 0.948648, 0.220716, -0.000004, 0.000019, 0.948647, 0.220715,
+    -0.000459, 0.001974, 0.948647, 0.220715, -0.001837, 0.007897, 0.948653, 0.220716, -0.004134,
+    0.017769, 0.948667, 0.220719, -0.00735, 0.031592, 0.948697, 0.220724, -0.011484, 0.049369,
+    0.948753, 0.220733, -0.016538, 0.071108, 0.948843, 0.220748, -0.02251, 0.096822, 0.948984,
+    0.220763, -0.029328, 0.126499, 0.949202, 0.220719, -0.036278, 0.159746, 0.949485, 0.220759,
+    -0.045023, 0.196504, 0.949872, 0.220727, -0.054077, 0.237107, 0.950436, 0.220541, -0.063225,
+    0.281741, 0.951201, 0.22021, -0.072653, 0.329105, 0.952118, 0.219641, -0.082304, 0.379271,
+    0.953785, 0.218621, -0.091195, 0.432258, 0.955311, 0.21683, -0.099382, 0.487076, 0.958325,
+    0.214335, -0.106571, 0.543382, 0.961799, 0.210336, -0.11183, 0.600757, 0.966543, 0.204707,
+    -0.115153, 0.658244, 0.971094, 0.196751, -0.116218, 0.715162, 0.9756, 0.186307, -0.114772,
+    0.770663, 0.980051, 0.173294, -0.11063, 0.823818, 0.982546, 0.157921, -0.104138, 0.874179,
+    0.985866, 0.140653, -0.095285, 0.921319, 0.987892, 0.121903, -0.084478, 0.963939, 0.989144,
+    0.10212, -0.072009, 1.002245, 0.990092, 0.081619, -0.058328, 1.036302, 0.992356, 0.060788,
+    -0.043935, 1.065815, 0.995567, 0.039909, -0.029182, 1.0911, 0.998247, 0.019487, -0.014417,
+    1.112461, 1.00001, -0.000067, 0.000059, 1.130412, 0.926481, 0.260986, -0.000005, 0.000019,
+    0.926481, 0.260986, -0.000543, 0.001928, 0.926479, 0.260985, -0.002173, 0.007713, 0.926486,
+    0.260987, -0.004888, 0.017354, 0.926506, 0.260991, -0.00869, 0.030854, 0.926549, 0.261,
+    -0.013579, 0.048216, 0.926628, 0.261016, -0.019553, 0.069449, 0.926757, 0.261041, -0.02661,
+    0.094566, 0.926959, 0.261065, -0.034547, 0.123517, 0.927261, 0.261052, -0.043039, 0.156101,
+    0.92767, 0.261091, -0.053115, 0.192226, 0.92821, 0.261104, -0.063908, 0.231632, 0.929007,
+    0.260929, -0.074737, 0.275367, 0.930187, 0.260593, -0.085802, 0.322034, 0.93148, 0.260027,
+    -0.097373, 0.371009, 0.934039, 0.258902, -0.107598, 0.423446, 0.937318, 0.257216, -0.117629,
+    0.477475, 0.941561, 0.254258, -0.125752, 0.533664, 0.947251, 0.249804, -0.13211, 0.590862,
+    0.95461, 0.243325, -0.136155, 0.648367, 0.962933, 0.233995, -0.137561, 0.705924, 0.971482,
+    0.221507, -0.136043, 0.76262, 0.978609, 0.20587, -0.131392, 0.816725, 0.981779, 0.187337,
+    -0.123708, 0.867533, 0.983239, 0.166658, -0.113214, 0.915337, 0.98675, 0.144574, -0.100557,
+    0.959777, 0.987262, 0.121203, -0.085756, 0.999219, 0.988672, 0.097074, -0.069582, 1.034536,
+    0.99035, 0.072449, -0.052445, 1.064982, 0.993561, 0.047694, -0.034877, 1.091081, 0.997441,
+    0.023314, -0.017256, 1.113002, 1.000008, -0.000053, 0.000052, 1.131467, 0.900623, 0.299168,
+    -0.000006, 0.000018, 0.900623, 0.299168, -0.000623, 0.001874, 0.90062, 0.299167, -0.00249,
+    0.007497, 0.900623, 0.299167, -0.005603, 0.01687, 0.90065, 0.299174, -0.009961, 0.029993,
+    0.900708, 0.299188, -0.015564, 0.046871, 0.900813, 0.299214, -0.02241, 0.067514, 0.900987,
+    0.299255, -0.030494, 0.091935, 0.901255, 0.299288, -0.039451, 0.120048, 0.90165, 0.299317,
+    -0.049443, 0.151837, 0.902205, 0.299375, -0.060824, 0.187127, 0.90291, 0.299445, -0.073254,
+    0.225287, 0.903996, 0.299297, -0.085562, 0.267929, 0.905619, 0.298988, -0.098172, 0.313677,
+    0.907718, 0.298464, -0.111212, 0.361771, 0.911253, 0.297474, -0.123327, 0.413185, 0.916254,
+    0.29578, -0.134556, 0.466654, 0.923323, 0.29291, -0.144009, 0.522329, 0.932912, 0.288352,
+    -0.151601, 0.579388, 0.943401, 0.280983, -0.156424, 0.637274, 0.954096, 0.270433, -0.158049,
+    0.694918, 0.963283, 0.256195, -0.156254, 0.751338, 0.970494, 0.237897, -0.151093, 0.80657,
+    0.98024, 0.21689, -0.142457, 0.859568, 0.986677, 0.192962, -0.129903, 0.903513, 0.984253,
+    0.166266, -0.116035, 0.953898, 0.986068, 0.139573, -0.099247, 0.996031, 0.987435, 0.112075,
+    -0.080588, 1.032549, 0.988203, 0.083885, -0.060877, 1.064175, 0.991601, 0.055351, -0.040498,
+    1.09108, 0.996646, 0.027097, -0.020006, 1.113693, 1.000009, -0.00006, 0.000108, 1.132759,
+    0.871239, 0.334933, -0.000007, 0.000017, 0.87124, 0.334933, -0.000697, 0.001813, 0.871239,
+    0.334932, -0.002788, 0.007253, 0.871238, 0.334931, -0.006273, 0.016319, 0.871264, 0.334938,
+    -0.011152, 0.029014, 0.871338, 0.334959, -0.017423, 0.045343, 0.871473, 0.334997, -0.025084,
+    0.065316, 0.871695, 0.335057, -0.034127, 0.088947, 0.872026, 0.335113, -0.044128, 0.116154,
+    0.872534, 0.335188, -0.055439, 0.146988, 0.873249, 0.335278, -0.067996, 0.181255, 0.874139,
+    0.335399, -0.081866, 0.218242, 0.875586, 0.335363, -0.095799, 0.259539, 0.877859, 0.335192,
+    -0.109889, 0.304184, 0.881182, 0.33486, -0.124216, 0.351458, 0.885502, 0.333965, -0.137989,
+    0.401444, 0.892461, 0.332399, -0.150373, 0.454411, 0.90297, 0.329865, -0.161351, 0.509609,
+    0.915323, 0.325242, -0.169832, 0.566565, 0.927931, 0.31748, -0.175214, 0.624485, 0.942217,
+    0.306548, -0.177258, 0.682569, 0.954348, 0.290955, -0.175454, 0.739832, 0.964148, 0.270493,
+    -0.169688, 0.79528, 0.980347, 0.248633, -0.159781, 0.846731, 0.981534, 0.218794, -0.147257,
+    0.900296, 0.991014, 0.189882, -0.130474, 0.943854, 0.984232, 0.157306, -0.11229, 0.991822,
+    0.984833, 0.126502, -0.091351, 1.030271, 0.986057, 0.094936, -0.068994, 1.062513, 0.990392,
+    0.062813, -0.045939, 1.090962, 0.995661, 0.030826, -0.02274, 1.114546, 0.999996, -0.000058,
+    0.000052, 1.134332, 0.838528, 0.367963, -0.000007, 0.000017, 0.83853, 0.367964, -0.000766,
+    0.001745, 0.838529, 0.367963, -0.003063, 0.006981, 0.838528, 0.367961, -0.006892, 0.015707,
+    0.838547, 0.367965, -0.012251, 0.027925, 0.838627, 0.36799, -0.019139, 0.043642, 0.838793,
+    0.368044, -0.027552, 0.062869, 0.839071, 0.36813, -0.037476, 0.085622, 0.839453, 0.368208,
+    -0.048464, 0.111832, 0.840089, 0.368342, -0.060969, 0.141586, 0.840963, 0.368469, -0.074551,
+    0.17468, 0.842104, 0.368638, -0.089605, 0.210647, 0.84424, 0.368906, -0.105196, 0.250235,
+    0.846833, 0.36878, -0.120653, 0.293615, 0.8513, 0.368683, -0.136159, 0.339872, 0.859049,
+    0.368802, -0.151667, 0.38878, 0.868793, 0.367744, -0.16519, 0.441204, 0.881832, 0.365878,
+    -0.177449, 0.495781, 0.897423, 0.361858, -0.186744, 0.552748, 0.91237, 0.353986, -0.192993,
+    0.610602, 0.927898, 0.341985, -0.195418, 0.668828, 0.94292, 0.325003, -0.19363, 0.726897,
+    0.956351, 0.302959, -0.187536, 0.783651, 0.966242, 0.275636, -0.177189, 0.838347, 0.974828,
+    0.244447, -0.163113, 0.891187, 0.982071, 0.210601, -0.145374, 0.940756, 0.984204, 0.17504,
+    -0.124598, 0.984882, 0.983494, 0.140222, -0.101697, 1.026681, 0.983156, 0.105689, -0.076988,
+    1.061598, 0.988176, 0.070142, -0.051335, 1.091163, 0.994415, 0.03454, -0.025519, 1.115579,
+    1.000004, -0.000036, 0.000078, 1.13617, 0.802711, 0.397952, -0.000008, 0.000016, 0.802712,
+    0.397952, -0.000828, 0.001671, 0.802711, 0.397951, -0.003313, 0.006682, 0.802713, 0.39795,
+    -0.007453, 0.015036, 0.802728, 0.397951, -0.013249, 0.026733, 0.802802, 0.397975, -0.020696,
+    0.04178, 0.802989, 0.398041, -0.029789, 0.06019, 0.803315, 0.398154, -0.040505, 0.081981,
+    0.803744, 0.398257, -0.052406, 0.107109, 0.804513, 0.398461, -0.065973, 0.135667, 0.805554,
+    0.398655, -0.080601, 0.167487, 0.807028, 0.398908, -0.096557, 0.202264, 0.809772, 0.399482,
+    -0.113577, 0.240072, 0.813071, 0.399583, -0.130342, 0.282062, 0.819003, 0.399955, -0.147093,
+    0.327215, 0.82897, 0.40092, -0.163664, 0.375112, 0.842074, 0.401314, -0.178767, 0.42664,
+    0.86198, 0.402313, -0.192083, 0.48133, 0.875429, 0.396765, -0.202441, 0.537624, 0.905495,
+    0.394414, -0.209844, 0.596796, 0.916825, 0.378565, -0.21247, 0.655507, 0.930726, 0.358582,
+    -0.210668, 0.714045, 0.945289, 0.33419, -0.20418, 0.771188, 0.9575, 0.304077, -0.193194,
+    0.827149, 0.968806, 0.270154, -0.177913, 0.880693, 0.976004, 0.23225, -0.158867, 0.931758,
+    0.982227, 0.192819, -0.136823, 0.980689, 0.982361, 0.153492, -0.111745, 1.023372, 0.981652,
+    0.116007, -0.08472, 1.060017, 0.986027, 0.077228, -0.0566, 1.090947, 0.993445, 0.038101,
+    -0.028119, 1.116761, 1.000005, -0.00008, 0.00009, 1.13828, 0.764033, 0.424602, -0.000008,
+    0.000015, 0.764033, 0.424602, -0.000884, 0.00159, 0.764033, 0.424602, -0.003535, 0.00636,
+    0.764038, 0.424602, -0.007952, 0.014312, 0.764058, 0.424605, -0.014135, 0.025446, 0.764127,
+    0.424626, -0.022079, 0.03977, 0.764313, 0.424695, -0.031776, 0.057299, 0.764663, 0.424825,
+    -0.043162, 0.078049, 0.76518, 0.42498, -0.05591, 0.102017, 0.766054, 0.425247, -0.070393,
+    0.129275, 0.767286, 0.425538, -0.085994, 0.159722, 0.76916, 0.425952, -0.102855, 0.193173,
+    0.772358, 0.426816, -0.121019, 0.229153, 0.777311, 0.427674, -0.13898, 0.269692, 0.78429,
+    0.428508, -0.156774, 0.313497, 0.796566, 0.430707, -0.174331, 0.360518, 0.811246, 0.432178,
+    -0.190938, 0.410828, 0.830818, 0.43277, -0.205115, 0.465167, 0.851775, 0.430343, -0.216872,
+    0.521611, 0.873344, 0.422842, -0.224459, 0.580338, 0.895589, 0.410339, -0.228073, 0.640208,
+    0.915466, 0.390769, -0.22659, 0.699769, 0.935017, 0.365117, -0.220028, 0.758904, 0.950124,
+    0.332783, -0.208325, 0.815769, 0.965751, 0.29671, -0.191794, 0.869349, 0.972893, 0.254244,
+    -0.171868, 0.923491, 0.980186, 0.210711, -0.148093, 0.973401, 0.980618, 0.166816, -0.121305,
+    1.018962, 0.979336, 0.125933, -0.092247, 1.058757, 0.983426, 0.084226, -0.061783, 1.091401,
+    0.991992, 0.0416, -0.030673, 1.118155, 1.000017, -0.000061, 0.000074, 1.140706, 0.722766,
+    0.447634, -0.000009, 0.000014, 0.722766, 0.447633, -0.000932, 0.001504, 0.722768, 0.447634,
+    -0.003726, 0.006017, 0.722777, 0.447636, -0.008384, 0.013539, 0.722806, 0.447643, -0.014901,
+    0.024073, 0.722883, 0.447668, -0.023273, 0.037627, 0.723066, 0.447738, -0.03349, 0.054216,
+    0.72346, 0.447895, -0.045388, 0.073859, 0.724092, 0.448127, -0.058942, 0.096598, 0.72504,
+    0.448444, -0.074155, 0.122462, 0.726544, 0.448897, -0.090641, 0.151444, 0.728836, 0.449531,
+    -0.108322, 0.183437, 0.732281, 0.450512, -0.12718, 0.217962, 0.738404, 0.45221, -0.146306,
+    0.25645, 0.748509, 0.454886, -0.165216, 0.299053, 0.761801, 0.457812, -0.183724, 0.345139,
+    0.779354, 0.46107, -0.201426, 0.394412, 0.800765, 0.462515, -0.216681, 0.447977, 0.825347,
+    0.461295, -0.229244, 0.504505, 0.850012, 0.454419, -0.237832, 0.563238, 0.876027, 0.441909,
+    -0.241996, 0.623667, 0.901402, 0.422472, -0.240913, 0.685006, 0.931206, 0.399429, -0.234159,
+    0.744483, 0.941253, 0.360428, -0.22263, 0.804343, 0.953385, 0.319178, -0.205465, 0.860284,
+    0.964659, 0.274662, -0.183997, 0.914288, 0.975944, 0.228094, -0.158905, 0.966646, 0.985698,
+    0.18096, -0.129774, 1.009617, 0.98057, 0.134991, -0.099429, 1.0573, 0.981291, 0.090859,
+    -0.066678, 1.091547, 0.990838, 0.045003, -0.033182, 1.119789, 1.000028, -0.000068, 0.000088,
+    1.143441, 0.679209, 0.46678, -0.000009, 0.000014, 0.679209, 0.466781, -0.000971, 0.001414,
+    0.679213, 0.466782, -0.003886, 0.005654, 0.679227, 0.466786, -0.008742, 0.012723, 0.679266,
+    0.4668, -0.015538, 0.022624, 0.679359, 0.466835, -0.024266, 0.035365, 0.67956, 0.466916,
+    -0.034912, 0.050966, 0.680072, 0.467156, -0.04728, 0.069456, 0.680764, 0.467438, -0.061451,
+    0.090885, 0.681864, 0.467855, -0.077215, 0.115293, 0.683541, 0.468433, -0.094444, 0.142721,
+    0.686245, 0.469333, -0.112816, 0.17314, 0.69021, 0.470611, -0.132237, 0.206227, 0.697707,
+    0.473484, -0.152397, 0.242595, 0.708264, 0.47669, -0.172166, 0.283664, 0.724036, 0.481371,
+    -0.191543, 0.328644, 0.745649, 0.487129, -0.210181, 0.377415, 0.776458, 0.494372, -0.227129,
+    0.430651, 0.796368, 0.489434, -0.239985, 0.486485, 0.825611, 0.484229, -0.249765, 0.545523,
+    0.853699, 0.471146, -0.254384, 0.606956, 0.885204, 0.45273, -0.253953, 0.669089, 0.906393,
+    0.422063, -0.247459, 0.730764, 0.936412, 0.389253, -0.235469, 0.792007, 0.948068, 0.343719,
+    -0.217944, 0.850703, 0.957168, 0.294423, -0.195333, 0.905648, 0.966191, 0.243758, -0.168773,
+    0.95843, 0.978372, 0.19322, -0.139041, 1.009982, 0.978284, 0.143963, -0.106369, 1.055623,
+    0.980052, 0.097259, -0.071507, 1.091881, 0.989253, 0.048363, -0.035698, 1.121754, 1.000046,
+    -0.000085, 0.000106, 1.146563, 0.633689, 0.481796, -0.00001, 0.000013, 0.633689, 0.481796,
+    -0.001003, 0.001319, 0.633693, 0.481798, -0.004011, 0.005275, 0.633712, 0.481805, -0.009023,
+    0.011871, 0.633763, 0.481827, -0.016037, 0.02111, 0.633877, 0.481877, -0.025043, 0.033002,
+    0.634107, 0.481981, -0.036025, 0.047572, 0.634721, 0.482308, -0.048772, 0.064862, 0.635471,
+    0.482643, -0.063423, 0.084924, 0.636761, 0.483209, -0.079657, 0.107822, 0.638628, 0.483934,
+    -0.097385, 0.133627, 0.641587, 0.485042, -0.116325, 0.162378, 0.646702, 0.487144, -0.136256,
+    0.193974, 0.654701, 0.49069, -0.1571, 0.228295, 0.667398, 0.495738, -0.177698, 0.267772,
+    0.691291, 0.506461, -0.198322, 0.312275, 0.715655, 0.514015, -0.21771, 0.360338, 0.736211,
+    0.51471, -0.234988, 0.411439, 0.766273, 0.515547, -0.249233, 0.467853, 0.800072, 0.511914,
+    -0.259844, 0.527739, 0.831956, 0.499537, -0.265516, 0.589738, 0.873511, 0.484077, -0.265731,
+    0.653896, 0.892515, 0.449285, -0.25923, 0.716399, 0.918121, 0.411569, -0.247486, 0.779498,
+    0.939724, 0.366785, -0.229619, 0.840564, 0.95249, 0.314686, -0.205823, 0.896301, 0.961952,
+    0.260028, -0.17828, 0.951645, 0.970479, 0.205214, -0.147235, 1.005281, 0.97255, 0.15296,
+    -0.112926, 1.053828, 0.975654, 0.10346, -0.076074, 1.092503, 0.987685, 0.051607, -0.038116,
+    1.123971, 1.000054, -0.000076, 0.000095, 1.150067, 0.586555, 0.492451, -0.00001, 0.000012,
+    0.586556, 0.492451, -0.001025, 0.001221, 0.586561, 0.492454, -0.004099, 0.004883, 0.586584,
+    0.492465, -0.009222, 0.010988, 0.586645, 0.492496, -0.01639, 0.019542, 0.586781, 0.492563,
+    -0.025593, 0.030557, 0.587051, 0.492702, -0.03681, 0.044059, 0.587743, 0.493112, -0.049829,
+    0.060111, 0.588579, 0.493525, -0.064803, 0.078758, 0.589997, 0.494218, -0.081389, 0.100097,
+    0.592139, 0.495184, -0.099487, 0.124229, 0.595415, 0.496576, -0.118778, 0.151243, 0.601307,
+    0.499375, -0.139072, 0.181227, 0.610124, 0.50368, -0.160196, 0.213995, 0.625374, 0.511382,
+    -0.181679, 0.25141, 0.64441, 0.518982, -0.20256, 0.293912, 0.67582, 0.532235, -0.22308,
+    0.341749, 0.700619, 0.535464, -0.241068, 0.392436, 0.736252, 0.540064, -0.256911, 0.448685,
+    0.77154, 0.536328, -0.268084, 0.508965, 0.80854, 0.525633, -0.274893, 0.571922, 0.844736,
+    0.505429, -0.275583, 0.637199, 0.887573, 0.481839, -0.270009, 0.701954, 0.904807, 0.434876,
+    -0.257745, 0.766091, 0.930816, 0.388413, -0.240015, 0.829787, 0.946671, 0.334196, -0.21603,
+    0.888755, 0.959552, 0.276595, -0.187349, 0.945525, 0.968646, 0.217701, -0.154982, 1.001029,
+    0.972499, 0.161145, -0.119104, 1.051309, 0.97579, 0.109247, -0.08046, 1.09319, 0.986615,
+    0.054683, -0.040361, 1.126516, 1.000038, -0.000047, 0.000071, 1.154019, 0.538184, 0.498539,
+    -0.00001, 0.000011, 0.538185, 0.49854, -0.001038, 0.00112, 0.538191, 0.498543, -0.00415,
+    0.00448, 0.538218, 0.498559, -0.009336, 0.010083, 0.538292, 0.4986, -0.016592, 0.017933,
+    0.538449, 0.498689, -0.025906, 0.028048, 0.538773, 0.498879, -0.037245, 0.040458, 0.539508,
+    0.499353, -0.05043, 0.055238, 0.540461, 0.499886, -0.065554, 0.07244, 0.542, 0.500715,
+    -0.082347, 0.092183, 0.544387, 0.501934, -0.100626, 0.114602, 0.548241, 0.503888, -0.120145,
+    0.13986, 0.554243, 0.506904, -0.140611, 0.168098, 0.565158, 0.513219, -0.161956, 0.199475,
+    0.580802, 0.521971, -0.183983, 0.234605, 0.603711, 0.533248, -0.205622, 0.275956, 0.632568,
+    0.545007, -0.22655, 0.322497, 0.672084, 0.559425, -0.246122, 0.374185, 0.704093, 0.56105,
+    -0.262737, 0.42965, 0.743351, 0.559311, -0.275142, 0.490394, 0.784235, 0.549424, -0.282538,
+    0.554467, 0.822837, 0.529008, -0.284161, 0.619936, 0.860318, 0.498656, -0.27913, 0.687644,
+    0.891156, 0.457428, -0.267527, 0.753568, 0.917434, 0.407514, -0.249008, 0.817947, 0.940491,
+    0.352321, -0.225138, 0.881268, 0.953082, 0.291267, -0.195497, 0.938631, 0.965269, 0.229828,
+    -0.162127, 0.9962, 0.973184, 0.169478, -0.125217, 1.051497, 0.974, 0.114762, -0.084745,
+    1.09483, 0.984604, 0.057694, -0.042555, 1.129385, 1.000019, -0.000029, 0.0001, 1.158485,
+    0.488981, 0.499879, -0.00001, 0.00001, 0.488981, 0.499879, -0.00104, 0.001018, 0.488988,
+    0.499883, -0.004161, 0.004071, 0.489019, 0.499903, -0.009361, 0.009161, 0.489102, 0.499955,
+    -0.016636, 0.016297, 0.489279, 0.500067, -0.025973, 0.025496, 0.489726, 0.500387, -0.037292,
+    0.036802, 0.490422, 0.500852, -0.050551, 0.050285, 0.491514, 0.501543, -0.065663, 0.066027,
+    0.493175, 0.502528, -0.082475, 0.084153, 0.495777, 0.504011, -0.100804, 0.104832, 0.500024,
+    0.506441, -0.120363, 0.128294, 0.507306, 0.510855, -0.140937, 0.154826, 0.518844, 0.518046,
+    -0.16236, 0.184704, 0.53705, 0.529743, -0.184628, 0.218308, 0.56626, 0.547767, -0.207368,
+    0.258296, 0.592204, 0.557168, -0.228505, 0.303284, 0.62939, 0.570124, -0.248629, 0.354317,
+    0.670936, 0.578861, -0.266358, 0.410414, 0.71312, 0.578988, -0.280191, 0.470749, 0.758903,
+    0.570873, -0.288729, 0.536694, 0.800122, 0.550066, -0.290933, 0.603472, 0.842437, 0.520301,
+    -0.286949, 0.672529, 0.877254, 0.478205, -0.275678, 0.741278, 0.907288, 0.427206, -0.257352,
+    0.807245, 0.933944, 0.369858, -0.233266, 0.872549, 0.948894, 0.30581, -0.203017, 0.932988,
+    0.959761, 0.240487, -0.168685, 0.991931, 0.971187, 0.17742, -0.130713, 1.049678, 0.96897,
+    0.120212, -0.088655, 1.095525, 0.983414, 0.060543, -0.044658, 1.132688, 0.999977, -0.000085,
+    0.000116, 1.163564, 0.439371, 0.49631, -0.00001, 0.000009, 0.439372, 0.496311, -0.001033,
+    0.000914, 0.43938, 0.496316, -0.004131, 0.003658, 0.439414, 0.49634, -0.009294, 0.008233,
+    0.439505, 0.496404, -0.016516, 0.014648, 0.439701, 0.49654, -0.025784, 0.022924, 0.440212,
+    0.496953, -0.037, 0.033117, 0.440918, 0.497454, -0.050177, 0.045295, 0.44209, 0.498269,
+    -0.065161, 0.059564, 0.443899, 0.499462, -0.081838, 0.076067, 0.446765, 0.501301, -0.099999,
+    0.095005, 0.45135, 0.504219, -0.119418, 0.116654, 0.459977, 0.510349, -0.13993, 0.141472,
+    0.473155, 0.519647, -0.161383, 0.16986, 0.49224, 0.532736, -0.183687, 0.202209, 0.520056,
+    0.550833, -0.206648, 0.239617, 0.552901, 0.56726, -0.228969, 0.284319, 0.59245, 0.582573,
+    -0.249906, 0.335023, 0.637658, 0.594073, -0.268603, 0.391543, 0.683038, 0.595943, -0.283401,
+    0.452395, 0.732077, 0.589655, -0.293325, 0.518316, 0.780787, 0.571628, -0.296924, 0.588369,
+    0.83276, 0.545375, -0.293243, 0.657714, 0.86233, 0.497114, -0.282537, 0.728275, 0.903441,
+    0.448411, -0.264274, 0.796036, 0.923569, 0.38517, -0.240362, 0.864191, 0.943259, 0.319505,
+    -0.209721, 0.926224, 0.95885, 0.252004, -0.174659, 0.988218, 0.965124, 0.184579, -0.135648,
+    1.047912, 0.969091, 0.124858, -0.09251, 1.097971, 0.981747, 0.063313, -0.04667, 1.136864,
+    0.999946, -0.000134, 0.00013, 1.169371, 0.389813, 0.487708, -0.00001, 0.000008, 0.389813,
+    0.487708, -0.001015, 0.000811, 0.389821, 0.487714, -0.00406, 0.003245, 0.389858, 0.487743,
+    -0.009133, 0.007305, 0.389956, 0.487819, -0.016229, 0.013, 0.390169, 0.487983, -0.025333,
+    0.020356, 0.390709, 0.488459, -0.036345, 0.029437, 0.391441, 0.489021, -0.049298, 0.040313,
+    0.392695, 0.489981, -0.064003, 0.053115, 0.394659, 0.491423, -0.080351, 0.068005, 0.397775,
+    0.49366, -0.098193, 0.085211, 0.40329, 0.497865, -0.117324, 0.105097, 0.412268, 0.504786,
+    -0.137559, 0.128144, 0.427139, 0.516597, -0.15897, 0.155041, 0.449553, 0.534064, -0.181438,
+    0.186385, 0.485836, 0.562341, -0.205374, 0.223304, 0.513345, 0.573773, -0.227618, 0.265343,
+    0.557155, 0.593658, -0.249715, 0.31604, 0.602926, 0.605419, -0.26899, 0.372354, 0.655171,
+    0.6122, -0.285359, 0.434965, 0.706379, 0.606612, -0.296401, 0.501203, 0.757396, 0.588769,
+    -0.300874, 0.57148, 0.814596, 0.563399, -0.298311, 0.644318, 0.845372, 0.513655, -0.288206,
+    0.715429, 0.889289, 0.463392, -0.270531, 0.786414, 0.912185, 0.398242, -0.246322, 0.855358,
+    0.935173, 0.331143, -0.215847, 0.921376, 0.947499, 0.260243, -0.1799, 0.983051, 0.963721,
+    0.192061, -0.140137, 1.046762, 0.968795, 0.129206, -0.096088, 1.100564, 0.980353, 0.065885,
+    -0.048614, 1.141593, 0.999877, 0.000024, 0.000139, 1.176034, 0.340771, 0.473955, -0.000009,
+    0.000007, 0.340784, 0.473973, -0.000986, 0.000709, 0.340793, 0.473981, -0.003945, 0.002837,
+    0.340832, 0.474014, -0.008876, 0.006387, 0.340936, 0.474101, -0.015771, 0.01137, 0.341166,
+    0.474298, -0.024616, 0.017815, 0.341713, 0.474821, -0.035313, 0.025797, 0.342521, 0.475519,
+    -0.047865, 0.035394, 0.343813, 0.476591, -0.06215, 0.046746, 0.345895, 0.478276, -0.078042,
+    0.060039, 0.349315, 0.481052, -0.095367, 0.075545, 0.35519, 0.485999, -0.113985, 0.093668,
+    0.365876, 0.495634, -0.13394, 0.115068, 0.382527, 0.510448, -0.155172, 0.140487, 0.406435,
+    0.530588, -0.177731, 0.170713, 0.439985, 0.557046, -0.201415, 0.206354, 0.473891, 0.576556,
+    -0.224653, 0.247101, 0.520258, 0.599805, -0.24768, 0.297059, 0.570584, 0.616002, -0.268428,
+    0.354189, 0.623833, 0.622854, -0.285132, 0.416697, 0.678519, 0.618927, -0.297363, 0.484463,
+    0.732098, 0.602294, -0.303548, 0.555546, 0.785463, 0.573367, -0.302196, 0.630296, 0.836423,
+    0.532404, -0.292391, 0.703973, 0.870693, 0.475052, -0.27599, 0.777208, 0.902901, 0.411481,
+    -0.251492, 0.847677, 0.926101, 0.341427, -0.220848, 0.915328, 0.944215, 0.269479, -0.184817,
+    0.980245, 0.96065, 0.19843, -0.144498, 1.047035, 0.962595, 0.133621, -0.099171, 1.103686,
+    0.977936, 0.068375, -0.050484, 1.147217, 0.999868, -0.000045, 0.000161, 1.183663, 0.292793,
+    0.455045, -0.000009, 0.000006, 0.292793, 0.455045, -0.000947, 0.000609, 0.292802, 0.455053,
+    -0.003788, 0.002438, 0.292843, 0.455091, -0.008521, 0.005489, 0.292951, 0.455192, -0.015141,
+    0.009775, 0.293219, 0.45546, -0.02362, 0.015332, 0.29374, 0.455984, -0.033898, 0.022234,
+    0.29458, 0.456782, -0.045938, 0.030578, 0.295941, 0.458026, -0.059645, 0.040514, 0.298151,
+    0.460013, -0.074871, 0.052255, 0.302005, 0.463618, -0.091536, 0.066112, 0.31237, 0.476014,
+    -0.109996, 0.082904, 0.321129, 0.482993, -0.129089, 0.102399, 0.338931, 0.500298, -0.15007,
+    0.126354, 0.364843, 0.524185, -0.172671, 0.155504, 0.397827, 0.550957, -0.196293, 0.190175,
+    0.437885, 0.57854, -0.220401, 0.230832, 0.484957, 0.604134, -0.244282, 0.278775, 0.539112,
+    0.62427, -0.266336, 0.336274, 0.597137, 0.634699, -0.28428, 0.399764, 0.655877, 0.63283,
+    -0.297337, 0.468688, 0.710314, 0.614855, -0.304566, 0.541769, 0.766352, 0.586703, -0.304516,
+    0.616412, 0.813984, 0.542037, -0.295946, 0.692657, 0.8597, 0.488366, -0.279671, 0.76875,
+    0.895016, 0.424108, -0.256014, 0.840751, 0.919519, 0.352107, -0.225292, 0.910386, 0.948844,
+    0.281253, -0.188437, 0.975925, 0.956826, 0.204795, -0.148408, 1.046418, 0.964035, 0.136959,
+    -0.10206, 1.107702, 0.977613, 0.070577, -0.052198, 1.153696, 0.999954, -0.000071, 0.00013,
+    1.192565, 0.249085, 0.435643, -0.000009, 0.000005, 0.246372, 0.430898, -0.000897, 0.000513,
+    0.246381, 0.430908, -0.003587, 0.002051, 0.246422, 0.430949, -0.008069, 0.00462, 0.246533,
+    0.431062, -0.014337, 0.008232, 0.246841, 0.431423, -0.02235, 0.012932, 0.247324, 0.431926,
+    -0.032097, 0.018789, 0.248193, 0.432834, -0.043487, 0.025922, 0.24963, 0.434301, -0.056446,
+    0.034494, 0.252022, 0.436737, -0.070873, 0.044738, 0.256255, 0.441245, -0.086689, 0.057008,
+    0.264334, 0.450535, -0.104003, 0.071891, 0.277742, 0.465912, -0.122989, 0.090201, 0.297568,
+    0.487449, -0.143807, 0.112908, 0.328828, 0.520575, -0.166821, 0.141317, 0.360916, 0.546664,
+    -0.190418, 0.175135, 0.409163, 0.58507, -0.215873, 0.216523, 0.454305, 0.609089, -0.239689,
+    0.263276, 0.518846, 0.641711, -0.263222, 0.319359, 0.565665, 0.639237, -0.281907, 0.382966,
+    0.626762, 0.638522, -0.296313, 0.453583, 0.688343, 0.624728, -0.304344, 0.528285, 0.750067,
+    0.598568, -0.304885, 0.60457, 0.810625, 0.553656, -0.296856, 0.679022, 0.8585, 0.501343,
+    -0.28338, 0.761196, 0.884388, 0.433442, -0.258701, 0.833921, 0.912709, 0.361006, -0.229065,
+    0.9074, 0.930563, 0.28437, -0.192947, 0.976127, 0.951622, 0.209609, -0.151476, 1.049607,
+    0.95925, 0.14072, -0.105056, 1.112905, 0.97523, 0.072516, -0.053459, 1.161822, 1.00001,
+    -0.000057, 0.000047, 1.202848, 0.236656, 0.470259, -0.000009, 0.000004, 0.202079, 0.401551,
+    -0.000836, 0.000421, 0.202089, 0.401562, -0.003343, 0.001683, 0.20213, 0.401608, -0.007519,
+    0.003791, 0.202244, 0.401736, -0.01336, 0.006761, 0.202556, 0.402142, -0.020822, 0.01064,
+    0.203059, 0.40273, -0.029895, 0.015506, 0.203931, 0.403725, -0.040506, 0.021483, 0.20541,
+    0.405397, -0.052575, 0.028753, 0.20803, 0.408466, -0.066039, 0.037577, 0.212892, 0.414527,
+    -0.080903, 0.04836, 0.222182, 0.426838, -0.097413, 0.061786, 0.237181, 0.446241, -0.115881,
+    0.078746, 0.261414, 0.476727, -0.136799, 0.100491, 0.289014, 0.505388, -0.159242, 0.127446,
+    0.331169, 0.547959, -0.184117, 0.161507, 0.377119, 0.585275, -0.209663, 0.202451, 0.42005,
+    0.603945, -0.233531, 0.248926, 0.474954, 0.626722, -0.257237, 0.303714, 0.538652, 0.644329,
+    -0.27825, 0.366774, 0.601479, 0.644291, -0.294109, 0.438687, 0.668252, 0.633346, -0.302941,
+    0.514742, 0.727333, 0.603688, -0.304725, 0.594126, 0.784228, 0.562163, -0.298627, 0.674137,
+    0.833186, 0.507546, -0.284313, 0.752057, 0.870284, 0.440808, -0.261991, 0.827776, 0.903615,
+    0.367831, -0.2316, 0.903844, 0.928316, 0.291241, -0.195341, 0.976679, 0.947647, 0.214822,
+    -0.154451, 1.048964, 0.960394, 0.143391, -0.107422, 1.120002, 0.973105, 0.07434, -0.054822,
+    1.171405, 1.00002, 0.000166, -0.00026, 1.215018, 0.222163, 0.508094, -0.000009, 0.000004,
+    0.1605, 0.36707, -0.000764, 0.000334, 0.16051, 0.367082, -0.003056, 0.001337, 0.160552,
+    0.367134, -0.006873, 0.003012, 0.160666, 0.367275, -0.012212, 0.005379, 0.160967, 0.367704,
+    -0.019033, 0.008489, 0.161477, 0.36837, -0.027321, 0.012422, 0.16239, 0.369559, -0.037016,
+    0.017317, 0.16436, 0.372495, -0.048079, 0.023379, 0.166943, 0.375738, -0.060422, 0.03087,
+    0.172596, 0.384049, -0.07424, 0.040291, 0.183262, 0.400327, -0.089934, 0.052404, 0.19993,
+    0.424543, -0.107893, 0.068164, 0.224349, 0.457442, -0.128433, 0.088725, 0.256936, 0.496986,
+    -0.151425, 0.115165, 0.292309, 0.529129, -0.175553, 0.147799, 0.337364, 0.56537, -0.201143,
+    0.188152, 0.398979, 0.614552, -0.228406, 0.237857, 0.45238, 0.63048, -0.251505, 0.292125,
+    0.516452, 0.649755, -0.272478, 0.353832, 0.576665, 0.646917, -0.290475, 0.424229, 0.64104,
+    0.633512, -0.300695, 0.501434, 0.705964, 0.607688, -0.303443, 0.582807, 0.765162, 0.566145,
+    -0.297605, 0.664601, 0.818591, 0.512737, -0.283899, 0.745811, 0.861708, 0.44761, -0.26214,
+    0.82367, 0.895978, 0.375155, -0.234153, 0.900801, 0.923439, 0.297686, -0.198205, 0.97593,
+    0.945408, 0.219046, -0.156502, 1.055309, 0.954825, 0.146506, -0.109211, 1.127489, 0.972611,
+    0.076322, -0.056837, 1.182731, 1.000081, -0.000191, 0.000159, 1.229606, 0.205352, 0.550263,
+    -0.000009, 0.000003, 0.122246, 0.32757, -0.000682, 0.000254, 0.122255, 0.327582, -0.002727,
+    0.001018, 0.122297, 0.32764, -0.006134, 0.002296, 0.122432, 0.327856, -0.010891, 0.00411,
+    0.1227, 0.328255, -0.016985, 0.006508, 0.12322, 0.329031, -0.024376, 0.009585, 0.124149,
+    0.330401, -0.033026, 0.013481, 0.125948, 0.333246, -0.042909, 0.018416, 0.129547, 0.339401,
+    -0.054096, 0.024727, 0.136565, 0.351908, -0.066895, 0.03297, 0.14887, 0.373531, -0.081865,
+    0.043969, 0.166731, 0.402185, -0.099313, 0.058659, 0.19197, 0.438783, -0.119601, 0.078262,
+    0.222343, 0.475304, -0.142085, 0.103536, 0.266926, 0.525844, -0.167459, 0.136435, 0.307538,
+    0.55446, -0.19285, 0.176069, 0.362713, 0.591906, -0.219197, 0.224615, 0.421809, 0.619014,
+    -0.243881, 0.28072, 0.485108, 0.635955, -0.26633, 0.344756, 0.561088, 0.651082, -0.284651,
+    0.415262, 0.624152, 0.638139, -0.297142, 0.490727, 0.689215, 0.612997, -0.302125, 0.572222,
+    0.750827, 0.572035, -0.297226, 0.656306, 0.811003, 0.520794, -0.283504, 0.73917, 0.865869,
+    0.467994, -0.263406, 0.825223, 0.893355, 0.381128, -0.234462, 0.903883, 0.914104, 0.30003,
+    -0.199324, 0.980151, 0.94025, 0.222096, -0.160149, 1.064254, 0.9535, 0.148788, -0.11229,
+    1.139901, 0.970866, 0.077256, -0.057055, 1.197526, 0.999947, -0.000521, 0.000552, 1.247352,
+    0.185805, 0.598335, -0.00001, 0.000003, 0.087952, 0.283225, -0.000589, 0.000183, 0.087961,
+    0.28324, -0.002358, 0.000733, 0.088002, 0.283304, -0.005304, 0.001655, 0.088142, 0.283571,
+    -0.009414, 0.002971, 0.088407, 0.284024, -0.01468, 0.004734, 0.088928, 0.284921, -0.021072,
+    0.00704, 0.089932, 0.286713, -0.028563, 0.010043, 0.092111, 0.291096, -0.037192, 0.013985,
+    0.096544, 0.30047, -0.047181, 0.01926, 0.106688, 0.323417, -0.059314, 0.026629, 0.118861,
+    0.346204, -0.073403, 0.036594, 0.138685, 0.381942, -0.090552, 0.050491, 0.162878, 0.417827,
+    -0.110227, 0.069065, 0.196118, 0.461864, -0.133169, 0.093997, 0.233855, 0.500735, -0.157795,
+    0.125713, 0.28262, 0.544582, -0.184531, 0.16602, 0.336144, 0.579425, -0.210883, 0.214369,
+    0.395622, 0.607043, -0.236446, 0.271678, 0.465846, 0.631753, -0.258992, 0.336624, 0.529082,
+    0.632981, -0.276871, 0.407693, 0.611985, 0.639043, -0.29135, 0.487249, 0.666932, 0.606685,
+    -0.296038, 0.564946, 0.733182, 0.573031, -0.295217, 0.6482, 0.792968, 0.522929, -0.284803,
+    0.734049, 0.836195, 0.454533, -0.263455, 0.817833, 0.879615, 0.382304, -0.23509, 0.902244,
+    0.91119, 0.303453, -0.199923, 0.985487, 0.937367, 0.223882, -0.159605, 1.074284, 0.953851,
+    0.149929, -0.11232, 1.154631, 0.969598, 0.078765, -0.058866, 1.215749, 1.00007, 0.000969,
+    -0.001407, 1.269354, 0.163131, 0.65574, -0.00001, 0.000002, 0.058282, 0.234276, -0.000488,
+    0.000121, 0.058291, 0.234292, -0.00195, 0.000486, 0.058331, 0.234366, -0.004387, 0.001099,
+    0.05846, 0.23465, -0.007787, 0.001986, 0.058732, 0.235218, -0.012142, 0.003198, 0.059289,
+    0.23642, -0.017433, 0.004837, 0.060517, 0.239365, -0.023674, 0.007069, 0.06337, 0.246929,
+    -0.031046, 0.010178, 0.070149, 0.266015, -0.040159, 0.014692, 0.082453, 0.298877, -0.051728,
+    0.02136, 0.096085, 0.327472, -0.065244, 0.03061, 0.116719, 0.367012, -0.081962, 0.043815,
+    0.138764, 0.39799, -0.101146, 0.061634, 0.170239, 0.439522, -0.123545, 0.085747, 0.212016,
+    0.488001, -0.148881, 0.117525, 0.257929, 0.527143, -0.17514, 0.157112, 0.310135, 0.560697,
+    -0.201898, 0.205573, 0.377056, 0.599101, -0.227865, 0.263397, 0.438233, 0.612791, -0.250861,
+    0.329403, 0.511214, 0.624723, -0.269714, 0.402963, 0.579354, 0.616889, -0.282412, 0.481733,
+    0.648945, 0.597254, -0.288238, 0.56386, 0.715825, 0.564261, -0.287162, 0.648663, 0.775604,
+    0.516856, -0.278398, 0.733389, 0.830276, 0.459267, -0.262735, 0.818725, 0.871889, 0.388835,
+    -0.238842, 0.902239, 0.913749, 0.311512, -0.203619, 0.984334, 0.933635, 0.227863, -0.16382,
+    1.085073, 0.950878, 0.152184, -0.114493, 1.17169, 0.970124, 0.079151, -0.058293, 1.238899,
+    1.000519, -0.001636, 0.001968, 1.297732, 0.136824, 0.730163, -0.00001, 0.000002, 0.033924,
+    0.181033, -0.000377, 0.000071, 0.033933, 0.181053, -0.001507, 0.000283, 0.033981, 0.181192,
+    -0.003388, 0.000644, 0.034101, 0.181506, -0.006016, 0.001176, 0.034382, 0.182272, -0.009382,
+    0.001935, 0.035052, 0.184323, -0.013496, 0.003026, 0.036889, 0.190863, -0.0185, 0.004642,
+    0.041099, 0.206082, -0.024837, 0.007133, 0.048762, 0.231798, -0.033111, 0.011032, 0.059793,
+    0.263044, -0.043666, 0.016965, 0.075938, 0.303328, -0.057152, 0.025867, 0.09392, 0.336254,
+    -0.073072, 0.038362, 0.118706, 0.376621, -0.092355, 0.05596, 0.15236, 0.424928, -0.114667,
+    0.079773, 0.190894, 0.467178, -0.139406, 0.110872, 0.236292, 0.506792, -0.165928, 0.150432,
+    0.28876, 0.542035, -0.192828, 0.198851, 0.349068, 0.572417, -0.218501, 0.256212, 0.418516,
+    0.598123, -0.241977, 0.322762, 0.485289, 0.60408, -0.260877, 0.396934, 0.559373, 0.602866,
+    -0.274459, 0.478146, 0.63187, 0.585871, -0.280713, 0.563411, 0.701952, 0.55459, -0.278926,
+    0.65035, 0.762323, 0.501457, -0.26844, 0.736193, 0.812296, 0.446382, -0.252607, 0.827371,
+    0.858526, 0.379007, -0.228659, 0.915382, 0.900121, 0.306368, -0.198744, 1.005602, 0.932843,
+    0.229616, -0.164101, 1.106017, 0.956142, 0.155724, -0.120928, 1.198608, 0.966794, 0.084391,
+    -0.068659, 1.271707, 0.999588, 0.002857, -0.005855, 1.335318, 0.106488, 0.846113, -0.00001,
+    0.000001, 0.015593, 0.123894, -0.000258, 0.000032, 0.015602, 0.123923, -0.001031, 0.000131,
+    0.015647, 0.124099, -0.002318, 0.000301, 0.015773, 0.124593, -0.004117, 0.000566, 0.016218,
+    0.126873, -0.006444, 0.000984, 0.017312, 0.132526, -0.00941, 0.001672, 0.02018, 0.147514,
+    -0.013445, 0.002873, 0.025271, 0.170758, -0.019019, 0.004957, 0.033201, 0.201138, -0.026711,
+    0.008477, 0.043664, 0.232671, -0.036532, 0.013973, 0.05904, 0.272678, -0.049397, 0.022469,
+    0.077616, 0.310002, -0.064939, 0.034679, 0.10104, 0.348216, -0.083633, 0.051867, 0.133336,
+    0.395815, -0.105656, 0.075448, 0.168947, 0.434201, -0.129495, 0.105808, 0.213033, 0.474151,
+    -0.155059, 0.144277, 0.268303, 0.517312, -0.182184, 0.192551, 0.325929, 0.546243, -0.207827,
+    0.249328, 0.394045, 0.57331, -0.23223, 0.316535, 0.461656, 0.582391, -0.251316, 0.390162,
+    0.53704, 0.584837, -0.266198, 0.472894, 0.608721, 0.568851, -0.273238, 0.559488, 0.680568,
+    0.540541, -0.273553, 0.651973, 0.752221, 0.500376, -0.264883, 0.745556, 0.807471, 0.441376,
+    -0.248773, 0.843165, 0.855493, 0.372572, -0.224183, 0.940615, 0.891133, 0.294127, -0.192452,
+    1.043448, 0.923419, 0.218292, -0.153633, 1.154107, 0.940342, 0.147751, -0.104778, 1.245447,
+    0.968312, 0.074614, -0.049325, 1.322596, 1.000033, -0.005849, 0.010949, 1.392784, 0.063476,
+    0.997983, -0.00001, 0.000001, 0.00403, 0.063351, -0.000132, 0.000008, 0.00404, 0.063427,
+    -0.000527, 0.000034, 0.004088, 0.063798, -0.001185, 0.000084, 0.004312, 0.066032, -0.002127,
+    0.000181, 0.005219, 0.07555, -0.00354, 0.000393, 0.007338, 0.094448, -0.005815, 0.000876,
+    0.010442, 0.114372, -0.009207, 0.001865, 0.016089, 0.145291, -0.014363, 0.003791, 0.023412,
+    0.174299, -0.021414, 0.007118, 0.033661, 0.20716, -0.030789, 0.012505, 0.046685, 0.239636,
+    -0.042701, 0.020696, 0.064445, 0.277298, -0.057529, 0.032682, 0.086043, 0.313419, -0.075009,
+    0.049294, 0.11417, 0.353708, -0.095295, 0.071741, 0.149138, 0.395347, -0.118177, 0.101226,
+    0.191622, 0.436673, -0.143129, 0.138876, 0.242091, 0.476173, -0.169136, 0.185447, 0.301813,
+    0.513713, -0.195547, 0.242108, 0.367243, 0.541753, -0.219969, 0.307846, 0.437777, 0.55908,
+    -0.241018, 0.382336, 0.512308, 0.564821, -0.257528, 0.46509, 0.58893, 0.557158, -0.268012,
+    0.554853, 0.666593, 0.535709, -0.270955, 0.649478, 0.732284, 0.493031, -0.266341, 0.749555,
+    0.793566, 0.438349, -0.252992, 0.852489, 0.845085, 0.37182, -0.231129, 0.958935, 0.89573,
+    0.298435, -0.199604, 1.062518, 0.922179, 0.22251, -0.16387, 1.198788, 0.945565, 0.15147,
+    -0.113403, 1.301983, 0.970401, 0.076999, -0.054169, 1.39656, 1.000817, -0.006362, 0.011995,
+    1.482077, 0.000796, 1.0, -0.00001, 0.0, 0.000012, 0.008702, -0.00001, 0.0, 0.000041, 0.006961,
+    -0.000045, 0.000001, 0.000198, 0.015094, -0.000218, 0.000011, 0.000591, 0.025619, -0.000671,
+    0.000059, 0.001418, 0.039445, -0.001614, 0.00022, 0.00292, 0.056358, -0.00331, 0.000639,
+    0.005377, 0.075996, -0.006053, 0.001552, 0.009149, 0.098462, -0.010181, 0.003303, 0.014829,
+    0.124838, -0.016067, 0.006361, 0.022764, 0.153437, -0.02401, 0.011297, 0.033715, 0.185252,
+    -0.034363, 0.018849, 0.048263, 0.219196, -0.047315, 0.029846, 0.067611, 0.256596, -0.063029,
+    0.045272, 0.092282, 0.295633, -0.081489, 0.066183, 0.123264, 0.335995, -0.102493, 0.093667,
+    0.161692, 0.377261, -0.125612, 0.128765, 0.206779, 0.415784, -0.150199, 0.172418, 0.259947,
+    0.451836, -0.175373, 0.225368, 0.320872, 0.48319, -0.199987, 0.287997, 0.39021, 0.509055,
+    -0.222684, 0.360222, 0.463654, 0.523217, -0.242102, 0.441649, 0.541115, 0.525806, -0.256763,
+    0.531228, 0.61147, 0.510081, -0.267857, 0.633494, 0.688885, 0.485743, -0.271007, 0.740547,
+    0.764207, 0.446887, -0.26629, 0.853038, 0.836937, 0.394802, -0.253514, 0.971358, 0.894478,
+    0.328366, -0.238384, 1.124274, 0.940115, 0.256208, -0.208444, 1.263553, 0.960285, 0.18525,
+    -0.168356, 1.408734, 0.967298, 0.115658, -0.116267, 1.54164, 0.994751, 0.033911, -0.053828,
+    1.656946,
+];
+
+/// Integral of GGX BRDF over the hemisphere (norm) and integral of the Fresnel term (using
+/// Schlick's approximation with F0 = 0) over the same hemisphere.
+const LTC_AMPLITUDES: [f32; LTC_TABLE_SIZE * LTC_TABLE_SIZE * 2] = [
+    1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 0.999997, 0.0, 0.999988, 0.0, 0.999947,
+    0.000001, 0.999728, 0.000005, 0.994868, 0.000017, 0.983754, 0.000003, 0.982226, 0.00001,
+    0.971871, 0.000016, 0.961277, 0.000017, 0.943574, 0.000017, 0.926735, 0.000026, 0.904186,
+    0.00003, 0.876769, 0.000034, 0.844806, 0.000036, 0.810086, 0.000041, 0.770485, 0.000043,
+    0.728756, 0.000046, 0.684367, 0.000047, 0.638538, 0.000048, 0.592179, 0.000048, 0.546079,
+    0.000047, 0.50093, 0.000045, 0.45733, 0.000043, 0.415858, 0.000041, 0.3769, 0.000039, 0.340529,
+    0.000036, 0.306883, 0.000034, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 0.999999, 0.0, 0.999997,
+    0.0, 0.999986, 0.0, 0.999944, 0.000001, 0.999716, 0.000006, 0.993525, 0.000014, 0.983736,
+    0.000003, 0.982145, 0.000011, 0.971372, 0.000014, 0.96102, 0.000018, 0.94463, 0.000021,
+    0.926569, 0.000026, 0.903808, 0.000031, 0.876724, 0.000035, 0.845367, 0.000039, 0.809657,
+    0.000042, 0.770736, 0.000046, 0.728541, 0.000048, 0.684222, 0.000049, 0.638465, 0.000049,
+    0.592148, 0.000049, 0.546083, 0.000048, 0.500983, 0.000047, 0.457494, 0.000045, 0.416074,
+    0.000042, 0.37706, 0.00004, 0.340675, 0.000037, 0.307037, 0.000034, 1.0, 0.0, 1.0, 0.0, 1.0,
+    0.0, 1.0, 0.0, 0.999998, 0.0, 0.999995, 0.0, 0.999982, 0.0, 0.999934, 0.000001, 0.999677,
+    0.000007, 0.992478, 0.000009, 0.983681, 0.000004, 0.981875, 0.000013, 0.971905, 0.000015,
+    0.960198, 0.000021, 0.945488, 0.000026, 0.926637, 0.00003, 0.903496, 0.000035, 0.876279,
+    0.00004, 0.844843, 0.000044, 0.809532, 0.000048, 0.770427, 0.000051, 0.728334, 0.000053,
+    0.684067, 0.000054, 0.638422, 0.000055, 0.592202, 0.000054, 0.546232, 0.000053, 0.501266,
+    0.000051, 0.457881, 0.000049, 0.416536, 0.000046, 0.377586, 0.000043, 0.341258, 0.00004,
+    0.30765, 0.000037, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 0.999999, 0.0, 0.999997, 0.0, 0.999991, 0.0,
+    0.999974, 0.0, 0.999918, 0.000002, 0.999601, 0.00001, 0.992492, 0.000008, 0.983586, 0.000006,
+    0.981322, 0.000018, 0.972243, 0.000019, 0.958839, 0.000024, 0.945399, 0.000032, 0.925926,
+    0.000037, 0.902972, 0.000043, 0.875901, 0.000048, 0.844483, 0.000053, 0.808977, 0.000058,
+    0.769903, 0.000061, 0.728009, 0.000063, 0.683826, 0.000064, 0.63835, 0.000064, 0.592295,
+    0.000063, 0.546524, 0.000061, 0.501728, 0.000059, 0.458488, 0.000056, 0.417294, 0.000053,
+    0.378468, 0.000049, 0.342219, 0.000046, 0.308664, 0.000042, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0,
+    0.999998, 0.0, 0.999995, 0.0, 0.999986, 0.0, 0.999963, 0.000001, 0.999894, 0.000003, 0.999455,
+    0.000015, 0.992608, 0.000008, 0.984248, 0.000011, 0.980265, 0.000022, 0.972287, 0.000026,
+    0.959109, 0.000031, 0.94455, 0.000039, 0.925206, 0.000047, 0.902638, 0.000055, 0.875291,
+    0.000062, 0.843747, 0.000068, 0.808293, 0.000072, 0.769352, 0.000076, 0.7275, 0.000078,
+    0.683531, 0.000079, 0.638241, 0.000078, 0.592469, 0.000077, 0.546945, 0.000074, 0.502384,
+    0.000071, 0.459396, 0.000067, 0.418387, 0.000062, 0.379707, 0.000058, 0.34357, 0.000053,
+    0.310088, 0.000049, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 0.999998, 0.0, 0.999992, 0.0, 0.999979, 0.0,
+    0.999948, 0.000001, 0.999863, 0.000006, 0.999129, 0.000021, 0.992727, 0.00001, 0.985955,
+    0.000018, 0.9788, 0.000025, 0.971935, 0.000036, 0.959496, 0.000044, 0.943764, 0.000054,
+    0.92501, 0.000064, 0.901743, 0.000073, 0.874168, 0.000081, 0.842726, 0.000088, 0.807344,
+    0.000094, 0.768488, 0.000098, 0.726839, 0.0001, 0.683168, 0.0001, 0.638146, 0.000098, 0.592701,
+    0.000096, 0.547491, 0.000092, 0.503278, 0.000087, 0.460564, 0.000082, 0.419808, 0.000076,
+    0.381333, 0.00007, 0.345344, 0.000064, 0.311958, 0.000059, 1.0, 0.0, 1.0, 0.0, 0.999999, 0.0,
+    0.999996, 0.0, 0.999989, 0.0, 0.99997, 0.0, 0.999929, 0.000002, 0.999821, 0.00001, 0.998132,
+    0.000024, 0.992854, 0.000015, 0.987156, 0.000029, 0.977993, 0.000034, 0.971087, 0.00005,
+    0.959366, 0.000063, 0.942549, 0.000075, 0.9242, 0.000087, 0.900446, 0.000099, 0.873208,
+    0.00011, 0.8417, 0.000118, 0.8063, 0.000125, 0.767582, 0.000129, 0.726135, 0.00013, 0.682689,
+    0.000129, 0.638044, 0.000126, 0.593004, 0.000122, 0.548213, 0.000116, 0.504384, 0.000109,
+    0.462034, 0.000102, 0.421572, 0.000094, 0.383327, 0.000086, 0.347513, 0.000079, 0.31425,
+    0.000071, 1.0, 0.0, 1.0, 0.0, 0.999999, 0.0, 0.999995, 0.0, 0.999984, 0.000001, 0.999959,
+    0.000001, 0.999906, 0.000004, 0.999768, 0.000016, 0.996878, 0.000023, 0.992926, 0.000023,
+    0.987816, 0.000045, 0.977347, 0.000052, 0.96992, 0.000068, 0.958579, 0.000086, 0.941965,
+    0.000104, 0.923143, 0.000122, 0.899351, 0.000137, 0.87198, 0.000151, 0.840229, 0.000161,
+    0.804863, 0.000168, 0.766372, 0.000172, 0.725218, 0.000172, 0.68216, 0.00017, 0.63799,
+    0.000164, 0.593399, 0.000157, 0.549098, 0.000148, 0.505727, 0.000139, 0.463801, 0.000128,
+    0.423698, 0.000118, 0.385744, 0.000108, 0.350145, 0.000098, 0.317016, 0.000088, 1.0, 0.000001,
+    1.0, 0.000001, 0.999999, 0.000001, 0.999993, 0.000001, 0.999979, 0.000002, 0.999947, 0.000003,
+    0.999878, 0.000009, 0.999698, 0.000028, 0.996306, 0.000029, 0.992998, 0.000038, 0.987824,
+    0.000064, 0.97727, 0.00008, 0.96903, 0.000102, 0.957792, 0.000126, 0.941545, 0.00015, 0.921499,
+    0.000172, 0.898219, 0.000193, 0.870284, 0.000209, 0.838634, 0.000222, 0.803495, 0.000229,
+    0.765136, 0.000232, 0.724273, 0.00023, 0.681649, 0.000224, 0.637957, 0.000216, 0.593883,
+    0.000204, 0.550163, 0.000191, 0.507349, 0.000178, 0.465894, 0.000164, 0.426203, 0.000149,
+    0.388603, 0.000136, 0.35326, 0.000122, 0.320289, 0.00011, 1.0, 0.000004, 1.0, 0.000004,
+    0.999998, 0.000004, 0.999991, 0.000005, 0.999973, 0.000005, 0.999931, 0.000008, 0.999845,
+    0.000018, 0.999598, 0.000047, 0.995918, 0.000042, 0.993047, 0.000064, 0.987338, 0.000088,
+    0.978071, 0.000123, 0.96794, 0.000154, 0.956669, 0.000186, 0.940638, 0.000216, 0.919662,
+    0.000247, 0.896626, 0.000273, 0.868308, 0.000292, 0.836965, 0.000306, 0.801713, 0.000313,
+    0.763542, 0.000313, 0.723151, 0.000308, 0.681032, 0.000298, 0.63791, 0.000283, 0.59454,
+    0.000267, 0.551469, 0.000248, 0.509247, 0.000229, 0.468337, 0.000209, 0.429133, 0.00019,
+    0.391913, 0.000172, 0.356839, 0.000154, 0.324075, 0.000138, 1.0, 0.000012, 1.0, 0.000012,
+    0.999998, 0.000012, 0.999989, 0.000013, 0.999965, 0.000015, 0.999913, 0.00002, 0.999804,
+    0.000036, 0.999429, 0.000079, 0.995643, 0.00007, 0.993024, 0.000108, 0.98719, 0.000138,
+    0.978223, 0.000182, 0.966376, 0.000227, 0.955119, 0.000273, 0.939413, 0.000317, 0.918354,
+    0.000355, 0.894537, 0.000387, 0.866537, 0.000411, 0.834811, 0.000424, 0.799728, 0.000429,
+    0.762039, 0.000425, 0.722033, 0.000413, 0.680481, 0.000396, 0.638065, 0.000374, 0.595408,
+    0.000349, 0.553034, 0.000322, 0.511476, 0.000295, 0.471171, 0.000268, 0.4325, 0.000242,
+    0.395691, 0.000218, 0.36096, 0.000195, 0.32841, 0.000174, 1.0, 0.000032, 1.0, 0.000032,
+    0.999997, 0.000032, 0.999986, 0.000033, 0.999956, 0.000036, 0.999891, 0.000046, 0.999756,
+    0.000072, 0.998962, 0.000127, 0.99543, 0.000122, 0.992863, 0.000183, 0.987076, 0.000224,
+    0.978384, 0.000283, 0.964954, 0.000347, 0.953414, 0.00041, 0.937697, 0.000465, 0.91687,
+    0.000514, 0.89187, 0.000552, 0.864345, 0.000578, 0.832263, 0.00059, 0.797769, 0.000589,
+    0.760281, 0.000578, 0.720753, 0.000556, 0.679933, 0.000527, 0.63832, 0.000494, 0.596427,
+    0.000457, 0.554851, 0.000419, 0.514051, 0.000381, 0.474465, 0.000344, 0.436361, 0.000309,
+    0.400016, 0.000277, 0.365649, 0.000247, 0.33333, 0.000219, 1.0, 0.000076, 1.0, 0.000076,
+    0.999997, 0.000076, 0.999983, 0.000078, 0.999946, 0.000084, 0.999865, 0.000101, 0.999697,
+    0.000143, 0.997803, 0.000186, 0.995273, 0.000219, 0.992345, 0.000301, 0.986789, 0.000369,
+    0.978248, 0.000449, 0.964863, 0.000534, 0.951303, 0.000612, 0.935543, 0.000685, 0.914881,
+    0.000744, 0.889384, 0.000788, 0.861625, 0.000813, 0.829922, 0.000819, 0.79535, 0.000808,
+    0.758363, 0.000783, 0.7196, 0.000746, 0.679496, 0.000701, 0.63866, 0.000651, 0.597672,
+    0.000598, 0.556998, 0.000544, 0.517054, 0.000492, 0.478224, 0.000442, 0.44075, 0.000395,
+    0.404919, 0.000352, 0.370951, 0.000313, 0.33891, 0.000277, 1.0, 0.000168, 1.0, 0.000168,
+    0.999996, 0.000169, 0.999979, 0.000173, 0.999934, 0.000184, 0.999834, 0.000212, 0.999625,
+    0.000278, 0.997221, 0.00032, 0.995032, 0.000389, 0.991404, 0.000482, 0.986133, 0.000597,
+    0.977635, 0.000706, 0.964638, 0.000817, 0.94894, 0.000924, 0.932971, 0.001013, 0.912459,
+    0.001081, 0.887039, 0.001125, 0.85832, 0.001143, 0.827316, 0.001136, 0.792641, 0.001106,
+    0.756569, 0.00106, 0.718262, 0.001, 0.679134, 0.000931, 0.639304, 0.000857, 0.599329, 0.000781,
+    0.559597, 0.000707, 0.520552, 0.000635, 0.482502, 0.000568, 0.445744, 0.000506, 0.410482,
+    0.000449, 0.37693, 0.000397, 0.345196, 0.000351, 1.0, 0.000353, 1.0, 0.000353, 0.999995,
+    0.000354, 0.999975, 0.000361, 0.999919, 0.000379, 0.999797, 0.000424, 0.999533, 0.000524,
+    0.996825, 0.000572, 0.994881, 0.000693, 0.990853, 0.000811, 0.985258, 0.000959, 0.976869,
+    0.001114, 0.964171, 0.001262, 0.946447, 0.001392, 0.929937, 0.001496, 0.909429, 0.001566,
+    0.884261, 0.001603, 0.854955, 0.001603, 0.824201, 0.001572, 0.790243, 0.001511, 0.754451,
+    0.001431, 0.717208, 0.001336, 0.678971, 0.001232, 0.6401, 0.001125, 0.601253, 0.001019,
+    0.562601, 0.000916, 0.524579, 0.000819, 0.487376, 0.000729, 0.451378, 0.000646, 0.416729,
+    0.000571, 0.383652, 0.000504, 0.352256, 0.000444, 1.0, 0.000704, 1.0, 0.000704, 0.999994,
+    0.000706, 0.999969, 0.000716, 0.999901, 0.000746, 0.999752, 0.000817, 0.999398, 0.000962,
+    0.996489, 0.00102, 0.994531, 0.001205, 0.990338, 0.001368, 0.984473, 0.001563, 0.975686,
+    0.001757, 0.963108, 0.001937, 0.945241, 0.00209, 0.926318, 0.002202, 0.905741, 0.002263,
+    0.880894, 0.002277, 0.851905, 0.002241, 0.820466, 0.002166, 0.787655, 0.002057, 0.752311,
+    0.001926, 0.716266, 0.00178, 0.67894, 0.001627, 0.641426, 0.001475, 0.603671, 0.001326,
+    0.566127, 0.001185, 0.529148, 0.001054, 0.492926, 0.000934, 0.457752, 0.000825, 0.423762,
+    0.000727, 0.391205, 0.00064, 0.360191, 0.000562, 1.0, 0.001341, 1.0, 0.001342, 0.999993,
+    0.001345, 0.999962, 0.001362, 0.99988, 0.001408, 0.999698, 0.001514, 0.999087, 0.001709,
+    0.996202, 0.001796, 0.993847, 0.002038, 0.989597, 0.002271, 0.983268, 0.002511, 0.974275,
+    0.002749, 0.961457, 0.002959, 0.943729, 0.003121, 0.922145, 0.003224, 0.901425, 0.003254,
+    0.876847, 0.003216, 0.848453, 0.003119, 0.816977, 0.002973, 0.784655, 0.002788, 0.750618,
+    0.00258, 0.715125, 0.002362, 0.679496, 0.002142, 0.642984, 0.001927, 0.606642, 0.001721,
+    0.570361, 0.001531, 0.534407, 0.001355, 0.499231, 0.001196, 0.464938, 0.001053, 0.43169,
+    0.000926, 0.399674, 0.000813, 0.369096, 0.000713, 1.0, 0.00246, 0.999999, 0.00246, 0.999991,
+    0.002466, 0.999954, 0.002491, 0.999853, 0.00256, 0.999631, 0.002714, 0.998015, 0.002897,
+    0.995902, 0.003097, 0.99286, 0.003374, 0.988317, 0.003678, 0.981811, 0.003992, 0.972378,
+    0.004267, 0.959406, 0.004493, 0.941745, 0.004639, 0.919016, 0.00469, 0.896299, 0.004646,
+    0.872118, 0.004518, 0.844477, 0.004315, 0.813781, 0.004055, 0.781352, 0.003759, 0.748908,
+    0.003442, 0.714778, 0.003121, 0.680156, 0.002808, 0.645285, 0.002509, 0.61017, 0.002229,
+    0.575211, 0.001973, 0.540625, 0.00174, 0.50647, 0.001531, 0.473056, 0.001344, 0.440584,
+    0.001178, 0.409204, 0.001033, 0.379104, 0.000905, 1.0, 0.004356, 0.999999, 0.004357, 0.999989,
+    0.004365, 0.999944, 0.004402, 0.999821, 0.004502, 0.999547, 0.004719, 0.997375, 0.004906,
+    0.995537, 0.005221, 0.992124, 0.005558, 0.987208, 0.005929, 0.98004, 0.006273, 0.970132,
+    0.006562, 0.956634, 0.006751, 0.938886, 0.006828, 0.916168, 0.006774, 0.890525, 0.006588,
+    0.866599, 0.006302, 0.839862, 0.005928, 0.810442, 0.005498, 0.778938, 0.005037, 0.746971,
+    0.004568, 0.714861, 0.004107, 0.681565, 0.003668, 0.648203, 0.003258, 0.614622, 0.002879,
+    0.581039, 0.002538, 0.547648, 0.00223, 0.514719, 0.001957, 0.482275, 0.001714, 0.450631,
+    0.001501, 0.41994, 0.001314, 0.390343, 0.00115, 1.0, 0.00748, 0.999999, 0.007481, 0.999986,
+    0.007493, 0.999931, 0.007545, 0.99978, 0.007685, 0.999436, 0.007978, 0.996951, 0.008178,
+    0.995151, 0.008611, 0.991261, 0.008993, 0.985591, 0.009371, 0.977909, 0.009725, 0.967293,
+    0.009962, 0.953089, 0.010036, 0.935136, 0.009954, 0.912527, 0.009678, 0.886082, 0.009259,
+    0.860384, 0.008715, 0.834614, 0.008083, 0.806673, 0.007403, 0.776866, 0.006709, 0.745814,
+    0.006027, 0.715008, 0.005377, 0.683842, 0.00477, 0.651771, 0.004214, 0.619957, 0.003709,
+    0.587841, 0.003257, 0.555828, 0.002855, 0.524065, 0.002499, 0.492753, 0.002187, 0.461986,
+    0.001913, 0.432034, 0.001672, 0.403031, 0.001463, 1.0, 0.012493, 0.999999, 0.012495, 0.999983,
+    0.012511, 0.999915, 0.012581, 0.999728, 0.012767, 0.999269, 0.013144, 0.996562, 0.013349,
+    0.993905, 0.013775, 0.989805, 0.014208, 0.983797, 0.014604, 0.975188, 0.014849, 0.963566,
+    0.014905, 0.948844, 0.014742, 0.930382, 0.014328, 0.908094, 0.013699, 0.882072, 0.012882,
+    0.854056, 0.011935, 0.82877, 0.010922, 0.802622, 0.009888, 0.774914, 0.008873, 0.745891,
+    0.007907, 0.716025, 0.007006, 0.686715, 0.006181, 0.656862, 0.005433, 0.626279, 0.004766,
+    0.595899, 0.004174, 0.56532, 0.003651, 0.534812, 0.003192, 0.504637, 0.002789, 0.474897,
+    0.002437, 0.445724, 0.002132, 0.417369, 0.001864, 1.0, 0.020351, 0.999999, 0.020352, 0.999979,
+    0.020373, 0.999894, 0.020463, 0.999659, 0.020701, 0.99859, 0.02107, 0.99617, 0.021339,
+    0.993021, 0.021744, 0.988375, 0.022131, 0.981269, 0.022329, 0.971755, 0.022327, 0.959077,
+    0.021997, 0.943404, 0.021346, 0.924626, 0.020378, 0.902495, 0.019137, 0.877403, 0.017715,
+    0.849855, 0.016184, 0.822577, 0.014628, 0.798398, 0.013103, 0.773105, 0.011657, 0.746529,
+    0.010309, 0.718883, 0.009079, 0.690544, 0.007971, 0.662759, 0.006985, 0.634428, 0.00611,
+    0.605343, 0.005339, 0.576332, 0.004663, 0.547278, 0.004073, 0.518145, 0.003557, 0.489579,
+    0.00311, 0.461273, 0.00272, 0.433684, 0.002381, 1.0, 0.032405, 0.999998, 0.032407, 0.999973,
+    0.032432, 0.999865, 0.032541, 0.999566, 0.032824, 0.997571, 0.033031, 0.995675, 0.033421,
+    0.991843, 0.033674, 0.986174, 0.033772, 0.978134, 0.033595, 0.967243, 0.033004, 0.953448,
+    0.031959, 0.936745, 0.030449, 0.917434, 0.028554, 0.895861, 0.026385, 0.87197, 0.024058,
+    0.846132, 0.021698, 0.819371, 0.019395, 0.794196, 0.017214, 0.771569, 0.015196, 0.7478,
+    0.013359, 0.72294, 0.01171, 0.696984, 0.010244, 0.670196, 0.00895, 0.643671, 0.007813,
+    0.616901, 0.00682, 0.589276, 0.005955, 0.56142, 0.0052, 0.533956, 0.004543, 0.506294, 0.003973,
+    0.479112, 0.003479, 0.452257, 0.003049, 1.0, 0.050543, 0.999998, 0.050545, 0.999966, 0.050573,
+    0.999826, 0.050693, 0.999435, 0.050995, 0.996955, 0.051003, 0.994349, 0.051163, 0.989983,
+    0.051092, 0.983212, 0.050619, 0.973826, 0.049624, 0.96146, 0.047974, 0.946142, 0.04563,
+    0.928501, 0.042718, 0.909058, 0.039396, 0.888176, 0.035842, 0.86604, 0.032247, 0.842476,
+    0.028745, 0.818161, 0.025449, 0.793508, 0.02241, 0.770433, 0.019657, 0.7499, 0.017203,
+    0.728066, 0.015023, 0.704937, 0.01311, 0.680672, 0.011435, 0.655374, 0.009975, 0.629777,
+    0.008702, 0.604533, 0.0076, 0.578431, 0.006643, 0.551893, 0.005811, 0.525708, 0.005088,
+    0.499602, 0.004463, 0.473584, 0.003917, 1.0, 0.077356, 0.999997, 0.077358, 0.999955, 0.077384,
+    0.999769, 0.077493, 0.999224, 0.077743, 0.996385, 0.077407, 0.992968, 0.077043, 0.987435,
+    0.076156, 0.979264, 0.074533, 0.968031, 0.07198, 0.953729, 0.068423, 0.936918, 0.063973,
+    0.91845, 0.05887, 0.899173, 0.053417, 0.879708, 0.047938, 0.859835, 0.042608, 0.839349,
+    0.037603, 0.818263, 0.033031, 0.796533, 0.028907, 0.774549, 0.025243, 0.753231, 0.022013,
+    0.734431, 0.019187, 0.714487, 0.016719, 0.693056, 0.014573, 0.670404, 0.012717, 0.646541,
+    0.011102, 0.622078, 0.009705, 0.597728, 0.008494, 0.573265, 0.007447, 0.548273, 0.006534,
+    0.52312, 0.005746, 0.498558, 0.005057, 1.0, 0.116354, 0.999996, 0.116355, 0.999938, 0.116368,
+    0.999682, 0.116417, 0.998058, 0.116142, 0.995626, 0.115477, 0.990906, 0.113962, 0.983837,
+    0.111489, 0.973544, 0.107655, 0.959874, 0.102314, 0.943284, 0.095551, 0.925193, 0.087817,
+    0.906602, 0.079513, 0.888274, 0.071097, 0.870906, 0.06302, 0.85404, 0.05545, 0.837452,
+    0.048575, 0.820296, 0.042409, 0.802341, 0.036966, 0.783463, 0.032185, 0.76394, 0.028017,
+    0.744114, 0.024394, 0.725583, 0.021257, 0.70732, 0.018543, 0.687609, 0.016197, 0.666515,
+    0.014165, 0.644288, 0.012412, 0.621232, 0.010887, 0.597672, 0.009573, 0.574462, 0.008429,
+    0.551289, 0.007431, 0.527899, 0.006569, 1.0, 0.172233, 0.999994, 0.172231, 0.99991, 0.172207,
+    0.999538, 0.172089, 0.996966, 0.170912, 0.993645, 0.169026, 0.987617, 0.165544, 0.978157,
+    0.160028, 0.964819, 0.152165, 0.948193, 0.142105, 0.929432, 0.130319, 0.910662, 0.117719,
+    0.892912, 0.104975, 0.876934, 0.092716, 0.862994, 0.081378, 0.850094, 0.071076, 0.837847,
+    0.061941, 0.825189, 0.053891, 0.811526, 0.046862, 0.796533, 0.040756, 0.780183, 0.03547,
+    0.762666, 0.03091, 0.744044, 0.026967, 0.725033, 0.023566, 0.707023, 0.020634, 0.689089,
+    0.018097, 0.669794, 0.015907, 0.649516, 0.014005, 0.628298, 0.012359, 0.60646, 0.01093,
+    0.584259, 0.00968, 0.562048, 0.008585, 1.0, 0.251201, 0.999991, 0.251193, 0.99986, 0.251078,
+    0.999262, 0.250552, 0.996068, 0.248012, 0.990769, 0.243411, 0.981882, 0.235771, 0.968434,
+    0.224414, 0.950964, 0.20948, 0.931154, 0.191799, 0.911361, 0.172708, 0.893598, 0.15351,
+    0.878827, 0.135205, 0.86713, 0.118339, 0.857772, 0.10318, 0.849949, 0.08978, 0.842276, 0.07801,
+    0.834337, 0.067806, 0.824966, 0.05895, 0.814089, 0.051307, 0.801599, 0.044728, 0.78728,
+    0.039051, 0.77152, 0.034168, 0.754422, 0.029957, 0.736294, 0.026317, 0.717534, 0.023179,
+    0.698718, 0.020467, 0.681214, 0.018106, 0.663334, 0.016051, 0.644502, 0.014269, 0.625166,
+    0.012716, 0.605258, 0.011353, 1.0, 0.361379, 0.999985, 0.361357, 0.999752, 0.361024, 0.997468,
+    0.358741, 0.993312, 0.353598, 0.984817, 0.343577, 0.970674, 0.327467, 0.951093, 0.305183,
+    0.92887, 0.278428, 0.907575, 0.249632, 0.889758, 0.221005, 0.876614, 0.19408, 0.867926,
+    0.169646, 0.862361, 0.147803, 0.858924, 0.128587, 0.856325, 0.111825, 0.853375, 0.097218,
+    0.849625, 0.084604, 0.84426, 0.073702, 0.837371, 0.06433, 0.82844, 0.056237, 0.817925,
+    0.049281, 0.805667, 0.0433, 0.792076, 0.038146, 0.776978, 0.033672, 0.760894, 0.029829,
+    0.743887, 0.026488, 0.726173, 0.023584, 0.70807, 0.021059, 0.689785, 0.018844, 0.671932,
+    0.016902, 0.655459, 0.015194, 1.0, 0.51329, 0.999966, 0.513228, 0.999443, 0.512284, 0.995391,
+    0.506747, 0.986311, 0.493818, 0.969485, 0.470384, 0.945122, 0.436013, 0.918747, 0.394756,
+    0.896021, 0.351471, 0.880389, 0.310056, 0.871551, 0.272022, 0.86801, 0.237927, 0.867966,
+    0.207775, 0.8696, 0.181236, 0.871608, 0.157969, 0.873139, 0.137659, 0.873907, 0.120113,
+    0.873295, 0.10492, 0.871074, 0.091784, 0.867233, 0.080452, 0.861888, 0.070689, 0.855034,
+    0.062305, 0.846722, 0.055062, 0.837224, 0.048812, 0.826422, 0.043393, 0.814668, 0.038691,
+    0.802035, 0.034624, 0.788522, 0.03107, 0.774318, 0.027968, 0.759544, 0.025258, 0.744345,
+    0.022882, 0.728755, 0.020781, 1.0, 0.720436, 0.999866, 0.720196, 0.996232, 0.715148, 0.983553,
+    0.696632, 0.955419, 0.655561, 0.918901, 0.595898, 0.889779, 0.531674, 0.874756, 0.471633,
+    0.871253, 0.4176, 0.87424, 0.368892, 0.880028, 0.324948, 0.88648, 0.285488, 0.892498, 0.250307,
+    0.897603, 0.219192, 0.901706, 0.191937, 0.904684, 0.168173, 0.906486, 0.147517, 0.907119,
+    0.129599, 0.906653, 0.114105, 0.905132, 0.100717, 0.902619, 0.089156, 0.899313, 0.079226,
+    0.895183, 0.070653, 0.89025, 0.063226, 0.88457, 0.056775, 0.878198, 0.051156, 0.871235,
+    0.046256, 0.863849, 0.042002, 0.855967, 0.038286, 0.847567, 0.035009, 0.838682, 0.032105,
+    0.829564, 0.029552, 0.999921, 0.995944, 0.875098, 0.8634, 0.922744, 0.874334, 0.938459,
+    0.832769, 0.944425, 0.768965, 0.947247, 0.697137, 0.948786, 0.62504, 0.949712, 0.556681,
+    0.95031, 0.493638, 0.950717, 0.436339, 0.951004, 0.384801, 0.951212, 0.338856, 0.951366,
+    0.298207, 0.951478, 0.262471, 0.951559, 0.231215, 0.951615, 0.203991, 0.95165, 0.180355,
+    0.951667, 0.159885, 0.951667, 0.142189, 0.951652, 0.126909, 0.951623, 0.113724, 0.951582,
+    0.10235, 0.951529, 0.092535, 0.951465, 0.084062, 0.951391, 0.07674, 0.951308, 0.070407,
+    0.951216, 0.064921, 0.951116, 0.060161, 0.951008, 0.056024, 0.950893, 0.052423, 0.950771,
+    0.049281, 0.950642, 0.046534,
+];
+
+fn make_texture(
+    state: &mut PipelineState,
+    pixels: impl Iterator<Item = [f32; 4]>,
+) -> Result<Rc<RefCell<GpuTexture>>, FrameworkError> {
+    let mut bytes = Vec::with_capacity(LTC_TABLE_SIZE * LTC_TABLE_SIZE * 16);
+    for pixel in pixels {
+        for v in pixel.iter() {
+            bytes.extend_from_slice(&v.to_ne_bytes());
+        }
+    }
+
+    let mut texture = GpuTexture::new(
+        state,
+        GpuTextureKind::Rectangle {
+            width: LTC_TABLE_SIZE,
+            height: LTC_TABLE_SIZE,
+        },
+        PixelKind::RGBA32F,
+        MinificationFilter::Linear,
+        MagnificationFilter::Linear,
+        1,
+        Some(&bytes),
+    )?;
+    texture
+        .bind_mut(state, 0)
+        .set_wrap(Coordinate::S, WrapMode::ClampToEdge)
+        .set_wrap(Coordinate::T, WrapMode::ClampToEdge);
+
+    Ok(Rc::new(RefCell::new(texture)))
+}
+
+/// GPU textures with LTC look-up tables.
+pub struct LtcTables {
+    /// Inverse LTC matrices.
+    pub inv_matrices: Rc<RefCell<GpuTexture>>,
+    /// Norm of BRDF (x) and Fresnel term (y).
+    pub amplitudes: Rc<RefCell<GpuTexture>>,
+}
+
+impl LtcTables {
+    pub fn new(state: &mut PipelineState) -> Result<Self, FrameworkError> {
+        Ok(Self {
+            inv_matrices: make_texture(
+                state,
+                LTC_INV_MATRICES
+                    .chunks_exact(4)
+                    .map(|m| [m[0], m[1], m[2], m[3]]),
+            )?,
+            amplitudes: make_texture(
+                state,
+                LTC_AMPLITUDES
+                    .chunks_exact(2)
+                    .map(|a| [a[0], a[1], 0.0, 0.0]),
+            )?,
+        })
+    }
+}
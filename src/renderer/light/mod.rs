@@ -1,6 +1,7 @@
 use crate::renderer::framework::framebuffer::BlendParameters;
 use crate::renderer::framework::geometry_buffer::{GeometryBuffer, GeometryBufferKind};
 use crate::renderer::shadow::csm::CsmRenderContext;
+use crate::scene::light::area::{AreaLight, AreaLightShape};
 use crate::scene::light::directional::DirectionalLight;
 use crate::scene::light::point::PointLight;
 use crate::scene::light::spot::SpotLight;
@@ -25,9 +26,11 @@ use crate::{
             },
         },
         gbuffer::GBuffer,
+        gpu_timer::{GpuPass, GpuTimer},
         light::{
-            ambient::AmbientLightShader, directional::DirectionalLightShader,
-            point::PointLightShader, spot::SpotLightShader,
+            ambient::AmbientLightShader, area::AreaLightShader,
+            directional::DirectionalLightShader, ltc::LtcTables, point::PointLightShader,
+            spot::SpotLightShader,
         },
         light_volume::LightVolumeRenderer,
         shadow::{
@@ -58,7 +61,9 @@ use std::{
 };
 
 pub mod ambient;
+pub mod area;
 pub mod directional;
+mod ltc;
 pub mod point;
 pub mod spot;
 
@@ -70,6 +75,7 @@ pub struct LightingStatistics {
     pub spot_lights_rendered: usize,
     pub spot_shadow_maps_rendered: usize,
     pub directional_lights_rendered: usize,
+    pub area_lights_rendered: usize,
 }
 
 impl AddAssign for LightingStatistics {
@@ -79,6 +85,7 @@ impl AddAssign for LightingStatistics {
         self.spot_lights_rendered += rhs.spot_lights_rendered;
         self.spot_shadow_maps_rendered += rhs.spot_shadow_maps_rendered;
         self.directional_lights_rendered += rhs.directional_lights_rendered;
+        self.area_lights_rendered += rhs.area_lights_rendered;
         self.csm_rendered += rhs.csm_rendered;
     }
 }
@@ -91,12 +98,14 @@ impl Display for LightingStatistics {
             \tPoint Lights: {}\n\
             \tSpot Lights: {}\n\
             \tDirectional Lights: {}\n\
+            \tArea Lights: {}\n\
             \tPoint Shadow Maps: {}\n\
             \tSpot Shadow Maps: {}\n\
             \tSpot Shadow Maps: {}\n",
             self.point_lights_rendered,
             self.spot_lights_rendered,
             self.directional_lights_rendered,
+            self.area_lights_rendered,
             self.point_shadow_maps_rendered,
             self.spot_shadow_maps_rendered,
             self.csm_rendered
@@ -110,6 +119,8 @@ pub struct DeferredLightRenderer {
     point_light_shader: PointLightShader,
     directional_light_shader: DirectionalLightShader,
     ambient_light_shader: AmbientLightShader,
+    area_light_shader: AreaLightShader,
+    ltc_tables: LtcTables,
     quad: GeometryBuffer,
     sphere: GeometryBuffer,
     skybox: GeometryBuffer,
@@ -123,6 +134,7 @@ pub struct DeferredLightRenderer {
 
 pub(crate) struct DeferredRendererContext<'a> {
     pub state: &'a mut PipelineState,
+    pub gpu_timer: &'a mut GpuTimer,
     pub scene: &'a Scene,
     pub camera: &'a Camera,
     pub gbuffer: &'a mut GBuffer,
@@ -189,6 +201,8 @@ impl DeferredLightRenderer {
             point_light_shader: PointLightShader::new(state)?,
             directional_light_shader: DirectionalLightShader::new(state)?,
             ambient_light_shader: AmbientLightShader::new(state)?,
+            area_light_shader: AreaLightShader::new(state)?,
+            ltc_tables: LtcTables::new(state)?,
             quad: GeometryBuffer::from_surface_data(
                 &SurfaceData::make_unit_xy_quad(),
                 GeometryBufferKind::StaticDraw,
@@ -303,6 +317,7 @@ impl DeferredLightRenderer {
 
         let DeferredRendererContext {
             state,
+            gpu_timer,
             scene,
             camera,
             gbuffer,
@@ -342,6 +357,7 @@ impl DeferredLightRenderer {
 
         // Fill SSAO map.
         if settings.use_ssao {
+            gpu_timer.switch(state, GpuPass::Ssao);
             pass_stats += self.ssao_renderer.render(
                 state,
                 gbuffer,
@@ -350,6 +366,8 @@ impl DeferredLightRenderer {
             );
         }
 
+        gpu_timer.switch(state, GpuPass::Lighting);
+
         // Render skybox (if any).
         if let Some(skybox) = camera.skybox_ref() {
             let size = camera.projection().z_far() / 2.0f32.sqrt();
@@ -461,6 +479,14 @@ impl DeferredLightRenderer {
                         && distance_to_camera <= settings.point_shadows_distance
                         && settings.point_shadows_enabled,
                 )
+            } else if let Some(area_light) = light.cast::<AreaLight>() {
+                (
+                    area_light.radius(),
+                    settings.point_shadows_distance,
+                    area_light.base_light_ref().is_cast_shadows()
+                        && distance_to_camera <= settings.point_shadows_distance
+                        && settings.point_shadows_enabled,
+                )
             } else if let Some(directional) = light.cast::<DirectionalLight>() {
                 (
                     f32::MAX,
@@ -500,6 +526,8 @@ impl DeferredLightRenderer {
             let mut light_view_projection = Matrix4::identity();
 
             if shadows_enabled {
+                gpu_timer.switch(state, GpuPass::Shadows);
+
                 if let Some(spot) = light.cast::<SpotLight>() {
                     let light_projection_matrix =
                         Matrix4::new_perspective(1.0, spot.full_cone_angle(), 0.01, light_radius);
@@ -533,7 +561,9 @@ impl DeferredLightRenderer {
                     );
 
                     light_stats.spot_shadow_maps_rendered += 1;
-                } else if light.cast::<PointLight>().is_some() {
+                } else if light.cast::<PointLight>().is_some()
+                    || light.cast::<AreaLight>().is_some()
+                {
                     pass_stats +=
                         self.point_shadow_map_renderer
                             .render(PointShadowMapRenderContext {
@@ -569,6 +599,8 @@ impl DeferredLightRenderer {
 
                     light_stats.csm_rendered += 1;
                 };
+
+                gpu_timer.switch(state, GpuPass::Lighting);
             }
 
             // Mark lighted areas in stencil buffer to do light calculations only on them.
@@ -764,6 +796,60 @@ impl DeferredLightRenderer {
                             );
                     },
                 )
+            } else if let Some(area_light) = light.cast::<AreaLight>() {
+                let shader = &self.area_light_shader;
+
+                light_stats.area_lights_rendered += 1;
+
+                // Half-extents of the shape in world space, the shape lies in local XZ plane.
+                let basis = light.global_transform().basis();
+                let size = area_light.size();
+                let axis_x = basis.column(0) * (size.x * 0.5);
+                let axis_z = basis.column(2) * (size.y * 0.5);
+
+                frame_buffer.draw(
+                    quad,
+                    state,
+                    viewport,
+                    &shader.program,
+                    &draw_params,
+                    |mut program_binding| {
+                        program_binding
+                            .set_bool(&shader.shadows_enabled, shadows_enabled)
+                            .set_bool(&shader.soft_shadows, settings.point_soft_shadows)
+                            .set_vector3(&shader.light_position, &light_position)
+                            .set_vector3(&shader.light_axis_x, &axis_x)
+                            .set_vector3(&shader.light_axis_z, &axis_z)
+                            .set_vector3(&shader.light_direction, &-emit_direction)
+                            .set_bool(&shader.is_disc, area_light.shape() == AreaLightShape::Disc)
+                            .set_bool(&shader.two_sided, area_light.is_two_sided())
+                            .set_f32(&shader.light_radius, light_radius)
+                            .set_matrix4(&shader.inv_view_proj_matrix, &inv_view_projection)
+                            .set_linear_color(
+                                &shader.light_color,
+                                &area_light.base_light_ref().color(),
+                            )
+                            .set_matrix4(&shader.wvp_matrix, &frame_matrix)
+                            .set_vector3(&shader.camera_position, &camera_global_position)
+                            .set_f32(&shader.shadow_bias, area_light.shadow_bias())
+                            .set_f32(
+                                &shader.light_intensity,
+                                area_light.base_light_ref().intensity(),
+                            )
+                            .set_texture(&shader.depth_sampler, &gbuffer_depth_map)
+                            .set_texture(&shader.color_sampler, &gbuffer_diffuse_map)
+                            .set_texture(&shader.normal_sampler, &gbuffer_normal_map)
+                            .set_texture(&shader.material_sampler, &gbuffer_material_map)
+                            .set_texture(&shader.ltc_inv_matrices, &self.ltc_tables.inv_matrices)
+                            .set_texture(&shader.ltc_amplitudes, &self.ltc_tables.amplitudes)
+                            .set_texture(
+                                &shader.point_shadow_texture,
+                                &self
+                                    .point_shadow_map_renderer
+                                    .cascade_texture(cascade_index),
+                            );
+                    },
+                )
             } else if let Some(directional) = light.cast::<DirectionalLight>() {
                 let shader = &self.directional_light_shader;
 
@@ -16,22 +16,28 @@ pub mod framework;
 pub mod batch;
 pub mod cache;
 pub mod debug_renderer;
+pub mod render_graph;
 pub mod renderer2d;
 pub mod ui_renderer;
 
 mod bloom;
+mod cluster;
 mod flat_shader;
 mod forward_renderer;
 mod fxaa;
 mod gbuffer;
+mod gpu_timer;
 mod hdr;
 mod light;
 mod light_volume;
 mod particle_system_renderer;
+mod reflection_probe;
 mod shadow;
 mod skybox_shader;
 mod sprite_renderer;
 mod ssao;
+mod ssr;
+mod taa;
 
 use crate::{
     core::{
@@ -54,6 +60,7 @@ use crate::{
         batch::BatchStorage,
         bloom::BloomRenderer,
         cache::{geometry::GeometryCache, shader::ShaderCache, texture::TextureCache, CacheEntry},
+        cluster::LightClusters,
         debug_renderer::DebugRenderer,
         flat_shader::FlatShader,
         forward_renderer::{ForwardRenderContext, ForwardRenderer},
@@ -69,25 +76,43 @@ use crate::{
             state::{PipelineState, PipelineStatistics},
         },
         fxaa::FxaaRenderer,
-        gbuffer::{GBuffer, GBufferRenderContext},
+        gbuffer::{GBuffer, GBufferRenderContext, MotionVectorRenderContext},
+        gpu_timer::{GpuPass, GpuTimer},
         hdr::HighDynamicRangeRenderer,
         light::{DeferredLightRenderer, DeferredRendererContext, LightingStatistics},
         particle_system_renderer::{ParticleSystemRenderContext, ParticleSystemRenderer},
+        reflection_probe::{
+            EnvironmentRenderContext, ProbeCaptureContext, ReflectionProbeData,
+            ReflectionProbeRenderer,
+        },
+        render_graph::{
+            BuiltInRenderPass, RenderGraph, RenderGraphPassContext, RenderGraphPassKind,
+            RenderGraphResources,
+        },
         renderer2d::Renderer2d,
         sprite_renderer::{SpriteRenderContext, SpriteRenderer},
+        ssr::{ScreenSpaceReflectionsRenderer, SsrRenderContext},
+        taa::{TaaRenderContext, TaaRenderer},
         ui_renderer::{UiRenderContext, UiRenderer},
     },
-    resource::texture::{Texture, TextureKind},
-    scene::{camera::Camera, mesh::surface::SurfaceData, Scene, SceneContainer},
+    resource::texture::{Texture, TextureKind, TexturePixelKind},
+    scene::{
+        camera::Camera, mesh::surface::SurfaceData, node::Node, reflection_probe::ReflectionProbe,
+        Scene, SceneContainer,
+    },
     utils::log::{Log, MessageKind},
 };
 use fxhash::FxHashMap;
 use glow::HasContext;
-use serde::{Deserialize, Serialize};
+use serde::{
+    de::{self, EnumAccess, VariantAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
 use std::{
     cell::RefCell,
     collections::hash_map::Entry,
     fmt::{Display, Formatter},
+    path::Path,
     rc::Rc,
     sync::mpsc::Receiver,
 };
@@ -95,7 +120,7 @@ use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 
 /// Renderer statistics for one frame, also includes current frames per second
 /// amount.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 pub struct Statistics {
     /// Shows how many pipeline state changes was made per frame.
     pub pipeline: PipelineStatistics,
@@ -103,6 +128,9 @@ pub struct Statistics {
     pub lighting: LightingStatistics,
     /// Shows how many draw calls was made and how many triangles were rendered.
     pub geometry: RenderPassStatistics,
+    /// Shows how much time GPU spent to render each pass. Keep in mind, that GPU timings are
+    /// measured asynchronously and they're a few frames behind the rest of the statistics.
+    pub gpu_time: GpuTimeStatistics,
     /// Real time consumed to render frame. Time given in **seconds**.
     pub pure_frame_time: f32,
     /// Total time renderer took to process single frame, usually includes
@@ -125,17 +153,119 @@ impl Display for Statistics {
             Capped Frame Time: {:.2} ms\n\
             {}\n\
             {}\n\
+            {}\n\
             {}\n",
             self.frames_per_second,
             self.pure_frame_time * 1000.0,
             self.capped_frame_time * 1000.0,
             self.geometry,
             self.lighting,
+            self.gpu_time,
             self.pipeline
         )
     }
 }
 
+/// Time (in **milliseconds**) that GPU spent to render each pass of a frame. Time of a pass
+/// includes all cameras and scenes. Timings are available only if the GPU supports timer queries
+/// (OpenGL 3.3, or OpenGL ES 3.0 and WebGL 2 with `EXT_disjoint_timer_query` extension).
+#[derive(Debug, Clone, Default)]
+pub struct GpuTimeStatistics {
+    /// `true` if timings were measured, `false` - if timer queries are not supported or results
+    /// are not ready yet.
+    pub available: bool,
+    /// Time spent to fill G-Buffer.
+    pub gbuffer: f32,
+    /// Time spent to render shadow maps of all lights.
+    pub shadows: f32,
+    /// Time spent to calculate screen-space ambient occlusion.
+    pub ssao: f32,
+    /// Time spent to render skybox, ambient lighting and all lights (excluding shadow maps).
+    pub lighting: f32,
+    /// Time spent to update reflection probes and to render environment and screen-space
+    /// reflections.
+    pub reflections: f32,
+    /// Time spent to render particle systems, sprites, 2D and forward render paths.
+    pub forward: f32,
+    /// Total time spent by custom scene render passes.
+    pub custom: f32,
+    /// Time spent by each custom render pass. Scene render passes are named by their index, for
+    /// example `Scene Render Pass 0 (HDR)`, passes of render graph - by the name of their node.
+    pub custom_passes: Vec<(String, f32)>,
+    /// Time spent to render bloom.
+    pub bloom: f32,
+    /// Time spent on tone mapping, color grading and gamma correction.
+    pub hdr: f32,
+    /// Time spent on anti-aliasing (FXAA or TAA).
+    pub anti_aliasing: f32,
+    /// Time spent to render user interface.
+    pub ui: f32,
+    /// Time spent on everything else (debug geometry, copying frames, etc.).
+    pub other: f32,
+}
+
+impl GpuTimeStatistics {
+    /// Returns total time (in **milliseconds**) that GPU spent to render a frame.
+    pub fn total(&self) -> f32 {
+        self.gbuffer
+            + self.shadows
+            + self.ssao
+            + self.lighting
+            + self.reflections
+            + self.forward
+            + self.custom
+            + self.bloom
+            + self.hdr
+            + self.anti_aliasing
+            + self.ui
+            + self.other
+    }
+}
+
+impl Display for GpuTimeStatistics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if !self.available {
+            return write!(f, "GPU Time: N/A");
+        }
+
+        write!(
+            f,
+            "GPU Time: {:.2} ms\n\
+            \tG-Buffer: {:.2} ms\n\
+            \tShadows: {:.2} ms\n\
+            \tSSAO: {:.2} ms\n\
+            \tLighting: {:.2} ms\n\
+            \tReflections: {:.2} ms\n\
+            \tForward: {:.2} ms\n\
+            \tCustom: {:.2} ms\n\
+            \tBloom: {:.2} ms\n\
+            \tHDR: {:.2} ms\n\
+            \tAnti-aliasing: {:.2} ms\n\
+            \tUI: {:.2} ms\n\
+            \tOther: {:.2} ms",
+            self.total(),
+            self.gbuffer,
+            self.shadows,
+            self.ssao,
+            self.lighting,
+            self.reflections,
+            self.forward,
+            self.custom,
+            self.bloom,
+            self.hdr,
+            self.anti_aliasing,
+            self.ui,
+            self.other
+        )?;
+
+        for (name, time) in self.custom_passes.iter() {
+            write!(f, "\n\tCustom - {}: {:.2} ms", name, time)?;
+        }
+
+        Ok(())
+    }
+}
+
 /// GPU statistics for single frame.
 #[derive(Debug, Copy, Clone, Default)]
 pub struct RenderPassStatistics {
@@ -203,6 +333,42 @@ pub enum ShadowMapPrecision {
     Full,
 }
 
+/// Anti-aliasing technique that is used to smooth jagged edges of a rendered frame.
+#[derive(
+    Copy,
+    Clone,
+    Hash,
+    PartialOrd,
+    PartialEq,
+    Eq,
+    Ord,
+    Debug,
+    Serialize,
+    Deserialize,
+    Inspect,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum AntialiasingMode {
+    /// No anti-aliasing.
+    None,
+    /// Fast Approximate Anti-Aliasing. Cheap post-processing pass, but it blurs the frame
+    /// a bit and cannot fix sub-pixel details.
+    Fxaa,
+    /// Temporal Anti-Aliasing. Renders each frame with a sub-pixel offset and blends it
+    /// with the history of previous frames. Gives much better quality than FXAA, but may
+    /// produce ghosting on fast moving objects.
+    Taa,
+}
+
+impl Default for AntialiasingMode {
+    fn default() -> Self {
+        Self::Fxaa
+    }
+}
+
 /// Cascaded-shadow maps settings.
 #[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Inspect, Reflect, Eq)]
 pub struct CsmSettings {
@@ -231,6 +397,39 @@ impl Default for CsmSettings {
     }
 }
 
+/// Screen-space reflections settings.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Inspect, Reflect)]
+pub struct SsrSettings {
+    /// Whether screen-space reflections enabled or not.
+    pub enabled: bool,
+
+    /// Maximum amount of ray marching steps for each pixel. The more steps, the more precise
+    /// reflections are, but the worse performance is.
+    pub max_steps: usize,
+
+    /// Maximum distance (in world units) that a reflected ray can travel.
+    pub max_distance: f32,
+
+    /// Maximum distance (in view space units) between a ray and a surface behind it, at which
+    /// the ray is still considered as hitting the surface.
+    pub thickness: f32,
+
+    /// Surfaces with roughness higher than this value will not have any reflections.
+    pub max_roughness: f32,
+}
+
+impl Default for SsrSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_steps: 64,
+            max_distance: 50.0,
+            thickness: 0.5,
+            max_roughness: 0.6,
+        }
+    }
+}
+
 /// Quality settings allows you to find optimal balance between performance and
 /// graphics quality.
 #[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Inspect, Reflect)]
@@ -264,6 +463,10 @@ pub struct QualitySettings {
     /// Cascaded-shadow maps settings.
     pub csm_settings: CsmSettings,
 
+    /// Screen-space reflections settings.
+    #[serde(default)]
+    pub ssr_settings: SsrSettings,
+
     /// Whether to use screen space ambient occlusion or not.
     pub use_ssao: bool,
     /// Radius of sampling hemisphere used in SSAO, it defines much ambient
@@ -274,8 +477,15 @@ pub struct QualitySettings {
     /// its own scatter switch, but this one is able to globally disable scatter.
     pub light_scatter_enabled: bool,
 
-    /// Whether to use Fast Approximate AntiAliasing or not.
-    pub fxaa: bool,
+    /// Anti-aliasing technique that will be used. Settings that were saved before this field
+    /// was added have `fxaa` flag instead, it is converted to [`AntialiasingMode::Fxaa`] or
+    /// [`AntialiasingMode::None`].
+    #[serde(
+        default,
+        alias = "fxaa",
+        deserialize_with = "deserialize_antialiasing_mode"
+    )]
+    pub antialiasing: AntialiasingMode,
 
     /// Whether to use Parallax Mapping or not.
     pub use_parallax_mapping: bool,
@@ -284,6 +494,57 @@ pub struct QualitySettings {
     pub use_bloom: bool,
 }
 
+fn deserialize_antialiasing_mode<'de, D>(deserializer: D) -> Result<AntialiasingMode, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    // Legacy `fxaa` flag is read as a unit variant, it works for the formats that read enum
+    // variants as identifiers (like RON).
+    const VARIANTS: &[&str] = &["None", "Fxaa", "Taa", "true", "false"];
+
+    #[derive(Deserialize)]
+    #[serde(variant_identifier)]
+    enum VariantName {
+        None,
+        Fxaa,
+        Taa,
+        #[serde(rename = "true")]
+        True,
+        #[serde(rename = "false")]
+        False,
+    }
+
+    struct ModeVisitor;
+
+    impl<'de> Visitor<'de> for ModeVisitor {
+        type Value = AntialiasingMode;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(formatter, "anti-aliasing mode or legacy fxaa flag")
+        }
+
+        fn visit_bool<E: de::Error>(self, fxaa: bool) -> Result<Self::Value, E> {
+            Ok(if fxaa {
+                AntialiasingMode::Fxaa
+            } else {
+                AntialiasingMode::None
+            })
+        }
+
+        fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+            let (name, variant) = data.variant::<VariantName>()?;
+            variant.unit_variant()?;
+            Ok(match name {
+                VariantName::None | VariantName::False => AntialiasingMode::None,
+                VariantName::Fxaa | VariantName::True => AntialiasingMode::Fxaa,
+                VariantName::Taa => AntialiasingMode::Taa,
+            })
+        }
+    }
+
+    deserializer.deserialize_enum("AntialiasingMode", VARIANTS, ModeVisitor)
+}
+
 impl Default for QualitySettings {
     fn default() -> Self {
         Self::high()
@@ -312,13 +573,18 @@ impl QualitySettings {
             point_shadow_map_precision: ShadowMapPrecision::Full,
             spot_shadow_map_precision: ShadowMapPrecision::Full,
 
-            fxaa: true,
+            antialiasing: AntialiasingMode::Fxaa,
 
             use_bloom: true,
 
             use_parallax_mapping: false, // TODO: Enable when it is fixed!
 
             csm_settings: Default::default(),
+
+            ssr_settings: SsrSettings {
+                enabled: true,
+                ..Default::default()
+            },
         }
     }
 
@@ -343,7 +609,7 @@ impl QualitySettings {
             point_shadow_map_precision: ShadowMapPrecision::Full,
             spot_shadow_map_precision: ShadowMapPrecision::Full,
 
-            fxaa: true,
+            antialiasing: AntialiasingMode::Fxaa,
 
             use_bloom: true,
 
@@ -355,6 +621,8 @@ impl QualitySettings {
                 precision: ShadowMapPrecision::Full,
                 pcf: true,
             },
+
+            ssr_settings: Default::default(),
         }
     }
 
@@ -379,7 +647,7 @@ impl QualitySettings {
             point_shadow_map_precision: ShadowMapPrecision::Half,
             spot_shadow_map_precision: ShadowMapPrecision::Half,
 
-            fxaa: true,
+            antialiasing: AntialiasingMode::Fxaa,
 
             use_bloom: true,
 
@@ -391,6 +659,8 @@ impl QualitySettings {
                 precision: ShadowMapPrecision::Full,
                 pcf: false,
             },
+
+            ssr_settings: Default::default(),
         }
     }
 
@@ -415,7 +685,7 @@ impl QualitySettings {
             point_shadow_map_precision: ShadowMapPrecision::Half,
             spot_shadow_map_precision: ShadowMapPrecision::Half,
 
-            fxaa: false,
+            antialiasing: AntialiasingMode::None,
 
             use_bloom: false,
 
@@ -427,6 +697,8 @@ impl QualitySettings {
                 precision: ShadowMapPrecision::Half,
                 pcf: false,
             },
+
+            ssr_settings: Default::default(),
         }
     }
 }
@@ -473,6 +745,7 @@ impl Default for Statistics {
             pipeline: Default::default(),
             lighting: Default::default(),
             geometry: Default::default(),
+            gpu_time: Default::default(),
             pure_frame_time: 0.0,
             capped_frame_time: 0.0,
             frames_per_second: 0,
@@ -504,6 +777,27 @@ pub struct AssociatedSceneData {
     /// Bloom contains only overly bright pixels that creates light
     /// bleeding effect (glow effect).
     pub bloom_renderer: BloomRenderer,
+
+    /// Screen-space reflections renderer, it has to be created per scene, because it contains
+    /// hierarchical depth buffer of the scene.
+    pub ssr_renderer: ScreenSpaceReflectionsRenderer,
+
+    /// Environment reflections (from reflection probes and environment map of a camera) of each
+    /// pixel, it is used as a fallback for screen-space reflections.
+    pub environment_reflections_framebuffer: FrameBuffer,
+
+    /// Environment cube maps of reflection probes of the scene that are rendered at run-time.
+    pub reflection_probes: FxHashMap<Handle<Node>, ReflectionProbeData>,
+
+    /// Accumulated frames of temporal anti-aliasing.
+    pub taa_history_framebuffer: FrameBuffer,
+
+    /// Frame buffers of transient textures of the render graph.
+    pub transient_framebuffers: FxHashMap<String, FrameBuffer>,
+
+    /// Unjittered view-projection matrix of the camera from previous frame. It is `None` if
+    /// there is no valid history yet.
+    pub taa_previous_view_projection: Option<Matrix4<f32>>,
 }
 
 impl AssociatedSceneData {
@@ -598,13 +892,57 @@ impl AssociatedSceneData {
             }],
         )?;
 
+        let taa_history_texture = GpuTexture::new(
+            state,
+            GpuTextureKind::Rectangle { width, height },
+            PixelKind::RGBA8,
+            MinificationFilter::Linear,
+            MagnificationFilter::Linear,
+            1,
+            None,
+        )?;
+
+        let taa_history_framebuffer = FrameBuffer::new(
+            state,
+            None,
+            vec![Attachment {
+                kind: AttachmentKind::Color,
+                texture: Rc::new(RefCell::new(taa_history_texture)),
+            }],
+        )?;
+
+        let environment_reflections_texture = GpuTexture::new(
+            state,
+            GpuTextureKind::Rectangle { width, height },
+            PixelKind::RGBA16F,
+            MinificationFilter::Linear,
+            MagnificationFilter::Linear,
+            1,
+            None,
+        )?;
+
+        let environment_reflections_framebuffer = FrameBuffer::new(
+            state,
+            None,
+            vec![Attachment {
+                kind: AttachmentKind::Color,
+                texture: Rc::new(RefCell::new(environment_reflections_texture)),
+            }],
+        )?;
+
         Ok(Self {
             gbuffer: GBuffer::new(state, width, height)?,
             hdr_renderer: HighDynamicRangeRenderer::new(state)?,
             bloom_renderer: BloomRenderer::new(state, width, height)?,
+            ssr_renderer: ScreenSpaceReflectionsRenderer::new(state, width, height)?,
+            environment_reflections_framebuffer,
+            reflection_probes: Default::default(),
             hdr_scene_framebuffer,
             ldr_scene_framebuffer,
             ldr_temp_framebuffer,
+            taa_history_framebuffer,
+            taa_previous_view_projection: None,
+            transient_framebuffers: Default::default(),
         })
     }
 
@@ -646,6 +984,20 @@ impl AssociatedSceneData {
             .texture
             .clone()
     }
+
+    /// Returns texture with environment reflections of each pixel.
+    pub fn environment_reflections_texture(&self) -> Rc<RefCell<GpuTexture>> {
+        self.environment_reflections_framebuffer.color_attachments()[0]
+            .texture
+            .clone()
+    }
+
+    /// Returns texture with accumulated frames of temporal anti-aliasing.
+    pub fn taa_history_texture(&self) -> Rc<RefCell<GpuTexture>> {
+        self.taa_history_framebuffer.color_attachments()[0]
+            .texture
+            .clone()
+    }
 }
 
 pub(crate) fn make_viewport_matrix(viewport: Rect<i32>) -> Matrix4<f32> {
@@ -666,6 +1018,8 @@ pub(crate) fn make_viewport_matrix(viewport: Rect<i32>) -> Matrix4<f32> {
 /// See module docs.
 pub struct Renderer {
     backbuffer: FrameBuffer,
+    render_graph: RenderGraph,
+    gpu_timer: GpuTimer,
     scene_render_passes: Vec<Rc<RefCell<dyn SceneRenderPass>>>,
     deferred_light_renderer: DeferredLightRenderer,
     flat_shader: FlatShader,
@@ -700,6 +1054,10 @@ pub struct Renderer {
     batch_storage: BatchStorage,
     forward_renderer: ForwardRenderer,
     fxaa_renderer: FxaaRenderer,
+    taa_renderer: TaaRenderer,
+    reflection_probe_renderer: ReflectionProbeRenderer,
+    // Index of current frame, it is used to select sub-pixel jitter for TAA.
+    frame_index: u64,
     renderer2d: Renderer2d,
     texture_event_receiver: Receiver<ResourceEvent<Texture>>,
     shader_event_receiver: Receiver<ResourceEvent<Shader>>,
@@ -838,6 +1196,7 @@ pub struct SceneRenderPassContext<'a, 'b> {
 }
 
 /// A trait for custom scene rendering pass. It could be used to add your own rendering techniques.
+/// If you need more control over the order of passes, use [`render_graph::RenderGraph`] instead.
 pub trait SceneRenderPass {
     /// Renders scene into high dynamic range target. It will be called for **each** scene
     /// registered in the engine, but you are able to filter out scene by its handle.
@@ -914,6 +1273,7 @@ pub(crate) struct MaterialContext<'a, 'b, 'c> {
     pub camera_position: &'a Vector3<f32>,
     pub use_pom: bool,
     pub light_position: &'a Vector3<f32>,
+    pub light_clusters: Option<&'a LightClusters>,
 
     // Fallback samplers.
     pub normal_dummy: Rc<RefCell<GpuTexture>>,
@@ -950,6 +1310,36 @@ pub(crate) fn apply_material(ctx: MaterialContext) {
         ctx.program_binding
             .set_vector3(location, ctx.light_position);
     }
+    if let Some(light_clusters) = ctx.light_clusters {
+        if let Some(location) = &built_in_uniforms[BuiltInUniform::ViewMatrix as usize] {
+            ctx.program_binding
+                .set_matrix4(location, light_clusters.view_matrix());
+        }
+        if let Some(location) = &built_in_uniforms[BuiltInUniform::AmbientLightColor as usize] {
+            ctx.program_binding
+                .set_linear_color(location, light_clusters.ambient_color());
+        }
+        if let Some(location) = &built_in_uniforms[BuiltInUniform::LightData as usize] {
+            ctx.program_binding
+                .set_texture(location, light_clusters.light_data());
+        }
+        if let Some(location) = &built_in_uniforms[BuiltInUniform::LightClusters as usize] {
+            ctx.program_binding
+                .set_texture(location, light_clusters.clusters());
+        }
+        if let Some(location) = &built_in_uniforms[BuiltInUniform::LightIndices as usize] {
+            ctx.program_binding
+                .set_texture(location, light_clusters.light_indices());
+        }
+        if let Some(location) = &built_in_uniforms[BuiltInUniform::ClusterParameters as usize] {
+            ctx.program_binding
+                .set_vector4(location, light_clusters.parameters());
+        }
+        if let Some(location) = &built_in_uniforms[BuiltInUniform::Viewport as usize] {
+            ctx.program_binding
+                .set_vector4(location, light_clusters.viewport());
+        }
+    }
 
     // Apply material properties.
     for (name, value) in ctx.material.properties() {
@@ -1070,6 +1460,8 @@ impl Renderer {
 
         Ok(Self {
             backbuffer: FrameBuffer::backbuffer(&mut state),
+            render_graph: Default::default(),
+            gpu_timer: GpuTimer::new(&state),
             frame_size,
             deferred_light_renderer: DeferredLightRenderer::new(&mut state, frame_size, &settings)?,
             flat_shader: FlatShader::new(&mut state)?,
@@ -1155,9 +1547,12 @@ impl Renderer {
             texture_cache: Default::default(),
             geometry_cache: Default::default(),
             batch_storage: Default::default(),
-            forward_renderer: ForwardRenderer::new(),
+            forward_renderer: ForwardRenderer::new(&mut state)?,
             ui_frame_buffers: Default::default(),
             fxaa_renderer: FxaaRenderer::new(&mut state)?,
+            taa_renderer: TaaRenderer::new(&mut state)?,
+            reflection_probe_renderer: ReflectionProbeRenderer::new(&mut state)?,
+            frame_index: 0,
             statistics: Statistics::default(),
             renderer2d: Renderer2d::new(&mut state)?,
             shader_event_receiver,
@@ -1168,6 +1563,17 @@ impl Renderer {
         })
     }
 
+    /// Returns a reference to the render graph that defines passes of scene rendering.
+    pub fn render_graph(&self) -> &RenderGraph {
+        &self.render_graph
+    }
+
+    /// Returns a reference to the render graph that defines passes of scene rendering. It can be
+    /// used to add custom passes and to disable, replace or remove built-in passes.
+    pub fn render_graph_mut(&mut self) -> &mut RenderGraph {
+        &mut self.render_graph
+    }
+
     /// Adds a custom render pass.
     pub fn add_render_pass(&mut self, pass: Rc<RefCell<dyn SceneRenderPass>>) {
         self.scene_render_passes.push(pass);
@@ -1196,7 +1602,7 @@ impl Renderer {
 
     /// Returns statistics for last frame.
     pub fn get_statistics(&self) -> Statistics {
-        self.statistics
+        self.statistics.clone()
     }
 
     /// Unloads texture from GPU memory.
@@ -1259,6 +1665,16 @@ impl Renderer {
         self.quality_settings
     }
 
+    /// Returns sub-pixel jitter (in pixels) that should be applied to cameras for the next frame.
+    /// It is always zero if temporal anti-aliasing is disabled.
+    pub fn camera_jitter(&self) -> Vector2<f32> {
+        if self.quality_settings.antialiasing == AntialiasingMode::Taa {
+            taa::jitter(self.frame_index)
+        } else {
+            Vector2::default()
+        }
+    }
+
     /// Removes all cached GPU data, forces renderer to re-upload data to GPU.
     /// Do not call this method until you absolutely need! It may cause **significant**
     /// performance lag!
@@ -1268,6 +1684,98 @@ impl Renderer {
         self.renderer2d.flush();
     }
 
+    /// Renders environment of the given reflection probe and returns it as a cube map texture that
+    /// can be assigned to the probe as baked environment (see
+    /// [`ReflectionProbe::set_baked_environment`]). Baked probes are not re-rendered at run-time.
+    ///
+    /// The texture has no source file, its content is stored in the scene when it is saved. Use
+    /// [`Self::bake_reflection_probe_to_file`] to store the environment as a separate texture
+    /// resource.
+    pub fn bake_reflection_probe(
+        &mut self,
+        scene: &Scene,
+        probe: Handle<Node>,
+    ) -> Result<Texture, FrameworkError> {
+        self.bake_reflection_probe_internal(scene, probe, true)
+    }
+
+    /// Does the same as [`Self::bake_reflection_probe`], but also saves the environment as a DDS
+    /// file at the given path (the path should have `.dds` extension). The returned texture
+    /// references the file, so the scene will store only the path and the texture will be loaded
+    /// by the resource manager as any other texture resource.
+    pub fn bake_reflection_probe_to_file<P: AsRef<Path>>(
+        &mut self,
+        scene: &Scene,
+        probe: Handle<Node>,
+        path: P,
+    ) -> Result<Texture, FrameworkError> {
+        let texture = self.bake_reflection_probe_internal(scene, probe, false)?;
+
+        let mut data = texture.data_ref();
+        data.set_path(path);
+        data.save().map_err(|e| {
+            FrameworkError::Custom(format!("Unable to save baked environment! Reason: {}", e))
+        })?;
+        drop(data);
+
+        Ok(texture)
+    }
+
+    fn bake_reflection_probe_internal(
+        &mut self,
+        scene: &Scene,
+        probe: Handle<Node>,
+        serialize_content: bool,
+    ) -> Result<Texture, FrameworkError> {
+        let probe_ref = scene
+            .graph
+            .try_get(probe)
+            .and_then(|node| node.cast::<ReflectionProbe>())
+            .ok_or_else(|| {
+                FrameworkError::Custom(format!("{} is not a reflection probe!", probe))
+            })?;
+
+        self.batch_storage.generate_batches(&scene.graph);
+
+        let bytes = self.reflection_probe_renderer.bake(
+            ProbeCaptureContext {
+                state: &mut self.state,
+                gpu_timer: &mut self.gpu_timer,
+                scene,
+                deferred_light_renderer: &mut self.deferred_light_renderer,
+                geometry_cache: &mut self.geometry_cache,
+                texture_cache: &mut self.texture_cache,
+                shader_cache: &mut self.shader_cache,
+                batch_storage: &self.batch_storage,
+                quality_settings: &self.quality_settings,
+                white_dummy: self.white_dummy.clone(),
+                normal_dummy: self.normal_dummy.clone(),
+                black_dummy: self.black_dummy.clone(),
+                environment_dummy: self.environment_dummy.clone(),
+            },
+            probe_ref,
+        );
+
+        // Baking is not a part of any frame, so its timings must not be reported.
+        self.gpu_timer.discard_frame(&mut self.state);
+
+        let bytes = bytes?;
+
+        let resolution = probe_ref.resolution() as u32;
+        Texture::from_bytes(
+            TextureKind::Cube {
+                width: resolution,
+                height: resolution,
+            },
+            TexturePixelKind::RGBA32F,
+            bytes,
+            serialize_content,
+        )
+        .ok_or_else(|| {
+            FrameworkError::Custom("Unable to create baked environment texture!".to_owned())
+        })
+    }
+
     /// Renders given UI into specified render target. This method is especially useful if you need
     /// to have off-screen UIs (like interactive touch-screen in Doom 3, Dead Space, etc).
     pub fn render_ui_to_texture(
@@ -1390,17 +1898,13 @@ impl Renderer {
         self.renderer2d.update_caches(dt);
     }
 
-    fn render_frame(
+    fn render_frame<'a>(
         &mut self,
-        scenes: &SceneContainer,
+        scenes: impl Iterator<Item = (Handle<Scene>, &'a Scene)>,
         drawing_context: &DrawingContext,
     ) -> Result<(), FrameworkError> {
         scope_profile!();
 
-        // Make sure to drop associated data for destroyed scenes.
-        self.scene_data_map
-            .retain(|h, _| scenes.is_valid_handle(*h));
-
         // We have to invalidate resource bindings cache because some textures or programs,
         // or other GL resources can be destroyed and then on their "names" some new resource
         // are created, but cache still thinks that resource is correctly bound, but it is different
@@ -1408,6 +1912,10 @@ impl Renderer {
         self.state.invalidate_resource_bindings_cache();
         let dt = self.statistics.capped_frame_time;
         self.statistics.begin_frame();
+        self.frame_index = self.frame_index.wrapping_add(1);
+
+        self.gpu_timer.begin_frame(&mut self.state);
+        self.gpu_timer.switch(&mut self.state, GpuPass::Other);
 
         let window_viewport = Rect::new(0, 0, self.frame_size.0 as i32, self.frame_size.1 as i32);
         self.backbuffer.clear(
@@ -1421,7 +1929,19 @@ impl Renderer {
         let backbuffer_width = self.frame_size.0 as f32;
         let backbuffer_height = self.frame_size.1 as f32;
 
-        for (scene_handle, scene) in scenes.pair_iter().filter(|(_, s)| s.enabled) {
+        let (passes, error) = self.render_graph.compile();
+        if let Some(error) = error {
+            Log::err(format!(
+                "Unable to compile render graph, passes will be executed in the order they were added. Reason: {}",
+                error
+            ));
+        }
+        let bloom_enabled = self
+            .render_graph
+            .pass(BuiltInRenderPass::Bloom.name())
+            .map_or(false, |pass| pass.is_enabled());
+
+        for (scene_handle, scene) in scenes {
             let graph = &scene.graph;
 
             let frame_size = scene
@@ -1493,6 +2013,34 @@ impl Renderer {
                 );
             }
 
+            self.gpu_timer.switch(state, GpuPass::Reflections);
+            self.statistics.geometry += self.reflection_probe_renderer.update_probes(
+                ProbeCaptureContext {
+                    state,
+                    gpu_timer: &mut self.gpu_timer,
+                    scene,
+                    deferred_light_renderer: &mut self.deferred_light_renderer,
+                    geometry_cache: &mut self.geometry_cache,
+                    texture_cache: &mut self.texture_cache,
+                    shader_cache: &mut self.shader_cache,
+                    batch_storage: &self.batch_storage,
+                    quality_settings: &self.quality_settings,
+                    white_dummy: self.white_dummy.clone(),
+                    normal_dummy: self.normal_dummy.clone(),
+                    black_dummy: self.black_dummy.clone(),
+                    environment_dummy: self.environment_dummy.clone(),
+                },
+                &mut scene_associated_data.reflection_probes,
+            )?;
+
+            render_graph::update_transient_framebuffers(
+                state,
+                self.render_graph.transient_textures(),
+                &mut scene_associated_data.transient_framebuffers,
+                frame_size.x as usize,
+                frame_size.y as usize,
+            )?;
+
             for camera in graph.linear_iter().filter_map(|node| {
                 if let Some(camera) = node.cast::<Camera>() {
                     if camera.is_enabled() {
@@ -1506,217 +2054,424 @@ impl Renderer {
             }) {
                 let viewport = camera.viewport_pixels(frame_size);
 
-                self.statistics += scene_associated_data.gbuffer.fill(GBufferRenderContext {
-                    state,
-                    camera,
-                    geom_cache: &mut self.geometry_cache,
-                    batch_storage: &self.batch_storage,
-                    texture_cache: &mut self.texture_cache,
-                    shader_cache: &mut self.shader_cache,
-                    environment_dummy: self.environment_dummy.clone(),
-                    use_parallax_mapping: self.quality_settings.use_parallax_mapping,
-                    normal_dummy: self.normal_dummy.clone(),
-                    white_dummy: self.white_dummy.clone(),
-                    black_dummy: self.black_dummy.clone(),
-                    graph,
-                });
-
-                scene_associated_data.copy_depth_stencil_to_scene_framebuffer(state);
-
-                scene_associated_data.hdr_scene_framebuffer.clear(
-                    state,
-                    viewport,
-                    Some(self.backbuffer_clear_color),
-                    None, // Keep depth, we've just copied valid data in it.
-                    Some(0),
-                );
-
-                let (pass_stats, light_stats) =
-                    self.deferred_light_renderer
-                        .render(DeferredRendererContext {
-                            state,
-                            scene,
-                            camera,
-                            gbuffer: &mut scene_associated_data.gbuffer,
-                            white_dummy: self.white_dummy.clone(),
-                            ambient_color: scene.ambient_lighting_color,
-                            settings: &self.quality_settings,
-                            textures: &mut self.texture_cache,
-                            geometry_cache: &mut self.geometry_cache,
-                            batch_storage: &self.batch_storage,
-                            frame_buffer: &mut scene_associated_data.hdr_scene_framebuffer,
-                            shader_cache: &mut self.shader_cache,
-                            normal_dummy: self.normal_dummy.clone(),
-                            black_dummy: self.black_dummy.clone(),
-                        });
-
-                self.statistics.lighting += light_stats;
-                self.statistics.geometry += pass_stats;
-
-                let depth = scene_associated_data.gbuffer.depth();
-
-                self.statistics +=
-                    self.particle_system_renderer
-                        .render(ParticleSystemRenderContext {
-                            state,
-                            framebuffer: &mut scene_associated_data.hdr_scene_framebuffer,
-                            graph,
-                            camera,
-                            white_dummy: self.white_dummy.clone(),
-                            depth,
-                            frame_width: frame_size.x,
-                            frame_height: frame_size.y,
-                            viewport,
-                            texture_cache: &mut self.texture_cache,
-                        });
-
-                self.statistics += self.sprite_renderer.render(SpriteRenderContext {
-                    state,
-                    framebuffer: &mut scene_associated_data.hdr_scene_framebuffer,
-                    graph,
-                    camera,
-                    white_dummy: self.white_dummy.clone(),
-                    viewport,
-                    textures: &mut self.texture_cache,
-                });
-
-                self.statistics += self.renderer2d.render(
-                    state,
-                    camera,
-                    &mut scene_associated_data.hdr_scene_framebuffer,
-                    viewport,
-                    graph,
-                    &mut self.texture_cache,
-                    self.white_dummy.clone(),
-                    scene.ambient_lighting_color,
-                )?;
-
-                self.statistics += self.forward_renderer.render(ForwardRenderContext {
-                    state,
-                    camera,
-                    geom_cache: &mut self.geometry_cache,
-                    texture_cache: &mut self.texture_cache,
-                    shader_cache: &mut self.shader_cache,
-                    batch_storage: &self.batch_storage,
-                    framebuffer: &mut scene_associated_data.hdr_scene_framebuffer,
-                    viewport,
-                    quality_settings: &self.quality_settings,
-                    white_dummy: self.white_dummy.clone(),
-                    normal_dummy: self.normal_dummy.clone(),
-                    black_dummy: self.black_dummy.clone(),
-                });
-
-                for render_pass in self.scene_render_passes.iter() {
-                    self.statistics +=
-                        render_pass
-                            .borrow_mut()
-                            .on_hdr_render(SceneRenderPassContext {
-                                pipeline_state: state,
-                                texture_cache: &mut self.texture_cache,
-                                geometry_cache: &mut self.geometry_cache,
-                                quality_settings: &self.quality_settings,
-                                batch_storage: &self.batch_storage,
+                for (name, pass) in passes.iter() {
+                    match pass {
+                        RenderGraphPassKind::BuiltIn(BuiltInRenderPass::GBuffer) => {
+                            self.gpu_timer.switch(state, GpuPass::GBuffer);
+                            self.statistics +=
+                                scene_associated_data.gbuffer.fill(GBufferRenderContext {
+                                    state,
+                                    camera,
+                                    geom_cache: &mut self.geometry_cache,
+                                    batch_storage: &self.batch_storage,
+                                    texture_cache: &mut self.texture_cache,
+                                    shader_cache: &mut self.shader_cache,
+                                    environment_dummy: self.environment_dummy.clone(),
+                                    use_parallax_mapping: self
+                                        .quality_settings
+                                        .use_parallax_mapping,
+                                    normal_dummy: self.normal_dummy.clone(),
+                                    white_dummy: self.white_dummy.clone(),
+                                    black_dummy: self.black_dummy.clone(),
+                                    graph,
+                                });
+
+                            if self.quality_settings.antialiasing == AntialiasingMode::Taa {
+                                self.statistics += scene_associated_data
+                                    .gbuffer
+                                    .render_motion_vectors(MotionVectorRenderContext {
+                                        state,
+                                        camera,
+                                        geom_cache: &mut self.geometry_cache,
+                                        batch_storage: &self.batch_storage,
+                                        previous_view_projection: scene_associated_data
+                                            .taa_previous_view_projection,
+                                    });
+                            }
+
+                            self.gpu_timer.switch(state, GpuPass::Other);
+                            scene_associated_data.copy_depth_stencil_to_scene_framebuffer(state);
+
+                            scene_associated_data.hdr_scene_framebuffer.clear(
+                                state,
                                 viewport,
-                                scene,
+                                Some(self.backbuffer_clear_color),
+                                None, // Keep depth, we've just copied valid data in it.
+                                Some(0),
+                            );
+                        }
+                        RenderGraphPassKind::BuiltIn(BuiltInRenderPass::Lighting) => {
+                            let (pass_stats, light_stats) =
+                                self.deferred_light_renderer
+                                    .render(DeferredRendererContext {
+                                        state,
+                                        gpu_timer: &mut self.gpu_timer,
+                                        scene,
+                                        camera,
+                                        gbuffer: &mut scene_associated_data.gbuffer,
+                                        white_dummy: self.white_dummy.clone(),
+                                        ambient_color: scene.ambient_lighting_color,
+                                        settings: &self.quality_settings,
+                                        textures: &mut self.texture_cache,
+                                        geometry_cache: &mut self.geometry_cache,
+                                        batch_storage: &self.batch_storage,
+                                        frame_buffer: &mut scene_associated_data
+                                            .hdr_scene_framebuffer,
+                                        shader_cache: &mut self.shader_cache,
+                                        normal_dummy: self.normal_dummy.clone(),
+                                        black_dummy: self.black_dummy.clone(),
+                                    });
+
+                            self.statistics.lighting += light_stats;
+                            self.statistics.geometry += pass_stats;
+                        }
+                        RenderGraphPassKind::BuiltIn(BuiltInRenderPass::Reflections) => {
+                            // When SSR is enabled, environment reflections are used as a fallback for rays that
+                            // did not hit anything, otherwise they're added directly to the frame.
+                            let ssr_enabled = self.quality_settings.ssr_settings.enabled;
+                            self.gpu_timer.switch(state, GpuPass::Reflections);
+                            self.statistics.geometry += self
+                                .reflection_probe_renderer
+                                .render_environment(EnvironmentRenderContext {
+                                    state,
+                                    graph,
+                                    camera,
+                                    gbuffer: &scene_associated_data.gbuffer,
+                                    probes: &scene_associated_data.reflection_probes,
+                                    texture_cache: &mut self.texture_cache,
+                                    environment_dummy: self.environment_dummy.clone(),
+                                    weighted: !ssr_enabled,
+                                    frame_buffer: if ssr_enabled {
+                                        &mut scene_associated_data
+                                            .environment_reflections_framebuffer
+                                    } else {
+                                        &mut scene_associated_data.hdr_scene_framebuffer
+                                    },
+                                });
+
+                            if ssr_enabled {
+                                let frame_texture = scene_associated_data.hdr_scene_frame_texture();
+                                let environment_reflections =
+                                    scene_associated_data.environment_reflections_texture();
+
+                                self.statistics.geometry +=
+                                    scene_associated_data.ssr_renderer.render(SsrRenderContext {
+                                        state,
+                                        camera,
+                                        gbuffer: &scene_associated_data.gbuffer,
+                                        settings: &self.quality_settings.ssr_settings,
+                                        frame_texture,
+                                        environment_reflections,
+                                        frame_buffer: &mut scene_associated_data
+                                            .hdr_scene_framebuffer,
+                                    });
+                            }
+                        }
+                        RenderGraphPassKind::BuiltIn(BuiltInRenderPass::Forward) => {
+                            let depth = scene_associated_data.gbuffer.depth();
+
+                            self.gpu_timer.switch(state, GpuPass::Forward);
+                            self.statistics +=
+                                self.particle_system_renderer
+                                    .render(ParticleSystemRenderContext {
+                                        state,
+                                        framebuffer: &mut scene_associated_data
+                                            .hdr_scene_framebuffer,
+                                        graph,
+                                        camera,
+                                        white_dummy: self.white_dummy.clone(),
+                                        depth,
+                                        frame_width: frame_size.x,
+                                        frame_height: frame_size.y,
+                                        viewport,
+                                        texture_cache: &mut self.texture_cache,
+                                    });
+
+                            self.statistics += self.sprite_renderer.render(SpriteRenderContext {
+                                state,
+                                framebuffer: &mut scene_associated_data.hdr_scene_framebuffer,
+                                graph,
                                 camera,
-                                scene_handle,
                                 white_dummy: self.white_dummy.clone(),
-                                normal_dummy: self.normal_dummy.clone(),
-                                metallic_dummy: self.metallic_dummy.clone(),
-                                environment_dummy: self.environment_dummy.clone(),
-                                black_dummy: self.black_dummy.clone(),
-                                depth_texture: scene_associated_data.gbuffer.depth(),
-                                normal_texture: scene_associated_data.gbuffer.normal_texture(),
-                                ambient_texture: scene_associated_data.gbuffer.ambient_texture(),
-                                framebuffer: &mut scene_associated_data.hdr_scene_framebuffer,
-                                ui_renderer: &mut self.ui_renderer,
-                            })?;
-                }
-
-                let quad = &self.quad;
-
-                // Prepare glow map.
-                self.statistics.geometry += scene_associated_data.bloom_renderer.render(
-                    state,
-                    quad,
-                    scene_associated_data.hdr_scene_frame_texture(),
-                );
-
-                // Convert high dynamic range frame to low dynamic range (sRGB) with tone mapping and gamma correction.
-                self.statistics.geometry += scene_associated_data.hdr_renderer.render(
-                    state,
-                    scene_associated_data.hdr_scene_frame_texture(),
-                    scene_associated_data.bloom_renderer.result(),
-                    &mut scene_associated_data.ldr_scene_framebuffer,
-                    viewport,
-                    quad,
-                    dt,
-                    camera.exposure(),
-                    camera.color_grading_lut_ref(),
-                    camera.color_grading_enabled(),
-                    &mut self.texture_cache,
-                );
-
-                // Apply FXAA if needed.
-                if self.quality_settings.fxaa {
-                    self.statistics.geometry += self.fxaa_renderer.render(
-                        state,
-                        viewport,
-                        scene_associated_data.ldr_scene_frame_texture(),
-                        &mut scene_associated_data.ldr_temp_framebuffer,
-                    );
-
-                    let quad = &self.quad;
-                    let temp_frame_texture = scene_associated_data.ldr_temp_frame_texture();
-                    self.statistics.geometry += blit_pixels(
-                        state,
-                        &mut scene_associated_data.ldr_scene_framebuffer,
-                        temp_frame_texture,
-                        &self.flat_shader,
-                        viewport,
-                        quad,
-                    );
-                }
-
-                // Render debug geometry in the LDR frame buffer.
-                self.statistics += self.debug_renderer.render(
-                    state,
-                    viewport,
-                    &mut scene_associated_data.ldr_scene_framebuffer,
-                    &scene.drawing_context,
-                    camera,
-                );
+                                viewport,
+                                textures: &mut self.texture_cache,
+                            });
 
-                for render_pass in self.scene_render_passes.iter() {
-                    self.statistics +=
-                        render_pass
-                            .borrow_mut()
-                            .on_ldr_render(SceneRenderPassContext {
-                                pipeline_state: state,
-                                texture_cache: &mut self.texture_cache,
-                                geometry_cache: &mut self.geometry_cache,
-                                quality_settings: &self.quality_settings,
-                                batch_storage: &self.batch_storage,
+                            self.statistics += self.renderer2d.render(
+                                state,
+                                camera,
+                                &mut scene_associated_data.hdr_scene_framebuffer,
+                                viewport,
+                                graph,
+                                &mut self.texture_cache,
+                                self.white_dummy.clone(),
+                                scene.ambient_lighting_color,
+                            )?;
+
+                            self.statistics +=
+                                self.forward_renderer.render(ForwardRenderContext {
+                                    state,
+                                    camera,
+                                    graph,
+                                    ambient_color: scene.ambient_lighting_color,
+                                    geom_cache: &mut self.geometry_cache,
+                                    texture_cache: &mut self.texture_cache,
+                                    shader_cache: &mut self.shader_cache,
+                                    batch_storage: &self.batch_storage,
+                                    framebuffer: &mut scene_associated_data.hdr_scene_framebuffer,
+                                    viewport,
+                                    quality_settings: &self.quality_settings,
+                                    white_dummy: self.white_dummy.clone(),
+                                    normal_dummy: self.normal_dummy.clone(),
+                                    black_dummy: self.black_dummy.clone(),
+                                })?;
+                        }
+                        RenderGraphPassKind::BuiltIn(BuiltInRenderPass::CustomHdr) => {
+                            for (index, render_pass) in self.scene_render_passes.iter().enumerate()
+                            {
+                                let timer_pass = self
+                                    .gpu_timer
+                                    .custom_pass(&format!("Scene Render Pass {} (HDR)", index));
+                                self.gpu_timer.switch(state, timer_pass);
+                                self.statistics += render_pass.borrow_mut().on_hdr_render(
+                                    SceneRenderPassContext {
+                                        pipeline_state: state,
+                                        texture_cache: &mut self.texture_cache,
+                                        geometry_cache: &mut self.geometry_cache,
+                                        quality_settings: &self.quality_settings,
+                                        batch_storage: &self.batch_storage,
+                                        viewport,
+                                        scene,
+                                        camera,
+                                        scene_handle,
+                                        white_dummy: self.white_dummy.clone(),
+                                        normal_dummy: self.normal_dummy.clone(),
+                                        metallic_dummy: self.metallic_dummy.clone(),
+                                        environment_dummy: self.environment_dummy.clone(),
+                                        black_dummy: self.black_dummy.clone(),
+                                        depth_texture: scene_associated_data.gbuffer.depth(),
+                                        normal_texture: scene_associated_data
+                                            .gbuffer
+                                            .normal_texture(),
+                                        ambient_texture: scene_associated_data
+                                            .gbuffer
+                                            .ambient_texture(),
+                                        framebuffer: &mut scene_associated_data
+                                            .hdr_scene_framebuffer,
+                                        ui_renderer: &mut self.ui_renderer,
+                                    },
+                                )?;
+                            }
+                        }
+                        RenderGraphPassKind::BuiltIn(BuiltInRenderPass::Bloom) => {
+                            let quad = &self.quad;
+
+                            // Prepare glow map.
+                            self.gpu_timer.switch(state, GpuPass::Bloom);
+                            self.statistics.geometry +=
+                                scene_associated_data.bloom_renderer.render(
+                                    state,
+                                    quad,
+                                    scene_associated_data.hdr_scene_frame_texture(),
+                                );
+                        }
+                        RenderGraphPassKind::BuiltIn(BuiltInRenderPass::ToneMapping) => {
+                            let quad = &self.quad;
+
+                            // Convert high dynamic range frame to low dynamic range (sRGB) with tone mapping and gamma correction.
+                            self.gpu_timer.switch(state, GpuPass::Hdr);
+                            self.statistics.geometry += scene_associated_data.hdr_renderer.render(
+                                state,
+                                scene_associated_data.hdr_scene_frame_texture(),
+                                if bloom_enabled {
+                                    scene_associated_data.bloom_renderer.result()
+                                } else {
+                                    self.black_dummy.clone()
+                                },
+                                &mut scene_associated_data.ldr_scene_framebuffer,
+                                viewport,
+                                quad,
+                                dt,
+                                camera.exposure(),
+                                camera.color_grading_lut_ref(),
+                                camera.color_grading_enabled(),
+                                &mut self.texture_cache,
+                            );
+                        }
+                        RenderGraphPassKind::BuiltIn(BuiltInRenderPass::AntiAliasing) => {
+                            // Apply anti-aliasing if needed.
+                            self.gpu_timer.switch(state, GpuPass::AntiAliasing);
+                            match self.quality_settings.antialiasing {
+                                AntialiasingMode::None => {
+                                    scene_associated_data.taa_previous_view_projection = None;
+                                    scene_associated_data.gbuffer.clear_motion_history();
+                                }
+                                AntialiasingMode::Fxaa => {
+                                    scene_associated_data.taa_previous_view_projection = None;
+                                    scene_associated_data.gbuffer.clear_motion_history();
+
+                                    self.statistics.geometry += self.fxaa_renderer.render(
+                                        state,
+                                        viewport,
+                                        scene_associated_data.ldr_scene_frame_texture(),
+                                        &mut scene_associated_data.ldr_temp_framebuffer,
+                                    );
+
+                                    let quad = &self.quad;
+                                    let temp_frame_texture =
+                                        scene_associated_data.ldr_temp_frame_texture();
+                                    self.statistics.geometry += blit_pixels(
+                                        state,
+                                        &mut scene_associated_data.ldr_scene_framebuffer,
+                                        temp_frame_texture,
+                                        &self.flat_shader,
+                                        viewport,
+                                        quad,
+                                    );
+                                }
+                                AntialiasingMode::Taa => {
+                                    let quad = &self.quad;
+                                    let current_view_projection =
+                                        camera.unjittered_view_projection_matrix();
+
+                                    if let Some(previous_view_projection) =
+                                        scene_associated_data.taa_previous_view_projection
+                                    {
+                                        let frame_texture =
+                                            scene_associated_data.ldr_scene_frame_texture();
+                                        let history_texture =
+                                            scene_associated_data.taa_history_texture();
+                                        self.statistics.geometry +=
+                                            self.taa_renderer.render(TaaRenderContext {
+                                                state,
+                                                viewport,
+                                                camera,
+                                                previous_view_projection,
+                                                frame_texture,
+                                                history_texture,
+                                                depth_texture: scene_associated_data
+                                                    .gbuffer
+                                                    .depth(),
+                                                velocity_texture: scene_associated_data
+                                                    .gbuffer
+                                                    .velocity_texture(),
+                                                frame_buffer: &mut scene_associated_data
+                                                    .ldr_temp_framebuffer,
+                                            });
+
+                                        let temp_frame_texture =
+                                            scene_associated_data.ldr_temp_frame_texture();
+                                        self.statistics.geometry += blit_pixels(
+                                            state,
+                                            &mut scene_associated_data.ldr_scene_framebuffer,
+                                            temp_frame_texture,
+                                            &self.flat_shader,
+                                            viewport,
+                                            quad,
+                                        );
+                                    }
+
+                                    // Resolved frame becomes history for the next frame.
+                                    let frame_texture =
+                                        scene_associated_data.ldr_scene_frame_texture();
+                                    self.statistics.geometry += blit_pixels(
+                                        state,
+                                        &mut scene_associated_data.taa_history_framebuffer,
+                                        frame_texture,
+                                        &self.flat_shader,
+                                        viewport,
+                                        quad,
+                                    );
+
+                                    scene_associated_data.taa_previous_view_projection =
+                                        Some(current_view_projection);
+                                }
+                            }
+                        }
+                        RenderGraphPassKind::BuiltIn(BuiltInRenderPass::Debug) => {
+                            // Render debug geometry in the LDR frame buffer.
+                            self.gpu_timer.switch(state, GpuPass::Other);
+                            self.statistics += self.debug_renderer.render(
+                                state,
                                 viewport,
-                                scene,
+                                &mut scene_associated_data.ldr_scene_framebuffer,
+                                &scene.drawing_context,
                                 camera,
-                                scene_handle,
-                                white_dummy: self.white_dummy.clone(),
-                                normal_dummy: self.normal_dummy.clone(),
-                                metallic_dummy: self.metallic_dummy.clone(),
-                                environment_dummy: self.environment_dummy.clone(),
-                                black_dummy: self.black_dummy.clone(),
-                                depth_texture: scene_associated_data.gbuffer.depth(),
-                                normal_texture: scene_associated_data.gbuffer.normal_texture(),
-                                ambient_texture: scene_associated_data.gbuffer.ambient_texture(),
-                                framebuffer: &mut scene_associated_data.ldr_scene_framebuffer,
-                                ui_renderer: &mut self.ui_renderer,
-                            })?;
+                            );
+                        }
+                        RenderGraphPassKind::BuiltIn(BuiltInRenderPass::CustomLdr) => {
+                            for (index, render_pass) in self.scene_render_passes.iter().enumerate()
+                            {
+                                let timer_pass = self
+                                    .gpu_timer
+                                    .custom_pass(&format!("Scene Render Pass {} (LDR)", index));
+                                self.gpu_timer.switch(state, timer_pass);
+                                self.statistics += render_pass.borrow_mut().on_ldr_render(
+                                    SceneRenderPassContext {
+                                        pipeline_state: state,
+                                        texture_cache: &mut self.texture_cache,
+                                        geometry_cache: &mut self.geometry_cache,
+                                        quality_settings: &self.quality_settings,
+                                        batch_storage: &self.batch_storage,
+                                        viewport,
+                                        scene,
+                                        camera,
+                                        scene_handle,
+                                        white_dummy: self.white_dummy.clone(),
+                                        normal_dummy: self.normal_dummy.clone(),
+                                        metallic_dummy: self.metallic_dummy.clone(),
+                                        environment_dummy: self.environment_dummy.clone(),
+                                        black_dummy: self.black_dummy.clone(),
+                                        depth_texture: scene_associated_data.gbuffer.depth(),
+                                        normal_texture: scene_associated_data
+                                            .gbuffer
+                                            .normal_texture(),
+                                        ambient_texture: scene_associated_data
+                                            .gbuffer
+                                            .ambient_texture(),
+                                        framebuffer: &mut scene_associated_data
+                                            .ldr_scene_framebuffer,
+                                        ui_renderer: &mut self.ui_renderer,
+                                    },
+                                )?;
+                            }
+                        }
+                        RenderGraphPassKind::Custom(pass) => {
+                            let timer_pass = self.gpu_timer.custom_pass(name);
+                            self.gpu_timer.switch(state, timer_pass);
+                            self.statistics +=
+                                pass.borrow_mut().execute(RenderGraphPassContext {
+                                    pipeline_state: state,
+                                    texture_cache: &mut self.texture_cache,
+                                    geometry_cache: &mut self.geometry_cache,
+                                    batch_storage: &self.batch_storage,
+                                    quality_settings: &self.quality_settings,
+                                    resources: RenderGraphResources {
+                                        gbuffer: &scene_associated_data.gbuffer,
+                                        hdr_framebuffer: &mut scene_associated_data
+                                            .hdr_scene_framebuffer,
+                                        ldr_framebuffer: &mut scene_associated_data
+                                            .ldr_scene_framebuffer,
+                                        bloom: scene_associated_data.bloom_renderer.result(),
+                                        transient: &mut scene_associated_data
+                                            .transient_framebuffers,
+                                    },
+                                    scene,
+                                    camera,
+                                    viewport,
+                                    scene_handle,
+                                    white_dummy: self.white_dummy.clone(),
+                                    normal_dummy: self.normal_dummy.clone(),
+                                    black_dummy: self.black_dummy.clone(),
+                                })?;
+                        }
+                    }
                 }
             }
 
             // Optionally render everything into back buffer.
             if scene.render_target.is_none() {
+                self.gpu_timer.switch(state, GpuPass::Other);
                 let quad = &self.quad;
                 self.statistics.geometry += blit_pixels(
                     state,
@@ -1730,6 +2485,7 @@ impl Renderer {
         }
 
         // Render UI on top of everything without gamma correction.
+        self.gpu_timer.switch(&mut self.state, GpuPass::Ui);
         self.statistics += self.ui_renderer.render(UiRenderContext {
             state: &mut self.state,
             viewport: window_viewport,
@@ -1741,9 +2497,72 @@ impl Renderer {
             texture_cache: &mut self.texture_cache,
         })?;
 
+        self.gpu_timer.end_frame(&mut self.state);
+        self.statistics.gpu_time = self.gpu_timer.timings();
+
         Ok(())
     }
 
+    /// Renders the given scene into an offscreen frame buffer of the current frame size (see
+    /// [`Self::get_frame_size`]) and reads the result back into an image. The scene is rendered
+    /// from its enabled cameras, exactly as it would be rendered on screen, except that UI is not
+    /// drawn. The scene does not need to be added to a scene container and it is rendered even if
+    /// it is disabled. This method does not require a window, so it can be used together with
+    /// [`crate::engine::headless::HeadlessRenderer`] to make golden-image tests of scenes.
+    ///
+    /// # Important notes
+    ///
+    /// Scene must not have a render target (see [`Scene::render_target`]), otherwise it will be
+    /// rendered into the render target and the resulting image will be empty. Make sure to call
+    /// [`Scene::update`] with the same frame size before rendering, so cameras and transforms are
+    /// up-to-date, and to wait until all the resources used by the scene are loaded.
+    ///
+    /// Rendering data associated with the scene (such as temporal anti-aliasing history) is shared
+    /// between all scenes rendered with this method and it is discarded on the next frame rendered
+    /// by the engine.
+    pub fn render_scene_to_image(
+        &mut self,
+        scene: &Scene,
+    ) -> Result<image::RgbaImage, FrameworkError> {
+        let (width, height) = (self.frame_size.0.max(1), self.frame_size.1.max(1));
+
+        let mut frame_buffer =
+            make_ui_frame_buffer(Vector2::new(width as f32, height as f32), &mut self.state)?;
+
+        // Temporarily replace back buffer with the offscreen frame buffer, so the rest of the
+        // pipeline does not need to know anything about the capture.
+        std::mem::swap(&mut self.backbuffer, &mut frame_buffer);
+        let result = self.render_frame(
+            std::iter::once((Handle::NONE, scene)),
+            &DrawingContext::new(),
+        );
+        std::mem::swap(&mut self.backbuffer, &mut frame_buffer);
+        result?;
+
+        self.statistics.end_frame();
+        self.state.check_error();
+        self.statistics.finalize();
+        self.statistics.pipeline = self.state.pipeline_statistics();
+
+        let bytes = frame_buffer.read_rgba8_pixels(
+            &mut self.state,
+            Rect::new(0, 0, width as i32, height as i32),
+        );
+
+        // OpenGL stores rows from bottom to top, images are stored from top to bottom.
+        let row_size = width as usize * 4;
+        let flipped = bytes
+            .chunks_exact(row_size)
+            .rev()
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>();
+
+        image::RgbaImage::from_raw(width, height, flipped).ok_or_else(|| {
+            FrameworkError::Custom("Unable to create image from frame pixels!".to_owned())
+        })
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     pub(crate) fn render_and_swap_buffers(
         &mut self,
@@ -1751,7 +2570,14 @@ impl Renderer {
         drawing_context: &DrawingContext,
         context: &glutin::WindowedContext<glutin::PossiblyCurrent>,
     ) -> Result<(), FrameworkError> {
-        self.render_frame(scenes, drawing_context)?;
+        // Make sure to drop associated data for destroyed scenes.
+        self.scene_data_map
+            .retain(|h, _| scenes.is_valid_handle(*h));
+
+        self.render_frame(
+            scenes.pair_iter().filter(|(_, s)| s.enabled),
+            drawing_context,
+        )?;
         self.statistics.end_frame();
         context.swap_buffers()?;
         self.state.check_error();
@@ -1766,7 +2592,14 @@ impl Renderer {
         scenes: &SceneContainer,
         drawing_context: &DrawingContext,
     ) -> Result<(), FrameworkError> {
-        self.render_frame(scenes, drawing_context)?;
+        // Make sure to drop associated data for destroyed scenes.
+        self.scene_data_map
+            .retain(|h, _| scenes.is_valid_handle(*h));
+
+        self.render_frame(
+            scenes.pair_iter().filter(|(_, s)| s.enabled),
+            drawing_context,
+        )?;
         self.statistics.end_frame();
         self.state.check_error();
         self.statistics.finalize();
@@ -1774,3 +2607,31 @@ impl Renderer {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::renderer::{AntialiasingMode, QualitySettings};
+
+    fn read_antialiasing(field: &str) -> AntialiasingMode {
+        let mut settings = ron::to_string(&QualitySettings::default()).unwrap();
+        let (start, end) = {
+            let start = settings.find("antialiasing:").unwrap();
+            (start, start + settings[start..].find(',').unwrap())
+        };
+        settings.replace_range(start..end, field);
+        ron::from_str::<QualitySettings>(&settings)
+            .unwrap()
+            .antialiasing
+    }
+
+    #[test]
+    fn test_legacy_fxaa_flag() {
+        assert_eq!(read_antialiasing("fxaa:false"), AntialiasingMode::None);
+        assert_eq!(read_antialiasing("fxaa:true"), AntialiasingMode::Fxaa);
+        assert_eq!(read_antialiasing("antialiasing:Taa"), AntialiasingMode::Taa);
+        assert_eq!(
+            read_antialiasing("antialiasing:None"),
+            AntialiasingMode::None
+        );
+    }
+}
@@ -0,0 +1,670 @@
+//! Reflection probes renderer. It renders environment of reflection probes into cube maps and
+//! calculates environment reflections for each pixel of a frame using the probes that affect it.
+
+use crate::{
+    core::{
+        algebra::{Matrix3, Matrix4, UnitQuaternion, Vector2, Vector3},
+        color::Color,
+        math::{aabb::AxisAlignedBoundingBox, frustum::Frustum, Matrix4Ext, Rect},
+        pool::Handle,
+        scope_profile,
+        sstorage::ImmutableString,
+    },
+    renderer::{
+        batch::BatchStorage,
+        blit_pixels,
+        cache::{geometry::GeometryCache, shader::ShaderCache, texture::TextureCache},
+        flat_shader::FlatShader,
+        framework::{
+            error::FrameworkError,
+            framebuffer::{
+                Attachment, AttachmentKind, BlendParameters, DrawParameters, FrameBuffer,
+            },
+            geometry_buffer::{GeometryBuffer, GeometryBufferKind},
+            gpu_program::{GpuProgram, UniformLocation},
+            gpu_texture::{
+                Coordinate, CubeMapFace, GpuTexture, GpuTextureKind, MagnificationFilter,
+                MinificationFilter, PixelKind, WrapMode,
+            },
+            state::{BlendFactor, BlendFunc, PipelineState},
+        },
+        gbuffer::{GBuffer, GBufferRenderContext},
+        gpu_timer::GpuTimer,
+        light::{DeferredLightRenderer, DeferredRendererContext},
+        make_viewport_matrix, AssociatedSceneData, QualitySettings, RenderPassStatistics,
+    },
+    scene::{
+        base::BaseBuilder,
+        camera::{Camera, CameraBuilder, PerspectiveProjection, Projection},
+        graph::Graph,
+        mesh::surface::SurfaceData,
+        node::{Node, NodeTrait},
+        reflection_probe::{ReflectionProbe, ReflectionProbeUpdateMode},
+        Scene,
+    },
+};
+use fxhash::FxHashMap;
+use std::{cell::RefCell, cmp::Ordering, collections::hash_map::Entry, rc::Rc};
+
+// Keep in sync with shader define.
+const MAX_PROBES: usize = 4;
+
+struct ProbeFace {
+    face: CubeMapFace,
+    look: Vector3<f32>,
+    up: Vector3<f32>,
+}
+
+const FACES: [ProbeFace; 6] = [
+    ProbeFace {
+        face: CubeMapFace::PositiveX,
+        look: Vector3::new(1.0, 0.0, 0.0),
+        up: Vector3::new(0.0, -1.0, 0.0),
+    },
+    ProbeFace {
+        face: CubeMapFace::NegativeX,
+        look: Vector3::new(-1.0, 0.0, 0.0),
+        up: Vector3::new(0.0, -1.0, 0.0),
+    },
+    ProbeFace {
+        face: CubeMapFace::PositiveY,
+        look: Vector3::new(0.0, 1.0, 0.0),
+        up: Vector3::new(0.0, 0.0, 1.0),
+    },
+    ProbeFace {
+        face: CubeMapFace::NegativeY,
+        look: Vector3::new(0.0, -1.0, 0.0),
+        up: Vector3::new(0.0, 0.0, -1.0),
+    },
+    ProbeFace {
+        face: CubeMapFace::PositiveZ,
+        look: Vector3::new(0.0, 0.0, 1.0),
+        up: Vector3::new(0.0, -1.0, 0.0),
+    },
+    ProbeFace {
+        face: CubeMapFace::NegativeZ,
+        look: Vector3::new(0.0, 0.0, -1.0),
+        up: Vector3::new(0.0, -1.0, 0.0),
+    },
+];
+
+/// GPU data of a reflection probe that renders its environment at run-time.
+pub struct ReflectionProbeData {
+    framebuffer: FrameBuffer,
+    resolution: usize,
+    rendered: bool,
+}
+
+impl ReflectionProbeData {
+    fn new(state: &mut PipelineState, resolution: usize) -> Result<Self, FrameworkError> {
+        let mut texture = GpuTexture::new(
+            state,
+            GpuTextureKind::Cube {
+                width: resolution,
+                height: resolution,
+            },
+            PixelKind::RGBA16F,
+            MinificationFilter::Linear,
+            MagnificationFilter::Linear,
+            1,
+            None,
+        )?;
+        texture
+            .bind_mut(state, 0)
+            .set_wrap(Coordinate::S, WrapMode::ClampToEdge)
+            .set_wrap(Coordinate::T, WrapMode::ClampToEdge)
+            .set_wrap(Coordinate::R, WrapMode::ClampToEdge);
+
+        Ok(Self {
+            framebuffer: FrameBuffer::new(
+                state,
+                None,
+                vec![Attachment {
+                    kind: AttachmentKind::Color,
+                    texture: Rc::new(RefCell::new(texture)),
+                }],
+            )?,
+            resolution,
+            rendered: false,
+        })
+    }
+
+    /// Returns environment cube map of the probe.
+    pub fn environment(&self) -> Rc<RefCell<GpuTexture>> {
+        self.framebuffer.color_attachments()[0].texture.clone()
+    }
+}
+
+struct EnvironmentShader {
+    program: GpuProgram,
+    wvp_matrix: UniformLocation,
+    depth_texture: UniformLocation,
+    diffuse_texture: UniformLocation,
+    normal_texture: UniformLocation,
+    material_texture: UniformLocation,
+    environment_map: UniformLocation,
+    probes: [UniformLocation; MAX_PROBES],
+    probe_transforms: UniformLocation,
+    probe_inv_transforms: UniformLocation,
+    probe_half_extents: UniformLocation,
+    probe_blend_distances: UniformLocation,
+    probe_count: UniformLocation,
+    inv_view_projection: UniformLocation,
+    camera_position: UniformLocation,
+    weighted: UniformLocation,
+}
+
+impl EnvironmentShader {
+    fn new(state: &mut PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("shaders/environment_reflections_fs.glsl");
+        let vertex_source = include_str!("shaders/flat_vs.glsl");
+        let program = GpuProgram::from_source(
+            state,
+            "EnvironmentReflectionsShader",
+            vertex_source,
+            fragment_source,
+        )?;
+        Ok(Self {
+            wvp_matrix: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            depth_texture: program
+                .uniform_location(state, &ImmutableString::new("depthTexture"))?,
+            diffuse_texture: program
+                .uniform_location(state, &ImmutableString::new("diffuseTexture"))?,
+            normal_texture: program
+                .uniform_location(state, &ImmutableString::new("normalTexture"))?,
+            material_texture: program
+                .uniform_location(state, &ImmutableString::new("materialTexture"))?,
+            environment_map: program
+                .uniform_location(state, &ImmutableString::new("environmentMap"))?,
+            probes: [
+                program.uniform_location(state, &ImmutableString::new("probe0"))?,
+                program.uniform_location(state, &ImmutableString::new("probe1"))?,
+                program.uniform_location(state, &ImmutableString::new("probe2"))?,
+                program.uniform_location(state, &ImmutableString::new("probe3"))?,
+            ],
+            probe_transforms: program
+                .uniform_location(state, &ImmutableString::new("probeTransforms"))?,
+            probe_inv_transforms: program
+                .uniform_location(state, &ImmutableString::new("probeInvTransforms"))?,
+            probe_half_extents: program
+                .uniform_location(state, &ImmutableString::new("probeHalfExtents"))?,
+            probe_blend_distances: program
+                .uniform_location(state, &ImmutableString::new("probeBlendDistances"))?,
+            probe_count: program.uniform_location(state, &ImmutableString::new("probeCount"))?,
+            inv_view_projection: program
+                .uniform_location(state, &ImmutableString::new("invViewProjection"))?,
+            camera_position: program
+                .uniform_location(state, &ImmutableString::new("cameraPosition"))?,
+            weighted: program.uniform_location(state, &ImmutableString::new("weighted"))?,
+            program,
+        })
+    }
+}
+
+/// A set of renderer parts that are needed to render environment of probes.
+pub(crate) struct ProbeCaptureContext<'a> {
+    pub state: &'a mut PipelineState,
+    pub gpu_timer: &'a mut GpuTimer,
+    pub scene: &'a Scene,
+    pub deferred_light_renderer: &'a mut DeferredLightRenderer,
+    pub geometry_cache: &'a mut GeometryCache,
+    pub texture_cache: &'a mut TextureCache,
+    pub shader_cache: &'a mut ShaderCache,
+    pub batch_storage: &'a BatchStorage,
+    pub quality_settings: &'a QualitySettings,
+    pub white_dummy: Rc<RefCell<GpuTexture>>,
+    pub normal_dummy: Rc<RefCell<GpuTexture>>,
+    pub black_dummy: Rc<RefCell<GpuTexture>>,
+    pub environment_dummy: Rc<RefCell<GpuTexture>>,
+}
+
+pub(crate) struct EnvironmentRenderContext<'a> {
+    pub state: &'a mut PipelineState,
+    pub graph: &'a Graph,
+    pub camera: &'a Camera,
+    pub gbuffer: &'a GBuffer,
+    pub probes: &'a FxHashMap<Handle<Node>, ReflectionProbeData>,
+    pub texture_cache: &'a mut TextureCache,
+    pub environment_dummy: Rc<RefCell<GpuTexture>>,
+    /// If `true`, reflections will be weighted by material properties and added to the frame
+    /// buffer. Otherwise raw environment color will be written to the frame buffer.
+    pub weighted: bool,
+    pub frame_buffer: &'a mut FrameBuffer,
+}
+
+pub struct ReflectionProbeRenderer {
+    shader: EnvironmentShader,
+    flat_shader: FlatShader,
+    quad: GeometryBuffer,
+    // Intermediate buffers to render environment of probes, one per each resolution.
+    capture_buffers: FxHashMap<usize, AssociatedSceneData>,
+}
+
+/// Returns a cube map that can be used as environment map of the camera.
+pub(crate) fn camera_environment(
+    state: &mut PipelineState,
+    camera: &Camera,
+    texture_cache: &mut TextureCache,
+) -> Option<Rc<RefCell<GpuTexture>>> {
+    camera
+        .environment_ref()
+        .and_then(|environment| texture_cache.get(state, environment))
+        .filter(|texture| matches!(texture.borrow().kind(), GpuTextureKind::Cube { .. }))
+}
+
+fn probe_environment(
+    state: &mut PipelineState,
+    handle: Handle<Node>,
+    probe: &ReflectionProbe,
+    probes: &FxHashMap<Handle<Node>, ReflectionProbeData>,
+    texture_cache: &mut TextureCache,
+) -> Option<Rc<RefCell<GpuTexture>>> {
+    if let Some(baked_environment) = probe.baked_environment() {
+        texture_cache
+            .get(state, baked_environment)
+            .filter(|texture| matches!(texture.borrow().kind(), GpuTextureKind::Cube { .. }))
+    } else {
+        probes
+            .get(&handle)
+            .filter(|data| data.rendered)
+            .map(|data| data.environment())
+    }
+}
+
+/// Returns distance from the point to the closest point of the box, it is zero if the box contains
+/// the point.
+fn distance_to_aabb(aabb: &AxisAlignedBoundingBox, point: Vector3<f32>) -> f32 {
+    let closest = Vector3::new(
+        point.x.clamp(aabb.min.x, aabb.max.x),
+        point.y.clamp(aabb.min.y, aabb.max.y),
+        point.z.clamp(aabb.min.z, aabb.max.z),
+    );
+    (closest - point).norm()
+}
+
+/// Renders every face of environment of the given probe, `on_face` is called after each face
+/// is rendered with the HDR frame buffer that contains the face.
+fn capture<F>(
+    capture_buffers: &mut FxHashMap<usize, AssociatedSceneData>,
+    ctx: &mut ProbeCaptureContext,
+    probe: &ReflectionProbe,
+    mut on_face: F,
+) -> Result<RenderPassStatistics, FrameworkError>
+where
+    F: FnMut(&mut PipelineState, &mut FrameBuffer, usize) -> RenderPassStatistics,
+{
+    scope_profile!();
+
+    let mut statistics = RenderPassStatistics::default();
+
+    let resolution = probe.resolution();
+    let capture = match capture_buffers.entry(resolution) {
+        Entry::Occupied(entry) => entry.into_mut(),
+        Entry::Vacant(entry) => {
+            entry.insert(AssociatedSceneData::new(ctx.state, resolution, resolution)?)
+        }
+    };
+
+    // Probes use the sky of the first active camera of the scene.
+    let skybox = ctx
+        .scene
+        .graph
+        .linear_iter()
+        .filter_map(|node| node.cast::<Camera>())
+        .find(|camera| camera.is_enabled())
+        .and_then(|camera| camera.skybox_ref().cloned());
+
+    // There is no need to render ambient occlusion for reflections.
+    let settings = QualitySettings {
+        use_ssao: false,
+        ..*ctx.quality_settings
+    };
+
+    let viewport = Rect::new(0, 0, resolution as i32, resolution as i32);
+
+    for (index, face) in FACES.iter().enumerate() {
+        let mut camera_builder = CameraBuilder::new(BaseBuilder::new()).with_projection(
+            Projection::Perspective(PerspectiveProjection {
+                fov: std::f32::consts::FRAC_PI_2,
+                z_near: probe.z_near(),
+                z_far: probe.z_far(),
+            }),
+        );
+        if let Some(skybox) = skybox.clone() {
+            camera_builder = camera_builder.with_skybox(skybox);
+        }
+        let mut camera = camera_builder.build_camera();
+        camera.global_transform.set(
+            Matrix4::new_translation(&probe.global_position())
+                * UnitQuaternion::face_towards(&face.look, &face.up).to_homogeneous(),
+        );
+        camera.calculate_matrices(Vector2::new(resolution as f32, resolution as f32));
+        camera.visibility_cache = probe.visibility_cache.clone();
+
+        statistics += capture.gbuffer.fill(GBufferRenderContext {
+            state: ctx.state,
+            camera: &camera,
+            geom_cache: ctx.geometry_cache,
+            batch_storage: ctx.batch_storage,
+            texture_cache: ctx.texture_cache,
+            shader_cache: ctx.shader_cache,
+            environment_dummy: ctx.environment_dummy.clone(),
+            use_parallax_mapping: settings.use_parallax_mapping,
+            normal_dummy: ctx.normal_dummy.clone(),
+            white_dummy: ctx.white_dummy.clone(),
+            black_dummy: ctx.black_dummy.clone(),
+            graph: &ctx.scene.graph,
+        });
+
+        capture.copy_depth_stencil_to_scene_framebuffer(ctx.state);
+
+        capture
+            .hdr_scene_framebuffer
+            .clear(ctx.state, viewport, Some(Color::BLACK), None, Some(0));
+
+        // Lighting of the capture is a part of reflections pass, it must not switch the timer.
+        ctx.gpu_timer.suppress();
+        let (pass_stats, _) = ctx.deferred_light_renderer.render(DeferredRendererContext {
+            state: ctx.state,
+            gpu_timer: ctx.gpu_timer,
+            scene: ctx.scene,
+            camera: &camera,
+            gbuffer: &mut capture.gbuffer,
+            ambient_color: ctx.scene.ambient_lighting_color,
+            settings: &settings,
+            textures: ctx.texture_cache,
+            geometry_cache: ctx.geometry_cache,
+            batch_storage: ctx.batch_storage,
+            frame_buffer: &mut capture.hdr_scene_framebuffer,
+            shader_cache: ctx.shader_cache,
+            normal_dummy: ctx.normal_dummy.clone(),
+            white_dummy: ctx.white_dummy.clone(),
+            black_dummy: ctx.black_dummy.clone(),
+        });
+        ctx.gpu_timer.resume();
+        statistics += pass_stats;
+
+        statistics += on_face(ctx.state, &mut capture.hdr_scene_framebuffer, index);
+    }
+
+    Ok(statistics)
+}
+
+impl ReflectionProbeRenderer {
+    pub fn new(state: &mut PipelineState) -> Result<Self, FrameworkError> {
+        Ok(Self {
+            shader: EnvironmentShader::new(state)?,
+            flat_shader: FlatShader::new(state)?,
+            quad: GeometryBuffer::from_surface_data(
+                &SurfaceData::make_unit_xy_quad(),
+                GeometryBufferKind::StaticDraw,
+                state,
+            ),
+            capture_buffers: Default::default(),
+        })
+    }
+
+    /// Renders environment of every probe of the scene that needs it.
+    pub(crate) fn update_probes(
+        &mut self,
+        mut ctx: ProbeCaptureContext,
+        probes: &mut FxHashMap<Handle<Node>, ReflectionProbeData>,
+    ) -> Result<RenderPassStatistics, FrameworkError> {
+        let Self {
+            flat_shader,
+            quad,
+            capture_buffers,
+            ..
+        } = self;
+
+        let mut statistics = RenderPassStatistics::default();
+
+        let graph = &ctx.scene.graph;
+
+        // Make sure to drop data of deleted probes.
+        probes.retain(|handle, _| {
+            graph
+                .try_get(*handle)
+                .map_or(false, |node| node.cast::<ReflectionProbe>().is_some())
+        });
+
+        for (handle, node) in graph.pair_iter() {
+            let probe = if let Some(probe) = node.cast::<ReflectionProbe>() {
+                probe
+            } else {
+                continue;
+            };
+
+            if probe.baked_environment().is_some() || !probe.global_visibility() {
+                continue;
+            }
+
+            let resolution = probe.resolution();
+            let data = match probes.entry(handle) {
+                Entry::Occupied(entry) => {
+                    let data = entry.into_mut();
+                    if data.resolution != resolution {
+                        *data = ReflectionProbeData::new(ctx.state, resolution)?;
+                    }
+                    data
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(ReflectionProbeData::new(ctx.state, resolution)?)
+                }
+            };
+
+            let update_requested = probe.take_update_request();
+            if data.rendered
+                && !update_requested
+                && probe.update_mode() == ReflectionProbeUpdateMode::Once
+            {
+                continue;
+            }
+
+            let viewport = Rect::new(0, 0, resolution as i32, resolution as i32);
+            statistics += capture(
+                capture_buffers,
+                &mut ctx,
+                probe,
+                |state, frame_buffer, index| {
+                    let texture = frame_buffer.color_attachments()[0].texture.clone();
+                    data.framebuffer
+                        .set_cubemap_face(state, 0, FACES[index].face);
+                    let mut statistics = RenderPassStatistics::default();
+                    statistics += blit_pixels(
+                        state,
+                        &mut data.framebuffer,
+                        texture,
+                        flat_shader,
+                        viewport,
+                        quad,
+                    );
+                    statistics
+                },
+            )?;
+            data.rendered = true;
+        }
+
+        Ok(statistics)
+    }
+
+    /// Renders environment of the given probe and returns raw bytes of RGBA32F cube map.
+    pub(crate) fn bake(
+        &mut self,
+        mut ctx: ProbeCaptureContext,
+        probe: &ReflectionProbe,
+    ) -> Result<Vec<u8>, FrameworkError> {
+        let resolution = probe.resolution() as i32;
+        let mut faces = vec![Vec::new(); FACES.len()];
+        capture(
+            &mut self.capture_buffers,
+            &mut ctx,
+            probe,
+            |state, frame_buffer, index| {
+                faces[index] = frame_buffer
+                    .read_rgba32f_pixels(state, Rect::new(0, 0, resolution, resolution));
+                Default::default()
+            },
+        )?;
+        Ok(faces.concat())
+    }
+
+    /// Calculates environment reflections for every pixel of the frame.
+    pub(crate) fn render_environment(
+        &self,
+        args: EnvironmentRenderContext,
+    ) -> RenderPassStatistics {
+        scope_profile!();
+
+        let EnvironmentRenderContext {
+            state,
+            graph,
+            camera,
+            gbuffer,
+            probes,
+            texture_cache,
+            environment_dummy,
+            weighted,
+            frame_buffer,
+        } = args;
+
+        let camera_environment = camera_environment(state, camera, texture_cache);
+
+        // Pick the probes which influence volumes can affect visible pixels. If there are too many
+        // of them, prefer the ones that are closer to the camera - the probe which volume contains
+        // the camera always wins.
+        let camera_position = camera.global_position();
+        let frustum = Frustum::from(camera.view_projection_matrix()).unwrap_or_default();
+        let mut visible_probes = graph
+            .pair_iter()
+            .filter_map(|(handle, node)| {
+                node.cast::<ReflectionProbe>()
+                    .filter(|probe| probe.global_visibility())
+                    .and_then(|probe| {
+                        let influence_volume = probe.world_bounding_box();
+                        if frustum.is_intersects_aabb(&influence_volume) {
+                            probe_environment(state, handle, probe, probes, texture_cache).map(
+                                |environment| {
+                                    let distance =
+                                        distance_to_aabb(&influence_volume, camera_position);
+                                    (probe, environment, distance)
+                                },
+                            )
+                        } else {
+                            None
+                        }
+                    })
+            })
+            .collect::<Vec<_>>();
+        visible_probes.sort_by(|(_, _, a), (_, _, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        visible_probes.truncate(MAX_PROBES);
+
+        // Nothing to add.
+        if weighted && visible_probes.is_empty() && camera_environment.is_none() {
+            return Default::default();
+        }
+
+        let mut probe_textures = Vec::with_capacity(MAX_PROBES);
+        let mut probe_transforms = Vec::with_capacity(MAX_PROBES);
+        let mut probe_inv_transforms = Vec::with_capacity(MAX_PROBES);
+        let mut probe_half_extents = Vec::with_capacity(MAX_PROBES);
+        let mut probe_blend_distances = Vec::with_capacity(MAX_PROBES);
+        for (probe, environment, _) in visible_probes.iter() {
+            // Influence volume is defined by the scale of the probe, keep only translation and
+            // rotation in the matrix and pass the scale as half extents.
+            let global_transform = probe.global_transform();
+            let basis = global_transform.basis();
+            let scale = Vector3::new(
+                basis.column(0).norm(),
+                basis.column(1).norm(),
+                basis.column(2).norm(),
+            );
+            let rotation = Matrix3::from_columns(&[
+                basis
+                    .column(0)
+                    .try_normalize(f32::EPSILON)
+                    .unwrap_or_else(Vector3::x),
+                basis
+                    .column(1)
+                    .try_normalize(f32::EPSILON)
+                    .unwrap_or_else(Vector3::y),
+                basis
+                    .column(2)
+                    .try_normalize(f32::EPSILON)
+                    .unwrap_or_else(Vector3::z),
+            ]);
+            let transform =
+                Matrix4::new_translation(&probe.global_position()) * rotation.to_homogeneous();
+
+            probe_textures.push(environment.clone());
+            probe_transforms.push(transform);
+            probe_inv_transforms.push(transform.try_inverse().unwrap_or_default());
+            probe_half_extents.push(scale.scale(0.5));
+            probe_blend_distances.push(probe.blend_distance());
+        }
+        // Unused samplers still must have a cube map bound.
+        probe_textures.resize(MAX_PROBES, environment_dummy.clone());
+
+        let environment_map = camera_environment.unwrap_or(environment_dummy);
+
+        let viewport = Rect::new(0, 0, gbuffer.width, gbuffer.height);
+        let frame_matrix = make_viewport_matrix(viewport);
+        let inv_view_projection = camera
+            .view_projection_matrix()
+            .try_inverse()
+            .unwrap_or_default();
+
+        let depth_texture = gbuffer.depth();
+        let diffuse_texture = gbuffer.diffuse_texture();
+        let normal_texture = gbuffer.normal_texture();
+        let material_texture = gbuffer.material_texture();
+
+        let shader = &self.shader;
+        let mut statistics = RenderPassStatistics::default();
+        statistics += frame_buffer.draw(
+            &self.quad,
+            state,
+            viewport,
+            &shader.program,
+            &DrawParameters {
+                cull_face: None,
+                color_write: Default::default(),
+                depth_write: false,
+                stencil_test: None,
+                depth_test: false,
+                blend: if weighted {
+                    Some(BlendParameters {
+                        func: BlendFunc::new(BlendFactor::One, BlendFactor::One),
+                        ..Default::default()
+                    })
+                } else {
+                    None
+                },
+                stencil_op: Default::default(),
+            },
+            |mut program_binding| {
+                for (location, texture) in shader.probes.iter().zip(probe_textures.iter()) {
+                    program_binding.set_texture(location, texture);
+                }
+
+                program_binding
+                    .set_matrix4(&shader.wvp_matrix, &frame_matrix)
+                    .set_texture(&shader.depth_texture, &depth_texture)
+                    .set_texture(&shader.diffuse_texture, &diffuse_texture)
+                    .set_texture(&shader.normal_texture, &normal_texture)
+                    .set_texture(&shader.material_texture, &material_texture)
+                    .set_texture(&shader.environment_map, &environment_map)
+                    .set_matrix4_array(&shader.probe_transforms, &probe_transforms)
+                    .set_matrix4_array(&shader.probe_inv_transforms, &probe_inv_transforms)
+                    .set_vector3_slice(&shader.probe_half_extents, &probe_half_extents)
+                    .set_f32_slice(&shader.probe_blend_distances, &probe_blend_distances)
+                    .set_i32(&shader.probe_count, visible_probes.len() as i32)
+                    .set_matrix4(&shader.inv_view_projection, &inv_view_projection)
+                    .set_vector3(&shader.camera_position, &camera_position)
+                    .set_bool(&shader.weighted, weighted);
+            },
+        );
+        statistics
+    }
+}
@@ -0,0 +1,812 @@
+//! Render graph is a declarative description of the rendering pipeline of a scene. It consists of
+//! passes, each pass declares resources (G-Buffer targets, depth, HDR frame, etc.) it reads and
+//! writes and the renderer uses this information to figure out execution order of the passes.
+//!
+//! # Built-in passes
+//!
+//! By default, the graph contains built-in passes of the renderer (see [`BuiltInRenderPass`]).
+//! They can be disabled, replaced with custom passes or removed completely. Custom scene render
+//! passes (see [`super::SceneRenderPass`]) are executed by [`BuiltInRenderPass::CustomHdr`] and
+//! [`BuiltInRenderPass::CustomLdr`] passes.
+//!
+//! # Execution order
+//!
+//! Execution order of passes is defined by their resources:
+//!
+//! - Passes that write the same resource are executed in the order they were added to the graph.
+//! - Passes that only read a resource are executed after all the passes that write it.
+//!
+//! If these rules are not enough to order two passes, then they are executed in the order they
+//! were added to the graph. Passes cannot form cycles (for example, when pass A reads what pass B
+//! writes and vice versa), if there's a cycle the renderer will execute passes in the order they
+//! were added to the graph and will print an error message to the log.
+//!
+//! # Transient textures
+//!
+//! Custom passes can use additional textures that are allocated by the renderer for each scene,
+//! see [`TransientTextureDescriptor`]. Such textures are referenced by their names using
+//! [`RenderResource::Transient`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! use fyrox::renderer::{
+//!     framework::{error::FrameworkError, gpu_texture::PixelKind},
+//!     render_graph::{
+//!         BuiltInRenderPass, RenderGraph, RenderGraphNode, RenderGraphPass,
+//!         RenderGraphPassContext, RenderResource, TransientTextureDescriptor,
+//!     },
+//!     RenderPassStatistics,
+//! };
+//! use std::{cell::RefCell, rc::Rc};
+//!
+//! struct Outline;
+//!
+//! impl RenderGraphPass for Outline {
+//!     fn execute(
+//!         &mut self,
+//!         ctx: RenderGraphPassContext,
+//!     ) -> Result<RenderPassStatistics, FrameworkError> {
+//!         // Read G-Buffer textures using `ctx.resources.texture(..)` and draw into a frame buffer
+//!         // returned by `ctx.resources.framebuffer_mut(..)`.
+//!         Ok(Default::default())
+//!     }
+//! }
+//!
+//! fn setup(graph: &mut RenderGraph) {
+//!     // Turn off built-in bloom.
+//!     graph
+//!         .pass_mut(BuiltInRenderPass::Bloom.name())
+//!         .unwrap()
+//!         .set_enabled(false);
+//!
+//!     graph.add_transient_texture(TransientTextureDescriptor::new(
+//!         "Outline",
+//!         PixelKind::RGBA8,
+//!     ));
+//!
+//!     graph.add_pass(
+//!         RenderGraphNode::custom("Outline", Rc::new(RefCell::new(Outline)))
+//!             .with_read(RenderResource::Depth)
+//!             .with_read(RenderResource::GBufferNormal)
+//!             .with_write(RenderResource::Transient("Outline".to_string()))
+//!             .with_write(RenderResource::LdrFrame),
+//!     );
+//! }
+//! ```
+
+use crate::{
+    core::{math::Rect, pool::Handle},
+    renderer::{
+        batch::BatchStorage,
+        cache::{geometry::GeometryCache, texture::TextureCache},
+        framework::{
+            error::FrameworkError,
+            framebuffer::{Attachment, AttachmentKind, FrameBuffer},
+            gpu_texture::{
+                GpuTexture, GpuTextureKind, MagnificationFilter, MinificationFilter, PixelKind,
+            },
+            state::PipelineState,
+        },
+        gbuffer::GBuffer,
+        QualitySettings, RenderPassStatistics,
+    },
+    scene::{camera::Camera, Scene},
+};
+use fxhash::FxHashMap;
+use std::{
+    cell::RefCell,
+    fmt::{Debug, Formatter},
+    rc::Rc,
+};
+
+/// A resource that can be read or written by a render pass.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RenderResource {
+    /// Diffuse (albedo) texture of G-Buffer.
+    GBufferDiffuse,
+    /// Texture with world-space normals of G-Buffer.
+    GBufferNormal,
+    /// Texture with ambient lighting and emission of G-Buffer.
+    GBufferAmbient,
+    /// Texture with material properties (metallic, roughness, etc.) of G-Buffer.
+    GBufferMaterial,
+    /// Depth-stencil texture of the scene, it is shared between all frame buffers of the scene.
+    Depth,
+    /// High dynamic range frame of the scene (lit scene before tone mapping).
+    HdrFrame,
+    /// Texture with overly bright pixels of HDR frame.
+    Bloom,
+    /// Low dynamic range frame of the scene (final frame).
+    LdrFrame,
+    /// A transient texture with the given name (see [`TransientTextureDescriptor`]).
+    Transient(String),
+}
+
+/// Built-in passes of the renderer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BuiltInRenderPass {
+    /// Fills G-Buffer, copies depth to HDR frame buffer and clears HDR frame.
+    GBuffer,
+    /// Deferred lighting - SSAO, skybox, ambient lighting, shadows and lights.
+    Lighting,
+    /// Reflections from reflection probes and screen-space reflections.
+    Reflections,
+    /// Particle systems, sprites, 2D and forward render paths.
+    Forward,
+    /// Custom scene render passes that render into HDR frame (see
+    /// [`super::SceneRenderPass::on_hdr_render`]).
+    CustomHdr,
+    /// Extracts overly bright pixels from HDR frame.
+    Bloom,
+    /// Tone mapping, color grading and gamma correction.
+    ToneMapping,
+    /// FXAA or TAA (see [`super::QualitySettings::antialiasing`]).
+    AntiAliasing,
+    /// Debug geometry of the scene.
+    Debug,
+    /// Custom scene render passes that render into LDR frame (see
+    /// [`super::SceneRenderPass::on_ldr_render`]).
+    CustomLdr,
+}
+
+impl BuiltInRenderPass {
+    /// All built-in passes in their default order.
+    pub const ALL: [BuiltInRenderPass; 10] = [
+        Self::GBuffer,
+        Self::Lighting,
+        Self::Reflections,
+        Self::Forward,
+        Self::CustomHdr,
+        Self::Bloom,
+        Self::ToneMapping,
+        Self::AntiAliasing,
+        Self::Debug,
+        Self::CustomLdr,
+    ];
+
+    /// Returns name of the pass in a render graph.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::GBuffer => "GBuffer",
+            Self::Lighting => "Lighting",
+            Self::Reflections => "Reflections",
+            Self::Forward => "Forward",
+            Self::CustomHdr => "CustomHdr",
+            Self::Bloom => "Bloom",
+            Self::ToneMapping => "ToneMapping",
+            Self::AntiAliasing => "AntiAliasing",
+            Self::Debug => "Debug",
+            Self::CustomLdr => "CustomLdr",
+        }
+    }
+
+    fn reads(self) -> Vec<RenderResource> {
+        use RenderResource::*;
+        match self {
+            Self::GBuffer => vec![],
+            Self::Lighting | Self::Reflections | Self::CustomHdr => vec![
+                GBufferDiffuse,
+                GBufferNormal,
+                GBufferAmbient,
+                GBufferMaterial,
+                Depth,
+            ],
+            Self::Forward | Self::Debug | Self::CustomLdr => vec![Depth],
+            Self::Bloom => vec![HdrFrame],
+            Self::ToneMapping => vec![HdrFrame, Bloom],
+            Self::AntiAliasing => vec![LdrFrame, Depth],
+        }
+    }
+
+    fn writes(self) -> Vec<RenderResource> {
+        use RenderResource::*;
+        match self {
+            Self::GBuffer => vec![
+                GBufferDiffuse,
+                GBufferNormal,
+                GBufferAmbient,
+                GBufferMaterial,
+                Depth,
+                HdrFrame,
+            ],
+            Self::Lighting | Self::Reflections | Self::Forward | Self::CustomHdr => {
+                vec![HdrFrame]
+            }
+            Self::Bloom => vec![Bloom],
+            Self::ToneMapping | Self::AntiAliasing | Self::Debug | Self::CustomLdr => {
+                vec![LdrFrame]
+            }
+        }
+    }
+}
+
+/// Resources of a scene that are available to custom render passes.
+pub struct RenderGraphResources<'a> {
+    pub(crate) gbuffer: &'a GBuffer,
+    pub(crate) hdr_framebuffer: &'a mut FrameBuffer,
+    pub(crate) ldr_framebuffer: &'a mut FrameBuffer,
+    pub(crate) bloom: Rc<RefCell<GpuTexture>>,
+    pub(crate) transient: &'a mut FxHashMap<String, FrameBuffer>,
+}
+
+impl<'a> RenderGraphResources<'a> {
+    /// Returns a texture of the given resource. It returns `None` only if there's no transient
+    /// texture with the given name.
+    pub fn texture(&self, resource: &RenderResource) -> Option<Rc<RefCell<GpuTexture>>> {
+        match resource {
+            RenderResource::GBufferDiffuse => Some(self.gbuffer.diffuse_texture()),
+            RenderResource::GBufferNormal => Some(self.gbuffer.normal_texture()),
+            RenderResource::GBufferAmbient => Some(self.gbuffer.ambient_texture()),
+            RenderResource::GBufferMaterial => Some(self.gbuffer.material_texture()),
+            RenderResource::Depth => Some(self.gbuffer.depth()),
+            RenderResource::HdrFrame => Some(first_color_texture(self.hdr_framebuffer)),
+            RenderResource::Bloom => Some(self.bloom.clone()),
+            RenderResource::LdrFrame => Some(first_color_texture(self.ldr_framebuffer)),
+            RenderResource::Transient(name) => self.transient.get(name).map(first_color_texture),
+        }
+    }
+
+    /// Returns a frame buffer that can be used to render into the given resource. Only HDR and
+    /// LDR frames and transient textures can be rendered into, frame buffers of HDR and LDR frames
+    /// have depth-stencil attachment with depth of the scene.
+    pub fn framebuffer_mut(&mut self, resource: &RenderResource) -> Option<&mut FrameBuffer> {
+        match resource {
+            RenderResource::HdrFrame => Some(self.hdr_framebuffer),
+            RenderResource::LdrFrame => Some(self.ldr_framebuffer),
+            RenderResource::Transient(name) => self.transient.get_mut(name),
+            _ => None,
+        }
+    }
+}
+
+fn first_color_texture(framebuffer: &FrameBuffer) -> Rc<RefCell<GpuTexture>> {
+    framebuffer.color_attachments()[0].texture.clone()
+}
+
+/// A context for custom render graph passes.
+pub struct RenderGraphPassContext<'a, 'b> {
+    /// A pipeline state that is used as a wrapper to underlying graphics API.
+    pub pipeline_state: &'a mut PipelineState,
+
+    /// A texture cache that uploads engine's `Texture` as internal `GpuTexture` to GPU.
+    pub texture_cache: &'a mut TextureCache,
+
+    /// A geometry cache that uploads engine's `SurfaceData` as internal `GeometryBuffer` to GPU.
+    pub geometry_cache: &'a mut GeometryCache,
+
+    /// A storage that contains "pre-compiled" groups of render data (batches).
+    pub batch_storage: &'a BatchStorage,
+
+    /// Current quality settings of the renderer.
+    pub quality_settings: &'a QualitySettings,
+
+    /// Resources of the scene being rendered.
+    pub resources: RenderGraphResources<'a>,
+
+    /// A scene being rendered.
+    pub scene: &'b Scene,
+
+    /// A camera from the scene that is used as "eyes".
+    pub camera: &'b Camera,
+
+    /// A viewport of the camera.
+    pub viewport: Rect<i32>,
+
+    /// A handle of the scene being rendered.
+    pub scene_handle: Handle<Scene>,
+
+    /// An 1x1 white pixel texture that could be used a stub when there is no texture.
+    pub white_dummy: Rc<RefCell<GpuTexture>>,
+
+    /// An 1x1 pixel texture with (0, 1, 0) vector that could be used a stub when
+    /// there is no normal map.
+    pub normal_dummy: Rc<RefCell<GpuTexture>>,
+
+    /// An 1x1 black pixel texture that could be used a stub when there is no texture.
+    pub black_dummy: Rc<RefCell<GpuTexture>>,
+}
+
+/// A trait for custom render graph passes.
+pub trait RenderGraphPass {
+    /// Executes the pass. It will be called for **each** camera of **each** scene, but you are
+    /// able to filter out scenes by their handles.
+    fn execute(
+        &mut self,
+        ctx: RenderGraphPassContext,
+    ) -> Result<RenderPassStatistics, FrameworkError>;
+}
+
+/// Kind of a render graph pass.
+#[derive(Clone)]
+pub enum RenderGraphPassKind {
+    /// Built-in pass of the renderer.
+    BuiltIn(BuiltInRenderPass),
+    /// Custom pass.
+    Custom(Rc<RefCell<dyn RenderGraphPass>>),
+}
+
+impl Debug for RenderGraphPassKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BuiltIn(pass) => write!(f, "BuiltIn({:?})", pass),
+            Self::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+/// A pass of a render graph with declared resources.
+#[derive(Clone, Debug)]
+pub struct RenderGraphNode {
+    name: String,
+    reads: Vec<RenderResource>,
+    writes: Vec<RenderResource>,
+    enabled: bool,
+    kind: RenderGraphPassKind,
+}
+
+impl RenderGraphNode {
+    /// Creates a node for the given built-in pass, the node will have built-in name and resources
+    /// of the pass.
+    pub fn built_in(pass: BuiltInRenderPass) -> Self {
+        Self {
+            name: pass.name().to_owned(),
+            reads: pass.reads(),
+            writes: pass.writes(),
+            enabled: true,
+            kind: RenderGraphPassKind::BuiltIn(pass),
+        }
+    }
+
+    /// Creates a node for the given custom pass. Resources of the pass must be declared using
+    /// [`Self::with_read`] and [`Self::with_write`].
+    pub fn custom<S: AsRef<str>>(name: S, pass: Rc<RefCell<dyn RenderGraphPass>>) -> Self {
+        Self {
+            name: name.as_ref().to_owned(),
+            reads: Default::default(),
+            writes: Default::default(),
+            enabled: true,
+            kind: RenderGraphPassKind::Custom(pass),
+        }
+    }
+
+    /// Declares a resource that is read by the pass.
+    pub fn with_read(mut self, resource: RenderResource) -> Self {
+        if !self.reads.contains(&resource) {
+            self.reads.push(resource);
+        }
+        self
+    }
+
+    /// Declares a resource that is written by the pass.
+    pub fn with_write(mut self, resource: RenderResource) -> Self {
+        if !self.writes.contains(&resource) {
+            self.writes.push(resource);
+        }
+        self
+    }
+
+    /// Sets whether the pass is enabled or not.
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Returns name of the pass.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns resources that are read by the pass.
+    pub fn reads(&self) -> &[RenderResource] {
+        &self.reads
+    }
+
+    /// Returns resources that are written by the pass.
+    pub fn writes(&self) -> &[RenderResource] {
+        &self.writes
+    }
+
+    /// Enables or disables the pass. Disabled passes are not executed, but they still define
+    /// execution order of other passes.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Returns `true` if the pass is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Returns kind of the pass.
+    pub fn kind(&self) -> &RenderGraphPassKind {
+        &self.kind
+    }
+}
+
+/// Description of a transient texture, the renderer allocates such textures for every scene.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransientTextureDescriptor {
+    /// Unique name of the texture.
+    pub name: String,
+    /// Pixel format of the texture.
+    pub pixel_kind: PixelKind,
+    /// Size of the texture relative to the size of the scene frame.
+    pub scale: f32,
+}
+
+impl TransientTextureDescriptor {
+    /// Creates new descriptor for a texture with the size of the scene frame.
+    pub fn new<S: AsRef<str>>(name: S, pixel_kind: PixelKind) -> Self {
+        Self {
+            name: name.as_ref().to_owned(),
+            pixel_kind,
+            scale: 1.0,
+        }
+    }
+
+    /// Sets size of the texture relative to the size of the scene frame.
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    fn size(&self, width: usize, height: usize) -> (usize, usize) {
+        (
+            ((width as f32 * self.scale) as usize).max(1),
+            ((height as f32 * self.scale) as usize).max(1),
+        )
+    }
+}
+
+/// An error that may occur during render graph compilation.
+#[derive(Debug, thiserror::Error)]
+pub enum RenderGraphError {
+    /// Passes with the given names form a cycle.
+    #[error("Render graph passes {0:?} form a cycle!")]
+    Cycle(Vec<String>),
+}
+
+/// See module docs.
+#[derive(Clone, Debug)]
+pub struct RenderGraph {
+    nodes: Vec<RenderGraphNode>,
+    transient_textures: Vec<TransientTextureDescriptor>,
+    execution_order: Option<Vec<usize>>,
+}
+
+impl Default for RenderGraph {
+    fn default() -> Self {
+        Self {
+            nodes: BuiltInRenderPass::ALL
+                .iter()
+                .map(|pass| RenderGraphNode::built_in(*pass))
+                .collect(),
+            transient_textures: Default::default(),
+            execution_order: None,
+        }
+    }
+}
+
+impl RenderGraph {
+    /// Creates an empty render graph, it has no passes at all.
+    pub fn empty() -> Self {
+        Self {
+            nodes: Default::default(),
+            transient_textures: Default::default(),
+            execution_order: None,
+        }
+    }
+
+    /// Adds new pass to the end of the graph.
+    pub fn add_pass(&mut self, node: RenderGraphNode) {
+        self.nodes.push(node);
+        self.execution_order = None;
+    }
+
+    /// Inserts new pass right before a pass with the given name, or to the end of the graph if
+    /// there's no such pass. Position of a pass matters only for passes that write the same
+    /// resources.
+    pub fn insert_pass_before(&mut self, name: &str, node: RenderGraphNode) {
+        match self.pass_index(name) {
+            Some(index) => self.nodes.insert(index, node),
+            None => self.nodes.push(node),
+        }
+        self.execution_order = None;
+    }
+
+    /// Replaces a pass with the given name with new pass and returns the old one.
+    pub fn replace_pass(&mut self, name: &str, node: RenderGraphNode) -> Option<RenderGraphNode> {
+        let index = self.pass_index(name)?;
+        self.execution_order = None;
+        Some(std::mem::replace(&mut self.nodes[index], node))
+    }
+
+    /// Removes a pass with the given name and returns it.
+    pub fn remove_pass(&mut self, name: &str) -> Option<RenderGraphNode> {
+        let index = self.pass_index(name)?;
+        self.execution_order = None;
+        Some(self.nodes.remove(index))
+    }
+
+    /// Returns a reference to a pass with the given name.
+    pub fn pass(&self, name: &str) -> Option<&RenderGraphNode> {
+        self.nodes.iter().find(|node| node.name == name)
+    }
+
+    /// Returns a reference to a pass with the given name. Only enabled state of a pass can be
+    /// changed, so it is safe to modify it without recompilation of the graph.
+    pub fn pass_mut(&mut self, name: &str) -> Option<&mut RenderGraphNode> {
+        self.nodes.iter_mut().find(|node| node.name == name)
+    }
+
+    fn pass_index(&self, name: &str) -> Option<usize> {
+        self.nodes.iter().position(|node| node.name == name)
+    }
+
+    /// Returns all passes of the graph in the order they were added.
+    pub fn passes(&self) -> &[RenderGraphNode] {
+        &self.nodes
+    }
+
+    /// Adds a transient texture, any previous texture with the same name will be replaced.
+    pub fn add_transient_texture(&mut self, descriptor: TransientTextureDescriptor) {
+        self.transient_textures
+            .retain(|t| t.name != descriptor.name);
+        self.transient_textures.push(descriptor);
+    }
+
+    /// Removes a transient texture with the given name.
+    pub fn remove_transient_texture(&mut self, name: &str) {
+        self.transient_textures.retain(|t| t.name != name);
+    }
+
+    /// Returns descriptors of all transient textures.
+    pub fn transient_textures(&self) -> &[TransientTextureDescriptor] {
+        &self.transient_textures
+    }
+
+    /// Calculates execution order of passes (see module docs) and returns indices of passes.
+    pub fn sort(&self) -> Result<Vec<usize>, RenderGraphError> {
+        let count = self.nodes.len();
+        let mut successors = vec![Vec::<usize>::new(); count];
+        let mut in_degree = vec![0usize; count];
+
+        let mut add_edge = |from: usize, to: usize| {
+            if from != to && !successors[from].contains(&to) {
+                successors[from].push(to);
+                in_degree[to] += 1;
+            }
+        };
+
+        let mut resources = Vec::<&RenderResource>::new();
+        for node in self.nodes.iter() {
+            for resource in node.reads.iter().chain(node.writes.iter()) {
+                if !resources.contains(&resource) {
+                    resources.push(resource);
+                }
+            }
+        }
+
+        for resource in resources {
+            let writers = (0..count)
+                .filter(|i| self.nodes[*i].writes.contains(resource))
+                .collect::<Vec<_>>();
+
+            for pair in writers.windows(2) {
+                add_edge(pair[0], pair[1]);
+            }
+
+            for reader in (0..count).filter(|i| {
+                self.nodes[*i].reads.contains(resource) && !self.nodes[*i].writes.contains(resource)
+            }) {
+                for writer in writers.iter() {
+                    add_edge(*writer, reader);
+                }
+            }
+        }
+
+        // Kahn's algorithm, ties are resolved by the order of addition.
+        let mut order = Vec::with_capacity(count);
+        let mut ready = (0..count)
+            .filter(|i| in_degree[*i] == 0)
+            .collect::<Vec<_>>();
+        while let Some(position) = ready
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, index)| **index)
+            .map(|(position, _)| position)
+        {
+            let index = ready.swap_remove(position);
+            order.push(index);
+            for successor in successors[index].iter() {
+                in_degree[*successor] -= 1;
+                if in_degree[*successor] == 0 {
+                    ready.push(*successor);
+                }
+            }
+        }
+
+        if order.len() == count {
+            Ok(order)
+        } else {
+            Err(RenderGraphError::Cycle(
+                (0..count)
+                    .filter(|i| !order.contains(i))
+                    .map(|i| self.nodes[i].name.clone())
+                    .collect(),
+            ))
+        }
+    }
+
+    /// Returns names and kinds of enabled passes in their execution order. If the graph has a
+    /// cycle, then the passes are returned in the order they were added and an error is returned
+    /// as well.
+    pub(crate) fn compile(
+        &mut self,
+    ) -> (Vec<(String, RenderGraphPassKind)>, Option<RenderGraphError>) {
+        let mut error = None;
+        if self.execution_order.is_none() {
+            self.execution_order = Some(match self.sort() {
+                Ok(order) => order,
+                Err(err) => {
+                    error = Some(err);
+                    (0..self.nodes.len()).collect()
+                }
+            });
+        }
+
+        let passes = self
+            .execution_order
+            .iter()
+            .flatten()
+            .map(|i| &self.nodes[*i])
+            .filter(|node| node.enabled)
+            .map(|node| (node.name.clone(), node.kind.clone()))
+            .collect();
+
+        (passes, error)
+    }
+}
+
+/// Makes sure that every transient texture of the graph has a frame buffer of correct size and
+/// format, removes frame buffers of textures that are not in the graph anymore.
+pub(crate) fn update_transient_framebuffers(
+    state: &mut PipelineState,
+    descriptors: &[TransientTextureDescriptor],
+    framebuffers: &mut FxHashMap<String, FrameBuffer>,
+    frame_width: usize,
+    frame_height: usize,
+) -> Result<(), FrameworkError> {
+    framebuffers.retain(|name, _| descriptors.iter().any(|d| &d.name == name));
+
+    for descriptor in descriptors {
+        let (width, height) = descriptor.size(frame_width, frame_height);
+
+        let is_valid = framebuffers.get(&descriptor.name).map_or(false, |fb| {
+            let texture = fb.color_attachments()[0].texture.borrow();
+            matches!(texture.kind(), GpuTextureKind::Rectangle { width: w, height: h } if w == width && h == height)
+                && texture.pixel_kind() == descriptor.pixel_kind
+        });
+
+        if !is_valid {
+            let texture = GpuTexture::new(
+                state,
+                GpuTextureKind::Rectangle { width, height },
+                descriptor.pixel_kind,
+                MinificationFilter::Linear,
+                MagnificationFilter::Linear,
+                1,
+                None,
+            )?;
+
+            framebuffers.insert(
+                descriptor.name.clone(),
+                FrameBuffer::new(
+                    state,
+                    None,
+                    vec![Attachment {
+                        kind: AttachmentKind::Color,
+                        texture: Rc::new(RefCell::new(texture)),
+                    }],
+                )?,
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::renderer::{
+        framework::error::FrameworkError,
+        render_graph::{
+            BuiltInRenderPass, RenderGraph, RenderGraphError, RenderGraphNode, RenderGraphPass,
+            RenderGraphPassContext, RenderResource,
+        },
+        RenderPassStatistics,
+    };
+    use std::{cell::RefCell, rc::Rc};
+
+    struct Stub;
+
+    impl RenderGraphPass for Stub {
+        fn execute(
+            &mut self,
+            _ctx: RenderGraphPassContext,
+        ) -> Result<RenderPassStatistics, FrameworkError> {
+            Ok(Default::default())
+        }
+    }
+
+    fn names(graph: &RenderGraph) -> Vec<&str> {
+        graph
+            .sort()
+            .unwrap()
+            .into_iter()
+            .map(|i| graph.passes()[i].name())
+            .collect()
+    }
+
+    #[test]
+    fn test_default_order() {
+        let graph = RenderGraph::default();
+        let expected = BuiltInRenderPass::ALL
+            .iter()
+            .map(|p| p.name())
+            .collect::<Vec<_>>();
+        assert_eq!(names(&graph), expected);
+    }
+
+    #[test]
+    fn test_dependency_order() {
+        let mut graph = RenderGraph::default();
+        // Consumer is added first, but it reads what producer writes.
+        graph.add_pass(
+            RenderGraphNode::custom("Consumer", Rc::new(RefCell::new(Stub)))
+                .with_read(RenderResource::Transient("Mask".to_string()))
+                .with_write(RenderResource::LdrFrame),
+        );
+        graph.add_pass(
+            RenderGraphNode::custom("Producer", Rc::new(RefCell::new(Stub)))
+                .with_read(RenderResource::HdrFrame)
+                .with_write(RenderResource::Transient("Mask".to_string())),
+        );
+        graph.insert_pass_before(
+            BuiltInRenderPass::Forward.name(),
+            RenderGraphNode::custom("Opaque", Rc::new(RefCell::new(Stub)))
+                .with_write(RenderResource::HdrFrame),
+        );
+
+        let order = names(&graph);
+        let position = |name: &str| order.iter().position(|n| *n == name).unwrap();
+
+        assert!(position("Producer") < position("Consumer"));
+        assert!(position("CustomHdr") < position("Producer"));
+        assert!(position("CustomLdr") < position("Consumer"));
+        assert!(position("Reflections") < position("Opaque"));
+        assert!(position("Opaque") < position("Forward"));
+    }
+
+    #[test]
+    fn test_cycle() {
+        let mut graph = RenderGraph::empty();
+        graph.add_pass(
+            RenderGraphNode::custom("A", Rc::new(RefCell::new(Stub)))
+                .with_read(RenderResource::Transient("X".to_string()))
+                .with_write(RenderResource::Transient("Y".to_string())),
+        );
+        graph.add_pass(
+            RenderGraphNode::custom("B", Rc::new(RefCell::new(Stub)))
+                .with_read(RenderResource::Transient("Y".to_string()))
+                .with_write(RenderResource::Transient("X".to_string())),
+        );
+
+        assert!(matches!(graph.sort(), Err(RenderGraphError::Cycle(_))));
+
+        let (passes, error) = graph.compile();
+        assert_eq!(passes.len(), 2);
+        assert!(error.is_some());
+    }
+}
@@ -0,0 +1,322 @@
+// Area lights shading using linearly transformed cosines (LTC).
+// "Real-Time Polygonal-Light Shading with Linearly Transformed Cosines", Eric Heitz et al.
+// "Real-Time Line- and Disk-Light Shading with Linearly Transformed Cosines", Eric Heitz, Stephen Hill.
+
+uniform sampler2D depthTexture;
+uniform sampler2D colorTexture;
+uniform sampler2D normalTexture;
+uniform sampler2D materialTexture;
+uniform sampler2D ltcInvMatrices;
+uniform sampler2D ltcAmplitudes;
+uniform samplerCube pointShadowTexture;
+
+uniform vec3 lightPos;
+// Half-extents of the light shape in world space.
+uniform vec3 lightAxisX;
+uniform vec3 lightAxisZ;
+uniform vec3 lightDirection;
+uniform bool isDisc;
+uniform bool twoSided;
+uniform float lightRadius;
+uniform vec4 lightColor;
+uniform mat4 invViewProj;
+uniform vec3 cameraPosition;
+uniform bool softShadows;
+uniform bool shadowsEnabled;
+uniform float shadowBias;
+uniform float lightIntensity;
+
+in vec2 texCoord;
+out vec4 FragColor;
+
+// Keep in sync with LTC_TABLE_SIZE.
+const float LTC_TABLE_SIZE = 32.0;
+
+// Integral of a polygon edge, returns a vector which is already divided by 2 * PI.
+vec3 IntegrateEdge(vec3 v1, vec3 v2)
+{
+    float x = dot(v1, v2);
+    float y = abs(x);
+
+    // Rational fit of theta / (2 * PI * sin(theta)).
+    float a = 0.8543985 + (0.4965155 + 0.0145206 * y) * y;
+    float b = 3.4175940 + (4.1616724 + y) * y;
+    float v = a / b;
+
+    float thetaSinTheta = (x > 0.0) ? v : 0.5 * inversesqrt(max(1.0 - x * x, 1e-7)) - v;
+
+    return cross(v1, v2) * thetaSinTheta;
+}
+
+// Horizon-clipped form factor of a sphere with given form factor (which is sin^2(sigma)) in
+// direction with given cosine to the horizon normal. "Moving Frostbite to PBR", Sebastien Lagarde.
+float ClippedSphereFormFactor(float cosTheta, float formFactor)
+{
+    formFactor = min(formFactor, 0.9999);
+
+    if (cosTheta * cosTheta > formFactor)
+    {
+        return formFactor * max(cosTheta, 0.0);
+    }
+
+    float sinTheta = sqrt(max(1.0 - cosTheta * cosTheta, 0.0));
+    float x = sqrt(1.0 / formFactor - 1.0);
+    float y = -x * (cosTheta / sinTheta);
+    float sinThetaSqrtY = sinTheta * sqrt(max(1.0 - y * y, 0.0));
+
+    return max(((cosTheta * acos(y) - x * sinThetaSqrtY) * formFactor + atan(sinThetaSqrtY / x)) / PI, 0.0);
+}
+
+float RectangleFormFactor(mat3 Minv, vec3 points[4])
+{
+    vec3 L0 = Minv * points[0];
+    vec3 L1 = Minv * points[1];
+    vec3 L2 = Minv * points[2];
+    vec3 L3 = Minv * points[3];
+
+    // Orientation of vertices is not known, make sure that normal of the polygon looks away from
+    // the shading point.
+    vec3 polygonNormal = cross(L1 - L0, L3 - L0);
+    if (dot(polygonNormal, L0) < 0.0)
+    {
+        polygonNormal = -polygonNormal;
+    }
+
+    L0 = normalize(L0);
+    L1 = normalize(L1);
+    L2 = normalize(L2);
+    L3 = normalize(L3);
+
+    vec3 F = IntegrateEdge(L0, L1) + IntegrateEdge(L1, L2) + IntegrateEdge(L2, L3) + IntegrateEdge(L3, L0);
+    if (dot(F, polygonNormal) < 0.0)
+    {
+        F = -F;
+    }
+
+    float formFactor = length(F);
+    if (formFactor <= 0.0)
+    {
+        return 0.0;
+    }
+
+    return ClippedSphereFormFactor(F.z / formFactor, formFactor);
+}
+
+// Blinn's method of solving of cubic equations.
+vec3 SolveCubic(vec4 coefficients)
+{
+    coefficients.xyz /= coefficients.w;
+    coefficients.yz /= 3.0;
+
+    float A = coefficients.w;
+    float B = coefficients.z;
+    float C = coefficients.y;
+    float D = coefficients.x;
+
+    // Hessian and discriminant.
+    vec3 delta = vec3(
+        -coefficients.z * coefficients.z + coefficients.y,
+        -coefficients.y * coefficients.z + coefficients.x,
+        dot(vec2(coefficients.z, -coefficients.y), coefficients.xy)
+    );
+
+    float discriminant = max(dot(vec2(4.0 * delta.x, -delta.y), delta.zy), 0.0);
+
+    vec2 xlc, xsc;
+
+    {
+        float C_a = delta.x;
+        float D_a = -2.0 * B * delta.x + delta.y;
+
+        float theta = atan(sqrt(discriminant), -D_a) / 3.0;
+
+        float x_1a = 2.0 * sqrt(max(-C_a, 0.0)) * cos(theta);
+        float x_3a = 2.0 * sqrt(max(-C_a, 0.0)) * cos(theta + (2.0 / 3.0) * PI);
+
+        float xl = ((x_1a + x_3a) > 2.0 * B) ? x_1a : x_3a;
+
+        xlc = vec2(xl - B, A);
+    }
+
+    {
+        float C_d = delta.z;
+        float D_d = -D * delta.y + 2.0 * C * delta.z;
+
+        float theta = atan(D * sqrt(discriminant), -D_d) / 3.0;
+
+        float x_1d = 2.0 * sqrt(max(-C_d, 0.0)) * cos(theta);
+        float x_3d = 2.0 * sqrt(max(-C_d, 0.0)) * cos(theta + (2.0 / 3.0) * PI);
+
+        float xs = (x_1d + x_3d < 2.0 * C) ? x_1d : x_3d;
+
+        xsc = vec2(-D, xs + C);
+    }
+
+    float E = xlc.y * xsc.y;
+    float F = -xlc.x * xsc.y - xlc.y * xsc.x;
+    float G = xlc.x * xsc.x;
+
+    vec2 xmc = vec2(C * F - B * G, -B * F + C * E);
+
+    vec3 root = vec3(xsc.x / xsc.y, xmc.x / xmc.y, xlc.x / xlc.y);
+
+    if (root.x < root.y && root.x < root.z)
+    {
+        root.xyz = root.yxz;
+    }
+    else if (root.z < root.x && root.z < root.y)
+    {
+        root.xyz = root.xzy;
+    }
+
+    return root;
+}
+
+float DiscFormFactor(mat3 Minv, vec3 center, vec3 axisX, vec3 axisZ)
+{
+    vec3 C = Minv * center;
+    vec3 V1 = Minv * axisX;
+    vec3 V2 = Minv * axisZ;
+
+    // Find principal axes of the transformed ellipse.
+    float a, b;
+    float d11 = dot(V1, V1);
+    float d22 = dot(V2, V2);
+    float d12 = dot(V1, V2);
+    if (abs(d12) / sqrt(d11 * d22) > 0.0001)
+    {
+        float tr = d11 + d22;
+        float det = sqrt(max(-d12 * d12 + d11 * d22, 0.0));
+
+        float u = 0.5 * sqrt(max(tr - 2.0 * det, 0.0));
+        float v = 0.5 * sqrt(tr + 2.0 * det);
+        float eMax = (u + v) * (u + v);
+        float eMin = (u - v) * (u - v);
+
+        vec3 V1_, V2_;
+        if (d11 > d22)
+        {
+            V1_ = d12 * V1 + (eMax - d11) * V2;
+            V2_ = d12 * V1 + (eMin - d11) * V2;
+        }
+        else
+        {
+            V1_ = d12 * V2 + (eMax - d22) * V1;
+            V2_ = d12 * V2 + (eMin - d22) * V1;
+        }
+
+        a = 1.0 / eMax;
+        b = 1.0 / eMin;
+        V1 = normalize(V1_);
+        V2 = normalize(V2_);
+    }
+    else
+    {
+        a = 1.0 / d11;
+        b = 1.0 / d22;
+        V1 *= sqrt(a);
+        V2 *= sqrt(b);
+    }
+
+    vec3 V3 = cross(V1, V2);
+    if (dot(C, V3) < 0.0)
+    {
+        V3 = -V3;
+    }
+
+    float L = dot(V3, C);
+    float x0 = dot(V1, C) / L;
+    float y0 = dot(V2, C) / L;
+
+    a *= L * L;
+    b *= L * L;
+
+    float c0 = a * b;
+    float c1 = a * b * (1.0 + x0 * x0 + y0 * y0) - a - b;
+    float c2 = 1.0 - a * (1.0 + x0 * x0) - b * (1.0 + y0 * y0);
+    float c3 = 1.0;
+
+    vec3 roots = SolveCubic(vec4(c0, c1, c2, c3));
+    float e1 = roots.x;
+    float e2 = roots.y;
+    float e3 = roots.z;
+
+    vec3 averageDirection = vec3(a * x0 / (a - e2), b * y0 / (b - e2), 1.0);
+    averageDirection = normalize(mat3(V1, V2, V3) * averageDirection);
+
+    float L1 = sqrt(-e2 / e3);
+    float L2 = sqrt(-e2 / e1);
+
+    float formFactor = L1 * L2 * inversesqrt((1.0 + L1 * L1) * (1.0 + L2 * L2));
+
+    return ClippedSphereFormFactor(averageDirection.z, formFactor);
+}
+
+float AreaLightFormFactor(mat3 Minv, vec3 center)
+{
+    if (isDisc)
+    {
+        return DiscFormFactor(Minv, center, lightAxisX, lightAxisZ);
+    }
+    else
+    {
+        vec3 points[4];
+        points[0] = center - lightAxisX - lightAxisZ;
+        points[1] = center + lightAxisX - lightAxisZ;
+        points[2] = center + lightAxisX + lightAxisZ;
+        points[3] = center - lightAxisX + lightAxisZ;
+        return RectangleFormFactor(Minv, points);
+    }
+}
+
+void main()
+{
+    vec3 material = texture(materialTexture, texCoord).rgb;
+    float metallic = material.x;
+    float roughness = material.y;
+
+    vec3 fragmentPosition = S_UnProject(vec3(texCoord, texture(depthTexture, texCoord).r), invViewProj);
+    vec3 fragmentToLight = lightPos - fragmentPosition;
+    float distance = length(fragmentToLight);
+
+    // Light is emitted only from one side of the shape, unless it is two-sided. Do not discard
+    // fragments here, stencil buffer must be cleaned for every pixel of the light volume.
+    float sideFactor = (twoSided || dot(fragmentToLight, lightDirection) <= 0.0) ? 1.0 : 0.0;
+
+    vec3 albedo = texture(colorTexture, texCoord).rgb;
+    vec3 N = normalize(texture(normalTexture, texCoord).xyz * 2.0 - 1.0);
+    vec3 V = normalize(cameraPosition - fragmentPosition);
+    float NdotV = clamp(dot(N, V), 0.0, 1.0);
+
+    // Orthonormal basis around the normal, with view vector in XZ plane.
+    vec3 T1 = V - N * dot(V, N);
+    float T1Length = length(T1);
+    T1 = (T1Length > 0.0001) ? T1 / T1Length : normalize(cross(N, abs(N.y) < 0.999 ? vec3(0.0, 1.0, 0.0) : vec3(1.0, 0.0, 0.0)));
+    vec3 T2 = cross(N, T1);
+    mat3 toTangentSpace = transpose(mat3(T1, T2, N));
+
+    vec2 uv = vec2(roughness, sqrt(1.0 - NdotV));
+    uv = uv * ((LTC_TABLE_SIZE - 1.0) / LTC_TABLE_SIZE) + 0.5 / LTC_TABLE_SIZE;
+    vec4 t1 = texture(ltcInvMatrices, uv);
+    vec2 t2 = texture(ltcAmplitudes, uv).xy;
+
+    mat3 ltcInvMatrix = mat3(
+        vec3(t1.x, 0.0, t1.z),
+        vec3(0.0, 1.0, 0.0),
+        vec3(t1.y, 0.0, t1.w)
+    );
+
+    vec3 F0 = mix(vec3(0.04), albedo, metallic);
+
+    float diffuse = AreaLightFormFactor(toTangentSpace, fragmentToLight);
+    vec3 specular = AreaLightFormFactor(ltcInvMatrix * toTangentSpace, fragmentToLight) * (F0 * t2.x + (1.0 - F0) * t2.y);
+
+    vec3 lighting = (albedo * (1.0 - metallic) * diffuse + specular) * lightColor.rgb;
+
+    float distanceAttenuation = S_LightDistanceAttenuation(distance, lightRadius);
+
+    float shadow = S_PointShadow(
+        shadowsEnabled, softShadows, distance, shadowBias, fragmentToLight / distance, pointShadowTexture);
+
+    FragColor = vec4(sideFactor * lightIntensity * distanceAttenuation * shadow * lighting, 1.0);
+}
@@ -0,0 +1,103 @@
+// Calculates environment reflections using reflection probes that affect a pixel. Pixels that
+// are not fully covered by probes use environment map of the camera.
+
+// Keep in sync with MAX_PROBES in reflection_probe.rs
+#define MAX_PROBES 4
+
+uniform sampler2D depthTexture;
+uniform sampler2D diffuseTexture;
+uniform sampler2D normalTexture;
+uniform sampler2D materialTexture;
+uniform samplerCube environmentMap;
+uniform samplerCube probe0;
+uniform samplerCube probe1;
+uniform samplerCube probe2;
+uniform samplerCube probe3;
+uniform mat4 probeTransforms[MAX_PROBES];
+uniform mat4 probeInvTransforms[MAX_PROBES];
+uniform vec3 probeHalfExtents[MAX_PROBES];
+uniform float probeBlendDistances[MAX_PROBES];
+uniform int probeCount;
+uniform mat4 invViewProjection;
+uniform vec3 cameraPosition;
+// If true, reflections will be weighted by Fresnel term and roughness, otherwise raw
+// environment color will be written (for further use in screen-space reflections).
+uniform bool weighted;
+
+in vec2 texCoord;
+out vec4 FragColor;
+
+vec3 SampleProbe(int index, vec3 direction)
+{
+    if (index == 0) return texture(probe0, direction).rgb;
+    if (index == 1) return texture(probe1, direction).rgb;
+    if (index == 2) return texture(probe2, direction).rgb;
+    return texture(probe3, direction).rgb;
+}
+
+void main()
+{
+    float depth = texture(depthTexture, texCoord).r;
+    if (depth >= 1.0) {
+        FragColor = vec4(0.0);
+        return;
+    }
+
+    vec3 worldPosition = S_UnProject(vec3(texCoord, depth), invViewProjection);
+    vec3 normal = normalize(texture(normalTexture, texCoord).xyz * 2.0 - 1.0);
+    vec3 viewDir = normalize(worldPosition - cameraPosition);
+    vec3 reflectionDir = reflect(viewDir, normal);
+
+    vec3 accumulated = vec3(0.0);
+    float totalWeight = 0.0;
+    for (int i = 0; i < MAX_PROBES; ++i) {
+        if (i >= probeCount) {
+            break;
+        }
+
+        vec3 localPosition = (probeInvTransforms[i] * vec4(worldPosition, 1.0)).xyz;
+        vec3 halfExtents = probeHalfExtents[i];
+        vec3 distanceToFaces = halfExtents - abs(localPosition);
+        float minDistance = min(distanceToFaces.x, min(distanceToFaces.y, distanceToFaces.z));
+        if (minDistance <= 0.0) {
+            continue;
+        }
+
+        float weight = clamp(minDistance / max(probeBlendDistances[i], 0.0001), 0.0, 1.0);
+
+        // Box projection: find where reflected ray leaves influence volume and use direction from
+        // the center of the probe to that point, this gives much more correct reflections for
+        // nearby objects than plain reflection vector.
+        vec3 localDir = mat3(probeInvTransforms[i]) * reflectionDir;
+        vec3 safeDir = vec3(
+            abs(localDir.x) < 1e-6 ? 1e-6 : localDir.x,
+            abs(localDir.y) < 1e-6 ? 1e-6 : localDir.y,
+            abs(localDir.z) < 1e-6 ? 1e-6 : localDir.z);
+        vec3 exitTimes = (sign(safeDir) * halfExtents - localPosition) / safeDir;
+        float exitTime = min(exitTimes.x, min(exitTimes.y, exitTimes.z));
+        vec3 localHit = localPosition + localDir * exitTime;
+        vec3 sampleDir = mat3(probeTransforms[i]) * localHit;
+
+        accumulated += SampleProbe(i, sampleDir) * weight;
+        totalWeight += weight;
+    }
+
+    if (totalWeight > 1.0) {
+        accumulated /= totalWeight;
+        totalWeight = 1.0;
+    }
+
+    vec3 environment = accumulated + texture(environmentMap, reflectionDir).rgb * (1.0 - totalWeight);
+
+    if (weighted) {
+        vec3 material = texture(materialTexture, texCoord).rgb;
+        float metallic = material.x;
+        float roughness = material.y;
+        vec3 albedo = texture(diffuseTexture, texCoord).rgb;
+        vec3 F0 = mix(vec3(0.04), albedo, metallic);
+        vec3 fresnel = S_FresnelSchlick(max(dot(normal, -viewDir), 0.0), F0);
+        FragColor = vec4(environment * fresnel * (1.0 - roughness), 1.0);
+    } else {
+        FragColor = vec4(environment, 1.0);
+    }
+}
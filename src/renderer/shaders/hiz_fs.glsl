@@ -0,0 +1,28 @@
+// Builds next level of hierarchical depth buffer (Hi-Z). Each texel of the level contains
+// the closest (minimal) depth of corresponding texels of the previous level.
+
+uniform sampler2D previousLevel;
+
+in vec2 texCoord;
+out float outDepth;
+
+void main()
+{
+    ivec2 previousSize = textureSize(previousLevel, 0);
+    ivec2 coord = ivec2(gl_FragCoord.xy) * 2;
+
+    // Previous level could have odd size, in this case the last texel must also cover extra
+    // row or column, otherwise some depth values will be lost.
+    int extentX = (previousSize.x % 2 == 1 && coord.x + 3 == previousSize.x) ? 3 : 2;
+    int extentY = (previousSize.y % 2 == 1 && coord.y + 3 == previousSize.y) ? 3 : 2;
+
+    float depth = 1.0;
+    for (int y = 0; y < extentY; ++y) {
+        for (int x = 0; x < extentX; ++x) {
+            ivec2 sampleCoord = min(coord + ivec2(x, y), previousSize - 1);
+            depth = min(depth, texelFetch(previousLevel, sampleCoord, 0).r);
+        }
+    }
+
+    outDepth = depth;
+}
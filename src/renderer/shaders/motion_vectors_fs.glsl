@@ -0,0 +1,15 @@
+// Writes screen-space (in texture coordinates) motion of a pixel between previous and current
+// frames. Alpha is set to one to mark pixels that have motion vector, the rest of the pixels
+// are reprojected using camera motion only.
+
+in vec4 currentPosition;
+in vec4 previousPosition;
+
+out vec4 FragColor;
+
+void main()
+{
+    vec2 current = currentPosition.xy / currentPosition.w;
+    vec2 previous = previousPosition.xy / previousPosition.w;
+    FragColor = vec4((current - previous) * 0.5, 0.0, 1.0);
+}
\ No newline at end of file
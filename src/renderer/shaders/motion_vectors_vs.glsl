@@ -0,0 +1,43 @@
+layout(location = 0) in vec3 vertexPosition;
+layout(location = 4) in vec4 boneWeights;
+layout(location = 5) in vec4 boneIndices;
+
+// Jittered matrix, it is used only for rasterization so the pass matches G-Buffer pixels.
+uniform mat4 worldViewProjection;
+uniform mat4 currentWorldViewProjection;
+uniform mat4 previousWorldViewProjection;
+uniform bool useSkeletalAnimation;
+uniform mat4 boneMatrices[60];
+uniform mat4 previousBoneMatrices[60];
+
+out vec4 currentPosition;
+out vec4 previousPosition;
+
+void main()
+{
+    vec4 vertex = vec4(vertexPosition, 1.0);
+    vec4 localPosition = vertex;
+    vec4 previousLocalPosition = vertex;
+
+    if (useSkeletalAnimation)
+    {
+        int i0 = int(boneIndices.x);
+        int i1 = int(boneIndices.y);
+        int i2 = int(boneIndices.z);
+        int i3 = int(boneIndices.w);
+
+        localPosition = (boneMatrices[i0] * vertex) * boneWeights.x
+            + (boneMatrices[i1] * vertex) * boneWeights.y
+            + (boneMatrices[i2] * vertex) * boneWeights.z
+            + (boneMatrices[i3] * vertex) * boneWeights.w;
+
+        previousLocalPosition = (previousBoneMatrices[i0] * vertex) * boneWeights.x
+            + (previousBoneMatrices[i1] * vertex) * boneWeights.y
+            + (previousBoneMatrices[i2] * vertex) * boneWeights.z
+            + (previousBoneMatrices[i3] * vertex) * boneWeights.w;
+    }
+
+    currentPosition = currentWorldViewProjection * localPosition;
+    previousPosition = previousWorldViewProjection * previousLocalPosition;
+    gl_Position = worldViewProjection * localPosition;
+}
\ No newline at end of file
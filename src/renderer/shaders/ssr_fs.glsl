@@ -0,0 +1,175 @@
+// Screen-space reflections with hierarchical depth buffer (Hi-Z) ray marching.
+//
+// Reflected ray is traced in screen space (texture coordinates + non-linear depth), Hi-Z levels
+// allow to skip large empty areas of the screen. If the ray does not hit anything on the screen,
+// environment reflections (from reflection probes or environment map of the camera) are used
+// instead.
+
+// Keep in sync with HIZ_LEVEL_COUNT in ssr.rs
+#define HIZ_LEVEL_COUNT 6
+
+uniform sampler2D hizLevel0;
+uniform sampler2D hizLevel1;
+uniform sampler2D hizLevel2;
+uniform sampler2D hizLevel3;
+uniform sampler2D hizLevel4;
+uniform sampler2D hizLevel5;
+uniform sampler2D frameTexture;
+uniform sampler2D diffuseTexture;
+uniform sampler2D normalTexture;
+uniform sampler2D materialTexture;
+uniform sampler2D environmentReflections;
+uniform mat4 viewProjection;
+uniform mat4 invViewProjection;
+uniform mat4 invProjection;
+uniform mat4 viewMatrix;
+uniform vec3 cameraPosition;
+uniform float zNear;
+uniform int maxSteps;
+uniform float maxDistance;
+uniform float thickness;
+uniform float maxRoughness;
+
+in vec2 texCoord;
+out vec4 FragColor;
+
+ivec2 HiZSize(int level)
+{
+    if (level == 0) return textureSize(hizLevel0, 0);
+    if (level == 1) return textureSize(hizLevel1, 0);
+    if (level == 2) return textureSize(hizLevel2, 0);
+    if (level == 3) return textureSize(hizLevel3, 0);
+    if (level == 4) return textureSize(hizLevel4, 0);
+    return textureSize(hizLevel5, 0);
+}
+
+float HiZDepth(int level, ivec2 cell)
+{
+    cell = clamp(cell, ivec2(0), HiZSize(level) - 1);
+    if (level == 0) return texelFetch(hizLevel0, cell, 0).r;
+    if (level == 1) return texelFetch(hizLevel1, cell, 0).r;
+    if (level == 2) return texelFetch(hizLevel2, cell, 0).r;
+    if (level == 3) return texelFetch(hizLevel3, cell, 0).r;
+    if (level == 4) return texelFetch(hizLevel4, cell, 0).r;
+    return texelFetch(hizLevel5, cell, 0).r;
+}
+
+// Returns ray parameter at which the ray leaves the given cell.
+float CellExitTime(vec3 start, vec3 dir, vec2 cell, vec2 cellCount)
+{
+    vec2 crossStep = vec2(dir.x >= 0.0 ? 1.0 : 0.0, dir.y >= 0.0 ? 1.0 : 0.0);
+    // Small offset is needed to actually step into the next cell.
+    vec2 crossOffset = (crossStep * 2.0 - 1.0) * 0.001 / cellCount;
+    vec2 boundary = (cell + crossStep) / cellCount + crossOffset;
+    vec2 safeDir = vec2(abs(dir.x) < 1e-6 ? 1e-6 : dir.x, abs(dir.y) < 1e-6 ? 1e-6 : dir.y);
+    vec2 t = (boundary - start.xy) / safeDir;
+    return min(t.x, t.y);
+}
+
+float ViewDepth(vec3 screenPos)
+{
+    return S_UnProject(screenPos, invProjection).z;
+}
+
+// Traces the ray in screen space, returns true if there was a hit.
+bool TraceHiZ(vec3 start, vec3 end, out vec3 hit)
+{
+    vec3 dir = end - start;
+
+    int level = 0;
+    float t = 0.0;
+
+    // Step out of the cell of the start point to prevent self-intersection.
+    vec2 startCellCount = vec2(HiZSize(0));
+    t = CellExitTime(start, dir, floor(start.xy * startCellCount), startCellCount);
+
+    for (int i = 0; i < maxSteps && t <= 1.0; ++i) {
+        vec3 position = start + dir * t;
+        vec2 cellCount = vec2(HiZSize(level));
+        vec2 cell = floor(position.xy * cellCount);
+        float minDepth = HiZDepth(level, ivec2(cell));
+
+        if (position.z < minDepth) {
+            // Ray is in front of the closest surface of the cell, try to move it to the depth
+            // of the surface, but not further than the cell boundary.
+            float exitTime = CellExitTime(start, dir, cell, cellCount);
+            float depthTime = dir.z > 0.0 ? (minDepth - start.z) / dir.z : exitTime;
+            if (depthTime < exitTime) {
+                t = depthTime;
+                if (level == 0) {
+                    // Ray reached the surface inside of a single pixel.
+                    hit = start + dir * t;
+                    return true;
+                }
+                level--;
+            } else {
+                t = exitTime;
+                level = min(level + 1, HIZ_LEVEL_COUNT - 1);
+            }
+        } else {
+            // Ray is behind of the closest surface, refine on lower level.
+            if (level == 0) {
+                // Check thickness to not reflect objects that are behind of the surface.
+                float sceneDepth = ViewDepth(vec3(position.xy, minDepth));
+                float rayDepth = ViewDepth(position);
+                if (abs(sceneDepth - rayDepth) <= thickness) {
+                    hit = position;
+                    return true;
+                }
+                // Ray went behind of the surface, step over it.
+                t = CellExitTime(start, dir, cell, cellCount);
+            } else {
+                level--;
+            }
+        }
+    }
+
+    return false;
+}
+
+void main()
+{
+    float depth = texture(hizLevel0, texCoord).r;
+    vec3 material = texture(materialTexture, texCoord).rgb;
+    float metallic = material.x;
+    float roughness = material.y;
+
+    if (depth >= 1.0) {
+        FragColor = vec4(0.0);
+        return;
+    }
+
+    vec3 albedo = texture(diffuseTexture, texCoord).rgb;
+    vec3 normal = normalize(texture(normalTexture, texCoord).xyz * 2.0 - 1.0);
+    vec3 worldPosition = S_UnProject(vec3(texCoord, depth), invViewProjection);
+    vec3 viewDir = normalize(worldPosition - cameraPosition);
+    vec3 reflectionDir = normalize(reflect(viewDir, normal));
+
+    // Clip the ray by near plane, otherwise its projection will be invalid.
+    float rayLength = maxDistance;
+    vec3 viewSpacePosition = (viewMatrix * vec4(worldPosition, 1.0)).xyz;
+    vec3 viewSpaceDir = mat3(viewMatrix) * reflectionDir;
+    if (viewSpaceDir.z > 0.0) {
+        rayLength = min(rayLength, 0.99 * (-zNear - viewSpacePosition.z) / viewSpaceDir.z);
+    }
+
+    vec3 start = vec3(texCoord, depth);
+    vec3 end = S_Project(worldPosition + reflectionDir * rayLength, viewProjection);
+
+    vec3 reflectedColor = texture(environmentReflections, texCoord).rgb;
+
+    vec3 hit;
+    if (roughness <= maxRoughness && TraceHiZ(start, end, hit)) {
+        // Fade out reflections near screen edges and on rough surfaces to hide sharp transition
+        // to the environment reflections.
+        vec2 edgeDistance = min(hit.xy, 1.0 - hit.xy);
+        float edgeFade = clamp(min(edgeDistance.x, edgeDistance.y) * 10.0, 0.0, 1.0);
+        float roughnessFade = 1.0 - clamp(roughness / maxRoughness, 0.0, 1.0);
+        reflectedColor = mix(reflectedColor, texture(frameTexture, hit.xy).rgb, edgeFade * roughnessFade);
+    }
+
+    vec3 F0 = mix(vec3(0.04), albedo, metallic);
+    vec3 fresnel = S_FresnelSchlick(max(dot(normal, -viewDir), 0.0), F0);
+
+    FragColor = vec4(reflectedColor * fresnel * (1.0 - roughness), 1.0);
+}
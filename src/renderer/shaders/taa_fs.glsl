@@ -0,0 +1,56 @@
+// Temporal anti-aliasing resolve pass.
+//
+// Current frame is rendered with sub-pixel jitter, then it is blended with accumulated history
+// of previous frames. History is reprojected using per-object motion vectors from the G-Buffer,
+// which account for movement of objects and skinned meshes. Pixels that have no motion vector
+// (sky, for example) are reprojected using velocity that is reconstructed from the depth buffer
+// and view-projection matrices of current and previous frames. To reduce ghosting history color
+// is clamped to the color range of 3x3 neighborhood of the current pixel.
+
+uniform sampler2D currentTexture;
+uniform sampler2D historyTexture;
+uniform sampler2D depthTexture;
+uniform sampler2D velocityTexture;
+uniform mat4 inverseViewProjection;
+uniform mat4 currentViewProjection;
+uniform mat4 previousViewProjection;
+uniform vec2 inverseScreenSize;
+uniform float blendFactor;
+
+in vec2 texCoord;
+out vec4 fragColor;
+
+void main()
+{
+    vec3 current = texture(currentTexture, texCoord).rgb;
+
+    vec3 neighborhoodMin = current;
+    vec3 neighborhoodMax = current;
+    for (int y = -1; y <= 1; ++y) {
+        for (int x = -1; x <= 1; ++x) {
+            vec3 neighbor = texture(currentTexture, texCoord + vec2(x, y) * inverseScreenSize).rgb;
+            neighborhoodMin = min(neighborhoodMin, neighbor);
+            neighborhoodMax = max(neighborhoodMax, neighbor);
+        }
+    }
+
+    // Velocity is calculated using unjittered matrices, so jitter itself does not cause any motion.
+    vec2 velocity;
+    vec4 motionVector = texture(velocityTexture, texCoord);
+    if (motionVector.w > 0.5) {
+        velocity = motionVector.xy;
+    } else {
+        float depth = texture(depthTexture, texCoord).r;
+        vec3 worldPosition = S_UnProject(vec3(texCoord, depth), inverseViewProjection);
+        velocity = S_Project(worldPosition, currentViewProjection).xy - S_Project(worldPosition, previousViewProjection).xy;
+    }
+    vec2 historyTexCoord = texCoord - velocity;
+
+    if (historyTexCoord.x < 0.0 || historyTexCoord.x > 1.0 || historyTexCoord.y < 0.0 || historyTexCoord.y > 1.0) {
+        // There is no history for the pixel, it was off-screen on previous frame.
+        fragColor = vec4(current, 1.0);
+    } else {
+        vec3 history = clamp(texture(historyTexture, historyTexCoord).rgb, neighborhoodMin, neighborhoodMax);
+        fragColor = vec4(mix(history, current, blendFactor), 1.0);
+    }
+}
@@ -289,6 +289,7 @@ impl CsmRenderer {
                                     camera_position: &camera.global_position(),
                                     use_pom: false,
                                     light_position: &Default::default(),
+                                    light_clusters: None,
                                     normal_dummy: normal_dummy.clone(),
                                     white_dummy: white_dummy.clone(),
                                     black_dummy: black_dummy.clone(),
@@ -258,6 +258,7 @@ impl PointShadowMapRenderer {
                                         camera_position: &Default::default(),
                                         use_pom: false,
                                         light_position: &light_pos,
+                                        light_clusters: None,
                                         normal_dummy: normal_dummy.clone(),
                                         white_dummy: white_dummy.clone(),
                                         black_dummy: black_dummy.clone(),
@@ -176,6 +176,7 @@ impl SpotShadowMapRenderer {
                                     camera_position: &Default::default(),
                                     use_pom: false,
                                     light_position: &Default::default(),
+                                    light_clusters: None,
                                     normal_dummy: normal_dummy.clone(),
                                     white_dummy: white_dummy.clone(),
                                     black_dummy: black_dummy.clone(),
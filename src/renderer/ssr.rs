@@ -0,0 +1,355 @@
+//! Screen-space reflections (SSR). Reflections are calculated by ray marching in a hierarchical
+//! depth buffer (Hi-Z), which allows to skip large empty areas of the screen and to trace rays
+//! for long distances in a few steps. Rays that leave the screen or do not hit anything use
+//! environment reflections (see `reflection_probe.rs`) instead.
+
+use crate::{
+    core::{math::Rect, scope_profile, sstorage::ImmutableString},
+    renderer::{
+        flat_shader::FlatShader,
+        framework::{
+            error::FrameworkError,
+            framebuffer::{
+                Attachment, AttachmentKind, BlendParameters, DrawParameters, FrameBuffer,
+            },
+            geometry_buffer::{GeometryBuffer, GeometryBufferKind},
+            gpu_program::{GpuProgram, UniformLocation},
+            gpu_texture::{
+                GpuTexture, GpuTextureKind, MagnificationFilter, MinificationFilter, PixelKind,
+            },
+            state::{BlendFactor, BlendFunc, PipelineState},
+        },
+        gbuffer::GBuffer,
+        make_viewport_matrix, RenderPassStatistics, SsrSettings,
+    },
+    scene::{camera::Camera, mesh::surface::SurfaceData},
+};
+use std::{cell::RefCell, rc::Rc};
+
+// Keep in sync with shader define. First level is the depth buffer of G-Buffer itself.
+const HIZ_LEVEL_COUNT: usize = 6;
+
+struct HiZShader {
+    program: GpuProgram,
+    wvp_matrix: UniformLocation,
+    previous_level: UniformLocation,
+}
+
+impl HiZShader {
+    fn new(state: &mut PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("shaders/hiz_fs.glsl");
+        let vertex_source = include_str!("shaders/flat_vs.glsl");
+        let program = GpuProgram::from_source(state, "HiZShader", vertex_source, fragment_source)?;
+        Ok(Self {
+            wvp_matrix: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            previous_level: program
+                .uniform_location(state, &ImmutableString::new("previousLevel"))?,
+            program,
+        })
+    }
+}
+
+struct SsrShader {
+    program: GpuProgram,
+    wvp_matrix: UniformLocation,
+    hiz_levels: [UniformLocation; HIZ_LEVEL_COUNT],
+    frame_texture: UniformLocation,
+    diffuse_texture: UniformLocation,
+    normal_texture: UniformLocation,
+    material_texture: UniformLocation,
+    environment_reflections: UniformLocation,
+    view_projection: UniformLocation,
+    inv_view_projection: UniformLocation,
+    inv_projection: UniformLocation,
+    view_matrix: UniformLocation,
+    camera_position: UniformLocation,
+    z_near: UniformLocation,
+    max_steps: UniformLocation,
+    max_distance: UniformLocation,
+    thickness: UniformLocation,
+    max_roughness: UniformLocation,
+}
+
+impl SsrShader {
+    fn new(state: &mut PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("shaders/ssr_fs.glsl");
+        let vertex_source = include_str!("shaders/flat_vs.glsl");
+        let program = GpuProgram::from_source(state, "SsrShader", vertex_source, fragment_source)?;
+        Ok(Self {
+            wvp_matrix: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            hiz_levels: [
+                program.uniform_location(state, &ImmutableString::new("hizLevel0"))?,
+                program.uniform_location(state, &ImmutableString::new("hizLevel1"))?,
+                program.uniform_location(state, &ImmutableString::new("hizLevel2"))?,
+                program.uniform_location(state, &ImmutableString::new("hizLevel3"))?,
+                program.uniform_location(state, &ImmutableString::new("hizLevel4"))?,
+                program.uniform_location(state, &ImmutableString::new("hizLevel5"))?,
+            ],
+            frame_texture: program
+                .uniform_location(state, &ImmutableString::new("frameTexture"))?,
+            diffuse_texture: program
+                .uniform_location(state, &ImmutableString::new("diffuseTexture"))?,
+            normal_texture: program
+                .uniform_location(state, &ImmutableString::new("normalTexture"))?,
+            material_texture: program
+                .uniform_location(state, &ImmutableString::new("materialTexture"))?,
+            environment_reflections: program
+                .uniform_location(state, &ImmutableString::new("environmentReflections"))?,
+            view_projection: program
+                .uniform_location(state, &ImmutableString::new("viewProjection"))?,
+            inv_view_projection: program
+                .uniform_location(state, &ImmutableString::new("invViewProjection"))?,
+            inv_projection: program
+                .uniform_location(state, &ImmutableString::new("invProjection"))?,
+            view_matrix: program.uniform_location(state, &ImmutableString::new("viewMatrix"))?,
+            camera_position: program
+                .uniform_location(state, &ImmutableString::new("cameraPosition"))?,
+            z_near: program.uniform_location(state, &ImmutableString::new("zNear"))?,
+            max_steps: program.uniform_location(state, &ImmutableString::new("maxSteps"))?,
+            max_distance: program.uniform_location(state, &ImmutableString::new("maxDistance"))?,
+            thickness: program.uniform_location(state, &ImmutableString::new("thickness"))?,
+            max_roughness: program
+                .uniform_location(state, &ImmutableString::new("maxRoughness"))?,
+            program,
+        })
+    }
+}
+
+fn make_framebuffer(
+    state: &mut PipelineState,
+    width: usize,
+    height: usize,
+    pixel_kind: PixelKind,
+) -> Result<FrameBuffer, FrameworkError> {
+    let texture = GpuTexture::new(
+        state,
+        GpuTextureKind::Rectangle { width, height },
+        pixel_kind,
+        MinificationFilter::Nearest,
+        MagnificationFilter::Nearest,
+        1,
+        None,
+    )?;
+
+    FrameBuffer::new(
+        state,
+        None,
+        vec![Attachment {
+            kind: AttachmentKind::Color,
+            texture: Rc::new(RefCell::new(texture)),
+        }],
+    )
+}
+
+struct HiZLevel {
+    framebuffer: FrameBuffer,
+    viewport: Rect<i32>,
+}
+
+impl HiZLevel {
+    fn texture(&self) -> Rc<RefCell<GpuTexture>> {
+        self.framebuffer.color_attachments()[0].texture.clone()
+    }
+}
+
+pub(crate) struct SsrRenderContext<'a> {
+    pub state: &'a mut PipelineState,
+    pub camera: &'a Camera,
+    pub gbuffer: &'a GBuffer,
+    pub settings: &'a SsrSettings,
+    /// Lit frame that will be used as a source of reflected colors.
+    pub frame_texture: Rc<RefCell<GpuTexture>>,
+    /// Environment reflections that will be used when a reflected ray does not hit anything on
+    /// the screen.
+    pub environment_reflections: Rc<RefCell<GpuTexture>>,
+    /// Frame buffer to which reflections will be added.
+    pub frame_buffer: &'a mut FrameBuffer,
+}
+
+pub struct ScreenSpaceReflectionsRenderer {
+    hiz_shader: HiZShader,
+    ssr_shader: SsrShader,
+    flat_shader: FlatShader,
+    // Every next level is two times smaller than previous.
+    hiz_chain: Vec<HiZLevel>,
+    reflections: FrameBuffer,
+    quad: GeometryBuffer,
+    width: i32,
+    height: i32,
+}
+
+impl ScreenSpaceReflectionsRenderer {
+    pub fn new(
+        state: &mut PipelineState,
+        width: usize,
+        height: usize,
+    ) -> Result<Self, FrameworkError> {
+        let mut hiz_chain = Vec::with_capacity(HIZ_LEVEL_COUNT - 1);
+        let (mut level_width, mut level_height) = (width, height);
+        for _ in 1..HIZ_LEVEL_COUNT {
+            level_width = (level_width / 2).max(1);
+            level_height = (level_height / 2).max(1);
+            hiz_chain.push(HiZLevel {
+                framebuffer: make_framebuffer(state, level_width, level_height, PixelKind::F32)?,
+                viewport: Rect::new(0, 0, level_width as i32, level_height as i32),
+            });
+        }
+
+        Ok(Self {
+            hiz_shader: HiZShader::new(state)?,
+            ssr_shader: SsrShader::new(state)?,
+            flat_shader: FlatShader::new(state)?,
+            hiz_chain,
+            reflections: make_framebuffer(state, width, height, PixelKind::RGBA16F)?,
+            quad: GeometryBuffer::from_surface_data(
+                &SurfaceData::make_unit_xy_quad(),
+                GeometryBufferKind::StaticDraw,
+                state,
+            ),
+            width: width as i32,
+            height: height as i32,
+        })
+    }
+
+    fn build_hiz(
+        &mut self,
+        state: &mut PipelineState,
+        depth: Rc<RefCell<GpuTexture>>,
+    ) -> RenderPassStatistics {
+        let mut statistics = RenderPassStatistics::default();
+
+        let mut previous_level = depth;
+        for level in self.hiz_chain.iter_mut() {
+            let frame_matrix = make_viewport_matrix(level.viewport);
+
+            let shader = &self.hiz_shader;
+            statistics += level.framebuffer.draw(
+                &self.quad,
+                state,
+                level.viewport,
+                &shader.program,
+                &DrawParameters {
+                    cull_face: None,
+                    color_write: Default::default(),
+                    depth_write: false,
+                    stencil_test: None,
+                    depth_test: false,
+                    blend: None,
+                    stencil_op: Default::default(),
+                },
+                |mut program_binding| {
+                    program_binding
+                        .set_matrix4(&shader.wvp_matrix, &frame_matrix)
+                        .set_texture(&shader.previous_level, &previous_level);
+                },
+            );
+
+            previous_level = level.texture();
+        }
+
+        statistics
+    }
+
+    pub(crate) fn render(&mut self, args: SsrRenderContext) -> RenderPassStatistics {
+        scope_profile!();
+
+        let SsrRenderContext {
+            state,
+            camera,
+            gbuffer,
+            settings,
+            frame_texture,
+            environment_reflections,
+            frame_buffer,
+        } = args;
+
+        let depth = gbuffer.depth();
+
+        let mut statistics = self.build_hiz(state, depth.clone());
+
+        let viewport = Rect::new(0, 0, self.width, self.height);
+        let frame_matrix = make_viewport_matrix(viewport);
+
+        let view_projection = camera.view_projection_matrix();
+        let inv_view_projection = view_projection.try_inverse().unwrap_or_default();
+        let inv_projection = camera.projection_matrix().try_inverse().unwrap_or_default();
+
+        let mut hiz_levels = Vec::with_capacity(HIZ_LEVEL_COUNT);
+        hiz_levels.push(depth);
+        hiz_levels.extend(self.hiz_chain.iter().map(|level| level.texture()));
+
+        let diffuse_texture = gbuffer.diffuse_texture();
+        let normal_texture = gbuffer.normal_texture();
+        let material_texture = gbuffer.material_texture();
+
+        let shader = &self.ssr_shader;
+        statistics += self.reflections.draw(
+            &self.quad,
+            state,
+            viewport,
+            &shader.program,
+            &DrawParameters {
+                cull_face: None,
+                color_write: Default::default(),
+                depth_write: false,
+                stencil_test: None,
+                depth_test: false,
+                blend: None,
+                stencil_op: Default::default(),
+            },
+            |mut program_binding| {
+                for (location, texture) in shader.hiz_levels.iter().zip(hiz_levels.iter()) {
+                    program_binding.set_texture(location, texture);
+                }
+
+                program_binding
+                    .set_matrix4(&shader.wvp_matrix, &frame_matrix)
+                    .set_texture(&shader.frame_texture, &frame_texture)
+                    .set_texture(&shader.diffuse_texture, &diffuse_texture)
+                    .set_texture(&shader.normal_texture, &normal_texture)
+                    .set_texture(&shader.material_texture, &material_texture)
+                    .set_texture(&shader.environment_reflections, &environment_reflections)
+                    .set_matrix4(&shader.view_projection, &view_projection)
+                    .set_matrix4(&shader.inv_view_projection, &inv_view_projection)
+                    .set_matrix4(&shader.inv_projection, &inv_projection)
+                    .set_matrix4(&shader.view_matrix, &camera.view_matrix())
+                    .set_vector3(&shader.camera_position, &camera.global_position())
+                    .set_f32(&shader.z_near, camera.projection().z_near())
+                    .set_i32(&shader.max_steps, settings.max_steps as i32)
+                    .set_f32(&shader.max_distance, settings.max_distance)
+                    .set_f32(&shader.thickness, settings.thickness)
+                    .set_f32(&shader.max_roughness, settings.max_roughness);
+            },
+        );
+
+        // Add reflections to the frame.
+        let reflections = self.reflections.color_attachments()[0].texture.clone();
+        statistics += frame_buffer.draw(
+            &self.quad,
+            state,
+            viewport,
+            &self.flat_shader.program,
+            &DrawParameters {
+                cull_face: None,
+                color_write: Default::default(),
+                depth_write: false,
+                stencil_test: None,
+                depth_test: false,
+                blend: Some(BlendParameters {
+                    func: BlendFunc::new(BlendFactor::One, BlendFactor::One),
+                    ..Default::default()
+                }),
+                stencil_op: Default::default(),
+            },
+            |mut program_binding| {
+                program_binding
+                    .set_matrix4(&self.flat_shader.wvp_matrix, &frame_matrix)
+                    .set_texture(&self.flat_shader.diffuse_texture, &reflections);
+            },
+        );
+
+        statistics
+    }
+}
@@ -0,0 +1,192 @@
+//! Temporal anti-aliasing (TAA). Each frame is rendered with a small sub-pixel offset of the
+//! projection matrix (jitter) and then blended with accumulated history of previous frames,
+//! which gives results similar to super-sampling, but for the price of a single full-screen pass.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Vector2},
+        math::Rect,
+        sstorage::ImmutableString,
+    },
+    renderer::{
+        framework::{
+            error::FrameworkError,
+            framebuffer::{DrawParameters, FrameBuffer},
+            geometry_buffer::{GeometryBuffer, GeometryBufferKind},
+            gpu_program::{GpuProgram, UniformLocation},
+            gpu_texture::GpuTexture,
+            state::PipelineState,
+        },
+        make_viewport_matrix, RenderPassStatistics,
+    },
+    scene::{camera::Camera, mesh::surface::SurfaceData},
+};
+use std::{cell::RefCell, rc::Rc};
+
+/// Amount of jitter positions in the sequence, after that the sequence repeats.
+const JITTER_SEQUENCE_LENGTH: u64 = 8;
+
+/// Defines how much of the current frame will be mixed with the history.
+const BLEND_FACTOR: f32 = 0.1;
+
+/// Returns an element of Halton low-discrepancy sequence with the given index and base.
+fn halton(mut index: u64, base: u64) -> f32 {
+    let mut fraction = 1.0;
+    let mut result = 0.0;
+    while index > 0 {
+        fraction /= base as f32;
+        result += fraction * (index % base) as f32;
+        index /= base;
+    }
+    result
+}
+
+/// Returns sub-pixel jitter (in pixels, in `[-0.5; 0.5]` range) for the given frame.
+pub(crate) fn jitter(frame_index: u64) -> Vector2<f32> {
+    // Halton sequence starts from zero, skip it.
+    let index = frame_index % JITTER_SEQUENCE_LENGTH + 1;
+    Vector2::new(halton(index, 2) - 0.5, halton(index, 3) - 0.5)
+}
+
+struct TaaShader {
+    pub program: GpuProgram,
+    pub wvp_matrix: UniformLocation,
+    pub current_texture: UniformLocation,
+    pub history_texture: UniformLocation,
+    pub depth_texture: UniformLocation,
+    pub velocity_texture: UniformLocation,
+    pub inverse_view_projection: UniformLocation,
+    pub current_view_projection: UniformLocation,
+    pub previous_view_projection: UniformLocation,
+    pub inverse_screen_size: UniformLocation,
+    pub blend_factor: UniformLocation,
+}
+
+impl TaaShader {
+    pub fn new(state: &mut PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("shaders/taa_fs.glsl");
+        let vertex_source = include_str!("shaders/flat_vs.glsl");
+
+        let program = GpuProgram::from_source(state, "TAAShader", vertex_source, fragment_source)?;
+        Ok(Self {
+            wvp_matrix: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            current_texture: program
+                .uniform_location(state, &ImmutableString::new("currentTexture"))?,
+            history_texture: program
+                .uniform_location(state, &ImmutableString::new("historyTexture"))?,
+            depth_texture: program
+                .uniform_location(state, &ImmutableString::new("depthTexture"))?,
+            velocity_texture: program
+                .uniform_location(state, &ImmutableString::new("velocityTexture"))?,
+            inverse_view_projection: program
+                .uniform_location(state, &ImmutableString::new("inverseViewProjection"))?,
+            current_view_projection: program
+                .uniform_location(state, &ImmutableString::new("currentViewProjection"))?,
+            previous_view_projection: program
+                .uniform_location(state, &ImmutableString::new("previousViewProjection"))?,
+            inverse_screen_size: program
+                .uniform_location(state, &ImmutableString::new("inverseScreenSize"))?,
+            blend_factor: program.uniform_location(state, &ImmutableString::new("blendFactor"))?,
+            program,
+        })
+    }
+}
+
+pub(crate) struct TaaRenderContext<'a> {
+    pub state: &'a mut PipelineState,
+    pub viewport: Rect<i32>,
+    pub camera: &'a Camera,
+    /// Unjittered view-projection matrix of previous frame.
+    pub previous_view_projection: Matrix4<f32>,
+    pub frame_texture: Rc<RefCell<GpuTexture>>,
+    pub history_texture: Rc<RefCell<GpuTexture>>,
+    pub depth_texture: Rc<RefCell<GpuTexture>>,
+    /// Per-object motion vectors, pixels without them are reprojected using camera motion.
+    pub velocity_texture: Rc<RefCell<GpuTexture>>,
+    pub frame_buffer: &'a mut FrameBuffer,
+}
+
+pub struct TaaRenderer {
+    shader: TaaShader,
+    quad: GeometryBuffer,
+}
+
+impl TaaRenderer {
+    pub fn new(state: &mut PipelineState) -> Result<Self, FrameworkError> {
+        Ok(Self {
+            shader: TaaShader::new(state)?,
+            quad: GeometryBuffer::from_surface_data(
+                &SurfaceData::make_unit_xy_quad(),
+                GeometryBufferKind::StaticDraw,
+                state,
+            ),
+        })
+    }
+
+    pub(crate) fn render(&self, args: TaaRenderContext) -> RenderPassStatistics {
+        let TaaRenderContext {
+            state,
+            viewport,
+            camera,
+            previous_view_projection,
+            frame_texture,
+            history_texture,
+            depth_texture,
+            velocity_texture,
+            frame_buffer,
+        } = args;
+
+        let mut statistics = RenderPassStatistics::default();
+
+        let frame_matrix = make_viewport_matrix(viewport);
+        let inverse_view_projection = camera
+            .view_projection_matrix()
+            .try_inverse()
+            .unwrap_or_default();
+        let current_view_projection = camera.unjittered_view_projection_matrix();
+
+        statistics += frame_buffer.draw(
+            &self.quad,
+            state,
+            viewport,
+            &self.shader.program,
+            &DrawParameters {
+                cull_face: None,
+                color_write: Default::default(),
+                depth_write: false,
+                stencil_test: None,
+                depth_test: false,
+                blend: None,
+                stencil_op: Default::default(),
+            },
+            |mut program_binding| {
+                program_binding
+                    .set_matrix4(&self.shader.wvp_matrix, &frame_matrix)
+                    .set_matrix4(
+                        &self.shader.inverse_view_projection,
+                        &inverse_view_projection,
+                    )
+                    .set_matrix4(
+                        &self.shader.current_view_projection,
+                        &current_view_projection,
+                    )
+                    .set_matrix4(
+                        &self.shader.previous_view_projection,
+                        &previous_view_projection,
+                    )
+                    .set_vector2(
+                        &self.shader.inverse_screen_size,
+                        &Vector2::new(1.0 / viewport.w() as f32, 1.0 / viewport.h() as f32),
+                    )
+                    .set_f32(&self.shader.blend_factor, BLEND_FACTOR)
+                    .set_texture(&self.shader.current_texture, &frame_texture)
+                    .set_texture(&self.shader.history_texture, &history_texture)
+                    .set_texture(&self.shader.depth_texture, &depth_texture)
+                    .set_texture(&self.shader.velocity_texture, &velocity_texture);
+            },
+        );
+
+        statistics
+    }
+}
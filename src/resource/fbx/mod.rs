@@ -10,7 +10,7 @@ mod document;
 pub mod error;
 mod scene;
 
-use crate::resource::model::{MaterialSearchOptions, ModelImportOptions};
+use crate::resource::model::ModelImportOptions;
 use crate::scene::mesh::Mesh;
 use crate::scene::pivot::PivotBuilder;
 use crate::{
@@ -18,7 +18,6 @@ use crate::{
     core::{
         algebra::{Matrix4, Point3, UnitQuaternion, Vector2, Vector3, Vector4},
         instant::Instant,
-        io,
         math::{self, triangulator::triangulate, RotationOrder},
         parking_lot::Mutex,
         pool::Handle,
@@ -54,7 +53,6 @@ use crate::{
 };
 use fxhash::{FxHashMap, FxHashSet};
 use std::{cmp::Ordering, path::Path, sync::Arc};
-use walkdir::WalkDir;
 
 /// Input angles in degrees
 fn quat_from_euler(euler: Vector3<f32>) -> UnitQuaternion<f32> {
@@ -284,38 +282,10 @@ async fn create_surfaces(
                 let texture = fbx_scene.get(*texture_handle).as_texture()?;
                 let path = texture.get_file_path();
                 if let Some(filename) = path.file_name() {
-                    let texture_path = match model_import_options.material_search_options {
-                        MaterialSearchOptions::MaterialsDirectory(ref directory) => {
-                            Some(directory.join(filename))
-                        }
-                        MaterialSearchOptions::RecursiveUp => {
-                            let mut texture_path = None;
-                            let mut path = model_path.to_owned();
-                            while let Some(parent) = path.parent() {
-                                let candidate = parent.join(filename);
-                                if io::exists(&candidate).await {
-                                    texture_path = Some(candidate);
-                                    break;
-                                }
-                                path.pop();
-                            }
-                            texture_path
-                        }
-                        MaterialSearchOptions::WorkingDirectory => {
-                            let mut texture_path = None;
-                            for dir in WalkDir::new(".").into_iter().flatten() {
-                                if dir.path().is_dir() {
-                                    let candidate = dir.path().join(filename);
-                                    if candidate.exists() {
-                                        texture_path = Some(candidate);
-                                        break;
-                                    }
-                                }
-                            }
-                            texture_path
-                        }
-                        MaterialSearchOptions::UsePathDirectly => Some(path.clone()),
-                    };
+                    let texture_path = model_import_options
+                        .material_search_options
+                        .find_texture(model_path, path)
+                        .await;
 
                     if let Some(texture_path) = texture_path {
                         let texture = resource_manager.request_texture(texture_path.as_path());
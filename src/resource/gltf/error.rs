@@ -0,0 +1,87 @@
+//! Contains all possible errors that can occur during glTF parsing and conversion.
+
+use crate::{
+    core::io::FileLoadError,
+    scene::mesh::buffer::{ValidationError, VertexFetchError},
+};
+
+/// See module docs.
+#[derive(Debug, thiserror::Error)]
+pub enum GltfError {
+    /// An error has occurred while parsing glTF document.
+    #[error("glTF: Parsing error: {0}")]
+    Gltf(gltf::Error),
+
+    /// An error occurred during file loading.
+    #[error("glTF: File load error {0:?}.")]
+    FileLoadError(FileLoadError),
+
+    /// Embedded data has invalid base64 encoding.
+    #[error("glTF: Invalid base64 data: {0}")]
+    Base64(base64::DecodeError),
+
+    /// URI is not supported (only relative paths and base64 data URIs are supported).
+    #[error("glTF: Unsupported URI {0}")]
+    UnsupportedUri(String),
+
+    /// A buffer refers to binary chunk, but the document has no binary chunk.
+    #[error("glTF: Binary chunk is missing.")]
+    MissingBinaryChunk,
+
+    /// Actual buffer size is less than declared.
+    #[error("glTF: Buffer {index} has {actual} bytes, but {expected} bytes were expected.")]
+    BufferLength {
+        /// Index of the buffer.
+        index: usize,
+        /// Declared buffer length.
+        expected: usize,
+        /// Actual buffer length.
+        actual: usize,
+    },
+
+    /// A skin has too many joints, the engine supports up to 256 bones per surface.
+    #[error("glTF: Skin has {0} joints, but only 256 are supported.")]
+    TooManyJoints(usize),
+
+    /// A primitive of a mesh references non-existing vertex.
+    #[error("glTF: Mesh {0} references non-existing vertex.")]
+    IndexOutOfBounds(usize),
+
+    /// Mesh data is invalid.
+    #[error("glTF: Invalid vertex data: {0:?}")]
+    Validation(ValidationError),
+
+    /// Unable to calculate normals or tangents.
+    #[error("glTF: Unable to fetch vertex data: {0:?}")]
+    VertexFetch(VertexFetchError),
+}
+
+impl From<gltf::Error> for GltfError {
+    fn from(err: gltf::Error) -> Self {
+        GltfError::Gltf(err)
+    }
+}
+
+impl From<FileLoadError> for GltfError {
+    fn from(err: FileLoadError) -> Self {
+        GltfError::FileLoadError(err)
+    }
+}
+
+impl From<base64::DecodeError> for GltfError {
+    fn from(err: base64::DecodeError) -> Self {
+        GltfError::Base64(err)
+    }
+}
+
+impl From<ValidationError> for GltfError {
+    fn from(err: ValidationError) -> Self {
+        GltfError::Validation(err)
+    }
+}
+
+impl From<VertexFetchError> for GltfError {
+    fn from(err: VertexFetchError) -> Self {
+        GltfError::VertexFetch(err)
+    }
+}
@@ -0,0 +1,1524 @@
+//! Contains all methods to load and convert glTF 2.0 model format.
+//!
+//! glTF is an open format for transmission of 3D scenes, it supports meshes, skinning, PBR materials,
+//! cameras, punctual lights (`KHR_lights_punctual` extension) and keyframe animations. Both text
+//! (`.gltf`) and binary (`.glb`) variants are supported. Buffers and images can be stored in
+//! external files, embedded as base64 data URIs or stored in the binary chunk of `.glb` file.
+//!
+//! PBR materials are mapped to the standard shader. Since the standard shader takes metalness and
+//! roughness from separate textures, combined metallic-roughness texture of glTF is split in two
+//! textures at load time.
+//!
+//! Embedded images (as well as the generated ones) are loaded as in-memory textures by default.
+//! Textures must have a path to be restored when a scene with the model is loaded, so if
+//! [`ModelImportOptions::extract_embedded_textures`] is set, the images are extracted next to the
+//! model in files named `<model name>_<image name>.<extension>`, for example `robot_image0.png`.
+//!
+//! Normally you should never use methods from this module directly, use resource manager to load
+//! models and create their instances.
+
+pub mod error;
+
+use crate::{
+    animation::{Animation, KeyFrame, Track},
+    core::{
+        algebra::{Matrix4, Quaternion, UnitQuaternion, Vector2, Vector3, Vector4},
+        color::Color,
+        instant::Instant,
+        io,
+        math::TriangleDefinition,
+        parking_lot::Mutex,
+        pool::Handle,
+        sstorage::ImmutableString,
+    },
+    engine::resource_manager::ResourceManager,
+    material::{shader::SamplerFallback, Material, PropertyValue},
+    resource::{
+        gltf::error::GltfError,
+        model::{MaterialSearchOptions, ModelImportOptions},
+        texture::{CompressionOptions, Texture},
+    },
+    scene::{
+        base::BaseBuilder,
+        camera::{CameraBuilder, OrthographicProjection, PerspectiveProjection, Projection},
+        graph::Graph,
+        light::{
+            directional::DirectionalLightBuilder, point::PointLightBuilder, spot::SpotLightBuilder,
+            BaseLightBuilder,
+        },
+        mesh::{
+            buffer::{TriangleBuffer, VertexBuffer},
+            surface::{Surface, SurfaceData},
+            vertex::{AnimatedVertex, StaticVertex},
+            Mesh, MeshBuilder, RenderPath,
+        },
+        node::Node,
+        pivot::PivotBuilder,
+        transform::TransformBuilder,
+        Scene,
+    },
+    utils::log::{Log, MessageKind},
+};
+use fxhash::{FxHashMap, FxHashSet};
+use gltf::{
+    animation::{util::ReadOutputs, Interpolation},
+    khr_lights_punctual::Kind as LightKind,
+    material::AlphaMode,
+    mesh::Mode,
+    Document, Gltf,
+};
+use image::{DynamicImage, GrayImage, ImageOutputFormat, Luma};
+use std::{
+    f32::consts::{FRAC_PI_2, PI},
+    io::Cursor,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Time offset of an extra key frame that is added right before every key of a curve with step
+/// interpolation, it is needed because engine tracks always interpolate between key frames.
+const STEP_EPSILON: f32 = 1.0e-4;
+
+/// Amount of extra key frames that is added between keys of a curve with cubic spline
+/// interpolation, engine tracks interpolate linearly between key frames.
+const CUBIC_SPLINE_SUBDIVISIONS: usize = 3;
+
+/// Decodes percent-encoded characters of a relative URI.
+fn decode_uri(uri: &str) -> String {
+    let bytes = uri.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            if let Some(byte) = uri
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Loads the data either from data URI or from a file relative to the model.
+async fn load_uri(uri: &str, model_path: &Path) -> Result<Vec<u8>, GltfError> {
+    if let Some(data) = uri.strip_prefix("data:") {
+        match data.split_once(";base64,") {
+            Some((_, encoded)) => Ok(base64::decode(encoded)?),
+            None => Err(GltfError::UnsupportedUri(uri.to_owned())),
+        }
+    } else if uri.contains("://") {
+        Err(GltfError::UnsupportedUri(uri.to_owned()))
+    } else {
+        Ok(io::load_file(model_directory(model_path).join(decode_uri(uri))).await?)
+    }
+}
+
+fn model_directory(model_path: &Path) -> &Path {
+    model_path.parent().unwrap_or_else(|| Path::new(""))
+}
+
+async fn load_buffers(
+    document: &Document,
+    mut blob: Option<Vec<u8>>,
+    model_path: &Path,
+) -> Result<Vec<Vec<u8>>, GltfError> {
+    let mut buffers = Vec::new();
+    for buffer in document.buffers() {
+        let data = match buffer.source() {
+            gltf::buffer::Source::Bin => blob.take().ok_or(GltfError::MissingBinaryChunk)?,
+            gltf::buffer::Source::Uri(uri) => load_uri(uri, model_path).await?,
+        };
+        if data.len() < buffer.length() {
+            return Err(GltfError::BufferLength {
+                index: buffer.index(),
+                expected: buffer.length(),
+                actual: data.len(),
+            });
+        }
+        buffers.push(data);
+    }
+    Ok(buffers)
+}
+
+fn set_material_property(material: &mut Material, name: &str, value: PropertyValue) {
+    if let Err(e) = material.set_property(&ImmutableString::new(name), value) {
+        Log::writeln(
+            MessageKind::Error,
+            format!(
+                "Unable to set material property {} for glTF material! Reason: {:?}",
+                name, e
+            ),
+        );
+    }
+}
+
+/// Creates 1x1 single channel image, it is used to pass constant factors to the standard shader
+/// that takes such values only from textures.
+fn make_constant_image(value: u8) -> DynamicImage {
+    DynamicImage::ImageLuma8(GrayImage::from_pixel(1, 1, Luma([value])))
+}
+
+/// Extracts a single channel of an image, scaled by the given factor, into separate image.
+fn extract_channel(image: &DynamicImage, channel: usize, factor: f32) -> DynamicImage {
+    let rgba = image.to_rgba8();
+    let channel = GrayImage::from_fn(rgba.width(), rgba.height(), |x, y| {
+        Luma([(rgba.get_pixel(x, y).0[channel] as f32 * factor.clamp(0.0, 1.0)) as u8])
+    });
+    DynamicImage::ImageLuma8(channel)
+}
+
+/// Writes the data to a file, the file is left untouched if it already has the same content.
+/// Returns `true` if the file was written.
+fn write_if_changed(path: &Path, data: &[u8]) -> std::io::Result<bool> {
+    if std::fs::read(path).map_or(true, |existing| existing != data) {
+        std::fs::write(path, data).map(|_| true)
+    } else {
+        Ok(false)
+    }
+}
+
+/// Animation curve of a single property of a node. Vectors are stored with unused `w` component,
+/// rotations are stored as `(i, j, k, w)` quaternion coordinates.
+struct Curve {
+    interpolation: Interpolation,
+    times: Vec<f32>,
+    values: Vec<Vector4<f32>>,
+    is_rotation: bool,
+}
+
+impl Curve {
+    fn value(&self, index: usize) -> Vector4<f32> {
+        match self.interpolation {
+            // Cubic spline stores (in-tangent, value, out-tangent) triples.
+            Interpolation::CubicSpline => self.values[index * 3 + 1],
+            _ => self.values[index],
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        let expected = match self.interpolation {
+            Interpolation::CubicSpline => self.times.len() * 3,
+            _ => self.times.len(),
+        };
+        !self.times.is_empty() && self.values.len() == expected
+    }
+
+    fn eval(&self, time: f32) -> Vector4<f32> {
+        let next = self.times.partition_point(|&t| t <= time);
+        if next == 0 {
+            return self.value(0);
+        } else if next >= self.times.len() {
+            return self.value(self.times.len() - 1);
+        }
+
+        let current = next - 1;
+        let dt = self.times[next] - self.times[current];
+        let t = (time - self.times[current]) / dt;
+
+        let value = match self.interpolation {
+            Interpolation::Step => self.value(current),
+            Interpolation::Linear => {
+                if self.is_rotation {
+                    let a = UnitQuaternion::from_quaternion(Quaternion::from(self.value(current)));
+                    let b = UnitQuaternion::from_quaternion(Quaternion::from(self.value(next)));
+                    return a
+                        .try_slerp(&b, t, f32::EPSILON)
+                        .unwrap_or(b)
+                        .into_inner()
+                        .coords;
+                } else {
+                    self.value(current).lerp(&self.value(next), t)
+                }
+            }
+            Interpolation::CubicSpline => {
+                let t2 = t * t;
+                let t3 = t2 * t;
+                let out_tangent = self.values[current * 3 + 2];
+                let in_tangent = self.values[next * 3];
+                self.value(current) * (2.0 * t3 - 3.0 * t2 + 1.0)
+                    + out_tangent * (dt * (t3 - 2.0 * t2 + t))
+                    + self.value(next) * (-2.0 * t3 + 3.0 * t2)
+                    + in_tangent * (dt * (t3 - t2))
+            }
+        };
+
+        if self.is_rotation {
+            value.try_normalize(f32::EPSILON).unwrap_or_else(Vector4::w)
+        } else {
+            value
+        }
+    }
+
+    /// Collects times at which the curve should be sampled to be represented by a track.
+    fn sample_times(&self, times: &mut Vec<f32>) {
+        times.extend_from_slice(&self.times);
+        for segment in self.times.windows(2) {
+            match self.interpolation {
+                Interpolation::Step => times.push((segment[1] - STEP_EPSILON).max(segment[0])),
+                Interpolation::CubicSpline => {
+                    for i in 1..=CUBIC_SPLINE_SUBDIVISIONS {
+                        let t = i as f32 / (CUBIC_SPLINE_SUBDIVISIONS + 1) as f32;
+                        times.push(segment[0] + (segment[1] - segment[0]) * t);
+                    }
+                }
+                Interpolation::Linear => (),
+            }
+        }
+    }
+}
+
+type SharedSurfaceData = Arc<Mutex<SurfaceData>>;
+
+#[derive(Default)]
+struct NodeCurves {
+    translation: Option<Curve>,
+    rotation: Option<Curve>,
+    scale: Option<Curve>,
+}
+
+struct Context<'a> {
+    document: &'a Document,
+    buffers: Vec<Vec<u8>>,
+    resource_manager: ResourceManager,
+    model_path: &'a Path,
+    model_import_options: &'a ModelImportOptions,
+    textures: FxHashMap<usize, Texture>,
+    images: FxHashMap<usize, Option<DynamicImage>>,
+    materials: FxHashMap<Option<usize>, Arc<Mutex<Material>>>,
+    surfaces: FxHashMap<(usize, usize, bool), Option<SharedSurfaceData>>,
+}
+
+impl<'a> Context<'a> {
+    fn buffer_view_data(&self, view: &gltf::buffer::View) -> &[u8] {
+        let buffer = &self.buffers[view.buffer().index()];
+        &buffer[view.offset()..view.offset() + view.length()]
+    }
+
+    /// Finds a file of external image using material search options.
+    async fn find_image(&self, uri: &str) -> Option<PathBuf> {
+        let relative = model_directory(self.model_path).join(decode_uri(uri));
+        let options = &self.model_import_options.material_search_options;
+        // glTF stores paths relative to the model file, so try it first for "local" search modes.
+        if matches!(options, MaterialSearchOptions::RecursiveUp) && io::exists(&relative).await {
+            Some(relative)
+        } else {
+            options.find_texture(self.model_path, &relative).await
+        }
+    }
+
+    fn report_missing_image(&self, uri: &str) {
+        Log::writeln(
+            MessageKind::Warning,
+            format!(
+                "Unable to find a texture {} for 3D model {:?} using {:?} option!",
+                uri, self.model_path, self.model_import_options
+            ),
+        );
+    }
+
+    /// Returns a path of a file, that is placed next to the model, for an embedded or generated
+    /// image. The name is built from the name of the model and the given suffix, so the path
+    /// stays the same every time the model is loaded.
+    fn embedded_image_path(&self, suffix: &str, extension: &str) -> PathBuf {
+        let stem = self
+            .model_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        model_directory(self.model_path).join(format!("{}_{}.{}", stem, suffix, extension))
+    }
+
+    /// Creates a texture from an embedded or generated image. If extraction of embedded textures
+    /// is enabled in import options, the image is extracted next to the model and the texture is
+    /// requested from the file, this way the texture has a path and it will be restored correctly
+    /// when a scene with the model is saved and loaded back. Otherwise, or if the file cannot be
+    /// written, the texture is created in memory.
+    fn embedded_texture(&self, suffix: &str, data: &[u8]) -> Option<Texture> {
+        if self.model_import_options.extract_embedded_textures {
+            let extension = image::guess_format(data)
+                .ok()
+                .and_then(|format| format.extensions_str().first().cloned())
+                .unwrap_or("png");
+            let path = self.embedded_image_path(suffix, extension);
+
+            match write_if_changed(&path, data) {
+                Ok(written) => {
+                    if written {
+                        Log::writeln(
+                            MessageKind::Information,
+                            format!(
+                                "Embedded image of {:?} was extracted to {:?}",
+                                self.model_path, path
+                            ),
+                        );
+                    }
+
+                    return Some(self.resource_manager.request_texture(path));
+                }
+                Err(e) => {
+                    Log::writeln(
+                        MessageKind::Warning,
+                        format!(
+                            "Unable to extract embedded image {:?} of {:?}, the texture won't be \
+                            saved with the scene. Reason: {}",
+                            path, self.model_path, e
+                        ),
+                    );
+                }
+            }
+        }
+
+        match Texture::load_from_memory(data, CompressionOptions::NoCompression, true) {
+            Ok(texture) => Some(texture),
+            Err(e) => {
+                Log::writeln(
+                    MessageKind::Error,
+                    format!(
+                        "Unable to load embedded texture {} of {:?}. Reason: {:?}",
+                        suffix, self.model_path, e
+                    ),
+                );
+                None
+            }
+        }
+    }
+
+    /// Encodes generated image and creates a texture from it, see [`Self::embedded_texture`].
+    fn generated_texture(&self, suffix: &str, image: &DynamicImage) -> Option<Texture> {
+        let mut data = Cursor::new(Vec::new());
+        match image.write_to(&mut data, ImageOutputFormat::Png) {
+            Ok(()) => self.embedded_texture(suffix, data.get_ref()),
+            Err(e) => {
+                Log::writeln(
+                    MessageKind::Error,
+                    format!(
+                        "Unable to encode generated texture {} of {:?}. Reason: {:?}",
+                        suffix, self.model_path, e
+                    ),
+                );
+                None
+            }
+        }
+    }
+
+    /// Creates a texture with a single constant value, see [`make_constant_image`].
+    fn constant_texture(&self, value: f32) -> Option<Texture> {
+        let value = (value.clamp(0.0, 1.0) * 255.0) as u8;
+        self.generated_texture(&format!("constant{}", value), &make_constant_image(value))
+    }
+
+    async fn texture(&mut self, image: gltf::Image<'_>) -> Option<Texture> {
+        if let Some(texture) = self.textures.get(&image.index()) {
+            return Some(texture.clone());
+        }
+
+        let suffix = format!("image{}", image.index());
+        let texture = match image.source() {
+            gltf::image::Source::View { view, .. } => {
+                self.embedded_texture(&suffix, self.buffer_view_data(&view))?
+            }
+            gltf::image::Source::Uri { uri, .. } => {
+                if uri.starts_with("data:") {
+                    match load_uri(uri, self.model_path).await {
+                        Ok(data) => self.embedded_texture(&suffix, &data)?,
+                        Err(e) => {
+                            Log::writeln(
+                                MessageKind::Error,
+                                format!(
+                                    "Unable to load embedded texture {} of {:?}. Reason: {}",
+                                    image.index(),
+                                    self.model_path,
+                                    e
+                                ),
+                            );
+                            return None;
+                        }
+                    }
+                } else if let Some(path) = self.find_image(uri).await {
+                    self.resource_manager.request_texture(path)
+                } else {
+                    self.report_missing_image(uri);
+                    return None;
+                }
+            }
+        };
+
+        self.textures.insert(image.index(), texture.clone());
+
+        Some(texture)
+    }
+
+    /// Loads and decodes an image, it is used when an image must be processed before it can be
+    /// used by the standard shader.
+    async fn decoded_image(&mut self, image: gltf::Image<'_>) -> Option<DynamicImage> {
+        if let Some(decoded) = self.images.get(&image.index()) {
+            return decoded.clone();
+        }
+
+        let data = match image.source() {
+            gltf::image::Source::View { view, .. } => Ok(self.buffer_view_data(&view).to_vec()),
+            gltf::image::Source::Uri { uri, .. } => {
+                if uri.starts_with("data:") {
+                    load_uri(uri, self.model_path).await
+                } else if let Some(path) = self.find_image(uri).await {
+                    io::load_file(path).await.map_err(GltfError::from)
+                } else {
+                    self.report_missing_image(uri);
+                    self.images.insert(image.index(), None);
+                    return None;
+                }
+            }
+        };
+
+        let decoded = match data.map(|data| image::load_from_memory(&data)) {
+            Ok(Ok(decoded)) => Some(decoded),
+            Ok(Err(e)) => {
+                Log::writeln(
+                    MessageKind::Error,
+                    format!(
+                        "Unable to decode image {} of {:?}. Reason: {:?}",
+                        image.index(),
+                        self.model_path,
+                        e
+                    ),
+                );
+                None
+            }
+            Err(e) => {
+                Log::writeln(
+                    MessageKind::Error,
+                    format!(
+                        "Unable to load image {} of {:?}. Reason: {}",
+                        image.index(),
+                        self.model_path,
+                        e
+                    ),
+                );
+                None
+            }
+        };
+
+        self.images.insert(image.index(), decoded.clone());
+
+        decoded
+    }
+
+    async fn material(&mut self, material: gltf::Material<'_>) -> Arc<Mutex<Material>> {
+        if let Some(converted) = self.materials.get(&material.index()) {
+            return converted.clone();
+        }
+
+        let mut result = Material::standard();
+
+        let pbr = material.pbr_metallic_roughness();
+
+        set_material_property(
+            &mut result,
+            "diffuseColor",
+            PropertyValue::Color(Color::from(Vector4::from(pbr.base_color_factor()))),
+        );
+
+        let mut samplers = Vec::new();
+
+        if let Some(info) = pbr.base_color_texture() {
+            samplers.push((
+                "diffuseTexture",
+                self.texture(info.texture().source()).await,
+                SamplerFallback::White,
+            ));
+        }
+
+        if let Some(normal) = material.normal_texture() {
+            samplers.push((
+                "normalTexture",
+                self.texture(normal.texture().source()).await,
+                SamplerFallback::Normal,
+            ));
+        }
+
+        // Occlusion is stored in red channel, just as the standard shader expects.
+        if let Some(occlusion) = material.occlusion_texture() {
+            samplers.push((
+                "aoTexture",
+                self.texture(occlusion.texture().source()).await,
+                SamplerFallback::White,
+            ));
+        }
+
+        // Metalness is stored in blue channel, roughness - in green.
+        let metallic_factor = pbr.metallic_factor();
+        let roughness_factor = pbr.roughness_factor();
+        let metallic_roughness = match pbr.metallic_roughness_texture() {
+            Some(info) => {
+                let image = info.texture().source();
+                let index = image.index();
+                self.decoded_image(image).await.map(|image| (index, image))
+            }
+            None => None,
+        };
+        match metallic_roughness {
+            Some((index, image)) => {
+                // Factors are baked in the images, so they're part of the name.
+                samplers.push((
+                    "metallicTexture",
+                    self.generated_texture(
+                        &format!(
+                            "image{}_metallic{}",
+                            index,
+                            (metallic_factor.clamp(0.0, 1.0) * 255.0) as u8
+                        ),
+                        &extract_channel(&image, 2, metallic_factor),
+                    ),
+                    SamplerFallback::Black,
+                ));
+                samplers.push((
+                    "roughnessTexture",
+                    self.generated_texture(
+                        &format!(
+                            "image{}_roughness{}",
+                            index,
+                            (roughness_factor.clamp(0.0, 1.0) * 255.0) as u8
+                        ),
+                        &extract_channel(&image, 1, roughness_factor),
+                    ),
+                    SamplerFallback::White,
+                ));
+            }
+            None => {
+                if metallic_factor > 0.0 {
+                    samplers.push((
+                        "metallicTexture",
+                        self.constant_texture(metallic_factor),
+                        SamplerFallback::Black,
+                    ));
+                }
+                if roughness_factor < 1.0 {
+                    samplers.push((
+                        "roughnessTexture",
+                        self.constant_texture(roughness_factor),
+                        SamplerFallback::White,
+                    ));
+                }
+            }
+        }
+
+        let emission =
+            Vector3::from(material.emissive_factor()) * material.emissive_strength().unwrap_or(1.0);
+        if emission != Vector3::default() {
+            set_material_property(
+                &mut result,
+                "emissionStrength",
+                PropertyValue::Vector3(emission),
+            );
+            let texture = match material.emissive_texture() {
+                Some(info) => self.texture(info.texture().source()).await,
+                None => self.constant_texture(1.0),
+            };
+            samplers.push(("emissionTexture", texture, SamplerFallback::Black));
+        }
+
+        for (name, texture, fallback) in samplers {
+            if let Some(texture) = texture {
+                set_material_property(
+                    &mut result,
+                    name,
+                    PropertyValue::Sampler {
+                        value: Some(texture),
+                        fallback,
+                    },
+                );
+            }
+        }
+
+        let result = Arc::new(Mutex::new(result));
+        self.materials.insert(material.index(), result.clone());
+        result
+    }
+
+    fn surface_data(
+        &mut self,
+        mesh: &gltf::Mesh,
+        primitive: &gltf::Primitive,
+        skinned: bool,
+    ) -> Result<Option<SharedSurfaceData>, GltfError> {
+        let key = (mesh.index(), primitive.index(), skinned);
+        if let Some(data) = self.surfaces.get(&key) {
+            return Ok(data.clone());
+        }
+
+        let data = self
+            .convert_primitive(mesh, primitive, skinned)?
+            .map(|data| Arc::new(Mutex::new(data)));
+
+        self.surfaces.insert(key, data.clone());
+
+        Ok(data)
+    }
+
+    fn convert_primitive(
+        &self,
+        mesh: &gltf::Mesh,
+        primitive: &gltf::Primitive,
+        skinned: bool,
+    ) -> Result<Option<SurfaceData>, GltfError> {
+        let buffers = &self.buffers;
+        let reader = primitive.reader(|buffer| buffers.get(buffer.index()).map(|b| b.as_slice()));
+
+        let positions = match reader.read_positions() {
+            Some(positions) => positions.map(Vector3::from).collect::<Vec<_>>(),
+            None => return Ok(None),
+        };
+        let vertex_count = positions.len();
+
+        let normals = reader
+            .read_normals()
+            .map(|normals| normals.map(Vector3::from).collect::<Vec<_>>());
+        let tangents = reader
+            .read_tangents()
+            .map(|tangents| tangents.map(Vector4::from).collect::<Vec<_>>());
+        let tex_coords = reader
+            .read_tex_coords(0)
+            .map(|uvs| uvs.into_f32().map(Vector2::from).collect::<Vec<_>>());
+        let indices = match reader.read_indices() {
+            Some(indices) => indices.into_u32().collect::<Vec<_>>(),
+            None => (0..vertex_count as u32).collect(),
+        };
+
+        let triangles = match primitive.mode() {
+            Mode::Triangles => indices
+                .chunks_exact(3)
+                .map(|t| TriangleDefinition([t[0], t[1], t[2]]))
+                .collect::<Vec<_>>(),
+            Mode::TriangleStrip => (0..indices.len().saturating_sub(2))
+                .map(|i| {
+                    if i % 2 == 0 {
+                        TriangleDefinition([indices[i], indices[i + 1], indices[i + 2]])
+                    } else {
+                        TriangleDefinition([indices[i + 1], indices[i], indices[i + 2]])
+                    }
+                })
+                .collect(),
+            Mode::TriangleFan => (1..indices.len().saturating_sub(1))
+                .map(|i| TriangleDefinition([indices[0], indices[i], indices[i + 1]]))
+                .collect(),
+            mode => {
+                Log::writeln(
+                    MessageKind::Warning,
+                    format!(
+                        "glTF: Primitive {} of mesh {} has unsupported mode {:?} and was ignored.",
+                        primitive.index(),
+                        mesh.index(),
+                        mode
+                    ),
+                );
+                return Ok(None);
+            }
+        };
+
+        if triangles
+            .iter()
+            .any(|t| t.0.iter().any(|&i| i as usize >= vertex_count))
+        {
+            return Err(GltfError::IndexOutOfBounds(mesh.index()));
+        }
+
+        let static_vertices = positions
+            .iter()
+            .enumerate()
+            .map(|(i, position)| StaticVertex {
+                position: *position,
+                tex_coord: tex_coords
+                    .as_ref()
+                    .and_then(|uvs| uvs.get(i).cloned())
+                    .unwrap_or_default(),
+                normal: normals
+                    .as_ref()
+                    .and_then(|normals| normals.get(i).cloned())
+                    .unwrap_or_else(Vector3::y),
+                tangent: tangents
+                    .as_ref()
+                    .and_then(|tangents| tangents.get(i).cloned())
+                    .unwrap_or_else(|| Vector4::new(1.0, 0.0, 0.0, 1.0)),
+            });
+
+        let vertex_buffer = if skinned {
+            let joints = reader
+                .read_joints(0)
+                .map(|joints| joints.into_u16().collect::<Vec<_>>())
+                .unwrap_or_default();
+            let weights = reader
+                .read_weights(0)
+                .map(|weights| weights.into_f32().collect::<Vec<_>>())
+                .unwrap_or_default();
+
+            let mut vertices = Vec::with_capacity(vertex_count);
+            for (i, vertex) in static_vertices.enumerate() {
+                let mut bone_indices = [0u8; 4];
+                for (index, &joint) in bone_indices
+                    .iter_mut()
+                    .zip(joints.get(i).cloned().unwrap_or_default().iter())
+                {
+                    *index = u8::try_from(joint)
+                        .map_err(|_| GltfError::TooManyJoints(joint as usize + 1))?;
+                }
+                vertices.push(AnimatedVertex {
+                    position: vertex.position,
+                    tex_coord: vertex.tex_coord,
+                    normal: vertex.normal,
+                    tangent: vertex.tangent,
+                    bone_weights: weights.get(i).cloned().unwrap_or_default(),
+                    bone_indices,
+                });
+            }
+            VertexBuffer::new(vertex_count, AnimatedVertex::layout(), vertices)?
+        } else {
+            VertexBuffer::new(
+                vertex_count,
+                StaticVertex::layout(),
+                static_vertices.collect(),
+            )?
+        };
+
+        let mut data = SurfaceData::new(vertex_buffer, TriangleBuffer::new(triangles), false);
+
+        if normals.is_none() {
+            data.calculate_normals()?;
+        }
+        if tangents.is_none() {
+            data.calculate_tangents()?;
+        }
+
+        Ok(Some(data))
+    }
+
+    async fn convert_mesh(
+        &mut self,
+        base: BaseBuilder,
+        mesh: gltf::Mesh<'_>,
+        skinned: bool,
+        graph: &mut Graph,
+    ) -> Result<Handle<Node>, GltfError> {
+        let mut surfaces = Vec::new();
+        let mut render_path = RenderPath::Deferred;
+        for primitive in mesh.primitives() {
+            if let Some(data) = self.surface_data(&mesh, &primitive, skinned)? {
+                let mut surface = Surface::new(data);
+                let material = primitive.material();
+                if material.alpha_mode() == AlphaMode::Blend {
+                    render_path = RenderPath::Forward;
+                }
+                surface.set_material(self.material(material).await);
+                surfaces.push(surface);
+            }
+        }
+
+        Ok(MeshBuilder::new(base)
+            .with_surfaces(surfaces)
+            .with_render_path(render_path)
+            .build(graph))
+    }
+
+    /// Converts a camera or a light of a node. glTF cameras and lights are looking along -Z axis,
+    /// while engine cameras are looking along +Z and lights along -Y, so the converted entity is
+    /// attached as a child node with additional rotation.
+    fn convert_attachments(
+        &self,
+        node: &gltf::Node,
+        name: &str,
+        graph: &mut Graph,
+    ) -> Vec<Handle<Node>> {
+        let mut attachments = Vec::new();
+
+        if let Some(camera) = node.camera() {
+            let base = BaseBuilder::new()
+                .with_name(format!("{}Camera", name))
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_rotation(UnitQuaternion::from_axis_angle(
+                            &Vector3::y_axis(),
+                            PI,
+                        ))
+                        .build(),
+                );
+            let projection = match camera.projection() {
+                gltf::camera::Projection::Perspective(perspective) => {
+                    Projection::Perspective(PerspectiveProjection {
+                        fov: perspective.yfov(),
+                        z_near: perspective.znear(),
+                        z_far: perspective
+                            .zfar()
+                            .unwrap_or_else(|| PerspectiveProjection::default().z_far),
+                    })
+                }
+                gltf::camera::Projection::Orthographic(orthographic) => {
+                    Projection::Orthographic(OrthographicProjection {
+                        z_near: orthographic.znear(),
+                        z_far: orthographic.zfar(),
+                        vertical_size: orthographic.ymag(),
+                    })
+                }
+            };
+            attachments.push(
+                CameraBuilder::new(base)
+                    .with_projection(projection)
+                    .build(graph),
+            );
+        }
+
+        if let Some(light) = node.light() {
+            let base = BaseLightBuilder::new(
+                BaseBuilder::new()
+                    .with_name(format!("{}Light", name))
+                    .with_local_transform(
+                        TransformBuilder::new()
+                            .with_local_rotation(UnitQuaternion::from_axis_angle(
+                                &Vector3::x_axis(),
+                                FRAC_PI_2,
+                            ))
+                            .build(),
+                    ),
+            )
+            .with_color(Color::from(Vector3::from(light.color())))
+            .with_intensity(light.intensity());
+            let handle = match light.kind() {
+                LightKind::Directional => DirectionalLightBuilder::new(base).build(graph),
+                LightKind::Point => {
+                    let mut builder = PointLightBuilder::new(base);
+                    if let Some(range) = light.range() {
+                        builder = builder.with_radius(range);
+                    }
+                    builder.build(graph)
+                }
+                LightKind::Spot {
+                    inner_cone_angle,
+                    outer_cone_angle,
+                } => {
+                    // glTF cone angles are measured from the axis, engine angles are full angles.
+                    let mut builder = SpotLightBuilder::new(base)
+                        .with_hotspot_cone_angle(2.0 * inner_cone_angle)
+                        .with_falloff_angle_delta(2.0 * (outer_cone_angle - inner_cone_angle));
+                    if let Some(range) = light.range() {
+                        builder = builder.with_distance(range);
+                    }
+                    builder.build(graph)
+                }
+            };
+            attachments.push(handle);
+        }
+
+        attachments
+    }
+
+    async fn convert_node(
+        &mut self,
+        node: &gltf::Node<'_>,
+        graph: &mut Graph,
+    ) -> Result<Handle<Node>, GltfError> {
+        let name = node_name(node);
+        let (translation, rotation, scale) = node.transform().decomposed();
+
+        let attachments = self.convert_attachments(node, &name, graph);
+
+        let base = BaseBuilder::new()
+            .with_name(name)
+            .with_children(&attachments)
+            .with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::from(translation))
+                    .with_local_rotation(UnitQuaternion::from_quaternion(Quaternion::from(
+                        Vector4::from(rotation),
+                    )))
+                    .with_local_scale(Vector3::from(scale))
+                    .build(),
+            );
+
+        match node.mesh() {
+            Some(mesh) => {
+                self.convert_mesh(base, mesh, node.skin().is_some(), graph)
+                    .await
+            }
+            None => Ok(PivotBuilder::new(base).build(graph)),
+        }
+    }
+}
+
+fn node_name(node: &gltf::Node) -> String {
+    node.name()
+        .map(|name| name.to_owned())
+        .unwrap_or_else(|| format!("Node{}", node.index()))
+}
+
+fn read_curve(
+    channel: &gltf::animation::Channel,
+    buffers: &[Vec<u8>],
+) -> Option<(gltf::animation::Property, Curve)> {
+    let reader = channel.reader(|buffer| buffers.get(buffer.index()).map(|b| b.as_slice()));
+    let times = reader.read_inputs()?.collect::<Vec<_>>();
+    let (values, is_rotation) = match reader.read_outputs()? {
+        ReadOutputs::Translations(translations) => (
+            translations
+                .map(|v| Vector4::new(v[0], v[1], v[2], 0.0))
+                .collect(),
+            false,
+        ),
+        ReadOutputs::Rotations(rotations) => {
+            (rotations.into_f32().map(Vector4::from).collect(), true)
+        }
+        ReadOutputs::Scales(scales) => (
+            scales
+                .map(|v| Vector4::new(v[0], v[1], v[2], 0.0))
+                .collect(),
+            false,
+        ),
+        // Morph targets are not supported.
+        ReadOutputs::MorphTargetWeights(_) => return None,
+    };
+    let curve = Curve {
+        interpolation: channel.sampler().interpolation(),
+        times,
+        values,
+        is_rotation,
+    };
+    if curve.is_valid() {
+        Some((channel.target().property(), curve))
+    } else {
+        Log::writeln(
+            MessageKind::Warning,
+            format!(
+                "glTF: Channel {} of animation {} has invalid data and was ignored.",
+                channel.index(),
+                channel.animation().index()
+            ),
+        );
+        None
+    }
+}
+
+fn convert_animation(
+    animation: &gltf::Animation,
+    buffers: &[Vec<u8>],
+    node_map: &FxHashMap<usize, Handle<Node>>,
+) -> Animation {
+    let mut node_curves = FxHashMap::<usize, NodeCurves>::default();
+    for channel in animation.channels() {
+        if let Some((property, curve)) = read_curve(&channel, buffers) {
+            let curves = node_curves
+                .entry(channel.target().node().index())
+                .or_default();
+            match property {
+                gltf::animation::Property::Translation => curves.translation = Some(curve),
+                gltf::animation::Property::Rotation => curves.rotation = Some(curve),
+                gltf::animation::Property::Scale => curves.scale = Some(curve),
+                gltf::animation::Property::MorphTargetWeights => (),
+            }
+        }
+    }
+
+    let mut result = Animation::default();
+
+    for (node_index, curves) in node_curves {
+        let node_handle = match node_map.get(&node_index) {
+            Some(handle) => *handle,
+            // Node is not a part of converted scene.
+            None => continue,
+        };
+
+        let mut times = Vec::new();
+        for curve in [&curves.translation, &curves.rotation, &curves.scale]
+            .into_iter()
+            .flatten()
+        {
+            curve.sample_times(&mut times);
+        }
+        times.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        times.dedup_by(|a, b| (*a - *b).abs() <= f32::EPSILON);
+
+        let node = animation
+            .channels()
+            .map(|channel| channel.target().node())
+            .find(|node| node.index() == node_index)
+            .unwrap();
+        let (translation, rotation, scale) = node.transform().decomposed();
+
+        let key_frames = times
+            .into_iter()
+            .map(|time| {
+                let eval = |curve: &Option<Curve>, default: Vector4<f32>| {
+                    curve.as_ref().map_or(default, |curve| curve.eval(time))
+                };
+                let position = eval(
+                    &curves.translation,
+                    Vector4::new(translation[0], translation[1], translation[2], 0.0),
+                );
+                let rotation = eval(&curves.rotation, Vector4::from(rotation));
+                let scale = eval(
+                    &curves.scale,
+                    Vector4::new(scale[0], scale[1], scale[2], 0.0),
+                );
+                KeyFrame::new(
+                    time,
+                    position.xyz(),
+                    scale.xyz(),
+                    UnitQuaternion::from_quaternion(Quaternion::from(rotation)),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let mut track = Track::new();
+        track.set_node(node_handle);
+        track.set_key_frames(&key_frames);
+        result.add_track(track);
+    }
+
+    result
+}
+
+/// Converts glTF document to native engine representation.
+async fn convert(
+    document: &Document,
+    buffers: Vec<Vec<u8>>,
+    resource_manager: ResourceManager,
+    scene: &mut Scene,
+    model_path: &Path,
+    model_import_options: &ModelImportOptions,
+) -> Result<(), GltfError> {
+    let mut context = Context {
+        document,
+        buffers,
+        resource_manager,
+        model_path,
+        model_import_options,
+        textures: Default::default(),
+        images: Default::default(),
+        materials: Default::default(),
+        surfaces: Default::default(),
+    };
+
+    // Use default scene, or the first one. If there is no scenes at all, convert every node that
+    // has no parent.
+    let roots = match document
+        .default_scene()
+        .or_else(|| document.scenes().next())
+    {
+        Some(gltf_scene) => gltf_scene.nodes().collect::<Vec<_>>(),
+        None => {
+            let children = document
+                .nodes()
+                .flat_map(|node| node.children().map(|child| child.index()))
+                .collect::<FxHashSet<_>>();
+            document
+                .nodes()
+                .filter(|node| !children.contains(&node.index()))
+                .collect()
+        }
+    };
+
+    let root = scene.graph.get_root();
+    let mut node_map = FxHashMap::default();
+    let mut stack = roots
+        .into_iter()
+        .map(|node| (node, root))
+        .collect::<Vec<_>>();
+    while let Some((node, parent)) = stack.pop() {
+        if node_map.contains_key(&node.index()) {
+            continue;
+        }
+        let handle = context.convert_node(&node, &mut scene.graph).await?;
+        scene.graph.link_nodes(handle, parent);
+        node_map.insert(node.index(), handle);
+        stack.extend(node.children().map(|child| (child, handle)));
+    }
+
+    // Bind skinned meshes to their skeletons.
+    for node in context.document.nodes() {
+        if let (Some(skin), Some(&handle)) = (node.skin(), node_map.get(&node.index())) {
+            let joints = skin
+                .joints()
+                .map(|joint| node_map.get(&joint.index()).cloned().unwrap_or_default())
+                .collect::<Vec<_>>();
+            if joints.len() > 256 {
+                return Err(GltfError::TooManyJoints(joints.len()));
+            }
+
+            let buffers = &context.buffers;
+            let inverse_bind_matrices = skin
+                .reader(|buffer| buffers.get(buffer.index()).map(|b| b.as_slice()))
+                .read_inverse_bind_matrices()
+                .map(|matrices| matrices.map(Matrix4::from).collect::<Vec<_>>())
+                .unwrap_or_default();
+
+            for (i, &joint) in joints.iter().enumerate() {
+                if let Some(joint) = scene.graph.try_get_mut(joint) {
+                    joint.inv_bind_pose_transform = inverse_bind_matrices
+                        .get(i)
+                        .cloned()
+                        .unwrap_or_else(Matrix4::identity);
+                }
+            }
+
+            if let Some(mesh) = scene.graph[handle].cast_mut::<Mesh>() {
+                for surface in mesh.surfaces_mut() {
+                    surface.bones = joints.clone();
+                }
+            }
+        }
+    }
+
+    scene.graph.update_hierarchical_data();
+
+    for animation in context.document.animations() {
+        scene
+            .animations
+            .add(convert_animation(&animation, &context.buffers, &node_map));
+    }
+
+    Ok(())
+}
+
+/// Tries to load and convert glTF (or binary glTF) from given path.
+///
+/// Normally you should never use this method, use resource manager to load models.
+pub async fn load_to_scene<P: AsRef<Path>>(
+    scene: &mut Scene,
+    resource_manager: ResourceManager,
+    path: P,
+    model_import_options: &ModelImportOptions,
+) -> Result<(), GltfError> {
+    let start_time = Instant::now();
+
+    Log::writeln(
+        MessageKind::Information,
+        format!("Trying to load {:?}", path.as_ref()),
+    );
+
+    let now = Instant::now();
+    let data = io::load_file(path.as_ref()).await?;
+    let Gltf { document, blob } = Gltf::from_slice(&data)?;
+    let buffers = load_buffers(&document, blob, path.as_ref()).await?;
+    let parsing_time = now.elapsed().as_millis();
+
+    let now = Instant::now();
+    convert(
+        &document,
+        buffers,
+        resource_manager,
+        scene,
+        path.as_ref(),
+        model_import_options,
+    )
+    .await?;
+    let conversion_time = now.elapsed().as_millis();
+
+    Log::writeln(
+        MessageKind::Information,
+        format!(
+            "glTF {:?} loaded in {} ms\n\t- Parsing - {} ms\n\t- Conversion - {} ms",
+            path.as_ref(),
+            start_time.elapsed().as_millis(),
+            parsing_time,
+            conversion_time
+        ),
+    );
+
+    // Animations are retargeted by node names, so names must be unique.
+    let mut names = FxHashSet::<String>::default();
+    for node in scene.graph.linear_iter() {
+        if !names.insert(node.name_owned()) {
+            Log::writeln(
+                MessageKind::Warning,
+                format!(
+                    "A node with existing name {} was found during the load of {} resource! \
+                    Animations of such nodes won't be retargeted correctly.",
+                    node.name(),
+                    path.as_ref().display()
+                ),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{algebra::Vector4, futures::executor::block_on, sstorage::ImmutableString},
+        engine::{resource_manager::ResourceManager, SerializationContext},
+        material::PropertyValue,
+        resource::{gltf::load_to_scene, model::ModelImportOptions, texture::Texture},
+        scene::{
+            mesh::{
+                buffer::{VertexAttributeUsage, VertexReadTrait},
+                Mesh,
+            },
+            Scene,
+        },
+    };
+    use image::{DynamicImage, ImageOutputFormat, RgbaImage};
+    use std::{
+        io::Cursor,
+        path::{Path, PathBuf},
+        sync::Arc,
+    };
+
+    /// Size of the geometry, skin and animation data in the buffer, it is followed by an image
+    /// in the binary variant.
+    const GEOMETRY_SIZE: usize = 268;
+
+    fn extend_f32(buffer: &mut Vec<u8>, values: &[f32]) {
+        for value in values {
+            buffer.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+
+    /// Single skinned triangle bound to two joints (the second one is animated), with a textured
+    /// material.
+    fn make_buffer() -> Vec<u8> {
+        let mut buffer = Vec::new();
+        // Positions.
+        extend_f32(&mut buffer, &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0]);
+        // Joints.
+        for joints in [[0u16, 1, 0, 0], [0, 0, 0, 0], [1, 0, 0, 0]] {
+            for joint in joints {
+                buffer.extend_from_slice(&joint.to_le_bytes());
+            }
+        }
+        // Weights.
+        extend_f32(
+            &mut buffer,
+            &[0.25, 0.75, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0],
+        );
+        // Inverse bind matrices.
+        for _ in 0..2 {
+            extend_f32(
+                &mut buffer,
+                &[
+                    1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0,
+                ],
+            );
+        }
+        // Animation times and translations.
+        extend_f32(&mut buffer, &[0.0, 1.0]);
+        extend_f32(&mut buffer, &[0.0, 0.0, 0.0, 0.0, 1.0, 0.0]);
+        assert_eq!(buffer.len(), GEOMETRY_SIZE);
+        buffer
+    }
+
+    fn make_png() -> Vec<u8> {
+        let image = DynamicImage::ImageRgba8(RgbaImage::from_pixel(2, 2, [255, 0, 0, 255].into()));
+        let mut data = Cursor::new(Vec::new());
+        image.write_to(&mut data, ImageOutputFormat::Png).unwrap();
+        data.into_inner()
+    }
+
+    fn make_json(buffer: &str, image: &str, extra_views: &str) -> String {
+        format!(
+            r#"{{
+    "asset": {{ "version": "2.0" }},
+    "scene": 0,
+    "scenes": [{{ "nodes": [0, 1] }}],
+    "nodes": [
+        {{ "name": "Body", "mesh": 0, "skin": 0 }},
+        {{ "name": "Root", "children": [2] }},
+        {{ "name": "Bone", "translation": [0.0, 1.0, 0.0] }}
+    ],
+    "meshes": [{{
+        "primitives": [{{
+            "attributes": {{ "POSITION": 0, "JOINTS_0": 1, "WEIGHTS_0": 2 }},
+            "material": 0
+        }}]
+    }}],
+    "skins": [{{ "joints": [1, 2], "inverseBindMatrices": 3 }}],
+    "animations": [{{
+        "channels": [{{ "sampler": 0, "target": {{ "node": 2, "path": "translation" }} }}],
+        "samplers": [{{ "input": 4, "output": 5, "interpolation": "LINEAR" }}]
+    }}],
+    "materials": [{{
+        "pbrMetallicRoughness": {{
+            "baseColorTexture": {{ "index": 0 }},
+            "metallicFactor": 0.0,
+            "roughnessFactor": 0.5
+        }}
+    }}],
+    "textures": [{{ "source": 0 }}],
+    "images": [{image}],
+    "buffers": [{buffer}],
+    "bufferViews": [
+        {{ "buffer": 0, "byteOffset": 0, "byteLength": 36 }},
+        {{ "buffer": 0, "byteOffset": 36, "byteLength": 24 }},
+        {{ "buffer": 0, "byteOffset": 60, "byteLength": 48 }},
+        {{ "buffer": 0, "byteOffset": 108, "byteLength": 128 }},
+        {{ "buffer": 0, "byteOffset": 236, "byteLength": 8 }},
+        {{ "buffer": 0, "byteOffset": 244, "byteLength": 24 }}{extra_views}
+    ],
+    "accessors": [
+        {{ "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3",
+           "min": [0.0, 0.0, 0.0], "max": [1.0, 1.0, 0.0] }},
+        {{ "bufferView": 1, "componentType": 5123, "count": 3, "type": "VEC4" }},
+        {{ "bufferView": 2, "componentType": 5126, "count": 3, "type": "VEC4" }},
+        {{ "bufferView": 3, "componentType": 5126, "count": 2, "type": "MAT4" }},
+        {{ "bufferView": 4, "componentType": 5126, "count": 2, "type": "SCALAR",
+           "min": [0.0], "max": [1.0] }},
+        {{ "bufferView": 5, "componentType": 5126, "count": 2, "type": "VEC3" }}
+    ]
+}}"#,
+            buffer = buffer,
+            image = image,
+            extra_views = extra_views
+        )
+    }
+
+    fn test_directory(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join("fyrox_gltf_test").join(name);
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        path
+    }
+
+    fn write_gltf(directory: &Path) -> PathBuf {
+        let buffer = format!(
+            r#"{{ "byteLength": {}, "uri": "data:application/octet-stream;base64,{}" }}"#,
+            GEOMETRY_SIZE,
+            base64::encode(make_buffer())
+        );
+        let image = format!(
+            r#"{{ "uri": "data:image/png;base64,{}" }}"#,
+            base64::encode(make_png())
+        );
+        let path = directory.join("model.gltf");
+        std::fs::write(&path, make_json(&buffer, &image, "")).unwrap();
+        path
+    }
+
+    fn write_glb(directory: &Path) -> PathBuf {
+        let png = make_png();
+        let mut bin = make_buffer();
+        bin.extend_from_slice(&png);
+        while bin.len() % 4 != 0 {
+            bin.push(0);
+        }
+
+        let buffer = format!(r#"{{ "byteLength": {} }}"#, bin.len());
+        let view = format!(
+            r#",
+        {{ "buffer": 0, "byteOffset": {}, "byteLength": {} }}"#,
+            GEOMETRY_SIZE,
+            png.len()
+        );
+        let mut json = make_json(
+            &buffer,
+            r#"{ "bufferView": 6, "mimeType": "image/png" }"#,
+            &view,
+        )
+        .into_bytes();
+        while json.len() % 4 != 0 {
+            json.push(b' ');
+        }
+
+        let mut glb = Vec::new();
+        glb.extend_from_slice(b"glTF");
+        glb.extend_from_slice(&2u32.to_le_bytes());
+        glb.extend_from_slice(&((12 + 8 + json.len() + 8 + bin.len()) as u32).to_le_bytes());
+        glb.extend_from_slice(&(json.len() as u32).to_le_bytes());
+        glb.extend_from_slice(b"JSON");
+        glb.extend_from_slice(&json);
+        glb.extend_from_slice(&(bin.len() as u32).to_le_bytes());
+        glb.extend_from_slice(b"BIN\0");
+        glb.extend_from_slice(&bin);
+
+        let path = directory.join("model.glb");
+        std::fs::write(&path, glb).unwrap();
+        path
+    }
+
+    fn sampler(mesh: &Mesh, name: &str) -> Texture {
+        let material = mesh.surfaces()[0].material().lock();
+        match material.property_ref(&ImmutableString::new(name)) {
+            Some(PropertyValue::Sampler {
+                value: Some(texture),
+                ..
+            }) => texture.clone(),
+            _ => panic!("{} must be set!", name),
+        }
+    }
+
+    fn check_model(path: &Path, extract_embedded_textures: bool) {
+        let directory = path.parent().unwrap();
+        let resource_manager = ResourceManager::new(Arc::new(SerializationContext::new()));
+        let mut scene = Scene::new();
+        block_on(load_to_scene(
+            &mut scene,
+            resource_manager.clone(),
+            path,
+            &ModelImportOptions {
+                extract_embedded_textures,
+                ..Default::default()
+            },
+        ))
+        .unwrap();
+
+        // Root + 3 nodes of the model.
+        assert_eq!(scene.graph.node_count(), 4);
+
+        let body = scene.graph.find_by_name_from_root("Body");
+        let bone = scene.graph.find_by_name_from_root("Bone");
+        let root = scene.graph.find_by_name_from_root("Root");
+
+        let mesh = scene.graph[body].cast::<Mesh>().unwrap();
+        let surface = &mesh.surfaces()[0];
+        assert_eq!(surface.bones, vec![root, bone]);
+
+        let data = surface.data();
+        let data = data.lock();
+        let vertex = data.vertex_buffer.get(0).unwrap();
+        assert_eq!(
+            vertex.read_4_f32(VertexAttributeUsage::BoneWeight).unwrap(),
+            Vector4::new(0.25, 0.75, 0.0, 0.0)
+        );
+        assert_eq!(
+            vertex.read_4_u8(VertexAttributeUsage::BoneIndices).unwrap(),
+            Vector4::new(0, 1, 0, 0)
+        );
+
+        let animation = scene.animations.iter().next().unwrap();
+        assert_eq!(animation.get_tracks().len(), 1);
+        assert_eq!(animation.get_tracks()[0].get_node(), bone);
+
+        // Embedded and generated textures must be extracted next to the model only if it was
+        // requested, so they can be restored by the resource manager.
+        for (name, file) in [
+            ("diffuseTexture", "model_image0.png"),
+            ("roughnessTexture", "model_constant127.png"),
+        ] {
+            let texture = sampler(mesh, name);
+            let texture_path = texture.state().path().to_path_buf();
+            if extract_embedded_textures {
+                assert_eq!(texture_path, directory.join(file));
+                assert!(texture_path.exists());
+                assert_eq!(texture, resource_manager.request_texture(&texture_path));
+            } else {
+                assert_eq!(texture_path, PathBuf::default());
+                assert!(!directory.join(file).exists());
+            }
+            assert!(block_on(texture).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_load_gltf_with_embedded_data() {
+        check_model(&write_gltf(&test_directory("gltf")), false);
+        check_model(&write_gltf(&test_directory("gltf_extract")), true);
+    }
+
+    #[test]
+    fn test_load_glb() {
+        check_model(&write_glb(&test_directory("glb")), false);
+        check_model(&write_glb(&test_directory("glb_extract")), true);
+    }
+}
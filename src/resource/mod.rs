@@ -5,5 +5,7 @@
 pub mod absm;
 pub mod curve;
 pub mod fbx;
+pub mod gltf;
 pub mod model;
 pub mod texture;
+pub mod ui_scene;
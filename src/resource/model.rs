@@ -15,13 +15,14 @@
 //!
 //! # Supported formats
 //!
-//! Currently only FBX (common format in game industry for storing complex 3d models)
-//! and RGS (native Fyroxed format) formats are supported.
+//! Currently FBX (common format in game industry for storing complex 3d models), glTF 2.0
+//! (both `.gltf` and `.glb` variants) and RGS (native Fyroxed format) formats are supported.
 use crate::{
     animation::{Animation, AnimationContainer},
     asset::{define_new_resource, Resource, ResourceData},
     core::{
         inspect::{Inspect, PropertyInfo},
+        io,
         pool::Handle,
         reflect::Reflect,
         visitor::{Visit, VisitError, VisitResult, Visitor},
@@ -30,7 +31,10 @@ use crate::{
         resource_manager::{options::ImportOptions, ResourceManager},
         SerializationContext,
     },
-    resource::fbx::{self, error::FbxError},
+    resource::{
+        fbx::{self, error::FbxError},
+        gltf::{self, error::GltfError},
+    },
     scene::{
         graph::{map::NodeHandleMap, Graph},
         node::Node,
@@ -46,6 +50,7 @@ use std::{
     sync::Arc,
 };
 use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+use walkdir::WalkDir;
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 #[repr(u32)]
@@ -185,6 +190,34 @@ impl Model {
                 anim_copy.get_tracks_mut()[i].set_node(instance_node);
             }
 
+            // Do the same for property tracks.
+            for (i, ref_track) in ref_anim.property_tracks().iter().enumerate() {
+                let ref_node = &data.scene.graph[ref_track.node()];
+                let instance_node = graph.find_by_name(root, ref_node.name());
+                if instance_node.is_none() {
+                    Log::writeln(
+                        MessageKind::Error,
+                        format!(
+                            "Failed to retarget property track {} of animation {:?} for node {}",
+                            ref_track.property(),
+                            data.path(),
+                            ref_node.name()
+                        ),
+                    );
+                }
+                anim_copy.property_tracks_mut()[i].set_node(instance_node);
+            }
+
+            // Root node of root motion must be remapped too.
+            if let Some(settings) = anim_copy.root_motion_settings_mut() {
+                settings.node = data
+                    .scene
+                    .graph
+                    .try_get(settings.node)
+                    .map(|ref_node| graph.find_by_name(root, ref_node.name()))
+                    .unwrap_or_default();
+            }
+
             animation_handles.push(animations.add(anim_copy));
         }
 
@@ -241,7 +274,7 @@ impl Default for ModelData {
     }
 }
 
-/// Defines a way of searching materials when loading a model resource from foreign file format such as FBX.
+/// Defines a way of searching materials when loading a model resource from foreign file format such as FBX or glTF.
 ///
 /// # Motivation
 ///
@@ -313,6 +346,44 @@ impl MaterialSearchOptions {
     pub fn materials_directory<P: AsRef<Path>>(path: P) -> Self {
         Self::MaterialsDirectory(path.as_ref().to_path_buf())
     }
+
+    /// Tries to find a texture with the file name from `texture_path`, which was stored in a model
+    /// at `model_path`, using current search method. Returns `None` if no texture was found.
+    pub(crate) async fn find_texture(
+        &self,
+        model_path: &Path,
+        texture_path: &Path,
+    ) -> Option<PathBuf> {
+        let filename = texture_path.file_name()?;
+        match self {
+            MaterialSearchOptions::MaterialsDirectory(ref directory) => {
+                Some(directory.join(filename))
+            }
+            MaterialSearchOptions::RecursiveUp => {
+                let mut path = model_path.to_owned();
+                while let Some(parent) = path.parent() {
+                    let candidate = parent.join(filename);
+                    if io::exists(&candidate).await {
+                        return Some(candidate);
+                    }
+                    path.pop();
+                }
+                None
+            }
+            MaterialSearchOptions::WorkingDirectory => {
+                for dir in WalkDir::new(".").into_iter().flatten() {
+                    if dir.path().is_dir() {
+                        let candidate = dir.path().join(filename);
+                        if candidate.exists() {
+                            return Some(candidate);
+                        }
+                    }
+                }
+                None
+            }
+            MaterialSearchOptions::UsePathDirectly => Some(texture_path.to_owned()),
+        }
+    }
 }
 
 /// A set of options that will be applied to a model resource when loading it from external source.
@@ -335,6 +406,12 @@ pub struct ModelImportOptions {
     /// See [`MaterialSearchOptions`] docs for more info.
     #[serde(default)]
     pub material_search_options: MaterialSearchOptions,
+
+    /// Whether embedded and generated textures of a glTF model should be extracted in files next
+    /// to the model. Extracted textures have a path, so they will be restored when a scene with
+    /// the model is loaded, otherwise the textures are created in memory. Disabled by default.
+    #[serde(default)]
+    pub extract_embedded_textures: bool,
 }
 
 impl ImportOptions for ModelImportOptions {}
@@ -364,6 +441,9 @@ pub enum ModelLoadError {
     /// An error occurred while loading FBX file.
     #[error(transparent)]
     Fbx(FbxError),
+    /// An error occurred while loading glTF file.
+    #[error(transparent)]
+    Gltf(GltfError),
 }
 
 impl From<FbxError> for ModelLoadError {
@@ -372,6 +452,12 @@ impl From<FbxError> for ModelLoadError {
     }
 }
 
+impl From<GltfError> for ModelLoadError {
+    fn from(gltf: GltfError) -> Self {
+        ModelLoadError::Gltf(gltf)
+    }
+}
+
 impl From<VisitError> for ModelLoadError {
     fn from(e: VisitError) -> Self {
         ModelLoadError::Visit(e)
@@ -410,6 +496,23 @@ impl ModelData {
                 // any persistent unique ids, and we have to use names.
                 (scene, NodeMapping::UseNames)
             }
+            "gltf" | "glb" => {
+                let mut scene = Scene::new();
+                if let Some(filename) = path.as_ref().file_name() {
+                    let root = scene.graph.get_root();
+                    scene.graph[root].set_name(filename.to_string_lossy());
+                }
+                gltf::load_to_scene(
+                    &mut scene,
+                    resource_manager,
+                    path.as_ref(),
+                    &model_import_options,
+                )
+                .await?;
+                // glTF nodes are identified by indices, which are not stable across re-exports,
+                // so names are used as well.
+                (scene, NodeMapping::UseNames)
+            }
             // Scene can be used directly as model resource. Such scenes can be created in
             // Fyroxed.
             "rgs" => (
@@ -31,13 +31,14 @@ use crate::{
     },
     engine::resource_manager::options::ImportOptions,
 };
-use ddsfile::{Caps2, D3DFormat};
+use ddsfile::{Caps2, D3DFormat, DataFormat, Dds, NewD3dParams, PixelFormat, PixelFormatFlags};
 use fxhash::FxHasher;
 use image::{imageops::FilterType, ColorType, DynamicImage, ImageError, ImageFormat};
 use serde::{Deserialize, Serialize};
 use std::{
     borrow::Cow,
     fmt::{Debug, Formatter},
+    fs::File,
     hash::{Hash, Hasher},
     io::Cursor,
     ops::{Deref, DerefMut},
@@ -1062,6 +1063,8 @@ impl TextureData {
                     }
                     TexturePixelKind::RG16
                 }
+                D3DFormat::A8B8G8R8 => TexturePixelKind::RGBA8,
+                D3DFormat::A32B32G32R32F => TexturePixelKind::RGBA32F,
                 _ => return Err(TextureError::UnsupportedFormat),
             };
 
@@ -1101,82 +1104,89 @@ impl TextureData {
                 // check if the file is really contains expected data.
                 .or_else(|_| image::load_from_memory_with_format(data, ImageFormat::Tga))?;
 
-            let width = dyn_img.width();
-            let height = dyn_img.height();
-
-            let mut pixel_kind = match dyn_img {
-                DynamicImage::ImageLuma8(_) => TexturePixelKind::Luminance8,
-                DynamicImage::ImageLumaA8(_) => TexturePixelKind::LuminanceAlpha8,
-                DynamicImage::ImageRgb8(_) => TexturePixelKind::RGB8,
-                DynamicImage::ImageRgba8(_) => TexturePixelKind::RGBA8,
-                DynamicImage::ImageLuma16(_) => TexturePixelKind::Luminance16,
-                DynamicImage::ImageLumaA16(_) => TexturePixelKind::LuminanceAlpha16,
-                DynamicImage::ImageRgb16(_) => TexturePixelKind::RGB16,
-                DynamicImage::ImageRgba16(_) => TexturePixelKind::RGBA16,
-                DynamicImage::ImageRgb32F(_) => TexturePixelKind::RGB32F,
-                DynamicImage::ImageRgba32F(_) => TexturePixelKind::RGBA32F,
-                _ => unreachable!(),
-            };
+            Ok(Self::from_dynamic_image(dyn_img, compression, gen_mip_maps))
+        }
+    }
 
-            let mut mip_count = 0;
-            let mut bytes = Vec::new();
-
-            if gen_mip_maps {
-                let mut level_width = width;
-                let mut level_height = height;
-                let mut current_level = dyn_img;
-
-                while level_width != 0 && level_height != 0 {
-                    if mip_count != 0 {
-                        current_level = current_level.resize_exact(
-                            level_width,
-                            level_height,
-                            FilterType::Lanczos3,
-                        );
-                    }
+    /// Creates new rectangle texture from already decoded image, optionally compressing it and
+    /// generating mip maps.
+    pub(crate) fn from_dynamic_image(
+        dyn_img: DynamicImage,
+        compression: CompressionOptions,
+        gen_mip_maps: bool,
+    ) -> Self {
+        let width = dyn_img.width();
+        let height = dyn_img.height();
+
+        let mut pixel_kind = match dyn_img {
+            DynamicImage::ImageLuma8(_) => TexturePixelKind::Luminance8,
+            DynamicImage::ImageLumaA8(_) => TexturePixelKind::LuminanceAlpha8,
+            DynamicImage::ImageRgb8(_) => TexturePixelKind::RGB8,
+            DynamicImage::ImageRgba8(_) => TexturePixelKind::RGBA8,
+            DynamicImage::ImageLuma16(_) => TexturePixelKind::Luminance16,
+            DynamicImage::ImageLumaA16(_) => TexturePixelKind::LuminanceAlpha16,
+            DynamicImage::ImageRgb16(_) => TexturePixelKind::RGB16,
+            DynamicImage::ImageRgba16(_) => TexturePixelKind::RGBA16,
+            DynamicImage::ImageRgb32F(_) => TexturePixelKind::RGB32F,
+            DynamicImage::ImageRgba32F(_) => TexturePixelKind::RGBA32F,
+            _ => unreachable!(),
+        };
 
-                    mip_count += 1;
-
-                    if compression == CompressionOptions::NoCompression {
-                        bytes.extend_from_slice(current_level.as_bytes())
-                    } else if let Some((compressed_data, new_pixel_kind)) = try_compress(
-                        &current_level,
-                        level_width as usize,
-                        level_height as usize,
-                        compression,
-                    ) {
-                        pixel_kind = new_pixel_kind;
-                        bytes.extend_from_slice(&compressed_data);
-                    } else {
-                        bytes.extend_from_slice(current_level.as_bytes())
-                    }
+        let mut mip_count = 0;
+        let mut bytes = Vec::new();
 
-                    level_width = level_width.checked_shr(1).unwrap_or_default();
-                    level_height = level_height.checked_shr(1).unwrap_or_default();
+        if gen_mip_maps {
+            let mut level_width = width;
+            let mut level_height = height;
+            let mut current_level = dyn_img;
+
+            while level_width != 0 && level_height != 0 {
+                if mip_count != 0 {
+                    current_level =
+                        current_level.resize_exact(level_width, level_height, FilterType::Lanczos3);
                 }
-            } else {
-                mip_count = 1;
+
+                mip_count += 1;
 
                 if compression == CompressionOptions::NoCompression {
-                    bytes.extend_from_slice(dyn_img.as_bytes());
-                } else if let Some((compressed_data, new_pixel_kind)) =
-                    try_compress(&dyn_img, width as usize, height as usize, compression)
-                {
+                    bytes.extend_from_slice(current_level.as_bytes())
+                } else if let Some((compressed_data, new_pixel_kind)) = try_compress(
+                    &current_level,
+                    level_width as usize,
+                    level_height as usize,
+                    compression,
+                ) {
                     pixel_kind = new_pixel_kind;
                     bytes.extend_from_slice(&compressed_data);
                 } else {
-                    bytes.extend_from_slice(dyn_img.as_bytes())
+                    bytes.extend_from_slice(current_level.as_bytes())
                 }
+
+                level_width = level_width.checked_shr(1).unwrap_or_default();
+                level_height = level_height.checked_shr(1).unwrap_or_default();
             }
+        } else {
+            mip_count = 1;
+
+            if compression == CompressionOptions::NoCompression {
+                bytes.extend_from_slice(dyn_img.as_bytes());
+            } else if let Some((compressed_data, new_pixel_kind)) =
+                try_compress(&dyn_img, width as usize, height as usize, compression)
+            {
+                pixel_kind = new_pixel_kind;
+                bytes.extend_from_slice(&compressed_data);
+            } else {
+                bytes.extend_from_slice(dyn_img.as_bytes())
+            }
+        }
 
-            Ok(Self {
-                pixel_kind,
-                kind: TextureKind::Rectangle { width, height },
-                data_hash: data_hash(&bytes),
-                bytes: bytes.into(),
-                mip_count,
-                ..Default::default()
-            })
+        Self {
+            pixel_kind,
+            kind: TextureKind::Rectangle { width, height },
+            data_hash: data_hash(&bytes),
+            bytes: bytes.into(),
+            mip_count,
+            ..Default::default()
         }
     }
 
@@ -1327,8 +1337,18 @@ impl TextureData {
         self.path = path.as_ref().to_owned();
     }
 
-    /// Tries to save internal buffer into source file.
+    /// Tries to save internal buffer into source file. Format of the file is defined by the
+    /// extension of the path. Cube and volume textures, as well as textures with mip maps, can be
+    /// saved only in DDS format, use `.dds` extension for them.
     pub fn save(&self) -> Result<(), TextureError> {
+        if self
+            .path
+            .extension()
+            .map_or(false, |ext| ext.eq_ignore_ascii_case("dds"))
+        {
+            return self.save_dds();
+        }
+
         let color_type = match self.pixel_kind {
             TexturePixelKind::R8 => ColorType::L8,
             TexturePixelKind::Luminance8 => ColorType::L8,
@@ -1366,6 +1386,64 @@ impl TextureData {
         }
     }
 
+    fn save_dds(&self) -> Result<(), TextureError> {
+        let format = match self.pixel_kind {
+            TexturePixelKind::R8 | TexturePixelKind::Luminance8 => D3DFormat::L8,
+            TexturePixelKind::R16 | TexturePixelKind::Luminance16 => D3DFormat::L16,
+            TexturePixelKind::RGB8 => D3DFormat::R8G8B8,
+            TexturePixelKind::RGBA8 => D3DFormat::A8B8G8R8,
+            TexturePixelKind::RGBA32F => D3DFormat::A32B32G32R32F,
+            TexturePixelKind::DXT1RGB | TexturePixelKind::DXT1RGBA => D3DFormat::DXT1,
+            TexturePixelKind::DXT3RGBA => D3DFormat::DXT3,
+            TexturePixelKind::DXT5RGBA => D3DFormat::DXT5,
+            _ => return Err(TextureError::UnsupportedFormat),
+        };
+
+        let (width, height, depth, caps2) = match self.kind {
+            TextureKind::Line { length } => (length, 1, None, None),
+            TextureKind::Rectangle { width, height } => (width, height, None, None),
+            TextureKind::Cube { width, height } => (
+                width,
+                height,
+                None,
+                Some(Caps2::CUBEMAP | Caps2::CUBEMAP_ALLFACES),
+            ),
+            TextureKind::Volume {
+                width,
+                height,
+                depth,
+            } => (width, height, Some(depth), Some(Caps2::VOLUME)),
+        };
+
+        let mut dds = Dds::new_d3d(NewD3dParams {
+            height,
+            width,
+            depth,
+            format,
+            mipmap_levels: Some(self.mip_count),
+            caps2,
+        })
+        .map_err(|_| TextureError::UnsupportedFormat)?;
+        // Formats with FourCC code (such as float formats) are described by bit masks by
+        // ddsfile, which makes them unreadable. Force the code.
+        if let Some(fourcc) = format.get_fourcc() {
+            dds.header.spf = PixelFormat {
+                flags: PixelFormatFlags::FOURCC,
+                fourcc: Some(fourcc),
+                ..Default::default()
+            };
+        }
+        // Layout of the data is the same - faces (or slices) one after another, each with its
+        // mip chain.
+        dds.data = self.bytes.to_vec();
+
+        let mut file = File::create(&self.path)?;
+        dds.write(&mut file).map_err(|e| match e {
+            ddsfile::Error::Io(e) => TextureError::Io(e),
+            _ => TextureError::UnsupportedFormat,
+        })
+    }
+
     /// Returns a special reference holder that provides mutable access to content of the
     /// texture and automatically calculates hash of the data in its destructor.
     pub fn modify(&mut self) -> TextureDataRefMut<'_> {
@@ -1408,7 +1486,9 @@ impl<'a> TextureDataRefMut<'a> {
 
 #[cfg(test)]
 pub mod test {
-    use crate::resource::texture::{Texture, TextureKind, TexturePixelKind};
+    use crate::resource::texture::{
+        CompressionOptions, Texture, TextureData, TextureKind, TexturePixelKind,
+    };
 
     pub fn create_test_texture() -> Texture {
         Texture::from_bytes(
@@ -1422,4 +1502,43 @@ pub mod test {
         )
         .unwrap()
     }
+
+    #[test]
+    fn test_save_cube_texture_as_dds() {
+        let bytes = (0..6 * 2 * 2 * 4)
+            .flat_map(|i| (i as f32).to_le_bytes())
+            .collect::<Vec<_>>();
+
+        let mut texture = TextureData::from_bytes(
+            TextureKind::Cube {
+                width: 2,
+                height: 2,
+            },
+            TexturePixelKind::RGBA32F,
+            bytes.clone(),
+            false,
+        )
+        .unwrap();
+        let path = std::env::temp_dir().join("fyrox_test_cube_texture.dds");
+        texture.set_path(&path);
+        texture.save().unwrap();
+
+        let loaded = TextureData::load_from_memory(
+            &std::fs::read(&path).unwrap(),
+            CompressionOptions::NoCompression,
+            false,
+        )
+        .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(
+            loaded.kind(),
+            TextureKind::Cube {
+                width: 2,
+                height: 2
+            }
+        ));
+        assert_eq!(loaded.pixel_kind(), TexturePixelKind::RGBA32F);
+        assert_eq!(loaded.data(), bytes.as_slice());
+    }
 }
@@ -0,0 +1,126 @@
+//! UI scene resource holds a [`UiSceneSource`] that could be instantiated in a user interface
+//! multiple times. See [`crate::gui::scene`] module docs for more info about UI scenes.
+
+use crate::{
+    asset::{define_new_resource, Resource, ResourceData},
+    core::{pool::Handle, reflect::Reflect, visitor::prelude::*},
+    engine::resource_manager::options::ImportOptions,
+    gui::{
+        scene::{SerializationContext, UiSceneError, UiSceneSource},
+        UiNode, UserInterface,
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Cow,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// State of the [`UiSceneResource`]
+#[derive(Debug, Visit, Default)]
+pub struct UiSceneResourceState {
+    pub(crate) path: PathBuf,
+    /// Contents of the scene file with all fonts loaded.
+    #[visit(skip)]
+    pub source: UiSceneSource,
+}
+
+impl ResourceData for UiSceneResourceState {
+    fn path(&self) -> Cow<Path> {
+        Cow::Borrowed(&self.path)
+    }
+
+    fn set_path(&mut self, path: PathBuf) {
+        self.path = path;
+    }
+}
+
+impl UiSceneResourceState {
+    /// Load a UI scene resource from the specific file path.
+    pub async fn from_file(path: &Path) -> Result<Self, UiSceneError> {
+        Ok(Self {
+            source: UiSceneSource::from_file(path).await?,
+            path: path.to_path_buf(),
+        })
+    }
+}
+
+define_new_resource!(
+    /// See module docs.
+    #[derive(Reflect)]
+    #[reflect(hide_all)]
+    UiSceneResource<UiSceneResourceState, UiSceneError>
+);
+
+impl UiSceneResource {
+    /// Creates a new copy of the widgets of the scene in the given user interface and returns
+    /// a handle of the root widget. Use [`crate::engine::Engine::ui_serialization_context`] to
+    /// get the context for the engine's user interface.
+    ///
+    /// # Important notes
+    ///
+    /// Instances do not track their resource, reloading the resource affects only new instances.
+    pub fn instantiate(
+        &self,
+        ui: &mut UserInterface,
+        context: Arc<SerializationContext>,
+    ) -> Result<Handle<UiNode>, UiSceneError> {
+        let scene = self.data_ref().source.to_scene(context)?;
+        Ok(scene.instantiate(ui))
+    }
+}
+
+/// Import options for UI scene resource.
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct UiSceneImportOptions {}
+
+impl ImportOptions for UiSceneImportOptions {}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{algebra::Vector2, futures::executor::block_on},
+        engine::{resource_manager::ResourceManager, SerializationContext},
+        gui::{
+            scene::{self, UiScene},
+            text::{Text, TextBuilder},
+            widget::WidgetBuilder,
+            UserInterface,
+        },
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn test_ui_scene_resource() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        let text = TextBuilder::new(WidgetBuilder::new())
+            .with_text("Hello")
+            .build(&mut ui.build_ctx());
+
+        let context = Arc::new(scene::SerializationContext::new(ui.default_font.clone()));
+
+        let path = std::env::temp_dir().join("fyrox_ui_scene_resource_test.uis");
+        UiScene::from_ui(&ui, text)
+            .save(&path, context.clone())
+            .unwrap();
+
+        let resource_manager = ResourceManager::new(Arc::new(SerializationContext::new()));
+        let resource = resource_manager.request_ui_scene(&path);
+        assert_eq!(resource, resource_manager.request_ui_scene(&path));
+
+        block_on(resource.clone()).unwrap();
+
+        let mut other_ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        for _ in 0..2 {
+            let root = resource
+                .instantiate(&mut other_ui, context.clone())
+                .unwrap();
+            let text = other_ui
+                .node(root)
+                .cast::<Text>()
+                .expect("Root must be a text!");
+            assert_eq!(text.text(), "Hello");
+        }
+    }
+}
@@ -292,6 +292,16 @@ pub struct Camera {
     #[reflect(hidden)]
     projection_matrix: Matrix4<f32>,
 
+    #[visit(skip)]
+    #[inspect(skip)]
+    #[reflect(hidden)]
+    unjittered_projection_matrix: Matrix4<f32>,
+
+    #[visit(skip)]
+    #[inspect(skip)]
+    #[reflect(hidden)]
+    jitter: Vector2<f32>,
+
     /// Visibility cache allows you to quickly check if object is visible from the camera or not.
     #[visit(skip)]
     #[inspect(skip)]
@@ -335,7 +345,30 @@ impl Camera {
         let up = self.base.up_vector();
 
         self.view_matrix = Matrix4::look_at_rh(&Point3::from(pos), &Point3::from(pos + look), &up);
-        self.projection_matrix = self.projection.matrix(frame_size);
+        self.unjittered_projection_matrix = self.projection.matrix(frame_size);
+
+        // Jitter is defined in pixels, convert it to normalized device coordinates.
+        let viewport = self.viewport_pixels(frame_size);
+        let ndc_jitter = Vector3::new(
+            2.0 * self.jitter.x / viewport.w() as f32,
+            2.0 * self.jitter.y / viewport.h() as f32,
+            0.0,
+        );
+        self.projection_matrix =
+            Matrix4::new_translation(&ndc_jitter) * self.unjittered_projection_matrix;
+    }
+
+    /// Sets sub-pixel offset (in pixels) of the projection matrix. It is used by temporal
+    /// anti-aliasing to sample different parts of pixels each frame. Normally, you should not
+    /// call this method, the engine sets the jitter automatically when TAA is enabled in
+    /// [`crate::renderer::QualitySettings`].
+    pub fn set_jitter(&mut self, jitter: Vector2<f32>) {
+        self.jitter = jitter;
+    }
+
+    /// Returns current sub-pixel offset (in pixels) of the projection matrix.
+    pub fn jitter(&self) -> Vector2<f32> {
+        self.jitter
     }
 
     /// Sets new viewport in resolution-independent format. In other words
@@ -389,6 +422,12 @@ impl Camera {
         self.projection_matrix
     }
 
+    /// Returns current view-projection matrix without sub-pixel jitter.
+    #[inline]
+    pub fn unjittered_view_projection_matrix(&self) -> Matrix4<f32> {
+        self.unjittered_projection_matrix * self.view_matrix
+    }
+
     /// Returns current view matrix.
     #[inline]
     pub fn view_matrix(&self) -> Matrix4<f32> {
@@ -890,6 +929,8 @@ impl CameraBuilder {
             // recalculated before rendering.
             view_matrix: Matrix4::identity(),
             projection_matrix: Matrix4::identity(),
+            unjittered_projection_matrix: Matrix4::identity(),
+            jitter: Default::default(),
             visibility_cache: Default::default(),
             sky_box: self.skybox.into(),
             environment: self.environment.into(),
@@ -0,0 +1,578 @@
+//! Character controller is a kinematic capsule that moves through the physics world, slides along
+//! walls, climbs slopes and steps, snaps to the ground and rides moving platforms.
+//!
+//! For more info see [`CharacterController`].
+
+use crate::{
+    core::{
+        algebra::{Isometry3, Matrix4, Point3, Vector3},
+        inspect::{Inspect, PropertyInfo},
+        math::{aabb::AxisAlignedBoundingBox, Matrix4Ext},
+        pool::Handle,
+        reflect::Reflect,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+    },
+    engine::resource_manager::ResourceManager,
+    scene::{
+        base::{Base, BaseBuilder},
+        collider::InteractionGroups,
+        graph::{
+            physics::{CharacterMoveParams, PhysicsWorld},
+            Graph, NodePool,
+        },
+        node::{Node, NodeTrait, TypeUuidProvider, UpdateContext},
+    },
+};
+use fxhash::FxHashSet;
+use rapier3d::parry::shape::Capsule;
+use std::ops::{Deref, DerefMut};
+
+/// Character controller is a kinematic capsule that moves through the physics world using shape
+/// casts. It slides along walls, climbs slopes that are not steeper than
+/// [`CharacterController::max_slope_angle`], climbs steps that are not higher than
+/// [`CharacterController::max_step_height`], snaps to the ground when walking down the slopes and
+/// stairs and moves together with the platform it is standing on.
+///
+/// Position of the controller node is the center of the capsule, the capsule is always aligned
+/// with world Y axis. The controller moves itself according to its velocity on every update, the
+/// velocity is affected by gravity when the controller is not on the ground.
+///
+/// # Interaction with rigid bodies
+///
+/// The controller itself is not a part of the physics simulation, other bodies do not collide with
+/// it. If you need dynamic bodies to be pushed by the character, add a kinematic rigid body with a
+/// collider as a child of the controller. Colliders that are descendants of the controller are
+/// ignored by the controller.
+///
+/// # Example
+///
+/// ```rust
+/// # use fyrox::{
+/// #     core::algebra::Vector3,
+/// #     scene::character_controller::CharacterController,
+/// # };
+/// fn move_character(controller: &mut CharacterController, direction: Vector3<f32>, jump: bool) {
+///     let mut velocity = controller.velocity();
+///     velocity.x = direction.x * 3.0;
+///     velocity.z = direction.z * 3.0;
+///     if jump && controller.is_grounded() {
+///         velocity.y = 4.0;
+///     }
+///     controller.set_velocity(velocity);
+/// }
+/// ```
+#[derive(Visit, Inspect, Reflect, Debug)]
+pub struct CharacterController {
+    base: Base,
+
+    #[inspect(min_value = 0.0, step = 0.05, description = "Radius of the capsule.")]
+    #[reflect(setter = "set_radius")]
+    radius: InheritableVariable<f32>,
+
+    #[inspect(
+        min_value = 0.0,
+        step = 0.05,
+        description = "Total height of the capsule, including the caps."
+    )]
+    #[reflect(setter = "set_height")]
+    height: InheritableVariable<f32>,
+
+    #[inspect(
+        min_value = 0.0,
+        step = 0.05,
+        description = "Maximum angle (in radians) of a slope that can be climbed."
+    )]
+    #[reflect(setter = "set_max_slope_angle")]
+    max_slope_angle: InheritableVariable<f32>,
+
+    #[inspect(
+        min_value = 0.0,
+        step = 0.05,
+        description = "Maximum height of a step that can be climbed."
+    )]
+    #[reflect(setter = "set_max_step_height")]
+    max_step_height: InheritableVariable<f32>,
+
+    #[inspect(
+        min_value = 0.0,
+        step = 0.05,
+        description = "Maximum distance to the ground at which the controller will be snapped to it."
+    )]
+    #[reflect(setter = "set_snap_to_ground_distance")]
+    snap_to_ground_distance: InheritableVariable<f32>,
+
+    #[inspect(
+        min_value = 0.0,
+        step = 0.005,
+        description = "Small gap between the capsule and obstacles, it prevents the capsule from getting stuck."
+    )]
+    #[reflect(setter = "set_offset")]
+    offset: InheritableVariable<f32>,
+
+    #[inspect(
+        step = 0.05,
+        description = "Scale of the gravity of the physics world."
+    )]
+    #[reflect(setter = "set_gravity_scale")]
+    gravity_scale: InheritableVariable<f32>,
+
+    #[inspect(description = "Defines which colliders the controller collides with.")]
+    #[reflect(setter = "set_collision_groups")]
+    collision_groups: InheritableVariable<InteractionGroups>,
+
+    #[inspect(description = "Current velocity of the controller.")]
+    #[reflect(setter = "set_velocity")]
+    velocity: InheritableVariable<Vector3<f32>>,
+
+    #[visit(skip)]
+    #[inspect(skip)]
+    #[reflect(hidden)]
+    grounded: bool,
+
+    #[visit(skip)]
+    #[inspect(skip)]
+    #[reflect(hidden)]
+    ground_normal: Vector3<f32>,
+
+    #[visit(skip)]
+    #[inspect(skip)]
+    #[reflect(hidden)]
+    ground_collider: Handle<Node>,
+
+    #[visit(skip)]
+    #[inspect(skip)]
+    #[reflect(hidden)]
+    platform_position: Option<Isometry3<f32>>,
+}
+
+impl Default for CharacterController {
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            radius: InheritableVariable::new(0.3),
+            height: InheritableVariable::new(1.8),
+            max_slope_angle: InheritableVariable::new(45.0f32.to_radians()),
+            max_step_height: InheritableVariable::new(0.3),
+            snap_to_ground_distance: InheritableVariable::new(0.2),
+            offset: InheritableVariable::new(0.01),
+            gravity_scale: InheritableVariable::new(1.0),
+            collision_groups: Default::default(),
+            velocity: Default::default(),
+            grounded: false,
+            ground_normal: Vector3::y(),
+            ground_collider: Default::default(),
+            platform_position: None,
+        }
+    }
+}
+
+impl Clone for CharacterController {
+    fn clone(&self) -> Self {
+        Self {
+            base: self.base.clone(),
+            radius: self.radius.clone(),
+            height: self.height.clone(),
+            max_slope_angle: self.max_slope_angle.clone(),
+            max_step_height: self.max_step_height.clone(),
+            snap_to_ground_distance: self.snap_to_ground_distance.clone(),
+            offset: self.offset.clone(),
+            gravity_scale: self.gravity_scale.clone(),
+            collision_groups: self.collision_groups.clone(),
+            velocity: self.velocity.clone(),
+            // Do not copy.
+            grounded: false,
+            ground_normal: Vector3::y(),
+            ground_collider: Default::default(),
+            platform_position: None,
+        }
+    }
+}
+
+impl Deref for CharacterController {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for CharacterController {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl TypeUuidProvider for CharacterController {
+    fn type_uuid() -> Uuid {
+        uuid!("9d0b5a2e-1b8f-4a34-8a59-0f3cf5d0ab6e")
+    }
+}
+
+impl CharacterController {
+    /// Sets new radius of the capsule.
+    pub fn set_radius(&mut self, radius: f32) -> f32 {
+        self.radius.set(radius.max(0.0))
+    }
+
+    /// Returns current radius of the capsule.
+    pub fn radius(&self) -> f32 {
+        *self.radius
+    }
+
+    /// Sets new total height of the capsule. The height cannot be less than the diameter of the
+    /// capsule, in this case the capsule becomes a sphere.
+    pub fn set_height(&mut self, height: f32) -> f32 {
+        self.height.set(height.max(0.0))
+    }
+
+    /// Returns current total height of the capsule.
+    pub fn height(&self) -> f32 {
+        *self.height
+    }
+
+    /// Sets maximum angle (in radians) of a slope that can be climbed. Steeper slopes act as walls
+    /// and the controller slides down from them.
+    pub fn set_max_slope_angle(&mut self, angle: f32) -> f32 {
+        self.max_slope_angle.set(angle)
+    }
+
+    /// Returns maximum angle (in radians) of a slope that can be climbed.
+    pub fn max_slope_angle(&self) -> f32 {
+        *self.max_slope_angle
+    }
+
+    /// Sets maximum height of a step that can be climbed. Zero disables stair stepping.
+    pub fn set_max_step_height(&mut self, height: f32) -> f32 {
+        self.max_step_height.set(height.max(0.0))
+    }
+
+    /// Returns maximum height of a step that can be climbed.
+    pub fn max_step_height(&self) -> f32 {
+        *self.max_step_height
+    }
+
+    /// Sets maximum distance to the ground at which the controller that was on the ground will be
+    /// snapped to it. It keeps the controller on the ground while it walks down the slopes and
+    /// stairs. Zero disables ground snapping.
+    pub fn set_snap_to_ground_distance(&mut self, distance: f32) -> f32 {
+        self.snap_to_ground_distance.set(distance.max(0.0))
+    }
+
+    /// Returns maximum distance of ground snapping.
+    pub fn snap_to_ground_distance(&self) -> f32 {
+        *self.snap_to_ground_distance
+    }
+
+    /// Sets a small gap that will be kept between the capsule and obstacles.
+    pub fn set_offset(&mut self, offset: f32) -> f32 {
+        self.offset.set(offset.max(0.0))
+    }
+
+    /// Returns current gap between the capsule and obstacles.
+    pub fn offset(&self) -> f32 {
+        *self.offset
+    }
+
+    /// Sets scale of the gravity of the physics world. Zero disables gravity.
+    pub fn set_gravity_scale(&mut self, scale: f32) -> f32 {
+        self.gravity_scale.set(scale)
+    }
+
+    /// Returns current scale of the gravity.
+    pub fn gravity_scale(&self) -> f32 {
+        *self.gravity_scale
+    }
+
+    /// Sets new collision groups, they define which colliders the controller collides with.
+    pub fn set_collision_groups(&mut self, groups: InteractionGroups) -> InteractionGroups {
+        self.collision_groups.set(groups)
+    }
+
+    /// Returns current collision groups.
+    pub fn collision_groups(&self) -> InteractionGroups {
+        *self.collision_groups
+    }
+
+    /// Sets new velocity of the controller. Vertical component of the velocity is reset when the
+    /// controller lands on the ground or hits a ceiling.
+    pub fn set_velocity(&mut self, velocity: Vector3<f32>) -> Vector3<f32> {
+        self.velocity.set(velocity)
+    }
+
+    /// Returns current velocity of the controller.
+    pub fn velocity(&self) -> Vector3<f32> {
+        *self.velocity
+    }
+
+    /// Returns true if the controller is standing on a walkable surface.
+    pub fn is_grounded(&self) -> bool {
+        self.grounded
+    }
+
+    /// Returns a normal of the surface the controller is standing on. It is world up vector if the
+    /// controller is in the air.
+    pub fn ground_normal(&self) -> Vector3<f32> {
+        self.ground_normal
+    }
+
+    /// Returns a handle of the collider the controller is standing on.
+    pub fn ground_collider(&self) -> Handle<Node> {
+        self.ground_collider
+    }
+
+    fn move_self(&mut self, nodes: &NodePool, physics: &mut PhysicsWorld, dt: f32) {
+        let parent_transform = nodes
+            .try_borrow(self.parent())
+            .map(|p| p.global_transform())
+            .unwrap_or_else(Matrix4::identity);
+        let mut position = (parent_transform * self.local_transform().matrix()).position();
+
+        // Ride the platform.
+        if let Some(last_platform_position) = self.platform_position {
+            if let Some(platform_position) = physics.collider_body_position(self.ground_collider) {
+                position = (platform_position * last_platform_position.inverse())
+                    .transform_point(&Point3::from(position))
+                    .coords;
+            }
+        }
+
+        let up = Vector3::y();
+        let mut velocity = *self.velocity;
+        if !self.grounded {
+            velocity += physics.gravity.scale(*self.gravity_scale * dt);
+        }
+        let translation = velocity.scale(dt);
+
+        // Ignore colliders of the controller itself.
+        let mut own_nodes = FxHashSet::default();
+        let mut stack = self.children().to_vec();
+        while let Some(handle) = stack.pop() {
+            if let Some(node) = nodes.try_borrow(handle) {
+                own_nodes.insert(handle);
+                stack.extend_from_slice(node.children());
+            }
+        }
+
+        let radius = *self.radius;
+        let half_height = (*self.height * 0.5 - radius).max(0.0);
+        let result = physics.move_character(
+            &Capsule::new_y(half_height, radius),
+            position,
+            translation,
+            &CharacterMoveParams {
+                groups: *self.collision_groups,
+                max_slope_angle: *self.max_slope_angle,
+                max_step_height: *self.max_step_height,
+                snap_to_ground_distance: *self.snap_to_ground_distance,
+                offset: *self.offset,
+                was_grounded: self.grounded,
+            },
+            &|collider| own_nodes.contains(&collider),
+        );
+
+        let vertical_speed = velocity.dot(&up);
+        let vertical_displacement = (result.position - position).dot(&up);
+        if (result.grounded && vertical_speed < 0.0)
+            // Hit a ceiling.
+            || (vertical_speed > 0.0 && vertical_displacement < translation.dot(&up) * 0.5)
+        {
+            velocity -= up.scale(vertical_speed);
+        }
+        self.velocity.set_silent(velocity);
+
+        self.grounded = result.grounded;
+        self.ground_normal = result.ground_normal;
+        self.ground_collider = result.ground_collider;
+        self.platform_position = if result.grounded {
+            physics.collider_body_position(result.ground_collider)
+        } else {
+            None
+        };
+
+        let local_position = parent_transform
+            .try_inverse()
+            .unwrap_or_else(Matrix4::identity)
+            .transform_point(&Point3::from(result.position))
+            .coords;
+        self.local_transform_mut().set_position(local_position);
+    }
+}
+
+impl NodeTrait for CharacterController {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.local_bounding_box()
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.world_bounding_box()
+    }
+
+    fn restore_resources(&mut self, resource_manager: ResourceManager) {
+        self.base.restore_resources(resource_manager);
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn update(&mut self, context: &mut UpdateContext) -> bool {
+        if context.physics.enabled {
+            self.move_self(context.nodes, context.physics, context.dt);
+        }
+
+        self.base.update_lifetime(context.dt)
+    }
+}
+
+/// Allows you to create character controller in declarative manner.
+pub struct CharacterControllerBuilder {
+    base_builder: BaseBuilder,
+    radius: f32,
+    height: f32,
+    max_slope_angle: f32,
+    max_step_height: f32,
+    snap_to_ground_distance: f32,
+    offset: f32,
+    gravity_scale: f32,
+    collision_groups: InteractionGroups,
+    velocity: Vector3<f32>,
+}
+
+impl CharacterControllerBuilder {
+    /// Creates new character controller builder.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            radius: 0.3,
+            height: 1.8,
+            max_slope_angle: 45.0f32.to_radians(),
+            max_step_height: 0.3,
+            snap_to_ground_distance: 0.2,
+            offset: 0.01,
+            gravity_scale: 1.0,
+            collision_groups: Default::default(),
+            velocity: Default::default(),
+        }
+    }
+
+    /// Sets desired radius of the capsule.
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets desired total height of the capsule.
+    pub fn with_height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Sets desired maximum angle (in radians) of a slope that can be climbed.
+    pub fn with_max_slope_angle(mut self, angle: f32) -> Self {
+        self.max_slope_angle = angle;
+        self
+    }
+
+    /// Sets desired maximum height of a step that can be climbed.
+    pub fn with_max_step_height(mut self, height: f32) -> Self {
+        self.max_step_height = height;
+        self
+    }
+
+    /// Sets desired maximum distance of ground snapping.
+    pub fn with_snap_to_ground_distance(mut self, distance: f32) -> Self {
+        self.snap_to_ground_distance = distance;
+        self
+    }
+
+    /// Sets desired gap between the capsule and obstacles.
+    pub fn with_offset(mut self, offset: f32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Sets desired scale of the gravity.
+    pub fn with_gravity_scale(mut self, scale: f32) -> Self {
+        self.gravity_scale = scale;
+        self
+    }
+
+    /// Sets desired collision groups.
+    pub fn with_collision_groups(mut self, groups: InteractionGroups) -> Self {
+        self.collision_groups = groups;
+        self
+    }
+
+    /// Sets desired initial velocity.
+    pub fn with_velocity(mut self, velocity: Vector3<f32>) -> Self {
+        self.velocity = velocity;
+        self
+    }
+
+    /// Creates new character controller instance.
+    pub fn build_character_controller(self) -> CharacterController {
+        CharacterController {
+            base: self.base_builder.build_base(),
+            radius: self.radius.into(),
+            height: self.height.into(),
+            max_slope_angle: self.max_slope_angle.into(),
+            max_step_height: self.max_step_height.into(),
+            snap_to_ground_distance: self.snap_to_ground_distance.into(),
+            offset: self.offset.into(),
+            gravity_scale: self.gravity_scale.into(),
+            collision_groups: self.collision_groups.into(),
+            velocity: self.velocity.into(),
+            grounded: false,
+            ground_normal: Vector3::y(),
+            ground_collider: Default::default(),
+            platform_position: None,
+        }
+    }
+
+    /// Creates new character controller node.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_character_controller())
+    }
+
+    /// Creates new character controller node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::core::reflect::Reflect;
+    use crate::core::variable::try_inherit_properties;
+    use crate::scene::{
+        base::{test::check_inheritable_properties_equality, BaseBuilder},
+        character_controller::{CharacterController, CharacterControllerBuilder},
+    };
+
+    #[test]
+    fn test_character_controller_inheritance() {
+        let parent = CharacterControllerBuilder::new(BaseBuilder::new())
+            .with_radius(0.5)
+            .with_height(2.0)
+            .with_max_slope_angle(0.5)
+            .with_max_step_height(0.4)
+            .with_snap_to_ground_distance(0.1)
+            .with_offset(0.02)
+            .with_gravity_scale(2.0)
+            .build_node();
+
+        let mut child =
+            CharacterControllerBuilder::new(BaseBuilder::new()).build_character_controller();
+
+        try_inherit_properties(child.as_reflect_mut(), parent.as_reflect()).unwrap();
+
+        let parent = parent.cast::<CharacterController>().unwrap();
+
+        check_inheritable_properties_equality(&child.base, &parent.base);
+        check_inheritable_properties_equality(&child, parent);
+    }
+}
@@ -15,6 +15,7 @@ use crate::{
     scene::{
         base::{Base, BaseBuilder},
         graph::Graph,
+        joint::{JointAxisParams, JointMotor},
         node::{Node, NodeTrait, SyncContext, TypeUuidProvider},
     },
     utils::log::Log,
@@ -40,6 +41,11 @@ pub struct BallJoint {
     #[inspect(description = "Allowed angles range for the joint (in radians).")]
     #[visit(optional)] // Backward compatibility
     pub limits_angles: Range<f32>,
+
+    /// Motor that rotates the bodies around the joint.
+    #[inspect(description = "Motor that rotates the bodies around the joint.")]
+    #[visit(optional)] // Backward compatibility
+    pub motor: JointMotor,
 }
 
 impl Default for BallJoint {
@@ -47,6 +53,7 @@ impl Default for BallJoint {
         Self {
             limits_enabled: false,
             limits_angles: -std::f32::consts::PI..std::f32::consts::PI,
+            motor: Default::default(),
         }
     }
 }
@@ -71,6 +78,11 @@ pub struct PrismaticJoint {
     #[inspect(description = "Allowed linear distance range along local X axis of the joint.")]
     #[visit(optional)] // Backward compatibility
     pub limits: Range<f32>,
+
+    /// Motor that moves the bodies along local X axis of the joint.
+    #[inspect(description = "Motor that moves the bodies along local X axis of the joint.")]
+    #[visit(optional)] // Backward compatibility
+    pub motor: JointMotor,
 }
 
 impl Default for PrismaticJoint {
@@ -78,6 +90,78 @@ impl Default for PrismaticJoint {
         Self {
             limits_enabled: false,
             limits: -std::f32::consts::PI..std::f32::consts::PI,
+            motor: Default::default(),
+        }
+    }
+}
+
+/// Rope joint limits maximum distance between anchors of two bodies, but allows any relative
+/// rotations and does not prevent the bodies from moving closer to each other. The real world
+/// example is a rope or a chain.
+#[derive(Clone, Debug, Visit, PartialEq, Inspect, Reflect)]
+pub struct RopeJoint {
+    /// Maximum distance between anchors of the bodies.
+    #[inspect(
+        min_value = 0.0,
+        description = "Maximum distance between anchors of the bodies."
+    )]
+    pub max_length: f32,
+}
+
+impl Default for RopeJoint {
+    fn default() -> Self {
+        Self { max_length: 1.0 }
+    }
+}
+
+/// Spring joint pulls anchors of two bodies to each other using a damped spring along both
+/// local axes of the joint, relative rotations are not restricted. Anchors of the bodies are
+/// placed at the joint's position at the moment of binding, so the spring has zero rest length.
+/// Use [`PrismaticJoint`] with a motor to create a spring that acts along a single axis.
+#[derive(Clone, Debug, Visit, PartialEq, Inspect, Reflect)]
+pub struct SpringJoint {
+    /// Stiffness of the spring.
+    #[inspect(min_value = 0.0, description = "Stiffness of the spring.")]
+    pub stiffness: f32,
+
+    /// Damping of the spring.
+    #[inspect(min_value = 0.0, description = "Damping of the spring.")]
+    pub damping: f32,
+}
+
+impl Default for SpringJoint {
+    fn default() -> Self {
+        Self {
+            stiffness: 100.0,
+            damping: 1.0,
+        }
+    }
+}
+
+/// Generic joint allows to configure every degree of freedom (two linear and one angular)
+/// separately: each axis can be locked, limited and driven by a motor. By default both linear
+/// axes are locked and the angular axis is free, which is the same as [`BallJoint`].
+#[derive(Clone, Debug, Visit, PartialEq, Inspect, Reflect)]
+pub struct GenericJoint {
+    /// Parameters of linear motion along local X axis of the joint.
+    #[inspect(description = "Parameters of linear motion along local X axis of the joint.")]
+    pub x: JointAxisParams,
+
+    /// Parameters of linear motion along local Y axis of the joint.
+    #[inspect(description = "Parameters of linear motion along local Y axis of the joint.")]
+    pub y: JointAxisParams,
+
+    /// Parameters of rotation around the joint.
+    #[inspect(description = "Parameters of rotation around the joint.")]
+    pub angular: JointAxisParams,
+}
+
+impl Default for GenericJoint {
+    fn default() -> Self {
+        Self {
+            x: JointAxisParams::locked(),
+            y: JointAxisParams::locked(),
+            angular: JointAxisParams::free(),
         }
     }
 }
@@ -93,6 +177,12 @@ pub enum JointParams {
     FixedJoint(FixedJoint),
     /// See [`PrismaticJoint`] for more info.
     PrismaticJoint(PrismaticJoint),
+    /// See [`RopeJoint`] for more info.
+    RopeJoint(RopeJoint),
+    /// See [`SpringJoint`] for more info.
+    SpringJoint(SpringJoint),
+    /// See [`GenericJoint`] for more info.
+    GenericJoint(GenericJoint),
 }
 
 impl Default for JointParams {
@@ -23,25 +23,34 @@ use crate::{
         debug::SceneDrawingContext,
         dim2::{self, collider::ColliderShape, joint::JointParams, rigidbody::ApplyAction},
         graph::{
-            physics::{FeatureId, IntegrationParameters, PhysicsPerformanceStatistics},
+            physics::{
+                CollisionEventKind, FeatureId, IntegrationParameters, PhysicsPerformanceStatistics,
+            },
             NodePool,
         },
+        joint::{JointAxisParams, JointMotor, JointMotorModel},
         node::{Node, NodeTrait},
     },
     utils::log::{Log, MessageKind},
 };
+use fxhash::FxHashMap;
 use rapier2d::{
     dynamics::{
         CCDSolver, GenericJoint, GenericJointBuilder, ImpulseJointHandle, ImpulseJointSet,
-        IslandManager, JointAxesMask, JointAxis, MultibodyJointHandle, MultibodyJointSet,
-        RigidBody, RigidBodyActivation, RigidBodyBuilder, RigidBodyHandle, RigidBodySet,
-        RigidBodyType,
+        IslandManager, JointAxesMask, JointAxis, MotorModel, MultibodyJointHandle,
+        MultibodyJointSet, RigidBody, RigidBodyActivation, RigidBodyBuilder, RigidBodyHandle,
+        RigidBodySet, RigidBodyType,
     },
     geometry::{
-        BroadPhase, Collider, ColliderBuilder, ColliderHandle, ColliderSet, Cuboid,
-        InteractionGroups, NarrowPhase, Ray, SharedShape,
+        BroadPhase, Collider, ColliderBuilder, ColliderHandle, ColliderSet,
+        CollisionEvent as NativeCollisionEvent, CollisionEventFlags,
+        ContactPair as NativeContactPair, Cuboid, InteractionGroups, NarrowPhase, Ray, SharedShape,
+    },
+    parry::{self, query::TOIStatus, shape::Shape},
+    pipeline::{
+        ActiveEvents, DebugRenderPipeline, EventHandler, PhysicsPipeline, QueryFilter,
+        QueryPipeline,
     },
-    pipeline::{DebugRenderPipeline, EventHandler, PhysicsPipeline, QueryFilter, QueryPipeline},
 };
 use std::{
     cell::RefCell,
@@ -128,6 +137,93 @@ pub struct Intersection {
     pub toi: f32,
 }
 
+/// A shape cast result.
+#[derive(Debug, Clone)]
+pub struct ShapeCastResult {
+    /// A handle of the collider with which the cast shape collided first.
+    pub collider: Handle<Node>,
+
+    /// Distance that the shape has traveled along the cast direction before the collision.
+    pub toi: f32,
+
+    /// A contact point on the collider in world coordinates.
+    pub position: Point2<f32>,
+
+    /// An outward normal of the collider at the contact point in world coordinates.
+    pub normal: Vector2<f32>,
+
+    /// True if the shape was already intersecting the collider at the start of the cast. In this
+    /// case `toi` is zero and the contact data is unreliable.
+    pub penetrating: bool,
+}
+
+/// A set of options for the shape cast.
+pub struct ShapeCastOptions {
+    /// A shape to cast. Shapes that are built from scene geometry (triangle mesh and height
+    /// field) cannot be cast.
+    pub shape: ColliderShape,
+
+    /// Initial position and orientation of the shape in world coordinates.
+    pub shape_position: Isometry2<f32>,
+
+    /// A cast direction. Can be non-normalized.
+    pub direction: Vector2<f32>,
+
+    /// Maximum distance of cast.
+    pub max_len: f32,
+
+    /// Groups to check.
+    pub groups: collider::InteractionGroups,
+
+    /// Whether to ignore sensors.
+    pub exclude_sensors: bool,
+}
+
+/// A set of options for the shape intersection query.
+pub struct ShapeIntersectionOptions {
+    /// A shape to check. Shapes that are built from scene geometry (triangle mesh and height
+    /// field) cannot be used.
+    pub shape: ColliderShape,
+
+    /// Position and orientation of the shape in world coordinates.
+    pub shape_position: Isometry2<f32>,
+
+    /// Groups to check.
+    pub groups: collider::InteractionGroups,
+
+    /// Whether to ignore sensors.
+    pub exclude_sensors: bool,
+}
+
+/// A set of options for the point projection.
+pub struct PointProjectionOptions {
+    /// A point to project in world coordinates.
+    pub point: Point2<f32>,
+
+    /// If true, a point that is inside a collider is projected on itself, otherwise it is
+    /// projected on the boundary of the collider.
+    pub solid: bool,
+
+    /// Groups to check.
+    pub groups: collider::InteractionGroups,
+
+    /// Whether to ignore sensors.
+    pub exclude_sensors: bool,
+}
+
+/// A result of point projection.
+#[derive(Debug, Clone)]
+pub struct PointProjection {
+    /// A handle of the collider on which the point was projected.
+    pub collider: Handle<Node>,
+
+    /// Projected point in world coordinates.
+    pub position: Point2<f32>,
+
+    /// True if the original point was inside the collider.
+    pub is_inside: bool,
+}
+
 /// A set of options for the ray cast.
 pub struct RayCastOptions {
     /// A ray origin.
@@ -146,6 +242,24 @@ pub struct RayCastOptions {
     pub sort_results: bool,
 }
 
+/// A set of parameters for [`PhysicsWorld::move_character`].
+pub(crate) struct CharacterMoveParams {
+    pub groups: collider::InteractionGroups,
+    pub max_slope_angle: f32,
+    pub max_step_height: f32,
+    pub snap_to_ground_distance: f32,
+    pub offset: f32,
+    pub was_grounded: bool,
+}
+
+/// A result of [`PhysicsWorld::move_character`].
+pub(crate) struct CharacterMoveResult {
+    pub position: Vector2<f32>,
+    pub grounded: bool,
+    pub ground_normal: Vector2<f32>,
+    pub ground_collider: Handle<Node>,
+}
+
 /// Data of the contact.
 pub struct ContactData {
     /// The contact point in the local-space of the first shape.
@@ -191,6 +305,64 @@ pub struct ContactPair {
     pub has_any_active_contact: bool,
 }
 
+/// A contact point of a [`CollisionEvent`].
+#[derive(Copy, Clone, Debug)]
+pub struct CollisionContact {
+    /// The contact point on the collider in world coordinates.
+    pub position: Point2<f32>,
+    /// The contact normal in world coordinates, it points from the collider to the other collider.
+    pub normal: Vector2<f32>,
+    /// The distance between the colliders at the contact point, negative if they penetrate.
+    pub dist: f32,
+}
+
+/// Collision event is generated when two colliders start or stop touching (or intersecting, if one
+/// of them is a sensor) each other. Every event is generated twice - once for each collider of a
+/// pair, so `collider` is always the collider the event is about and `other` is the second one.
+/// The only exception is removal of a collider, in this case only the remaining collider receives
+/// a stop event.
+#[derive(Clone, Debug)]
+pub struct CollisionEvent {
+    /// A kind of the event.
+    pub kind: CollisionEventKind,
+    /// A handle of the collider the event is about.
+    pub collider: Handle<Node>,
+    /// A handle of the other collider of the pair. If the other collider was removed (the event
+    /// is a stop event then), the handle is no longer valid.
+    pub other: Handle<Node>,
+    /// Contact points between the colliders. It is filled only for
+    /// [`CollisionEventKind::ContactStarted`] events.
+    pub contacts: Vec<CollisionContact>,
+}
+
+// Collects collision events generated by the physics pipeline during a step.
+#[derive(Default)]
+struct CollisionEventCollector {
+    events: Mutex<Vec<NativeCollisionEvent>>,
+}
+
+impl EventHandler for CollisionEventCollector {
+    fn handle_collision_event(
+        &self,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        event: NativeCollisionEvent,
+        _contact_pair: Option<&NativeContactPair>,
+    ) {
+        self.events.lock().push(event);
+    }
+
+    fn handle_contact_force_event(
+        &self,
+        _dt: f32,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        _contact_pair: &NativeContactPair,
+        _total_force_magnitude: f32,
+    ) {
+    }
+}
+
 pub(super) struct Container<S, A>
 where
     A: Hash + Eq + Clone,
@@ -199,6 +371,36 @@ where
     map: BiDirHashMap<A, Handle<Node>>,
 }
 
+fn set_joint_motor(joint: &mut GenericJoint, axis: JointAxis, motor: &JointMotor) {
+    if motor.enabled {
+        joint
+            .set_motor(
+                axis,
+                motor.target_position,
+                motor.target_velocity,
+                motor.stiffness,
+                motor.damping,
+            )
+            .set_motor_max_force(axis, motor.max_force)
+            .set_motor_model(
+                axis,
+                match motor.model {
+                    JointMotorModel::AccelerationBased => MotorModel::AccelerationBased,
+                    JointMotorModel::ForceBased => MotorModel::ForceBased,
+                },
+            );
+    }
+}
+
+fn set_joint_axis(joint: &mut GenericJoint, axis: JointAxis, params: &JointAxisParams) {
+    if !params.locked {
+        if params.limits_enabled {
+            joint.set_limits(axis, [params.limits.start, params.limits.end]);
+        }
+        set_joint_motor(joint, axis, &params.motor);
+    }
+}
+
 fn convert_joint_params(
     params: scene::dim2::joint::JointParams,
     local_frame1: Isometry2<f32>,
@@ -208,6 +410,20 @@ fn convert_joint_params(
         JointParams::BallJoint(_) => JointAxesMask::LOCKED_REVOLUTE_AXES,
         JointParams::FixedJoint(_) => JointAxesMask::LOCKED_FIXED_AXES,
         JointParams::PrismaticJoint(_) => JointAxesMask::LOCKED_PRISMATIC_AXES,
+        JointParams::RopeJoint(_) | JointParams::SpringJoint(_) => JointAxesMask::empty(),
+        JointParams::GenericJoint(ref v) => {
+            let mut mask = JointAxesMask::empty();
+            for (axis, params) in [
+                (JointAxesMask::X, &v.x),
+                (JointAxesMask::Y, &v.y),
+                (JointAxesMask::ANG_X, &v.angular),
+            ] {
+                if params.locked {
+                    mask |= axis;
+                }
+            }
+            mask
+        }
     };
 
     let mut joint = GenericJointBuilder::new(locked_axis)
@@ -223,12 +439,33 @@ fn convert_joint_params(
                     [v.limits_angles.start, v.limits_angles.end],
                 );
             }
+            set_joint_motor(&mut joint, JointAxis::AngX, &v.motor);
         }
         scene::dim2::joint::JointParams::FixedJoint(_) => {}
         scene::dim2::joint::JointParams::PrismaticJoint(v) => {
             if v.limits_enabled {
                 joint.set_limits(JointAxis::X, [v.limits.start, v.limits.end]);
             }
+            set_joint_motor(&mut joint, JointAxis::X, &v.motor);
+        }
+        scene::dim2::joint::JointParams::RopeJoint(v) => {
+            // Coupled limits restrict the length of the vector between the anchors, the solver
+            // uses the length of the vector of per-axis limits as the max distance.
+            joint.coupled_axes = JointAxesMask::X | JointAxesMask::Y;
+            let max = v.max_length.max(0.0) / 2.0f32.sqrt();
+            for axis in [JointAxis::X, JointAxis::Y] {
+                joint.set_limits(axis, [0.0, max]);
+            }
+        }
+        scene::dim2::joint::JointParams::SpringJoint(v) => {
+            for axis in [JointAxis::X, JointAxis::Y] {
+                joint.set_motor(axis, 0.0, 0.0, v.stiffness, v.damping);
+            }
+        }
+        scene::dim2::joint::JointParams::GenericJoint(v) => {
+            set_joint_axis(&mut joint, JointAxis::X, &v.x);
+            set_joint_axis(&mut joint, JointAxis::Y, &v.y);
+            set_joint_axis(&mut joint, JointAxis::AngX, &v.angular);
         }
     }
 
@@ -345,7 +582,17 @@ pub struct PhysicsWorld {
     #[visit(skip)]
     #[inspect(skip)]
     #[reflect(hidden)]
-    event_handler: Box<dyn EventHandler>,
+    event_handler: CollisionEventCollector,
+    // Collision events generated during the last step.
+    #[visit(skip)]
+    #[inspect(skip)]
+    #[reflect(hidden)]
+    collision_events: Vec<CollisionEvent>,
+    // Colliders that were removed since the last step, their owners still must receive stop events.
+    #[visit(skip)]
+    #[inspect(skip)]
+    #[reflect(hidden)]
+    removed_colliders: FxHashMap<ColliderHandle, Handle<Node>>,
     #[visit(skip)]
     #[inspect(skip)]
     #[reflect(hidden)]
@@ -380,6 +627,18 @@ fn calculate_local_frames(
     )
 }
 
+fn query_filter<'a>(groups: collider::InteractionGroups, exclude_sensors: bool) -> QueryFilter<'a> {
+    let filter = QueryFilter::new().groups(InteractionGroups::new(
+        groups.memberships.0,
+        groups.filter.0,
+    ));
+    if exclude_sensors {
+        filter.exclude_sensors()
+    } else {
+        filter
+    }
+}
+
 impl PhysicsWorld {
     /// Creates a new instance of the physics world.
     pub(crate) fn new() -> Self {
@@ -408,7 +667,9 @@ impl PhysicsWorld {
                 set: MultibodyJointSet::new(),
                 map: Default::default(),
             },
-            event_handler: Box::new(()),
+            event_handler: Default::default(),
+            collision_events: Default::default(),
+            removed_colliders: Default::default(),
             query: RefCell::new(Default::default()),
             performance_statistics: Default::default(),
             debug_render_pipeline: Default::default(),
@@ -458,13 +719,101 @@ impl PhysicsWorld {
                 &mut self.multibody_joints.set,
                 &mut self.ccd_solver,
                 &(),
-                &*self.event_handler,
+                &self.event_handler,
             );
         }
 
+        self.collision_events.clear();
+        let native_events = std::mem::take(&mut *self.event_handler.events.lock());
+        for event in native_events {
+            self.push_collision_event(event);
+        }
+        self.removed_colliders.clear();
+
         self.performance_statistics.step_time += instant::Instant::now() - time;
     }
 
+    fn push_collision_event(&mut self, event: NativeCollisionEvent) {
+        let (handle1, handle2, flags, started) = match event {
+            NativeCollisionEvent::Started(h1, h2, flags) => (h1, h2, flags, true),
+            NativeCollisionEvent::Stopped(h1, h2, flags) => (h1, h2, flags, false),
+        };
+
+        // A stop event is generated when a collider is removed, it is delivered to the other
+        // collider only.
+        let find = |handle: ColliderHandle| match self.colliders.map.value_of(&handle) {
+            Some(collider) => Some((*collider, true)),
+            None if flags.contains(CollisionEventFlags::REMOVED) => {
+                self.removed_colliders.get(&handle).map(|c| (*c, false))
+            }
+            None => None,
+        };
+        let ((collider1, alive1), (collider2, alive2)) = match (find(handle1), find(handle2)) {
+            (Some(first), Some(second)) => (first, second),
+            _ => return,
+        };
+
+        let kind = match (flags.contains(CollisionEventFlags::SENSOR), started) {
+            (false, true) => CollisionEventKind::ContactStarted,
+            (false, false) => CollisionEventKind::ContactStopped,
+            (true, true) => CollisionEventKind::IntersectionStarted,
+            (true, false) => CollisionEventKind::IntersectionStopped,
+        };
+
+        let mut contacts1 = Vec::new();
+        let mut contacts2 = Vec::new();
+        if kind == CollisionEventKind::ContactStarted {
+            if let Some(pair) = self.narrow_phase.contact_pair(handle1, handle2) {
+                if let (Some(native1), Some(native2)) = (
+                    self.colliders.set.get(pair.collider1),
+                    self.colliders.set.get(pair.collider2),
+                ) {
+                    for manifold in pair.manifolds.iter() {
+                        for point in manifold.points.iter() {
+                            contacts1.push(CollisionContact {
+                                position: native1.position() * point.local_p1,
+                                normal: manifold.data.normal,
+                                dist: point.dist,
+                            });
+                            contacts2.push(CollisionContact {
+                                position: native2.position() * point.local_p2,
+                                normal: -manifold.data.normal,
+                                dist: point.dist,
+                            });
+                        }
+                    }
+                }
+
+                // Pair could be stored in the reversed order.
+                if pair.collider1 != handle1 {
+                    std::mem::swap(&mut contacts1, &mut contacts2);
+                }
+            }
+        }
+
+        if alive1 {
+            self.collision_events.push(CollisionEvent {
+                kind,
+                collider: collider1,
+                other: collider2,
+                contacts: contacts1,
+            });
+        }
+        if alive2 {
+            self.collision_events.push(CollisionEvent {
+                kind,
+                collider: collider2,
+                other: collider1,
+                contacts: contacts2,
+            });
+        }
+    }
+
+    /// Returns collision events that were generated during the last simulation step.
+    pub fn collision_events(&self) -> &[CollisionEvent] {
+        &self.collision_events
+    }
+
     pub(crate) fn add_body(&mut self, owner: Handle<Node>, body: RigidBody) -> RigidBodyHandle {
         let handle = self.bodies.set.insert(body);
         self.bodies.map.insert(handle, owner);
@@ -473,6 +822,14 @@ impl PhysicsWorld {
 
     pub(crate) fn remove_body(&mut self, handle: RigidBodyHandle) {
         assert!(self.bodies.map.remove_by_key(&handle).is_some());
+        // Attached colliders are removed with the body.
+        if let Some(body) = self.bodies.set.get(handle) {
+            for collider in body.colliders() {
+                if let Some(owner) = self.colliders.map.remove_by_key(collider) {
+                    self.removed_colliders.insert(*collider, owner);
+                }
+            }
+        }
         self.bodies.set.remove(
             handle,
             &mut self.islands,
@@ -504,7 +861,8 @@ impl PhysicsWorld {
             .remove(handle, &mut self.islands, &mut self.bodies.set, false)
             .is_some()
         {
-            assert!(self.colliders.map.remove_by_key(&handle).is_some());
+            let owner = self.colliders.map.remove_by_key(&handle).unwrap();
+            self.removed_colliders.insert(handle, owner);
             true
         } else {
             false
@@ -599,6 +957,301 @@ impl PhysicsWorld {
         );
     }
 
+    /// Sweeps a shape along the given direction and returns the first collider it hits, if any.
+    pub fn cast_shape(&self, opts: ShapeCastOptions) -> Option<ShapeCastResult> {
+        let shape = collider_shape_into_native_shape(&opts.shape)?;
+
+        let mut query = self.query.borrow_mut();
+        query.update(&self.islands, &self.bodies.set, &self.colliders.set);
+
+        let direction = opts
+            .direction
+            .try_normalize(f32::EPSILON)
+            .unwrap_or_default();
+        query
+            .cast_shape(
+                &self.bodies.set,
+                &self.colliders.set,
+                &opts.shape_position,
+                &direction,
+                &*shape,
+                opts.max_len,
+                query_filter(opts.groups, opts.exclude_sensors),
+            )
+            .map(|(handle, toi)| {
+                // Casts of support map shapes report a tiny time of impact instead of penetration
+                // if the shape starts inside of a collider, so check the initial position explicitly.
+                let collider = &self.colliders.set[handle];
+                let penetrating = toi.status == TOIStatus::Penetrating
+                    || parry::query::intersection_test(
+                        &opts.shape_position,
+                        &*shape,
+                        collider.position(),
+                        collider.shape(),
+                    )
+                    .unwrap_or_default();
+                ShapeCastResult {
+                    collider: self.colliders.map.value_of(&handle).cloned().unwrap(),
+                    toi: if penetrating { 0.0 } else { toi.toi },
+                    position: toi.witness1,
+                    normal: *toi.normal1,
+                    penetrating,
+                }
+            })
+    }
+
+    /// Calls the given callback for every collider that intersects the given shape placed at the
+    /// given position. The callback must return `false` to stop the search. Shapes that are built
+    /// from scene geometry (triangle mesh and height field) cannot be used.
+    pub fn intersections_with_shape<C>(&self, opts: ShapeIntersectionOptions, mut callback: C)
+    where
+        C: FnMut(Handle<Node>) -> bool,
+    {
+        if let Some(shape) = collider_shape_into_native_shape(&opts.shape) {
+            let mut query = self.query.borrow_mut();
+            query.update(&self.islands, &self.bodies.set, &self.colliders.set);
+
+            query.intersections_with_shape(
+                &self.bodies.set,
+                &self.colliders.set,
+                &opts.shape_position,
+                &*shape,
+                query_filter(opts.groups, opts.exclude_sensors),
+                |handle| callback(self.colliders.map.value_of(&handle).cloned().unwrap()),
+            );
+        }
+    }
+
+    /// Projects a point on the closest collider.
+    pub fn project_point(&self, opts: PointProjectionOptions) -> Option<PointProjection> {
+        let mut query = self.query.borrow_mut();
+        query.update(&self.islands, &self.bodies.set, &self.colliders.set);
+
+        query
+            .project_point(
+                &self.bodies.set,
+                &self.colliders.set,
+                &opts.point,
+                opts.solid,
+                query_filter(opts.groups, opts.exclude_sensors),
+            )
+            .map(|(handle, projection)| PointProjection {
+                collider: self.colliders.map.value_of(&handle).cloned().unwrap(),
+                position: projection.point,
+                is_inside: projection.is_inside,
+            })
+    }
+
+    /// Returns current position of a rigid body to which the given collider is attached.
+    pub(crate) fn collider_body_position(&self, collider: Handle<Node>) -> Option<Isometry2<f32>> {
+        self.colliders
+            .map
+            .key_of(&collider)
+            .and_then(|h| self.colliders.set.get(*h))
+            .and_then(|c| c.parent())
+            .and_then(|h| self.bodies.set.get(h))
+            .map(|b| *b.position())
+    }
+
+    /// Moves a shape by the given translation, the shape slides along obstacles, climbs steps
+    /// and snaps to the ground. `ignore` allows to exclude colliders (usually colliders of the
+    /// character itself) from the collision detection.
+    pub(crate) fn move_character(
+        &self,
+        shape: &dyn Shape,
+        mut position: Vector2<f32>,
+        translation: Vector2<f32>,
+        params: &CharacterMoveParams,
+        ignore: &dyn Fn(Handle<Node>) -> bool,
+    ) -> CharacterMoveResult {
+        const MAX_ITERATIONS: usize = 5;
+        const EPSILON: f32 = 1.0e-5;
+
+        let up = Vector2::y();
+        let min_ground_cos = params.max_slope_angle.cos();
+        let offset = params.offset;
+
+        let mut query = self.query.borrow_mut();
+        query.update(&self.islands, &self.bodies.set, &self.colliders.set);
+
+        let predicate = |handle: ColliderHandle, _: &Collider| {
+            self.colliders
+                .map
+                .value_of(&handle)
+                .map_or(true, |node| !ignore(*node))
+        };
+        let filter = QueryFilter::new()
+            .exclude_sensors()
+            .groups(InteractionGroups::new(
+                params.groups.memberships.0,
+                params.groups.filter.0,
+            ))
+            .predicate(&predicate);
+
+        let cast = |from: Vector2<f32>, translation: Vector2<f32>| {
+            let distance = translation.norm();
+            if distance < EPSILON {
+                return None;
+            }
+            query
+                .cast_shape(
+                    &self.bodies.set,
+                    &self.colliders.set,
+                    &Isometry2::translation(from.x, from.y),
+                    &translation.scale(1.0 / distance),
+                    shape,
+                    distance,
+                    filter,
+                )
+                .map(|(handle, toi)| {
+                    let normal = if toi.status == TOIStatus::Penetrating {
+                        -translation.scale(1.0 / distance)
+                    } else {
+                        toi.normal1.into_inner()
+                    };
+                    (handle, toi.toi, normal)
+                })
+        };
+
+        // Push the shape out of the obstacles first, otherwise casts will report zero time of impact.
+        let mut penetrations = ArrayVec::<ColliderHandle, 8>::new();
+        query.intersections_with_shape(
+            &self.bodies.set,
+            &self.colliders.set,
+            &Isometry2::translation(position.x, position.y),
+            shape,
+            filter,
+            |handle| penetrations.try_push(handle).is_ok(),
+        );
+        for handle in penetrations {
+            let collider = &self.colliders.set[handle];
+            if let Ok(Some(contact)) = parry::query::contact(
+                &Isometry2::translation(position.x, position.y),
+                shape,
+                collider.position(),
+                collider.shape(),
+                0.0,
+            ) {
+                if contact.dist < 0.0 {
+                    position += contact.normal1.scale(contact.dist - offset);
+                }
+            }
+        }
+
+        let mut grounded = false;
+        let mut ground_normal = up;
+        let mut ground_collider = None;
+
+        let try_step = |position: Vector2<f32>, horizontal: Vector2<f32>| {
+            let up_distance = match cast(position, up.scale(params.max_step_height + offset)) {
+                Some((_, toi, _)) => (toi - offset).max(0.0),
+                None => params.max_step_height,
+            };
+            if up_distance < EPSILON {
+                return None;
+            }
+            let raised = position + up.scale(up_distance);
+
+            let length = horizontal.norm();
+            let direction = horizontal.scale(1.0 / length);
+            let forward_distance = match cast(raised, horizontal + direction.scale(offset)) {
+                Some((_, toi, _)) => (toi - offset).max(0.0),
+                None => length,
+            };
+            if forward_distance < EPSILON {
+                return None;
+            }
+            let moved = raised + direction.scale(forward_distance);
+
+            // Step is valid only if there's walkable ground below.
+            match cast(moved, -up.scale(up_distance + offset)) {
+                Some((handle, toi, normal)) if normal.dot(&up) >= min_ground_cos => {
+                    Some((moved - up.scale((toi - offset).max(0.0)), handle, normal))
+                }
+                _ => None,
+            }
+        };
+
+        let mut remaining = translation;
+        for _ in 0..MAX_ITERATIONS {
+            let length = remaining.norm();
+            if length < EPSILON {
+                break;
+            }
+            let direction = remaining.scale(1.0 / length);
+
+            let (handle, toi, normal) = match cast(position, remaining + direction.scale(offset)) {
+                Some(hit) => hit,
+                None => {
+                    position += remaining;
+                    break;
+                }
+            };
+
+            let travel = (toi - offset).clamp(0.0, length);
+            position += direction.scale(travel);
+            remaining = direction.scale(length - travel);
+
+            let walkable = normal.dot(&up) >= min_ground_cos;
+            if walkable {
+                grounded = true;
+                ground_normal = normal;
+                ground_collider = Some(handle);
+            } else if params.max_step_height > 0.0 && (params.was_grounded || grounded) {
+                let horizontal = remaining - up.scale(remaining.dot(&up));
+                if horizontal.norm() > EPSILON {
+                    if let Some((stepped, handle, normal)) = try_step(position, horizontal) {
+                        position = stepped;
+                        grounded = true;
+                        ground_normal = normal;
+                        ground_collider = Some(handle);
+                        remaining = up.scale(remaining.dot(&up).max(0.0));
+                        continue;
+                    }
+                }
+            }
+
+            // Slide along the obstacle.
+            let into_surface = remaining.dot(&normal);
+            if into_surface < 0.0 {
+                remaining -= normal.scale(into_surface);
+            }
+
+            // Steep slopes cannot be climbed, they act as walls.
+            if !walkable && translation.dot(&up) <= 0.0 {
+                let up_component = remaining.dot(&up);
+                if up_component > 0.0 {
+                    remaining -= up.scale(up_component);
+                }
+            }
+        }
+
+        if !grounded && translation.dot(&up) <= EPSILON {
+            let probe = if params.was_grounded {
+                params.snap_to_ground_distance.max(2.0 * offset)
+            } else {
+                2.0 * offset
+            };
+            if let Some((handle, toi, normal)) = cast(position, -up.scale(probe + offset)) {
+                if normal.dot(&up) >= min_ground_cos {
+                    position -= up.scale((toi - offset).max(0.0));
+                    grounded = true;
+                    ground_normal = normal;
+                    ground_collider = Some(handle);
+                }
+            }
+        }
+
+        CharacterMoveResult {
+            position,
+            grounded,
+            ground_normal,
+            ground_collider: ground_collider
+                .and_then(|h| self.colliders.map.value_of(&h).cloned())
+                .unwrap_or_default(),
+        }
+    }
+
     pub(crate) fn set_rigid_body_position(
         &mut self,
         rigid_body: &scene::dim2::rigidbody::RigidBody,
@@ -864,7 +1517,8 @@ impl PhysicsWorld {
                             collider_node.solver_groups().memberships.0,
                             collider_node.solver_groups().filter.0,
                         ))
-                        .sensor(collider_node.is_sensor());
+                        .sensor(collider_node.is_sensor())
+                        .active_events(ActiveEvents::COLLISION_EVENTS);
 
                     if let Some(density) = collider_node.density() {
                         builder = builder.density(density);
@@ -1037,3 +1691,567 @@ impl Debug for PhysicsWorld {
         write!(f, "PhysicsWorld")
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            algebra::{Isometry2, Point2, UnitQuaternion, Vector2, Vector3},
+            pool::Handle,
+        },
+        scene::{
+            base::BaseBuilder,
+            collider::{BitMask, InteractionGroups},
+            dim2::{
+                collider::{ColliderBuilder, ColliderShape},
+                physics::{
+                    CharacterMoveParams, CharacterMoveResult, CollisionEvent,
+                    PointProjectionOptions, ShapeCastOptions, ShapeIntersectionOptions,
+                },
+                rigidbody::RigidBodyBuilder,
+            },
+            graph::{physics::CollisionEventKind, Graph},
+            node::Node,
+            rigidbody::RigidBodyType,
+            transform::TransformBuilder,
+        },
+    };
+    use rapier2d::parry::shape::Capsule;
+
+    // Capsule with total height of 2 meters, standing on the ground (y = 0) its center is at y = 1.
+    const HALF_HEIGHT: f32 = 0.5;
+    const RADIUS: f32 = 0.5;
+    const OFFSET: f32 = 0.01;
+    const STANDING_Y: f32 = HALF_HEIGHT + RADIUS + OFFSET;
+
+    fn add_static_box(
+        graph: &mut Graph,
+        position: Vector2<f32>,
+        angle: f32,
+        half_extents: Vector2<f32>,
+    ) -> Handle<Node> {
+        let collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(half_extents.x, half_extents.y))
+            .build(graph);
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(position.x, position.y, 0.0))
+                        .with_local_rotation(UnitQuaternion::from_axis_angle(
+                            &Vector3::z_axis(),
+                            angle,
+                        ))
+                        .build(),
+                )
+                .with_children(&[collider]),
+        )
+        .with_body_type(RigidBodyType::Static)
+        .build(graph);
+        collider
+    }
+
+    // Adds a static box that spans `begin..end` along X with its top face at the given height.
+    fn add_ground(graph: &mut Graph, begin: f32, end: f32, height: f32) -> Handle<Node> {
+        add_static_box(
+            graph,
+            Vector2::new((begin + end) * 0.5, height - 0.5),
+            0.0,
+            Vector2::new((end - begin) * 0.5, 0.5),
+        )
+    }
+
+    // Adds a slope that starts at the given point on the ground and rises towards +X.
+    fn add_slope(graph: &mut Graph, x: f32, angle: f32) -> Handle<Node> {
+        let normal = Vector2::new(-angle.sin(), angle.cos());
+        let tangent = Vector2::new(angle.cos(), angle.sin());
+        add_static_box(
+            graph,
+            Vector2::new(x, 0.0) - normal.scale(0.5) + tangent.scale(5.0),
+            angle,
+            Vector2::new(5.0, 0.5),
+        )
+    }
+
+    fn sync(graph: &mut Graph) {
+        // Colliders are built before their bodies, so native colliders are created only on the
+        // second sync when native bodies already exist.
+        graph.update_physics();
+        graph.update_physics();
+    }
+
+    fn move_character(
+        graph: &mut Graph,
+        position: Vector2<f32>,
+        translation: Vector2<f32>,
+        was_grounded: bool,
+    ) -> CharacterMoveResult {
+        sync(graph);
+        graph.physics2d.move_character(
+            &Capsule::new_y(HALF_HEIGHT, RADIUS),
+            position,
+            translation,
+            &CharacterMoveParams {
+                groups: InteractionGroups::default(),
+                max_slope_angle: 45.0f32.to_radians(),
+                max_step_height: 0.3,
+                snap_to_ground_distance: 0.3,
+                offset: OFFSET,
+                was_grounded,
+            },
+            &|_| false,
+        )
+    }
+
+    #[test]
+    fn test_move_character_2d_slides_along_wall() {
+        let mut graph = Graph::new();
+        add_ground(&mut graph, -20.0, 20.0, 0.0);
+        // Wall with its face at x = 1.5
+        add_static_box(
+            &mut graph,
+            Vector2::new(2.0, 5.0),
+            0.0,
+            Vector2::new(0.5, 5.0),
+        );
+
+        // Falling character is pressed against the wall, but keeps falling along it.
+        let result = move_character(
+            &mut graph,
+            Vector2::new(0.0, STANDING_Y + 2.0),
+            Vector2::new(2.0, -1.0),
+            false,
+        );
+
+        assert!((result.position.x - (1.0 - OFFSET)).abs() < 0.02);
+        assert!((result.position.y - (STANDING_Y + 1.0)).abs() < 0.02);
+        assert!(!result.grounded);
+    }
+
+    #[test]
+    fn test_move_character_2d_climbs_step() {
+        let mut graph = Graph::new();
+        add_ground(&mut graph, -20.0, 1.5, 0.0);
+        add_ground(&mut graph, 1.5, 20.0, 0.2);
+
+        let result = move_character(
+            &mut graph,
+            Vector2::new(0.0, STANDING_Y),
+            Vector2::new(2.5, 0.0),
+            true,
+        );
+
+        assert!((result.position.x - 2.5).abs() < 0.02);
+        assert!((result.position.y - (STANDING_Y + 0.2)).abs() < 0.02);
+        assert!(result.grounded);
+        assert!(result.ground_normal.y > 0.99);
+    }
+
+    #[test]
+    fn test_move_character_2d_blocked_by_high_step() {
+        let mut graph = Graph::new();
+        add_ground(&mut graph, -20.0, 1.5, 0.0);
+        add_ground(&mut graph, 1.5, 20.0, 0.5);
+
+        let result = move_character(
+            &mut graph,
+            Vector2::new(0.0, STANDING_Y),
+            Vector2::new(2.5, 0.0),
+            true,
+        );
+
+        assert!(result.position.x < 1.5);
+        assert!((result.position.y - STANDING_Y).abs() < 0.02);
+        assert!(result.grounded);
+    }
+
+    #[test]
+    fn test_move_character_2d_steep_slope() {
+        let mut graph = Graph::new();
+        add_ground(&mut graph, -20.0, 20.0, 0.0);
+        add_slope(&mut graph, 2.0, 60.0f32.to_radians());
+
+        let result = move_character(
+            &mut graph,
+            Vector2::new(0.0, STANDING_Y),
+            Vector2::new(3.0, 0.0),
+            true,
+        );
+
+        // The slope acts as a wall.
+        assert!(result.position.x < 2.0);
+        assert!((result.position.y - STANDING_Y).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_move_character_2d_gentle_slope() {
+        let mut graph = Graph::new();
+        add_ground(&mut graph, -20.0, 20.0, 0.0);
+        add_slope(&mut graph, 2.0, 30.0f32.to_radians());
+
+        let result = move_character(
+            &mut graph,
+            Vector2::new(0.0, STANDING_Y),
+            Vector2::new(4.0, 0.0),
+            true,
+        );
+
+        assert!(result.position.x > 2.5);
+        assert!(result.position.y > STANDING_Y + 0.2);
+        assert!(result.grounded);
+    }
+
+    #[test]
+    fn test_move_character_2d_snaps_to_ground() {
+        let mut graph = Graph::new();
+        add_ground(&mut graph, -20.0, 1.0, 0.0);
+        add_ground(&mut graph, 1.0, 20.0, -0.2);
+
+        let result = move_character(
+            &mut graph,
+            Vector2::new(0.0, STANDING_Y),
+            Vector2::new(3.0, 0.0),
+            true,
+        );
+        assert!((result.position.x - 3.0).abs() < 0.02);
+        assert!((result.position.y - (STANDING_Y - 0.2)).abs() < 0.02);
+        assert!(result.grounded);
+
+        // Airborne characters are not snapped.
+        let result = move_character(
+            &mut graph,
+            Vector2::new(0.0, STANDING_Y),
+            Vector2::new(3.0, 0.0),
+            false,
+        );
+        assert!((result.position.y - STANDING_Y).abs() < 0.02);
+        assert!(!result.grounded);
+    }
+
+    #[test]
+    fn test_move_character_2d_does_not_snap_from_high_ledge() {
+        let mut graph = Graph::new();
+        add_ground(&mut graph, -20.0, 1.0, 0.0);
+        add_ground(&mut graph, 1.0, 20.0, -1.0);
+
+        let result = move_character(
+            &mut graph,
+            Vector2::new(0.0, STANDING_Y),
+            Vector2::new(3.0, 0.0),
+            true,
+        );
+        assert!((result.position.y - STANDING_Y).abs() < 0.02);
+        assert!(!result.grounded);
+    }
+
+    #[test]
+    fn test_cast_shape_2d() {
+        let mut graph = Graph::new();
+        let ground = add_ground(&mut graph, -20.0, 20.0, 0.0);
+        sync(&mut graph);
+
+        let cast = |position: Vector2<f32>, direction: Vector2<f32>, max_len: f32| {
+            graph.physics2d.cast_shape(ShapeCastOptions {
+                shape: ColliderShape::ball(0.5),
+                shape_position: Isometry2::translation(position.x, position.y),
+                direction,
+                max_len,
+                groups: Default::default(),
+                exclude_sensors: true,
+            })
+        };
+
+        // Direction does not need to be normalized.
+        let result = cast(Vector2::new(1.0, 3.0), Vector2::new(0.0, -2.0), 10.0)
+            .expect("The ball must hit the ground!");
+        assert_eq!(result.collider, ground);
+        assert!((result.toi - 2.5).abs() < 0.01);
+        assert!((result.normal - Vector2::y()).norm() < 0.01);
+        assert!((result.position - Point2::new(1.0, 0.0)).norm() < 0.01);
+        assert!(!result.penetrating);
+
+        // Too short or in wrong direction.
+        assert!(cast(Vector2::new(0.0, 3.0), -Vector2::y(), 2.0).is_none());
+        assert!(cast(Vector2::new(0.0, 3.0), Vector2::y(), 10.0).is_none());
+
+        let result = cast(Vector2::new(0.0, 0.0), -Vector2::y(), 10.0)
+            .expect("The ball must hit the ground!");
+        assert!(result.penetrating);
+        assert_eq!(result.toi, 0.0);
+    }
+
+    // Creates a ground (spans from -1 to 0 along Y) that belongs to the first collision group
+    // and a sensor above it (spans from 0 to 1 along Y).
+    fn add_ground_with_sensor(graph: &mut Graph) -> (Handle<Node>, Handle<Node>) {
+        let ground = add_ground(graph, -20.0, 20.0, 0.0);
+        graph[ground]
+            .as_collider2d_mut()
+            .set_collision_groups(InteractionGroups::new(BitMask(0b01), BitMask(u32::MAX)));
+        let sensor = add_ground(graph, -20.0, 20.0, 1.0);
+        graph[sensor].as_collider2d_mut().set_is_sensor(true);
+        sync(graph);
+        (ground, sensor)
+    }
+
+    #[test]
+    fn test_cast_shape_2d_filtering() {
+        let mut graph = Graph::new();
+        let (ground, sensor) = add_ground_with_sensor(&mut graph);
+
+        let cast = |groups: InteractionGroups, exclude_sensors: bool| {
+            graph.physics2d.cast_shape(ShapeCastOptions {
+                shape: ColliderShape::cuboid(0.5, 0.5),
+                shape_position: Isometry2::translation(0.0, 3.0),
+                direction: -Vector2::y(),
+                max_len: 10.0,
+                groups,
+                exclude_sensors,
+            })
+        };
+
+        let result = cast(Default::default(), true).expect("The box must hit the ground!");
+        assert_eq!(result.collider, ground);
+        assert!((result.toi - 2.5).abs() < 0.01);
+
+        let result = cast(Default::default(), false).expect("The box must hit the sensor!");
+        assert_eq!(result.collider, sensor);
+        assert!((result.toi - 1.5).abs() < 0.01);
+
+        assert!(cast(
+            InteractionGroups::new(BitMask(u32::MAX), BitMask(0b10)),
+            true
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_intersections_with_shape_2d() {
+        let mut graph = Graph::new();
+        let (ground, sensor) = add_ground_with_sensor(&mut graph);
+        let platform = add_static_box(
+            &mut graph,
+            Vector2::new(5.0, 2.5),
+            0.0,
+            Vector2::new(1.0, 0.5),
+        );
+        sync(&mut graph);
+
+        let intersections = |radius: f32, groups: InteractionGroups, limit: usize| {
+            let mut colliders = Vec::new();
+            graph.physics2d.intersections_with_shape(
+                ShapeIntersectionOptions {
+                    shape: ColliderShape::ball(radius),
+                    shape_position: Isometry2::translation(0.0, 1.5),
+                    groups,
+                    exclude_sensors: true,
+                },
+                |collider| {
+                    colliders.push(collider);
+                    colliders.len() < limit
+                },
+            );
+            colliders.sort_by_key(|c| c.index());
+            colliders
+        };
+
+        assert!(intersections(0.4, Default::default(), usize::MAX).is_empty());
+        assert_eq!(
+            intersections(2.0, Default::default(), usize::MAX),
+            vec![ground]
+        );
+        assert_eq!(
+            intersections(10.0, Default::default(), usize::MAX),
+            vec![ground, platform]
+        );
+
+        // Search stops when the callback returns false.
+        assert_eq!(intersections(10.0, Default::default(), 1).len(), 1);
+
+        assert_eq!(
+            intersections(
+                10.0,
+                InteractionGroups::new(BitMask(u32::MAX), BitMask(0b10)),
+                usize::MAX
+            ),
+            vec![platform]
+        );
+
+        let mut colliders = Vec::new();
+        graph.physics2d.intersections_with_shape(
+            ShapeIntersectionOptions {
+                shape: ColliderShape::ball(1.0),
+                shape_position: Isometry2::translation(0.0, 1.5),
+                groups: Default::default(),
+                exclude_sensors: false,
+            },
+            |collider| {
+                colliders.push(collider);
+                true
+            },
+        );
+        colliders.sort_by_key(|c| c.index());
+        assert_eq!(colliders, vec![sensor]);
+    }
+
+    #[test]
+    fn test_project_point_2d() {
+        let mut graph = Graph::new();
+        let (ground, sensor) = add_ground_with_sensor(&mut graph);
+
+        let project = |point: Point2<f32>, solid: bool, groups: InteractionGroups| {
+            graph.physics2d.project_point(PointProjectionOptions {
+                point,
+                solid,
+                groups,
+                exclude_sensors: true,
+            })
+        };
+
+        let outside = Point2::new(1.0, -2.0);
+        let projection = project(outside, true, Default::default())
+            .expect("The point must be projected on the ground!");
+        assert_eq!(projection.collider, ground);
+        assert!((projection.position - Point2::new(1.0, -1.0)).norm() < 0.001);
+        assert!(!projection.is_inside);
+
+        // Solid projection keeps the point that is inside.
+        let inside = Point2::new(1.0, -0.25);
+        let projection = project(inside, true, Default::default())
+            .expect("The point must be projected on the ground!");
+        assert!((projection.position - inside).norm() < 0.001);
+        assert!(projection.is_inside);
+
+        // Otherwise the point is projected on the closest edge.
+        let projection = project(inside, false, Default::default())
+            .expect("The point must be projected on the ground!");
+        assert!((projection.position - Point2::new(1.0, 0.0)).norm() < 0.001);
+        assert!(projection.is_inside);
+
+        assert!(project(
+            outside,
+            true,
+            InteractionGroups::new(BitMask(u32::MAX), BitMask(0b10))
+        )
+        .is_none());
+
+        // The sensor is the closest one when it is not excluded.
+        let above = Point2::new(1.0, 2.0);
+        assert_eq!(
+            project(above, true, Default::default()).map(|p| p.collider),
+            Some(ground)
+        );
+        let projection = graph
+            .physics2d
+            .project_point(PointProjectionOptions {
+                point: above,
+                solid: true,
+                groups: Default::default(),
+                exclude_sensors: false,
+            })
+            .expect("The point must be projected on the sensor!");
+        assert_eq!(projection.collider, sensor);
+        assert!((projection.position - Point2::new(1.0, 1.0)).norm() < 0.001);
+    }
+
+    fn add_ball(
+        graph: &mut Graph,
+        position: Vector2<f32>,
+        body_type: RigidBodyType,
+        sensor: bool,
+    ) -> Handle<Node> {
+        let collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::ball(1.0))
+            .with_sensor(sensor)
+            .build(graph);
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(position.x, position.y, 0.0))
+                        .build(),
+                )
+                .with_children(&[collider]),
+        )
+        .with_body_type(body_type)
+        .with_gravity_scale(0.0)
+        .build(graph);
+        collider
+    }
+
+    fn find_event(
+        graph: &Graph,
+        collider: Handle<Node>,
+        other: Handle<Node>,
+    ) -> Option<CollisionEvent> {
+        graph
+            .physics2d
+            .collision_events()
+            .iter()
+            .find(|e| e.collider == collider && e.other == other)
+            .cloned()
+    }
+
+    #[test]
+    fn test_collision_events_2d() {
+        let mut graph = Graph::new();
+        let first = add_ball(&mut graph, Vector2::default(), RigidBodyType::Static, false);
+        let second = add_ball(
+            &mut graph,
+            Vector2::new(1.0, 0.0),
+            RigidBodyType::Dynamic,
+            false,
+        );
+        // Intersects both balls, but collisions between fixed bodies are not reported.
+        let sensor = add_ball(
+            &mut graph,
+            Vector2::new(1.0, -1.5),
+            RigidBodyType::Static,
+            true,
+        );
+        sync(&mut graph);
+
+        let event = find_event(&graph, first, second).expect("Contact must be started!");
+        assert_eq!(event.kind, CollisionEventKind::ContactStarted);
+        assert!(!event.contacts.is_empty());
+        for contact in event.contacts.iter() {
+            assert!((contact.position - Point2::new(1.0, 0.0)).norm() < 0.01);
+            assert!((contact.normal - Vector2::x()).norm() < 0.01);
+            assert!(contact.dist < 0.0);
+        }
+
+        let event = find_event(&graph, second, first).expect("Contact must be started!");
+        assert_eq!(event.kind, CollisionEventKind::ContactStarted);
+        assert!(!event.contacts.is_empty());
+        for contact in event.contacts.iter() {
+            // The contact point is on the surface of the second ball, that is shifted by the
+            // penetration depth.
+            assert!((contact.position - Point2::new(1.0 + contact.dist, 0.0)).norm() < 0.01);
+            assert!((contact.normal + Vector2::x()).norm() < 0.01);
+        }
+
+        for (collider, other) in [(sensor, second), (second, sensor)] {
+            let event = find_event(&graph, collider, other).expect("Intersection must be started!");
+            assert_eq!(event.kind, CollisionEventKind::IntersectionStarted);
+            assert!(event.contacts.is_empty());
+        }
+        assert!(find_event(&graph, sensor, first).is_none());
+
+        // Removed collider does not receive events, but the remaining ones do.
+        graph.remove_node(second);
+        graph.update_physics();
+
+        assert_eq!(
+            find_event(&graph, first, second).map(|e| e.kind),
+            Some(CollisionEventKind::ContactStopped)
+        );
+        assert_eq!(
+            find_event(&graph, sensor, second).map(|e| e.kind),
+            Some(CollisionEventKind::IntersectionStopped)
+        );
+        assert!(graph
+            .physics2d
+            .collision_events()
+            .iter()
+            .all(|e| e.collider != second));
+    }
+}
@@ -48,6 +48,7 @@ use crate::{
         mesh::Mesh,
         node::{container::NodeContainer, Node, SyncContext, UpdateContext},
         pivot::Pivot,
+        ragdoll::Ragdoll,
         sound::context::SoundContext,
         transform::TransformBuilder,
     },
@@ -139,6 +140,12 @@ pub struct Graph {
     pub(crate) script_message_sender: Sender<ScriptMessage>,
     #[reflect(hidden)]
     pub(crate) script_message_receiver: Receiver<ScriptMessage>,
+
+    // Handles of ragdolls that were alive during the last update, it allows to update ragdolls
+    // without scanning the whole graph.
+    #[inspect(skip)]
+    #[reflect(hidden)]
+    pub(crate) ragdolls: Vec<Handle<Node>>,
 }
 
 impl Default for Graph {
@@ -156,6 +163,7 @@ impl Default for Graph {
             event_broadcaster: Default::default(),
             script_message_receiver: rx,
             script_message_sender: tx,
+            ragdolls: Default::default(),
         }
     }
 }
@@ -230,6 +238,7 @@ impl Graph {
             event_broadcaster: Default::default(),
             script_message_receiver: rx,
             script_message_sender: tx,
+            ragdolls: Default::default(),
         }
     }
 
@@ -927,6 +936,17 @@ impl Graph {
 
     /// Updates nodes in graph using given delta time. There is no need to call it manually.
     pub fn update(&mut self, frame_size: Vector2<f32>, dt: f32) {
+        self.update_internal(frame_size, dt, true)
+    }
+
+    /// Synchronizes scene nodes with physics worlds and performs a single simulation step of both
+    /// 3D and 2D physics. The engine calls it at fixed rate, there is no need to call it manually.
+    pub fn update_physics(&mut self) {
+        self.sync_with_physics();
+        self.step_physics();
+    }
+
+    fn sync_with_physics(&mut self) {
         let last_time = instant::Instant::now();
         self.update_hierarchical_data();
         self.performance_statistics.hierarchical_properties_time =
@@ -935,7 +955,9 @@ impl Graph {
         let last_time = instant::Instant::now();
         self.sync_native();
         self.performance_statistics.sync_time = instant::Instant::now() - last_time;
+    }
 
+    fn step_physics(&mut self) {
         self.physics.performance_statistics.reset();
         self.physics.update();
         self.performance_statistics.physics = self.physics.performance_statistics.clone();
@@ -943,10 +965,25 @@ impl Graph {
         self.physics2d.performance_statistics.reset();
         self.physics2d.update();
         self.performance_statistics.physics2d = self.physics2d.performance_statistics.clone();
+    }
+
+    pub(crate) fn update_internal(
+        &mut self,
+        frame_size: Vector2<f32>,
+        dt: f32,
+        step_physics: bool,
+    ) {
+        self.sync_with_physics();
+
+        if step_physics {
+            self.step_physics();
+        }
 
         self.sound_context.update(&self.pool);
         self.performance_statistics.sound_update_time = self.sound_context.full_render_duration();
 
+        self.ragdolls.clear();
+
         for i in 0..self.pool.get_capacity() {
             let mut update_context = UpdateContext {
                 frame_size,
@@ -976,6 +1013,8 @@ impl Graph {
 
                 if !is_alive {
                     self.remove_node(handle);
+                } else if node.cast::<Ragdoll>().is_some() {
+                    self.ragdolls.push(handle);
                 }
             }
         }
@@ -22,7 +22,7 @@ use crate::{
         collider::{self, ColliderShape, GeometrySource},
         debug::SceneDrawingContext,
         graph::{isometric_global_transform, NodePool},
-        joint::JointParams,
+        joint::{JointAxisParams, JointMotor, JointMotorModel, JointParams},
         mesh::{
             buffer::{VertexAttributeUsage, VertexReadTrait},
             Mesh,
@@ -36,19 +36,23 @@ use crate::{
         raw_mesh::{RawMeshBuilder, RawVertex},
     },
 };
+use fxhash::FxHashMap;
 use fyrox_core::parking_lot::Mutex;
+use rapier3d::parry::{self, query::TOIStatus, shape::Shape};
 use rapier3d::pipeline::{DebugRenderPipeline, QueryFilter};
 use rapier3d::{
     dynamics::{
         CCDSolver, GenericJoint, GenericJointBuilder, ImpulseJointHandle, ImpulseJointSet,
-        IslandManager, JointAxesMask, MultibodyJointHandle, MultibodyJointSet, RigidBody,
-        RigidBodyActivation, RigidBodyBuilder, RigidBodyHandle, RigidBodySet, RigidBodyType,
+        IslandManager, JointAxesMask, MotorModel, MultibodyJointHandle, MultibodyJointSet,
+        RigidBody, RigidBodyActivation, RigidBodyBuilder, RigidBodyHandle, RigidBodySet,
+        RigidBodyType,
     },
     geometry::{
-        BroadPhase, Collider, ColliderBuilder, ColliderHandle, ColliderSet, Cuboid,
-        InteractionGroups, NarrowPhase, Ray, SharedShape,
+        BroadPhase, Collider, ColliderBuilder, ColliderHandle, ColliderSet,
+        CollisionEvent as NativeCollisionEvent, CollisionEventFlags,
+        ContactPair as NativeContactPair, Cuboid, InteractionGroups, NarrowPhase, Ray, SharedShape,
     },
-    pipeline::{EventHandler, PhysicsPipeline, QueryPipeline},
+    pipeline::{ActiveEvents, EventHandler, PhysicsPipeline, QueryPipeline},
     prelude::JointAxis,
 };
 use std::{
@@ -221,6 +225,93 @@ pub struct Intersection {
     pub toi: f32,
 }
 
+/// A shape cast result.
+#[derive(Debug, Clone)]
+pub struct ShapeCastResult {
+    /// A handle of the collider with which the cast shape collided first.
+    pub collider: Handle<Node>,
+
+    /// Distance that the shape has traveled along the cast direction before the collision.
+    pub toi: f32,
+
+    /// A contact point on the collider in world coordinates.
+    pub position: Point3<f32>,
+
+    /// An outward normal of the collider at the contact point in world coordinates.
+    pub normal: Vector3<f32>,
+
+    /// True if the shape was already intersecting the collider at the start of the cast. In this
+    /// case `toi` is zero and the contact data is unreliable.
+    pub penetrating: bool,
+}
+
+/// A set of options for the shape cast.
+pub struct ShapeCastOptions {
+    /// A shape to cast. Shapes that are built from scene geometry (triangle mesh, height field
+    /// and polyhedron) cannot be cast.
+    pub shape: ColliderShape,
+
+    /// Initial position and orientation of the shape in world coordinates.
+    pub shape_position: Isometry3<f32>,
+
+    /// A cast direction. Can be non-normalized.
+    pub direction: Vector3<f32>,
+
+    /// Maximum distance of cast.
+    pub max_len: f32,
+
+    /// Groups to check.
+    pub groups: collider::InteractionGroups,
+
+    /// Whether to ignore sensors.
+    pub exclude_sensors: bool,
+}
+
+/// A set of options for the shape intersection query.
+pub struct ShapeIntersectionOptions {
+    /// A shape to check. Shapes that are built from scene geometry (triangle mesh, height field
+    /// and polyhedron) cannot be used.
+    pub shape: ColliderShape,
+
+    /// Position and orientation of the shape in world coordinates.
+    pub shape_position: Isometry3<f32>,
+
+    /// Groups to check.
+    pub groups: collider::InteractionGroups,
+
+    /// Whether to ignore sensors.
+    pub exclude_sensors: bool,
+}
+
+/// A set of options for the point projection.
+pub struct PointProjectionOptions {
+    /// A point to project in world coordinates.
+    pub point: Point3<f32>,
+
+    /// If true, a point that is inside a collider is projected on itself, otherwise it is
+    /// projected on the boundary of the collider.
+    pub solid: bool,
+
+    /// Groups to check.
+    pub groups: collider::InteractionGroups,
+
+    /// Whether to ignore sensors.
+    pub exclude_sensors: bool,
+}
+
+/// A result of point projection.
+#[derive(Debug, Clone)]
+pub struct PointProjection {
+    /// A handle of the collider on which the point was projected.
+    pub collider: Handle<Node>,
+
+    /// Projected point in world coordinates.
+    pub position: Point3<f32>,
+
+    /// True if the original point was inside the collider.
+    pub is_inside: bool,
+}
+
 /// A set of options for the ray cast.
 pub struct RayCastOptions {
     /// A ray origin.
@@ -289,6 +380,24 @@ impl<const CAP: usize> QueryResultsStorage for ArrayVec<Intersection, CAP> {
     }
 }
 
+/// A set of parameters for [`PhysicsWorld::move_character`].
+pub(crate) struct CharacterMoveParams {
+    pub groups: collider::InteractionGroups,
+    pub max_slope_angle: f32,
+    pub max_step_height: f32,
+    pub snap_to_ground_distance: f32,
+    pub offset: f32,
+    pub was_grounded: bool,
+}
+
+/// A result of [`PhysicsWorld::move_character`].
+pub(crate) struct CharacterMoveResult {
+    pub position: Vector3<f32>,
+    pub grounded: bool,
+    pub ground_normal: Vector3<f32>,
+    pub ground_collider: Handle<Node>,
+}
+
 /// Data of the contact.
 pub struct ContactData {
     /// The contact point in the local-space of the first shape.
@@ -334,6 +443,77 @@ pub struct ContactPair {
     pub has_any_active_contact: bool,
 }
 
+/// A kind of [`CollisionEvent`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CollisionEventKind {
+    /// Colliders started touching each other.
+    ContactStarted,
+    /// Colliders stopped touching each other.
+    ContactStopped,
+    /// Colliders started intersecting each other, at least one of them is a sensor.
+    IntersectionStarted,
+    /// Colliders stopped intersecting each other, at least one of them is a sensor.
+    IntersectionStopped,
+}
+
+/// A contact point of a [`CollisionEvent`].
+#[derive(Copy, Clone, Debug)]
+pub struct CollisionContact {
+    /// The contact point on the collider in world coordinates.
+    pub position: Point3<f32>,
+    /// The contact normal in world coordinates, it points from the collider to the other collider.
+    pub normal: Vector3<f32>,
+    /// The distance between the colliders at the contact point, negative if they penetrate.
+    pub dist: f32,
+}
+
+/// Collision event is generated when two colliders start or stop touching (or intersecting, if one
+/// of them is a sensor) each other. Every event is generated twice - once for each collider of a
+/// pair, so `collider` is always the collider the event is about and `other` is the second one.
+/// The only exception is removal of a collider, in this case only the remaining collider receives
+/// a stop event.
+#[derive(Clone, Debug)]
+pub struct CollisionEvent {
+    /// A kind of the event.
+    pub kind: CollisionEventKind,
+    /// A handle of the collider the event is about.
+    pub collider: Handle<Node>,
+    /// A handle of the other collider of the pair. If the other collider was removed (the event
+    /// is a stop event then), the handle is no longer valid.
+    pub other: Handle<Node>,
+    /// Contact points between the colliders. It is filled only for
+    /// [`CollisionEventKind::ContactStarted`] events.
+    pub contacts: Vec<CollisionContact>,
+}
+
+// Collects collision events generated by the physics pipeline during a step.
+#[derive(Default)]
+struct CollisionEventCollector {
+    events: Mutex<Vec<NativeCollisionEvent>>,
+}
+
+impl EventHandler for CollisionEventCollector {
+    fn handle_collision_event(
+        &self,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        event: NativeCollisionEvent,
+        _contact_pair: Option<&NativeContactPair>,
+    ) {
+        self.events.lock().push(event);
+    }
+
+    fn handle_contact_force_event(
+        &self,
+        _dt: f32,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        _contact_pair: &NativeContactPair,
+        _total_force_magnitude: f32,
+    ) {
+    }
+}
+
 pub(super) struct Container<S, A>
 where
     A: Hash + Eq + Clone,
@@ -342,6 +522,36 @@ where
     map: BiDirHashMap<A, Handle<Node>>,
 }
 
+fn set_joint_motor(joint: &mut GenericJoint, axis: JointAxis, motor: &JointMotor) {
+    if motor.enabled {
+        joint
+            .set_motor(
+                axis,
+                motor.target_position,
+                motor.target_velocity,
+                motor.stiffness,
+                motor.damping,
+            )
+            .set_motor_max_force(axis, motor.max_force)
+            .set_motor_model(
+                axis,
+                match motor.model {
+                    JointMotorModel::AccelerationBased => MotorModel::AccelerationBased,
+                    JointMotorModel::ForceBased => MotorModel::ForceBased,
+                },
+            );
+    }
+}
+
+fn set_joint_axis(joint: &mut GenericJoint, axis: JointAxis, params: &JointAxisParams) {
+    if !params.locked {
+        if params.limits_enabled {
+            joint.set_limits(axis, [params.limits.start, params.limits.end]);
+        }
+        set_joint_motor(joint, axis, &params.motor);
+    }
+}
+
 fn convert_joint_params(
     params: scene::joint::JointParams,
     local_frame1: Isometry3<f32>,
@@ -352,6 +562,23 @@ fn convert_joint_params(
         JointParams::FixedJoint(_) => JointAxesMask::LOCKED_FIXED_AXES,
         JointParams::PrismaticJoint(_) => JointAxesMask::LOCKED_PRISMATIC_AXES,
         JointParams::RevoluteJoint(_) => JointAxesMask::LOCKED_REVOLUTE_AXES,
+        JointParams::RopeJoint(_) | JointParams::SpringJoint(_) => JointAxesMask::empty(),
+        JointParams::GenericJoint(ref v) => {
+            let mut mask = JointAxesMask::empty();
+            for (axis, params) in [
+                (JointAxesMask::X, &v.x),
+                (JointAxesMask::Y, &v.y),
+                (JointAxesMask::Z, &v.z),
+                (JointAxesMask::ANG_X, &v.angular_x),
+                (JointAxesMask::ANG_Y, &v.angular_y),
+                (JointAxesMask::ANG_Z, &v.angular_z),
+            ] {
+                if params.locked {
+                    mask |= axis;
+                }
+            }
+            mask
+        }
     };
 
     let mut joint = GenericJointBuilder::new(locked_axis)
@@ -379,17 +606,44 @@ fn convert_joint_params(
                     [v.z_limits_angles.start, v.z_limits_angles.end],
                 );
             }
+            set_joint_motor(&mut joint, JointAxis::AngX, &v.x_motor);
+            set_joint_motor(&mut joint, JointAxis::AngY, &v.y_motor);
+            set_joint_motor(&mut joint, JointAxis::AngZ, &v.z_motor);
         }
         scene::joint::JointParams::FixedJoint(_) => {}
         scene::joint::JointParams::PrismaticJoint(v) => {
             if v.limits_enabled {
                 joint.set_limits(JointAxis::X, [v.limits.start, v.limits.end]);
             }
+            set_joint_motor(&mut joint, JointAxis::X, &v.motor);
         }
         scene::joint::JointParams::RevoluteJoint(v) => {
             if v.limits_enabled {
                 joint.set_limits(JointAxis::AngX, [v.limits.start, v.limits.end]);
             }
+            set_joint_motor(&mut joint, JointAxis::AngX, &v.motor);
+        }
+        scene::joint::JointParams::RopeJoint(v) => {
+            // Coupled limits restrict the length of the vector between the anchors, the solver
+            // uses the length of the vector of per-axis limits as the max distance.
+            joint.coupled_axes = JointAxesMask::X | JointAxesMask::Y | JointAxesMask::Z;
+            let max = v.max_length.max(0.0) / 3.0f32.sqrt();
+            for axis in [JointAxis::X, JointAxis::Y, JointAxis::Z] {
+                joint.set_limits(axis, [0.0, max]);
+            }
+        }
+        scene::joint::JointParams::SpringJoint(v) => {
+            for axis in [JointAxis::X, JointAxis::Y, JointAxis::Z] {
+                joint.set_motor(axis, 0.0, 0.0, v.stiffness, v.damping);
+            }
+        }
+        scene::joint::JointParams::GenericJoint(v) => {
+            set_joint_axis(&mut joint, JointAxis::X, &v.x);
+            set_joint_axis(&mut joint, JointAxis::Y, &v.y);
+            set_joint_axis(&mut joint, JointAxis::Z, &v.z);
+            set_joint_axis(&mut joint, JointAxis::AngX, &v.angular_x);
+            set_joint_axis(&mut joint, JointAxis::AngY, &v.angular_y);
+            set_joint_axis(&mut joint, JointAxis::AngZ, &v.angular_z);
         }
     }
 
@@ -616,13 +870,8 @@ fn make_heightfield(terrain: &Terrain) -> SharedShape {
     )
 }
 
-// Converts descriptor in a shared shape.
-fn collider_shape_into_native_shape(
-    shape: &ColliderShape,
-    owner_inv_global_transform: Matrix4<f32>,
-    owner_collider: Handle<Node>,
-    pool: &NodePool,
-) -> Option<SharedShape> {
+// Converts descriptor of a shape that does not depend on scene geometry in a shared shape.
+fn primitive_shape_into_native_shape(shape: &ColliderShape) -> Option<SharedShape> {
     match shape {
         ColliderShape::Ball(ball) => Some(SharedShape::ball(ball.radius)),
 
@@ -647,6 +896,20 @@ fn collider_shape_into_native_shape(
             Point3::from(triangle.b),
             Point3::from(triangle.c),
         )),
+        ColliderShape::Trimesh(_)
+        | ColliderShape::Heightfield(_)
+        | ColliderShape::Polyhedron(_) => None,
+    }
+}
+
+// Converts descriptor in a shared shape.
+fn collider_shape_into_native_shape(
+    shape: &ColliderShape,
+    owner_inv_global_transform: Matrix4<f32>,
+    owner_collider: Handle<Node>,
+    pool: &NodePool,
+) -> Option<SharedShape> {
+    match shape {
         ColliderShape::Trimesh(trimesh) => {
             if trimesh.sources.is_empty() {
                 None
@@ -667,6 +930,7 @@ fn collider_shape_into_native_shape(
             .try_borrow(polyhedron.geometry_source.0)
             .and_then(|n| n.cast::<Mesh>())
             .map(|mesh| make_polyhedron_shape(owner_inv_global_transform, mesh)),
+        _ => primitive_shape_into_native_shape(shape),
     }
 }
 
@@ -900,7 +1164,17 @@ pub struct PhysicsWorld {
     #[visit(skip)]
     #[inspect(skip)]
     #[reflect(hidden)]
-    event_handler: Box<dyn EventHandler>,
+    event_handler: CollisionEventCollector,
+    // Collision events generated during the last step.
+    #[visit(skip)]
+    #[inspect(skip)]
+    #[reflect(hidden)]
+    collision_events: Vec<CollisionEvent>,
+    // Colliders that were removed since the last step, their owners still must receive stop events.
+    #[visit(skip)]
+    #[inspect(skip)]
+    #[reflect(hidden)]
+    removed_colliders: FxHashMap<ColliderHandle, Handle<Node>>,
     #[visit(skip)]
     #[inspect(skip)]
     #[reflect(hidden)]
@@ -936,6 +1210,18 @@ fn calculate_local_frames(
     )
 }
 
+fn query_filter<'a>(groups: collider::InteractionGroups, exclude_sensors: bool) -> QueryFilter<'a> {
+    let filter = QueryFilter::new().groups(InteractionGroups::new(
+        groups.memberships.0,
+        groups.filter.0,
+    ));
+    if exclude_sensors {
+        filter.exclude_sensors()
+    } else {
+        filter
+    }
+}
+
 impl PhysicsWorld {
     /// Creates a new instance of the physics world.
     pub(super) fn new() -> Self {
@@ -964,7 +1250,9 @@ impl PhysicsWorld {
                 set: MultibodyJointSet::new(),
                 map: Default::default(),
             },
-            event_handler: Box::new(()),
+            event_handler: Default::default(),
+            collision_events: Default::default(),
+            removed_colliders: Default::default(),
             query: RefCell::new(Default::default()),
             performance_statistics: Default::default(),
             debug_render_pipeline: Default::default(),
@@ -1014,13 +1302,101 @@ impl PhysicsWorld {
                 &mut self.multibody_joints.set,
                 &mut self.ccd_solver,
                 &(),
-                &*self.event_handler,
+                &self.event_handler,
             );
         }
 
+        self.collision_events.clear();
+        let native_events = std::mem::take(&mut *self.event_handler.events.lock());
+        for event in native_events {
+            self.push_collision_event(event);
+        }
+        self.removed_colliders.clear();
+
         self.performance_statistics.step_time += instant::Instant::now() - time;
     }
 
+    fn push_collision_event(&mut self, event: NativeCollisionEvent) {
+        let (handle1, handle2, flags, started) = match event {
+            NativeCollisionEvent::Started(h1, h2, flags) => (h1, h2, flags, true),
+            NativeCollisionEvent::Stopped(h1, h2, flags) => (h1, h2, flags, false),
+        };
+
+        // A stop event is generated when a collider is removed, it is delivered to the other
+        // collider only.
+        let find = |handle: ColliderHandle| match self.colliders.map.value_of(&handle) {
+            Some(collider) => Some((*collider, true)),
+            None if flags.contains(CollisionEventFlags::REMOVED) => {
+                self.removed_colliders.get(&handle).map(|c| (*c, false))
+            }
+            None => None,
+        };
+        let ((collider1, alive1), (collider2, alive2)) = match (find(handle1), find(handle2)) {
+            (Some(first), Some(second)) => (first, second),
+            _ => return,
+        };
+
+        let kind = match (flags.contains(CollisionEventFlags::SENSOR), started) {
+            (false, true) => CollisionEventKind::ContactStarted,
+            (false, false) => CollisionEventKind::ContactStopped,
+            (true, true) => CollisionEventKind::IntersectionStarted,
+            (true, false) => CollisionEventKind::IntersectionStopped,
+        };
+
+        let mut contacts1 = Vec::new();
+        let mut contacts2 = Vec::new();
+        if kind == CollisionEventKind::ContactStarted {
+            if let Some(pair) = self.narrow_phase.contact_pair(handle1, handle2) {
+                if let (Some(native1), Some(native2)) = (
+                    self.colliders.set.get(pair.collider1),
+                    self.colliders.set.get(pair.collider2),
+                ) {
+                    for manifold in pair.manifolds.iter() {
+                        for point in manifold.points.iter() {
+                            contacts1.push(CollisionContact {
+                                position: native1.position() * point.local_p1,
+                                normal: manifold.data.normal,
+                                dist: point.dist,
+                            });
+                            contacts2.push(CollisionContact {
+                                position: native2.position() * point.local_p2,
+                                normal: -manifold.data.normal,
+                                dist: point.dist,
+                            });
+                        }
+                    }
+                }
+
+                // Pair could be stored in the reversed order.
+                if pair.collider1 != handle1 {
+                    std::mem::swap(&mut contacts1, &mut contacts2);
+                }
+            }
+        }
+
+        if alive1 {
+            self.collision_events.push(CollisionEvent {
+                kind,
+                collider: collider1,
+                other: collider2,
+                contacts: contacts1,
+            });
+        }
+        if alive2 {
+            self.collision_events.push(CollisionEvent {
+                kind,
+                collider: collider2,
+                other: collider1,
+                contacts: contacts2,
+            });
+        }
+    }
+
+    /// Returns collision events that were generated during the last simulation step.
+    pub fn collision_events(&self) -> &[CollisionEvent] {
+        &self.collision_events
+    }
+
     pub(super) fn add_body(&mut self, owner: Handle<Node>, body: RigidBody) -> RigidBodyHandle {
         let handle = self.bodies.set.insert(body);
         self.bodies.map.insert(handle, owner);
@@ -1029,6 +1405,14 @@ impl PhysicsWorld {
 
     pub(crate) fn remove_body(&mut self, handle: RigidBodyHandle) {
         assert!(self.bodies.map.remove_by_key(&handle).is_some());
+        // Attached colliders are removed with the body.
+        if let Some(body) = self.bodies.set.get(handle) {
+            for collider in body.colliders() {
+                if let Some(owner) = self.colliders.map.remove_by_key(collider) {
+                    self.removed_colliders.insert(*collider, owner);
+                }
+            }
+        }
         self.bodies.set.remove(
             handle,
             &mut self.islands,
@@ -1060,7 +1444,8 @@ impl PhysicsWorld {
             .remove(handle, &mut self.islands, &mut self.bodies.set, false)
             .is_some()
         {
-            assert!(self.colliders.map.remove_by_key(&handle).is_some());
+            let owner = self.colliders.map.remove_by_key(&handle).unwrap();
+            self.removed_colliders.insert(handle, owner);
             true
         } else {
             false
@@ -1155,6 +1540,301 @@ impl PhysicsWorld {
         );
     }
 
+    /// Sweeps a shape along the given direction and returns the first collider it hits, if any.
+    pub fn cast_shape(&self, opts: ShapeCastOptions) -> Option<ShapeCastResult> {
+        let shape = primitive_shape_into_native_shape(&opts.shape)?;
+
+        let mut query = self.query.borrow_mut();
+        query.update(&self.islands, &self.bodies.set, &self.colliders.set);
+
+        let direction = opts
+            .direction
+            .try_normalize(f32::EPSILON)
+            .unwrap_or_default();
+        query
+            .cast_shape(
+                &self.bodies.set,
+                &self.colliders.set,
+                &opts.shape_position,
+                &direction,
+                &*shape,
+                opts.max_len,
+                query_filter(opts.groups, opts.exclude_sensors),
+            )
+            .map(|(handle, toi)| {
+                // Casts of support map shapes report a tiny time of impact instead of penetration
+                // if the shape starts inside of a collider, so check the initial position explicitly.
+                let collider = &self.colliders.set[handle];
+                let penetrating = toi.status == TOIStatus::Penetrating
+                    || parry::query::intersection_test(
+                        &opts.shape_position,
+                        &*shape,
+                        collider.position(),
+                        collider.shape(),
+                    )
+                    .unwrap_or_default();
+                ShapeCastResult {
+                    collider: self.colliders.map.value_of(&handle).cloned().unwrap(),
+                    toi: if penetrating { 0.0 } else { toi.toi },
+                    position: toi.witness1,
+                    normal: *toi.normal1,
+                    penetrating,
+                }
+            })
+    }
+
+    /// Calls the given callback for every collider that intersects the given shape placed at the
+    /// given position. The callback must return `false` to stop the search. Shapes that are built
+    /// from scene geometry (triangle mesh, height field and polyhedron) cannot be used.
+    pub fn intersections_with_shape<C>(&self, opts: ShapeIntersectionOptions, mut callback: C)
+    where
+        C: FnMut(Handle<Node>) -> bool,
+    {
+        if let Some(shape) = primitive_shape_into_native_shape(&opts.shape) {
+            let mut query = self.query.borrow_mut();
+            query.update(&self.islands, &self.bodies.set, &self.colliders.set);
+
+            query.intersections_with_shape(
+                &self.bodies.set,
+                &self.colliders.set,
+                &opts.shape_position,
+                &*shape,
+                query_filter(opts.groups, opts.exclude_sensors),
+                |handle| callback(self.colliders.map.value_of(&handle).cloned().unwrap()),
+            );
+        }
+    }
+
+    /// Projects a point on the closest collider.
+    pub fn project_point(&self, opts: PointProjectionOptions) -> Option<PointProjection> {
+        let mut query = self.query.borrow_mut();
+        query.update(&self.islands, &self.bodies.set, &self.colliders.set);
+
+        query
+            .project_point(
+                &self.bodies.set,
+                &self.colliders.set,
+                &opts.point,
+                opts.solid,
+                query_filter(opts.groups, opts.exclude_sensors),
+            )
+            .map(|(handle, projection)| PointProjection {
+                collider: self.colliders.map.value_of(&handle).cloned().unwrap(),
+                position: projection.point,
+                is_inside: projection.is_inside,
+            })
+    }
+
+    /// Returns current position of a rigid body to which the given collider is attached.
+    pub(crate) fn collider_body_position(&self, collider: Handle<Node>) -> Option<Isometry3<f32>> {
+        self.colliders
+            .map
+            .key_of(&collider)
+            .and_then(|h| self.colliders.set.get(*h))
+            .and_then(|c| c.parent())
+            .and_then(|h| self.bodies.set.get(h))
+            .map(|b| *b.position())
+    }
+
+    /// Moves a shape by the given translation, the shape slides along obstacles, climbs steps
+    /// and snaps to the ground. `ignore` allows to exclude colliders (usually colliders of the
+    /// character itself) from the collision detection.
+    pub(crate) fn move_character(
+        &self,
+        shape: &dyn Shape,
+        mut position: Vector3<f32>,
+        translation: Vector3<f32>,
+        params: &CharacterMoveParams,
+        ignore: &dyn Fn(Handle<Node>) -> bool,
+    ) -> CharacterMoveResult {
+        const MAX_ITERATIONS: usize = 5;
+        const EPSILON: f32 = 1.0e-5;
+
+        let up = Vector3::y();
+        let min_ground_cos = params.max_slope_angle.cos();
+        let offset = params.offset;
+
+        let mut query = self.query.borrow_mut();
+        query.update(&self.islands, &self.bodies.set, &self.colliders.set);
+
+        let predicate = |handle: ColliderHandle, _: &Collider| {
+            self.colliders
+                .map
+                .value_of(&handle)
+                .map_or(true, |node| !ignore(*node))
+        };
+        let filter = QueryFilter::new()
+            .exclude_sensors()
+            .groups(InteractionGroups::new(
+                params.groups.memberships.0,
+                params.groups.filter.0,
+            ))
+            .predicate(&predicate);
+
+        let cast = |from: Vector3<f32>, translation: Vector3<f32>| {
+            let distance = translation.norm();
+            if distance < EPSILON {
+                return None;
+            }
+            query
+                .cast_shape(
+                    &self.bodies.set,
+                    &self.colliders.set,
+                    &Isometry3::translation(from.x, from.y, from.z),
+                    &translation.scale(1.0 / distance),
+                    shape,
+                    distance,
+                    filter,
+                )
+                .map(|(handle, toi)| {
+                    let normal = if toi.status == TOIStatus::Penetrating {
+                        -translation.scale(1.0 / distance)
+                    } else {
+                        toi.normal1.into_inner()
+                    };
+                    (handle, toi.toi, normal)
+                })
+        };
+
+        // Push the shape out of the obstacles first, otherwise casts will report zero time of impact.
+        let mut penetrations = ArrayVec::<ColliderHandle, 8>::new();
+        query.intersections_with_shape(
+            &self.bodies.set,
+            &self.colliders.set,
+            &Isometry3::translation(position.x, position.y, position.z),
+            shape,
+            filter,
+            |handle| penetrations.try_push(handle).is_ok(),
+        );
+        for handle in penetrations {
+            let collider = &self.colliders.set[handle];
+            if let Ok(Some(contact)) = parry::query::contact(
+                &Isometry3::translation(position.x, position.y, position.z),
+                shape,
+                collider.position(),
+                collider.shape(),
+                0.0,
+            ) {
+                if contact.dist < 0.0 {
+                    position += contact.normal1.scale(contact.dist - offset);
+                }
+            }
+        }
+
+        let mut grounded = false;
+        let mut ground_normal = up;
+        let mut ground_collider = None;
+
+        let try_step = |position: Vector3<f32>, horizontal: Vector3<f32>| {
+            let up_distance = match cast(position, up.scale(params.max_step_height + offset)) {
+                Some((_, toi, _)) => (toi - offset).max(0.0),
+                None => params.max_step_height,
+            };
+            if up_distance < EPSILON {
+                return None;
+            }
+            let raised = position + up.scale(up_distance);
+
+            let length = horizontal.norm();
+            let direction = horizontal.scale(1.0 / length);
+            let forward_distance = match cast(raised, horizontal + direction.scale(offset)) {
+                Some((_, toi, _)) => (toi - offset).max(0.0),
+                None => length,
+            };
+            if forward_distance < EPSILON {
+                return None;
+            }
+            let moved = raised + direction.scale(forward_distance);
+
+            // Step is valid only if there's walkable ground below.
+            match cast(moved, -up.scale(up_distance + offset)) {
+                Some((handle, toi, normal)) if normal.dot(&up) >= min_ground_cos => {
+                    Some((moved - up.scale((toi - offset).max(0.0)), handle, normal))
+                }
+                _ => None,
+            }
+        };
+
+        let mut remaining = translation;
+        for _ in 0..MAX_ITERATIONS {
+            let length = remaining.norm();
+            if length < EPSILON {
+                break;
+            }
+            let direction = remaining.scale(1.0 / length);
+
+            let (handle, toi, normal) = match cast(position, remaining + direction.scale(offset)) {
+                Some(hit) => hit,
+                None => {
+                    position += remaining;
+                    break;
+                }
+            };
+
+            let travel = (toi - offset).clamp(0.0, length);
+            position += direction.scale(travel);
+            remaining = direction.scale(length - travel);
+
+            let walkable = normal.dot(&up) >= min_ground_cos;
+            if walkable {
+                grounded = true;
+                ground_normal = normal;
+                ground_collider = Some(handle);
+            } else if params.max_step_height > 0.0 && (params.was_grounded || grounded) {
+                let horizontal = remaining - up.scale(remaining.dot(&up));
+                if horizontal.norm() > EPSILON {
+                    if let Some((stepped, handle, normal)) = try_step(position, horizontal) {
+                        position = stepped;
+                        grounded = true;
+                        ground_normal = normal;
+                        ground_collider = Some(handle);
+                        remaining = up.scale(remaining.dot(&up).max(0.0));
+                        continue;
+                    }
+                }
+            }
+
+            // Slide along the obstacle.
+            let into_surface = remaining.dot(&normal);
+            if into_surface < 0.0 {
+                remaining -= normal.scale(into_surface);
+            }
+
+            // Steep slopes cannot be climbed, they act as walls.
+            if !walkable && translation.dot(&up) <= 0.0 {
+                let up_component = remaining.dot(&up);
+                if up_component > 0.0 {
+                    remaining -= up.scale(up_component);
+                }
+            }
+        }
+
+        if !grounded && translation.dot(&up) <= EPSILON {
+            let probe = if params.was_grounded {
+                params.snap_to_ground_distance.max(2.0 * offset)
+            } else {
+                2.0 * offset
+            };
+            if let Some((handle, toi, normal)) = cast(position, -up.scale(probe + offset)) {
+                if normal.dot(&up) >= min_ground_cos {
+                    position -= up.scale((toi - offset).max(0.0));
+                    grounded = true;
+                    ground_normal = normal;
+                    ground_collider = Some(handle);
+                }
+            }
+        }
+
+        CharacterMoveResult {
+            position,
+            grounded,
+            ground_normal,
+            ground_collider: ground_collider
+                .and_then(|h| self.colliders.map.value_of(&h).cloned())
+                .unwrap_or_default(),
+        }
+    }
+
     pub(crate) fn set_rigid_body_position(
         &mut self,
         rigid_body: &scene::rigidbody::RigidBody,
@@ -1453,7 +2133,8 @@ impl PhysicsWorld {
                             collider_node.solver_groups().memberships.0,
                             collider_node.solver_groups().filter.0,
                         ))
-                        .sensor(collider_node.is_sensor());
+                        .sensor(collider_node.is_sensor())
+                        .active_events(ActiveEvents::COLLISION_EVENTS);
 
                     if let Some(density) = collider_node.density() {
                         builder = builder.density(density);
@@ -1626,3 +2307,572 @@ impl Debug for PhysicsWorld {
         write!(f, "PhysicsWorld")
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            algebra::{Isometry3, Point3, UnitQuaternion, Vector3},
+            pool::Handle,
+        },
+        scene::{
+            base::BaseBuilder,
+            collider::{BitMask, ColliderBuilder, ColliderShape, InteractionGroups},
+            graph::{
+                physics::{
+                    CharacterMoveParams, CharacterMoveResult, CollisionEvent, CollisionEventKind,
+                    PointProjectionOptions, ShapeCastOptions, ShapeIntersectionOptions,
+                },
+                Graph,
+            },
+            node::Node,
+            rigidbody::{RigidBodyBuilder, RigidBodyType},
+            transform::TransformBuilder,
+        },
+    };
+    use rapier3d::parry::shape::Capsule;
+
+    // Capsule with total height of 2 meters, standing on the ground (y = 0) its center is at y = 1.
+    const HALF_HEIGHT: f32 = 0.5;
+    const RADIUS: f32 = 0.5;
+    const OFFSET: f32 = 0.01;
+    const STANDING_Y: f32 = HALF_HEIGHT + RADIUS + OFFSET;
+
+    fn add_static_box(
+        graph: &mut Graph,
+        position: Vector3<f32>,
+        rotation: UnitQuaternion<f32>,
+        half_extents: Vector3<f32>,
+    ) -> Handle<Node> {
+        let collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(
+                half_extents.x,
+                half_extents.y,
+                half_extents.z,
+            ))
+            .build(graph);
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(position)
+                        .with_local_rotation(rotation)
+                        .build(),
+                )
+                .with_children(&[collider]),
+        )
+        .with_body_type(RigidBodyType::Static)
+        .build(graph);
+        collider
+    }
+
+    // Adds a static box that spans `begin..end` along X with its top face at the given height.
+    fn add_ground(graph: &mut Graph, begin: f32, end: f32, height: f32) -> Handle<Node> {
+        add_static_box(
+            graph,
+            Vector3::new((begin + end) * 0.5, height - 0.5, 0.0),
+            UnitQuaternion::identity(),
+            Vector3::new((end - begin) * 0.5, 0.5, 20.0),
+        )
+    }
+
+    // Adds a slope that starts at the given point on the ground and rises towards +X.
+    fn add_slope(graph: &mut Graph, x: f32, angle: f32) -> Handle<Node> {
+        let rotation = UnitQuaternion::from_axis_angle(&Vector3::z_axis(), angle);
+        let normal = rotation * Vector3::y();
+        let tangent = rotation * Vector3::x();
+        add_static_box(
+            graph,
+            Vector3::new(x, 0.0, 0.0) - normal.scale(0.5) + tangent.scale(5.0),
+            rotation,
+            Vector3::new(5.0, 0.5, 20.0),
+        )
+    }
+
+    fn sync(graph: &mut Graph) {
+        // Colliders are built before their bodies, so native colliders are created only on the
+        // second sync when native bodies already exist.
+        graph.update_physics();
+        graph.update_physics();
+    }
+
+    fn move_character(
+        graph: &mut Graph,
+        position: Vector3<f32>,
+        translation: Vector3<f32>,
+        was_grounded: bool,
+    ) -> CharacterMoveResult {
+        sync(graph);
+        graph.physics.move_character(
+            &Capsule::new_y(HALF_HEIGHT, RADIUS),
+            position,
+            translation,
+            &CharacterMoveParams {
+                groups: InteractionGroups::default(),
+                max_slope_angle: 45.0f32.to_radians(),
+                max_step_height: 0.3,
+                snap_to_ground_distance: 0.3,
+                offset: OFFSET,
+                was_grounded,
+            },
+            &|_| false,
+        )
+    }
+
+    #[test]
+    fn test_move_character_slides_along_wall() {
+        let mut graph = Graph::new();
+        add_ground(&mut graph, -20.0, 20.0, 0.0);
+        // Wall with its face at x = 1.5
+        add_static_box(
+            &mut graph,
+            Vector3::new(2.0, 5.0, 0.0),
+            UnitQuaternion::identity(),
+            Vector3::new(0.5, 5.0, 20.0),
+        );
+
+        let result = move_character(
+            &mut graph,
+            Vector3::new(0.0, STANDING_Y, 0.0),
+            Vector3::new(2.0, 0.0, 1.0),
+            true,
+        );
+
+        // Blocked by the wall, but keeps moving along it.
+        assert!((result.position.x - (1.0 - OFFSET)).abs() < 0.02);
+        assert!((result.position.z - 1.0).abs() < 0.02);
+        assert!((result.position.y - STANDING_Y).abs() < 0.02);
+        assert!(result.grounded);
+    }
+
+    #[test]
+    fn test_move_character_climbs_step() {
+        let mut graph = Graph::new();
+        add_ground(&mut graph, -20.0, 1.5, 0.0);
+        add_ground(&mut graph, 1.5, 20.0, 0.2);
+
+        let result = move_character(
+            &mut graph,
+            Vector3::new(0.0, STANDING_Y, 0.0),
+            Vector3::new(2.5, 0.0, 0.0),
+            true,
+        );
+
+        assert!((result.position.x - 2.5).abs() < 0.02);
+        assert!((result.position.y - (STANDING_Y + 0.2)).abs() < 0.02);
+        assert!(result.grounded);
+        assert!(result.ground_normal.y > 0.99);
+    }
+
+    #[test]
+    fn test_move_character_blocked_by_high_step() {
+        let mut graph = Graph::new();
+        add_ground(&mut graph, -20.0, 1.5, 0.0);
+        add_ground(&mut graph, 1.5, 20.0, 0.5);
+
+        let result = move_character(
+            &mut graph,
+            Vector3::new(0.0, STANDING_Y, 0.0),
+            Vector3::new(2.5, 0.0, 0.0),
+            true,
+        );
+
+        assert!(result.position.x < 1.5);
+        assert!((result.position.y - STANDING_Y).abs() < 0.02);
+        assert!(result.grounded);
+    }
+
+    #[test]
+    fn test_move_character_steep_slope() {
+        let mut graph = Graph::new();
+        add_ground(&mut graph, -20.0, 20.0, 0.0);
+        add_slope(&mut graph, 2.0, 60.0f32.to_radians());
+
+        let result = move_character(
+            &mut graph,
+            Vector3::new(0.0, STANDING_Y, 0.0),
+            Vector3::new(3.0, 0.0, 0.0),
+            true,
+        );
+
+        // The slope acts as a wall.
+        assert!(result.position.x < 2.0);
+        assert!((result.position.y - STANDING_Y).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_move_character_gentle_slope() {
+        let mut graph = Graph::new();
+        add_ground(&mut graph, -20.0, 20.0, 0.0);
+        add_slope(&mut graph, 2.0, 30.0f32.to_radians());
+
+        let result = move_character(
+            &mut graph,
+            Vector3::new(0.0, STANDING_Y, 0.0),
+            Vector3::new(4.0, 0.0, 0.0),
+            true,
+        );
+
+        assert!(result.position.x > 2.5);
+        assert!(result.position.y > STANDING_Y + 0.2);
+        assert!(result.grounded);
+    }
+
+    #[test]
+    fn test_move_character_snaps_to_ground() {
+        let mut graph = Graph::new();
+        add_ground(&mut graph, -20.0, 1.0, 0.0);
+        add_ground(&mut graph, 1.0, 20.0, -0.2);
+
+        let result = move_character(
+            &mut graph,
+            Vector3::new(0.0, STANDING_Y, 0.0),
+            Vector3::new(3.0, 0.0, 0.0),
+            true,
+        );
+        assert!((result.position.x - 3.0).abs() < 0.02);
+        assert!((result.position.y - (STANDING_Y - 0.2)).abs() < 0.02);
+        assert!(result.grounded);
+
+        // Airborne characters are not snapped.
+        let result = move_character(
+            &mut graph,
+            Vector3::new(0.0, STANDING_Y, 0.0),
+            Vector3::new(3.0, 0.0, 0.0),
+            false,
+        );
+        assert!((result.position.y - STANDING_Y).abs() < 0.02);
+        assert!(!result.grounded);
+    }
+
+    #[test]
+    fn test_move_character_does_not_snap_from_high_ledge() {
+        let mut graph = Graph::new();
+        add_ground(&mut graph, -20.0, 1.0, 0.0);
+        add_ground(&mut graph, 1.0, 20.0, -1.0);
+
+        let result = move_character(
+            &mut graph,
+            Vector3::new(0.0, STANDING_Y, 0.0),
+            Vector3::new(3.0, 0.0, 0.0),
+            true,
+        );
+        assert!((result.position.y - STANDING_Y).abs() < 0.02);
+        assert!(!result.grounded);
+    }
+
+    #[test]
+    fn test_cast_shape() {
+        let mut graph = Graph::new();
+        let ground = add_ground(&mut graph, -20.0, 20.0, 0.0);
+        sync(&mut graph);
+
+        let cast = |position: Vector3<f32>, direction: Vector3<f32>, max_len: f32| {
+            graph.physics.cast_shape(ShapeCastOptions {
+                shape: ColliderShape::ball(0.5),
+                shape_position: Isometry3::translation(position.x, position.y, position.z),
+                direction,
+                max_len,
+                groups: Default::default(),
+                exclude_sensors: true,
+            })
+        };
+
+        // Direction does not need to be normalized.
+        let result = cast(
+            Vector3::new(1.0, 3.0, 3.0),
+            Vector3::new(0.0, -2.0, 0.0),
+            10.0,
+        )
+        .expect("The ball must hit the ground!");
+        assert_eq!(result.collider, ground);
+        assert!((result.toi - 2.5).abs() < 0.01);
+        assert!((result.normal - Vector3::y()).norm() < 0.01);
+        assert!((result.position - Point3::new(1.0, 0.0, 3.0)).norm() < 0.01);
+        assert!(!result.penetrating);
+
+        // Too short or in wrong direction.
+        assert!(cast(Vector3::new(0.0, 3.0, 0.0), -Vector3::y(), 2.0).is_none());
+        assert!(cast(Vector3::new(0.0, 3.0, 0.0), Vector3::y(), 10.0).is_none());
+
+        let result = cast(Vector3::new(0.0, 0.0, 0.0), -Vector3::y(), 10.0)
+            .expect("The ball must hit the ground!");
+        assert!(result.penetrating);
+        assert_eq!(result.toi, 0.0);
+    }
+
+    // Creates a ground (spans from -1 to 0 along Y) that belongs to the first collision group
+    // and a sensor above it (spans from 0 to 1 along Y).
+    fn add_ground_with_sensor(graph: &mut Graph) -> (Handle<Node>, Handle<Node>) {
+        let ground = add_ground(graph, -20.0, 20.0, 0.0);
+        graph[ground]
+            .as_collider_mut()
+            .set_collision_groups(InteractionGroups::new(BitMask(0b01), BitMask(u32::MAX)));
+        let sensor = add_ground(graph, -20.0, 20.0, 1.0);
+        graph[sensor].as_collider_mut().set_is_sensor(true);
+        sync(graph);
+        (ground, sensor)
+    }
+
+    #[test]
+    fn test_cast_shape_filtering() {
+        let mut graph = Graph::new();
+        let (ground, sensor) = add_ground_with_sensor(&mut graph);
+
+        let cast = |groups: InteractionGroups, exclude_sensors: bool| {
+            graph.physics.cast_shape(ShapeCastOptions {
+                shape: ColliderShape::cuboid(0.5, 0.5, 0.5),
+                shape_position: Isometry3::translation(0.0, 3.0, 0.0),
+                direction: -Vector3::y(),
+                max_len: 10.0,
+                groups,
+                exclude_sensors,
+            })
+        };
+
+        let result = cast(Default::default(), true).expect("The box must hit the ground!");
+        assert_eq!(result.collider, ground);
+        assert!((result.toi - 2.5).abs() < 0.01);
+
+        let result = cast(Default::default(), false).expect("The box must hit the sensor!");
+        assert_eq!(result.collider, sensor);
+        assert!((result.toi - 1.5).abs() < 0.01);
+
+        assert!(cast(
+            InteractionGroups::new(BitMask(u32::MAX), BitMask(0b10)),
+            true
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_intersections_with_shape() {
+        let mut graph = Graph::new();
+        let (ground, sensor) = add_ground_with_sensor(&mut graph);
+        let platform = add_static_box(
+            &mut graph,
+            Vector3::new(5.0, 2.5, 0.0),
+            UnitQuaternion::identity(),
+            Vector3::new(1.0, 0.5, 1.0),
+        );
+        sync(&mut graph);
+
+        let intersections = |radius: f32, groups: InteractionGroups, limit: usize| {
+            let mut colliders = Vec::new();
+            graph.physics.intersections_with_shape(
+                ShapeIntersectionOptions {
+                    shape: ColliderShape::ball(radius),
+                    shape_position: Isometry3::translation(0.0, 1.5, 0.0),
+                    groups,
+                    exclude_sensors: true,
+                },
+                |collider| {
+                    colliders.push(collider);
+                    colliders.len() < limit
+                },
+            );
+            colliders.sort_by_key(|c| c.index());
+            colliders
+        };
+
+        assert!(intersections(0.4, Default::default(), usize::MAX).is_empty());
+        assert_eq!(
+            intersections(2.0, Default::default(), usize::MAX),
+            vec![ground]
+        );
+        assert_eq!(
+            intersections(10.0, Default::default(), usize::MAX),
+            vec![ground, platform]
+        );
+
+        // Search stops when the callback returns false.
+        assert_eq!(intersections(10.0, Default::default(), 1).len(), 1);
+
+        assert_eq!(
+            intersections(
+                10.0,
+                InteractionGroups::new(BitMask(u32::MAX), BitMask(0b10)),
+                usize::MAX
+            ),
+            vec![platform]
+        );
+
+        let mut colliders = Vec::new();
+        graph.physics.intersections_with_shape(
+            ShapeIntersectionOptions {
+                shape: ColliderShape::ball(1.0),
+                shape_position: Isometry3::translation(0.0, 1.5, 0.0),
+                groups: Default::default(),
+                exclude_sensors: false,
+            },
+            |collider| {
+                colliders.push(collider);
+                true
+            },
+        );
+        colliders.sort_by_key(|c| c.index());
+        assert_eq!(colliders, vec![sensor]);
+    }
+
+    #[test]
+    fn test_project_point() {
+        let mut graph = Graph::new();
+        let (ground, sensor) = add_ground_with_sensor(&mut graph);
+
+        let project = |point: Point3<f32>, solid: bool, groups: InteractionGroups| {
+            graph.physics.project_point(PointProjectionOptions {
+                point,
+                solid,
+                groups,
+                exclude_sensors: true,
+            })
+        };
+
+        let outside = Point3::new(1.0, -2.0, 3.0);
+        let projection = project(outside, true, Default::default())
+            .expect("The point must be projected on the ground!");
+        assert_eq!(projection.collider, ground);
+        assert!((projection.position - Point3::new(1.0, -1.0, 3.0)).norm() < 0.001);
+        assert!(!projection.is_inside);
+
+        // Solid projection keeps the point that is inside.
+        let inside = Point3::new(1.0, -0.25, 3.0);
+        let projection = project(inside, true, Default::default())
+            .expect("The point must be projected on the ground!");
+        assert!((projection.position - inside).norm() < 0.001);
+        assert!(projection.is_inside);
+
+        // Otherwise the point is projected on the closest face.
+        let projection = project(inside, false, Default::default())
+            .expect("The point must be projected on the ground!");
+        assert!((projection.position - Point3::new(1.0, 0.0, 3.0)).norm() < 0.001);
+        assert!(projection.is_inside);
+
+        assert!(project(
+            outside,
+            true,
+            InteractionGroups::new(BitMask(u32::MAX), BitMask(0b10))
+        )
+        .is_none());
+
+        // The sensor is the closest one when it is not excluded.
+        let above = Point3::new(1.0, 2.0, 3.0);
+        assert_eq!(
+            project(above, true, Default::default()).map(|p| p.collider),
+            Some(ground)
+        );
+        let projection = graph
+            .physics
+            .project_point(PointProjectionOptions {
+                point: above,
+                solid: true,
+                groups: Default::default(),
+                exclude_sensors: false,
+            })
+            .expect("The point must be projected on the sensor!");
+        assert_eq!(projection.collider, sensor);
+        assert!((projection.position - Point3::new(1.0, 1.0, 3.0)).norm() < 0.001);
+    }
+
+    fn add_ball(
+        graph: &mut Graph,
+        position: Vector3<f32>,
+        body_type: RigidBodyType,
+        sensor: bool,
+    ) -> Handle<Node> {
+        let collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::ball(1.0))
+            .with_sensor(sensor)
+            .build(graph);
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(position)
+                        .build(),
+                )
+                .with_children(&[collider]),
+        )
+        .with_body_type(body_type)
+        .with_gravity_scale(0.0)
+        .build(graph);
+        collider
+    }
+
+    fn find_event(
+        graph: &Graph,
+        collider: Handle<Node>,
+        other: Handle<Node>,
+    ) -> Option<CollisionEvent> {
+        graph
+            .physics
+            .collision_events()
+            .iter()
+            .find(|e| e.collider == collider && e.other == other)
+            .cloned()
+    }
+
+    #[test]
+    fn test_collision_events() {
+        let mut graph = Graph::new();
+        let first = add_ball(&mut graph, Vector3::default(), RigidBodyType::Static, false);
+        let second = add_ball(
+            &mut graph,
+            Vector3::new(1.0, 0.0, 0.0),
+            RigidBodyType::Dynamic,
+            false,
+        );
+        // Intersects both balls, but collisions between fixed bodies are not reported.
+        let sensor = add_ball(
+            &mut graph,
+            Vector3::new(1.0, 0.0, -1.5),
+            RigidBodyType::Static,
+            true,
+        );
+        sync(&mut graph);
+
+        let event = find_event(&graph, first, second).expect("Contact must be started!");
+        assert_eq!(event.kind, CollisionEventKind::ContactStarted);
+        assert!(!event.contacts.is_empty());
+        for contact in event.contacts.iter() {
+            assert!((contact.position - Point3::new(1.0, 0.0, 0.0)).norm() < 0.01);
+            assert!((contact.normal - Vector3::x()).norm() < 0.01);
+            assert!(contact.dist < 0.0);
+        }
+
+        let event = find_event(&graph, second, first).expect("Contact must be started!");
+        assert_eq!(event.kind, CollisionEventKind::ContactStarted);
+        assert!(!event.contacts.is_empty());
+        for contact in event.contacts.iter() {
+            // The contact point is on the surface of the second ball, that is shifted by the
+            // penetration depth.
+            assert!((contact.position - Point3::new(1.0 + contact.dist, 0.0, 0.0)).norm() < 0.01);
+            assert!((contact.normal + Vector3::x()).norm() < 0.01);
+        }
+
+        for (collider, other) in [(sensor, second), (second, sensor)] {
+            let event = find_event(&graph, collider, other).expect("Intersection must be started!");
+            assert_eq!(event.kind, CollisionEventKind::IntersectionStarted);
+            assert!(event.contacts.is_empty());
+        }
+        assert!(find_event(&graph, sensor, first).is_none());
+
+        // Removed collider does not receive events, but the remaining ones do.
+        graph.remove_node(second);
+        graph.update_physics();
+
+        assert_eq!(
+            find_event(&graph, first, second).map(|e| e.kind),
+            Some(CollisionEventKind::ContactStopped)
+        );
+        assert_eq!(
+            find_event(&graph, sensor, second).map(|e| e.kind),
+            Some(CollisionEventKind::IntersectionStopped)
+        );
+        assert!(graph
+            .physics
+            .collision_events()
+            .iter()
+            .all(|e| e.collider != second));
+    }
+}
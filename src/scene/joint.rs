@@ -26,6 +26,149 @@ use std::{
 };
 use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 
+/// Defines how motor parameters are converted to forces, see [`JointMotor`] docs.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Visit,
+    Inspect,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum JointMotorModel {
+    /// Stiffness and damping are automatically scaled by masses of the bodies, so the motor
+    /// behaves the same way regardless of the masses. This is the default model.
+    AccelerationBased,
+    /// Stiffness and damping are used as is, heavier bodies will react slower.
+    ForceBased,
+}
+
+impl Default for JointMotorModel {
+    fn default() -> Self {
+        Self::AccelerationBased
+    }
+}
+
+/// A motor drives relative motion of two bodies along (or around) an axis of a joint. Motor
+/// force is computed as `stiffness * (target_position - position) + damping * (target_velocity - velocity)`,
+/// so a motor with zero stiffness tries to maintain the target velocity and a motor with zero
+/// target velocity acts like a damped spring that pulls the bodies to the target position.
+#[derive(Clone, Debug, Visit, PartialEq, Inspect, Reflect)]
+pub struct JointMotor {
+    /// Whether the motor is enabled or not. Default is `false`.
+    #[inspect(description = "Whether the motor is enabled or not.")]
+    pub enabled: bool,
+
+    /// Target relative velocity along the axis (linear or angular depending on the axis).
+    #[inspect(
+        description = "Target relative velocity along the axis (linear or angular depending on the axis)."
+    )]
+    pub target_velocity: f32,
+
+    /// Target relative position along the axis (distance or angle in radians depending on the axis).
+    #[inspect(
+        description = "Target relative position along the axis (distance or angle in radians depending on the axis)."
+    )]
+    pub target_position: f32,
+
+    /// Stiffness of the motor, defines how strong the motor pulls the bodies to the target position.
+    #[inspect(
+        min_value = 0.0,
+        description = "Stiffness of the motor, defines how strong the motor pulls the bodies to the target position."
+    )]
+    pub stiffness: f32,
+
+    /// Damping of the motor, defines how strong the motor pulls the bodies to the target velocity.
+    #[inspect(
+        min_value = 0.0,
+        description = "Damping of the motor, defines how strong the motor pulls the bodies to the target velocity."
+    )]
+    pub damping: f32,
+
+    /// Maximum force (or torque) the motor can apply.
+    #[inspect(
+        min_value = 0.0,
+        description = "Maximum force (or torque) the motor can apply."
+    )]
+    pub max_force: f32,
+
+    /// Defines how stiffness and damping are converted to forces.
+    #[inspect(description = "Defines how stiffness and damping are converted to forces.")]
+    pub model: JointMotorModel,
+}
+
+impl Default for JointMotor {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            target_velocity: 0.0,
+            target_position: 0.0,
+            stiffness: 0.0,
+            damping: 1.0,
+            max_force: f32::MAX,
+            model: Default::default(),
+        }
+    }
+}
+
+/// Parameters of a single degree of freedom of a [`GenericJoint`].
+#[derive(Clone, Debug, Visit, PartialEq, Inspect, Reflect)]
+pub struct JointAxisParams {
+    /// Whether any relative motion along the axis is prohibited or not.
+    #[inspect(description = "Whether any relative motion along the axis is prohibited or not.")]
+    pub locked: bool,
+
+    /// Whether limits of the axis are enabled or not. Has no effect on locked axes.
+    #[inspect(
+        description = "Whether limits of the axis are enabled or not. Has no effect on locked axes."
+    )]
+    pub limits_enabled: bool,
+
+    /// Allowed range of relative position along the axis (distance or angle in radians).
+    #[inspect(
+        description = "Allowed range of relative position along the axis (distance or angle in radians)."
+    )]
+    pub limits: Range<f32>,
+
+    /// Motor of the axis. Has no effect on locked axes.
+    #[inspect(description = "Motor of the axis. Has no effect on locked axes.")]
+    pub motor: JointMotor,
+}
+
+impl JointAxisParams {
+    /// Creates parameters of a locked axis.
+    pub fn locked() -> Self {
+        Self {
+            locked: true,
+            ..Default::default()
+        }
+    }
+
+    /// Creates parameters of a free axis.
+    pub fn free() -> Self {
+        Self {
+            locked: false,
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for JointAxisParams {
+    fn default() -> Self {
+        Self {
+            locked: false,
+            limits_enabled: false,
+            limits: -std::f32::consts::PI..std::f32::consts::PI,
+            motor: Default::default(),
+        }
+    }
+}
+
 /// Ball joint locks any translational moves between two objects on the axis between objects, but
 /// allows rigid bodies to perform relative rotations. The real world example is a human shoulder,
 /// pendulum, etc.
@@ -60,6 +203,21 @@ pub struct BallJoint {
     #[inspect(description = "Allowed angle range around local Z axis of the joint (in radians).")]
     #[visit(optional)] // Backward compatibility
     pub z_limits_angles: Range<f32>,
+
+    /// Motor that rotates the bodies around local X axis of the joint.
+    #[inspect(description = "Motor that rotates the bodies around local X axis of the joint.")]
+    #[visit(optional)] // Backward compatibility
+    pub x_motor: JointMotor,
+
+    /// Motor that rotates the bodies around local Y axis of the joint.
+    #[inspect(description = "Motor that rotates the bodies around local Y axis of the joint.")]
+    #[visit(optional)] // Backward compatibility
+    pub y_motor: JointMotor,
+
+    /// Motor that rotates the bodies around local Z axis of the joint.
+    #[inspect(description = "Motor that rotates the bodies around local Z axis of the joint.")]
+    #[visit(optional)] // Backward compatibility
+    pub z_motor: JointMotor,
 }
 
 impl Default for BallJoint {
@@ -71,6 +229,9 @@ impl Default for BallJoint {
             y_limits_angles: -std::f32::consts::PI..std::f32::consts::PI,
             z_limits_enabled: false,
             z_limits_angles: -std::f32::consts::PI..std::f32::consts::PI,
+            x_motor: Default::default(),
+            y_motor: Default::default(),
+            z_motor: Default::default(),
         }
     }
 }
@@ -95,6 +256,11 @@ pub struct PrismaticJoint {
     )]
     #[visit(optional)] // Backward compatibility
     pub limits: Range<f32>,
+
+    /// Motor that moves the bodies along local X axis of the joint.
+    #[inspect(description = "Motor that moves the bodies along local X axis of the joint.")]
+    #[visit(optional)] // Backward compatibility
+    pub motor: JointMotor,
 }
 
 impl Default for PrismaticJoint {
@@ -102,6 +268,7 @@ impl Default for PrismaticJoint {
         Self {
             limits_enabled: false,
             limits: -std::f32::consts::PI..std::f32::consts::PI,
+            motor: Default::default(),
         }
     }
 }
@@ -122,6 +289,11 @@ pub struct RevoluteJoint {
     #[inspect(description = "Allowed angle range around local X axis of the joint (in radians).")]
     #[visit(optional)] // Backward compatibility
     pub limits: Range<f32>,
+
+    /// Motor that rotates the bodies around local X axis of the joint.
+    #[inspect(description = "Motor that rotates the bodies around local X axis of the joint.")]
+    #[visit(optional)] // Backward compatibility
+    pub motor: JointMotor,
 }
 
 impl Default for RevoluteJoint {
@@ -129,6 +301,95 @@ impl Default for RevoluteJoint {
         Self {
             limits_enabled: false,
             limits: -std::f32::consts::PI..std::f32::consts::PI,
+            motor: Default::default(),
+        }
+    }
+}
+
+/// Rope joint limits maximum distance between anchors of two bodies, but allows any relative
+/// rotations and does not prevent the bodies from moving closer to each other. The real world
+/// example is a rope or a chain.
+#[derive(Clone, Debug, Visit, PartialEq, Inspect, Reflect)]
+pub struct RopeJoint {
+    /// Maximum distance between anchors of the bodies.
+    #[inspect(
+        min_value = 0.0,
+        description = "Maximum distance between anchors of the bodies."
+    )]
+    pub max_length: f32,
+}
+
+impl Default for RopeJoint {
+    fn default() -> Self {
+        Self { max_length: 1.0 }
+    }
+}
+
+/// Spring joint pulls anchors of two bodies to each other using a damped spring along every
+/// local axis of the joint, relative rotations are not restricted. Anchors of the bodies are
+/// placed at the joint's position at the moment of binding, so the spring has zero rest length.
+/// Use [`PrismaticJoint`] with a motor to create a spring that acts along a single axis (for
+/// example a vehicle suspension).
+#[derive(Clone, Debug, Visit, PartialEq, Inspect, Reflect)]
+pub struct SpringJoint {
+    /// Stiffness of the spring.
+    #[inspect(min_value = 0.0, description = "Stiffness of the spring.")]
+    pub stiffness: f32,
+
+    /// Damping of the spring.
+    #[inspect(min_value = 0.0, description = "Damping of the spring.")]
+    pub damping: f32,
+}
+
+impl Default for SpringJoint {
+    fn default() -> Self {
+        Self {
+            stiffness: 100.0,
+            damping: 1.0,
+        }
+    }
+}
+
+/// Generic joint allows to configure every degree of freedom (three linear and three angular)
+/// separately: each axis can be locked, limited and driven by a motor. It can be used to create
+/// joints that are not covered by other joint kinds. By default all linear axes are locked and
+/// all angular axes are free, which is the same as [`BallJoint`].
+#[derive(Clone, Debug, Visit, PartialEq, Inspect, Reflect)]
+pub struct GenericJoint {
+    /// Parameters of linear motion along local X axis of the joint.
+    #[inspect(description = "Parameters of linear motion along local X axis of the joint.")]
+    pub x: JointAxisParams,
+
+    /// Parameters of linear motion along local Y axis of the joint.
+    #[inspect(description = "Parameters of linear motion along local Y axis of the joint.")]
+    pub y: JointAxisParams,
+
+    /// Parameters of linear motion along local Z axis of the joint.
+    #[inspect(description = "Parameters of linear motion along local Z axis of the joint.")]
+    pub z: JointAxisParams,
+
+    /// Parameters of rotation around local X axis of the joint.
+    #[inspect(description = "Parameters of rotation around local X axis of the joint.")]
+    pub angular_x: JointAxisParams,
+
+    /// Parameters of rotation around local Y axis of the joint.
+    #[inspect(description = "Parameters of rotation around local Y axis of the joint.")]
+    pub angular_y: JointAxisParams,
+
+    /// Parameters of rotation around local Z axis of the joint.
+    #[inspect(description = "Parameters of rotation around local Z axis of the joint.")]
+    pub angular_z: JointAxisParams,
+}
+
+impl Default for GenericJoint {
+    fn default() -> Self {
+        Self {
+            x: JointAxisParams::locked(),
+            y: JointAxisParams::locked(),
+            z: JointAxisParams::locked(),
+            angular_x: JointAxisParams::free(),
+            angular_y: JointAxisParams::free(),
+            angular_z: JointAxisParams::free(),
         }
     }
 }
@@ -146,6 +407,12 @@ pub enum JointParams {
     PrismaticJoint(PrismaticJoint),
     /// See [`RevoluteJoint`] for more info.
     RevoluteJoint(RevoluteJoint),
+    /// See [`RopeJoint`] for more info.
+    RopeJoint(RopeJoint),
+    /// See [`SpringJoint`] for more info.
+    SpringJoint(SpringJoint),
+    /// See [`GenericJoint`] for more info.
+    GenericJoint(GenericJoint),
 }
 
 impl Default for JointParams {
@@ -0,0 +1,331 @@
+//! Area light is a light source that emits light from a surface of some shape instead of a
+//! single point. Example: window, TV screen, ceiling lamp with diffuser, etc.
+//!
+//! # Shapes
+//!
+//! Area light supports two kinds of shapes (see [`AreaLightShape`]): rectangle and disc (or
+//! ellipse, if its sides are different). Light is emitted from one side of the shape, in the
+//! direction opposite to the up vector (-Y) of the light, just like spot lights do. The shape lies
+//! in local XZ plane of the light and it can be made two-sided if needed.
+//!
+//! # Shading
+//!
+//! Area lights are shaded using linearly transformed cosines (LTC), which gives correct specular
+//! highlights for rough and glossy surfaces (highlights have the shape of the light source) and
+//! correct diffuse lighting.
+//!
+//! # Light volume
+//!
+//! Physically area lights have infinite range, but for performance reasons, each area light has
+//! a radius after which light intensity smoothly decays to zero. Everything outside of sphere
+//! with this radius won't be lit by the light.
+//!
+//! # Shadows
+//!
+//! Area lights use point shadow maps from the center of the light, which means that they cast
+//! hard (or PCF-filtered) shadows. Shadows of area lights can be enabled/disabled using the same
+//! settings as for point lights.
+
+use crate::{
+    core::{
+        algebra::Vector2,
+        inspect::{Inspect, PropertyInfo},
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        reflect::Reflect,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::{Visit, VisitResult, Visitor},
+    },
+    engine::resource_manager::ResourceManager,
+    scene::{
+        base::Base,
+        graph::Graph,
+        light::{BaseLight, BaseLightBuilder},
+        node::{Node, NodeTrait, TypeUuidProvider},
+    },
+};
+use std::ops::{Deref, DerefMut};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Shape of an area light.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Visit,
+    Inspect,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum AreaLightShape {
+    /// Rectangle with sides defined by the size of the light.
+    Rectangle,
+    /// Disc (ellipse) with diameters defined by the size of the light.
+    Disc,
+}
+
+impl Default for AreaLightShape {
+    fn default() -> Self {
+        Self::Rectangle
+    }
+}
+
+/// See module docs.
+#[derive(Debug, Inspect, Reflect, Clone, Visit)]
+pub struct AreaLight {
+    base_light: BaseLight,
+
+    #[reflect(setter = "set_shape")]
+    shape: InheritableVariable<AreaLightShape>,
+
+    #[reflect(setter = "set_size")]
+    size: InheritableVariable<Vector2<f32>>,
+
+    #[reflect(setter = "set_two_sided")]
+    two_sided: InheritableVariable<bool>,
+
+    #[inspect(min_value = 0.0, step = 0.1)]
+    #[reflect(setter = "set_radius")]
+    radius: InheritableVariable<f32>,
+
+    #[inspect(min_value = 0.0, step = 0.001)]
+    #[reflect(setter = "set_shadow_bias")]
+    shadow_bias: InheritableVariable<f32>,
+}
+
+impl Deref for AreaLight {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base_light.base
+    }
+}
+
+impl DerefMut for AreaLight {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base_light.base
+    }
+}
+
+impl TypeUuidProvider for AreaLight {
+    fn type_uuid() -> Uuid {
+        uuid!("3b0e1a64-8f2d-4c7e-9a51-6d2f0c8b7e13")
+    }
+}
+
+impl AreaLight {
+    /// Returns a reference to base light.
+    pub fn base_light_ref(&self) -> &BaseLight {
+        &self.base_light
+    }
+
+    /// Returns a reference to base light.
+    pub fn base_light_mut(&mut self) -> &mut BaseLight {
+        &mut self.base_light
+    }
+
+    /// Sets new shape of the light.
+    pub fn set_shape(&mut self, shape: AreaLightShape) -> AreaLightShape {
+        self.shape.set(shape)
+    }
+
+    /// Returns current shape of the light.
+    pub fn shape(&self) -> AreaLightShape {
+        *self.shape
+    }
+
+    /// Sets size of the light shape - width (along local X axis) and length (along local Z axis)
+    /// of a rectangle or diameters of a disc. Keep in mind, that the size is also affected by
+    /// the scale of the light.
+    pub fn set_size(&mut self, size: Vector2<f32>) -> Vector2<f32> {
+        self.size.set(Vector2::new(size.x.abs(), size.y.abs()))
+    }
+
+    /// Returns current size of the light shape.
+    pub fn size(&self) -> Vector2<f32> {
+        *self.size
+    }
+
+    /// Defines whether the light emits light from both sides of its shape or not.
+    pub fn set_two_sided(&mut self, two_sided: bool) -> bool {
+        self.two_sided.set(two_sided)
+    }
+
+    /// Returns `true` if the light emits light from both sides of its shape.
+    pub fn is_two_sided(&self) -> bool {
+        *self.two_sided
+    }
+
+    /// Sets radius of the light volume, light intensity will smoothly decay to zero at this
+    /// distance from the center of the light.
+    pub fn set_radius(&mut self, radius: f32) -> f32 {
+        self.radius.set(radius.abs())
+    }
+
+    /// Returns radius of the light volume.
+    pub fn radius(&self) -> f32 {
+        *self.radius
+    }
+
+    /// Sets new shadow bias value. Bias will be used to offset fragment's depth before
+    /// compare it with shadow map value, it is used to remove "shadow acne".
+    pub fn set_shadow_bias(&mut self, bias: f32) -> f32 {
+        self.shadow_bias.set(bias)
+    }
+
+    /// Returns current value of shadow bias.
+    pub fn shadow_bias(&self) -> f32 {
+        *self.shadow_bias
+    }
+}
+
+impl NodeTrait for AreaLight {
+    crate::impl_query_component!(base_light: BaseLight);
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        AxisAlignedBoundingBox::unit()
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.local_bounding_box()
+            .transform(&self.global_transform())
+    }
+
+    fn restore_resources(&mut self, resource_manager: ResourceManager) {
+        self.base_light.restore_resources(resource_manager);
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+}
+
+impl Default for AreaLight {
+    fn default() -> Self {
+        Self {
+            base_light: Default::default(),
+            shape: Default::default(),
+            size: InheritableVariable::new(Vector2::new(1.0, 1.0)),
+            two_sided: InheritableVariable::new(false),
+            radius: InheritableVariable::new(10.0),
+            shadow_bias: InheritableVariable::new(0.025),
+        }
+    }
+}
+
+/// Allows you to build area light in declarative manner.
+pub struct AreaLightBuilder {
+    base_light_builder: BaseLightBuilder,
+    shape: AreaLightShape,
+    size: Vector2<f32>,
+    two_sided: bool,
+    radius: f32,
+    shadow_bias: f32,
+}
+
+impl AreaLightBuilder {
+    /// Creates new builder instance.
+    pub fn new(base_light_builder: BaseLightBuilder) -> Self {
+        Self {
+            base_light_builder,
+            shape: Default::default(),
+            size: Vector2::new(1.0, 1.0),
+            two_sided: false,
+            radius: 10.0,
+            shadow_bias: 0.025,
+        }
+    }
+
+    /// Sets desired shape of the light.
+    pub fn with_shape(mut self, shape: AreaLightShape) -> Self {
+        self.shape = shape;
+        self
+    }
+
+    /// Sets desired size of the light shape.
+    pub fn with_size(mut self, size: Vector2<f32>) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Sets whether the light should emit light from both sides of its shape or not.
+    pub fn with_two_sided(mut self, two_sided: bool) -> Self {
+        self.two_sided = two_sided;
+        self
+    }
+
+    /// Sets desired radius of the light volume.
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets desired shadow bias.
+    pub fn with_shadow_bias(mut self, bias: f32) -> Self {
+        self.shadow_bias = bias;
+        self
+    }
+
+    /// Builds new instance of area light.
+    pub fn build_area_light(self) -> AreaLight {
+        AreaLight {
+            base_light: self.base_light_builder.build(),
+            shape: self.shape.into(),
+            size: self.size.into(),
+            two_sided: self.two_sided.into(),
+            radius: self.radius.into(),
+            shadow_bias: self.shadow_bias.into(),
+        }
+    }
+
+    /// Builds new instance of area light node.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_area_light())
+    }
+
+    /// Builds new instance of area light and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::core::algebra::Vector2;
+    use crate::core::reflect::Reflect;
+    use crate::core::variable::try_inherit_properties;
+    use crate::scene::{
+        base::{test::check_inheritable_properties_equality, BaseBuilder},
+        light::{
+            area::{AreaLight, AreaLightBuilder, AreaLightShape},
+            BaseLightBuilder,
+        },
+    };
+
+    #[test]
+    fn test_area_light_inheritance() {
+        let parent = AreaLightBuilder::new(BaseLightBuilder::new(BaseBuilder::new()))
+            .with_shape(AreaLightShape::Disc)
+            .with_size(Vector2::new(2.0, 3.0))
+            .with_two_sided(true)
+            .with_radius(1.0)
+            .with_shadow_bias(0.1)
+            .build_node();
+
+        let mut child =
+            AreaLightBuilder::new(BaseLightBuilder::new(BaseBuilder::new())).build_area_light();
+
+        try_inherit_properties(child.as_reflect_mut(), parent.as_reflect()).unwrap();
+
+        let parent = parent.cast::<AreaLight>().unwrap();
+
+        check_inheritable_properties_equality(&child.base_light.base, &parent.base_light.base);
+        check_inheritable_properties_equality(&child.base_light, &parent.base_light);
+        check_inheritable_properties_equality(&child, parent);
+    }
+}
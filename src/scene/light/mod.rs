@@ -1,12 +1,13 @@
 //! Contains all structures and methods to create and manage lights.
 //!
 //! Light sources arte basic building blocks of many scenes in games, it improves
-//! perception of scene and makes it look natural. Fyrox engine supports three kinds
+//! perception of scene and makes it look natural. Fyrox engine supports four kinds
 //! of light sources:
 //!
 //! 1) Directional - similar to sun in real life, its rays are parallel.
 //! 2) Spot - similar to flash light, it has cone light volume and circle spot.
 //! 3) Point - similar to light bulb, it has spherical light volume.
+//! 4) Area - similar to window or TV screen, it emits light from rectangle or disc.
 //!
 //! Each kind of light source is suitable for specific conditions, for example
 //! spot light can be used if you have a character with flashlight, point - if
@@ -30,6 +31,7 @@ use crate::{
 };
 use std::ops::{Deref, DerefMut};
 
+pub mod area;
 pub mod directional;
 pub mod point;
 pub mod spot;
@@ -63,9 +63,17 @@ pub enum RenderPath {
     /// objects and there is no way to change blending. Deferred rendering is default rendering path.
     Deferred = 0,
 
-    /// Forward rendering path supports translucency and custom blending. However current support
-    /// of forward rendering is very little. It is ideal for transparent objects like glass.
+    /// Forward rendering path supports translucency and custom blending. It is ideal for transparent
+    /// objects like glass. Meshes with this render path are lit by every light source that affects
+    /// them, but they do not receive shadows.
     Forward = 1,
+
+    /// Forward+ (clustered forward) rendering path for opaque meshes. Such meshes are not rendered
+    /// into G-Buffer, instead they're shaded in a single pass against every light source that
+    /// affects them. It could be useful for meshes with custom lighting models. Meshes with this
+    /// render path are rendered before meshes with [`RenderPath::Forward`] render path, and they
+    /// do not receive shadows and screen-space effects (SSAO, SSR, decals).
+    ForwardPlus = 2,
 }
 
 impl Default for RenderPath {
@@ -80,6 +88,7 @@ impl RenderPath {
         match id {
             0 => Ok(Self::Deferred),
             1 => Ok(Self::Forward),
+            2 => Ok(Self::ForwardPlus),
             _ => Err(format!("Invalid render path id {}!", id)),
         }
     }
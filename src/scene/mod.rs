@@ -7,6 +7,7 @@
 pub mod accel;
 pub mod base;
 pub mod camera;
+pub mod character_controller;
 pub mod collider;
 pub mod debug;
 pub mod decal;
@@ -18,6 +19,8 @@ pub mod mesh;
 pub mod node;
 pub mod particle_system;
 pub mod pivot;
+pub mod ragdoll;
+pub mod reflection_probe;
 pub mod rigidbody;
 pub mod sound;
 pub mod sprite;
@@ -26,7 +29,10 @@ pub mod transform;
 pub mod visibility;
 
 use crate::{
-    animation::{machine::container::AnimationMachineContainer, AnimationContainer},
+    animation::{
+        machine::{container::AnimationMachineContainer, LayerMask},
+        AnimationContainer,
+    },
     core::{
         algebra::Vector2,
         color::Color,
@@ -562,18 +568,36 @@ impl Scene {
     /// it updates physics, animations, and each graph node. In most cases there is
     /// no need to call it directly, engine automatically updates all available scenes.
     pub fn update(&mut self, frame_size: Vector2<f32>, dt: f32) {
+        self.update_internal(frame_size, dt, true)
+    }
+
+    /// Performs single fixed update tick, it steps physics of the scene. In most cases there is
+    /// no need to call it directly, engine automatically calls it at fixed rate for all enabled
+    /// scenes.
+    pub fn fixed_update(&mut self) {
+        self.graph.update_physics();
+    }
+
+    pub(crate) fn update_internal(
+        &mut self,
+        frame_size: Vector2<f32>,
+        dt: f32,
+        step_physics: bool,
+    ) {
         let last = instant::Instant::now();
         self.animations.update_animations(dt);
         self.performance_statistics.animations_update_time = instant::Instant::now() - last;
 
-        self.graph.update(frame_size, dt);
+        self.graph.update_internal(frame_size, dt, step_physics);
         self.performance_statistics.graph = self.graph.performance_statistics.clone();
 
         for machine in self.animation_machines.iter_mut() {
-            machine
-                .evaluate_pose(&self.animations, dt)
-                .apply(&mut self.graph);
+            machine.evaluate_pose(&self.animations, dt);
+            machine.solve_ik(&self.graph);
+            machine.pose().apply(&mut self.graph);
         }
+
+        ragdoll::update_ragdolls(&mut self.graph, dt);
     }
 
     /// Creates deep copy of a scene, filter predicate allows you to filter out nodes
@@ -591,6 +615,17 @@ impl Scene {
             for track in animation.get_tracks_mut() {
                 track.set_node(old_new_map.map[&track.get_node()]);
             }
+            animation.retain_property_tracks(|track| old_new_map.map.contains_key(&track.node()));
+            for track in animation.property_tracks_mut() {
+                track.set_node(old_new_map.map[&track.node()]);
+            }
+            if let Some(settings) = animation.root_motion_settings_mut() {
+                settings.node = old_new_map
+                    .map
+                    .get(&settings.node)
+                    .cloned()
+                    .unwrap_or_default();
+            }
         }
 
         let mut animation_machines = self.animation_machines.clone();
@@ -600,6 +635,22 @@ impl Scene {
                 .get(&machine.root)
                 .cloned()
                 .unwrap_or_default();
+
+            for layer in machine.layers_mut() {
+                let mask = layer
+                    .mask()
+                    .nodes()
+                    .iter()
+                    .filter_map(|node| old_new_map.map.get(node).cloned())
+                    .collect();
+                layer.set_mask(LayerMask::new(mask));
+            }
+
+            for chain in machine.ik_chains_mut() {
+                for joint in chain.joints_mut() {
+                    *joint = old_new_map.map.get(joint).cloned().unwrap_or_default();
+                }
+            }
         }
 
         (
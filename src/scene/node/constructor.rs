@@ -7,11 +7,14 @@ use crate::{
         camera::Camera,
         decal::Decal,
         dim2::{self, rectangle::Rectangle},
-        light::{directional::DirectionalLight, point::PointLight, spot::SpotLight},
+        light::{
+            area::AreaLight, directional::DirectionalLight, point::PointLight, spot::SpotLight,
+        },
         mesh::Mesh,
         node::{Node, NodeTrait, TypeUuidProvider},
         particle_system::ParticleSystem,
         pivot::Pivot,
+        reflection_probe::ReflectionProbe,
         sound::{listener::Listener, Sound},
         sprite::Sprite,
         terrain::Terrain,
@@ -33,10 +36,12 @@ impl NodeConstructorContainer {
     pub fn new() -> Self {
         let container = NodeConstructorContainer::default();
 
+        container.add::<dim2::character_controller::CharacterController>();
         container.add::<dim2::collider::Collider>();
         container.add::<dim2::joint::Joint>();
         container.add::<Rectangle>();
         container.add::<dim2::rigidbody::RigidBody>();
+        container.add::<AreaLight>();
         container.add::<DirectionalLight>();
         container.add::<PointLight>();
         container.add::<SpotLight>();
@@ -45,10 +50,13 @@ impl NodeConstructorContainer {
         container.add::<Sound>();
         container.add::<Listener>();
         container.add::<Camera>();
+        container.add::<scene::character_controller::CharacterController>();
         container.add::<scene::collider::Collider>();
         container.add::<Decal>();
         container.add::<scene::joint::Joint>();
         container.add::<Pivot>();
+        container.add::<scene::ragdoll::Ragdoll>();
+        container.add::<ReflectionProbe>();
         container.add::<scene::rigidbody::RigidBody>();
         container.add::<Sprite>();
         container.add::<Terrain>();
@@ -22,7 +22,7 @@ use crate::{
         decal::Decal,
         dim2::{self, rectangle::Rectangle},
         graph::{self, Graph, NodePool},
-        light::{point::PointLight, spot::SpotLight},
+        light::{area::AreaLight, point::PointLight, spot::SpotLight},
         mesh::Mesh,
         particle_system::ParticleSystem,
         sound::{context::SoundContext, listener::Listener, Sound},
@@ -39,11 +39,7 @@ use std::{
 pub mod constructor;
 pub mod container;
 
-/// A trait for an entity that has unique type identifier.
-pub trait TypeUuidProvider: Sized {
-    /// Return type UUID.
-    fn type_uuid() -> Uuid;
-}
+pub use crate::core::TypeUuidProvider;
 
 /// A set of useful methods that is possible to auto-implement.
 pub trait BaseNodeTrait: Any + Debug + Deref<Target = Base> + DerefMut + Send {
@@ -395,6 +391,7 @@ impl Node {
     define_is_as!(SpotLight  => fn is_spot_light, fn as_spot_light, fn as_spot_light_mut);
     define_is_as!(PointLight  => fn is_point_light, fn as_point_light, fn as_point_light_mut);
     define_is_as!(PointLight  => fn is_directional_light, fn as_directional_light, fn as_directional_light_mut);
+    define_is_as!(AreaLight  => fn is_area_light, fn as_area_light, fn as_area_light_mut);
     define_is_as!(ParticleSystem => fn is_particle_system, fn as_particle_system, fn as_particle_system_mut);
     define_is_as!(Sprite  => fn is_sprite, fn as_sprite, fn as_sprite_mut);
     define_is_as!(Terrain  => fn is_terrain, fn as_terrain, fn as_terrain_mut);
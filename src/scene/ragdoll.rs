@@ -0,0 +1,707 @@
+//! Ragdoll is a set of rigid bodies, colliders and joints that mimics a skeleton of a character.
+//!
+//! For more info see [`Ragdoll`].
+
+use crate::{
+    core::{
+        algebra::{Matrix3, Matrix4, Point3, Rotation3, UnitQuaternion, Vector3},
+        inspect::{Inspect, PropertyInfo},
+        math::{aabb::AxisAlignedBoundingBox, Matrix4Ext},
+        pool::Handle,
+        reflect::Reflect,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+    },
+    engine::resource_manager::ResourceManager,
+    scene::{
+        base::{Base, BaseBuilder},
+        collider::{CapsuleShape, ColliderBuilder, ColliderShape},
+        graph::Graph,
+        joint::{BallJoint, JointBuilder, JointParams},
+        node::{Node, NodeTrait, TypeUuidProvider},
+        rigidbody::{RigidBody, RigidBodyBuilder, RigidBodyType},
+        transform::TransformBuilder,
+    },
+};
+use fxhash::FxHashMap;
+use std::ops::{Deref, DerefMut};
+
+/// Limb is a pair of a bone of a skeleton and a rigid body that drives (or follows) the bone.
+#[derive(Clone, Debug, Default, PartialEq, Visit, Inspect, Reflect)]
+pub struct Limb {
+    /// A handle of a bone of a skeleton.
+    #[inspect(description = "A handle of a bone of a skeleton.")]
+    pub bone: Handle<Node>,
+
+    /// A handle of a rigid body that is attached to the bone.
+    #[inspect(description = "A handle of a rigid body that is attached to the bone.")]
+    pub physical_bone: Handle<Node>,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct BlendSource {
+    position: Vector3<f32>,
+    rotation: UnitQuaternion<f32>,
+}
+
+/// Ragdoll is a set of rigid bodies, colliders and joints that mimics a skeleton of a character.
+/// It has two modes:
+///
+/// - **Inactive** (default) - the bones are driven by animations and the rigid bodies are kinematic,
+///   they just follow the bones. In this mode ragdoll works as a set of hit boxes.
+/// - **Active** - the rigid bodies are dynamic and the bones follow the rigid bodies.
+///
+/// When the ragdoll is deactivated, the bones are smoothly blended from the last physics-driven pose
+/// to the pose that is set by animations (by an animation machine or by [`crate::animation::AnimationPose::apply`]),
+/// see [`Ragdoll::set_blend_time`].
+///
+/// # Important notes
+///
+/// Rigid bodies and joints of the ragdoll are attached to the ragdoll node, so the ragdoll node
+/// itself should not move while the ragdoll is active. Otherwise the joints will be re-bound using
+/// current positions of the bodies.
+///
+/// # Example
+///
+/// The easiest way to create a ragdoll is to generate it from a skeleton of a character:
+///
+/// ```rust
+/// # use fyrox::{
+/// #     core::pool::Handle,
+/// #     scene::{base::BaseBuilder, graph::Graph, node::Node, ragdoll::RagdollBuilder},
+/// # };
+/// fn create_ragdoll(graph: &mut Graph, hips: Handle<Node>) -> Handle<Node> {
+///     RagdollBuilder::new(BaseBuilder::new().with_name("Ragdoll"))
+///         .with_limb_thickness(0.2)
+///         .with_blend_time(0.5)
+///         .build_from_skeleton(hips, graph)
+/// }
+/// ```
+#[derive(Visit, Inspect, Reflect, Debug)]
+pub struct Ragdoll {
+    base: Base,
+
+    #[inspect(
+        description = "Whether the ragdoll is driven by physics (true) or by animations (false)."
+    )]
+    #[reflect(setter = "set_active")]
+    is_active: InheritableVariable<bool>,
+
+    #[inspect(
+        min_value = 0.0,
+        step = 0.1,
+        description = "Time (in seconds) of blending from physics-driven pose to animation pose."
+    )]
+    #[reflect(setter = "set_blend_time")]
+    blend_time: InheritableVariable<f32>,
+
+    #[inspect(
+        description = "Limbs of the ragdoll, a limb of a parent bone must precede limbs of its children."
+    )]
+    #[reflect(setter = "set_limbs")]
+    limbs: InheritableVariable<Vec<Limb>>,
+
+    #[visit(skip)]
+    #[inspect(skip)]
+    #[reflect(hidden)]
+    prev_active: bool,
+
+    #[visit(skip)]
+    #[inspect(skip)]
+    #[reflect(hidden)]
+    blend_elapsed: f32,
+
+    #[visit(skip)]
+    #[inspect(skip)]
+    #[reflect(hidden)]
+    blend_sources: FxHashMap<Handle<Node>, BlendSource>,
+}
+
+impl Default for Ragdoll {
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            is_active: InheritableVariable::new(false),
+            blend_time: InheritableVariable::new(0.5),
+            limbs: Default::default(),
+            prev_active: false,
+            blend_elapsed: 0.0,
+            blend_sources: Default::default(),
+        }
+    }
+}
+
+impl Clone for Ragdoll {
+    fn clone(&self) -> Self {
+        Self {
+            base: self.base.clone(),
+            is_active: self.is_active.clone(),
+            blend_time: self.blend_time.clone(),
+            limbs: self.limbs.clone(),
+            prev_active: false,
+            blend_elapsed: 0.0,
+            blend_sources: Default::default(),
+        }
+    }
+}
+
+impl Deref for Ragdoll {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Ragdoll {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl TypeUuidProvider for Ragdoll {
+    fn type_uuid() -> Uuid {
+        uuid!("f4441683-dcef-472d-9d7d-4adca4579107")
+    }
+}
+
+impl Ragdoll {
+    /// Activates or deactivates the ragdoll. Active ragdoll is driven by physics, inactive -
+    /// by animations. See [`Ragdoll`] docs for more info.
+    pub fn set_active(&mut self, active: bool) -> bool {
+        self.is_active.set(active)
+    }
+
+    /// Returns true if the ragdoll is driven by physics, false - otherwise.
+    pub fn is_active(&self) -> bool {
+        *self.is_active
+    }
+
+    /// Sets time (in seconds) of blending from the last physics-driven pose to the animation pose
+    /// after the ragdoll was deactivated. Zero means instant transition.
+    pub fn set_blend_time(&mut self, time: f32) -> f32 {
+        self.blend_time.set(time.max(0.0))
+    }
+
+    /// Returns current blend time.
+    pub fn blend_time(&self) -> f32 {
+        *self.blend_time
+    }
+
+    /// Returns true if the ragdoll is blending from physics-driven pose to the animation pose.
+    pub fn is_blending(&self) -> bool {
+        !self.blend_sources.is_empty()
+    }
+
+    /// Sets new set of limbs of the ragdoll. A limb of a parent bone must precede limbs of its
+    /// children, otherwise the bones will lag behind the rigid bodies.
+    pub fn set_limbs(&mut self, limbs: Vec<Limb>) -> Vec<Limb> {
+        self.limbs.set(limbs)
+    }
+
+    /// Returns a reference to the limbs of the ragdoll.
+    pub fn limbs(&self) -> &[Limb] {
+        &self.limbs
+    }
+
+    fn update_limbs(&mut self, self_handle: Handle<Node>, graph: &mut Graph, dt: f32) {
+        let is_active = *self.is_active;
+
+        if is_active != self.prev_active {
+            self.prev_active = is_active;
+
+            let body_type = if is_active {
+                RigidBodyType::Dynamic
+            } else {
+                RigidBodyType::KinematicPositionBased
+            };
+
+            self.blend_sources.clear();
+            for limb in self.limbs.iter() {
+                if let Some(body) = graph
+                    .try_get_mut(limb.physical_bone)
+                    .and_then(|n| n.cast_mut::<RigidBody>())
+                {
+                    body.set_body_type(body_type);
+                }
+
+                if !is_active {
+                    if let Some(bone) = graph.try_get(limb.bone) {
+                        let transform = bone.local_transform();
+                        self.blend_sources.insert(
+                            limb.bone,
+                            BlendSource {
+                                position: **transform.position(),
+                                rotation: **transform.rotation(),
+                            },
+                        );
+                    }
+                }
+            }
+
+            self.blend_elapsed = 0.0;
+        }
+
+        let ragdoll_transform = actual_global_transform(graph, self_handle);
+
+        if is_active {
+            for limb in self.limbs.iter() {
+                if let Some(body) = graph.try_get(limb.physical_bone) {
+                    let body_transform = ragdoll_transform * body.local_transform().matrix();
+                    set_bone_global_pose(
+                        graph,
+                        limb.bone,
+                        body_transform.position(),
+                        rotation_of(&body_transform),
+                    );
+                }
+            }
+        } else {
+            if !self.blend_sources.is_empty() {
+                self.blend_elapsed += dt;
+
+                let t = if *self.blend_time > 0.0 {
+                    (self.blend_elapsed / *self.blend_time).min(1.0)
+                } else {
+                    1.0
+                };
+
+                for (bone, source) in self.blend_sources.iter() {
+                    if let Some(bone) = graph.try_get_mut(*bone) {
+                        let transform = bone.local_transform_mut();
+                        let position = source.position.lerp(&**transform.position(), t);
+                        let rotation = source.rotation.nlerp(&**transform.rotation(), t);
+                        transform.set_position(position).set_rotation(rotation);
+                    }
+                }
+
+                if t >= 1.0 {
+                    self.blend_sources.clear();
+                }
+            }
+
+            // Kinematic bodies just follow the bones.
+            let inv_ragdoll_transform = ragdoll_transform
+                .try_inverse()
+                .unwrap_or_else(Matrix4::identity);
+            let inv_ragdoll_rotation = rotation_of(&ragdoll_transform).inverse();
+            for limb in self.limbs.iter() {
+                if graph.is_valid_handle(limb.bone) && graph.is_valid_handle(limb.physical_bone) {
+                    let bone_transform = actual_global_transform(graph, limb.bone);
+                    let position = inv_ragdoll_transform
+                        .transform_point(&Point3::from(bone_transform.position()))
+                        .coords;
+                    let rotation = inv_ragdoll_rotation * rotation_of(&bone_transform);
+                    graph[limb.physical_bone]
+                        .local_transform_mut()
+                        .set_position(position)
+                        .set_rotation(rotation);
+                }
+            }
+        }
+    }
+}
+
+/// Calculates global transform of a node using current local transforms of the node and all its
+/// ancestors. Unlike [`Base::global_transform`] it is always up-to-date.
+fn actual_global_transform(graph: &Graph, mut node: Handle<Node>) -> Matrix4<f32> {
+    let mut transform = Matrix4::identity();
+    while let Some(node_ref) = graph.try_get(node) {
+        transform = node_ref.local_transform().matrix() * transform;
+        node = node_ref.parent();
+    }
+    transform
+}
+
+fn rotation_of(transform: &Matrix4<f32>) -> UnitQuaternion<f32> {
+    let basis = transform.basis();
+    let basis = Matrix3::from_columns(&[
+        basis.column(0).normalize(),
+        basis.column(1).normalize(),
+        basis.column(2).normalize(),
+    ]);
+    UnitQuaternion::from(Rotation3::from_matrix_eps(
+        &basis,
+        f32::EPSILON,
+        16,
+        Rotation3::identity(),
+    ))
+}
+
+fn set_bone_global_pose(
+    graph: &mut Graph,
+    bone: Handle<Node>,
+    position: Vector3<f32>,
+    rotation: UnitQuaternion<f32>,
+) {
+    let parent = match graph.try_get(bone) {
+        Some(bone) => bone.parent(),
+        None => return,
+    };
+
+    let parent_transform = actual_global_transform(graph, parent);
+    let local_position = parent_transform
+        .try_inverse()
+        .unwrap_or_else(Matrix4::identity)
+        .transform_point(&Point3::from(position))
+        .coords;
+    let local_rotation = rotation_of(&parent_transform).inverse() * rotation;
+
+    let transform = graph[bone].local_transform_mut();
+    // Compensate pre- and post-rotations, they're part of the local rotation of the bone.
+    let local_rotation =
+        transform.pre_rotation().inverse() * local_rotation * **transform.post_rotation();
+    transform
+        .set_position(local_position)
+        .set_rotation(local_rotation);
+}
+
+impl NodeTrait for Ragdoll {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.local_bounding_box()
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.world_bounding_box()
+    }
+
+    fn restore_resources(&mut self, resource_manager: ResourceManager) {
+        self.base.restore_resources(resource_manager);
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+}
+
+/// Synchronizes bones and rigid bodies of every ragdoll that was updated by the last graph update.
+/// It must be called after animation poses were applied to the graph, so the ragdolls could
+/// override (or blend) them.
+pub(crate) fn update_ragdolls(graph: &mut Graph, dt: f32) {
+    let ragdolls = std::mem::take(&mut graph.ragdolls);
+    for &handle in ragdolls.iter() {
+        if graph
+            .try_get(handle)
+            .map_or(false, |n| n.cast::<Ragdoll>().is_some())
+        {
+            let (ticket, mut node) = graph.take_reserve_internal(handle);
+            if let Some(ragdoll) = node.cast_mut::<Ragdoll>() {
+                ragdoll.update_limbs(handle, graph, dt);
+            }
+            graph.put_back_internal(ticket, node);
+        }
+    }
+    graph.ragdolls = ragdolls;
+}
+
+/// Allows you to create ragdoll node in declarative manner.
+pub struct RagdollBuilder {
+    base_builder: BaseBuilder,
+    is_active: bool,
+    blend_time: f32,
+    limbs: Vec<Limb>,
+    limb_thickness: f32,
+}
+
+impl RagdollBuilder {
+    /// Creates new ragdoll builder.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            is_active: false,
+            blend_time: 0.5,
+            limbs: Default::default(),
+            limb_thickness: 0.25,
+        }
+    }
+
+    /// Sets whether the ragdoll should be driven by physics or not.
+    pub fn with_active(mut self, active: bool) -> Self {
+        self.is_active = active;
+        self
+    }
+
+    /// Sets desired time of blending from physics-driven pose to animation pose.
+    pub fn with_blend_time(mut self, time: f32) -> Self {
+        self.blend_time = time;
+        self
+    }
+
+    /// Sets desired limbs. A limb of a parent bone must precede limbs of its children. Limbs are
+    /// ignored by [`Self::build_from_skeleton`], it generates limbs on its own.
+    pub fn with_limbs(mut self, limbs: Vec<Limb>) -> Self {
+        self.limbs = limbs;
+        self
+    }
+
+    /// Sets ratio between radius of a capsule collider of a limb and length of the limb. It is
+    /// used only by [`Self::build_from_skeleton`].
+    pub fn with_limb_thickness(mut self, thickness: f32) -> Self {
+        self.limb_thickness = thickness;
+        self
+    }
+
+    /// Creates new ragdoll instance.
+    pub fn build_ragdoll(self) -> Ragdoll {
+        Ragdoll {
+            base: self.base_builder.build_base(),
+            is_active: self.is_active.into(),
+            blend_time: self.blend_time.into(),
+            limbs: self.limbs.into(),
+            prev_active: self.is_active,
+            blend_elapsed: 0.0,
+            blend_sources: Default::default(),
+        }
+    }
+
+    /// Creates new ragdoll node.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_ragdoll())
+    }
+
+    /// Creates new ragdoll node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+
+    /// Creates new ragdoll node and generates a limb for every bone of a skeleton starting from
+    /// the given root bone. Every bone that has children gets a rigid body with a capsule collider
+    /// that spans from the bone to its children. Rigid bodies of adjacent bones are connected
+    /// by ball joints, collisions between them are disabled.
+    pub fn build_from_skeleton(self, root_bone: Handle<Node>, graph: &mut Graph) -> Handle<Node> {
+        // Global transforms of the bones must be valid.
+        graph.update_hierarchical_data();
+
+        let body_type = if self.is_active {
+            RigidBodyType::Dynamic
+        } else {
+            RigidBodyType::KinematicPositionBased
+        };
+        let thickness = self.limb_thickness;
+
+        let ragdoll = self.build(graph);
+        let ragdoll_transform = actual_global_transform(graph, ragdoll);
+
+        let mut limbs = Vec::new();
+        make_limbs(
+            graph,
+            &LimbContext {
+                ragdoll,
+                inv_ragdoll_transform: ragdoll_transform
+                    .try_inverse()
+                    .unwrap_or_else(Matrix4::identity),
+                inv_ragdoll_rotation: rotation_of(&ragdoll_transform).inverse(),
+                body_type,
+                thickness,
+            },
+            root_bone,
+            Handle::NONE,
+            &mut limbs,
+        );
+
+        graph[ragdoll]
+            .cast_mut::<Ragdoll>()
+            .unwrap()
+            .set_limbs(limbs);
+
+        ragdoll
+    }
+}
+
+struct LimbContext {
+    ragdoll: Handle<Node>,
+    inv_ragdoll_transform: Matrix4<f32>,
+    inv_ragdoll_rotation: UnitQuaternion<f32>,
+    body_type: RigidBodyType,
+    thickness: f32,
+}
+
+fn make_limbs(
+    graph: &mut Graph,
+    ctx: &LimbContext,
+    bone: Handle<Node>,
+    parent_body: Handle<Node>,
+    limbs: &mut Vec<Limb>,
+) {
+    let bone_ref = &graph[bone];
+
+    // Leaf bones define length of their parents only.
+    if bone_ref.children().is_empty() {
+        return;
+    }
+
+    let name = bone_ref.name_owned();
+    let children = bone_ref.children().to_vec();
+    let position = bone_ref.global_position();
+    let rotation = graph.global_rotation(bone);
+
+    let end = children
+        .iter()
+        .map(|c| graph[*c].global_position())
+        .sum::<Vector3<f32>>()
+        .scale(1.0 / children.len() as f32);
+    let local_end = rotation.inverse_transform_vector(&(end - position));
+    let length = local_end.norm();
+    let radius = (length * ctx.thickness).max(0.01);
+    let (begin, end) = if length > 2.0 * radius {
+        let dir = local_end.scale(1.0 / length);
+        (dir.scale(radius), local_end - dir.scale(radius))
+    } else {
+        (Vector3::default(), Vector3::default())
+    };
+
+    let local_position = ctx
+        .inv_ragdoll_transform
+        .transform_point(&Point3::from(position))
+        .coords;
+    let local_rotation = ctx.inv_ragdoll_rotation * rotation;
+    let local_transform = || {
+        TransformBuilder::new()
+            .with_local_position(local_position)
+            .with_local_rotation(local_rotation)
+            .build()
+    };
+
+    let collider = ColliderBuilder::new(BaseBuilder::new().with_name(format!("{}Collider", name)))
+        .with_shape(ColliderShape::Capsule(CapsuleShape { begin, end, radius }))
+        .build(graph);
+
+    let physical_bone = RigidBodyBuilder::new(
+        BaseBuilder::new()
+            .with_name(format!("{}Body", name))
+            .with_local_transform(local_transform())
+            .with_children(&[collider]),
+    )
+    .with_body_type(ctx.body_type)
+    .build(graph);
+    graph.link_nodes(physical_bone, ctx.ragdoll);
+
+    if parent_body.is_some() {
+        let joint = JointBuilder::new(
+            BaseBuilder::new()
+                .with_name(format!("{}Joint", name))
+                .with_local_transform(local_transform()),
+        )
+        .with_params(JointParams::BallJoint(BallJoint::default()))
+        .with_body1(parent_body)
+        .with_body2(physical_bone)
+        .with_contacts_enabled(false)
+        .build(graph);
+        graph.link_nodes(joint, ctx.ragdoll);
+    }
+
+    limbs.push(Limb {
+        bone,
+        physical_bone,
+    });
+
+    for child in children {
+        make_limbs(graph, ctx, child, physical_bone, limbs);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::core::reflect::Reflect;
+    use crate::core::variable::try_inherit_properties;
+    use crate::{
+        core::{
+            algebra::{Vector2, Vector3},
+            math::Matrix4Ext,
+        },
+        scene::{
+            base::{test::check_inheritable_properties_equality, BaseBuilder},
+            pivot::PivotBuilder,
+            ragdoll::{actual_global_transform, Ragdoll, RagdollBuilder},
+            rigidbody::{RigidBody, RigidBodyType},
+            transform::TransformBuilder,
+            Scene,
+        },
+    };
+
+    #[test]
+    fn test_ragdoll_inheritance() {
+        let parent = RagdollBuilder::new(BaseBuilder::new())
+            .with_active(true)
+            .with_blend_time(1.0)
+            .build_node();
+
+        let mut child = RagdollBuilder::new(BaseBuilder::new()).build_ragdoll();
+
+        try_inherit_properties(child.as_reflect_mut(), parent.as_reflect()).unwrap();
+
+        let parent = parent.cast::<Ragdoll>().unwrap();
+
+        check_inheritable_properties_equality(&child.base, &parent.base);
+        check_inheritable_properties_equality(&child, parent);
+    }
+
+    #[test]
+    fn test_ragdoll_activation() {
+        let mut scene = Scene::new();
+        let graph = &mut scene.graph;
+
+        let bone = |graph: &mut _, name: &str, position: Vector3<f32>, children: &[_]| {
+            PivotBuilder::new(
+                BaseBuilder::new()
+                    .with_name(name)
+                    .with_local_transform(
+                        TransformBuilder::new()
+                            .with_local_position(position)
+                            .build(),
+                    )
+                    .with_children(children),
+            )
+            .build(graph)
+        };
+        let head = bone(graph, "Head", Vector3::new(0.0, 0.5, 0.0), &[]);
+        let spine = bone(graph, "Spine", Vector3::new(0.0, 1.0, 0.0), &[head]);
+        let hips = bone(graph, "Hips", Vector3::new(0.0, 2.0, 0.0), &[spine]);
+
+        let ragdoll = RagdollBuilder::new(BaseBuilder::new())
+            .with_limb_thickness(0.1)
+            .build_from_skeleton(hips, graph);
+
+        // Leaf bones do not have limbs.
+        let limbs = graph[ragdoll].cast::<Ragdoll>().unwrap().limbs().to_vec();
+        assert_eq!(limbs.len(), 2);
+        assert_eq!(limbs[0].bone, hips);
+        assert_eq!(limbs[1].bone, spine);
+
+        let body_type =
+            |scene: &Scene, body| scene.graph[body].cast::<RigidBody>().unwrap().body_type();
+        for limb in limbs.iter() {
+            assert_eq!(
+                body_type(&scene, limb.physical_bone),
+                RigidBodyType::KinematicPositionBased
+            );
+        }
+
+        scene.graph[ragdoll]
+            .cast_mut::<Ragdoll>()
+            .unwrap()
+            .set_active(true);
+        for _ in 0..30 {
+            scene.update(Vector2::new(100.0, 100.0), 1.0 / 60.0);
+        }
+
+        for limb in limbs.iter() {
+            assert_eq!(
+                body_type(&scene, limb.physical_bone),
+                RigidBodyType::Dynamic
+            );
+
+            // Bones follow the bodies.
+            let bone_position = actual_global_transform(&scene.graph, limb.bone).position();
+            let body_position =
+                actual_global_transform(&scene.graph, limb.physical_bone).position();
+            assert!((bone_position - body_position).norm() < 0.001);
+        }
+
+        // The ragdoll falls down due to gravity.
+        assert!(actual_global_transform(&scene.graph, hips).position().y < 1.5);
+    }
+}
@@ -0,0 +1,429 @@
+//! Reflection probe is a local source of environment reflections.
+//!
+//! For more info see [`ReflectionProbe`]
+
+use crate::{
+    asset::ResourceState,
+    core::{
+        inspect::{Inspect, PropertyInfo},
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        reflect::Reflect,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+    },
+    engine::resource_manager::ResourceManager,
+    resource::texture::Texture,
+    scene::{
+        base::{Base, BaseBuilder},
+        graph::Graph,
+        node::{Node, NodeTrait, TypeUuidProvider, UpdateContext},
+        visibility::VisibilityCache,
+    },
+};
+use std::{
+    cell::Cell,
+    ops::{Deref, DerefMut},
+};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Defines when a reflection probe will render its environment cube map.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Visit,
+    Inspect,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum ReflectionProbeUpdateMode {
+    /// Environment will be rendered only once, when the probe is rendered for the first time or
+    /// when an update was requested by [`ReflectionProbe::request_update`]. It is the best option
+    /// for static environments.
+    Once,
+    /// Environment will be rendered every frame. It is very expensive, because the scene is
+    /// rendered six more times for each probe, use it only when you really need real-time
+    /// reflections.
+    EachFrame,
+}
+
+impl Default for ReflectionProbeUpdateMode {
+    fn default() -> Self {
+        Self::Once
+    }
+}
+
+/// Reflection probe captures its surroundings into an environment cube map, which is then used
+/// for reflections of every surface inside of the influence volume of the probe. It allows
+/// to have correct reflections in indoor areas, for example a room will reflect its walls
+/// instead of the sky box of the camera.
+///
+/// # Influence volume
+///
+/// A probe defines a cube that affects every pixel of a scene that got into the cube. Exact cube
+/// size is defined by probe's `local scale` (the same as for decals). Reflections of a probe
+/// smoothly fade out near the faces of the cube, the width of the fade zone is defined by
+/// [`ReflectionProbe::set_blend_distance`]. When a pixel is affected by multiple probes, their
+/// reflections are blended together, pixels that are not affected by any probe will use
+/// environment map of the camera.
+///
+/// # Baked and real-time probes
+///
+/// Environment of a probe can be either rendered by the renderer at run-time (see
+/// [`ReflectionProbeUpdateMode`]) or baked offline using
+/// [`crate::renderer::Renderer::bake_reflection_probe_to_file`] (or
+/// [`crate::renderer::Renderer::bake_reflection_probe`] to store the environment in the scene) and
+/// assigned as a texture resource using [`ReflectionProbe::set_baked_environment`]. Baked
+/// environment is always preferred, if it is set the probe won't render anything at run-time.
+///
+/// # Limitations
+///
+/// Only opaque geometry that is drawn by the deferred renderer is captured, particles, sprites
+/// and forward-rendered geometry will not be visible in reflections. Up to four probes which
+/// influence volumes are in the view of a camera are used per frame, if there are more of them
+/// the ones closer to the camera are preferred.
+///
+/// # Example
+///
+/// ```
+/// use fyrox::{
+///     core::{algebra::Vector3, pool::Handle},
+///     scene::{
+///         base::BaseBuilder, graph::Graph, node::Node,
+///         reflection_probe::{ReflectionProbeBuilder, ReflectionProbeUpdateMode},
+///         transform::TransformBuilder,
+///     },
+/// };
+///
+/// fn create_room_probe(graph: &mut Graph) -> Handle<Node> {
+///     ReflectionProbeBuilder::new(
+///         BaseBuilder::new().with_local_transform(
+///             TransformBuilder::new()
+///                 .with_local_position(Vector3::new(0.0, 1.5, 0.0))
+///                 .with_local_scale(Vector3::new(8.0, 3.0, 6.0))
+///                 .build(),
+///         ),
+///     )
+///     .with_update_mode(ReflectionProbeUpdateMode::Once)
+///     .build(graph)
+/// }
+/// ```
+#[derive(Debug, Visit, Clone, Inspect, Reflect)]
+pub struct ReflectionProbe {
+    base: Base,
+
+    #[inspect(min_value = 0.0)]
+    #[reflect(setter = "set_blend_distance")]
+    blend_distance: InheritableVariable<f32>,
+
+    #[inspect(min_value = 1.0)]
+    #[reflect(setter = "set_resolution")]
+    resolution: InheritableVariable<usize>,
+
+    #[inspect(min_value = 0.0)]
+    #[reflect(setter = "set_z_near")]
+    z_near: InheritableVariable<f32>,
+
+    #[inspect(min_value = 0.0)]
+    #[reflect(setter = "set_z_far")]
+    z_far: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_update_mode")]
+    update_mode: InheritableVariable<ReflectionProbeUpdateMode>,
+
+    #[reflect(setter = "set_baked_environment")]
+    baked_environment: InheritableVariable<Option<Texture>>,
+
+    #[visit(skip)]
+    #[inspect(skip)]
+    #[reflect(hidden)]
+    update_requested: Cell<bool>,
+
+    /// Visibility cache allows you to quickly check if object is visible from the probe or not.
+    #[visit(skip)]
+    #[inspect(skip)]
+    #[reflect(hidden)]
+    pub visibility_cache: VisibilityCache,
+}
+
+impl Default for ReflectionProbe {
+    fn default() -> Self {
+        ReflectionProbeBuilder::new(BaseBuilder::new()).build_reflection_probe()
+    }
+}
+
+impl Deref for ReflectionProbe {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for ReflectionProbe {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl TypeUuidProvider for ReflectionProbe {
+    fn type_uuid() -> Uuid {
+        uuid!("5a7f0c3e-2b9d-4e61-8c4a-91d6f3b2e8a7")
+    }
+}
+
+impl ReflectionProbe {
+    /// Sets the width (in world units) of the zone near the faces of the influence volume, in
+    /// which reflections of the probe smoothly fade out.
+    pub fn set_blend_distance(&mut self, blend_distance: f32) -> f32 {
+        self.blend_distance.set(blend_distance.max(0.0))
+    }
+
+    /// Returns current blend distance.
+    pub fn blend_distance(&self) -> f32 {
+        *self.blend_distance
+    }
+
+    /// Sets the size (in pixels) of each face of environment cube map.
+    pub fn set_resolution(&mut self, resolution: usize) -> usize {
+        self.resolution.set(resolution.max(1))
+    }
+
+    /// Returns current size of each face of environment cube map.
+    pub fn resolution(&self) -> usize {
+        *self.resolution
+    }
+
+    /// Sets the distance to the near clipping plane that will be used to render environment.
+    pub fn set_z_near(&mut self, z_near: f32) -> f32 {
+        self.z_near.set(z_near)
+    }
+
+    /// Returns the distance to the near clipping plane.
+    pub fn z_near(&self) -> f32 {
+        *self.z_near
+    }
+
+    /// Sets the distance to the far clipping plane that will be used to render environment.
+    pub fn set_z_far(&mut self, z_far: f32) -> f32 {
+        self.z_far.set(z_far)
+    }
+
+    /// Returns the distance to the far clipping plane.
+    pub fn z_far(&self) -> f32 {
+        *self.z_far
+    }
+
+    /// Sets new update mode of the probe. See [`ReflectionProbeUpdateMode`] docs for more info.
+    pub fn set_update_mode(
+        &mut self,
+        mode: ReflectionProbeUpdateMode,
+    ) -> ReflectionProbeUpdateMode {
+        self.update_mode.set(mode)
+    }
+
+    /// Returns current update mode.
+    pub fn update_mode(&self) -> ReflectionProbeUpdateMode {
+        *self.update_mode
+    }
+
+    /// Sets pre-rendered environment cube map. If set, the probe won't render its environment
+    /// at run-time.
+    pub fn set_baked_environment(&mut self, environment: Option<Texture>) -> Option<Texture> {
+        std::mem::replace(self.baked_environment.get_mut(), environment)
+    }
+
+    /// Returns current pre-rendered environment cube map.
+    pub fn baked_environment(&self) -> Option<&Texture> {
+        self.baked_environment.as_ref()
+    }
+
+    /// Forces the renderer to re-render environment of the probe on next frame. It is useful
+    /// for probes with [`ReflectionProbeUpdateMode::Once`] update mode, when surroundings of the
+    /// probe has changed.
+    pub fn request_update(&self) {
+        self.update_requested.set(true);
+    }
+
+    /// Returns `true` if an update of the environment was requested and resets the flag.
+    pub(crate) fn take_update_request(&self) -> bool {
+        self.update_requested.replace(false)
+    }
+}
+
+impl NodeTrait for ReflectionProbe {
+    crate::impl_query_component!();
+
+    /// Returns current **local-space** bounding box, which is the influence volume of the probe.
+    #[inline]
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        AxisAlignedBoundingBox::unit()
+    }
+
+    /// Returns current **world-space** bounding box.
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.local_bounding_box()
+            .transform(&self.global_transform())
+    }
+
+    fn restore_resources(&mut self, resource_manager: ResourceManager) {
+        self.base.restore_resources(resource_manager.clone());
+
+        // Environment that was baked without a source file is stored in the scene itself and
+        // must not be resolved.
+        let is_procedural = self.baked_environment.as_ref().map_or(false, |environment| {
+            matches!(&*environment.state(), ResourceState::Ok(data) if data.is_procedural())
+        });
+        if !is_procedural {
+            let mut state = resource_manager.state();
+            let texture_container = &mut state.containers_mut().textures;
+            texture_container.try_restore_inheritable_resource(&mut self.baked_environment);
+        }
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn update(&mut self, context: &mut UpdateContext) -> bool {
+        // Environment is rendered in all directions, so there is no need to use frustum culling.
+        self.visibility_cache.clear();
+        self.visibility_cache.update(
+            context.nodes,
+            self.global_position(),
+            *self.z_near,
+            *self.z_far,
+            None,
+        );
+
+        self.base.update_lifetime(context.dt)
+    }
+}
+
+/// Allows you to create a reflection probe in a declarative manner.
+pub struct ReflectionProbeBuilder {
+    base_builder: BaseBuilder,
+    blend_distance: f32,
+    resolution: usize,
+    z_near: f32,
+    z_far: f32,
+    update_mode: ReflectionProbeUpdateMode,
+    baked_environment: Option<Texture>,
+}
+
+impl ReflectionProbeBuilder {
+    /// Creates a new instance of the builder.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            blend_distance: 1.0,
+            resolution: 128,
+            z_near: 0.025,
+            z_far: 128.0,
+            update_mode: Default::default(),
+            baked_environment: None,
+        }
+    }
+
+    /// Sets desired blend distance.
+    pub fn with_blend_distance(mut self, blend_distance: f32) -> Self {
+        self.blend_distance = blend_distance.max(0.0);
+        self
+    }
+
+    /// Sets desired size of each face of environment cube map.
+    pub fn with_resolution(mut self, resolution: usize) -> Self {
+        self.resolution = resolution.max(1);
+        self
+    }
+
+    /// Sets desired distance to the near clipping plane.
+    pub fn with_z_near(mut self, z_near: f32) -> Self {
+        self.z_near = z_near;
+        self
+    }
+
+    /// Sets desired distance to the far clipping plane.
+    pub fn with_z_far(mut self, z_far: f32) -> Self {
+        self.z_far = z_far;
+        self
+    }
+
+    /// Sets desired update mode.
+    pub fn with_update_mode(mut self, update_mode: ReflectionProbeUpdateMode) -> Self {
+        self.update_mode = update_mode;
+        self
+    }
+
+    /// Sets desired pre-rendered environment cube map.
+    pub fn with_baked_environment(mut self, environment: Texture) -> Self {
+        self.baked_environment = Some(environment);
+        self
+    }
+
+    /// Creates new reflection probe.
+    pub fn build_reflection_probe(self) -> ReflectionProbe {
+        ReflectionProbe {
+            base: self.base_builder.build_base(),
+            blend_distance: self.blend_distance.into(),
+            resolution: self.resolution.into(),
+            z_near: self.z_near.into(),
+            z_far: self.z_far.into(),
+            update_mode: self.update_mode.into(),
+            baked_environment: self.baked_environment.into(),
+            update_requested: Cell::new(false),
+            visibility_cache: Default::default(),
+        }
+    }
+
+    /// Creates new reflection probe node.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_reflection_probe())
+    }
+
+    /// Creates new instance of reflection probe node and puts it in the given graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{reflect::Reflect, variable::try_inherit_properties},
+        resource::texture::test::create_test_texture,
+        scene::{
+            base::{test::check_inheritable_properties_equality, BaseBuilder},
+            reflection_probe::{
+                ReflectionProbe, ReflectionProbeBuilder, ReflectionProbeUpdateMode,
+            },
+        },
+    };
+
+    #[test]
+    fn test_reflection_probe_inheritance() {
+        let parent = ReflectionProbeBuilder::new(BaseBuilder::new())
+            .with_blend_distance(2.0)
+            .with_resolution(64)
+            .with_z_near(0.5)
+            .with_z_far(32.0)
+            .with_update_mode(ReflectionProbeUpdateMode::EachFrame)
+            .with_baked_environment(create_test_texture())
+            .build_node();
+
+        let mut child = ReflectionProbeBuilder::new(BaseBuilder::new()).build_reflection_probe();
+
+        try_inherit_properties(child.as_reflect_mut(), parent.as_reflect()).unwrap();
+
+        let parent = parent.cast::<ReflectionProbe>().unwrap();
+
+        check_inheritable_properties_equality(&child.base, &parent.base);
+        check_inheritable_properties_equality(&child, parent);
+    }
+}
@@ -16,7 +16,10 @@ use crate::{
 };
 use fyrox_sound::{
     context::DistanceModel,
-    effects::{reverb::Reverb, BaseEffect, EffectInput, InputFilter},
+    effects::{
+        chorus::Chorus, compressor::Compressor, delay::Delay, equalizer::Equalizer, reverb::Reverb,
+        BaseEffect, EffectInput, InputFilter,
+    },
     renderer::Renderer,
     source::{SoundSource, SoundSourceBuilder, Status},
 };
@@ -198,41 +201,160 @@ impl SoundContext {
             }
         }
 
+        // Properties of effects could be changed bypassing their setters (by reflection or when
+        // an effect is loaded), so the values are clamped here too. Negative time makes
+        // `Duration::from_secs_f32` panic and feedback above one makes the output diverge.
+        fn effect_time(seconds: f32) -> Duration {
+            Duration::from_secs_f32(seconds.max(0.0).min(u32::MAX as f32))
+        }
+
+        fn sync_base(
+            native_effect: &mut fyrox_sound::effects::BaseEffect,
+            effect: &sound::effect::BaseEffect,
+            nodes: &NodePool,
+        ) {
+            effect.gain.try_sync_model(|v| native_effect.set_gain(v));
+            effect.inputs.try_sync_model(|v| {
+                native_effect.clear_inputs();
+                sync_effect_inputs(native_effect, &v, nodes)
+            });
+        }
+
+        fn init_base(
+            native_effect: &mut fyrox_sound::effects::BaseEffect,
+            effect: &sound::effect::BaseEffect,
+            nodes: &NodePool,
+        ) {
+            native_effect.set_gain(effect.gain());
+            sync_effect_inputs(native_effect, effect.inputs(), nodes);
+        }
+
         for effect in self.effects.iter() {
             if effect.native.get().is_some() {
                 let native_effect = state.effect_mut(effect.native.get());
-                if let (
-                    fyrox_sound::effects::Effect::Reverb(native_reverb),
-                    Effect::Reverb(reverb),
-                ) = (native_effect, effect)
-                {
-                    reverb.decay_time.try_sync_model(|v| {
-                        native_reverb.set_decay_time(Duration::from_secs_f32(v))
-                    });
-                    reverb.gain.try_sync_model(|v| native_reverb.set_gain(v));
-                    reverb.wet.try_sync_model(|v| native_reverb.set_wet(v));
-                    reverb.dry.try_sync_model(|v| native_reverb.set_dry(v));
-                    reverb.fc.try_sync_model(|v| native_reverb.set_fc(v));
-                    reverb.inputs.try_sync_model(|v| {
-                        native_reverb.clear_inputs();
-                        sync_effect_inputs(native_reverb, &v, nodes)
-                    });
+                match (native_effect, effect) {
+                    (
+                        fyrox_sound::effects::Effect::Reverb(native_reverb),
+                        Effect::Reverb(reverb),
+                    ) => {
+                        reverb
+                            .decay_time
+                            .try_sync_model(|v| native_reverb.set_decay_time(effect_time(v)));
+                        reverb.wet.try_sync_model(|v| native_reverb.set_wet(v));
+                        reverb.dry.try_sync_model(|v| native_reverb.set_dry(v));
+                        reverb.fc.try_sync_model(|v| native_reverb.set_fc(v));
+                        sync_base(native_reverb, reverb, nodes);
+                    }
+                    (fyrox_sound::effects::Effect::Delay(native_delay), Effect::Delay(delay)) => {
+                        delay
+                            .delay_time
+                            .try_sync_model(|v| native_delay.set_delay_time(effect_time(v)));
+                        delay
+                            .feedback
+                            .try_sync_model(|v| native_delay.set_feedback(v.clamp(0.0, 0.99)));
+                        delay.wet.try_sync_model(|v| native_delay.set_wet(v));
+                        delay.dry.try_sync_model(|v| native_delay.set_dry(v));
+                        sync_base(native_delay, delay, nodes);
+                    }
+                    (
+                        fyrox_sound::effects::Effect::Chorus(native_chorus),
+                        Effect::Chorus(chorus),
+                    ) => {
+                        chorus
+                            .delay
+                            .try_sync_model(|v| native_chorus.set_delay(effect_time(v)));
+                        chorus
+                            .depth
+                            .try_sync_model(|v| native_chorus.set_depth(effect_time(v)));
+                        chorus.rate.try_sync_model(|v| native_chorus.set_rate(v));
+                        chorus
+                            .feedback
+                            .try_sync_model(|v| native_chorus.set_feedback(v.clamp(-0.99, 0.99)));
+                        chorus.wet.try_sync_model(|v| native_chorus.set_wet(v));
+                        chorus.dry.try_sync_model(|v| native_chorus.set_dry(v));
+                        sync_base(native_chorus, chorus, nodes);
+                    }
+                    (
+                        fyrox_sound::effects::Effect::Compressor(native_compressor),
+                        Effect::Compressor(compressor),
+                    ) => {
+                        compressor
+                            .threshold
+                            .try_sync_model(|v| native_compressor.set_threshold(v));
+                        compressor
+                            .ratio
+                            .try_sync_model(|v| native_compressor.set_ratio(v.max(1.0)));
+                        compressor
+                            .attack
+                            .try_sync_model(|v| native_compressor.set_attack(effect_time(v)));
+                        compressor
+                            .release
+                            .try_sync_model(|v| native_compressor.set_release(effect_time(v)));
+                        compressor
+                            .makeup_gain
+                            .try_sync_model(|v| native_compressor.set_makeup_gain(v));
+                        sync_base(native_compressor, compressor, nodes);
+                    }
+                    (
+                        fyrox_sound::effects::Effect::Equalizer(native_equalizer),
+                        Effect::Equalizer(equalizer),
+                    ) => {
+                        equalizer
+                            .bands
+                            .try_sync_model(|v| native_equalizer.set_bands(v));
+                        sync_base(native_equalizer, equalizer, nodes);
+                    }
+                    _ => (),
                 }
             } else {
-                match effect {
+                let native_effect = match effect {
                     Effect::Reverb(reverb) => {
                         let mut native_reverb = Reverb::new(BaseEffect::default());
-                        native_reverb.set_gain(reverb.gain());
                         native_reverb.set_fc(reverb.fc());
-                        native_reverb.set_decay_time(Duration::from_secs_f32(reverb.decay_time()));
+                        native_reverb.set_decay_time(effect_time(reverb.decay_time()));
                         native_reverb.set_dry(reverb.dry());
                         native_reverb.set_wet(reverb.wet());
-                        sync_effect_inputs(&mut *native_reverb, &*reverb.inputs, nodes);
-                        let native =
-                            state.add_effect(fyrox_sound::effects::Effect::Reverb(native_reverb));
-                        reverb.native.set(native);
+                        init_base(&mut native_reverb, reverb, nodes);
+                        fyrox_sound::effects::Effect::Reverb(native_reverb)
                     }
-                }
+                    Effect::Delay(delay) => {
+                        let mut native_delay = Delay::new(BaseEffect::default());
+                        native_delay.set_delay_time(effect_time(delay.delay_time()));
+                        native_delay.set_feedback(delay.feedback().clamp(0.0, 0.99));
+                        native_delay.set_dry(delay.dry());
+                        native_delay.set_wet(delay.wet());
+                        init_base(&mut native_delay, delay, nodes);
+                        fyrox_sound::effects::Effect::Delay(native_delay)
+                    }
+                    Effect::Chorus(chorus) => {
+                        let mut native_chorus = Chorus::new(BaseEffect::default());
+                        native_chorus.set_delay(effect_time(chorus.delay()));
+                        native_chorus.set_depth(effect_time(chorus.depth()));
+                        native_chorus.set_rate(chorus.rate());
+                        native_chorus.set_feedback(chorus.feedback().clamp(-0.99, 0.99));
+                        native_chorus.set_dry(chorus.dry());
+                        native_chorus.set_wet(chorus.wet());
+                        init_base(&mut native_chorus, chorus, nodes);
+                        fyrox_sound::effects::Effect::Chorus(native_chorus)
+                    }
+                    Effect::Compressor(compressor) => {
+                        let mut native_compressor = Compressor::new(BaseEffect::default());
+                        native_compressor.set_threshold(compressor.threshold());
+                        native_compressor.set_ratio(compressor.ratio().max(1.0));
+                        native_compressor.set_attack(effect_time(compressor.attack()));
+                        native_compressor.set_release(effect_time(compressor.release()));
+                        native_compressor.set_makeup_gain(compressor.makeup_gain());
+                        init_base(&mut native_compressor, compressor, nodes);
+                        fyrox_sound::effects::Effect::Compressor(native_compressor)
+                    }
+                    Effect::Equalizer(equalizer) => {
+                        let mut native_equalizer = Equalizer::new(BaseEffect::default());
+                        native_equalizer.set_bands(equalizer.bands().clone());
+                        init_base(&mut native_equalizer, equalizer, nodes);
+                        fyrox_sound::effects::Effect::Equalizer(native_equalizer)
+                    }
+                };
+                effect.native.set(state.add_effect(native_effect));
             }
         }
     }
@@ -19,6 +19,8 @@ use std::{
 };
 use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 
+pub use fyrox_sound::effects::equalizer::{EqualizerBand, EqualizerBandKind};
+
 const DEFAULT_FC: f32 = 0.25615; // 11296 Hz at 44100 Hz sample rate
 
 /// Effect input allows you to setup a source of samples for an effect with an optional filtering.
@@ -110,6 +112,14 @@ impl Default for BaseEffect {
 pub enum Effect {
     /// See [`ReverbEffect`] docs.
     Reverb(ReverbEffect),
+    /// See [`DelayEffect`] docs.
+    Delay(DelayEffect),
+    /// See [`ChorusEffect`] docs.
+    Chorus(ChorusEffect),
+    /// See [`CompressorEffect`] docs.
+    Compressor(CompressorEffect),
+    /// See [`EqualizerEffect`] docs.
+    Equalizer(EqualizerEffect),
 }
 
 impl Deref for Effect {
@@ -118,6 +128,10 @@ impl Deref for Effect {
     fn deref(&self) -> &Self::Target {
         match self {
             Effect::Reverb(v) => v,
+            Effect::Delay(v) => v,
+            Effect::Chorus(v) => v,
+            Effect::Compressor(v) => v,
+            Effect::Equalizer(v) => v,
         }
     }
 }
@@ -126,6 +140,10 @@ impl DerefMut for Effect {
     fn deref_mut(&mut self) -> &mut Self::Target {
         match self {
             Effect::Reverb(v) => v,
+            Effect::Delay(v) => v,
+            Effect::Chorus(v) => v,
+            Effect::Compressor(v) => v,
+            Effect::Equalizer(v) => v,
         }
     }
 }
@@ -138,6 +156,10 @@ impl Default for Effect {
 
 impl Effect {
     define_is_as!(Effect : Reverb -> ref ReverbEffect => fn is_reverb, fn as_reverb, fn as_reverb_mut);
+    define_is_as!(Effect : Delay -> ref DelayEffect => fn is_delay, fn as_delay, fn as_delay_mut);
+    define_is_as!(Effect : Chorus -> ref ChorusEffect => fn is_chorus, fn as_chorus, fn as_chorus_mut);
+    define_is_as!(Effect : Compressor -> ref CompressorEffect => fn is_compressor, fn as_compressor, fn as_compressor_mut);
+    define_is_as!(Effect : Equalizer -> ref EqualizerEffect => fn is_equalizer, fn as_equalizer, fn as_equalizer_mut);
 }
 
 /// Base effect builder allows you to build an effect.
@@ -262,7 +284,7 @@ impl ReverbEffect {
     /// Sets desired duration of reverberation, the more size your environment has,
     /// the larger duration of reverberation should be.
     pub fn set_decay_time(&mut self, decay_time: f32) -> f32 {
-        self.decay_time.set(decay_time)
+        self.decay_time.set(decay_time.max(0.0))
     }
 
     /// Returns current decay time.
@@ -336,10 +358,10 @@ impl ReverbEffectBuilder {
     pub fn build_effect(self) -> Effect {
         Effect::Reverb(ReverbEffect {
             base: self.base_builder.build(),
-            dry: self.dry.into(),
-            wet: self.wet.into(),
+            dry: self.dry.clamp(0.0, 1.0).into(),
+            wet: self.wet.clamp(0.0, 1.0).into(),
             fc: self.fc.into(),
-            decay_time: self.decay_time.into(),
+            decay_time: self.decay_time.max(0.0).into(),
         })
     }
 
@@ -348,3 +370,584 @@ impl ReverbEffectBuilder {
         context.add_effect(self.build_effect())
     }
 }
+
+macro_rules! impl_effect_deref {
+    ($ty:ty) => {
+        impl Deref for $ty {
+            type Target = BaseEffect;
+
+            fn deref(&self) -> &Self::Target {
+                &self.base
+            }
+        }
+
+        impl DerefMut for $ty {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.base
+            }
+        }
+    };
+}
+
+/// Delay effect repeats input signal after some period of time (echo).
+#[derive(Visit, Inspect, Reflect, Debug, Clone)]
+pub struct DelayEffect {
+    pub(crate) base: BaseEffect,
+
+    #[reflect(setter = "set_delay_time")]
+    pub(crate) delay_time: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_feedback")]
+    pub(crate) feedback: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_dry")]
+    pub(crate) dry: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_wet")]
+    pub(crate) wet: InheritableVariable<f32>,
+}
+
+impl Default for DelayEffect {
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            delay_time: InheritableVariable::new(0.25),
+            feedback: InheritableVariable::new(0.5),
+            dry: InheritableVariable::new(1.0),
+            wet: InheritableVariable::new(0.5),
+        }
+    }
+}
+
+impl_effect_deref!(DelayEffect);
+
+impl DelayEffect {
+    /// Sets time (in seconds) between repetitions of the signal.
+    pub fn set_delay_time(&mut self, delay_time: f32) -> f32 {
+        self.delay_time.set(delay_time.max(0.0))
+    }
+
+    /// Returns time (in seconds) between repetitions of the signal.
+    pub fn delay_time(&self) -> f32 {
+        *self.delay_time
+    }
+
+    /// Sets how much of delayed signal will be fed back to the input, each repetition is
+    /// attenuated by this factor.
+    pub fn set_feedback(&mut self, feedback: f32) -> f32 {
+        self.feedback.set(feedback.clamp(0.0, 0.99))
+    }
+
+    /// Returns feedback factor.
+    pub fn feedback(&self) -> f32 {
+        *self.feedback
+    }
+
+    /// Sets how much of input signal should be passed to output without any processing.
+    pub fn set_dry(&mut self, dry: f32) -> f32 {
+        self.dry.set(dry.clamp(0.0, 1.0))
+    }
+
+    /// Returns dry part.
+    pub fn dry(&self) -> f32 {
+        *self.dry
+    }
+
+    /// Sets how much of delayed signal should be passed to output.
+    pub fn set_wet(&mut self, wet: f32) -> f32 {
+        self.wet.set(wet.clamp(0.0, 1.0))
+    }
+
+    /// Returns wet part.
+    pub fn wet(&self) -> f32 {
+        *self.wet
+    }
+}
+
+/// Allows you to create a new delay effect.
+pub struct DelayEffectBuilder {
+    base_builder: BaseEffectBuilder,
+    delay_time: f32,
+    feedback: f32,
+    dry: f32,
+    wet: f32,
+}
+
+impl DelayEffectBuilder {
+    /// Creates new delay effect builder.
+    pub fn new(base_builder: BaseEffectBuilder) -> Self {
+        Self {
+            base_builder,
+            delay_time: 0.25,
+            feedback: 0.5,
+            dry: 1.0,
+            wet: 0.5,
+        }
+    }
+
+    define_with!(
+        /// Sets desired delay time (in seconds).
+        fn with_delay_time(delay_time: f32)
+    );
+
+    define_with!(
+        /// Sets desired feedback factor.
+        fn with_feedback(feedback: f32)
+    );
+
+    define_with!(
+        /// Sets desired dry coefficient.
+        fn with_dry(dry: f32)
+    );
+
+    define_with!(
+        /// Sets desired wet coefficient.
+        fn with_wet(wet: f32)
+    );
+
+    /// Creates new delay effect.
+    pub fn build_effect(self) -> Effect {
+        Effect::Delay(DelayEffect {
+            base: self.base_builder.build(),
+            delay_time: self.delay_time.max(0.0).into(),
+            feedback: self.feedback.clamp(0.0, 0.99).into(),
+            dry: self.dry.clamp(0.0, 1.0).into(),
+            wet: self.wet.clamp(0.0, 1.0).into(),
+        })
+    }
+
+    /// Creates new delay effect and adds it to the context.
+    pub fn build(self, context: &mut SoundContext) -> Handle<Effect> {
+        context.add_effect(self.build_effect())
+    }
+}
+
+/// Chorus effect mixes input signal with its copies delayed by a slowly changing amount of time.
+/// With short delays and strong feedback it works as flanger.
+#[derive(Visit, Inspect, Reflect, Debug, Clone)]
+pub struct ChorusEffect {
+    pub(crate) base: BaseEffect,
+
+    #[reflect(setter = "set_delay")]
+    pub(crate) delay: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_depth")]
+    pub(crate) depth: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_rate")]
+    pub(crate) rate: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_feedback")]
+    pub(crate) feedback: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_dry")]
+    pub(crate) dry: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_wet")]
+    pub(crate) wet: InheritableVariable<f32>,
+}
+
+impl Default for ChorusEffect {
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            delay: InheritableVariable::new(0.02),
+            depth: InheritableVariable::new(0.005),
+            rate: InheritableVariable::new(0.5),
+            feedback: InheritableVariable::new(0.0),
+            dry: InheritableVariable::new(1.0),
+            wet: InheritableVariable::new(0.5),
+        }
+    }
+}
+
+impl_effect_deref!(ChorusEffect);
+
+impl ChorusEffect {
+    /// Sets base delay (in seconds) of the copy of the signal. Chorus usually uses 0.015-0.03 s,
+    /// flanger 0.001-0.005 s.
+    pub fn set_delay(&mut self, delay: f32) -> f32 {
+        self.delay.set(delay.max(0.0))
+    }
+
+    /// Returns base delay (in seconds) of the copy of the signal.
+    pub fn delay(&self) -> f32 {
+        *self.delay
+    }
+
+    /// Sets max deviation (in seconds) of the delay from the base delay.
+    pub fn set_depth(&mut self, depth: f32) -> f32 {
+        self.depth.set(depth.max(0.0))
+    }
+
+    /// Returns max deviation (in seconds) of the delay from the base delay.
+    pub fn depth(&self) -> f32 {
+        *self.depth
+    }
+
+    /// Sets frequency (in Hz) of delay modulation.
+    pub fn set_rate(&mut self, rate: f32) -> f32 {
+        self.rate.set(rate.max(0.0))
+    }
+
+    /// Returns frequency (in Hz) of delay modulation.
+    pub fn rate(&self) -> f32 {
+        *self.rate
+    }
+
+    /// Sets how much of delayed signal will be fed back to the input.
+    pub fn set_feedback(&mut self, feedback: f32) -> f32 {
+        self.feedback.set(feedback.clamp(-0.99, 0.99))
+    }
+
+    /// Returns feedback factor.
+    pub fn feedback(&self) -> f32 {
+        *self.feedback
+    }
+
+    /// Sets how much of input signal should be passed to output without any processing.
+    pub fn set_dry(&mut self, dry: f32) -> f32 {
+        self.dry.set(dry.clamp(0.0, 1.0))
+    }
+
+    /// Returns dry part.
+    pub fn dry(&self) -> f32 {
+        *self.dry
+    }
+
+    /// Sets how much of modulated signal should be passed to output.
+    pub fn set_wet(&mut self, wet: f32) -> f32 {
+        self.wet.set(wet.clamp(0.0, 1.0))
+    }
+
+    /// Returns wet part.
+    pub fn wet(&self) -> f32 {
+        *self.wet
+    }
+}
+
+/// Allows you to create a new chorus (or flanger) effect.
+pub struct ChorusEffectBuilder {
+    base_builder: BaseEffectBuilder,
+    delay: f32,
+    depth: f32,
+    rate: f32,
+    feedback: f32,
+    dry: f32,
+    wet: f32,
+}
+
+impl ChorusEffectBuilder {
+    /// Creates new chorus effect builder.
+    pub fn new(base_builder: BaseEffectBuilder) -> Self {
+        Self {
+            base_builder,
+            delay: 0.02,
+            depth: 0.005,
+            rate: 0.5,
+            feedback: 0.0,
+            dry: 1.0,
+            wet: 0.5,
+        }
+    }
+
+    /// Creates new chorus effect builder with flanger settings.
+    pub fn flanger(base_builder: BaseEffectBuilder) -> Self {
+        Self {
+            delay: 0.003,
+            depth: 0.002,
+            rate: 0.25,
+            feedback: 0.7,
+            ..Self::new(base_builder)
+        }
+    }
+
+    define_with!(
+        /// Sets desired base delay (in seconds).
+        fn with_delay(delay: f32)
+    );
+
+    define_with!(
+        /// Sets desired modulation depth (in seconds).
+        fn with_depth(depth: f32)
+    );
+
+    define_with!(
+        /// Sets desired modulation frequency (in Hz).
+        fn with_rate(rate: f32)
+    );
+
+    define_with!(
+        /// Sets desired feedback factor.
+        fn with_feedback(feedback: f32)
+    );
+
+    define_with!(
+        /// Sets desired dry coefficient.
+        fn with_dry(dry: f32)
+    );
+
+    define_with!(
+        /// Sets desired wet coefficient.
+        fn with_wet(wet: f32)
+    );
+
+    /// Creates new chorus effect.
+    pub fn build_effect(self) -> Effect {
+        Effect::Chorus(ChorusEffect {
+            base: self.base_builder.build(),
+            delay: self.delay.max(0.0).into(),
+            depth: self.depth.max(0.0).into(),
+            rate: self.rate.max(0.0).into(),
+            feedback: self.feedback.clamp(-0.99, 0.99).into(),
+            dry: self.dry.clamp(0.0, 1.0).into(),
+            wet: self.wet.clamp(0.0, 1.0).into(),
+        })
+    }
+
+    /// Creates new chorus effect and adds it to the context.
+    pub fn build(self, context: &mut SoundContext) -> Handle<Effect> {
+        context.add_effect(self.build_effect())
+    }
+}
+
+/// Compressor reduces volume of loud sounds, narrowing dynamic range of the signal. With very
+/// high ratio and fast attack it works as limiter.
+#[derive(Visit, Inspect, Reflect, Debug, Clone)]
+pub struct CompressorEffect {
+    pub(crate) base: BaseEffect,
+
+    #[reflect(setter = "set_threshold")]
+    pub(crate) threshold: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_ratio")]
+    pub(crate) ratio: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_attack")]
+    pub(crate) attack: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_release")]
+    pub(crate) release: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_makeup_gain")]
+    pub(crate) makeup_gain: InheritableVariable<f32>,
+}
+
+impl Default for CompressorEffect {
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            threshold: InheritableVariable::new(-18.0),
+            ratio: InheritableVariable::new(4.0),
+            attack: InheritableVariable::new(0.01),
+            release: InheritableVariable::new(0.1),
+            makeup_gain: InheritableVariable::new(0.0),
+        }
+    }
+}
+
+impl_effect_deref!(CompressorEffect);
+
+impl CompressorEffect {
+    /// Sets level (in dB) above which the signal will be compressed.
+    pub fn set_threshold(&mut self, threshold: f32) -> f32 {
+        self.threshold.set(threshold.min(0.0))
+    }
+
+    /// Returns level (in dB) above which the signal will be compressed.
+    pub fn threshold(&self) -> f32 {
+        *self.threshold
+    }
+
+    /// Sets compression ratio, for example with ratio 4.0 every 4 dB above threshold become 1 dB.
+    pub fn set_ratio(&mut self, ratio: f32) -> f32 {
+        self.ratio.set(ratio.max(1.0))
+    }
+
+    /// Returns compression ratio.
+    pub fn ratio(&self) -> f32 {
+        *self.ratio
+    }
+
+    /// Sets attack time (in seconds).
+    pub fn set_attack(&mut self, attack: f32) -> f32 {
+        self.attack.set(attack.max(0.0))
+    }
+
+    /// Returns attack time (in seconds).
+    pub fn attack(&self) -> f32 {
+        *self.attack
+    }
+
+    /// Sets release time (in seconds).
+    pub fn set_release(&mut self, release: f32) -> f32 {
+        self.release.set(release.max(0.0))
+    }
+
+    /// Returns release time (in seconds).
+    pub fn release(&self) -> f32 {
+        *self.release
+    }
+
+    /// Sets gain (in dB) that is applied after compression.
+    pub fn set_makeup_gain(&mut self, makeup_gain: f32) -> f32 {
+        self.makeup_gain.set(makeup_gain)
+    }
+
+    /// Returns gain (in dB) that is applied after compression.
+    pub fn makeup_gain(&self) -> f32 {
+        *self.makeup_gain
+    }
+}
+
+/// Allows you to create a new compressor (or limiter) effect.
+pub struct CompressorEffectBuilder {
+    base_builder: BaseEffectBuilder,
+    threshold: f32,
+    ratio: f32,
+    attack: f32,
+    release: f32,
+    makeup_gain: f32,
+}
+
+impl CompressorEffectBuilder {
+    /// Creates new compressor effect builder.
+    pub fn new(base_builder: BaseEffectBuilder) -> Self {
+        Self {
+            base_builder,
+            threshold: -18.0,
+            ratio: 4.0,
+            attack: 0.01,
+            release: 0.1,
+            makeup_gain: 0.0,
+        }
+    }
+
+    /// Creates new compressor effect builder with limiter settings.
+    pub fn limiter(base_builder: BaseEffectBuilder) -> Self {
+        Self {
+            threshold: -1.0,
+            ratio: 100.0,
+            attack: 0.0001,
+            release: 0.05,
+            ..Self::new(base_builder)
+        }
+    }
+
+    define_with!(
+        /// Sets desired threshold (in dB).
+        fn with_threshold(threshold: f32)
+    );
+
+    define_with!(
+        /// Sets desired compression ratio.
+        fn with_ratio(ratio: f32)
+    );
+
+    define_with!(
+        /// Sets desired attack time (in seconds).
+        fn with_attack(attack: f32)
+    );
+
+    define_with!(
+        /// Sets desired release time (in seconds).
+        fn with_release(release: f32)
+    );
+
+    define_with!(
+        /// Sets desired makeup gain (in dB).
+        fn with_makeup_gain(makeup_gain: f32)
+    );
+
+    /// Creates new compressor effect.
+    pub fn build_effect(self) -> Effect {
+        Effect::Compressor(CompressorEffect {
+            base: self.base_builder.build(),
+            threshold: self.threshold.min(0.0).into(),
+            ratio: self.ratio.max(1.0).into(),
+            attack: self.attack.max(0.0).into(),
+            release: self.release.max(0.0).into(),
+            makeup_gain: self.makeup_gain.into(),
+        })
+    }
+
+    /// Creates new compressor effect and adds it to the context.
+    pub fn build(self, context: &mut SoundContext) -> Handle<Effect> {
+        context.add_effect(self.build_effect())
+    }
+}
+
+/// Parametric equalizer changes the balance of frequencies in the signal using a set of bands.
+#[derive(Visit, Inspect, Reflect, Debug, Clone)]
+pub struct EqualizerEffect {
+    pub(crate) base: BaseEffect,
+
+    #[reflect(setter = "set_bands")]
+    pub(crate) bands: InheritableVariable<Vec<EqualizerBand>>,
+}
+
+impl Default for EqualizerEffect {
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            bands: InheritableVariable::new(
+                fyrox_sound::effects::equalizer::Equalizer::default()
+                    .bands()
+                    .to_vec(),
+            ),
+        }
+    }
+}
+
+impl_effect_deref!(EqualizerEffect);
+
+impl EqualizerEffect {
+    /// Sets new set of bands.
+    pub fn set_bands(&mut self, bands: Vec<EqualizerBand>) -> Vec<EqualizerBand> {
+        self.bands.set(bands)
+    }
+
+    /// Returns shared reference to the bands array.
+    pub fn bands(&self) -> &Vec<EqualizerBand> {
+        &self.bands
+    }
+
+    /// Returns mutable reference to the bands array.
+    pub fn bands_mut(&mut self) -> &mut Vec<EqualizerBand> {
+        self.bands.get_mut()
+    }
+}
+
+/// Allows you to create a new equalizer effect.
+pub struct EqualizerEffectBuilder {
+    base_builder: BaseEffectBuilder,
+    bands: Vec<EqualizerBand>,
+}
+
+impl EqualizerEffectBuilder {
+    /// Creates new equalizer effect builder with three neutral bands.
+    pub fn new(base_builder: BaseEffectBuilder) -> Self {
+        Self {
+            base_builder,
+            bands: EqualizerEffect::default().bands.clone_inner(),
+        }
+    }
+
+    define_with!(
+        /// Sets desired bands.
+        fn with_bands(bands: Vec<EqualizerBand>)
+    );
+
+    /// Creates new equalizer effect.
+    pub fn build_effect(self) -> Effect {
+        Effect::Equalizer(EqualizerEffect {
+            base: self.base_builder.build(),
+            bands: self.bands.into(),
+        })
+    }
+
+    /// Creates new equalizer effect and adds it to the context.
+    pub fn build(self, context: &mut SoundContext) -> Handle<Effect> {
+        context.add_effect(self.build_effect())
+    }
+}
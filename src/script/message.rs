@@ -0,0 +1,123 @@
+//! Message passing between scripts. See [`ScriptMessageSender`] docs for more info.
+
+use crate::{core::pool::Handle, scene::node::Node, utils::log::Log};
+use std::{
+    any::Any,
+    fmt::{Debug, Formatter},
+    sync::mpsc::{channel, Receiver, Sender},
+};
+
+/// Payload of a script message, it could be any type that implements `Any + Send`.
+pub type ScriptMessagePayload = dyn Any + Send;
+
+/// Defines which scripts will receive a message.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RoutingStrategy {
+    /// A message will be delivered only to a script of the given node.
+    Node(Handle<Node>),
+    /// A message will be delivered to scripts of the given node and all its descendants.
+    Hierarchy(Handle<Node>),
+    /// A message will be delivered to every script of the scene.
+    Global,
+}
+
+/// A message that is sent between scripts: typed payload along with its routing strategy.
+pub struct ScriptMessageEnvelope {
+    /// Actual message data, use [`ScriptMessageEnvelope::data`] to get typed data.
+    pub payload: Box<ScriptMessagePayload>,
+    /// Defines which scripts will receive the message.
+    pub routing: RoutingStrategy,
+}
+
+impl Debug for ScriptMessageEnvelope {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ScriptMessageEnvelope {{ routing: {:?} }}", self.routing)
+    }
+}
+
+impl ScriptMessageEnvelope {
+    /// Tries to get a reference to the message data of the given type. Returns `None` if the
+    /// data has some other type.
+    pub fn data<T: Any>(&self) -> Option<&T> {
+        self.payload.downcast_ref::<T>()
+    }
+}
+
+/// Script message sender allows you to send typed messages to scripts of the same scene. Messages
+/// are delivered in [`crate::script::ScriptTrait::on_message`] at the end of the frame, after all
+/// scripts were updated, so there are never two scripts borrowed at once. Messages sent from
+/// `on_message` will be delivered on next frame.
+///
+/// # Example
+///
+/// ```rust
+/// # use fyrox::{
+/// #     core::pool::Handle,
+/// #     scene::node::Node,
+/// #     script::{message::ScriptMessageEnvelope, ScriptContext},
+/// # };
+/// struct DamageMessage {
+///     amount: f32,
+/// }
+///
+/// fn hit(target: Handle<Node>, ctx: &mut ScriptContext) {
+///     ctx.message_sender
+///         .send_to_target(target, DamageMessage { amount: 10.0 });
+/// }
+///
+/// fn on_message(message: &ScriptMessageEnvelope) {
+///     if let Some(damage) = message.data::<DamageMessage>() {
+///         println!("Received {} damage", damage.amount);
+///     }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct ScriptMessageSender {
+    sender: Sender<ScriptMessageEnvelope>,
+}
+
+impl Debug for ScriptMessageSender {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ScriptMessageSender")
+    }
+}
+
+impl ScriptMessageSender {
+    /// Sends a message using the given routing strategy.
+    pub fn send<T: Any + Send>(&self, routing: RoutingStrategy, payload: T) {
+        Log::verify(self.sender.send(ScriptMessageEnvelope {
+            payload: Box::new(payload),
+            routing,
+        }));
+    }
+
+    /// Sends a message to a script of the given node.
+    pub fn send_to_target<T: Any + Send>(&self, target: Handle<Node>, payload: T) {
+        self.send(RoutingStrategy::Node(target), payload)
+    }
+
+    /// Sends a message to scripts of the given node and all its descendants.
+    pub fn send_hierarchical<T: Any + Send>(&self, root: Handle<Node>, payload: T) {
+        self.send(RoutingStrategy::Hierarchy(root), payload)
+    }
+
+    /// Sends a message to every script of the scene.
+    pub fn send_global<T: Any + Send>(&self, payload: T) {
+        self.send(RoutingStrategy::Global, payload)
+    }
+}
+
+pub(crate) struct ScriptMessageQueue {
+    pub sender: ScriptMessageSender,
+    pub receiver: Receiver<ScriptMessageEnvelope>,
+}
+
+impl Default for ScriptMessageQueue {
+    fn default() -> Self {
+        let (sender, receiver) = channel();
+        Self {
+            sender: ScriptMessageSender { sender },
+            receiver,
+        }
+    }
+}
@@ -13,7 +13,8 @@ use crate::{
     engine::resource_manager::ResourceManager,
     event::Event,
     plugin::Plugin,
-    scene::{node::Node, Scene},
+    scene::{dim2, graph::physics::CollisionEvent, node::Node, Scene},
+    script::message::{ScriptMessageEnvelope, ScriptMessageSender},
     utils::{component::ComponentProvider, log::Log},
 };
 use std::{
@@ -23,6 +24,7 @@ use std::{
 };
 
 pub mod constructor;
+pub mod message;
 
 /// Base script trait is used to automatically implement some trait to reduce amount of boilerplate code.
 pub trait BaseScript: Visit + Inspect + Reflect + Send + Debug + 'static {
@@ -41,7 +43,8 @@ where
 
 /// A set of data, that provides contextual information for script methods.
 pub struct ScriptContext<'a, 'b> {
-    /// Amount of time that passed from last call. It has valid values only when called from `on_update`.
+    /// Amount of time that passed from last call. It has valid values only when called from
+    /// `on_update` or `on_fixed_update`.
     pub dt: f32,
 
     /// Amount of time (in seconds) that passed from creation of the engine. Keep in mind, that
@@ -70,6 +73,10 @@ pub struct ScriptContext<'a, 'b> {
 
     /// A reference to resource manager, use it to load resources.
     pub resource_manager: &'a ResourceManager,
+
+    /// A message sender of the scene, use it to send messages to other scripts of the scene. See
+    /// [`ScriptMessageSender`] docs for more info.
+    pub message_sender: &'a ScriptMessageSender,
 }
 
 /// A set of data that will be passed to a script instance just before its destruction.
@@ -129,11 +136,45 @@ pub trait ScriptTrait: BaseScript + ComponentProvider {
     ) {
     }
 
-    /// Performs a single update tick of the script. The method may be called multiple times per
-    /// frame, but it is guaranteed that the rate of call is stable and usually it will be called
-    /// 60 times per second (this may change in future releases).
+    /// Performs a single update tick of the script. The method is called once per frame,
+    /// [`ScriptContext::dt`] contains the time that passed since the last frame. Use
+    /// [`ScriptTrait::on_fixed_update`] for logic that must be deterministic.
     fn on_update(&mut self, #[allow(unused_variables)] ctx: &mut ScriptContext) {}
 
+    /// Performs a single fixed update tick of the script. The method is called zero or more times
+    /// per frame, right after a physics step, [`ScriptContext::dt`] is always equal to
+    /// [`crate::engine::Engine::fixed_timestep`].
+    fn on_fixed_update(&mut self, #[allow(unused_variables)] ctx: &mut ScriptContext) {}
+
+    /// Called when a collider starts or stops touching (or intersecting) some other collider. The
+    /// method is called for a script of the collider node itself and for a script of its parent
+    /// node (usually a rigid body). Events are delivered right after a physics step, before
+    /// [`ScriptTrait::on_fixed_update`].
+    fn on_collision(
+        &mut self,
+        #[allow(unused_variables)] event: &CollisionEvent,
+        #[allow(unused_variables)] ctx: &mut ScriptContext,
+    ) {
+    }
+
+    /// The same as [`ScriptTrait::on_collision`], but for 2D colliders.
+    fn on_collision_2d(
+        &mut self,
+        #[allow(unused_variables)] event: &dim2::physics::CollisionEvent,
+        #[allow(unused_variables)] ctx: &mut ScriptContext,
+    ) {
+    }
+
+    /// Called when there is a message for the script from some other script (or the script
+    /// itself). Messages are delivered once per frame, after [`ScriptTrait::on_update`] of every
+    /// script. See [`ScriptMessageSender`] docs for more info.
+    fn on_message(
+        &mut self,
+        #[allow(unused_variables)] message: &ScriptMessageEnvelope,
+        #[allow(unused_variables)] ctx: &mut ScriptContext,
+    ) {
+    }
+
     /// Allows you to restore resources after deserialization.
     ///
     /// # Motivation
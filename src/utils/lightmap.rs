@@ -9,6 +9,7 @@
 
 #![forbid(unsafe_code)]
 
+use crate::scene::light::area::{AreaLight, AreaLightShape};
 use crate::scene::light::directional::DirectionalLight;
 use crate::scene::light::point::PointLight;
 use crate::scene::light::spot::SpotLight;
@@ -241,6 +242,7 @@ impl Lightmap {
             if node.cast::<PointLight>().is_some()
                 || node.cast::<SpotLight>().is_some()
                 || node.cast::<DirectionalLight>().is_some()
+                || node.cast::<AreaLight>().is_some()
             {
                 light_count += 1;
             }
@@ -279,6 +281,25 @@ impl Lightmap {
                     distance: spot.distance(),
                     sqr_distance: spot.distance() * spot.distance(),
                 }))
+            } else if let Some(area) = light.cast::<AreaLight>() {
+                let basis = light.global_transform().basis();
+                let size = area.size();
+                lights.push(LightDefinition::Area(AreaLightDefinition {
+                    handle,
+                    intensity: area.base_light_ref().intensity(),
+                    color: area.base_light_ref().color().srgb_to_linear().as_frgb(),
+                    position: light.global_position(),
+                    axis_x: basis.column(0) * (size.x * 0.5),
+                    axis_z: basis.column(2) * (size.y * 0.5),
+                    direction: -light
+                        .up_vector()
+                        .try_normalize(f32::EPSILON)
+                        .unwrap_or_else(Vector3::y),
+                    shape: area.shape(),
+                    two_sided: area.is_two_sided(),
+                    radius: area.radius(),
+                    sqr_radius: area.radius() * area.radius(),
+                }))
             } else if let Some(directional) = light.cast::<DirectionalLight>() {
                 lights.push(LightDefinition::Directional(DirectionalLightDefinition {
                     handle,
@@ -500,6 +521,32 @@ pub struct PointLightDefinition {
     pub sqr_radius: f32,
 }
 
+/// Area light is a light source with some shape. Example: window.
+pub struct AreaLightDefinition {
+    /// A handle of light in the scene.
+    pub handle: Handle<Node>,
+    /// Intensity is how bright light is.
+    pub intensity: f32,
+    /// Color of light.
+    pub color: Vector3<f32>,
+    /// Position of the center of the shape in world coordinates.
+    pub position: Vector3<f32>,
+    /// Half-extent of the shape along its local X axis in world coordinates.
+    pub axis_x: Vector3<f32>,
+    /// Half-extent of the shape along its local Z axis in world coordinates.
+    pub axis_z: Vector3<f32>,
+    /// Direction in which light is emitted.
+    pub direction: Vector3<f32>,
+    /// Shape of the light.
+    pub shape: AreaLightShape,
+    /// Whether the light is emitted from both sides of the shape or not.
+    pub two_sided: bool,
+    /// Radius of sphere at which light intensity decays to zero.
+    pub radius: f32,
+    /// Square of radius.
+    pub sqr_radius: f32,
+}
+
 /// Light definition for lightmap rendering.
 pub enum LightDefinition {
     /// See docs of [DirectionalLightDefinition](struct.PointLightDefinition.html)
@@ -508,6 +555,8 @@ pub enum LightDefinition {
     Spot(SpotLightDefinition),
     /// See docs of [PointLightDefinition](struct.PointLightDefinition.html)
     Point(PointLightDefinition),
+    /// See docs of [AreaLightDefinition](struct.AreaLightDefinition.html)
+    Area(AreaLightDefinition),
 }
 
 impl LightDefinition {
@@ -516,6 +565,7 @@ impl LightDefinition {
             LightDefinition::Directional(v) => v.handle,
             LightDefinition::Spot(v) => v.handle,
             LightDefinition::Point(v) => v.handle,
+            LightDefinition::Area(v) => v.handle,
         }
     }
 }
@@ -656,6 +706,220 @@ fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
     k * k * (3.0 - 2.0 * k)
 }
 
+/// Integral of a polygon edge over a hemisphere, divided by 2 * PI. Same approximation is used
+/// by area lights shader.
+fn integrate_edge(v1: Vector3<f32>, v2: Vector3<f32>) -> Vector3<f32> {
+    let x = v1.dot(&v2);
+    let y = x.abs();
+
+    let a = 0.8543985 + (0.4965155 + 0.0145206 * y) * y;
+    let b = 3.417594 + (4.1616724 + y) * y;
+    let v = a / b;
+
+    let theta_sin_theta = if x > 0.0 {
+        v
+    } else {
+        0.5 / (1.0 - x * x).max(1e-7).sqrt() - v
+    };
+
+    v1.cross(&v2).scale(theta_sin_theta)
+}
+
+/// Form factor of a sphere clipped by horizon, see "Moving Frostbite to PBR".
+fn clipped_sphere_form_factor(cos_theta: f32, form_factor: f32) -> f32 {
+    let form_factor = form_factor.min(0.9999);
+
+    if cos_theta * cos_theta > form_factor {
+        return form_factor * cos_theta.max(0.0);
+    }
+
+    let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+    let x = (1.0 / form_factor - 1.0).sqrt();
+    let y = -x * (cos_theta / sin_theta);
+    let sin_theta_sqrt_y = sin_theta * (1.0 - y * y).max(0.0).sqrt();
+
+    (((cos_theta * y.acos() - x * sin_theta_sqrt_y) * form_factor + (sin_theta_sqrt_y / x).atan())
+        / std::f32::consts::PI)
+        .max(0.0)
+}
+
+/// Calculates form factor of a rectangle with given corners (relative to receiver) for a receiver
+/// with given normal.
+fn rectangle_form_factor(points: [Vector3<f32>; 4], normal: Vector3<f32>) -> f32 {
+    let mut polygon_normal = (points[1] - points[0]).cross(&(points[3] - points[0]));
+    if polygon_normal.dot(&points[0]) < 0.0 {
+        polygon_normal = -polygon_normal;
+    }
+
+    let mut directions = [Vector3::default(); 4];
+    for (direction, point) in directions.iter_mut().zip(points.iter()) {
+        *direction = match point.try_normalize(f32::EPSILON) {
+            Some(direction) => direction,
+            None => return 0.0,
+        };
+    }
+
+    let mut f = Vector3::default();
+    for i in 0..4 {
+        f += integrate_edge(directions[i], directions[(i + 1) % 4]);
+    }
+    if f.dot(&polygon_normal) < 0.0 {
+        f = -f;
+    }
+
+    let form_factor = f.norm();
+    if form_factor <= 0.0 {
+        return 0.0;
+    }
+
+    clipped_sphere_form_factor(f.dot(&normal) / form_factor, form_factor)
+}
+
+/// Blinn's method of solving of cubic equations, returns roots sorted so that the middle root is
+/// the second one.
+fn solve_cubic(c: Vector4<f32>) -> Vector3<f32> {
+    let mut c = c;
+    c.x /= c.w;
+    c.y /= c.w;
+    c.z /= c.w;
+    c.y /= 3.0;
+    c.z /= 3.0;
+
+    let a = c.w;
+    let b = c.z;
+    let cc = c.y;
+    let d = c.x;
+
+    let delta = Vector3::new(-c.z * c.z + c.y, -c.y * c.z + c.x, c.z * c.x - c.y * c.y);
+    let discriminant = (4.0 * delta.x * delta.z - delta.y * delta.y).max(0.0);
+
+    let two_thirds_pi = 2.0 / 3.0 * std::f32::consts::PI;
+
+    let (xl, xl_w) = {
+        let c_a = delta.x;
+        let d_a = -2.0 * b * delta.x + delta.y;
+        let theta = discriminant.sqrt().atan2(-d_a) / 3.0;
+        let x_1a = 2.0 * (-c_a).max(0.0).sqrt() * theta.cos();
+        let x_3a = 2.0 * (-c_a).max(0.0).sqrt() * (theta + two_thirds_pi).cos();
+        let xl = if x_1a + x_3a > 2.0 * b { x_1a } else { x_3a };
+        (xl - b, a)
+    };
+
+    let (xs, xs_w) = {
+        let c_d = delta.z;
+        let d_d = -d * delta.y + 2.0 * cc * delta.z;
+        let theta = (d * discriminant.sqrt()).atan2(-d_d) / 3.0;
+        let x_1d = 2.0 * (-c_d).max(0.0).sqrt() * theta.cos();
+        let x_3d = 2.0 * (-c_d).max(0.0).sqrt() * (theta + two_thirds_pi).cos();
+        let xs = if x_1d + x_3d < 2.0 * cc { x_1d } else { x_3d };
+        (-d, xs + cc)
+    };
+
+    let e = xl_w * xs_w;
+    let f = -xl * xs_w - xl_w * xs;
+    let g = xl * xs;
+
+    let xm = cc * f - b * g;
+    let xm_w = -b * f + cc * e;
+
+    let mut root = Vector3::new(xs / xs_w, xm / xm_w, xl / xl_w);
+    if root.x < root.y && root.x < root.z {
+        root = Vector3::new(root.y, root.x, root.z);
+    } else if root.z < root.x && root.z < root.y {
+        root = Vector3::new(root.x, root.z, root.y);
+    }
+    root
+}
+
+/// Calculates form factor of an ellipse with given center (relative to receiver) and half-axes
+/// for a receiver with given normal.
+fn disc_form_factor(
+    center: Vector3<f32>,
+    axis_x: Vector3<f32>,
+    axis_z: Vector3<f32>,
+    normal: Vector3<f32>,
+) -> f32 {
+    let mut v1 = axis_x;
+    let mut v2 = axis_z;
+
+    let d11 = v1.dot(&v1);
+    let d22 = v2.dot(&v2);
+    let d12 = v1.dot(&v2);
+    if d11 <= 0.0 || d22 <= 0.0 {
+        return 0.0;
+    }
+
+    let (mut a, mut b);
+    if d12.abs() / (d11 * d22).sqrt() > 0.0001 {
+        let tr = d11 + d22;
+        let det = (-d12 * d12 + d11 * d22).max(0.0).sqrt();
+
+        let u = 0.5 * (tr - 2.0 * det).max(0.0).sqrt();
+        let v = 0.5 * (tr + 2.0 * det).sqrt();
+        let e_max = (u + v) * (u + v);
+        let e_min = (u - v) * (u - v);
+
+        let (v1_, v2_) = if d11 > d22 {
+            (
+                v1.scale(d12) + v2.scale(e_max - d11),
+                v1.scale(d12) + v2.scale(e_min - d11),
+            )
+        } else {
+            (
+                v2.scale(d12) + v1.scale(e_max - d22),
+                v2.scale(d12) + v1.scale(e_min - d22),
+            )
+        };
+
+        a = 1.0 / e_max;
+        b = 1.0 / e_min;
+        v1 = v1_.normalize();
+        v2 = v2_.normalize();
+    } else {
+        a = 1.0 / d11;
+        b = 1.0 / d22;
+        v1 = v1.scale(a.sqrt());
+        v2 = v2.scale(b.sqrt());
+    }
+
+    let mut v3 = v1.cross(&v2);
+    if center.dot(&v3) < 0.0 {
+        v3 = -v3;
+    }
+
+    let l = v3.dot(&center);
+    if l <= 0.0 {
+        return 0.0;
+    }
+    let x0 = v1.dot(&center) / l;
+    let y0 = v2.dot(&center) / l;
+
+    a *= l * l;
+    b *= l * l;
+
+    let c0 = a * b;
+    let c1 = a * b * (1.0 + x0 * x0 + y0 * y0) - a - b;
+    let c2 = 1.0 - a * (1.0 + x0 * x0) - b * (1.0 + y0 * y0);
+    let c3 = 1.0;
+
+    let roots = solve_cubic(Vector4::new(c0, c1, c2, c3));
+    let (e1, e2, e3) = (roots.x, roots.y, roots.z);
+
+    let average_direction = (v1.scale(a * x0 / (a - e2)) + v2.scale(b * y0 / (b - e2)) + v3)
+        .try_normalize(f32::EPSILON)
+        .unwrap_or(v3);
+
+    let l1 = (-e2 / e3).sqrt();
+    let l2 = (-e2 / e1).sqrt();
+
+    let form_factor = l1 * l2 / ((1.0 + l1 * l1) * (1.0 + l2 * l2)).sqrt();
+    if !form_factor.is_finite() {
+        return 0.0;
+    }
+
+    clipped_sphere_form_factor(average_direction.dot(&normal), form_factor)
+}
+
 /// Generates lightmap for given surface data with specified transform.
 ///
 /// # Performance
@@ -718,6 +982,35 @@ fn generate_lightmap(
                                 * distance_attenuation(distance, point.sqr_radius);
                             (point.color, attenuation, point.position)
                         }
+                        LightDefinition::Area(area) => {
+                            let d = area.position - world_position;
+                            let distance = d.norm();
+                            // Exactly the same calculations as in area light shader, but only
+                            // for diffuse part.
+                            let side_factor = if area.two_sided || d.dot(&area.direction) <= 0.0 {
+                                1.0
+                            } else {
+                                0.0
+                            };
+                            let form_factor = match area.shape {
+                                AreaLightShape::Rectangle => rectangle_form_factor(
+                                    [
+                                        d - area.axis_x - area.axis_z,
+                                        d + area.axis_x - area.axis_z,
+                                        d + area.axis_x + area.axis_z,
+                                        d - area.axis_x + area.axis_z,
+                                    ],
+                                    world_normal,
+                                ),
+                                AreaLightShape::Disc => {
+                                    disc_form_factor(d, area.axis_x, area.axis_z, world_normal)
+                                }
+                            };
+                            let window =
+                                (1.0 - distance * distance / area.sqr_radius).clamp(0.0, 1.0);
+                            let attenuation = side_factor * area.intensity * form_factor * window;
+                            (area.color, attenuation, area.position)
+                        }
                     };
                     // Shadows
                     if attenuation >= 0.01 {
@@ -887,10 +1180,37 @@ mod test {
             transform::TransformBuilder,
             Scene,
         },
-        utils::lightmap::Lightmap,
+        utils::lightmap::{disc_form_factor, rectangle_form_factor, Lightmap},
     };
     use std::sync::Arc;
 
+    #[test]
+    fn test_area_light_form_factors() {
+        let normal = Vector3::new(0.0, 0.0, 1.0);
+        let center = Vector3::new(0.0, 0.0, 1.0);
+        let axis_x = Vector3::new(1.0, 0.0, 0.0);
+        let axis_z = Vector3::new(0.0, 1.0, 0.0);
+
+        // Disc of radius r at height h right above the receiver: r^2 / (r^2 + h^2).
+        let disc = disc_form_factor(center, axis_x, axis_z, normal);
+        assert!((disc - 0.5).abs() < 1.0e-4);
+
+        // Analytic form factor of 2x2 square at height 1 right above the receiver.
+        let rectangle = rectangle_form_factor(
+            [
+                center - axis_x - axis_z,
+                center + axis_x - axis_z,
+                center + axis_x + axis_z,
+                center - axis_x + axis_z,
+            ],
+            normal,
+        );
+        assert!((rectangle - 0.554_126).abs() < 1.0e-4);
+
+        // Light below the horizon.
+        assert_eq!(disc_form_factor(-center, axis_x, axis_z, normal), 0.0);
+    }
+
     #[test]
     fn test_generate_lightmap() {
         let mut scene = Scene::new();
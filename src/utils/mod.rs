@@ -14,15 +14,23 @@ pub mod watcher;
 
 use crate::core::algebra::Vector2;
 use crate::{
+    asset::Resource,
+    core::parking_lot::Mutex,
+    engine::resource_manager::ResourceManager,
     event::{ElementState, ModifiersState, MouseScrollDelta, VirtualKeyCode, WindowEvent},
     gui::{
         draw,
         message::{ButtonState, KeyCode, KeyboardModifiers, OsEvent},
+        scene::TextureResolver,
     },
-    resource::texture::Texture,
+    resource::texture::{Texture, TextureState},
 };
 use std::hash::Hasher;
-use std::{any::Any, sync::Arc};
+use std::{
+    any::Any,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 /// Translated key code to fyrox-ui key code.
 pub fn translate_key(key: VirtualKeyCode) -> KeyCode {
@@ -478,6 +486,38 @@ pub fn into_gui_texture(this: Texture) -> draw::SharedTexture {
     draw::SharedTexture(this.0.into_inner())
 }
 
+/// Texture resolver for UI scenes (see [`crate::gui::scene`]), it saves textures as paths of
+/// their resources and loads them back using resource manager.
+pub struct UiTextureResolver {
+    resource_manager: ResourceManager,
+}
+
+impl UiTextureResolver {
+    /// Creates new texture resolver.
+    pub fn new(resource_manager: ResourceManager) -> Self {
+        Self { resource_manager }
+    }
+}
+
+impl TextureResolver for UiTextureResolver {
+    fn texture_path(&self, texture: &draw::SharedTexture) -> Option<PathBuf> {
+        let state = texture.0.clone().downcast::<Mutex<TextureState>>().ok()?;
+        let path = Texture(Resource::from(state)).state().path().to_path_buf();
+        // Procedural textures have no path and cannot be saved as a reference.
+        if path.as_os_str().is_empty() {
+            None
+        } else {
+            Some(path)
+        }
+    }
+
+    fn load_texture(&self, path: &Path) -> Option<draw::SharedTexture> {
+        Some(into_gui_texture(
+            self.resource_manager.request_texture(path),
+        ))
+    }
+}
+
 /// "Transmutes" array of any sized type to a slice of bytes.
 pub fn array_as_u8_slice<T: Sized>(v: &[T]) -> &'_ [u8] {
     // SAFETY: It is safe to reinterpret data to read it.